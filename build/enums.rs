@@ -131,6 +131,21 @@ pub fn main() -> anyhow::Result<()> {
         libinput::LIBINPUT_CONFIG_DRAG_LOCK_STATE,
         "libinput_config_drag_lock_state",
     )?;
+    write_ty(
+        &mut f,
+        libinput::LIBINPUT_CONFIG_DWT_STATE,
+        "libinput_config_dwt_state",
+    )?;
+    write_ty(
+        &mut f,
+        libinput::LIBINPUT_CONFIG_DWTP_STATE,
+        "libinput_config_dwtp_state",
+    )?;
+    write_ty(
+        &mut f,
+        libinput::LIBINPUT_CONFIG_MIDDLE_EMULATION_STATE,
+        "libinput_config_middle_emulation_state",
+    )?;
 
     let mut f = open("pango_tys.rs")?;
     write_ty(&mut f, pango::CAIRO_FORMATS, "cairo_format_t")?;
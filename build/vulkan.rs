@@ -12,13 +12,20 @@ pub fn main() -> anyhow::Result<()> {
     compile_simple("fill.frag")?;
     compile_simple("fill.vert")?;
     compile_simple("tex.vert")?;
-    compile_tex_frag("tex.frag.spv", false, false)?;
-    compile_tex_frag("tex.frag.mult+opaque.spv", false, true)?;
-    compile_tex_frag("tex.frag.mult+alpha.spv", true, true)?;
+    compile_tex_frag("tex.frag.spv", false, false, false)?;
+    compile_tex_frag("tex.frag.mult+opaque.spv", false, true, false)?;
+    compile_tex_frag("tex.frag.mult+alpha.spv", true, true, false)?;
+    compile_tex_frag("tex.frag.cm+opaque.spv", false, false, true)?;
+    compile_tex_frag("tex.frag.cm+alpha.spv", true, false, true)?;
     Ok(())
 }
 
-fn compile_tex_frag(out: &str, alpha: bool, alpha_multiplier: bool) -> anyhow::Result<()> {
+fn compile_tex_frag(
+    out: &str,
+    alpha: bool,
+    alpha_multiplier: bool,
+    color_management: bool,
+) -> anyhow::Result<()> {
     let mut opts = CompileOptions::new().unwrap();
     if alpha {
         opts.add_macro_definition("ALPHA", None);
@@ -26,6 +33,9 @@ fn compile_tex_frag(out: &str, alpha: bool, alpha_multiplier: bool) -> anyhow::R
     if alpha_multiplier {
         opts.add_macro_definition("ALPHA_MULTIPLIER", None);
     }
+    if color_management {
+        opts.add_macro_definition("COLOR_MANAGEMENT", None);
+    }
     compile_shader("tex.frag", out, Some(&opts)).with_context(|| out.to_string())?;
     Ok(())
 }
@@ -11,24 +11,28 @@ use {
         },
         exec::Command,
         input::{
-            acceleration::AccelProfile, capability::Capability, FocusFollowsMouseMode, InputDevice,
-            Seat, SwitchEvent,
+            acceleration::AccelProfile, capability::Capability, led::Leds, FocusFollowsMouseMode,
+            InputDevice, PointerGesture, PointerInput, PointerTarget, Seat, SwitchEvent,
+            TouchGesture, DEFAULT_BINDING_MODE,
         },
         keyboard::{
             mods::{Modifiers, RELEASE},
             syms::KeySym,
-            Keymap,
+            Keymap, KeymapNames,
         },
+        layout::{Layout, LayoutGeometry, LayoutRequest},
         logging::LogLevel,
+        notifications::NotificationPosition,
         tasks::{JoinHandle, JoinSlot},
         theme::{colors::Colorable, sized::Resizable, Color},
         timer::Timer,
         video::{
             connector_type::{ConnectorType, CON_UNKNOWN},
-            Connector, DrmDevice, Format, GfxApi, Mode, TearingMode, Transform, VrrMode,
+            Connector, CustomMode, DrmDevice, Format, GfxApi, Mode, NightLightSchedule,
+            TearingMode, Transform, VrrMode,
         },
         xwayland::XScalingMode,
-        Axis, Direction, ModifiedKeySym, PciId, Workspace,
+        Axis, Direction, EmptyWorkspaceBehavior, ModifiedKeySym, PciId, Workspace,
     },
     bincode::Options,
     futures_util::task::ArcWake,
@@ -54,6 +58,7 @@ use {
 };
 
 type Callback<T = ()> = Rc<RefCell<dyn FnMut(T)>>;
+type LayoutCallback = Rc<RefCell<dyn FnMut(&LayoutRequest) -> Vec<LayoutGeometry>>>;
 
 fn cb<T, F: FnMut(T) + 'static>(f: F) -> Callback<T> {
     Rc::new(RefCell::new(f))
@@ -79,12 +84,25 @@ struct KeyHandler {
     latched: Vec<Box<dyn FnOnce()>>,
 }
 
+struct ModeBinding {
+    mod_mask: Modifiers,
+    cb: Callback,
+}
+
+#[derive(Default)]
+struct BindingModes {
+    /// The active mode of each seat that is not in the default mode.
+    active: HashMap<Seat, String>,
+    bindings: HashMap<(Seat, String), HashMap<ModifiedKeySym, ModeBinding>>,
+}
+
 pub(crate) struct Client {
     configure: extern "C" fn(),
     srv_data: *const u8,
     srv_unref: unsafe extern "C" fn(data: *const u8),
     srv_handler: unsafe extern "C" fn(data: *const u8, msg: *const u8, size: usize),
     key_handlers: RefCell<HashMap<(Seat, ModifiedKeySym), KeyHandler>>,
+    binding_modes: RefCell<BindingModes>,
     timer_handlers: RefCell<HashMap<Timer, Callback>>,
     response: RefCell<Vec<Response>>,
     on_new_seat: RefCell<Option<Callback<Seat>>>,
@@ -92,13 +110,26 @@ pub(crate) struct Client {
     on_input_device_removed: RefCell<Option<Callback<InputDevice>>>,
     on_connector_connected: RefCell<Option<Callback<Connector>>>,
     on_connector_disconnected: RefCell<Option<Callback<Connector>>>,
+    on_mode_fallback: RefCell<Option<Callback<(Connector, Mode, Mode)>>>,
+    on_connector_mode_changed: RefCell<Option<Callback<(Connector, Mode)>>>,
     on_graphics_initialized: Cell<Option<Box<dyn FnOnce()>>>,
     on_devices_enumerated: Cell<Option<Box<dyn FnOnce()>>>,
     on_new_connector: RefCell<Option<Callback<Connector>>>,
     on_new_drm_device: RefCell<Option<Callback<DrmDevice>>>,
     on_del_drm_device: RefCell<Option<Callback<DrmDevice>>>,
     on_idle: RefCell<Option<Callback>>,
+    on_lock: RefCell<Option<Callback>>,
+    on_unlock: RefCell<Option<Callback>>,
+    on_locker_crashed: RefCell<Option<Callback>>,
+    on_window_screenshot_saved: RefCell<Option<Callback<String>>>,
+    on_brightness_changed: RefCell<Option<Callback<(Connector, f64)>>>,
     on_switch_event: RefCell<HashMap<InputDevice, Callback<SwitchEvent>>>,
+    on_touch_gesture: RefCell<HashMap<Seat, Callback<TouchGesture>>>,
+    gesture_handlers: RefCell<HashMap<(Seat, PointerGesture), Callback>>,
+    sequence_handlers: RefCell<HashMap<(Seat, Vec<ModifiedKeySym>), Callback>>,
+    pointer_handlers: RefCell<HashMap<(Seat, PointerTarget, PointerInput), Callback>>,
+    layouts: RefCell<HashMap<Layout, LayoutCallback>>,
+    next_layout_id: Cell<u64>,
     bufs: RefCell<Vec<Vec<u8>>>,
     reload: Cell<bool>,
     read_interests: RefCell<HashMap<PollableId, Interest>>,
@@ -217,6 +248,7 @@ pub unsafe extern "C" fn init(
         srv_unref,
         srv_handler,
         key_handlers: Default::default(),
+        binding_modes: Default::default(),
         timer_handlers: Default::default(),
         response: Default::default(),
         on_new_seat: Default::default(),
@@ -224,13 +256,26 @@ pub unsafe extern "C" fn init(
         on_input_device_removed: Default::default(),
         on_connector_connected: Default::default(),
         on_connector_disconnected: Default::default(),
+        on_mode_fallback: Default::default(),
+        on_connector_mode_changed: Default::default(),
         on_graphics_initialized: Default::default(),
         on_devices_enumerated: Default::default(),
         on_new_connector: Default::default(),
         on_new_drm_device: Default::default(),
         on_del_drm_device: Default::default(),
         on_idle: Default::default(),
+        on_lock: Default::default(),
+        on_unlock: Default::default(),
+        on_locker_crashed: Default::default(),
+        on_window_screenshot_saved: Default::default(),
+        on_brightness_changed: Default::default(),
         on_switch_event: Default::default(),
+        on_touch_gesture: Default::default(),
+        gesture_handlers: Default::default(),
+        sequence_handlers: Default::default(),
+        pointer_handlers: Default::default(),
+        layouts: Default::default(),
+        next_layout_id: Cell::new(1),
         bufs: Default::default(),
         reload: Cell::new(false),
         read_interests: Default::default(),
@@ -338,7 +383,23 @@ impl Client {
     }
 
     pub fn unbind<T: Into<ModifiedKeySym>>(&self, seat: Seat, mod_sym: T) {
-        let mod_sym = mod_sym.into();
+        self.unbind_in_mode(seat, DEFAULT_BINDING_MODE, mod_sym.into());
+    }
+
+    pub fn unbind_in_mode(&self, seat: Seat, mode: &str, mod_sym: ModifiedKeySym) {
+        let removed = self
+            .binding_modes
+            .borrow_mut()
+            .bindings
+            .get_mut(&(seat, mode.to_string()))
+            .and_then(|b| b.remove(&mod_sym))
+            .is_some();
+        if removed && self.binding_mode(seat) == mode {
+            self.deactivate_binding(seat, mod_sym);
+        }
+    }
+
+    fn deactivate_binding(&self, seat: Seat, mod_sym: ModifiedKeySym) {
         if let Entry::Occupied(mut oe) = self.key_handlers.borrow_mut().entry((seat, mod_sym)) {
             oe.get_mut().cb = None;
             if oe.get().latched.is_empty() {
@@ -558,6 +619,42 @@ impl Client {
         self.send(&ClientMessage::SetStatus { status });
     }
 
+    pub fn set_bar_clock_format(&self, connector: Option<Connector>, format: Option<&str>) {
+        self.send(&ClientMessage::SetBarClockFormat { connector, format });
+    }
+
+    pub fn set_bar_show_focused_title(&self, connector: Option<Connector>, show: bool) {
+        self.send(&ClientMessage::SetBarShowFocusedTitle { connector, show });
+    }
+
+    pub fn set_notifications_enabled(&self, enabled: bool) {
+        self.send(&ClientMessage::SetNotificationsEnabled { enabled });
+    }
+
+    pub fn set_do_not_disturb(&self, dnd: bool) {
+        self.send(&ClientMessage::SetDoNotDisturb { dnd });
+    }
+
+    pub fn toggle_do_not_disturb(&self) {
+        self.send(&ClientMessage::ToggleDoNotDisturb);
+    }
+
+    pub fn set_notification_position(&self, position: NotificationPosition) {
+        self.send(&ClientMessage::SetNotificationPosition { position });
+    }
+
+    pub fn set_notification_timeout(&self, timeout: Duration) {
+        self.send(&ClientMessage::SetNotificationTimeout { timeout });
+    }
+
+    pub fn dismiss_notification(&self, all: bool) {
+        self.send(&ClientMessage::DismissNotification { all });
+    }
+
+    pub fn invoke_notification_action(&self) {
+        self.send(&ClientMessage::InvokeNotificationAction);
+    }
+
     pub fn set_status_tasks(&self, tasks: Vec<JoinHandle<()>>) {
         for old in self.status_task.replace(tasks) {
             old.abort();
@@ -584,6 +681,10 @@ impl Client {
         self.send(&ClientMessage::Close { seat });
     }
 
+    pub fn force_quit(&self, seat: Seat) {
+        self.send(&ClientMessage::ForceQuit { seat });
+    }
+
     pub fn focus_parent(&self, seat: Seat) {
         self.send(&ClientMessage::FocusParent { seat });
     }
@@ -630,6 +731,123 @@ impl Client {
             .insert(input_device, cb(f));
     }
 
+    pub fn set_pointer_motion_coalescing(&self, seat: Seat, enabled: bool) {
+        self.send(&ClientMessage::SetPointerMotionCoalescing { seat, enabled });
+    }
+
+    pub fn set_touch_gestures_enabled(&self, seat: Seat, enabled: bool) {
+        self.send(&ClientMessage::SetTouchGesturesEnabled { seat, enabled });
+    }
+
+    pub fn on_touch_gesture<F: FnMut(TouchGesture) + 'static>(&self, seat: Seat, f: F) {
+        self.on_touch_gesture.borrow_mut().insert(seat, cb(f));
+    }
+
+    pub fn bind_gesture<F: FnMut() + 'static>(
+        &self,
+        seat: Seat,
+        gesture: PointerGesture,
+        mut f: F,
+    ) {
+        let prev = self
+            .gesture_handlers
+            .borrow_mut()
+            .insert((seat, gesture), cb(move |_| f()));
+        if prev.is_none() {
+            self.send(&ClientMessage::AddGestureBinding { seat, gesture });
+        }
+    }
+
+    pub fn unbind_gesture(&self, seat: Seat, gesture: PointerGesture) {
+        let prev = self.gesture_handlers.borrow_mut().remove(&(seat, gesture));
+        if prev.is_some() {
+            self.send(&ClientMessage::RemoveGestureBinding { seat, gesture });
+        }
+    }
+
+    pub fn bind_sequence<F: FnMut() + 'static>(
+        &self,
+        seat: Seat,
+        keys: Vec<ModifiedKeySym>,
+        mut f: F,
+    ) {
+        let prev = self
+            .sequence_handlers
+            .borrow_mut()
+            .insert((seat, keys.clone()), cb(move |_| f()));
+        if prev.is_none() {
+            self.send(&ClientMessage::AddShortcutSequence { seat, keys });
+        }
+    }
+
+    pub fn unbind_sequence(&self, seat: Seat, keys: Vec<ModifiedKeySym>) {
+        let prev = self
+            .sequence_handlers
+            .borrow_mut()
+            .remove(&(seat, keys.clone()));
+        if prev.is_some() {
+            self.send(&ClientMessage::RemoveShortcutSequence { seat, keys });
+        }
+    }
+
+    pub fn set_shortcut_sequence_timeout(&self, seat: Seat, timeout: Duration) {
+        self.send(&ClientMessage::SetShortcutSequenceTimeout { seat, timeout });
+    }
+
+    pub fn set_break_out_key(&self, seat: Seat, key: ModifiedKeySym) {
+        self.send(&ClientMessage::SetBreakOutKey { seat, key });
+    }
+
+    pub fn bind_pointer<F: FnMut() + 'static>(
+        &self,
+        seat: Seat,
+        target: PointerTarget,
+        input: PointerInput,
+        mut f: F,
+    ) {
+        let prev = self
+            .pointer_handlers
+            .borrow_mut()
+            .insert((seat, target, input), cb(move |_| f()));
+        if prev.is_none() {
+            self.send(&ClientMessage::AddPointerBinding {
+                seat,
+                target,
+                input,
+            });
+        }
+    }
+
+    pub fn unbind_pointer(&self, seat: Seat, target: PointerTarget, input: PointerInput) {
+        let prev = self
+            .pointer_handlers
+            .borrow_mut()
+            .remove(&(seat, target, input));
+        if prev.is_some() {
+            self.send(&ClientMessage::RemovePointerBinding {
+                seat,
+                target,
+                input,
+            });
+        }
+    }
+
+    pub fn set_gesture_passthrough(&self, seat: Seat, app_id: &str, passthrough: bool) {
+        self.send(&ClientMessage::SetGesturePassthrough {
+            seat,
+            app_id,
+            passthrough,
+        });
+    }
+
+    pub fn set_workspace_swipe_fingers(&self, seat: Seat, fingers: u32) {
+        self.send(&ClientMessage::SetWorkspaceSwipeFingers { seat, fingers });
+    }
+
+    pub fn seat_screenshot_window(&self, seat: Seat) {
+        self.send(&ClientMessage::SeatScreenshotWindow { seat });
+    }
+
     pub fn set_double_click_interval(&self, usec: u64) {
         self.send(&ClientMessage::SetDoubleClickIntervalUsec { usec });
     }
@@ -777,6 +995,14 @@ impl Client {
         self.send(&ClientMessage::ConnectorSetFormat { connector, format });
     }
 
+    pub fn connector_set_hdr_enabled(&self, connector: Connector, enabled: bool) {
+        self.send(&ClientMessage::ConnectorSetHdrEnabled { connector, enabled });
+    }
+
+    pub fn connector_set_render_margin(&self, connector: Connector, margin: Duration) {
+        self.send(&ClientMessage::ConnectorSetRenderMargin { connector, margin });
+    }
+
     pub fn connector_get_scale(&self, connector: Connector) -> f64 {
         let res = self.send_with_response(&ClientMessage::ConnectorGetScale { connector });
         get_response!(res, 1.0, ConnectorGetScale { scale });
@@ -811,6 +1037,31 @@ impl Client {
         self.send(&ClientMessage::ConnectorSetMode { connector, mode });
     }
 
+    pub fn connector_set_custom_mode(&self, connector: Connector, mode: CustomMode) {
+        self.send(&ClientMessage::ConnectorSetCustomMode { connector, mode });
+    }
+
+    pub fn connector_get_brightness(&self, connector: Connector) -> Option<f64> {
+        let res = self.send_with_response(&ClientMessage::ConnectorGetBrightness { connector });
+        get_response!(res, None, ConnectorGetBrightness { brightness });
+        brightness
+    }
+
+    pub fn connector_set_brightness(&self, connector: Connector, brightness: f64) {
+        self.send(&ClientMessage::ConnectorSetBrightness {
+            connector,
+            brightness,
+        });
+    }
+
+    pub fn connector_change_brightness(&self, connector: Connector, delta: f64) {
+        self.send(&ClientMessage::ConnectorChangeBrightness { connector, delta });
+    }
+
+    pub fn seat_change_brightness(&self, seat: Seat, delta: f64) {
+        self.send(&ClientMessage::SeatChangeBrightness { seat, delta });
+    }
+
     pub fn connector_modes(&self, connector: Connector) -> Vec<Mode> {
         let res = self.send_with_response(&ClientMessage::ConnectorModes { connector });
         get_response!(res, Vec::new(), ConnectorModes { modes });
@@ -861,6 +1112,22 @@ impl Client {
         *self.on_idle.borrow_mut() = Some(cb(move |_| f()));
     }
 
+    pub fn on_lock<F: FnMut() + 'static>(&self, mut f: F) {
+        *self.on_lock.borrow_mut() = Some(cb(move |_| f()));
+    }
+
+    pub fn on_unlock<F: FnMut() + 'static>(&self, mut f: F) {
+        *self.on_unlock.borrow_mut() = Some(cb(move |_| f()));
+    }
+
+    pub fn on_locker_crashed<F: FnMut() + 'static>(&self, mut f: F) {
+        *self.on_locker_crashed.borrow_mut() = Some(cb(move |_| f()));
+    }
+
+    pub fn on_window_screenshot_saved<F: FnMut(String) + 'static>(&self, f: F) {
+        *self.on_window_screenshot_saved.borrow_mut() = Some(cb(f));
+    }
+
     pub fn on_connector_connected<F: FnMut(Connector) + 'static>(&self, f: F) {
         *self.on_connector_connected.borrow_mut() = Some(cb(f));
     }
@@ -869,6 +1136,18 @@ impl Client {
         *self.on_connector_disconnected.borrow_mut() = Some(cb(f));
     }
 
+    pub fn on_mode_fallback<F: FnMut(Connector, Mode, Mode) + 'static>(&self, mut f: F) {
+        *self.on_mode_fallback.borrow_mut() = Some(cb(move |(c, r, m)| f(c, r, m)));
+    }
+
+    pub fn on_connector_mode_changed<F: FnMut(Connector, Mode) + 'static>(&self, mut f: F) {
+        *self.on_connector_mode_changed.borrow_mut() = Some(cb(move |(c, m)| f(c, m)));
+    }
+
+    pub fn on_brightness_changed<F: FnMut(Connector, f64) + 'static>(&self, mut f: F) {
+        *self.on_brightness_changed.borrow_mut() = Some(cb(move |(c, b)| f(c, b)));
+    }
+
     pub fn on_graphics_initialized<F: FnOnce() + 'static>(&self, f: F) {
         self.on_graphics_initialized.set(Some(Box::new(f)));
     }
@@ -897,10 +1176,30 @@ impl Client {
         self.send(&ClientMessage::SetIdleGracePeriod { period })
     }
 
+    pub fn set_idle_inhibit_on_audio(&self, enabled: bool, roles: Vec<String>) {
+        self.send(&ClientMessage::SetIdleInhibitOnAudio { enabled, roles })
+    }
+
+    pub fn set_lock_grace_period(&self, period: Duration) {
+        self.send(&ClientMessage::SetLockGracePeriod { period })
+    }
+
+    pub fn set_fallback_lock_screen_prompt(&self, enabled: bool) {
+        self.send(&ClientMessage::SetFallbackLockScreenPrompt { enabled })
+    }
+
     pub fn set_explicit_sync_enabled(&self, enabled: bool) {
         self.send(&ClientMessage::SetExplicitSyncEnabled { enabled })
     }
 
+    pub fn set_force_implicit_sync(&self, force: bool) {
+        self.send(&ClientMessage::SetForceImplicitSync { force })
+    }
+
+    pub fn set_activation_token_lifetime(&self, lifetime: Duration) {
+        self.send(&ClientMessage::SetActivationTokenLifetime { lifetime })
+    }
+
     pub fn set_seat(&self, device: InputDevice, seat: Seat) {
         self.send(&ClientMessage::SetSeat { device, seat })
     }
@@ -932,6 +1231,50 @@ impl Client {
         self.send(&ClientMessage::SetCalibrationMatrix { device, matrix })
     }
 
+    pub fn set_button_debounce(&self, device: InputDevice, timeout: Duration) {
+        self.send(&ClientMessage::SetButtonDebounce { device, timeout })
+    }
+
+    pub fn set_device_key_remap(&self, device: InputDevice, remap: &[(u32, u32)]) {
+        self.send(&ClientMessage::SetDeviceKeyRemap {
+            device,
+            remap: remap.to_vec(),
+        })
+    }
+
+    pub fn set_key_remap(&self, remap: &[(u32, u32)]) {
+        self.send(&ClientMessage::SetKeyRemap {
+            remap: remap.to_vec(),
+        })
+    }
+
+    pub fn set_seat_outputs(&self, seat: Seat, connectors: &[Connector]) {
+        self.send(&ClientMessage::SetSeatOutputs {
+            seat,
+            connectors: connectors.to_vec(),
+        })
+    }
+
+    pub fn set_gamepad_resets_idle(&self, device: InputDevice, enabled: bool) {
+        self.send(&ClientMessage::SetGamepadResetsIdle { device, enabled })
+    }
+
+    pub fn set_disable_while_typing_enabled(&self, device: InputDevice, enabled: bool) {
+        self.send(&ClientMessage::SetDisableWhileTypingEnabled { device, enabled })
+    }
+
+    pub fn set_disable_while_trackpointing_enabled(&self, device: InputDevice, enabled: bool) {
+        self.send(&ClientMessage::SetDisableWhileTrackpointingEnabled { device, enabled })
+    }
+
+    pub fn set_disable_while_typing_window(&self, device: InputDevice, window: Duration) {
+        self.send(&ClientMessage::SetDisableWhileTypingWindow { device, window })
+    }
+
+    pub fn set_middle_button_emulation_enabled(&self, device: InputDevice, enabled: bool) {
+        self.send(&ClientMessage::SetMiddleButtonEmulationEnabled { device, enabled })
+    }
+
     pub fn set_px_per_wheel_scroll(&self, device: InputDevice, px: f64) {
         self.send(&ClientMessage::SetPxPerWheelScroll { device, px })
     }
@@ -970,6 +1313,22 @@ impl Client {
         devnode
     }
 
+    pub fn input_device_ids(&self, device: InputDevice) -> (u32, u32) {
+        let res = self.send_with_response(&ClientMessage::GetInputDeviceIds { device });
+        get_response!(res, (0, 0), GetInputDeviceIds { vendor, product });
+        (vendor, product)
+    }
+
+    pub fn set_input_device_enabled(&self, device: InputDevice, enabled: bool) {
+        self.send(&ClientMessage::SetInputDeviceEnabled { device, enabled })
+    }
+
+    pub fn input_device_enabled(&self, device: InputDevice) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetInputDeviceEnabled { device });
+        get_response!(res, true, GetInputDeviceEnabled { enabled });
+        enabled
+    }
+
     pub fn has_capability(&self, device: InputDevice, cap: Capability) -> bool {
         let res = self.send_with_response(&ClientMessage::HasCapability { device, cap });
         get_response!(res, false, HasCapability { has });
@@ -994,6 +1353,20 @@ impl Client {
         (rate, delay)
     }
 
+    pub fn seat_set_led_override(&self, seat: Seat, leds: Leds, enabled: Option<bool>) {
+        self.send(&ClientMessage::SeatSetLedOverride {
+            seat,
+            leds,
+            enabled,
+        })
+    }
+
+    pub fn seat_get_leds(&self, seat: Seat) -> Leds {
+        let res = self.send_with_response(&ClientMessage::SeatGetLeds { seat });
+        get_response!(res, Leds::NONE, SeatGetLeds { leds });
+        leds
+    }
+
     pub fn set_forward(&self, seat: Seat, forward: bool) {
         self.send(&ClientMessage::SetForward { seat, forward })
     }
@@ -1023,10 +1396,212 @@ impl Client {
         keymap
     }
 
+    pub fn keymap_from_names(&self, names: &KeymapNames<'_>) -> Keymap {
+        let res = self.send_with_response(&ClientMessage::KeymapFromNames {
+            rules: names.rules,
+            model: names.model,
+            layout: names.layout,
+            variant: names.variant,
+            options: names.options,
+        });
+        get_response!(res, Keymap(0), ParseKeymap { keymap });
+        keymap
+    }
+
     pub fn set_ei_socket_enabled(&self, enabled: bool) {
         self.send(&ClientMessage::SetEiSocketEnabled { enabled })
     }
 
+    pub fn set_ei_authorization_prompt(&self, enabled: bool) {
+        self.send(&ClientMessage::SetEiAuthorizationPrompt { enabled })
+    }
+
+    pub fn ei_permissions(&self) -> Vec<String> {
+        let res = self.send_with_response(&ClientMessage::GetEiPermissions);
+        get_response!(res, vec![], GetEiPermissions { executables });
+        executables
+    }
+
+    pub fn revoke_ei_permission(&self, executable: &str) {
+        self.send(&ClientMessage::RevokeEiPermission { executable })
+    }
+
+    pub fn create_virtual_output(&self, mode: WireMode) -> Option<Connector> {
+        let res = self.send_with_response(&ClientMessage::CreateVirtualOutput { mode });
+        get_response!(res, None, CreateVirtualOutput { connector });
+        connector
+    }
+
+    pub fn remove_virtual_output(&self, connector: Connector) {
+        self.send(&ClientMessage::RemoveVirtualOutput { connector })
+    }
+
+    pub fn set_jay_protocol_allowlist(&self, executables: Option<Vec<String>>) {
+        self.send(&ClientMessage::SetJayProtocolAllowlist { executables })
+    }
+
+    pub fn set_remember_window_state(&self, remember: bool) {
+        self.send(&ClientMessage::SetRememberWindowState { remember })
+    }
+
+    pub fn set_window_state_exclusions(&self, app_ids: Vec<String>) {
+        self.send(&ClientMessage::SetWindowStateExclusions { app_ids })
+    }
+
+    pub fn set_capture_redactions(&self, app_ids: Vec<String>) {
+        self.send(&ClientMessage::SetCaptureRedactions { app_ids })
+    }
+
+    pub fn set_capture_overlay(&self, text: Option<&str>, image: Option<&str>) {
+        self.send(&ClientMessage::SetCaptureOverlay {
+            text: text.map(|t| t.to_string()),
+            image: image.map(|i| i.to_string()),
+        })
+    }
+
+    pub fn set_window_screenshot_directory(&self, directory: Option<&str>) {
+        self.send(&ClientMessage::SetWindowScreenshotDirectory { directory })
+    }
+
+    pub fn set_window_screenshot_filename(&self, filename: Option<&str>) {
+        self.send(&ClientMessage::SetWindowScreenshotFilename { filename })
+    }
+
+    pub fn set_client_limits(&self, max_fds: Option<u64>, max_memory: Option<u64>) {
+        self.send(&ClientMessage::SetClientLimits {
+            max_fds,
+            max_memory,
+        })
+    }
+
+    pub fn set_outputs_powered(&self, powered: bool) {
+        self.send(&ClientMessage::SetOutputsPowered { powered })
+    }
+
+    pub fn toggle_outputs_powered(&self) {
+        self.send(&ClientMessage::ToggleOutputsPowered)
+    }
+
+    pub fn set_show_surface_regions(&self, show: bool) {
+        self.send(&ClientMessage::SetShowSurfaceRegions { show })
+    }
+
+    pub fn toggle_show_surface_regions(&self) {
+        self.send(&ClientMessage::ToggleShowSurfaceRegions)
+    }
+
+    pub fn set_throttle_interactive_resize(&self, enabled: bool) {
+        self.send(&ClientMessage::SetThrottleInteractiveResize { enabled })
+    }
+
+    pub fn set_empty_workspace_behavior(&self, behavior: EmptyWorkspaceBehavior) {
+        self.send(&ClientMessage::SetEmptyWorkspaceBehavior { behavior })
+    }
+
+    pub fn set_autotiling(&self, enabled: bool) {
+        self.send(&ClientMessage::SetAutotiling { enabled })
+    }
+
+    pub fn set_night_light_schedule(&self, schedule: NightLightSchedule) {
+        self.send(&ClientMessage::SetNightLightSchedule { schedule })
+    }
+
+    pub fn set_night_light_temperature(&self, kelvin: u32) {
+        self.send(&ClientMessage::SetNightLightTemperature { kelvin })
+    }
+
+    pub fn set_night_light_transition(&self, duration: Duration) {
+        self.send(&ClientMessage::SetNightLightTransition { duration })
+    }
+
+    pub fn create_layout<F: FnMut(&LayoutRequest) -> Vec<LayoutGeometry> + 'static>(
+        &self,
+        f: F,
+    ) -> Layout {
+        let id = self.next_layout_id.get();
+        self.next_layout_id.set(id + 1);
+        let layout = Layout(id);
+        self.layouts
+            .borrow_mut()
+            .insert(layout, Rc::new(RefCell::new(f)));
+        layout
+    }
+
+    pub fn destroy_layout(&self, layout: Layout) {
+        self.layouts.borrow_mut().remove(&layout);
+    }
+
+    pub fn relayout(&self, layout: Layout) {
+        self.send(&ClientMessage::Relayout { layout })
+    }
+
+    pub fn set_workspace_layout(&self, workspace: Workspace, layout: Option<Layout>) {
+        self.send(&ClientMessage::SetWorkspaceLayout { workspace, layout })
+    }
+
+    pub fn rename_workspace(&self, workspace: Workspace, name: &str) {
+        self.send(&ClientMessage::RenameWorkspace { workspace, name })
+    }
+
+    pub fn create_workspace(&self, workspace: Workspace, connector: Connector) {
+        self.send(&ClientMessage::CreateWorkspace {
+            workspace,
+            connector,
+        })
+    }
+
+    pub fn set_app_workspace(&self, app_id: &str, workspace: Option<Workspace>) {
+        self.send(&ClientMessage::SetAppWorkspace { app_id, workspace })
+    }
+
+    pub fn show_previous_workspace(&self, seat: Seat) {
+        self.send(&ClientMessage::ShowPreviousWorkspace { seat })
+    }
+
+    pub fn set_workspace_back_and_forth(&self, enabled: bool) {
+        self.send(&ClientMessage::SetWorkspaceBackAndForth { enabled })
+    }
+
+    pub fn mark(&self, seat: Seat, mark: &str) {
+        self.send(&ClientMessage::Mark { seat, mark })
+    }
+
+    pub fn unmark(&self, seat: Seat) {
+        self.send(&ClientMessage::Unmark { seat })
+    }
+
+    pub fn focus_mark(&self, seat: Seat, mark: &str) {
+        self.send(&ClientMessage::FocusMark { seat, mark })
+    }
+
+    pub fn swap_with_mark(&self, seat: Seat, mark: &str) {
+        self.send(&ClientMessage::SwapWithMark { seat, mark })
+    }
+
+    pub fn move_to_mark(&self, seat: Seat, mark: &str) {
+        self.send(&ClientMessage::MoveToMark { seat, mark })
+    }
+
+    pub fn set_show_marks(&self, show: bool) {
+        self.send(&ClientMessage::SetShowMarks { show })
+    }
+
+    pub fn set_workspace_inner_gap(&self, workspace: Workspace, gap: Option<i32>) {
+        self.send(&ClientMessage::SetWorkspaceInnerGap { workspace, gap })
+    }
+
+    pub fn set_workspace_outer_gap(&self, workspace: Workspace, gap: Option<i32>) {
+        self.send(&ClientMessage::SetWorkspaceOuterGap { workspace, gap })
+    }
+
+    pub fn connector_set_mirror(&self, connector: Connector, source: Option<Connector>) {
+        self.send(&ClientMessage::ConnectorSetMirror { connector, source })
+    }
+
+    pub fn set_presentation_output(&self, connector: Option<Connector>) {
+        self.send(&ClientMessage::SetPresentationOutput { connector })
+    }
+
     pub fn latch<F: FnOnce() + 'static>(&self, seat: Seat, f: F) {
         if !self.feat_mod_mask.get() {
             log::error!("compositor does not support latching");
@@ -1070,14 +1645,49 @@ impl Client {
     pub fn bind_masked<F: FnMut() + 'static>(
         &self,
         seat: Seat,
+        mod_mask: Modifiers,
+        mod_sym: ModifiedKeySym,
+        f: F,
+    ) {
+        self.bind_masked_in_mode(seat, DEFAULT_BINDING_MODE, mod_mask, mod_sym, f)
+    }
+
+    pub fn bind_masked_in_mode<F: FnMut() + 'static>(
+        &self,
+        seat: Seat,
+        mode: &str,
         mut mod_mask: Modifiers,
         mod_sym: ModifiedKeySym,
         mut f: F,
     ) {
         mod_mask |= mod_sym.mods | RELEASE;
+        let cb = cb(move |_| f());
+        self.binding_modes
+            .borrow_mut()
+            .bindings
+            .entry((seat, mode.to_string()))
+            .or_default()
+            .insert(
+                mod_sym,
+                ModeBinding {
+                    mod_mask,
+                    cb: cb.clone(),
+                },
+            );
+        if self.binding_mode(seat) == mode {
+            self.activate_binding(seat, mod_mask, mod_sym, cb);
+        }
+    }
+
+    fn activate_binding(
+        &self,
+        seat: Seat,
+        mod_mask: Modifiers,
+        mod_sym: ModifiedKeySym,
+        cb: Callback,
+    ) {
         let register = {
             let mut kh = self.key_handlers.borrow_mut();
-            let cb = cb(move |_| f());
             match kh.entry((seat, mod_sym)) {
                 Entry::Occupied(mut o) => {
                     let o = o.get_mut();
@@ -1119,6 +1729,54 @@ impl Client {
         }
     }
 
+    pub fn set_binding_mode(&self, seat: Seat, mode: &str) {
+        let old = self.binding_mode(seat);
+        if old == mode {
+            return;
+        }
+        let (old_keys, new_bindings) = {
+            let bm = &mut *self.binding_modes.borrow_mut();
+            if mode == DEFAULT_BINDING_MODE {
+                bm.active.remove(&seat);
+            } else {
+                bm.active.insert(seat, mode.to_string());
+            }
+            let old_keys: Vec<_> = bm
+                .bindings
+                .get(&(seat, old))
+                .map(|b| b.keys().copied().collect())
+                .unwrap_or_default();
+            let new_bindings: Vec<_> = bm
+                .bindings
+                .get(&(seat, mode.to_string()))
+                .map(|b| {
+                    b.iter()
+                        .map(|(mod_sym, b)| (*mod_sym, b.mod_mask, b.cb.clone()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            (old_keys, new_bindings)
+        };
+        for mod_sym in old_keys {
+            if new_bindings.iter().all(|(new, _, _)| *new != mod_sym) {
+                self.deactivate_binding(seat, mod_sym);
+            }
+        }
+        for (mod_sym, mod_mask, cb) in new_bindings {
+            self.activate_binding(seat, mod_mask, mod_sym, cb);
+        }
+        self.send(&ClientMessage::SetBindingMode { seat, mode });
+    }
+
+    pub fn binding_mode(&self, seat: Seat) -> String {
+        self.binding_modes
+            .borrow()
+            .active
+            .get(&seat)
+            .cloned()
+            .unwrap_or_else(|| DEFAULT_BINDING_MODE.to_string())
+    }
+
     pub fn log(&self, level: LogLevel, msg: &str, file: Option<&str>, line: Option<u32>) {
         self.send(&ClientMessage::Log {
             level,
@@ -1406,6 +2064,24 @@ impl Client {
                     run_cb("idle", handler, ());
                 }
             }
+            ServerMessage::Locked => {
+                let handler = self.on_lock.borrow_mut();
+                if let Some(handler) = handler.deref() {
+                    run_cb("lock", handler, ());
+                }
+            }
+            ServerMessage::Unlocked => {
+                let handler = self.on_unlock.borrow_mut();
+                if let Some(handler) = handler.deref() {
+                    run_cb("unlock", handler, ());
+                }
+            }
+            ServerMessage::LockerCrashed => {
+                let handler = self.on_locker_crashed.borrow_mut();
+                if let Some(handler) = handler.deref() {
+                    run_cb("locker crashed", handler, ());
+                }
+            }
             ServerMessage::DevicesEnumerated => {
                 if let Some(handler) = self.on_devices_enumerated.take() {
                     ignore_panic("devices enumerated", handler);
@@ -1444,6 +2120,96 @@ impl Client {
                     run_cb("switch event", &cb, event);
                 }
             }
+            ServerMessage::TouchGesture { seat, gesture } => {
+                let cb = self.on_touch_gesture.borrow().get(&seat).cloned();
+                if let Some(cb) = cb {
+                    run_cb("touch gesture", &cb, gesture);
+                }
+            }
+            ServerMessage::InvokeGesture { seat, gesture } => {
+                let cb = self
+                    .gesture_handlers
+                    .borrow()
+                    .get(&(seat, gesture))
+                    .cloned();
+                if let Some(cb) = cb {
+                    run_cb("gesture", &cb, ());
+                }
+            }
+            ServerMessage::InvokePointerBinding {
+                seat,
+                target,
+                input,
+            } => {
+                let cb = self
+                    .pointer_handlers
+                    .borrow()
+                    .get(&(seat, target, input))
+                    .cloned();
+                if let Some(cb) = cb {
+                    run_cb("pointer binding", &cb, ());
+                }
+            }
+            ServerMessage::InvokeShortcutSequence { seat, keys } => {
+                let cb = self.sequence_handlers.borrow().get(&(seat, keys)).cloned();
+                if let Some(cb) = cb {
+                    run_cb("shortcut sequence", &cb, ());
+                }
+            }
+            ServerMessage::ModeFallback {
+                connector,
+                requested,
+                chosen,
+            } => {
+                let handler = self.on_mode_fallback.borrow_mut().clone();
+                if let Some(handler) = handler {
+                    run_cb(
+                        "mode fallback",
+                        &handler,
+                        (connector, requested.to_mode(), chosen.to_mode()),
+                    );
+                }
+            }
+            ServerMessage::ConnectorModeChanged { connector, mode } => {
+                let handler = self.on_connector_mode_changed.borrow_mut().clone();
+                if let Some(handler) = handler {
+                    run_cb(
+                        "connector mode changed",
+                        &handler,
+                        (connector, mode.to_mode()),
+                    );
+                }
+            }
+            ServerMessage::BrightnessChanged {
+                connector,
+                brightness,
+            } => {
+                let handler = self.on_brightness_changed.borrow_mut().clone();
+                if let Some(handler) = handler {
+                    run_cb("brightness changed", &handler, (connector, brightness));
+                }
+            }
+            ServerMessage::Layout { layout, request } => {
+                let handler = self.layouts.borrow_mut().get(&layout).cloned();
+                let Some(handler) = handler else {
+                    return;
+                };
+                let Ok(mut handler) = handler.try_borrow_mut() else {
+                    log::error!("Cannot invoke layout callback because it is already running");
+                    return;
+                };
+                let mut geometries = None;
+                ignore_panic("layout", || geometries = Some(handler(&request)));
+                if let Some(geometries) = geometries {
+                    self.send(&ClientMessage::SetLayoutGeometries { geometries });
+                }
+            }
+            ServerMessage::WindowScreenshotSaved { path } => {
+                let handler = self.on_window_screenshot_saved.borrow_mut().clone();
+                if let Some(handler) = handler {
+                    run_cb("window screenshot saved", &handler, path);
+                }
+            }
         }
     }
 
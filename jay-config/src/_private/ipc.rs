@@ -1,20 +1,23 @@
 use {
     crate::{
+        _private::{PollableId, WireMode},
         input::{
-            acceleration::AccelProfile, capability::Capability, FocusFollowsMouseMode, InputDevice,
-            Seat, SwitchEvent,
+            acceleration::AccelProfile, capability::Capability, led::Leds, FocusFollowsMouseMode,
+            InputDevice, PointerGesture, PointerInput, PointerTarget, Seat, SwitchEvent,
+            TouchGesture,
         },
-        keyboard::{mods::Modifiers, syms::KeySym, Keymap},
+        keyboard::{mods::Modifiers, syms::KeySym, Keymap, ModifiedKeySym},
+        layout::{Layout, LayoutGeometry, LayoutRequest},
         logging::LogLevel,
+        notifications::NotificationPosition,
         theme::{colors::Colorable, sized::Resizable, Color},
         timer::Timer,
         video::{
-            connector_type::ConnectorType, Connector, DrmDevice, Format, GfxApi, TearingMode,
-            Transform, VrrMode,
+            connector_type::ConnectorType, Connector, CustomMode, DrmDevice, Format, GfxApi,
+            NightLightSchedule, TearingMode, Transform, VrrMode,
         },
-        Axis, Direction, PciId, Workspace,
-        _private::{PollableId, WireMode},
         xwayland::XScalingMode,
+        Axis, Direction, EmptyWorkspaceBehavior, PciId, Workspace,
     },
     serde::{Deserialize, Serialize},
     std::time::Duration,
@@ -92,6 +95,46 @@ pub enum ServerMessage {
         input_device: InputDevice,
         event: SwitchEvent,
     },
+    TouchGesture {
+        seat: Seat,
+        gesture: TouchGesture,
+    },
+    InvokeGesture {
+        seat: Seat,
+        gesture: PointerGesture,
+    },
+    InvokePointerBinding {
+        seat: Seat,
+        target: PointerTarget,
+        input: PointerInput,
+    },
+    InvokeShortcutSequence {
+        seat: Seat,
+        keys: Vec<ModifiedKeySym>,
+    },
+    ModeFallback {
+        connector: Connector,
+        requested: WireMode,
+        chosen: WireMode,
+    },
+    Locked,
+    Unlocked,
+    LockerCrashed,
+    ConnectorModeChanged {
+        connector: Connector,
+        mode: WireMode,
+    },
+    Layout {
+        layout: Layout,
+        request: LayoutRequest,
+    },
+    WindowScreenshotSaved {
+        path: String,
+    },
+    BrightnessChanged {
+        connector: Connector,
+        brightness: f64,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -530,6 +573,325 @@ pub enum ClientMessage<'a> {
     SetIdleGracePeriod {
         period: Duration,
     },
+    SetTouchGesturesEnabled {
+        seat: Seat,
+        enabled: bool,
+    },
+    AddGestureBinding {
+        seat: Seat,
+        gesture: PointerGesture,
+    },
+    RemoveGestureBinding {
+        seat: Seat,
+        gesture: PointerGesture,
+    },
+    SetGesturePassthrough {
+        seat: Seat,
+        app_id: &'a str,
+        passthrough: bool,
+    },
+    AddPointerBinding {
+        seat: Seat,
+        target: PointerTarget,
+        input: PointerInput,
+    },
+    RemovePointerBinding {
+        seat: Seat,
+        target: PointerTarget,
+        input: PointerInput,
+    },
+    SetBarClockFormat {
+        connector: Option<Connector>,
+        format: Option<&'a str>,
+    },
+    SetBarShowFocusedTitle {
+        connector: Option<Connector>,
+        show: bool,
+    },
+    SetNotificationsEnabled {
+        enabled: bool,
+    },
+    SetDoNotDisturb {
+        dnd: bool,
+    },
+    ToggleDoNotDisturb,
+    SetNotificationPosition {
+        position: NotificationPosition,
+    },
+    SetNotificationTimeout {
+        timeout: Duration,
+    },
+    DismissNotification {
+        all: bool,
+    },
+    InvokeNotificationAction,
+    SetJayProtocolAllowlist {
+        executables: Option<Vec<String>>,
+    },
+    SetRememberWindowState {
+        remember: bool,
+    },
+    SetWindowStateExclusions {
+        app_ids: Vec<String>,
+    },
+    SetOutputsPowered {
+        powered: bool,
+    },
+    ToggleOutputsPowered,
+    SetPresentationOutput {
+        connector: Option<Connector>,
+    },
+    AddShortcutSequence {
+        seat: Seat,
+        keys: Vec<ModifiedKeySym>,
+    },
+    RemoveShortcutSequence {
+        seat: Seat,
+        keys: Vec<ModifiedKeySym>,
+    },
+    SetShortcutSequenceTimeout {
+        seat: Seat,
+        timeout: Duration,
+    },
+    SetBreakOutKey {
+        seat: Seat,
+        key: ModifiedKeySym,
+    },
+    SetShowSurfaceRegions {
+        show: bool,
+    },
+    ToggleShowSurfaceRegions,
+    SetThrottleInteractiveResize {
+        enabled: bool,
+    },
+    SetButtonDebounce {
+        device: InputDevice,
+        timeout: Duration,
+    },
+    SetGamepadResetsIdle {
+        device: InputDevice,
+        enabled: bool,
+    },
+    SetDisableWhileTypingEnabled {
+        device: InputDevice,
+        enabled: bool,
+    },
+    SetDisableWhileTrackpointingEnabled {
+        device: InputDevice,
+        enabled: bool,
+    },
+    SetDisableWhileTypingWindow {
+        device: InputDevice,
+        window: Duration,
+    },
+    GetInputDeviceIds {
+        device: InputDevice,
+    },
+    SetInputDeviceEnabled {
+        device: InputDevice,
+        enabled: bool,
+    },
+    SeatSetLedOverride {
+        seat: Seat,
+        leds: Leds,
+        enabled: Option<bool>,
+    },
+    SeatGetLeds {
+        seat: Seat,
+    },
+    SetIdleInhibitOnAudio {
+        enabled: bool,
+        roles: Vec<String>,
+    },
+    SetLockGracePeriod {
+        period: Duration,
+    },
+    SetFallbackLockScreenPrompt {
+        enabled: bool,
+    },
+    SetCaptureRedactions {
+        app_ids: Vec<String>,
+    },
+    SetCaptureOverlay {
+        text: Option<String>,
+        image: Option<String>,
+    },
+    ConnectorSetHdrEnabled {
+        connector: Connector,
+        enabled: bool,
+    },
+    SetEiAuthorizationPrompt {
+        enabled: bool,
+    },
+    GetEiPermissions,
+    RevokeEiPermission {
+        executable: &'a str,
+    },
+    CreateVirtualOutput {
+        mode: WireMode,
+    },
+    RemoveVirtualOutput {
+        connector: Connector,
+    },
+    GetInputDeviceEnabled {
+        device: InputDevice,
+    },
+    ConnectorSetRenderMargin {
+        connector: Connector,
+        margin: Duration,
+    },
+    SetEmptyWorkspaceBehavior {
+        behavior: EmptyWorkspaceBehavior,
+    },
+    SetAutotiling {
+        enabled: bool,
+    },
+    SetNightLightSchedule {
+        schedule: NightLightSchedule,
+    },
+    SetNightLightTemperature {
+        kelvin: u32,
+    },
+    SetNightLightTransition {
+        duration: Duration,
+    },
+    SetWorkspaceLayout {
+        workspace: Workspace,
+        layout: Option<Layout>,
+    },
+    SetLayoutGeometries {
+        geometries: Vec<LayoutGeometry>,
+    },
+    ConnectorSetMirror {
+        connector: Connector,
+        source: Option<Connector>,
+    },
+    SetWorkspaceSwipeFingers {
+        seat: Seat,
+        fingers: u32,
+    },
+    SeatScreenshotWindow {
+        seat: Seat,
+    },
+    SetWindowScreenshotDirectory {
+        directory: Option<&'a str>,
+    },
+    SetWindowScreenshotFilename {
+        filename: Option<&'a str>,
+    },
+    ConnectorSetCustomMode {
+        connector: Connector,
+        mode: CustomMode,
+    },
+    ConnectorGetBrightness {
+        connector: Connector,
+    },
+    ConnectorSetBrightness {
+        connector: Connector,
+        brightness: f64,
+    },
+    ConnectorChangeBrightness {
+        connector: Connector,
+        delta: f64,
+    },
+    SeatChangeBrightness {
+        seat: Seat,
+        delta: f64,
+    },
+    SetClientLimits {
+        max_fds: Option<u64>,
+        max_memory: Option<u64>,
+    },
+    SetPointerMotionCoalescing {
+        seat: Seat,
+        enabled: bool,
+    },
+    SetMiddleButtonEmulationEnabled {
+        device: InputDevice,
+        enabled: bool,
+    },
+    KeymapFromNames {
+        rules: Option<&'a str>,
+        model: Option<&'a str>,
+        layout: Option<&'a str>,
+        variant: Option<&'a str>,
+        options: Option<&'a str>,
+    },
+    SetBindingMode {
+        seat: Seat,
+        mode: &'a str,
+    },
+    SetForceImplicitSync {
+        force: bool,
+    },
+    SetActivationTokenLifetime {
+        lifetime: Duration,
+    },
+    ForceQuit {
+        seat: Seat,
+    },
+    SetDeviceKeyRemap {
+        device: InputDevice,
+        remap: Vec<(u32, u32)>,
+    },
+    SetKeyRemap {
+        remap: Vec<(u32, u32)>,
+    },
+    SetSeatOutputs {
+        seat: Seat,
+        connectors: Vec<Connector>,
+    },
+    Relayout {
+        layout: Layout,
+    },
+    RenameWorkspace {
+        workspace: Workspace,
+        name: &'a str,
+    },
+    CreateWorkspace {
+        workspace: Workspace,
+        connector: Connector,
+    },
+    SetAppWorkspace {
+        app_id: &'a str,
+        workspace: Option<Workspace>,
+    },
+    ShowPreviousWorkspace {
+        seat: Seat,
+    },
+    SetWorkspaceBackAndForth {
+        enabled: bool,
+    },
+    Mark {
+        seat: Seat,
+        mark: &'a str,
+    },
+    Unmark {
+        seat: Seat,
+    },
+    FocusMark {
+        seat: Seat,
+        mark: &'a str,
+    },
+    SwapWithMark {
+        seat: Seat,
+        mark: &'a str,
+    },
+    MoveToMark {
+        seat: Seat,
+        mark: &'a str,
+    },
+    SetShowMarks {
+        show: bool,
+    },
+    SetWorkspaceInnerGap {
+        workspace: Workspace,
+        gap: Option<i32>,
+    },
+    SetWorkspaceOuterGap {
+        workspace: Workspace,
+        gap: Option<i32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -678,6 +1040,25 @@ pub enum Response {
     GetSocketPath {
         path: String,
     },
+    GetInputDeviceIds {
+        vendor: u32,
+        product: u32,
+    },
+    SeatGetLeds {
+        leds: Leds,
+    },
+    GetEiPermissions {
+        executables: Vec<String>,
+    },
+    CreateVirtualOutput {
+        connector: Option<Connector>,
+    },
+    GetInputDeviceEnabled {
+        enabled: bool,
+    },
+    ConnectorGetBrightness {
+        brightness: Option<f64>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
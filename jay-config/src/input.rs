@@ -2,10 +2,11 @@
 
 pub mod acceleration;
 pub mod capability;
+pub mod led;
 
 use {
     crate::{
-        input::{acceleration::AccelProfile, capability::Capability},
+        input::{acceleration::AccelProfile, capability::Capability, led::Leds},
         keyboard::{mods::Modifiers, Keymap},
         Axis, Direction, ModifiedKeySym, Workspace,
         _private::{ipc::WorkspaceSource, DEFAULT_SEAT_NAME},
@@ -87,6 +88,86 @@ impl InputDevice {
         get!().set_calibration_matrix(self, matrix);
     }
 
+    /// Sets the button debounce timeout of the device.
+    ///
+    /// If a button is pressed again within this time after it was released, both the
+    /// release and the press are ignored. This can be used to work around worn-out mouse
+    /// switches that produce spurious clicks.
+    ///
+    /// Releases are delayed by this timeout. A timeout of zero disables debouncing.
+    ///
+    /// The default is zero.
+    pub fn set_button_debounce(self, timeout: Duration) {
+        get!().set_button_debounce(self, timeout);
+    }
+
+    /// Sets the key remapping table of the device.
+    ///
+    /// Each entry maps the evdev code of a key or button to another evdev code, for
+    /// example `(58, 1)` to turn caps lock into escape or `(BTN_SIDE, BTN_MIDDLE)`. The
+    /// remapping happens before the keymap is applied, so the remapped key behaves exactly
+    /// like a physical key with the new code. Keys cannot be remapped to buttons or vice
+    /// versa.
+    ///
+    /// Entries of this table take precedence over the table set with [`set_key_remap`].
+    /// An empty table removes the device remapping.
+    pub fn set_key_remap(self, remap: &[(u32, u32)]) {
+        get!().set_device_key_remap(self, remap);
+    }
+
+    /// Sets whether input from this gamepad resets the idle timer.
+    ///
+    /// Jay does not otherwise process gamepad input. The gamepad is only monitored and
+    /// applications continue to receive all of its events. This has no effect on devices
+    /// that are not gamepads.
+    ///
+    /// The default is `false`.
+    pub fn set_gamepad_resets_idle(self, enabled: bool) {
+        get!().set_gamepad_resets_idle(self, enabled);
+    }
+
+    /// Sets whether the touchpad is disabled while typing.
+    ///
+    /// This is implemented by libinput and only affects touchpads that libinput pairs
+    /// with a keyboard, usually the internal keyboard of a laptop. See
+    /// [`InputDevice::set_disable_while_typing_window`] for a setting that works with all
+    /// devices.
+    ///
+    /// The default is device specific.
+    pub fn set_disable_while_typing_enabled(self, enabled: bool) {
+        get!().set_disable_while_typing_enabled(self, enabled);
+    }
+
+    /// Sets whether the touchpad is disabled while the trackpoint is in use.
+    ///
+    /// This is implemented by libinput.
+    ///
+    /// The default is device specific.
+    pub fn set_disable_while_trackpointing_enabled(self, enabled: bool) {
+        get!().set_disable_while_trackpointing_enabled(self, enabled);
+    }
+
+    /// Sets the time after a key press during which pointer motion, button presses, and
+    /// scrolling of this device are ignored.
+    ///
+    /// Key presses on any keyboard of the seat are considered, except for modifier
+    /// keys. A window of zero disables this.
+    ///
+    /// The default is zero.
+    pub fn set_disable_while_typing_window(self, window: Duration) {
+        get!().set_disable_while_typing_window(self, window);
+    }
+
+    /// Sets whether pressing the left and right buttons at the same time produces a
+    /// middle button press.
+    ///
+    /// This is implemented by libinput and only affects devices that support it.
+    ///
+    /// The default is device specific.
+    pub fn set_middle_button_emulation_enabled(self, enabled: bool) {
+        get!().set_middle_button_emulation_enabled(self, enabled);
+    }
+
     /// Returns the name of the device.
     pub fn name(self) -> String {
         get!(String::new()).device_name(self)
@@ -146,6 +227,43 @@ impl InputDevice {
         get!(String::new()).input_device_devnode(self)
     }
 
+    /// Returns the vendor ID of this device.
+    ///
+    /// For USB devices this is the USB vendor ID. Returns `0` if the ID is not known.
+    pub fn vendor_id(self) -> u32 {
+        get!(0).input_device_ids(self).0
+    }
+
+    /// Returns the product ID of this device.
+    ///
+    /// For USB devices this is the USB product ID. Returns `0` if the ID is not known.
+    pub fn product_id(self) -> u32 {
+        get!(0).input_device_ids(self).1
+    }
+
+    /// Sets whether this device sends events.
+    ///
+    /// Disabled devices stay attached to their seat but their input is ignored.
+    ///
+    /// The default is `true`.
+    pub fn set_enabled(self, enabled: bool) {
+        get!().set_input_device_enabled(self, enabled);
+    }
+
+    /// Returns whether this device sends events.
+    pub fn enabled(self) -> bool {
+        get!(true).input_device_enabled(self)
+    }
+
+    /// Enables this device if it is disabled and disables it otherwise.
+    ///
+    /// Returns the new state.
+    pub fn toggle_enabled(self) -> bool {
+        let enabled = !self.enabled();
+        self.set_enabled(enabled);
+        enabled
+    }
+
     /// Sets a callback that will be run if this device triggers a switch event.
     pub fn on_switch_event<F: FnMut(SwitchEvent) + 'static>(self, f: F) {
         get!().on_switch_event(self, f)
@@ -205,6 +323,21 @@ impl Seat {
         get!().set_cursor_size(self, size)
     }
 
+    /// Restricts the cursor of this seat to the given outputs.
+    ///
+    /// The cursor cannot leave these outputs and is moved to one of them if it is
+    /// currently on another output. Together with [`InputDevice::set_seat`], this allows
+    /// several users to work on separate outputs at the same time.
+    ///
+    /// The outputs are remembered by their connector names and the restriction applies
+    /// again when a connector is reconnected. If none of the outputs are connected, the
+    /// cursor can move across all outputs.
+    ///
+    /// An empty slice removes the restriction.
+    pub fn set_outputs(self, connectors: &[Connector]) {
+        get!().set_seat_outputs(self, connectors)
+    }
+
     /// Creates a compositor-wide hotkey.
     ///
     /// The closure is invoked when the user presses the last key of the modified keysym.
@@ -213,6 +346,8 @@ impl Seat {
     ///
     /// CapsLock and NumLock are ignored during modifier evaluation. Therefore, bindings
     /// containing these modifiers will never be invoked.
+    ///
+    /// The hotkey belongs to the binding mode [`DEFAULT_BINDING_MODE`].
     pub fn bind<T: Into<ModifiedKeySym>, F: FnMut() + 'static>(self, mod_sym: T, f: F) {
         self.bind_masked(Modifiers(!0), mod_sym, f)
     }
@@ -257,6 +392,109 @@ impl Seat {
         get!().unbind(self, mod_sym.into())
     }
 
+    /// Creates a hotkey that is only active while the seat is in the binding mode `mode`.
+    ///
+    /// See [`Seat::set_binding_mode`].
+    pub fn bind_in_mode<T: Into<ModifiedKeySym>, F: FnMut() + 'static>(
+        self,
+        mode: &str,
+        mod_sym: T,
+        f: F,
+    ) {
+        self.bind_masked_in_mode(mode, Modifiers(!0), mod_sym, f)
+    }
+
+    /// Creates a hotkey in a binding mode while ignoring some modifiers.
+    ///
+    /// See [`Seat::bind_masked`] and [`Seat::bind_in_mode`].
+    pub fn bind_masked_in_mode<T: Into<ModifiedKeySym>, F: FnMut() + 'static>(
+        self,
+        mode: &str,
+        mod_mask: Modifiers,
+        mod_sym: T,
+        f: F,
+    ) {
+        get!().bind_masked_in_mode(self, mode, mod_mask, mod_sym.into(), f)
+    }
+
+    /// Unbinds a hotkey from a binding mode.
+    pub fn unbind_in_mode<T: Into<ModifiedKeySym>>(self, mode: &str, mod_sym: T) {
+        get!().unbind_in_mode(self, mode, mod_sym.into())
+    }
+
+    /// Switches the seat to a binding mode.
+    ///
+    /// While the seat is in a binding mode, only the hotkeys of that mode are active.
+    /// Use [`DEFAULT_BINDING_MODE`] to return to the hotkeys created with [`Seat::bind`].
+    /// A mode without hotkeys forwards all keys to applications, so every mode should
+    /// contain a hotkey that switches back.
+    ///
+    /// Shortcut sequences, pointer bindings, and gestures are not affected by binding
+    /// modes.
+    ///
+    /// Privileged clients such as bars are notified when the mode changes.
+    pub fn set_binding_mode(self, mode: &str) {
+        get!().set_binding_mode(self, mode)
+    }
+
+    /// Returns the current binding mode of the seat.
+    pub fn binding_mode(self) -> String {
+        get!(DEFAULT_BINDING_MODE.to_string()).binding_mode(self)
+    }
+
+    /// Creates a hotkey that is invoked when the keys are pressed one after another.
+    ///
+    /// For example, `bind_sequence([MOD4 | SYM_a, SYM_1], f)` invokes the closure when
+    /// the user presses `logo+a` followed by `1`. Modifiers must match exactly.
+    ///
+    /// While a sequence is pending, an overlay at the bottom of the output shows the
+    /// keys that continue the sequence. Pressing any other key or waiting for the
+    /// timeout cancels the sequence. Keys that are part of a pending sequence are not
+    /// forwarded to applications.
+    ///
+    /// If the first key of a sequence is also bound with [`Seat::bind`], the sequence
+    /// takes precedence. If one sequence is a prefix of another, the shorter sequence
+    /// is invoked.
+    ///
+    /// See also [`Seat::set_shortcut_sequence_timeout`].
+    pub fn bind_sequence<T, F>(self, keys: impl IntoIterator<Item = T>, f: F)
+    where
+        T: Into<ModifiedKeySym>,
+        F: FnMut() + 'static,
+    {
+        let keys = keys.into_iter().map(|k| k.into()).collect();
+        get!().bind_sequence(self, keys, f)
+    }
+
+    /// Unbinds a hotkey sequence.
+    pub fn unbind_sequence<T: Into<ModifiedKeySym>>(self, keys: impl IntoIterator<Item = T>) {
+        let keys = keys.into_iter().map(|k| k.into()).collect();
+        get!().unbind_sequence(self, keys)
+    }
+
+    /// Sets the time after which a pending hotkey sequence is cancelled.
+    ///
+    /// The timeout is restarted whenever a key of the sequence is pressed.
+    ///
+    /// The default is 2 seconds.
+    pub fn set_shortcut_sequence_timeout(self, timeout: Duration) {
+        get!().set_shortcut_sequence_timeout(self, timeout)
+    }
+
+    /// Sets the key that releases the keyboard when an application inhibits shortcuts.
+    ///
+    /// Applications such as virtual machines can ask the compositor to forward all keys
+    /// to them instead of invoking shortcuts. While this is the case, an indicator is
+    /// shown at the top of the output. Pressing the break-out key ends the inhibition
+    /// until the application is focused again and also releases keyboard grabs.
+    ///
+    /// The break-out key is never forwarded to applications.
+    ///
+    /// The default is `MOD4 | SYM_Escape`.
+    pub fn set_break_out_key<T: Into<ModifiedKeySym>>(self, key: T) {
+        get!().set_break_out_key(self, key.into())
+    }
+
     /// Moves the keyboard focus of the seat in the specified direction.
     pub fn focus(self, direction: Direction) {
         get!().focus(self, direction)
@@ -268,6 +506,9 @@ impl Seat {
     }
 
     /// Sets the keymap of the seat.
+    ///
+    /// The keymap is sent to all clients. Keys that are pressed while the keymap changes
+    /// stay pressed.
     pub fn set_keymap(self, keymap: Keymap) {
         get!().seat_set_keymap(self, keymap)
     }
@@ -285,6 +526,25 @@ impl Seat {
         get!().seat_set_repeat_rate(self, rate, delay)
     }
 
+    /// Overrides the state of keyboard LEDs.
+    ///
+    /// By default, the LEDs of all keyboards attached to this seat follow the keyboard
+    /// state, e.g. the Caps Lock LED is on while Caps Lock is active.
+    ///
+    /// If `enabled` is `Some`, the LEDs in `leds` are forced on or off. This can be used
+    /// to turn the Scroll Lock LED into a user-controlled indicator. If `enabled` is
+    /// `None`, the LEDs in `leds` follow the keyboard state again.
+    pub fn set_led_override(self, leds: Leds, enabled: Option<bool>) {
+        get!().seat_set_led_override(self, leds, enabled)
+    }
+
+    /// Returns the LEDs currently shown by keyboards that use the seat keymap.
+    ///
+    /// This includes overrides set via [`Seat::set_led_override`].
+    pub fn leds(self) -> Leds {
+        get!(Leds::NONE).seat_get_leds(self)
+    }
+
     /// Returns whether the parent-container of the currently focused window is in mono-mode.
     pub fn mono(self) -> bool {
         get!(false).mono(self)
@@ -335,6 +595,52 @@ impl Seat {
         get!().close(self);
     }
 
+    /// Offers to kill the process of the currently focused window.
+    ///
+    /// This shows a prompt with the PID and executable of the process. If the user
+    /// confirms with `y`, the process is sent SIGTERM and, if it is still running after a
+    /// few seconds, SIGKILL. `n` or `Escape` cancel the prompt.
+    ///
+    /// This is useful to get rid of an unresponsive fullscreen application that has
+    /// grabbed the pointer.
+    pub fn force_quit(self) {
+        get!().force_quit(self);
+    }
+
+    /// Adds a mark to the currently focused window.
+    ///
+    /// Marks are unique. If another window has this mark, the mark is removed from that
+    /// window. A window can have multiple marks.
+    pub fn mark(self, mark: &str) {
+        get!().mark(self, mark);
+    }
+
+    /// Removes all marks from the currently focused window.
+    pub fn unmark(self) {
+        get!().unmark(self);
+    }
+
+    /// Focuses the window with the mark.
+    ///
+    /// If the window is on a workspace that is not currently shown, the workspace is
+    /// shown.
+    pub fn focus_mark(self, mark: &str) {
+        get!().focus_mark(self, mark);
+    }
+
+    /// Swaps the positions of the currently focused window and the window with the mark.
+    pub fn swap_with_mark(self, mark: &str) {
+        get!().swap_with_mark(self, mark);
+    }
+
+    /// Moves the currently focused window next to the window with the mark.
+    ///
+    /// If the window with the mark is floating, the focused window is tiled on the
+    /// workspace of that window instead.
+    pub fn move_to_mark(self, mark: &str) {
+        get!().move_to_mark(self, mark);
+    }
+
     /// Returns whether the currently focused window is floating.
     pub fn get_floating(self) -> bool {
         get!().get_floating(self)
@@ -367,6 +673,15 @@ impl Seat {
         get!().show_workspace(self, workspace)
     }
 
+    /// Shows the workspace that was shown before the current workspace on the output that
+    /// contains the seat's cursor.
+    ///
+    /// If that workspace no longer exists, it is created again. If there is no previous
+    /// workspace, this has no effect.
+    pub fn show_previous_workspace(self) {
+        get!().show_previous_workspace(self)
+    }
+
     /// Moves the currently focused window to the workspace.
     pub fn set_workspace(self, workspace: Workspace) {
         get!().set_workspace(self, workspace)
@@ -422,6 +737,122 @@ impl Seat {
         get!().set_focus_follows_mouse_mode(self, mode);
     }
 
+    /// Enables or disables the coalescing of pointer motion events on this seat.
+    ///
+    /// If enabled, clients receive at most one motion event per refresh cycle of the
+    /// output that contains the focused surface. Further motion events in the same cycle
+    /// are merged and sent at the end of the cycle or before the next button or axis
+    /// event. This reduces the CPU usage of clients when using high-frequency mice.
+    ///
+    /// The default is `false`.
+    pub fn set_pointer_motion_coalescing(self, enabled: bool) {
+        get!().set_pointer_motion_coalescing(self, enabled);
+    }
+
+    /// Enables or disables compositor touch gestures on this seat.
+    ///
+    /// If enabled, the compositor recognizes swipes with three or more fingers and
+    /// single-finger swipes starting at the edge of an output. Touch events are held
+    /// back from clients while the touch sequence might still become a gesture. If it
+    /// does not, the held events are delivered. A recognized gesture is reported via
+    /// [`Seat::on_touch_gesture`] when all fingers have been lifted. Moving the fingers
+    /// back towards their starting point before lifting them cancels the gesture.
+    ///
+    /// If a gesture is recognized after the events have already been delivered, for
+    /// example because a third finger was added later, the touch sequence is cancelled
+    /// for the client.
+    ///
+    /// The default is `false`.
+    pub fn set_touch_gestures_enabled(self, enabled: bool) {
+        get!().set_touch_gestures_enabled(self, enabled);
+    }
+
+    /// Sets a callback that will be run when a touch gesture is completed on this seat.
+    ///
+    /// See [`Seat::set_touch_gestures_enabled`].
+    pub fn on_touch_gesture<F: FnMut(TouchGesture) + 'static>(self, f: F) {
+        get!().on_touch_gesture(self, f)
+    }
+
+    /// Binds a touchpad gesture to a callback.
+    ///
+    /// The callback is invoked when the gesture ends without being cancelled. For
+    /// swipes and pinches, the direction is determined once the fingers have moved far
+    /// enough. If no binding exists for that direction, the gesture is forwarded to
+    /// applications as usual.
+    ///
+    /// See also [`Seat::set_gesture_passthrough`].
+    pub fn bind_gesture<F: FnMut() + 'static>(self, gesture: PointerGesture, f: F) {
+        get!().bind_gesture(self, gesture, f)
+    }
+
+    /// Unbinds a touchpad gesture.
+    pub fn unbind_gesture(self, gesture: PointerGesture) {
+        get!().unbind_gesture(self, gesture)
+    }
+
+    /// Sets whether bound gestures are forwarded to windows with the given app-id.
+    ///
+    /// If enabled, gestures that start over such a window are forwarded to the window
+    /// and no callbacks are invoked.
+    ///
+    /// The default is `false` for all app-ids.
+    pub fn set_gesture_passthrough(self, app_id: &str, passthrough: bool) {
+        get!().set_gesture_passthrough(self, app_id, passthrough)
+    }
+
+    /// Saves a screenshot of the focused window as a PNG file.
+    ///
+    /// See [`set_window_screenshot_directory`](crate::set_window_screenshot_directory)
+    /// and [`on_window_screenshot_saved`](crate::on_window_screenshot_saved).
+    pub fn screenshot_window(self) {
+        get!().seat_screenshot_window(self)
+    }
+
+    /// Changes the backlight brightness of the output that contains the cursor.
+    ///
+    /// See [`Connector::change_brightness`](crate::video::Connector::change_brightness).
+    pub fn change_brightness(self, delta: f64) {
+        get!().seat_change_brightness(self, delta)
+    }
+
+    /// Sets the number of fingers of touchpad swipes that switch workspaces.
+    ///
+    /// While such a swipe is in progress, the workspace on the output under the cursor
+    /// follows the fingers horizontally and the adjacent workspace slides into view.
+    /// When the fingers are lifted, the adjacent workspace is shown if the swipe
+    /// covered more than a quarter of the output. Otherwise the current workspace snaps
+    /// back.
+    ///
+    /// Gestures bound via [`Seat::bind_gesture`] take precedence.
+    ///
+    /// The default is `0`, which disables workspace swipes.
+    pub fn set_workspace_swipe_fingers(self, fingers: u32) {
+        get!().set_workspace_swipe_fingers(self, fingers)
+    }
+
+    /// Binds a pointer button or scroll direction on a part of the desktop to a callback.
+    ///
+    /// While an input is bound for a target, the built-in behavior for that input on the
+    /// target is disabled. Buttons are invoked when they are pressed. Scroll bindings
+    /// are invoked once per scroll step.
+    ///
+    /// Bindings on [`PointerTarget::Title`] and [`PointerTarget::Border`] first focus
+    /// the window whose title or border was clicked.
+    pub fn bind_pointer<F: FnMut() + 'static>(
+        self,
+        target: PointerTarget,
+        input: PointerInput,
+        f: F,
+    ) {
+        get!().bind_pointer(self, target, input, f)
+    }
+
+    /// Unbinds a pointer binding.
+    pub fn unbind_pointer(self, target: PointerTarget, input: PointerInput) {
+        get!().unbind_pointer(self, target, input)
+    }
+
     /// Enables or disable window management mode.
     ///
     /// In window management mode, floating windows can be moved by pressing the left
@@ -464,6 +895,91 @@ pub enum FocusFollowsMouseMode {
     False,
 }
 
+/// A touch gesture recognized by the compositor.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum TouchGesture {
+    /// A swipe with three or more fingers.
+    Swipe {
+        /// The number of fingers.
+        fingers: u32,
+        /// The direction in which the fingers moved.
+        direction: Direction,
+    },
+    /// A single-finger swipe that started at an edge of an output and moved towards
+    /// the center of the output.
+    EdgeSwipe {
+        /// The edge at which the swipe started.
+        edge: Direction,
+    },
+}
+
+/// A touchpad gesture that can be bound via [`Seat::bind_gesture`].
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum PointerGesture {
+    /// A swipe.
+    Swipe {
+        /// The number of fingers.
+        fingers: u32,
+        /// The direction in which the fingers moved.
+        direction: Direction,
+    },
+    /// A pinch where the fingers moved towards each other.
+    PinchIn {
+        /// The number of fingers.
+        fingers: u32,
+    },
+    /// A pinch where the fingers moved away from each other.
+    PinchOut {
+        /// The number of fingers.
+        fingers: u32,
+    },
+    /// A hold.
+    Hold {
+        /// The number of fingers.
+        fingers: u32,
+    },
+}
+
+/// A part of the desktop that pointer bindings can be attached to.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum PointerTarget {
+    /// The area of a workspace that is not covered by any window.
+    Desktop,
+    /// The title bar of a window.
+    Title,
+    /// The border of a window.
+    ///
+    /// This includes the gaps between tiled windows and the frame of floating windows.
+    Border,
+}
+
+/// A pointer input that can be bound via [`Seat::bind_pointer`].
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum PointerInput {
+    /// A button press. The button is identified by its evdev code, e.g. [`BTN_LEFT`].
+    Button(u32),
+    /// A scroll step upwards.
+    ScrollUp,
+    /// A scroll step downwards.
+    ScrollDown,
+}
+
+/// The evdev code of the left mouse button.
+pub const BTN_LEFT: u32 = 0x110;
+/// The evdev code of the right mouse button.
+pub const BTN_RIGHT: u32 = 0x111;
+/// The evdev code of the middle mouse button.
+pub const BTN_MIDDLE: u32 = 0x112;
+/// The evdev code of the side mouse button.
+pub const BTN_SIDE: u32 = 0x113;
+/// The evdev code of the extra mouse button.
+pub const BTN_EXTRA: u32 = 0x114;
+
+/// The name of the binding mode that is active when the compositor starts.
+///
+/// Hotkeys created with [`Seat::bind`] belong to this mode.
+pub const DEFAULT_BINDING_MODE: &str = "default";
+
 /// Returns all seats.
 pub fn get_seats() -> Vec<Seat> {
     get!().seats()
@@ -573,3 +1089,37 @@ pub enum SwitchEvent {
 pub fn set_libei_socket_enabled(enabled: bool) {
     get!().set_ei_socket_enabled(enabled);
 }
+
+/// Enables or disables the prompt that is shown when an application connects to the
+/// unauthenticated libei socket.
+///
+/// The prompt identifies the application and lets the user allow the connection once,
+/// allow the executable permanently, or deny the connection. If the prompt is disabled,
+/// all applications are allowed to connect.
+///
+/// The default is `true`.
+pub fn set_libei_authorization_prompt_enabled(enabled: bool) {
+    get!().set_ei_authorization_prompt(enabled);
+}
+
+/// Returns the executables that have permanently been allowed to connect to the
+/// unauthenticated libei socket.
+pub fn libei_permissions() -> Vec<String> {
+    get!().ei_permissions()
+}
+
+/// Revokes the permanent permission of an executable to connect to the unauthenticated
+/// libei socket.
+///
+/// Existing connections are not affected.
+pub fn revoke_libei_permission(executable: &str) {
+    get!().revoke_ei_permission(executable);
+}
+
+/// Sets the key remapping table of all input devices.
+///
+/// See [`InputDevice::set_key_remap`] for the format. Devices with their own table use
+/// this table for keys that their table does not contain.
+pub fn set_key_remap(remap: &[(u32, u32)]) {
+    get!().set_key_remap(remap);
+}
@@ -0,0 +1,41 @@
+//! Constants specifying keyboard LEDs.
+
+use {
+    serde::{Deserialize, Serialize},
+    std::ops::{BitOr, BitOrAssign},
+};
+
+/// Zero or more keyboard LEDs.
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Default, Hash, Debug)]
+pub struct Leds(pub u32);
+
+impl Leds {
+    /// No LEDs.
+    pub const NONE: Self = Leds(0);
+
+    /// Returns whether all LEDs in `other` are also contained in `self`.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+/// The Num Lock LED.
+pub const LED_NUM_LOCK: Leds = Leds(1 << 0);
+/// The Caps Lock LED.
+pub const LED_CAPS_LOCK: Leds = Leds(1 << 1);
+/// The Scroll Lock LED.
+pub const LED_SCROLL_LOCK: Leds = Leds(1 << 2);
+
+impl BitOr for Leds {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Leds {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
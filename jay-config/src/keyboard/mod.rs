@@ -109,3 +109,40 @@ impl Keymap {
 pub fn parse_keymap(keymap: &str) -> Keymap {
     get!(Keymap::INVALID).parse_keymap(keymap)
 }
+
+/// The RMLVO names of a keymap.
+///
+/// These are the names used by `setxkbmap` and most other desktop environments.
+/// Layouts, variants, and options are comma-separated lists, for example
+/// `layout: Some("us,de")` and `options: Some("grp:alt_shift_toggle,caps:escape")`.
+/// The compose key is configured with one of the `compose:` options, for example
+/// `compose:ralt`.
+///
+/// Names that are `None` are taken from the `XKB_DEFAULT_*` environment variables or use
+/// the default values of libxkbcommon.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct KeymapNames<'a> {
+    pub rules: Option<&'a str>,
+    pub model: Option<&'a str>,
+    pub layout: Option<&'a str>,
+    pub variant: Option<&'a str>,
+    pub options: Option<&'a str>,
+}
+
+/// Creates a keymap from RMLVO names.
+///
+/// Like [`parse_keymap`], this returns an invalid keymap if the keymap cannot be
+/// created.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let keymap = keymap_from_names(&KeymapNames {
+///     layout: Some("de"),
+///     variant: Some("nodeadkeys"),
+///     ..Default::default()
+/// });
+/// ```
+pub fn keymap_from_names(names: &KeymapNames<'_>) -> Keymap {
+    get!(Keymap::INVALID).keymap_from_names(names)
+}
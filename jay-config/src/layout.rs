@@ -0,0 +1,272 @@
+//! Tools for implementing custom tiling layouts.
+//!
+//! A layout is a function that receives the windows of a workspace and returns their
+//! geometries. Layouts are assigned to workspaces with
+//! [Workspace::set_layout](crate::Workspace::set_layout).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! // A master-stack layout.
+//! let layout = create_layout(|req| {
+//!     let n = req.windows.len() as i32;
+//!     if n == 1 {
+//!         return vec![LayoutGeometry::new(0, 0, req.width, req.height)];
+//!     }
+//!     let master_width = req.width / 2;
+//!     let stack_height = req.height / (n - 1);
+//!     let mut res = vec![LayoutGeometry::new(0, 0, master_width, req.height)];
+//!     for i in 0..n - 1 {
+//!         res.push(LayoutGeometry::new(
+//!             master_width,
+//!             i * stack_height,
+//!             req.width - master_width,
+//!             stack_height,
+//!         ));
+//!     }
+//!     res
+//! });
+//! get_workspace("1").set_layout(Some(layout));
+//! ```
+//!
+//! A dwm-style master-stack layout is available via [create_master_stack_layout].
+
+use {
+    crate::theme::sized::BORDER_WIDTH,
+    serde::{Deserialize, Serialize},
+    std::{cell::Cell, rc::Rc},
+};
+
+/// A custom layout.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct Layout(pub u64);
+
+impl Layout {
+    /// Destroys the layout.
+    ///
+    /// Workspaces that use this layout fall back to the built-in layout.
+    pub fn destroy(self) {
+        get!().destroy_layout(self);
+    }
+
+    /// Recomputes the geometries of all workspaces that use this layout.
+    ///
+    /// This should be called when the layout depends on state that has changed.
+    pub fn relayout(self) {
+        get!().relayout(self);
+    }
+}
+
+/// A window that is managed by a layout.
+///
+/// The identifier stays the same as long as the window is part of the layout.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct LayoutWindow(pub u64);
+
+/// The input of a layout.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct LayoutRequest {
+    /// The width of the area available to the layout.
+    pub width: i32,
+    /// The height of the area available to the layout.
+    pub height: i32,
+    /// The windows in the layout in the order in which they were added.
+    pub windows: Vec<LayoutWindow>,
+    /// The windows that were added since the last time the layout was computed.
+    pub added: Vec<LayoutWindow>,
+    /// The windows that were removed since the last time the layout was computed.
+    pub removed: Vec<LayoutWindow>,
+}
+
+/// The geometry of a window in a layout.
+///
+/// The geometry is relative to the top-left corner of the area available to the layout
+/// and includes the title of the window.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub struct LayoutGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl LayoutGeometry {
+    /// Creates a new geometry.
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+/// Creates a new layout.
+///
+/// The callback is invoked whenever windows are added to or removed from a workspace
+/// that uses the layout and whenever the size of the workspace changes. It must return
+/// one geometry per window in the order of [LayoutRequest::windows]. The geometries of
+/// all windows are applied at the same time.
+///
+/// If the callback returns the wrong number of geometries, the built-in layout is used
+/// instead.
+pub fn create_layout<F: FnMut(&LayoutRequest) -> Vec<LayoutGeometry> + 'static>(f: F) -> Layout {
+    get!(Layout(0)).create_layout(f)
+}
+
+/// The parameters of a master-stack layout.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MasterStackParams {
+    /// The fraction of the width that is used by the master area.
+    ///
+    /// The value is clamped to the range `0.05..=0.95`.
+    ///
+    /// Default: 0.55
+    pub master_ratio: f64,
+    /// The number of windows in the master area.
+    ///
+    /// Default: 1
+    pub master_count: usize,
+}
+
+impl Default for MasterStackParams {
+    fn default() -> Self {
+        Self {
+            master_ratio: 0.55,
+            master_count: 1,
+        }
+    }
+}
+
+impl MasterStackParams {
+    /// Computes the geometries of the windows.
+    ///
+    /// The first `master_count` windows are stacked vertically in the master area on the
+    /// left. The remaining windows are stacked vertically on the right. If either area is
+    /// empty, the other area uses the full width. `gap` is the space between windows.
+    pub fn geometries(&self, req: &LayoutRequest, gap: i32) -> Vec<LayoutGeometry> {
+        let num_windows = req.windows.len();
+        let num_masters = self.master_count.min(num_windows);
+        let num_stack = num_windows - num_masters;
+        let (master_width, stack_x) = if num_masters == 0 {
+            (0, 0)
+        } else if num_stack == 0 {
+            (req.width, req.width)
+        } else {
+            let ratio = self.master_ratio.clamp(0.05, 0.95);
+            let width = ((req.width - gap).max(0) as f64 * ratio).round() as i32;
+            (width, width + gap)
+        };
+        let mut res = Vec::with_capacity(num_windows);
+        push_column(&mut res, 0, master_width, req.height, num_masters, gap);
+        push_column(
+            &mut res,
+            stack_x,
+            req.width - stack_x,
+            req.height,
+            num_stack,
+            gap,
+        );
+        res
+    }
+}
+
+fn push_column(
+    res: &mut Vec<LayoutGeometry>,
+    x: i32,
+    width: i32,
+    height: i32,
+    num: usize,
+    gap: i32,
+) {
+    if num == 0 {
+        return;
+    }
+    let num = num as i32;
+    let available = (height - (num - 1) * gap).max(0);
+    for i in 0..num {
+        let y1 = available * i / num;
+        let y2 = available * (i + 1) / num;
+        res.push(LayoutGeometry::new(x, y1 + i * gap, width, y2 - y1));
+    }
+}
+
+/// A dwm-style master-stack layout.
+///
+/// Changing the parameters recomputes the geometries of all workspaces that use the
+/// layout.
+#[derive(Clone, Debug)]
+pub struct MasterStack {
+    layout: Layout,
+    params: Rc<Cell<MasterStackParams>>,
+}
+
+impl MasterStack {
+    /// Returns the layout that can be assigned to workspaces.
+    pub fn layout(&self) -> Layout {
+        self.layout
+    }
+
+    /// Returns the current parameters.
+    pub fn params(&self) -> MasterStackParams {
+        self.params.get()
+    }
+
+    /// Sets the parameters.
+    pub fn set_params(&self, mut params: MasterStackParams) {
+        params.master_ratio = params.master_ratio.clamp(0.05, 0.95);
+        if self.params.replace(params) != params {
+            self.layout.relayout();
+        }
+    }
+
+    /// Sets the fraction of the width that is used by the master area.
+    pub fn set_master_ratio(&self, ratio: f64) {
+        self.set_params(MasterStackParams {
+            master_ratio: ratio,
+            ..self.params()
+        });
+    }
+
+    /// Adds `delta` to the fraction of the width that is used by the master area.
+    pub fn adjust_master_ratio(&self, delta: f64) {
+        self.set_master_ratio(self.params().master_ratio + delta);
+    }
+
+    /// Sets the number of windows in the master area.
+    pub fn set_master_count(&self, count: usize) {
+        self.set_params(MasterStackParams {
+            master_count: count,
+            ..self.params()
+        });
+    }
+
+    /// Adds `delta` to the number of windows in the master area.
+    pub fn adjust_master_count(&self, delta: i32) {
+        let count = self.params().master_count as i64 + delta as i64;
+        self.set_master_count(count.max(0) as usize);
+    }
+}
+
+/// Creates a new master-stack layout with the default parameters.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let ms = create_master_stack_layout();
+/// get_workspace("1").set_layout(Some(ms.layout()));
+/// {
+///     let ms = ms.clone();
+///     seat.bind(MOD | SYM_l, move || ms.adjust_master_ratio(0.05));
+/// }
+/// seat.bind(MOD | SYM_i, move || ms.adjust_master_count(1));
+/// ```
+pub fn create_master_stack_layout() -> MasterStack {
+    let params = Rc::new(Cell::new(MasterStackParams::default()));
+    let layout = {
+        let params = params.clone();
+        create_layout(move |req| params.get().geometries(req, BORDER_WIDTH.get()))
+    };
+    MasterStack { layout, params }
+}
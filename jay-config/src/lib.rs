@@ -44,7 +44,9 @@
 #![warn(unsafe_op_in_unsafe_fn)]
 
 use {
-    crate::{_private::ipc::WorkspaceSource, keyboard::ModifiedKeySym, video::Connector},
+    crate::{
+        _private::ipc::WorkspaceSource, keyboard::ModifiedKeySym, layout::Layout, video::Connector,
+    },
     serde::{Deserialize, Serialize},
     std::{
         fmt::{Debug, Display, Formatter},
@@ -61,7 +63,9 @@ pub mod exec;
 pub mod input;
 pub mod io;
 pub mod keyboard;
+pub mod layout;
 pub mod logging;
+pub mod notifications;
 pub mod status;
 pub mod tasks;
 pub mod theme;
@@ -70,7 +74,7 @@ pub mod video;
 pub mod xwayland;
 
 /// A planar direction.
-#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub enum Direction {
     Left,
     Down,
@@ -137,6 +141,89 @@ pub fn toggle_default_workspace_capture() {
     get.set_default_workspace_capture(!get.get_default_workspace_capture());
 }
 
+/// Sets whether the input and opaque regions of surfaces are highlighted.
+///
+/// Input regions are drawn in green and opaque regions in blue. This is a debugging aid
+/// that helps to find out why clicks go to an unexpected window.
+///
+/// The default is `false`.
+pub fn set_show_surface_regions(show: bool) {
+    get!().set_show_surface_regions(show)
+}
+
+/// Toggles whether the input and opaque regions of surfaces are highlighted.
+pub fn toggle_show_surface_regions() {
+    get!().toggle_show_surface_regions()
+}
+
+/// Sets whether the compositor reduces its work during interactive resizes.
+///
+/// If this is enabled, then, while a window is being resized with the mouse, windows
+/// only receive a new size after they have committed the previous one and damage from
+/// other windows is deferred until the resize ends. This can make resizes smoother on
+/// weak GPUs.
+///
+/// The default is `false`.
+pub fn set_throttle_interactive_resize(enabled: bool) {
+    get!().set_throttle_interactive_resize(enabled)
+}
+
+/// What happens to a workspace when its last window is closed.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum EmptyWorkspaceBehavior {
+    /// The empty workspace is kept until another workspace is shown on its output.
+    Keep,
+    /// The empty workspace is destroyed immediately.
+    ///
+    /// If the workspace was being shown, the output shows no workspace until a new window
+    /// is opened on it or another workspace is shown.
+    Destroy,
+    /// If the workspace was being shown, the output switches to the workspace that was
+    /// shown before and the empty workspace is destroyed.
+    ///
+    /// If there is no such workspace, the empty workspace is kept.
+    ShowPrevious,
+}
+
+/// Sets what happens to a workspace when its last window is closed.
+///
+/// The default is [`EmptyWorkspaceBehavior::Keep`].
+pub fn set_empty_workspace_behavior(behavior: EmptyWorkspaceBehavior) {
+    get!().set_empty_workspace_behavior(behavior)
+}
+
+/// Enables or disables workspace back-and-forth.
+///
+/// If this is enabled, then [`Seat::show_workspace`](input::Seat::show_workspace) with
+/// the workspace that is currently shown switches to the previously shown workspace
+/// instead.
+///
+/// The default is `false`.
+pub fn set_workspace_back_and_forth(enabled: bool) {
+    get!().set_workspace_back_and_forth(enabled)
+}
+
+/// Sets whether the marks of windows are shown in their title bars.
+///
+/// See [`Seat::mark`](input::Seat::mark).
+///
+/// The default is `false`.
+pub fn set_show_marks(show: bool) {
+    get!().set_show_marks(show)
+}
+
+/// Enables or disables autotiling.
+///
+/// If this is enabled, new tiled windows are placed next to the focused window in a split
+/// whose direction depends on the aspect ratio of the focused window. Windows that are
+/// wider than they are tall are split horizontally, other windows are split vertically.
+/// This produces a spiral layout.
+///
+/// The default is `false`.
+pub fn set_autotiling(enabled: bool) {
+    get!().set_autotiling(enabled)
+}
+
 /// A workspace.
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub struct Workspace(pub u64);
@@ -171,6 +258,59 @@ impl Workspace {
     pub fn move_to_output(self, output: Connector) {
         get!().move_to_output(WorkspaceSource::Explicit(self), output);
     }
+
+    /// Sets the layout of this workspace.
+    ///
+    /// If `layout` is `None`, the workspace uses the built-in layout. The setting is
+    /// remembered even if the workspace does not currently exist.
+    pub fn set_layout(self, layout: Option<Layout>) {
+        get!().set_workspace_layout(self, layout)
+    }
+
+    /// Sets the gap between tiled windows on this workspace.
+    ///
+    /// If `gap` is `None`, the workspace uses the global [`INNER_GAP`](theme::sized::INNER_GAP).
+    /// The setting is remembered even if the workspace does not currently exist.
+    pub fn set_inner_gap(self, gap: Option<i32>) {
+        get!().set_workspace_inner_gap(self, gap)
+    }
+
+    /// Sets the gap between tiled windows and the edges of the output on this workspace.
+    ///
+    /// If `gap` is `None`, the workspace uses the global [`OUTER_GAP`](theme::sized::OUTER_GAP).
+    /// The setting is remembered even if the workspace does not currently exist.
+    pub fn set_outer_gap(self, gap: Option<i32>) {
+        get!().set_workspace_outer_gap(self, gap)
+    }
+
+    /// Renames this workspace.
+    ///
+    /// Afterwards, this handle refers to the new name. The layout of the workspace and the
+    /// applications assigned to it with [`set_app_workspace`] move to the new name as well.
+    ///
+    /// This has no effect if a workspace with the new name already exists.
+    pub fn rename(self, name: &str) {
+        get!().rename_workspace(self, name)
+    }
+
+    /// Creates this workspace on an output.
+    ///
+    /// The workspace is only shown if the output does not show any other workspace. This
+    /// has no effect if the workspace already exists.
+    pub fn create(self, output: Connector) {
+        get!().create_workspace(self, output)
+    }
+}
+
+/// Assigns an application to a workspace.
+///
+/// New windows whose app-id is `app_id` are opened on the workspace. If the workspace
+/// does not exist, it is created on the output that a new window would otherwise be
+/// opened on. If `workspace` is `None`, the assignment is removed.
+///
+/// For X windows, the app-id is the class of the window.
+pub fn set_app_workspace(app_id: &str, workspace: Option<Workspace>) {
+    get!().set_app_workspace(app_id, workspace)
 }
 
 /// Returns the workspace with the given name.
@@ -203,6 +343,28 @@ pub fn on_idle<F: FnMut() + 'static>(f: F) {
     get!().on_idle(f)
 }
 
+/// Sets the callback to be called when the session gets locked.
+///
+/// This can be used to start additional unlock helpers, e.g. a fingerprint reader that
+/// runs `jay unlock` after a successful verification.
+pub fn on_lock<F: FnMut() + 'static>(f: F) {
+    get!().on_lock(f)
+}
+
+/// Sets the callback to be called when the session gets unlocked.
+pub fn on_unlock<F: FnMut() + 'static>(f: F) {
+    get!().on_unlock(f)
+}
+
+/// Sets the callback to be called when the screen locker exits while the session is
+/// locked.
+///
+/// The session stays locked. This can be used to respawn the screen locker, which then
+/// takes over the existing lock.
+pub fn on_locker_crashed<F: FnMut() + 'static>(f: F) {
+    get!().on_locker_crashed(f)
+}
+
 /// Sets the callback to be called when all devices have been enumerated.
 ///
 /// This callback is only invoked once during the lifetime of the compositor. This is a
@@ -242,6 +404,39 @@ pub fn set_idle_grace_period(timeout: Duration) {
     get!().set_idle_grace_period(timeout)
 }
 
+/// Configures whether playing audio inhibits idle.
+///
+/// If this is enabled, Jay connects to PipeWire and inhibits idle while an audio output
+/// stream is running. If `roles` is not empty, only streams whose `media.role` is one of
+/// the given roles, e.g. `Music` or `Movie`, inhibit idle.
+///
+/// The default is `false`.
+pub fn set_idle_inhibit_on_audio(enabled: bool, roles: Vec<String>) {
+    get!().set_idle_inhibit_on_audio(enabled, roles)
+}
+
+/// Configures the lock grace period.
+///
+/// During the grace period after the session has been locked, pressing a key or button or
+/// touching a touchscreen unlocks the session without authentication.
+///
+/// The default is 0, i.e. no grace period.
+pub fn set_lock_grace_period(period: Duration) {
+    get!().set_lock_grace_period(period)
+}
+
+/// Configures whether the built-in lock screen asks for the password.
+///
+/// If the screen locker exits while the session is locked, Jay shows a built-in lock
+/// screen. If this is enabled, the session can be unlocked by typing the password of the
+/// user. Otherwise, the outputs are filled with red until another screen locker takes
+/// over or the session is unlocked with `jay unlock`.
+///
+/// The default is `true`.
+pub fn set_fallback_lock_screen_prompt(enabled: bool) {
+    get!().set_fallback_lock_screen_prompt(enabled)
+}
+
 /// Enables or disables explicit sync.
 ///
 /// Calling this after the compositor has started has no effect.
@@ -251,6 +446,36 @@ pub fn set_explicit_sync_enabled(enabled: bool) {
     get!().set_explicit_sync_enabled(enabled);
 }
 
+/// Forces clients to use implicit sync even if they use explicit sync.
+///
+/// This is a debugging tool for driver bugs that cause freezes or corruption with
+/// explicit sync. While this is enabled, the compositor ignores acquire points of
+/// surface commits and instead waits for the implicit fences of the buffers. Release
+/// points are still signaled.
+///
+/// Unlike [`set_explicit_sync_enabled`], this can be changed at runtime and affects
+/// all subsequent commits.
+///
+/// The default is `false`.
+pub fn set_force_implicit_sync(force: bool) {
+    get!().set_force_implicit_sync(force);
+}
+
+/// Sets how long activation tokens stay valid.
+///
+/// Launchers request activation tokens and pass them to the applications they start.
+/// The application uses the token to activate its window once it has been mapped. A
+/// token that has not been used within this period can no longer be used to activate a
+/// window.
+///
+/// Privileged clients such as status bars are notified when tokens are created, used,
+/// and expired, and can use this to show launch feedback.
+///
+/// The default is 30 seconds.
+pub fn set_activation_token_lifetime(lifetime: Duration) {
+    get!().set_activation_token_lifetime(lifetime);
+}
+
 /// Enables or disables dragging of tiles and workspaces.
 ///
 /// The default is `true`.
@@ -264,3 +489,101 @@ pub fn set_ui_drag_enabled(enabled: bool) {
 pub fn set_ui_drag_threshold(threshold: i32) {
     get!().set_ui_drag_threshold(threshold);
 }
+
+/// Enables or disables remembering the state of windows across application restarts.
+///
+/// If this is enabled, the compositor records whether the last window of an app-id was
+/// floating or fullscreen and on which workspace it was. The next window with the same
+/// app-id is mapped with this state. The state is stored in
+/// `$XDG_STATE_HOME/jay/window-state.json`.
+///
+/// The default is `false`.
+pub fn set_remember_window_state(remember: bool) {
+    get!().set_remember_window_state(remember);
+}
+
+/// Sets the app-ids whose window state should not be remembered.
+///
+/// The default is an empty list.
+pub fn set_window_state_exclusions(app_ids: Vec<String>) {
+    get!().set_window_state_exclusions(app_ids);
+}
+
+/// Sets the app-ids of windows that must never be captured.
+///
+/// These windows are blacked out in screenshots, screencasts, and screencopies. This can
+/// be used to hide password managers during screen shares.
+///
+/// The default is an empty list.
+pub fn set_capture_redactions(app_ids: Vec<String>) {
+    get!().set_capture_redactions(app_ids);
+}
+
+/// Sets an overlay that is shown in screen captures but not on the outputs.
+///
+/// The text and the image are shown in the bottom-right corner of screenshots,
+/// screencasts, and screencopies. `image` is the path of a PNG file.
+///
+/// The default is no overlay.
+pub fn set_capture_overlay(text: Option<&str>, image: Option<&str>) {
+    get!().set_capture_overlay(text, image);
+}
+
+/// Sets the directory in which window screenshots are saved.
+///
+/// See [`Seat::screenshot_window`](input::Seat::screenshot_window). The directory is
+/// created if it does not exist.
+///
+/// The default is `None`, which saves screenshots in the working directory of the
+/// compositor.
+pub fn set_window_screenshot_directory(directory: Option<&str>) {
+    get!().set_window_screenshot_directory(directory);
+}
+
+/// Sets the file name template of window screenshots.
+///
+/// The template is formatted with the current local time using strftime-like
+/// specifiers.
+///
+/// The default is `%Y-%m-%d-%H%M%S_jay.png`.
+pub fn set_window_screenshot_filename(template: Option<&str>) {
+    get!().set_window_screenshot_filename(template);
+}
+
+/// Sets the callback to be called when a window screenshot has been saved.
+///
+/// The callback receives the path of the file.
+pub fn on_window_screenshot_saved<F: FnMut(String) + 'static>(f: F) {
+    get!().on_window_screenshot_saved(f)
+}
+
+/// Sets the maximum resources that can be attributed to a single Wayland client.
+///
+/// `max_fds` limits the number of file descriptors of shm pools, dmabufs, and syncobj
+/// timelines held by the client. `max_memory` limits the approximate size in bytes of
+/// the client's shm pools and dmabufs. Clients that try to exceed a limit are
+/// disconnected. Clients that already exceed a new limit are only disconnected when
+/// they allocate more resources.
+///
+/// The current usage of each client can be inspected with `jay stats`.
+///
+/// The default is `None` for both limits, which means unlimited.
+pub fn set_client_limits(max_fds: Option<u64>, max_memory: Option<u64>) {
+    get!().set_client_limits(max_fds, max_memory);
+}
+
+/// Restricts the Jay private protocols to the given executables.
+///
+/// These protocols can be used to observe input and to capture the screen. By default,
+/// every client connected to the privileged socket can use them. If an allowlist is set,
+/// such clients can only use them if the absolute path of their executable is in the
+/// list. The Jay executable itself is always allowed.
+///
+/// The allowlist is checked when a client connects. `None` removes the restriction.
+/// There is no prompt for these protocols. To grant a client access, add its executable
+/// to the allowlist.
+///
+/// The default is `None`.
+pub fn set_jay_protocol_allowlist(executables: Option<Vec<String>>) {
+    get!().set_jay_protocol_allowlist(executables);
+}
@@ -0,0 +1,79 @@
+//! Tools for configuring the built-in notification daemon.
+//!
+//! When enabled, the compositor implements the `org.freedesktop.Notifications` D-Bus
+//! service on the session bus and displays notifications as popups on the output that
+//! was most recently used.
+
+use {
+    serde::{Deserialize, Serialize},
+    std::time::Duration,
+};
+
+/// The corner of the output in which notifications are displayed.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub enum NotificationPosition {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// Enables or disables the built-in notification daemon.
+///
+/// The daemon is only started if no other application owns the
+/// `org.freedesktop.Notifications` name on the session bus.
+///
+/// The default is `false`.
+pub fn set_enabled(enabled: bool) {
+    get!().set_notifications_enabled(enabled);
+}
+
+/// Enables or disables do-not-disturb mode.
+///
+/// While this mode is enabled, only critical notifications are displayed. Enabling this
+/// mode dismisses all other notifications that are currently displayed.
+///
+/// The default is `false`.
+pub fn set_do_not_disturb(dnd: bool) {
+    get!().set_do_not_disturb(dnd);
+}
+
+/// Toggles do-not-disturb mode.
+pub fn toggle_do_not_disturb() {
+    get!().toggle_do_not_disturb();
+}
+
+/// Sets the corner of the output in which notifications are displayed.
+///
+/// The default is [`NotificationPosition::TopRight`].
+pub fn set_position(position: NotificationPosition) {
+    get!().set_notification_position(position);
+}
+
+/// Sets how long notifications are displayed if the application does not specify a
+/// timeout.
+///
+/// Critical notifications without a timeout are displayed until they are dismissed.
+///
+/// The default is 5 seconds.
+pub fn set_default_timeout(timeout: Duration) {
+    get!().set_notification_timeout(timeout);
+}
+
+/// Dismisses the most recent notification.
+pub fn dismiss() {
+    get!().dismiss_notification(false);
+}
+
+/// Dismisses all notifications.
+pub fn dismiss_all() {
+    get!().dismiss_notification(true);
+}
+
+/// Invokes the default action of the most recent notification and dismisses it.
+///
+/// If the notification does not have a `default` action, its first action is invoked.
+pub fn invoke_default_action() {
+    get!().invoke_notification_action();
+}
@@ -137,6 +137,36 @@ pub fn set_i3bar_separator(separator: &str) {
     get!().set_i3bar_separator(separator);
 }
 
+/// Sets the format of the clock displayed at the right end of the bar.
+///
+/// The format uses the [strftime][strftime] syntax, e.g. `%H:%M`. The clock is displayed
+/// after the status text. If the format is `None`, no clock is displayed.
+///
+/// The default is `None`.
+///
+/// This applies to all outputs. The setting can be overwritten on a per-connector basis
+/// with [Connector::set_bar_clock_format].
+///
+/// [Connector::set_bar_clock_format]: crate::video::Connector::set_bar_clock_format
+/// [strftime]: https://docs.rs/chrono/latest/chrono/format/strftime/index.html
+pub fn set_bar_clock_format(format: Option<&str>) {
+    get!().set_bar_clock_format(None, format);
+}
+
+/// Sets whether the title of the focused window is displayed in the bar.
+///
+/// The title is displayed after the workspaces of the output that contains the window.
+///
+/// The default is `false`.
+///
+/// This applies to all outputs. The setting can be overwritten on a per-connector basis
+/// with [Connector::set_bar_show_focused_title].
+///
+/// [Connector::set_bar_show_focused_title]: crate::video::Connector::set_bar_show_focused_title
+pub fn set_bar_show_focused_title(show: bool) {
+    get!().set_bar_show_focused_title(None, show);
+}
+
 async fn handle_i3bar(name: String, mut read: BufReader<Async<OwnedFd>>) {
     use std::fmt::Write;
 
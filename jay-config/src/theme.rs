@@ -261,6 +261,18 @@ pub mod colors {
         ///
         /// Default: `#9d28c67f`.
         const 15 => HIGHLIGHT_COLOR,
+        /// The background color of notifications.
+        ///
+        /// Default: `#222222`.
+        const 16 => NOTIFICATION_BACKGROUND_COLOR,
+        /// The border color of notifications.
+        ///
+        /// Default: `#285577`.
+        const 17 => NOTIFICATION_BORDER_COLOR,
+        /// The text color of notifications.
+        ///
+        /// Default: `#ffffff`.
+        const 18 => NOTIFICATION_TEXT_COLOR,
     }
 
     /// Sets the color of GUI element.
@@ -312,5 +324,13 @@ pub mod sized {
         ///
         /// Default: 4
         const 02 => BORDER_WIDTH,
+        /// The gap between tiled windows.
+        ///
+        /// Default: 0
+        const 03 => INNER_GAP,
+        /// The gap between tiled windows and the edges of the output.
+        ///
+        /// Default: 0
+        const 04 => OUTER_GAP,
     }
 }
@@ -56,6 +56,231 @@ impl Mode {
     }
 }
 
+/// The timings of a mode that is not necessarily advertised by the connected display.
+///
+/// The timings can be specified directly, as in an X11 modeline, or computed with the
+/// [CVT](CustomMode::cvt) and [GTF](CustomMode::gtf) formulas. All horizontal values
+/// are in pixels and all vertical values are in lines.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct CustomMode {
+    /// The pixel clock in kHz.
+    pub clock_khz: u32,
+    pub hdisplay: u16,
+    pub hsync_start: u16,
+    pub hsync_end: u16,
+    pub htotal: u16,
+    pub vdisplay: u16,
+    pub vsync_start: u16,
+    pub vsync_end: u16,
+    pub vtotal: u16,
+    /// Whether the horizontal sync pulse has positive polarity.
+    pub hsync_positive: bool,
+    /// Whether the vertical sync pulse has positive polarity.
+    pub vsync_positive: bool,
+}
+
+impl CustomMode {
+    /// Computes the timings of a mode with the VESA Coordinated Video Timings formula.
+    ///
+    /// If `reduced_blanking` is true, the timings use the reduced blanking variant which
+    /// requires a lower pixel clock. This is only supported by digital displays.
+    ///
+    /// This produces the same timings as the `cvt` utility.
+    pub fn cvt(width: u16, height: u16, refresh_millihz: u32, reduced_blanking: bool) -> Self {
+        const H_GRANULARITY: u32 = 8;
+        const MIN_V_PORCH: u32 = 3;
+        const CLOCK_STEP: u32 = 250;
+        let refresh = refresh_millihz as f64 / 1000.0;
+        let hdisplay = width as u32 - width as u32 % H_GRANULARITY;
+        let vdisplay = height as u32;
+        let (w, h) = (width as u32, height as u32);
+        let vsync = if h % 3 == 0 && h * 4 / 3 == w {
+            4
+        } else if h % 9 == 0 && h * 16 / 9 == w {
+            5
+        } else if h % 10 == 0 && h * 16 / 10 == w {
+            6
+        } else if (h % 4 == 0 && h * 5 / 4 == w) || (h % 9 == 0 && h * 15 / 9 == w) {
+            7
+        } else {
+            10
+        };
+        let vsync_start = vdisplay + MIN_V_PORCH;
+        let vsync_end = vsync_start + vsync;
+        let (clock_khz, hsync_start, hsync_end, htotal, vtotal);
+        if reduced_blanking {
+            const MIN_VBLANK: f64 = 460.0;
+            const H_SYNC: u32 = 32;
+            const H_BLANK: u32 = 160;
+            const MIN_V_BPORCH: u32 = 6;
+            let hperiod = (1_000_000.0 / refresh - MIN_VBLANK) / vdisplay as f64;
+            let vblank =
+                ((MIN_VBLANK / hperiod) as u32 + 1).max(MIN_V_PORCH + vsync + MIN_V_BPORCH);
+            vtotal = vdisplay + vblank;
+            htotal = hdisplay + H_BLANK;
+            let clock = (refresh * htotal as f64 * vtotal as f64 / 1000.0) as u32;
+            clock_khz = clock - clock % CLOCK_STEP;
+            hsync_end = hdisplay + H_BLANK / 2;
+            hsync_start = hsync_end - H_SYNC;
+        } else {
+            const MIN_VSYNC_BP: f64 = 550.0;
+            const HSYNC_PERCENTAGE: u32 = 8;
+            const C_PRIME: f64 = 30.0;
+            const M_PRIME: f64 = 300.0;
+            let hperiod = (1_000_000.0 / refresh - MIN_VSYNC_BP) / (vdisplay + MIN_V_PORCH) as f64;
+            let vsync_bp = ((MIN_VSYNC_BP / hperiod) as u32 + 1).max(vsync + MIN_V_PORCH);
+            vtotal = vdisplay + vsync_bp + MIN_V_PORCH;
+            let hblank_percentage = (C_PRIME - M_PRIME * hperiod / 1000.0).max(20.0);
+            let mut hblank =
+                (hdisplay as f64 * hblank_percentage / (100.0 - hblank_percentage)) as u32;
+            hblank -= hblank % (2 * H_GRANULARITY);
+            htotal = hdisplay + hblank;
+            hsync_end = hdisplay + hblank / 2;
+            let start = hsync_end - htotal * HSYNC_PERCENTAGE / 100;
+            hsync_start = start + H_GRANULARITY - start % H_GRANULARITY;
+            let clock = (htotal as f64 * 1000.0 / hperiod) as u32;
+            clock_khz = clock - clock % CLOCK_STEP;
+        }
+        Self {
+            clock_khz,
+            hdisplay: hdisplay as _,
+            hsync_start: hsync_start as _,
+            hsync_end: hsync_end as _,
+            htotal: htotal as _,
+            vdisplay: vdisplay as _,
+            vsync_start: vsync_start as _,
+            vsync_end: vsync_end as _,
+            vtotal: vtotal as _,
+            hsync_positive: reduced_blanking,
+            vsync_positive: !reduced_blanking,
+        }
+    }
+
+    /// Computes the timings of a mode with the VESA Generalized Timing Formula.
+    ///
+    /// This produces the same timings as the `gtf` utility.
+    pub fn gtf(width: u16, height: u16, refresh_millihz: u32) -> Self {
+        const CELL_GRAN: f64 = 8.0;
+        const MIN_PORCH: f64 = 1.0;
+        const V_SYNC_RQD: f64 = 3.0;
+        const H_SYNC_PERCENT: f64 = 8.0;
+        const MIN_VSYNC_PLUS_BP: f64 = 550.0;
+        const C_PRIME: f64 = 30.0;
+        const M_PRIME: f64 = 300.0;
+        let refresh = refresh_millihz as f64 / 1000.0;
+        let hdisplay = (width as f64 / CELL_GRAN).round_ties_even() * CELL_GRAN;
+        let vdisplay = height as f64;
+        let hperiod_est = (1.0 / refresh - MIN_VSYNC_PLUS_BP / 1_000_000.0)
+            / (vdisplay + MIN_PORCH)
+            * 1_000_000.0;
+        let vsync_bp = (MIN_VSYNC_PLUS_BP / hperiod_est).round_ties_even();
+        let vtotal = vdisplay + vsync_bp + MIN_PORCH;
+        let refresh_est = 1.0 / hperiod_est / vtotal * 1_000_000.0;
+        let hperiod = hperiod_est / (refresh / refresh_est);
+        let duty_cycle = C_PRIME - M_PRIME * hperiod / 1000.0;
+        let hblank = (hdisplay * duty_cycle / (100.0 - duty_cycle) / (2.0 * CELL_GRAN))
+            .round_ties_even()
+            * (2.0 * CELL_GRAN);
+        let htotal = hdisplay + hblank;
+        let hsync = (H_SYNC_PERCENT / 100.0 * htotal / CELL_GRAN).round_ties_even() * CELL_GRAN;
+        let hsync_start = hdisplay + hblank / 2.0 - hsync;
+        let vsync_start = vdisplay + MIN_PORCH;
+        Self {
+            clock_khz: (htotal / hperiod * 1000.0).round() as u32,
+            hdisplay: hdisplay as _,
+            hsync_start: hsync_start as _,
+            hsync_end: (hsync_start + hsync) as _,
+            htotal: htotal as _,
+            vdisplay: vdisplay as _,
+            vsync_start: vsync_start as _,
+            vsync_end: (vsync_start + V_SYNC_RQD) as _,
+            vtotal: vtotal as _,
+            hsync_positive: false,
+            vsync_positive: true,
+        }
+    }
+
+    /// Parses an X11 modeline.
+    ///
+    /// The modeline consists of the pixel clock in MHz, the eight horizontal and vertical
+    /// timings, and optionally the sync polarities, for example
+    ///
+    /// ```text
+    /// 173.00 1920 2048 2248 2576 1080 1083 1088 1120 -hsync +vsync
+    /// ```
+    ///
+    /// A leading `Modeline` keyword and a quoted mode name are ignored. Returns `None`
+    /// if the modeline cannot be parsed.
+    pub fn from_modeline(modeline: &str) -> Option<Self> {
+        let mut tokens = modeline.split_whitespace().peekable();
+        if tokens
+            .peek()
+            .is_some_and(|t| t.eq_ignore_ascii_case("modeline"))
+        {
+            tokens.next();
+        }
+        if tokens.peek().is_some_and(|t| t.starts_with('"')) {
+            tokens.next();
+        }
+        let clock_mhz = f64::from_str(tokens.next()?).ok()?;
+        let mut timings = [0u16; 8];
+        for timing in &mut timings {
+            *timing = u16::from_str(tokens.next()?).ok()?;
+        }
+        let [hdisplay, hsync_start, hsync_end, htotal, vdisplay, vsync_start, vsync_end, vtotal] =
+            timings;
+        let mut hsync_positive = true;
+        let mut vsync_positive = true;
+        for flag in tokens {
+            match &*flag.to_ascii_lowercase() {
+                "+hsync" => hsync_positive = true,
+                "-hsync" => hsync_positive = false,
+                "+vsync" => vsync_positive = true,
+                "-vsync" => vsync_positive = false,
+                _ => return None,
+            }
+        }
+        Some(Self {
+            clock_khz: (clock_mhz * 1000.0).round() as u32,
+            hdisplay,
+            hsync_start,
+            hsync_end,
+            htotal,
+            vdisplay,
+            vsync_start,
+            vsync_end,
+            vtotal,
+            hsync_positive,
+            vsync_positive,
+        })
+    }
+
+    /// Returns whether the timings are consistent.
+    ///
+    /// This only checks that the values are ordered correctly. Whether the display and
+    /// the driver support the mode can only be determined by the compositor.
+    pub fn is_valid(&self) -> bool {
+        self.clock_khz > 0
+            && 0 < self.hdisplay
+            && self.hdisplay <= self.hsync_start
+            && self.hsync_start <= self.hsync_end
+            && self.hsync_end <= self.htotal
+            && 0 < self.vdisplay
+            && self.vdisplay <= self.vsync_start
+            && self.vsync_start <= self.vsync_end
+            && self.vsync_end <= self.vtotal
+    }
+
+    /// Returns the refresh rate of the mode in mhz.
+    pub fn refresh_rate(&self) -> u32 {
+        let pixels = self.htotal as u64 * self.vtotal as u64;
+        if pixels == 0 {
+            return 0;
+        }
+        ((self.clock_khz as u64 * 1_000_000 + pixels / 2) / pixels) as u32
+    }
+}
+
 /// A connector that is potentially connected to an output device.
 ///
 /// A connector is the part that sticks out of your graphics card. A graphics card usually
@@ -149,6 +374,26 @@ impl Connector {
         )
     }
 
+    /// Tries to set a custom mode on the connector.
+    ///
+    /// Unlike [`Connector::set_mode`], the mode does not have to be advertised by the
+    /// display. Before the mode is applied, the compositor asks the kernel whether the
+    /// driver supports it. If the driver rejects the mode, the current mode is kept.
+    ///
+    /// Custom modes can damage old displays that do not protect themselves against
+    /// timings outside of their specifications. Use with care.
+    pub fn set_custom_mode(self, mode: CustomMode) {
+        if !self.exists() {
+            log::warn!("set_custom_mode called on a connector that does not exist");
+            return;
+        }
+        if !mode.is_valid() {
+            log::warn!("Custom mode {mode:?} is invalid");
+            return;
+        }
+        get!().connector_set_custom_mode(self, mode)
+    }
+
     /// Returns the available modes of the connector.
     pub fn modes(self) -> Vec<Mode> {
         if !self.exists() {
@@ -212,6 +457,18 @@ impl Connector {
         get!().connector_set_enabled(self, enabled);
     }
 
+    /// Turns off all outputs except the output of this connector.
+    ///
+    /// This is useful for presentations. Use [set_outputs_powered] to turn the other
+    /// outputs on again.
+    pub fn present(self) {
+        if !self.exists() {
+            log::warn!("present called on a connector that does not exist");
+            return;
+        }
+        get!().set_presentation_output(Some(self));
+    }
+
     /// Sets the transformation to apply to the content of this connector.
     pub fn set_transform(self, transform: Transform) {
         if !self.exists() {
@@ -263,6 +520,20 @@ impl Connector {
         get!().set_vrr_cursor_hz(Some(self), hz)
     }
 
+    /// Sets the format of the clock displayed in the bar of this output.
+    ///
+    /// See [set_bar_clock_format](crate::status::set_bar_clock_format).
+    pub fn set_bar_clock_format(self, format: Option<&str>) {
+        get!().set_bar_clock_format(Some(self), format)
+    }
+
+    /// Sets whether the title of the focused window is displayed in the bar of this output.
+    ///
+    /// See [set_bar_show_focused_title](crate::status::set_bar_show_focused_title).
+    pub fn set_bar_show_focused_title(self, show: bool) {
+        get!().set_bar_show_focused_title(Some(self), show)
+    }
+
     /// Sets the tearing mode.
     pub fn set_tearing_mode(self, mode: TearingMode) {
         get!().set_tearing_mode(Some(self), mode)
@@ -272,6 +543,101 @@ impl Connector {
     pub fn set_format(self, format: Format) {
         get!().connector_set_format(self, format);
     }
+
+    /// Enables or disables HDR10 output.
+    ///
+    /// This has no effect if the display or the driver does not support HDR10. In HDR mode,
+    /// SDR content is mapped to a reference white of 203 cd/m².
+    ///
+    /// The default is `false`.
+    pub fn set_hdr_enabled(self, enabled: bool) {
+        get!().connector_set_hdr_enabled(self, enabled);
+    }
+
+    /// Sets the render margin of this connector.
+    ///
+    /// The compositor measures how long it takes to render a frame for this output and
+    /// starts rendering as late as possible before the next vblank to minimize input
+    /// latency. This margin is added to the measured render time to absorb variations in
+    /// the render time. Larger values reduce the risk of missed frames at the cost of
+    /// higher latency. The default is 0.
+    ///
+    /// This has no effect if the output uses VRR or tearing.
+    pub fn set_render_margin(self, margin: Duration) {
+        get!().connector_set_render_margin(self, margin);
+    }
+
+    /// Makes this connector display the contents of another connector.
+    ///
+    /// While the source is connected to a desktop display, this connector does not have
+    /// any workspaces of its own. Instead it shows the contents of the source, scaled to
+    /// its own mode while preserving the aspect ratio. Mirroring ends when the source is
+    /// disconnected and resumes when it is connected again.
+    ///
+    /// A connector that mirrors another connector cannot itself be mirrored.
+    ///
+    /// Pass `None` to stop mirroring.
+    pub fn set_mirror(self, source: Option<Connector>) {
+        get!().connector_set_mirror(self, source);
+    }
+
+    /// Returns the backlight brightness of the display.
+    ///
+    /// The brightness is a value between 0 and 1. Returns `None` if the display does not
+    /// have a backlight that can be controlled by the compositor.
+    pub fn brightness(self) -> Option<f64> {
+        if !self.exists() {
+            return None;
+        }
+        get!(None).connector_get_brightness(self)
+    }
+
+    /// Sets the backlight brightness of the display.
+    ///
+    /// The brightness is a value between 0 and 1. The brightness is changed via logind,
+    /// which means that the compositor does not need write access to
+    /// `/sys/class/backlight`.
+    ///
+    /// Internal panels use the backlight of the graphics card or the firmware. Other
+    /// displays only have a backlight if the driver exposes one for the connector.
+    pub fn set_brightness(self, brightness: f64) {
+        get!().connector_set_brightness(self, brightness)
+    }
+
+    /// Changes the backlight brightness of the display by `delta`.
+    ///
+    /// The brightness is a value between 0 and 1. Unlike with
+    /// [`Connector::set_brightness`], decreasing the brightness never turns the backlight
+    /// off completely.
+    pub fn change_brightness(self, delta: f64) {
+        get!().connector_change_brightness(self, delta)
+    }
+
+    /// Removes a virtual output created with [create_virtual_output].
+    pub fn remove_virtual_output(self) {
+        get!().remove_virtual_output(self);
+    }
+}
+
+/// Creates an output that is not backed by any display hardware.
+///
+/// This is only supported by the headless backend and the X backend. Virtual outputs of
+/// the headless backend accept any mode passed to [Connector::set_mode]. If the refresh
+/// rate is not specified, 60 Hz is used. The X backend instead creates a new window of
+/// the requested size and ignores the refresh rate.
+///
+/// Returns `None` if the output could not be created. The connector is announced via
+/// [on_new_connector] like any other connector.
+pub fn create_virtual_output(
+    width: i32,
+    height: i32,
+    refresh_millihz: Option<u32>,
+) -> Option<Connector> {
+    get!(None).create_virtual_output(WireMode {
+        width,
+        height,
+        refresh_millihz: refresh_millihz.unwrap_or(60_000),
+    })
 }
 
 /// Returns all available DRM devices.
@@ -285,6 +651,8 @@ pub fn on_new_drm_device<F: FnMut(DrmDevice) + 'static>(f: F) {
 }
 
 /// Sets the callback to be called when a DRM device is removed.
+///
+/// The properties of the device can still be queried while the callback runs.
 pub fn on_drm_device_removed<F: FnMut(DrmDevice) + 'static>(f: F) {
     get!().on_del_drm_device(f)
 }
@@ -300,10 +668,37 @@ pub fn on_connector_connected<F: FnMut(Connector) + 'static>(f: F) {
 }
 
 /// Sets the callback to be called when a connector is disconnected from an output device.
+///
+/// The properties of the output, such as its name, model, and mode, can still be queried
+/// while the callback runs.
 pub fn on_connector_disconnected<F: FnMut(Connector) + 'static>(f: F) {
     get!().on_connector_disconnected(f)
 }
 
+/// Sets the callback to be called when the mode of a connected output changes.
+///
+/// The callback receives the connector and the new mode.
+pub fn on_connector_mode_changed<F: FnMut(Connector, Mode) + 'static>(f: F) {
+    get!().on_connector_mode_changed(f)
+}
+
+/// Sets the callback to be called when the backlight brightness of a display is changed.
+///
+/// The callback receives the connector and the new brightness between 0 and 1. This can
+/// be used to show an on-screen display.
+pub fn on_brightness_changed<F: FnMut(Connector, f64) + 'static>(f: F) {
+    get!().on_brightness_changed(f)
+}
+
+/// Sets the callback to be called when the compositor had to fall back to a different mode.
+///
+/// This happens if the GPU cannot drive all connectors with their requested modes, for
+/// example, because the link bandwidth is insufficient. The callback receives the
+/// connector, the requested mode, and the mode that was actually applied.
+pub fn on_mode_fallback<F: FnMut(Connector, Mode, Mode) + 'static>(f: F) {
+    get!().on_mode_fallback(f)
+}
+
 /// Sets the callback to be called when the graphics of the compositor have been initialized.
 ///
 /// This callback is only invoked once during the lifetime of the compositor. This is a good place
@@ -628,6 +1023,64 @@ pub fn set_tearing_mode(mode: TearingMode) {
     get!().set_tearing_mode(None, mode)
 }
 
+/// Turns all outputs on or off.
+///
+/// Outputs that are turned off keep their workspaces and windows. This is independent
+/// of the idle timeout.
+pub fn set_outputs_powered(powered: bool) {
+    get!().set_outputs_powered(powered);
+}
+
+/// Turns all outputs off if any of them is on. Otherwise turns all outputs on.
+pub fn toggle_outputs_powered() {
+    get!().toggle_outputs_powered();
+}
+
+/// The schedule of the built-in night light.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Default)]
+pub enum NightLightSchedule {
+    /// The night light is disabled.
+    ///
+    /// This is the default.
+    #[default]
+    Disabled,
+    /// The night light is always enabled.
+    Always,
+    /// The night light is enabled between two times of the day.
+    ///
+    /// The times are given in minutes after midnight in the local time zone. If `start`
+    /// is after `end`, the night light is enabled over midnight.
+    Time { start: u32, end: u32 },
+    /// The night light is enabled between sunset and sunrise at a location.
+    ///
+    /// The latitude and longitude are given in degrees. North and east are positive.
+    Location { latitude: f64, longitude: f64 },
+}
+
+/// Sets the schedule of the built-in night light.
+///
+/// While the night light is enabled, the colors of all outputs are shifted towards the
+/// temperature set with [set_night_light_temperature]. The night light is applied via
+/// the gamma ramps of the outputs and is combined with the ramps of applications that use
+/// the `wlr-gamma-control` protocol.
+pub fn set_night_light_schedule(schedule: NightLightSchedule) {
+    get!().set_night_light_schedule(schedule);
+}
+
+/// Sets the color temperature of the night light in Kelvin.
+///
+/// The value is clamped to the range `1000..=6500`. The default is `4000`.
+pub fn set_night_light_temperature(kelvin: u32) {
+    get!().set_night_light_temperature(kelvin);
+}
+
+/// Sets the duration of the transitions between day and night.
+///
+/// Transitions start at the times determined by the schedule. The default is 30 minutes.
+pub fn set_night_light_transition(duration: Duration) {
+    get!().set_night_light_transition(duration);
+}
+
 /// A graphics format.
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct Format(pub u32);
@@ -4,7 +4,7 @@ use {
         drm_feedback::DrmFeedback,
         fixed::Fixed,
         format::Format,
-        gfx_api::{GfxFramebuffer, SyncFile},
+        gfx_api::{GfxContext, GfxFramebuffer, SyncFile},
         ifs::{
             wl_output::OutputId,
             wl_seat::{
@@ -16,10 +16,14 @@ use {
                 wl_pointer::{CONTINUOUS, FINGER, HORIZONTAL_SCROLL, VERTICAL_SCROLL, WHEEL},
             },
         },
+        kbvm::KbvmMap,
         libinput::consts::DeviceCapability,
         video::drm::{ConnectorType, DrmConnector, DrmError, DrmVersion},
     },
-    jay_config::{input::SwitchEvent, video::GfxApi},
+    jay_config::{
+        input::SwitchEvent,
+        video::{CustomMode, GfxApi},
+    },
     std::{
         any::Any,
         error::Error,
@@ -56,6 +60,45 @@ pub trait Backend {
     fn supports_presentation_feedback(&self) -> bool {
         false
     }
+
+    /// Applies the connector changes performed by `f` as a single transaction.
+    ///
+    /// Backends that support this defer modesets until `f` returns and then apply either
+    /// all of the changes or none of them.
+    fn apply_connector_transaction(
+        self: Rc<Self>,
+        f: &mut dyn FnMut(),
+    ) -> Result<(), Box<dyn Error>> {
+        f();
+        Ok(())
+    }
+
+    /// Tests whether the connector changes performed by `f` could be applied.
+    ///
+    /// The connectors are restored to their previous state before this function returns.
+    /// Backends that do not support this do not call `f` and always succeed.
+    fn test_connector_transaction(
+        self: Rc<Self>,
+        f: &mut dyn FnMut(),
+    ) -> Result<(), Box<dyn Error>> {
+        let _ = f;
+        Ok(())
+    }
+
+    /// Creates an output that is not backed by any display hardware.
+    fn create_virtual_output(
+        self: Rc<Self>,
+        mode: Mode,
+    ) -> Result<Rc<dyn Connector>, Box<dyn Error>> {
+        let _ = mode;
+        Err("The backend does not support virtual outputs".into())
+    }
+
+    /// Removes an output created with `create_virtual_output`.
+    fn remove_virtual_output(&self, id: ConnectorId) -> Result<(), Box<dyn Error>> {
+        let _ = id;
+        Err("The backend does not support virtual outputs".into())
+    }
 }
 
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
@@ -83,6 +126,38 @@ pub struct MonitorInfo {
     pub height_mm: i32,
     pub non_desktop: bool,
     pub vrr_capable: bool,
+    pub hdr_capable: bool,
+    pub edid: Option<Rc<EdidInfo>>,
+    pub properties: Rc<Vec<ConnectorProperty>>,
+}
+
+/// Information about a display that was read from its EDID.
+#[derive(Clone, Debug, Default)]
+pub struct EdidInfo {
+    pub product_code: u16,
+    pub serial_number: u32,
+    pub week_of_manufacture: Option<u8>,
+    pub year_of_manufacture: Option<u16>,
+    pub version: u8,
+    pub revision: u8,
+    pub range_limits: Option<EdidRangeLimits>,
+}
+
+/// The range of timings supported by a display.
+#[derive(Copy, Clone, Debug)]
+pub struct EdidRangeLimits {
+    pub min_vertical_hz: u16,
+    pub max_vertical_hz: u16,
+    pub min_horizontal_khz: u16,
+    pub max_horizontal_khz: u16,
+    pub max_pixel_clock_mhz: u16,
+}
+
+/// A property of a connector as reported by the kernel.
+#[derive(Clone, Debug)]
+pub struct ConnectorProperty {
+    pub name: String,
+    pub value: String,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -103,6 +178,12 @@ pub trait Connector {
     fn event(&self) -> Option<ConnectorEvent>;
     fn on_change(&self, cb: Rc<dyn Fn()>);
     fn damage(&self);
+    /// Damages the output because only the software cursor changed.
+    ///
+    /// The affected area is recorded in the software cursor damage of the output.
+    fn damage_software_cursor(&self) {
+        self.damage();
+    }
     fn drm_dev(&self) -> Option<DrmDeviceId>;
     fn enabled(&self) -> bool {
         true
@@ -114,6 +195,13 @@ pub trait Connector {
         None
     }
     fn set_mode(&self, mode: Mode);
+    /// Switches to a mode that does not have to be advertised by the display.
+    ///
+    /// The mode is only applied if the kernel accepts it in a test commit.
+    fn set_custom_mode(&self, mode: &CustomMode) -> Result<(), Box<dyn Error>> {
+        let _ = mode;
+        Err("The backend does not support custom modes".into())
+    }
     fn set_non_desktop_override(&self, non_desktop: Option<bool>) {
         let _ = non_desktop;
     }
@@ -123,12 +211,49 @@ pub trait Connector {
     fn set_vrr_enabled(&self, enabled: bool) {
         let _ = enabled;
     }
+    fn set_hdr_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
     fn set_tearing_enabled(&self, enabled: bool) {
         let _ = enabled;
     }
     fn set_fb_format(&self, format: &'static Format) {
         let _ = format;
     }
+    fn set_render_margin(&self, margin_ns: u64) {
+        let _ = margin_ns;
+    }
+    fn set_powered(&self, powered: bool) {
+        let _ = powered;
+    }
+    fn gamma_lut_size(&self) -> u32 {
+        0
+    }
+    fn set_gamma_lut(&self, lut: Option<Rc<GammaLut>>) {
+        let _ = lut;
+    }
+    /// Returns the brightness and the maximum brightness of the backlight of the display.
+    fn backlight(&self) -> Option<(u32, u32)> {
+        None
+    }
+    /// Sets the brightness of the backlight of the display.
+    fn set_backlight(&self, brightness: u32) {
+        let _ = brightness;
+    }
+    /// Makes the connector display the contents of another connector.
+    ///
+    /// While a source is set, the connector has no output node of its own.
+    fn set_mirror_source(&self, source: Option<ConnectorId>) {
+        let _ = source;
+    }
+}
+
+/// A gamma ramp with `gamma_lut_size` entries per channel.
+#[derive(Debug)]
+pub struct GammaLut {
+    pub red: Vec<u16>,
+    pub green: Vec<u16>,
+    pub blue: Vec<u16>,
 }
 
 #[derive(Debug)]
@@ -138,9 +263,14 @@ pub enum ConnectorEvent {
     Disconnected,
     Removed,
     ModeChanged(Mode),
+    /// The requested mode could not be applied and a lower mode is used instead.
+    ///
+    /// Contains the requested mode or `None` if the requested mode is used.
+    ModeFallback(Option<Mode>),
     Unavailable,
     Available,
     VrrChanged(bool),
+    HdrChanged(bool),
     FormatsChanged(Rc<Vec<&'static Format>>, &'static Format),
 }
 
@@ -194,6 +324,21 @@ pub trait InputDevice {
     fn dev_t(&self) -> Option<c::dev_t> {
         None
     }
+    fn vendor_id(&self) -> Option<u32> {
+        None
+    }
+    fn product_id(&self) -> Option<u32> {
+        None
+    }
+    fn events_enabled(&self) -> Option<bool> {
+        None
+    }
+    fn set_events_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+    fn set_leds(&self, leds: KeyboardLeds) {
+        let _ = leds;
+    }
     fn tap_enabled(&self) -> Option<bool> {
         None
     }
@@ -210,12 +355,40 @@ pub trait InputDevice {
         None
     }
     fn set_natural_scrolling_enabled(&self, enabled: bool);
+    fn dwt_enabled(&self) -> Option<bool> {
+        None
+    }
+    fn set_dwt_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+    fn dwtp_enabled(&self) -> Option<bool> {
+        None
+    }
+    fn set_dwtp_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+    fn middle_emulation_enabled(&self) -> Option<bool> {
+        None
+    }
+    fn set_middle_emulation_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+    fn gamepad_resets_idle(&self) -> Option<bool> {
+        None
+    }
+    fn set_gamepad_resets_idle(&self, enabled: bool) {
+        let _ = enabled;
+    }
     fn tablet_info(&self) -> Option<Box<TabletInit>> {
         None
     }
     fn tablet_pad_info(&self) -> Option<Box<TabletPadInit>> {
         None
     }
+    /// The keymap that the device uses initially instead of the keymap of its seat.
+    fn keymap(&self) -> Option<Rc<KbvmMap>> {
+        None
+    }
 }
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
@@ -244,6 +417,13 @@ impl InputDeviceCapability {
     }
 }
 
+bitflags! {
+    KeyboardLeds: u32;
+        LED_NUM_LOCK    = 1 << 0,
+        LED_CAPS_LOCK   = 1 << 1,
+        LED_SCROLL_LOCK = 1 << 2,
+}
+
 #[derive(Debug, Copy, Clone)]
 pub enum InputDeviceAccelProfile {
     Flat,
@@ -369,6 +549,10 @@ pub enum InputEvent {
         event: SwitchEvent,
     },
 
+    GamepadActivity {
+        time_usec: u64,
+    },
+
     TabletToolAdded {
         time_usec: u64,
         init: Box<TabletToolInit>,
@@ -457,6 +641,9 @@ pub trait BackendDrmDevice {
     fn version(&self) -> Result<DrmVersion, DrmError>;
     fn set_direct_scanout_enabled(&self, enabled: bool);
     fn is_render_device(&self) -> bool;
+    fn gfx_ctx(&self) -> Option<Rc<dyn GfxContext>> {
+        None
+    }
     fn create_lease(
         self: Rc<Self>,
         lessee: Rc<dyn BackendDrmLessee>,
@@ -1,3 +1,4 @@
 pub mod dummy;
+pub mod headless;
 pub mod metal;
 pub mod x;
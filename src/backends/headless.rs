@@ -0,0 +1,512 @@
+use {
+    crate::{
+        allocator::BufferObject,
+        async_engine::{Phase, SpawnedFuture},
+        backend::{
+            Backend, BackendDrmDevice, BackendEvent, Connector, ConnectorEvent, ConnectorId,
+            ConnectorKernelId, DrmDeviceId, DrmEvent, Mode, MonitorInfo,
+        },
+        format::XRGB8888,
+        gfx_api::{AcquireSync, GfxContext, GfxError, GfxFramebuffer, GfxTexture, ReleaseSync},
+        ifs::wl_output::OutputId,
+        state::State,
+        time::Time,
+        utils::{
+            asyncevent::AsyncEvent, clonecell::CloneCell, copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt, numcell::NumCell, oserror::OsError, syncqueue::SyncQueue,
+        },
+        video::{
+            drm::{ConnectorType, Drm, DrmError, DrmVersion},
+            gbm::{GbmBo, GbmDevice, GbmError, GBM_BO_USE_RENDERING},
+        },
+    },
+    bstr::ByteSlice,
+    jay_config::video::GfxApi,
+    std::{
+        any::Any, cell::Cell, error::Error, future::pending, io, os::unix::ffi::OsStrExt, rc::Rc,
+    },
+    thiserror::Error,
+    uapi::c::{self, dev_t},
+};
+
+const DEFAULT_MODE: Mode = Mode {
+    width: 1920,
+    height: 1080,
+    refresh_rate_millihz: 60_000,
+};
+const MAX_SIZE: i32 = 16384;
+/// Modes advertised by virtual outputs in addition to their initial mode.
+const COMMON_MODES: [(i32, i32); 6] = [
+    (3840, 2160),
+    (2560, 1440),
+    (1920, 1200),
+    (1920, 1080),
+    (1600, 900),
+    (1280, 720),
+];
+
+#[derive(Debug, Error)]
+pub enum HeadlessBackendError {
+    #[error("Could not read /dev/dri")]
+    ReadDri(#[source] io::Error),
+    #[error("There are no render nodes in /dev/dri")]
+    NoRenderNode,
+    #[error("Could not open render node {0}")]
+    OpenRenderNode(String, #[source] OsError),
+    #[error("The drm subsystem returned an error")]
+    DrmError(#[from] DrmError),
+    #[error("The gbm subsystem returned an error")]
+    GbmError(#[from] GbmError),
+    #[error("Could not create a graphics API context")]
+    CreateGfxContext(#[source] GfxError),
+    #[error("Could not create an graphics API image from a dma-buf")]
+    CreateImage(#[source] GfxError),
+    #[error("Could not create a framebuffer from a graphics API image")]
+    CreateFramebuffer(#[source] GfxError),
+    #[error("Could not create a texture from an graphics API image")]
+    CreateTexture(#[source] GfxError),
+    #[error("Render device does not support XRGB8888 format")]
+    XRGB8888,
+    #[error("Invalid output size {0}x{1}")]
+    InvalidSize(i32, i32),
+    #[error("Connector {0} is not a virtual output")]
+    UnknownOutput(ConnectorId),
+}
+
+/// Creates a backend that renders into GBM buffers without any display hardware.
+///
+/// The backend starts with a single virtual output. Further outputs can be created at
+/// runtime.
+pub fn create(state: &Rc<State>) -> Result<Rc<HeadlessBackend>, HeadlessBackendError> {
+    let drm = open_render_node()?;
+    let drm_dev = drm.dev();
+    let gbm = GbmDevice::new(&drm)?;
+    let ctx = match state.create_gfx_context(&drm, None) {
+        Ok(r) => r,
+        Err(e) => return Err(HeadlessBackendError::CreateGfxContext(e)),
+    };
+    let backend = Rc::new(HeadlessBackend {
+        state: state.clone(),
+        ctx,
+        gbm,
+        drm_device_id: state.drm_dev_ids.next(),
+        drm_dev,
+        running: Cell::new(false),
+        next_idx: NumCell::new(1),
+        outputs: Default::default(),
+    });
+    backend.add_output(DEFAULT_MODE)?;
+    Ok(backend)
+}
+
+fn open_render_node() -> Result<Drm, HeadlessBackendError> {
+    let dri = match std::fs::read_dir("/dev/dri") {
+        Ok(d) => d,
+        Err(e) => return Err(HeadlessBackendError::ReadDri(e)),
+    };
+    let mut nodes = vec![];
+    for f in dri {
+        let f = match f {
+            Ok(f) => f,
+            Err(e) => return Err(HeadlessBackendError::ReadDri(e)),
+        };
+        let path = f.path();
+        if let Some(file) = path.file_name() {
+            if file.as_bytes().starts_with_str("renderD") {
+                nodes.push(path);
+            }
+        }
+    }
+    nodes.sort();
+    let Some(node) = nodes.first() else {
+        return Err(HeadlessBackendError::NoRenderNode);
+    };
+    log::info!("Using render node {}", node.display());
+    let file = match uapi::open(node.as_path(), c::O_RDWR | c::O_CLOEXEC, 0) {
+        Ok(f) => Rc::new(f),
+        Err(e) => {
+            return Err(HeadlessBackendError::OpenRenderNode(
+                node.as_os_str().as_bytes().as_bstr().to_string(),
+                e.into(),
+            ))
+        }
+    };
+    Ok(Drm::open_existing(file)?)
+}
+
+pub struct HeadlessBackend {
+    state: Rc<State>,
+    ctx: Rc<dyn GfxContext>,
+    gbm: GbmDevice,
+    drm_device_id: DrmDeviceId,
+    drm_dev: dev_t,
+    running: Cell<bool>,
+    next_idx: NumCell<u32>,
+    outputs: CopyHashMap<ConnectorId, Rc<HeadlessOutput>>,
+}
+
+impl Backend for HeadlessBackend {
+    fn run(self: Rc<Self>) -> SpawnedFuture<Result<(), Box<dyn Error>>> {
+        let slf = self.clone();
+        self.state.eng.spawn("headless backend", async move {
+            slf.run().await;
+            Ok(())
+        })
+    }
+
+    fn clear(&self) {
+        for (_, output) in self.outputs.clear() {
+            output.present_future.take();
+        }
+    }
+
+    fn into_any(self: Rc<Self>) -> Rc<dyn Any> {
+        self
+    }
+
+    fn create_virtual_output(
+        self: Rc<Self>,
+        mode: Mode,
+    ) -> Result<Rc<dyn Connector>, Box<dyn Error>> {
+        let output = self.add_output(mode)?;
+        if self.running.get() {
+            self.announce_output(&output);
+        }
+        Ok(output)
+    }
+
+    fn remove_virtual_output(&self, id: ConnectorId) -> Result<(), Box<dyn Error>> {
+        let Some(output) = self.outputs.remove(&id) else {
+            return Err(Box::new(HeadlessBackendError::UnknownOutput(id)));
+        };
+        output.present_future.take();
+        output.events.push(ConnectorEvent::Disconnected);
+        output.events.push(ConnectorEvent::Removed);
+        output.changed();
+        Ok(())
+    }
+}
+
+impl HeadlessBackend {
+    async fn run(self: Rc<Self>) {
+        self.state.set_render_ctx(Some(self.ctx.clone()));
+        self.state
+            .backend_events
+            .push(BackendEvent::NewDrmDevice(Rc::new(HeadlessDrmDevice {
+                backend: self.clone(),
+            })));
+        self.running.set(true);
+        for output in self.outputs.lock().values() {
+            self.announce_output(output);
+        }
+        self.state
+            .backend_events
+            .push(BackendEvent::DevicesEnumerated);
+        self.state.set_backend_idle(false);
+        pending().await
+    }
+
+    fn add_output(self: &Rc<Self>, mode: Mode) -> Result<Rc<HeadlessOutput>, HeadlessBackendError> {
+        let mode = sanitize_mode(mode)?;
+        let images = self.create_images(mode.width, mode.height)?;
+        let output = Rc::new(HeadlessOutput {
+            id: self.state.connector_ids.next(),
+            idx: self.next_idx.fetch_add(1),
+            backend: self.clone(),
+            events: Default::default(),
+            cb: Default::default(),
+            mode: Cell::new(mode),
+            damage: Default::default(),
+            sequence: Default::default(),
+            next_image: Default::default(),
+            images: CloneCell::new(Rc::new(images)),
+            present_future: Default::default(),
+        });
+        self.outputs.set(output.id, output.clone());
+        Ok(output)
+    }
+
+    fn announce_output(&self, output: &Rc<HeadlessOutput>) {
+        self.state
+            .backend_events
+            .push(BackendEvent::NewConnector(output.clone()));
+        let mode = output.mode.get();
+        let mut modes = vec![mode];
+        for (width, height) in COMMON_MODES {
+            if (width, height) != (mode.width, mode.height) {
+                modes.push(Mode {
+                    width,
+                    height,
+                    refresh_rate_millihz: mode.refresh_rate_millihz,
+                });
+            }
+        }
+        output.events.push(ConnectorEvent::Connected(MonitorInfo {
+            modes,
+            output_id: Rc::new(OutputId::new(
+                String::new(),
+                "Jay".to_string(),
+                "Virtual Output".to_string(),
+                output.idx.to_string(),
+            )),
+            initial_mode: mode,
+            width_mm: 0,
+            height_mm: 0,
+            non_desktop: false,
+            vrr_capable: false,
+            hdr_capable: false,
+            edid: None,
+            properties: Default::default(),
+        }));
+        output.changed();
+        let future = self.state.eng.spawn2(
+            "headless present loop",
+            Phase::Present,
+            output.clone().present_loop(),
+        );
+        output.present_future.set(Some(future));
+        output.damage.trigger();
+    }
+
+    fn create_images(
+        &self,
+        width: i32,
+        height: i32,
+    ) -> Result<[HeadlessImage; 2], HeadlessBackendError> {
+        let formats = self.ctx.formats();
+        let format = match formats.get(&XRGB8888.drm) {
+            Some(f) => f,
+            None => return Err(HeadlessBackendError::XRGB8888),
+        };
+        let create_image = || {
+            let bo = self.gbm.create_bo(
+                &self.state.dma_buf_ids,
+                width,
+                height,
+                XRGB8888,
+                format.write_modifiers.keys(),
+                GBM_BO_USE_RENDERING,
+            )?;
+            let img = match self.ctx.clone().dmabuf_img(bo.dmabuf()) {
+                Ok(f) => f,
+                Err(e) => return Err(HeadlessBackendError::CreateImage(e)),
+            };
+            let fb = match img.clone().to_framebuffer() {
+                Ok(f) => f,
+                Err(e) => return Err(HeadlessBackendError::CreateFramebuffer(e)),
+            };
+            let tex = match img.to_texture() {
+                Ok(f) => f,
+                Err(e) => return Err(HeadlessBackendError::CreateTexture(e)),
+            };
+            Ok(HeadlessImage { _bo: bo, fb, tex })
+        };
+        Ok([create_image()?, create_image()?])
+    }
+}
+
+fn sanitize_mode(mut mode: Mode) -> Result<Mode, HeadlessBackendError> {
+    if mode.width <= 0 || mode.height <= 0 || mode.width > MAX_SIZE || mode.height > MAX_SIZE {
+        return Err(HeadlessBackendError::InvalidSize(mode.width, mode.height));
+    }
+    if mode.refresh_rate_millihz == 0 {
+        mode.refresh_rate_millihz = DEFAULT_MODE.refresh_rate_millihz;
+    }
+    Ok(mode)
+}
+
+struct HeadlessDrmDevice {
+    backend: Rc<HeadlessBackend>,
+}
+
+impl BackendDrmDevice for HeadlessDrmDevice {
+    fn id(&self) -> DrmDeviceId {
+        self.backend.drm_device_id
+    }
+
+    fn event(&self) -> Option<DrmEvent> {
+        None
+    }
+
+    fn on_change(&self, _cb: Rc<dyn Fn()>) {
+        // nothing
+    }
+
+    fn dev_t(&self) -> dev_t {
+        self.backend.drm_dev
+    }
+
+    fn make_render_device(&self) {
+        log::warn!("make_render_device is not supported by the headless backend");
+        // nothing
+    }
+
+    fn set_gfx_api(&self, _api: GfxApi) {
+        log::warn!("set_gfx_api is not supported by the headless backend");
+        // nothing
+    }
+
+    fn gtx_api(&self) -> GfxApi {
+        self.backend.ctx.gfx_api()
+    }
+
+    fn version(&self) -> Result<DrmVersion, DrmError> {
+        self.backend.gbm.drm.version()
+    }
+
+    fn set_direct_scanout_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn is_render_device(&self) -> bool {
+        true
+    }
+}
+
+struct HeadlessOutput {
+    id: ConnectorId,
+    idx: u32,
+    backend: Rc<HeadlessBackend>,
+    events: SyncQueue<ConnectorEvent>,
+    cb: CloneCell<Option<Rc<dyn Fn()>>>,
+    mode: Cell<Mode>,
+    damage: AsyncEvent,
+    sequence: NumCell<u64>,
+    next_image: NumCell<usize>,
+    images: CloneCell<Rc<[HeadlessImage; 2]>>,
+    present_future: Cell<Option<SpawnedFuture<()>>>,
+}
+
+struct HeadlessImage {
+    _bo: GbmBo,
+    fb: Rc<dyn GfxFramebuffer>,
+    tex: Rc<dyn GfxTexture>,
+}
+
+impl HeadlessOutput {
+    fn changed(&self) {
+        if let Some(cb) = self.cb.get() {
+            cb();
+        }
+    }
+
+    /// Renders damaged frames no faster than the refresh rate of the output.
+    async fn present_loop(self: Rc<Self>) {
+        let state = &self.backend.state;
+        loop {
+            self.damage.triggered().await;
+            self.present().await;
+            let refresh_nsec = self.mode.get().refresh_nsec();
+            if let Err(e) = state.wheel.timeout((refresh_nsec / 1_000_000).max(1)).await {
+                log::error!("Could not wait for the next frame: {}", ErrorFmt(e));
+                return;
+            }
+            state.vblank(self.id);
+        }
+    }
+
+    async fn present(&self) {
+        let state = &self.backend.state;
+        let Some(node) = state.root.outputs.get(&self.id) else {
+            return;
+        };
+        let now = Time::now_unchecked().nsec();
+        node.before_latch(now).await;
+        let images = self.images.get();
+        let image = &images[self.next_image.fetch_add(1) % images.len()];
+        let render_start = Time::now_unchecked();
+        let res = state.present_output(
+            &node,
+            &image.fb,
+            AcquireSync::Implicit,
+            ReleaseSync::Implicit,
+            &image.tex,
+            true,
+        );
+        let sync_file = match res {
+            Ok(s) => s,
+            Err(e) => {
+                log::error!("Could not render screen: {}", ErrorFmt(e));
+                return;
+            }
+        };
+        if let Some(sync_file) = sync_file {
+            if let Err(e) = state.ring.readable(&sync_file).await {
+                log::error!("Could not wait for the render to complete: {}", ErrorFmt(e));
+            }
+        }
+        node.stats
+            .render_time
+            .add(render_start.elapsed().as_nanos() as u64);
+        let now = Time::now_unchecked().nsec();
+        node.presented(
+            now / 1_000_000_000,
+            (now % 1_000_000_000) as u32,
+            self.mode.get().refresh_nsec() as u32,
+            self.sequence.fetch_add(1),
+            0,
+            false,
+        );
+    }
+}
+
+impl Connector for HeadlessOutput {
+    fn id(&self) -> ConnectorId {
+        self.id
+    }
+
+    fn kernel_id(&self) -> ConnectorKernelId {
+        ConnectorKernelId {
+            ty: ConnectorType::VIRTUAL,
+            idx: self.idx,
+        }
+    }
+
+    fn event(&self) -> Option<ConnectorEvent> {
+        self.events.pop()
+    }
+
+    fn on_change(&self, cb: Rc<dyn Fn()>) {
+        self.cb.set(Some(cb));
+    }
+
+    fn damage(&self) {
+        self.damage.trigger();
+    }
+
+    fn drm_dev(&self) -> Option<DrmDeviceId> {
+        Some(self.backend.drm_device_id)
+    }
+
+    fn set_mode(&self, mode: Mode) {
+        let mode = match sanitize_mode(mode) {
+            Ok(m) => m,
+            Err(e) => {
+                log::warn!(
+                    "Cannot change the mode of {}: {}",
+                    self.kernel_id(),
+                    ErrorFmt(e)
+                );
+                return;
+            }
+        };
+        if mode == self.mode.get() {
+            return;
+        }
+        if (mode.width, mode.height) != (self.mode.get().width, self.mode.get().height) {
+            match self.backend.create_images(mode.width, mode.height) {
+                Ok(images) => {
+                    self.images.set(Rc::new(images));
+                }
+                Err(e) => {
+                    log::error!("Could not create images: {}", ErrorFmt(e));
+                    return;
+                }
+            }
+        }
+        self.mode.set(mode);
+        self.events.push(ConnectorEvent::ModeChanged(mode));
+        self.changed();
+        self.damage.trigger();
+    }
+}
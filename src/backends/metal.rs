@@ -1,19 +1,27 @@
+mod backlight;
+mod gamepad;
 mod input;
 mod monitor;
 mod present;
+mod session;
 mod video;
 
 use {
     crate::{
         async_engine::SpawnedFuture,
         backend::{
-            Backend, InputDevice, InputDeviceAccelProfile, InputDeviceCapability,
-            InputDeviceGroupId, InputDeviceId, InputEvent, KeyState, TransformMatrix,
+            Backend, ConnectorId, InputDevice, InputDeviceAccelProfile, InputDeviceCapability,
+            InputDeviceGroupId, InputDeviceId, InputEvent, KeyState, KeyboardLeds, TransformMatrix,
         },
-        backends::metal::video::{
-            MetalDrmDeviceData, MetalLeaseData, MetalRenderContext, PendingDrmDevice,
-            PersistentDisplayData,
+        backends::metal::{
+            gamepad::monitor_gamepad,
+            session::{LibSeatSession, MetalSession},
+            video::{
+                ConnectorTransaction, MetalConnector, MetalDrmDeviceData, MetalLeaseData,
+                MetalRenderContext, PendingDrmDevice, PersistentDisplayData,
+            },
         },
+        cli::CliSession,
         dbus::{DbusError, SignalHandler},
         drm_feedback::DrmFeedback,
         gfx_api::{GfxError, SyncFile},
@@ -26,16 +34,18 @@ use {
         libinput::{
             consts::{
                 AccelProfile, LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE,
-                LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT, LIBINPUT_DEVICE_CAP_TABLET_PAD,
-                LIBINPUT_DEVICE_CAP_TABLET_TOOL,
+                LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT, LIBINPUT_DEVICE_CAP_KEYBOARD,
+                LIBINPUT_DEVICE_CAP_TABLET_PAD, LIBINPUT_DEVICE_CAP_TABLET_TOOL,
             },
             device::{LibInputDevice, RegisteredDevice},
             LibInput, LibInputAdapter, LibInputError,
         },
+        libseat::{LibSeat, LibSeatError},
         logind::{LogindError, Session},
         state::State,
         udev::{Udev, UdevError, UdevMonitor},
         utils::{
+            cell_ext::CellExt,
             clonecell::{CloneCell, UnsafeCellCloneSafe},
             copyhashmap::CopyHashMap,
             errorfmt::ErrorFmt,
@@ -58,7 +68,7 @@ use {
         ffi::{CStr, CString},
         fmt::{Debug, Formatter},
         future::pending,
-        rc::Rc,
+        rc::{Rc, Weak},
     },
     thiserror::Error,
     uapi::{c, OwnedFd},
@@ -72,6 +82,10 @@ pub enum MetalError {
     LogindSession(#[source] LogindError),
     #[error("Could not take control of the logind session")]
     TakeControl(#[source] LogindError),
+    #[error("Could not open a libseat session")]
+    LibSeat(#[source] LibSeatError),
+    #[error("Could not open a session")]
+    NoSession,
     #[error("Could not enumerate devices")]
     Enumerate(#[source] Box<Self>),
     #[error(transparent)]
@@ -104,6 +118,8 @@ pub enum MetalError {
     ImportImage(#[source] GfxError),
     #[error("Could not perform modeset")]
     Modeset(#[source] DrmError),
+    #[error("The driver does not support the custom mode")]
+    CustomModeRejected,
     #[error("Could not enable atomic modesetting")]
     AtomicModesetting(#[source] OsError),
     #[error("Could not inspect a plane")]
@@ -146,13 +162,15 @@ pub struct MetalBackend {
     libinput: Rc<LibInput>,
     libinput_fd: Rc<OwnedFd>,
     device_holder: Rc<DeviceHolder>,
-    session: Session,
+    session: MetalSession,
     pause_handler: Cell<Option<SignalHandler>>,
     resume_handler: Cell<Option<SignalHandler>>,
     ctx: CloneCell<Option<Rc<MetalRenderContext>>>,
     signaled_sync_file: CloneCell<Option<SyncFile>>,
     default_feedback: CloneCell<Option<Rc<DrmFeedback>>>,
     persistent_display_data: CopyHashMap<Rc<OutputId>, Rc<PersistentDisplayData>>,
+    idle: Cell<bool>,
+    connector_transaction: RefCell<Option<ConnectorTransaction>>,
 }
 
 impl Debug for MetalBackend {
@@ -171,11 +189,26 @@ impl MetalBackend {
             "handle libinput events",
             self.clone().handle_libinput_events(),
         );
+        let _libseat = self.state.eng.spawn(
+            "handle libseat events",
+            self.clone().handle_libseat_events(),
+        );
         if let Err(e) = self.enumerate_devices() {
             return Err(MetalError::Enumerate(Box::new(e)));
         }
         pending().await
     }
+
+    fn connector(&self, id: ConnectorId) -> Option<Rc<MetalConnector>> {
+        for dev in self.device_holder.drm_devices.lock().values() {
+            for connector in dev.connectors.lock().values() {
+                if connector.connector_id == id {
+                    return Some(connector.clone());
+                }
+            }
+        }
+        None
+    }
 }
 
 impl Backend for MetalBackend {
@@ -195,6 +228,7 @@ impl Backend for MetalBackend {
         for dev in self.device_holder.input_devices.take() {
             if let Some(dev) = dev {
                 dev.inputdev.take();
+                dev.gamepad_monitor.take();
                 dev.events.take();
                 dev.cb.take();
             }
@@ -246,15 +280,17 @@ impl Backend for MetalBackend {
     }
 
     fn set_idle(&self, idle: bool) {
+        self.idle.set(idle);
         let devices = self.device_holder.drm_devices.lock();
         for device in devices.values() {
             let mut change = device.dev.master.change();
             for connector in device.connectors.lock().values() {
                 if let Some(crtc) = connector.crtc.get() {
-                    if idle == crtc.active.value.get() {
-                        crtc.active.value.set(!idle);
+                    let active = !idle && connector.powered.get();
+                    if active != crtc.active.value.get() {
+                        crtc.active.value.set(active);
                         change.change_object(crtc.id, |c| {
-                            c.change(crtc.active.id, (!idle) as _);
+                            c.change(crtc.active.id, active as _);
                         });
                     }
                 }
@@ -282,6 +318,35 @@ impl Backend for MetalBackend {
     fn supports_presentation_feedback(&self) -> bool {
         true
     }
+
+    fn apply_connector_transaction(
+        self: Rc<Self>,
+        f: &mut dyn FnMut(),
+    ) -> Result<(), Box<dyn Error>> {
+        if self.connector_transaction.borrow().is_some() {
+            f();
+            return Ok(());
+        }
+        *self.connector_transaction.borrow_mut() = Some(Default::default());
+        f();
+        let transaction = self.connector_transaction.take().unwrap_or_default();
+        self.commit_connector_transaction(transaction)?;
+        Ok(())
+    }
+
+    fn test_connector_transaction(
+        self: Rc<Self>,
+        f: &mut dyn FnMut(),
+    ) -> Result<(), Box<dyn Error>> {
+        if self.connector_transaction.borrow().is_some() {
+            return Err("Cannot test connector changes inside of a transaction".into());
+        }
+        *self.connector_transaction.borrow_mut() = Some(Default::default());
+        f();
+        let transaction = self.connector_transaction.take().unwrap_or_default();
+        self.check_connector_transaction(transaction)?;
+        Ok(())
+    }
 }
 
 fn dup_fd(fd: c::c_int) -> Result<Rc<OwnedFd>, MetalError> {
@@ -291,7 +356,7 @@ fn dup_fd(fd: c::c_int) -> Result<Rc<OwnedFd>, MetalError> {
     }
 }
 
-pub async fn create(state: &Rc<State>) -> Result<Rc<MetalBackend>, MetalError> {
+async fn create_logind_session(state: &Rc<State>) -> Result<MetalSession, MetalError> {
     let socket = match state.dbus.system().await {
         Ok(s) => s,
         Err(e) => return Err(MetalError::DbusSystemSocket(e)),
@@ -303,6 +368,43 @@ pub async fn create(state: &Rc<State>) -> Result<Rc<MetalBackend>, MetalError> {
     if let Err(e) = session.take_control().await {
         return Err(MetalError::TakeControl(e));
     }
+    Ok(MetalSession::Logind(session))
+}
+
+fn create_libseat_session(state: &Rc<State>) -> Result<MetalSession, MetalError> {
+    let seat = match LibSeat::open() {
+        Ok(s) => s,
+        Err(e) => return Err(MetalError::LibSeat(e)),
+    };
+    Ok(MetalSession::LibSeat(LibSeatSession {
+        state: state.clone(),
+        seat: Rc::new(seat),
+        devices: Default::default(),
+    }))
+}
+
+async fn create_session(state: &Rc<State>) -> Result<MetalSession, MetalError> {
+    let mut sessions = &state.run_args.sessions[..];
+    if sessions.is_empty() {
+        sessions = &[CliSession::Logind, CliSession::Libseat];
+    }
+    for &session in sessions {
+        let res = match session {
+            CliSession::Logind => create_logind_session(state).await,
+            CliSession::Libseat => create_libseat_session(state),
+        };
+        match res {
+            Ok(s) => return Ok(s),
+            Err(e) => {
+                log::error!("Could not create {:?} session: {}", session, ErrorFmt(e));
+            }
+        }
+    }
+    Err(MetalError::NoSession)
+}
+
+pub async fn create(state: &Rc<State>) -> Result<Rc<MetalBackend>, MetalError> {
+    let session = create_session(state).await?;
     let device_holder = Rc::new(DeviceHolder {
         devices: Default::default(),
         input_devices: Default::default(),
@@ -333,25 +435,27 @@ pub async fn create(state: &Rc<State>) -> Result<Rc<MetalBackend>, MetalError> {
         signaled_sync_file: Default::default(),
         default_feedback: Default::default(),
         persistent_display_data: Default::default(),
+        idle: Cell::new(false),
+        connector_transaction: Default::default(),
     });
-    metal.pause_handler.set(Some({
-        let mtl = metal.clone();
-        let sh = metal.session.on_pause(move |p| mtl.handle_device_pause(p));
-        match sh {
-            Ok(sh) => sh,
-            Err(e) => return Err(MetalError::DevicePauseSignalHandler(e)),
-        }
-    }));
-    metal.resume_handler.set(Some({
-        let mtl = metal.clone();
-        let sh = metal
-            .session
-            .on_resume(move |p| mtl.handle_device_resume(p));
-        match sh {
-            Ok(sh) => sh,
-            Err(e) => return Err(MetalError::DeviceResumeSignalHandler(e)),
-        }
-    }));
+    if let MetalSession::Logind(session) = &metal.session {
+        metal.pause_handler.set(Some({
+            let mtl = metal.clone();
+            let sh = session.on_pause(move |p| mtl.handle_device_pause(p));
+            match sh {
+                Ok(sh) => sh,
+                Err(e) => return Err(MetalError::DevicePauseSignalHandler(e)),
+            }
+        }));
+        metal.resume_handler.set(Some({
+            let mtl = metal.clone();
+            let sh = session.on_resume(move |p| mtl.handle_device_resume(p));
+            match sh {
+                Ok(sh) => sh,
+                Err(e) => return Err(MetalError::DeviceResumeSignalHandler(e)),
+            }
+        }));
+    }
     Ok(metal)
 }
 
@@ -373,6 +477,9 @@ struct MetalInputDevice {
     transform_matrix: Cell<Option<TransformMatrix>>,
     tablet_id: Cell<Option<TabletId>>,
     tablet_pad_id: Cell<Option<TabletPadId>>,
+    gamepad: bool,
+    gamepad_monitor: Cell<Option<SpawnedFuture<()>>>,
+    slf: Weak<MetalInputDevice>,
 
     // state
     pressed_keys: SmallMap<u32, (), 5>,
@@ -381,6 +488,7 @@ struct MetalInputDevice {
     // config
     desired: InputDeviceProperties,
     effective: InputDeviceProperties,
+    gamepad_resets_idle: Cell<bool>,
 }
 
 #[derive(Default)]
@@ -392,6 +500,11 @@ struct InputDeviceProperties {
     drag_enabled: Cell<Option<bool>>,
     drag_lock_enabled: Cell<Option<bool>>,
     natural_scrolling_enabled: Cell<Option<bool>>,
+    dwt_enabled: Cell<Option<bool>>,
+    dwtp_enabled: Cell<Option<bool>>,
+    middle_emulation_enabled: Cell<Option<bool>>,
+    events_enabled: Cell<Option<bool>>,
+    leds: Cell<Option<KeyboardLeds>>,
     calibration_matrix: Cell<Option<[[f32; 3]; 2]>>,
 }
 
@@ -453,12 +566,36 @@ impl MetalInputDevice {
         if let Some(enabled) = self.desired.natural_scrolling_enabled.get() {
             self.set_natural_scrolling_enabled(enabled);
         }
+        if let Some(enabled) = self.desired.dwt_enabled.get() {
+            self.set_dwt_enabled(enabled);
+        }
+        if let Some(enabled) = self.desired.dwtp_enabled.get() {
+            self.set_dwtp_enabled(enabled);
+        }
+        if let Some(enabled) = self.desired.middle_emulation_enabled.get() {
+            self.set_middle_emulation_enabled(enabled);
+        }
         if let Some(lh) = self.desired.calibration_matrix.get() {
             self.set_calibration_matrix(lh);
         }
+        if let Some(enabled) = self.desired.events_enabled.get() {
+            self.set_events_enabled(enabled);
+        }
+        self.update_leds();
         self.fetch_effective();
     }
 
+    fn update_leds(&self) {
+        let Some(leds) = self.desired.leds.get() else {
+            return;
+        };
+        if let Some(dev) = self.inputdev.get() {
+            if dev.device().has_cap(LIBINPUT_DEVICE_CAP_KEYBOARD) {
+                dev.device().led_update(leds.0);
+            }
+        }
+    }
+
     fn fetch_effective(&self) {
         let Some(dev) = self.inputdev.get() else {
             return;
@@ -485,14 +622,31 @@ impl MetalInputDevice {
                 .natural_scrolling_enabled
                 .set(Some(device.natural_scrolling_enabled()));
         }
+        if device.dwt_available() {
+            self.effective.dwt_enabled.set(Some(device.dwt_enabled()));
+        }
+        if device.dwtp_available() {
+            self.effective.dwtp_enabled.set(Some(device.dwtp_enabled()));
+        }
+        if device.middle_emulation_available() {
+            self.effective
+                .middle_emulation_enabled
+                .set(Some(device.middle_emulation_enabled()));
+        }
         if device.has_calibration_matrix() {
             self.effective
                 .calibration_matrix
                 .set(Some(device.get_calibration_matrix()));
         }
+        if device.send_events_disable_available() {
+            self.effective
+                .events_enabled
+                .set(Some(device.send_events_enabled()));
+        }
     }
 
     fn pre_pause(&self) {
+        self.gamepad_monitor.take();
         let time_usec = self.state.now_usec();
         for (key, _) in self.pressed_keys.take() {
             self.event(InputEvent::Key {
@@ -523,6 +677,26 @@ impl MetalInputDevice {
     }
 }
 
+impl MetalInputDevice {
+    fn update_gamepad_monitor(&self) {
+        if !self.gamepad || !self.gamepad_resets_idle.get() {
+            self.gamepad_monitor.take();
+            return;
+        }
+        let Some(fd) = self.fd.get() else {
+            return;
+        };
+        if self.gamepad_monitor.is_some() {
+            return;
+        }
+        let future = self
+            .state
+            .eng
+            .spawn("monitor gamepad", monitor_gamepad(self.slf.clone(), fd));
+        self.gamepad_monitor.set(Some(future));
+    }
+}
+
 impl InputDevice for MetalInputDevice {
     fn id(&self) -> InputDeviceId {
         self.id
@@ -596,6 +770,36 @@ impl InputDevice for MetalInputDevice {
         Some(self.devnum)
     }
 
+    fn vendor_id(&self) -> Option<u32> {
+        self.inputdev.get().map(|dev| dev.device().vendor())
+    }
+
+    fn product_id(&self) -> Option<u32> {
+        self.inputdev.get().map(|dev| dev.device().product())
+    }
+
+    fn events_enabled(&self) -> Option<bool> {
+        self.effective.events_enabled.get()
+    }
+
+    fn set_events_enabled(&self, enabled: bool) {
+        self.desired.events_enabled.set(Some(enabled));
+        if let Some(dev) = self.inputdev.get() {
+            if dev.device().send_events_disable_available() {
+                dev.device().set_send_events_enabled(enabled);
+                self.effective
+                    .events_enabled
+                    .set(Some(dev.device().send_events_enabled()));
+            }
+        }
+    }
+
+    fn set_leds(&self, leds: KeyboardLeds) {
+        if self.desired.leds.replace(Some(leds)) != Some(leds) {
+            self.update_leds();
+        }
+    }
+
     fn set_tap_enabled(&self, enabled: bool) {
         self.desired.tap_enabled.set(Some(enabled));
         if let Some(dev) = self.inputdev.get() {
@@ -644,6 +848,42 @@ impl InputDevice for MetalInputDevice {
         }
     }
 
+    fn set_dwt_enabled(&self, enabled: bool) {
+        self.desired.dwt_enabled.set(Some(enabled));
+        if let Some(dev) = self.inputdev.get() {
+            if dev.device().dwt_available() {
+                dev.device().set_dwt_enabled(enabled);
+                self.effective
+                    .dwt_enabled
+                    .set(Some(dev.device().dwt_enabled()));
+            }
+        }
+    }
+
+    fn set_dwtp_enabled(&self, enabled: bool) {
+        self.desired.dwtp_enabled.set(Some(enabled));
+        if let Some(dev) = self.inputdev.get() {
+            if dev.device().dwtp_available() {
+                dev.device().set_dwtp_enabled(enabled);
+                self.effective
+                    .dwtp_enabled
+                    .set(Some(dev.device().dwtp_enabled()));
+            }
+        }
+    }
+
+    fn set_middle_emulation_enabled(&self, enabled: bool) {
+        self.desired.middle_emulation_enabled.set(Some(enabled));
+        if let Some(dev) = self.inputdev.get() {
+            if dev.device().middle_emulation_available() {
+                dev.device().set_middle_emulation_enabled(enabled);
+                self.effective
+                    .middle_emulation_enabled
+                    .set(Some(dev.device().middle_emulation_enabled()));
+            }
+        }
+    }
+
     fn left_handed(&self) -> Option<bool> {
         self.effective.left_handed.get()
     }
@@ -682,6 +922,27 @@ impl InputDevice for MetalInputDevice {
         self.effective.natural_scrolling_enabled.get()
     }
 
+    fn dwt_enabled(&self) -> Option<bool> {
+        self.effective.dwt_enabled.get()
+    }
+
+    fn dwtp_enabled(&self) -> Option<bool> {
+        self.effective.dwtp_enabled.get()
+    }
+
+    fn middle_emulation_enabled(&self) -> Option<bool> {
+        self.effective.middle_emulation_enabled.get()
+    }
+
+    fn gamepad_resets_idle(&self) -> Option<bool> {
+        self.gamepad.then(|| self.gamepad_resets_idle.get())
+    }
+
+    fn set_gamepad_resets_idle(&self, enabled: bool) {
+        self.gamepad_resets_idle.set(enabled);
+        self.update_gamepad_monitor();
+    }
+
     fn tablet_info(&self) -> Option<Box<TabletInit>> {
         let dev = self.inputdev.get()?;
         let dev = dev.device();
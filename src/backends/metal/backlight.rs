@@ -0,0 +1,81 @@
+use {
+    crate::{backend::ConnectorKernelId, video::drm::ConnectorType},
+    std::{fs, path::Path},
+};
+
+const BACKLIGHT_DIR: &str = "/sys/class/backlight";
+
+/// A backlight device in `/sys/class/backlight`.
+#[derive(Debug)]
+pub struct Backlight {
+    pub name: String,
+    pub max_brightness: u32,
+}
+
+impl Backlight {
+    /// Finds the backlight of a connector.
+    ///
+    /// A backlight whose parent device is the connector is always preferred. Internal
+    /// panels can also use backlights that do not belong to any connector. In that case
+    /// firmware interfaces are preferred over platform and raw interfaces.
+    pub fn find(card: &str, connector: ConnectorKernelId) -> Option<Self> {
+        let connector_dir = format!("{}-{}", card, connector);
+        let internal = matches!(
+            connector.ty,
+            ConnectorType::eDP | ConnectorType::LVDS | ConnectorType::DSI
+        );
+        let mut best = None;
+        for entry in fs::read_dir(BACKLIGHT_DIR).ok()? {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            let path = entry.path();
+            let Some(max_brightness) = read_u32(&path.join("max_brightness")) else {
+                continue;
+            };
+            if max_brightness == 0 {
+                continue;
+            }
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+            let backlight = Self {
+                name,
+                max_brightness,
+            };
+            if let Ok(parent) = fs::canonicalize(path.join("device")) {
+                if let Some(parent) = parent.file_name().and_then(|n| n.to_str()) {
+                    if parent == connector_dir {
+                        return Some(backlight);
+                    }
+                    if parent.starts_with("card") && parent.contains('-') {
+                        continue;
+                    }
+                }
+            }
+            if !internal {
+                continue;
+            }
+            let ty = fs::read_to_string(path.join("type")).unwrap_or_default();
+            let priority = match ty.trim() {
+                "firmware" => 0,
+                "platform" => 1,
+                _ => 2,
+            };
+            match &best {
+                Some((p, _)) if *p <= priority => {}
+                _ => best = Some((priority, backlight)),
+            }
+        }
+        best.map(|(_, b)| b)
+    }
+
+    /// Returns the current brightness of the backlight.
+    pub fn brightness(&self) -> Option<u32> {
+        read_u32(&Path::new(BACKLIGHT_DIR).join(&self.name).join("brightness"))
+    }
+}
+
+fn read_u32(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
@@ -0,0 +1,133 @@
+use {
+    crate::{
+        backend::InputEvent,
+        backends::metal::MetalInputDevice,
+        utils::{bitflags::BitflagsExt, errorfmt::ErrorFmt, oserror::OsError},
+    },
+    std::{
+        mem,
+        rc::{Rc, Weak},
+    },
+    uapi::{c, Errno, OwnedFd, _IOC, _IOR},
+};
+
+const EV_KEY: u16 = 0x01;
+const EV_ABS: u16 = 0x03;
+
+const ABS_CNT: usize = 0x40;
+
+const EVDEV_BASE: u64 = b'E' as _;
+
+const fn eviocgname(len: usize) -> u64 {
+    _IOC(uapi::_IOC_READ, EVDEV_BASE, 0x06, len as _)
+}
+
+const fn eviocgabs(abs: u16) -> u64 {
+    _IOR::<input_absinfo>(EVDEV_BASE, 0x40 + abs as u64)
+}
+
+/// An axis has to move by at least this fraction of its range before the movement
+/// counts as activity. This filters out noise from worn-out sticks.
+const AXIS_THRESHOLD_DIVISOR: i32 = 8;
+
+#[repr(C)]
+#[derive(Default)]
+struct input_absinfo {
+    value: i32,
+    minimum: i32,
+    maximum: i32,
+    fuzz: i32,
+    flat: i32,
+    resolution: i32,
+}
+
+#[derive(Copy, Clone)]
+struct Axis {
+    last: i32,
+    threshold: i32,
+}
+
+pub fn gamepad_name(fd: &OwnedFd) -> Option<String> {
+    let mut buf = [0u8; 256];
+    let res = unsafe { c::ioctl(fd.raw(), eviocgname(buf.len()), buf.as_mut_ptr()) };
+    if res < 0 {
+        return None;
+    }
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    Some(String::from_utf8_lossy(&buf[..len]).into_owned())
+}
+
+fn get_axis(fd: &OwnedFd, abs: u16) -> Result<Axis, OsError> {
+    let mut info = input_absinfo::default();
+    let res = unsafe { c::ioctl(fd.raw(), eviocgabs(abs), &mut info) };
+    if res < 0 {
+        return Err(OsError::default());
+    }
+    let range = info.maximum.saturating_sub(info.minimum);
+    Ok(Axis {
+        last: info.value,
+        threshold: (range / AXIS_THRESHOLD_DIVISOR).max(1),
+    })
+}
+
+pub async fn monitor_gamepad(dev: Weak<MetalInputDevice>, fd: Rc<OwnedFd>) {
+    let Some(state) = dev.upgrade().map(|d| d.state.clone()) else {
+        return;
+    };
+    let mut axes = [None::<Axis>; ABS_CNT];
+    let mut buf: [c::input_event; 32] = unsafe { mem::zeroed() };
+    loop {
+        match state.ring.readable(&fd).await {
+            Err(e) => {
+                log::error!(
+                    "Cannot wait for gamepad to become readable: {}",
+                    ErrorFmt(e)
+                );
+                return;
+            }
+            Ok(n) if n.intersects(c::POLLERR | c::POLLHUP) => return,
+            _ => {}
+        }
+        let mut active = false;
+        loop {
+            let events = match uapi::read(fd.raw(), &mut buf[..]) {
+                Ok([]) => break,
+                Ok(bytes) => bytes.len() / size_of::<c::input_event>(),
+                Err(Errno(c::EAGAIN)) => break,
+                Err(e) => {
+                    log::error!(
+                        "Could not read from gamepad: {}",
+                        ErrorFmt(OsError::from(e))
+                    );
+                    return;
+                }
+            };
+            for event in &buf[..events] {
+                match event.type_ {
+                    EV_KEY => active = true,
+                    EV_ABS if (event.code as usize) < ABS_CNT => {
+                        let axis = &mut axes[event.code as usize];
+                        if axis.is_none() {
+                            *axis = get_axis(&fd, event.code).ok();
+                        }
+                        if let Some(axis) = axis {
+                            if event.value.abs_diff(axis.last) >= axis.threshold as u32 {
+                                axis.last = event.value;
+                                active = true;
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if active {
+            let Some(dev) = dev.upgrade() else {
+                return;
+            };
+            dev.event(InputEvent::GamepadActivity {
+                time_usec: state.now_usec(),
+            });
+        }
+    }
+}
@@ -2,10 +2,12 @@ use {
     crate::{
         backend::{BackendEvent, ConnectorEvent},
         backends::metal::{
+            gamepad::gamepad_name,
+            session::{MetalSession, SessionDevice, SessionError},
             video::{FrontState, MetalDrmDeviceData, PendingDrmDevice},
             MetalBackend, MetalDevice, MetalError, MetalInputDevice,
         },
-        dbus::{DbusError, TRUE},
+        libseat::LibSeatEvent,
         udev::UdevDevice,
         utils::{
             bitflags::BitflagsExt,
@@ -14,12 +16,10 @@ use {
             nonblock::{set_block, set_nonblock},
         },
         video::drm::DrmMaster,
-        wire_dbus::org::freedesktop::login1::session::{
-            PauseDevice, ResumeDevice, TakeDeviceReply,
-        },
+        wire_dbus::org::freedesktop::login1::session::{PauseDevice, ResumeDevice},
     },
     bstr::ByteSlice,
-    std::{cell::Cell, rc::Rc},
+    std::{cell::Cell, ffi::CStr, rc::Rc},
     uapi::{c, OwnedFd},
 };
 
@@ -61,6 +61,7 @@ impl MetalBackend {
                 match action.to_bytes() {
                     b"add" => self.handle_device_add(dev),
                     b"change" => self.handle_device_change(dev),
+                    b"remove" => self.handle_device_remove(dev),
                     _ => None,
                 };
             }
@@ -88,11 +89,82 @@ impl MetalBackend {
         };
         match dev {
             MetalDevice::Input(id) => self.handle_input_device_resume(&id, resume.fd),
-            MetalDevice::Drm(dd) => self.handle_drm_device_resume(&dd, resume.fd),
+            MetalDevice::Drm(dd) => self.handle_drm_device_resume(&dd),
+        }
+    }
+
+    pub async fn handle_libseat_events(self: Rc<Self>) {
+        let MetalSession::LibSeat(session) = &self.session else {
+            return;
+        };
+        let fd = match session.seat.fd() {
+            Ok(fd) => Rc::new(fd),
+            Err(e) => {
+                log::error!("Could not retrieve the libseat fd: {}", ErrorFmt(e));
+                return;
+            }
+        };
+        loop {
+            if let Err(e) = self.state.ring.readable(&fd).await {
+                log::error!("Cannot wait for the libseat fd: {}", ErrorFmt(e));
+                break;
+            }
+            let events = match session.seat.dispatch(0) {
+                Ok(e) => e,
+                Err(e) => {
+                    log::error!("Could not dispatch libseat events: {}", ErrorFmt(e));
+                    break;
+                }
+            };
+            for event in events {
+                match event {
+                    LibSeatEvent::Enable => self.handle_seat_enabled(),
+                    LibSeatEvent::Disable => self.handle_seat_disabled(),
+                }
+            }
+        }
+        log::error!("libseat task exited. Future session changes will be ignored.");
+    }
+
+    fn handle_seat_disabled(self: &Rc<Self>) {
+        let MetalSession::LibSeat(session) = &self.session else {
+            return;
+        };
+        let devices: Vec<_> = self.device_holder.devices.lock().keys().copied().collect();
+        for dev in devices {
+            self.handle_device_paused(dev);
         }
+        session.seat.disable();
     }
 
-    fn handle_drm_device_resume(self: &Rc<Self>, dev: &Rc<MetalDrmDeviceData>, _fd: Rc<OwnedFd>) {
+    fn handle_seat_enabled(self: &Rc<Self>) {
+        let MetalSession::LibSeat(session) = &self.session else {
+            return;
+        };
+        let devices: Vec<_> = self
+            .device_holder
+            .devices
+            .lock()
+            .values()
+            .cloned()
+            .collect();
+        for dev in devices {
+            match dev {
+                MetalDevice::Input(id) => {
+                    // Input devices are revoked when the seat is disabled.
+                    match session.open_device(id.devnum, &id.devnode) {
+                        Ok(fd) => self.handle_input_device_resume(&id, fd),
+                        Err(e) => {
+                            log::error!("Could not reopen input device: {}", ErrorFmt(e));
+                        }
+                    }
+                }
+                MetalDevice::Drm(dd) => self.handle_drm_device_resume(&dd),
+            }
+        }
+    }
+
+    fn handle_drm_device_resume(self: &Rc<Self>, dev: &Rc<MetalDrmDeviceData>) {
         log::info!("Device resumed: {}", dev.dev.devnode.to_bytes().as_bstr());
         dev.dev.paused.set(false);
         self.break_leases(dev);
@@ -119,6 +191,16 @@ impl MetalBackend {
         self.reinit_input_device(dev, &fd);
     }
 
+    fn handle_device_remove(self: &Rc<Self>, dev: UdevDevice) -> Option<()> {
+        // Logind reports removed devices via PauseDevice signals.
+        if let MetalSession::LibSeat(_) = &self.session {
+            let devnum = dev.devnum();
+            self.handle_device_removed(devnum);
+            self.session.close_device(devnum);
+        }
+        None
+    }
+
     fn handle_device_removed(self: &Rc<Self>, dev: c::dev_t) {
         let dev = match self.device_holder.devices.remove(&dev) {
             Some(d) => d,
@@ -227,7 +309,7 @@ impl MetalBackend {
         };
         self.device_holder.pending_drm_devices.set(devnum, dev);
         let slf = self.clone();
-        self.get_device(devnum, move |res| {
+        self.get_device(devnum, devnode, move |res| {
             let dev = match slf.device_holder.pending_drm_devices.remove(&devnum) {
                 Some(d) if d.id == id => d,
                 _ => return,
@@ -314,7 +396,7 @@ impl MetalBackend {
             slots.push(None);
             slots.len() - 1
         };
-        let dev = Rc::new(MetalInputDevice {
+        let dev = Rc::new_cyclic(|slf| MetalInputDevice {
             state: self.state.clone(),
             slot,
             id: device_id,
@@ -336,12 +418,16 @@ impl MetalBackend {
             effective: Default::default(),
             tablet_id: Default::default(),
             tablet_pad_id: Default::default(),
+            gamepad: dev.is_joystick(),
+            gamepad_monitor: Default::default(),
+            slf: slf.clone(),
+            gamepad_resets_idle: Default::default(),
         });
         slots[slot] = Some(dev.clone());
         self.device_holder
             .devices
             .set(devnum, MetalDevice::Input(dev));
-        self.get_device(devnum, move |res| {
+        self.get_device(devnum, devnode, move |res| {
             let id = &slf.device_holder.devices;
             let mut slots = slf.device_holder.input_devices.borrow_mut();
             let dev = 'dev: {
@@ -361,7 +447,7 @@ impl MetalBackend {
                     return;
                 }
             };
-            if res.inactive == TRUE {
+            if res.inactive {
                 return;
             }
             slf.reinit_input_device(&dev, &res.fd);
@@ -377,6 +463,10 @@ impl MetalBackend {
         dev.fd.set(Some(fd.clone()));
         let inputdev = match self.libinput.open(dev.devnode.as_c_str()) {
             Ok(d) => Rc::new(d),
+            Err(_) if dev.gamepad => {
+                self.reinit_gamepad(dev, fd);
+                return;
+            }
             Err(_) => return,
         };
         inputdev.device().set_slot(dev.slot);
@@ -391,13 +481,25 @@ impl MetalBackend {
         dev.apply_config();
     }
 
-    fn get_device<F>(self: &Rc<Self>, dev: c::dev_t, f: F)
+    fn reinit_gamepad(&self, dev: &Rc<MetalInputDevice>, fd: &Rc<OwnedFd>) {
+        if !dev.fully_initialized.get() {
+            let name = gamepad_name(fd).unwrap_or_default();
+            dev.name.set(Rc::new(name));
+            self.state
+                .backend_events
+                .push(BackendEvent::NewInputDevice(dev.clone()));
+            dev.fully_initialized.set(true);
+        }
+        dev.update_gamepad_monitor();
+    }
+
+    fn get_device<F>(self: &Rc<Self>, dev: c::dev_t, devnode: &CStr, f: F)
     where
-        F: FnOnce(Result<&TakeDeviceReply, DbusError>) + 'static,
+        F: FnOnce(Result<SessionDevice, SessionError>) + 'static,
     {
         self.device_holder.num_pending_devices.fetch_add(1);
         let slf = self.clone();
-        self.session.get_device(dev, move |res| {
+        self.session.get_device(dev, devnode, move |res| {
             let rem = slf.device_holder.num_pending_devices.fetch_sub(1);
             f(res);
             if rem == 1 {
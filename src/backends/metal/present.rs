@@ -3,14 +3,18 @@ use {
         backend::Connector,
         backends::metal::{
             video::{
-                MetalConnector, MetalCrtc, MetalHardwareCursorChange, MetalPlane, RenderBuffer,
+                MetalConnector, MetalCrtc, MetalHardwareCursorChange, MetalPlane,
+                MetalWritebackConnector, RenderBuffer,
             },
             MetalError,
         },
         gfx_api::{
-            create_render_pass, AcquireSync, BufferResv, GfxApiOpt, GfxRenderPass, GfxTexture,
-            ReleaseSync, SyncFile,
+            create_render_pass, logical_size, AcquireSync, BufferResv, CopyTexture,
+            FramebufferRect, GfxApiOpt, GfxRenderPass, GfxTexture, ReleaseSync, SampleRect,
+            SyncFile,
         },
+        ifs::jay_screencast::ScreencastWriteback,
+        rect::Rect,
         theme::Color,
         time::Time,
         tracy::FrameName,
@@ -19,18 +23,25 @@ use {
         video::{
             dmabuf::DmaBufId,
             drm::{
-                DrmError, DrmFramebuffer, DRM_MODE_ATOMIC_NONBLOCK, DRM_MODE_PAGE_FLIP_ASYNC,
-                DRM_MODE_PAGE_FLIP_EVENT,
+                DrmCrtc, DrmError, DrmFramebuffer, DrmObject, DRM_MODE_ATOMIC_ALLOW_MODESET,
+                DRM_MODE_ATOMIC_NONBLOCK, DRM_MODE_PAGE_FLIP_ASYNC, DRM_MODE_PAGE_FLIP_EVENT,
             },
         },
     },
-    std::rc::{Rc, Weak},
-    uapi::c,
+    jay_config::video::Transform,
+    std::{
+        cell::Cell,
+        rc::{Rc, Weak},
+    },
+    uapi::{c, OwnedFd},
 };
 
 struct Latched {
     pass: GfxRenderPass,
     damage: u64,
+    software_cursor_damage: u64,
+    cursor_rect: Option<Rect>,
+    cursor_only: bool,
 }
 
 #[derive(Debug)]
@@ -67,6 +78,14 @@ pub struct PresentFb {
     sync_file: Option<SyncFile>,
 }
 
+pub struct WritebackJob {
+    connector: Rc<MetalWritebackConnector>,
+    attach: bool,
+    fb: DrmFramebuffer,
+    out_fence: Cell<c::c_int>,
+    capture: ScreencastWriteback,
+}
+
 enum CursorProgramming {
     Enable {
         plane: Rc<MetalPlane>,
@@ -85,12 +104,27 @@ enum CursorProgramming {
 pub const DEFAULT_PRE_COMMIT_MARGIN: u64 = 16_000_000; // 16ms
 pub const DEFAULT_POST_COMMIT_MARGIN: u64 = 1_500_000; // 1.5ms;
 pub const POST_COMMIT_MARGIN_DELTA: u64 = 500_000; // 500us
+pub const DEFAULT_RENDER_MARGIN: u64 = 0;
 
 impl MetalConnector {
     pub fn schedule_present(&self) {
         self.present_trigger.trigger();
     }
 
+    /// Returns the time that must be reserved for rendering before the page flip.
+    ///
+    /// This is the measured render time plus the configured render margin.
+    fn render_margin(&self) -> u64 {
+        let configured = self.display.borrow().persistent.render_margin.get();
+        self.pre_commit_margin.get().saturating_add(configured)
+    }
+
+    pub fn update_render_margin(&self) {
+        if let Some(output) = self.state.root.outputs.get(&self.connector_id) {
+            output.render_margin_ns.set(self.render_margin());
+        }
+    }
+
     pub async fn present_loop(self: Rc<Self>) {
         #[cfg_attr(not(feature = "tracy"), expect(unused_variables))]
         let frame_name = FrameName::get(&self.kernel_id().to_string());
@@ -102,6 +136,11 @@ impl MetalConnector {
                 continue;
             }
             let Some(node) = self.state.root.outputs.get(&self.connector_id) else {
+                if let Some(source) = self.mirror_source.get() {
+                    if let Err(e) = self.present_mirror(&source).await {
+                        log::error!("Could not present mirror: {}", ErrorFmt(e));
+                    }
+                }
                 continue;
             };
             let mut expected_sequence = self.sequence.get() + 1;
@@ -111,7 +150,7 @@ impl MetalConnector {
                 let next_present = self
                     .next_vblank_nsec
                     .get()
-                    .saturating_sub(self.pre_commit_margin.get())
+                    .saturating_sub(self.render_margin())
                     .saturating_sub(self.post_commit_margin.get());
                 if start.nsec() < next_present {
                     self.state.ring.timeout(next_present).await.unwrap();
@@ -144,6 +183,7 @@ impl MetalConnector {
                 self.pre_commit_margin_decay.add(max);
                 self.pre_commit_margin
                     .set(self.pre_commit_margin_decay.get());
+                self.update_render_margin();
                 max = 0;
             }
         }
@@ -188,11 +228,15 @@ impl MetalConnector {
         let buffer = &buffers[self.next_buffer.get() % buffers.len()];
         let mut present_fb = None;
         let mut direct_scanout_id = None;
+        let mut render_start = None;
         if let Some(latched) = &latched {
-            let fb = self.prepare_present_fb(buffer, &plane, &latched.pass, true)?;
+            render_start = Some(Time::now_unchecked());
+            let try_direct_scanout = !latched.cursor_only;
+            let fb = self.prepare_present_fb(buffer, &plane, &latched.pass, try_direct_scanout)?;
             direct_scanout_id = fb.direct_scanout_data.as_ref().map(|d| d.dma_buf_id);
             present_fb = Some(fb);
         }
+        let mut writeback = self.prepare_writeback(&node, &crtc);
         self.perform_screencopies(&present_fb, &node);
         if let Some(sync_file) = self.cursor_sync_file.take() {
             if let Err(e) = self.state.ring.readable(&sync_file).await {
@@ -203,13 +247,41 @@ impl MetalConnector {
             }
         }
         self.await_present_fb(present_fb.as_mut()).await;
+        if let (Some(start), Some(fb)) = (render_start, &present_fb) {
+            if fb.direct_scanout_data.is_none() {
+                let elapsed = start.elapsed().as_nanos() as u64;
+                node.stats.render_time.add(elapsed);
+            }
+        }
         let mut res = self.program_connector(
             version,
             &crtc,
             &plane,
             cursor_programming.as_ref(),
             present_fb.as_ref(),
+            writeback.as_ref(),
         );
+        if let Err(e) = &res {
+            if writeback.take().is_some() {
+                let e = ErrorFmt(e).to_string();
+                res = self.program_connector(
+                    version,
+                    &crtc,
+                    &plane,
+                    cursor_programming.as_ref(),
+                    present_fb.as_ref(),
+                    None,
+                );
+                if res.is_ok() {
+                    log::warn!(
+                        "Could not use writeback connector on {}: {}",
+                        self.kernel_id(),
+                        e,
+                    );
+                    self.writeback_unsupported.set(true);
+                }
+            }
+        }
         if res.is_err() {
             if let Some(dsd_id) = direct_scanout_id {
                 let fb = self.prepare_present_fb(
@@ -226,6 +298,7 @@ impl MetalConnector {
                     &plane,
                     cursor_programming.as_ref(),
                     present_fb.as_ref(),
+                    None,
                 );
                 if res.is_ok() {
                     let mut cache = self.scanout_buffers.borrow_mut();
@@ -248,24 +321,16 @@ impl MetalConnector {
             }
             Err(e)
         } else {
-            macro_rules! apply_change {
-                ($prop:expr) => {
-                    if let Some(v) = $prop.pending_value.take() {
-                        $prop.value.set(v);
-                    }
-                };
-            }
-            apply_change!(plane.src_w);
-            apply_change!(plane.src_h);
-            apply_change!(plane.crtc_x);
-            apply_change!(plane.crtc_y);
-            apply_change!(plane.crtc_w);
-            apply_change!(plane.crtc_h);
+            apply_plane_changes(&plane);
             if let Some(fb) = present_fb {
                 self.presentation_is_zero_copy
                     .set(fb.direct_scanout_data.is_some());
                 if fb.direct_scanout_data.is_none() {
                     self.next_buffer.fetch_add(1);
+                    self.cached_frame.set(Some(fb.tex.clone()));
+                    self.damage_mirrors();
+                } else {
+                    self.cached_frame.set(None);
                 }
                 self.next_framebuffer.set(Some(fb));
             }
@@ -273,15 +338,87 @@ impl MetalConnector {
                 self.cursor_swap_buffer.set(false);
                 self.cursor_front_buffer.fetch_add(1);
             }
+            if let Some(writeback) = writeback {
+                self.submit_writeback(&crtc, writeback);
+            }
             self.can_present.set(false);
             if let Some(latched) = latched {
                 self.has_damage.fetch_sub(latched.damage);
+                self.software_cursor_damage
+                    .fetch_sub(latched.software_cursor_damage);
+                if node.software_cursor_damage.get() == latched.cursor_rect {
+                    node.software_cursor_damage.take();
+                }
             }
             self.cursor_changed.set(false);
             Ok(())
         }
     }
 
+    /// Presents the most recent frame of the source, scaled to the mode of this connector.
+    async fn present_mirror(&self, source: &MetalConnector) -> Result<(), MetalError> {
+        let version = self.version.get();
+        if !self.can_present.get() {
+            return Ok(());
+        }
+        if !self.backend.check_render_context(&self.dev) {
+            return Ok(());
+        }
+        let Some(crtc) = self.crtc.get() else {
+            return Ok(());
+        };
+        if !crtc.active.value.get() {
+            return Ok(());
+        }
+        let Some(plane) = self.primary_plane.get() else {
+            return Ok(());
+        };
+        let Some(buffers) = self.buffers.get() else {
+            return Ok(());
+        };
+        let damage = self.has_damage.get();
+        let cursor_programming = self.compute_cursor_programming();
+        let mut present_fb = None;
+        if damage > 0 {
+            if let Some(frame) = source.cached_frame.get() {
+                let buffer = &buffers[self.next_buffer.get() % buffers.len()];
+                let pass = mirror_pass(&plane, frame);
+                let mut fb = self.prepare_present_fb(buffer, &plane, &pass, false)?;
+                self.await_present_fb(Some(&mut fb)).await;
+                present_fb = Some(fb);
+            }
+        }
+        if cursor_programming.is_none() && present_fb.is_none() {
+            self.has_damage.fetch_sub(damage);
+            return Ok(());
+        }
+        let res = self.program_connector(
+            version,
+            &crtc,
+            &plane,
+            cursor_programming.as_ref(),
+            present_fb.as_ref(),
+            None,
+        );
+        if let Err(e) = res {
+            if let MetalError::Commit(DrmError::Atomic(OsError(c::EACCES))) = e {
+                log::debug!("Could not perform atomic commit, likely because we're no longer the DRM master");
+                return Ok(());
+            }
+            return Err(e);
+        }
+        apply_plane_changes(&plane);
+        if let Some(fb) = present_fb {
+            self.presentation_is_zero_copy.set(false);
+            self.next_buffer.fetch_add(1);
+            self.next_framebuffer.set(Some(fb));
+        }
+        self.can_present.set(false);
+        self.has_damage.fetch_sub(damage);
+        self.cursor_changed.set(false);
+        Ok(())
+    }
+
     async fn await_present_fb(&self, new_fb: Option<&mut PresentFb>) {
         let Some(fb) = new_fb else {
             return;
@@ -308,6 +445,7 @@ impl MetalConnector {
         plane: &Rc<MetalPlane>,
         cursor: Option<&CursorProgramming>,
         new_fb: Option<&PresentFb>,
+        writeback: Option<&WritebackJob>,
     ) -> Result<(), MetalError> {
         zone!("program_connector");
         let mut changes = self.master.change();
@@ -403,21 +541,34 @@ impl MetalConnector {
                 }
             }
         }
+        let mut flags = DRM_MODE_ATOMIC_NONBLOCK | DRM_MODE_PAGE_FLIP_EVENT;
+        if let Some(writeback) = writeback {
+            try_async_flip = false;
+            let connector = &writeback.connector;
+            changes.change_object(connector.id, |c| {
+                if writeback.attach {
+                    // Attaching a connector to a CRTC always requires a modeset.
+                    flags |= DRM_MODE_ATOMIC_ALLOW_MODESET;
+                    c.change(connector.crtc_id.id, crtc.id.0 as _);
+                }
+                c.change(connector.fb_id, writeback.fb.id().0 as _);
+                c.change(connector.out_fence_ptr, writeback.out_fence.as_ptr() as u64);
+            });
+        }
         if version != self.version.get() {
             return Err(MetalError::OutOfDate);
         }
         let mut res;
         'commit: {
-            const FLAGS: u32 = DRM_MODE_ATOMIC_NONBLOCK | DRM_MODE_PAGE_FLIP_EVENT;
             if try_async_flip {
-                res = changes.commit(FLAGS | DRM_MODE_PAGE_FLIP_ASYNC, 0);
+                res = changes.commit(flags | DRM_MODE_PAGE_FLIP_ASYNC, 0);
                 if res.is_ok() {
                     self.presentation_is_sync.set(false);
                     break 'commit;
                 }
             }
             self.presentation_is_sync.set(true);
-            res = changes.commit(FLAGS, 0);
+            res = changes.commit(flags, 0);
         }
         res.map_err(MetalError::Commit)
     }
@@ -426,7 +577,7 @@ impl MetalConnector {
         if !self.cursor_damage.take() {
             return Ok(());
         }
-        if self.cursor_plane.is_none() {
+        if self.cursor_plane.is_none() || self.mirrors.is_not_empty() {
             return Ok(());
         }
         let buffers = self.cursor_buffers.get().unwrap();
@@ -493,9 +644,11 @@ impl MetalConnector {
             return None;
         }
         node.global.connector.damaged.set(false);
+        let software_cursor_damage = self.software_cursor_damage.get();
+        let cursor_rect = node.software_cursor_damage.get();
         let render_hw_cursor = !self.cursor_enabled.get();
         let mode = node.global.mode.get();
-        let pass = create_render_pass(
+        let mut pass = create_render_pass(
             (mode.width, mode.height),
             &**node,
             &self.state,
@@ -505,10 +658,71 @@ impl MetalConnector {
             render_hw_cursor,
             node.has_fullscreen(),
             true,
+            false,
             node.global.persistent.transform.get(),
             Some(&self.state.damage_visualizer),
         );
-        Some(Latched { pass, damage })
+        if self.hdr_active.get() {
+            pass.convert_to_hdr(&self.state.color_manager);
+        }
+        let mut cursor_only = false;
+        if software_cursor_damage == damage {
+            if let Some(rect) = cursor_rect {
+                cursor_only = self.restrict_to_cursor(node, &mut pass, rect);
+            }
+        }
+        Some(Latched {
+            pass,
+            damage,
+            software_cursor_damage,
+            cursor_rect,
+            cursor_only,
+        })
+    }
+
+    /// Turns the pass into a pass that only redraws the area of the software cursor on top
+    /// of the previously rendered frame.
+    ///
+    /// Returns `false` if the full frame must be rendered.
+    fn restrict_to_cursor(&self, node: &OutputNode, pass: &mut GfxRenderPass, rect: Rect) -> bool {
+        let Some(frame) = self.cached_frame.get() else {
+            return false;
+        };
+        if self.state.damage_visualizer.enabled() || self.state.idle.in_grace_period.get() {
+            return false;
+        }
+        let mode = node.global.mode.get();
+        if frame.size() != (mode.width, mode.height) {
+            return false;
+        }
+        let transform = node.global.persistent.transform.get();
+        let scale = node.global.persistent.scale.get().to_f64();
+        let (width, height) = logical_size((mode.width, mode.height), transform);
+        let px = |v: i32, round: fn(f64) -> f64| round(v as f64 * scale) as f32;
+        let clip = FramebufferRect::new(
+            px(rect.x1() - 1, f64::floor),
+            px(rect.y1() - 1, f64::floor),
+            px(rect.x2() + 1, f64::ceil),
+            px(rect.y2() + 1, f64::ceil),
+            transform,
+            width as f32,
+            height as f32,
+        );
+        if !pass.clip(&clip) {
+            return false;
+        }
+        let copy = CopyTexture {
+            tex: frame,
+            source: SampleRect::identity(),
+            target: FramebufferRect::new(0.0, 0.0, 1.0, 1.0, Transform::None, 1.0, 1.0),
+            buffer_resv: None,
+            acquire_sync: AcquireSync::Unnecessary,
+            release_sync: ReleaseSync::None,
+            alpha: None,
+            color_transform: None,
+        };
+        pass.ops.insert(0, GfxApiOpt::CopyTexture(copy));
+        true
     }
 
     fn trim_scanout_cache(&self) {
@@ -703,7 +917,11 @@ impl MetalConnector {
             // until the FB is no longer being scanned out, but if a notification pops up
             // then we must be able to disable direct scanout immediately.
             // https://gitlab.freedesktop.org/drm/amd/-/issues/3186
-            && self.dev.is_render_device();
+            && self.dev.is_render_device()
+            // client buffers would have to be converted to the HDR color space.
+            && !self.hdr_active.get()
+            // mirrors copy the rendered frame.
+            && self.mirrors.is_empty();
         let mut direct_scanout_data = None;
         if try_direct_scanout {
             direct_scanout_data = self.prepare_direct_scanout(&pass, plane);
@@ -748,6 +966,76 @@ impl MetalConnector {
         })
     }
 
+    fn prepare_writeback(&self, node: &OutputNode, crtc: &Rc<MetalCrtc>) -> Option<WritebackJob> {
+        if self.writeback_unsupported.get()
+            || self.try_async_flip()
+            || node.screencasts.is_empty()
+            || !self.dev.is_render_device()
+            || self.hdr_active.get()
+        {
+            return None;
+        }
+        let mut attach = false;
+        let connector = 'connector: {
+            let mut free = None;
+            for connector in self.dev.writeback_connectors.values() {
+                let attached = connector.crtc_id.value.get();
+                if attached == crtc.id {
+                    break 'connector connector.clone();
+                }
+                if attached == DrmCrtc::NONE && connector.crtcs.contains_key(&crtc.id) {
+                    free = Some(connector);
+                }
+            }
+            attach = true;
+            free?.clone()
+        };
+        let capture = node.claim_writeback_buffer(&connector.formats)?;
+        let fb = match self.dev.master.add_fb(&capture.dmabuf, None) {
+            Ok(fb) => fb,
+            Err(e) => {
+                log::warn!(
+                    "Could not import screencast buffer for writeback: {}",
+                    ErrorFmt(e)
+                );
+                self.writeback_unsupported.set(true);
+                return None;
+            }
+        };
+        Some(WritebackJob {
+            connector,
+            attach,
+            fb,
+            out_fence: Cell::new(-1),
+            capture,
+        })
+    }
+
+    fn submit_writeback(&self, crtc: &MetalCrtc, writeback: WritebackJob) {
+        if writeback.attach {
+            writeback.connector.crtc_id.value.set(crtc.id);
+        }
+        writeback.capture.committed();
+        self.writeback_jobs.push(writeback);
+    }
+
+    pub async fn writeback_loop(self: Rc<Self>) {
+        loop {
+            let writeback = self.writeback_jobs.pop().await;
+            let fence = writeback.out_fence.get();
+            if fence == -1 {
+                log::error!("Kernel did not return a writeback fence");
+                continue;
+            }
+            let fence = Rc::new(OwnedFd::new(fence));
+            if let Err(e) = self.state.ring.readable(&fence).await {
+                log::error!("Could not wait for writeback to complete: {}", ErrorFmt(e));
+                continue;
+            }
+            writeback.capture.complete();
+        }
+    }
+
     fn perform_screencopies(&self, new_fb: &Option<PresentFb>, output: &OutputNode) {
         let active_fb;
         let fb = match &new_fb {
@@ -789,3 +1077,53 @@ impl MetalConnector {
         }
     }
 }
+
+fn apply_plane_changes(plane: &MetalPlane) {
+    macro_rules! apply_change {
+        ($prop:expr) => {
+            if let Some(v) = $prop.pending_value.take() {
+                $prop.value.set(v);
+            }
+        };
+    }
+    apply_change!(plane.src_w);
+    apply_change!(plane.src_h);
+    apply_change!(plane.crtc_x);
+    apply_change!(plane.crtc_y);
+    apply_change!(plane.crtc_w);
+    apply_change!(plane.crtc_h);
+}
+
+/// Creates a pass that scales the frame to the plane while preserving its aspect ratio.
+fn mirror_pass(plane: &MetalPlane, frame: Rc<dyn GfxTexture>) -> GfxRenderPass {
+    let width = plane.mode_w.get() as f64;
+    let height = plane.mode_h.get() as f64;
+    let (frame_width, frame_height) = frame.size();
+    let scale = (width / frame_width.max(1) as f64).min(height / frame_height.max(1) as f64);
+    let target_width = frame_width as f64 * scale;
+    let target_height = frame_height as f64 * scale;
+    let x1 = ((width - target_width) / 2.0).round();
+    let y1 = ((height - target_height) / 2.0).round();
+    let copy = CopyTexture {
+        tex: frame,
+        source: SampleRect::identity(),
+        target: FramebufferRect::new(
+            x1 as f32,
+            y1 as f32,
+            (x1 + target_width) as f32,
+            (y1 + target_height) as f32,
+            Transform::None,
+            width as f32,
+            height as f32,
+        ),
+        buffer_resv: None,
+        acquire_sync: AcquireSync::Unnecessary,
+        release_sync: ReleaseSync::None,
+        alpha: None,
+        color_transform: None,
+    };
+    GfxRenderPass {
+        ops: vec![GfxApiOpt::CopyTexture(copy)],
+        clear: Some(Color::SOLID_BLACK),
+    }
+}
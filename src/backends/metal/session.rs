@@ -0,0 +1,126 @@
+use {
+    crate::{
+        dbus::{DbusError, TRUE},
+        libseat::{LibSeat, LibSeatError},
+        logind::Session,
+        state::State,
+        utils::copyhashmap::CopyHashMap,
+    },
+    std::{ffi::CStr, fs, rc::Rc},
+    thiserror::Error,
+    uapi::{c, OwnedFd},
+};
+
+#[derive(Debug, Error)]
+pub enum SessionError {
+    #[error(transparent)]
+    Dbus(#[from] DbusError),
+    #[error(transparent)]
+    LibSeat(#[from] LibSeatError),
+    #[error("Could not write the brightness")]
+    WriteBrightness(#[source] std::io::Error),
+}
+
+/// A device that was opened via the session.
+pub struct SessionDevice {
+    pub fd: Rc<OwnedFd>,
+    pub inactive: bool,
+}
+
+/// The session that grants access to DRM and input devices.
+pub enum MetalSession {
+    Logind(Session),
+    LibSeat(LibSeatSession),
+}
+
+pub struct LibSeatSession {
+    pub state: Rc<State>,
+    pub seat: Rc<LibSeat>,
+    pub devices: CopyHashMap<c::dev_t, c::c_int>,
+}
+
+impl MetalSession {
+    pub fn get_device<F>(&self, dev: c::dev_t, devnode: &CStr, f: F)
+    where
+        F: FnOnce(Result<SessionDevice, SessionError>) + 'static,
+    {
+        match self {
+            MetalSession::Logind(s) => s.get_device(dev, move |res| {
+                let res = res.map(|r| SessionDevice {
+                    fd: r.fd.clone(),
+                    inactive: r.inactive == TRUE,
+                });
+                f(res.map_err(|e| e.into()))
+            }),
+            MetalSession::LibSeat(s) => {
+                let res = s.open_device(dev, devnode).map(|fd| SessionDevice {
+                    fd,
+                    inactive: false,
+                });
+                // Logind replies asynchronously. Do the same here so that callers see
+                // the same ordering with both implementations.
+                s.state.run_toplevel.schedule(move || f(res));
+            }
+        }
+    }
+
+    pub fn device_paused(&self, major: u32, minor: u32) {
+        if let MetalSession::Logind(s) = self {
+            s.device_paused(major, minor);
+        }
+    }
+
+    pub fn close_device(&self, dev: c::dev_t) {
+        if let MetalSession::LibSeat(s) = self {
+            if let Some(id) = s.devices.remove(&dev) {
+                s.seat.close_device(id);
+            }
+        }
+    }
+
+    pub fn set_brightness<F>(&self, subsystem: &str, name: &str, brightness: u32, f: F)
+    where
+        F: FnOnce(Result<(), SessionError>) + 'static,
+    {
+        match self {
+            MetalSession::Logind(s) => {
+                s.set_brightness(subsystem, name, brightness, move |res| {
+                    f(res.map(|_| ()).map_err(|e| e.into()))
+                });
+            }
+            MetalSession::LibSeat(_) => {
+                // libseat has no brightness interface. This only works if the user has
+                // write access to the sysfs file, e.g. via udev rules.
+                let path = format!("/sys/class/{}/{}/brightness", subsystem, name);
+                let res = fs::write(path, brightness.to_string());
+                f(res.map_err(SessionError::WriteBrightness));
+            }
+        }
+    }
+
+    pub fn switch_to<F>(&self, vtnr: u32, f: F)
+    where
+        F: FnOnce(Result<(), SessionError>) + 'static,
+    {
+        match self {
+            MetalSession::Logind(s) => {
+                s.switch_to(vtnr, move |res| f(res.map(|_| ()).map_err(|e| e.into())));
+            }
+            MetalSession::LibSeat(s) => {
+                f(s.seat.switch_session(vtnr as _).map_err(|e| e.into()));
+            }
+        }
+    }
+}
+
+impl LibSeatSession {
+    /// Opens a device, closing the previous instance of the device if any.
+    pub fn open_device(&self, dev: c::dev_t, devnode: &CStr) -> Result<Rc<OwnedFd>, SessionError> {
+        if let Some(id) = self.devices.remove(&dev) {
+            self.seat.close_device(id);
+        }
+        let (id, fd) = self.seat.open_device(devnode)?;
+        self.devices.set(dev, id);
+        Ok(Rc::new(fd))
+    }
+}
@@ -4,19 +4,22 @@ use {
         async_engine::{Phase, SpawnedFuture},
         backend::{
             BackendDrmDevice, BackendDrmLease, BackendDrmLessee, BackendEvent, Connector,
-            ConnectorEvent, ConnectorId, ConnectorKernelId, DrmDeviceId, HardwareCursor,
-            HardwareCursorUpdate, Mode, MonitorInfo,
+            ConnectorEvent, ConnectorId, ConnectorKernelId, ConnectorProperty, DrmDeviceId,
+            EdidInfo, EdidRangeLimits, GammaLut, HardwareCursor, HardwareCursorUpdate, Mode,
+            MonitorInfo,
         },
         backends::metal::{
+            backlight::Backlight,
             present::{
-                DirectScanoutCache, PresentFb, DEFAULT_POST_COMMIT_MARGIN,
-                DEFAULT_PRE_COMMIT_MARGIN, POST_COMMIT_MARGIN_DELTA,
+                DirectScanoutCache, PresentFb, WritebackJob, DEFAULT_POST_COMMIT_MARGIN,
+                DEFAULT_PRE_COMMIT_MARGIN, DEFAULT_RENDER_MARGIN, POST_COMMIT_MARGIN_DELTA,
             },
             MetalBackend, MetalError,
         },
+        cmm::NamedPrimaries,
         drm_feedback::DrmFeedback,
-        edid::{CtaDataBlock, Descriptor, EdidExtension},
-        format::{Format, ARGB8888, XRGB8888},
+        edid::{CtaDataBlock, CtaHdrStaticMetadataDataBlock, Descriptor, EdidExtension, EdidFile},
+        format::{Format, ARGB8888, XRGB2101010, XRGB8888},
         gfx_api::{
             needs_render_usage, AcquireSync, GfxContext, GfxFramebuffer, GfxTexture, ReleaseSync,
             SyncFile,
@@ -32,15 +35,18 @@ use {
             asyncevent::AsyncEvent, bitflags::BitflagsExt, cell_ext::CellExt, clonecell::CloneCell,
             copyhashmap::CopyHashMap, errorfmt::ErrorFmt, geometric_decay::GeometricDecay,
             numcell::NumCell, on_change::OnChange, opaque_cell::OpaqueCell, oserror::OsError,
+            queue::AsyncQueue,
         },
         video::{
             dmabuf::DmaBufId,
             drm::{
-                drm_mode_modeinfo, Change, ConnectorStatus, ConnectorType, DrmBlob, DrmConnector,
+                drm_color_lut, drm_mode_modeinfo, hdr_metadata_infoframe, hdr_output_metadata,
+                Change, ConnectorStatus, ConnectorType, DrmBlob, DrmConnector, DrmConnectorInfo,
                 DrmCrtc, DrmEncoder, DrmError, DrmEvent, DrmFramebuffer, DrmLease, DrmMaster,
                 DrmModeInfo, DrmObject, DrmPlane, DrmProperty, DrmPropertyDefinition,
                 DrmPropertyType, DrmVersion, PropBlob, DRM_CLIENT_CAP_ATOMIC,
-                DRM_MODE_ATOMIC_ALLOW_MODESET,
+                DRM_CLIENT_CAP_WRITEBACK_CONNECTORS, DRM_MODE_ATOMIC_ALLOW_MODESET,
+                HDMI_EOTF_SMPTE_ST2084, HDMI_STATIC_METADATA_TYPE1,
             },
             gbm::{GbmBo, GbmDevice, GBM_BO_USE_LINEAR, GBM_BO_USE_RENDERING, GBM_BO_USE_SCANOUT},
             Modifier, INVALID_MODIFIER,
@@ -51,16 +57,18 @@ use {
     bstr::{BString, ByteSlice},
     indexmap::{indexset, IndexMap, IndexSet},
     isnt::std_1::collections::IsntHashMap2Ext,
-    jay_config::video::GfxApi,
+    jay_config::video::{CustomMode, GfxApi},
     std::{
         any::Any,
         cell::{Cell, RefCell},
         collections::hash_map::Entry,
+        error::Error,
         ffi::CString,
         fmt::{Debug, Formatter},
         mem,
         ops::DerefMut,
-        rc::Rc,
+        path::Path,
+        rc::{Rc, Weak},
     },
     uapi::{
         c::{self, dev_t},
@@ -90,6 +98,7 @@ pub struct MetalDrmDevice {
     pub crtcs: AHashMap<DrmCrtc, Rc<MetalCrtc>>,
     pub encoders: AHashMap<DrmEncoder, Rc<MetalEncoder>>,
     pub planes: AHashMap<DrmPlane, Rc<MetalPlane>>,
+    pub writeback_connectors: AHashMap<DrmConnector, Rc<MetalWritebackConnector>>,
     pub _min_width: u32,
     pub _max_width: u32,
     pub _min_height: u32,
@@ -167,6 +176,10 @@ impl BackendDrmDevice for MetalDrmDevice {
         Some(self.id) == self.backend.ctx.get().map(|c| c.dev_id)
     }
 
+    fn gfx_ctx(&self) -> Option<Rc<dyn GfxContext>> {
+        Some(self.ctx.get().gfx.clone())
+    }
+
     fn create_lease(
         self: Rc<Self>,
         lessee: Rc<dyn BackendDrmLessee>,
@@ -316,8 +329,19 @@ pub struct MetalDrmDeviceData {
 #[derive(Debug)]
 pub struct PersistentDisplayData {
     pub mode: RefCell<Option<DrmModeInfo>>,
+    pub custom_modes: RefCell<Vec<DrmModeInfo>>,
     pub vrr_requested: Cell<bool>,
+    pub hdr_requested: Cell<bool>,
     pub format: Cell<&'static Format>,
+    pub render_margin: Cell<u64>,
+}
+
+/// The connector properties required to drive a display in HDR10 mode.
+#[derive(Debug)]
+pub struct HdrProperties {
+    pub colorspace: MutableProperty<u64>,
+    pub colorspace_bt2020: u64,
+    pub hdr_output_metadata: MutableProperty<DrmBlob>,
 }
 
 #[derive(Debug)]
@@ -332,9 +356,14 @@ pub struct ConnectorDisplayData {
     pub non_desktop_effective: bool,
     pub vrr_capable: bool,
     pub _vrr_refresh_max_nsec: u64,
+    pub hdr_capable: bool,
+    pub hdr_metadata: Option<CtaHdrStaticMetadataDataBlock>,
+    pub hdr_props: Option<HdrProperties>,
 
     pub connector_id: ConnectorKernelId,
     pub output_id: Rc<OutputId>,
+    pub edid: Option<Rc<EdidInfo>>,
+    pub properties: Rc<Vec<ConnectorProperty>>,
 
     pub connection: ConnectorStatus,
     pub mm_width: u32,
@@ -346,6 +375,15 @@ impl ConnectorDisplayData {
     fn should_enable_vrr(&self) -> bool {
         self.persistent.vrr_requested.get() && self.vrr_capable
     }
+
+    fn should_enable_hdr(&self) -> bool {
+        self.persistent.hdr_requested.get() && self.hdr_capable
+    }
+
+    fn use_mode(&mut self, mode: DrmModeInfo) {
+        self.refresh = (1_000_000_000_000u64 / (mode.refresh_rate_millihz() as u64)) as u32;
+        self.mode = Some(mode);
+    }
 }
 
 linear_ids!(MetalLeaseIds, MetalLeaseId, u64);
@@ -438,12 +476,18 @@ pub struct MetalConnector {
     pub next_buffer: NumCell<usize>,
 
     pub enabled: Cell<bool>,
+    pub powered: Cell<bool>,
+    pub gamma_lut: CloneCell<Option<Rc<GammaLut>>>,
+    pub hdr_active: Cell<bool>,
+    pub hdr_metadata_blob: CloneCell<Option<Rc<PropBlob>>>,
     pub non_desktop_override: Cell<Option<bool>>,
 
     pub lease: Cell<Option<MetalLeaseId>>,
 
     pub can_present: Cell<bool>,
     pub has_damage: NumCell<u64>,
+    pub software_cursor_damage: NumCell<u64>,
+    pub cached_frame: CloneCell<Option<Rc<dyn GfxTexture>>>,
     pub cursor_changed: Cell<bool>,
     pub cursor_damage: Cell<bool>,
     pub next_vblank_nsec: Cell<u64>,
@@ -477,6 +521,8 @@ pub struct MetalConnector {
 
     pub tearing_requested: Cell<bool>,
     pub try_switch_format: Cell<bool>,
+    pub mode_fallback: CloneCell<Option<Rc<ModeFallback>>>,
+    pub reported_mode_fallback: Cell<Option<(Mode, Mode)>>,
 
     pub version: NumCell<u64>,
     pub sequence: Cell<u64>,
@@ -489,6 +535,21 @@ pub struct MetalConnector {
     pub vblank_miss_this_sec: NumCell<u32>,
     pub presentation_is_sync: Cell<bool>,
     pub presentation_is_zero_copy: Cell<bool>,
+    pub writeback_unsupported: Cell<bool>,
+    pub writeback_jobs: AsyncQueue<WritebackJob>,
+
+    pub mirror_source: CloneCell<Option<Rc<MetalConnector>>>,
+    pub mirrors: CopyHashMap<ConnectorId, Weak<MetalConnector>>,
+
+    /// The last brightness requested from logind that might not yet be visible in sysfs.
+    pub backlight_pending: Rc<Cell<Option<u32>>>,
+}
+
+/// A mode that was chosen because the requested mode could not be applied.
+#[derive(Debug)]
+pub struct ModeFallback {
+    pub requested: DrmModeInfo,
+    pub chosen: DrmModeInfo,
 }
 
 impl Debug for MetalConnector {
@@ -556,6 +617,7 @@ impl HardwareCursorUpdate for MetalHardwareCursorChange<'_> {
 
 pub struct ConnectorFutures {
     pub _present: SpawnedFuture<()>,
+    pub _writeback: SpawnedFuture<()>,
 }
 
 impl Debug for ConnectorFutures {
@@ -565,6 +627,108 @@ impl Debug for ConnectorFutures {
 }
 
 impl MetalConnector {
+    fn find_backlight(&self) -> Option<Backlight> {
+        let devnode = self.dev.devnode.to_str().ok()?;
+        let card = Path::new(devnode).file_name()?.to_str()?;
+        Backlight::find(card, self.kernel_id())
+    }
+
+    fn send_mode_changed(&self) {
+        let mode = self.display.borrow().mode.as_ref().map(|m| m.to_backend());
+        if let Some(mode) = mode {
+            self.send_event(ConnectorEvent::ModeChanged(mode));
+        }
+    }
+
+    fn apply_gamma_lut(&self, crtc: &MetalCrtc, changes: &mut Change) -> Result<(), DrmError> {
+        let Some(prop) = crtc.gamma_lut else {
+            return Ok(());
+        };
+        let blob = match self.gamma_lut.get() {
+            Some(lut) if lut.red.len() == crtc.gamma_lut_size as usize => {
+                let entries: Vec<_> = (0..lut.red.len())
+                    .map(|i| drm_color_lut {
+                        red: lut.red[i],
+                        green: lut.green[i],
+                        blue: lut.blue[i],
+                        reserved: 0,
+                    })
+                    .collect();
+                Some(Rc::new(self.master.create_blob(&entries[..])?))
+            }
+            _ => None,
+        };
+        let id = blob.as_ref().map(|b| b.id()).unwrap_or(DrmBlob::NONE);
+        changes.change_object(crtc.id, |c| {
+            c.change(prop, id.0 as _);
+        });
+        crtc.gamma_blob.set(blob);
+        Ok(())
+    }
+
+    fn apply_hdr(&self, dd: &ConnectorDisplayData, changes: &mut Change) -> Result<(), DrmError> {
+        let Some(props) = &dd.hdr_props else {
+            return Ok(());
+        };
+        let enabled = dd.should_enable_hdr();
+        let (colorspace, blob) = match enabled {
+            true => {
+                let metadata = create_hdr_output_metadata(dd.hdr_metadata.as_ref());
+                let blob = self.master.create_blob(&metadata)?;
+                (props.colorspace_bt2020, Some(Rc::new(blob)))
+            }
+            false => (0, None),
+        };
+        let id = blob.as_ref().map(|b| b.id()).unwrap_or(DrmBlob::NONE);
+        changes.change_object(self.id, |c| {
+            c.change(props.colorspace.id, colorspace);
+            c.change(props.hdr_output_metadata.id, id.0 as _);
+        });
+        props.colorspace.value.set(colorspace);
+        props.hdr_output_metadata.value.set(id);
+        self.hdr_metadata_blob.set(blob);
+        self.hdr_active.set(enabled);
+        Ok(())
+    }
+
+    /// Detaches a removed connector from its crtc and releases its scanout buffers.
+    ///
+    /// The buffers might still be scanned out. They are moved to `old_buffers` so that
+    /// they outlive the next commit.
+    fn release_resources(&self, old_buffers: &mut Vec<Rc<dyn Any>>) {
+        if let Some(crtc) = self.crtc.take() {
+            crtc.connector.set(None);
+        }
+        self.can_present.set(false);
+        self.primary_plane.set(None);
+        self.cursor_plane.set(None);
+        if let Some(buffers) = self.buffers.take() {
+            old_buffers.push(buffers);
+        }
+        if let Some(buffers) = self.cursor_buffers.take() {
+            old_buffers.push(buffers);
+        }
+        let fbs = [self.active_framebuffer.take(), self.next_framebuffer.take()];
+        for fb in fbs.into_iter().flatten() {
+            old_buffers.push(Rc::new(fb));
+        }
+        self.cached_frame.set(None);
+        self.cursor_sync_file.set(None);
+        self.scanout_buffers.borrow_mut().clear();
+        self.version.fetch_add(1);
+    }
+
+    fn send_hdr_enabled(&self) {
+        match self.frontend_state.get() {
+            FrontState::Removed
+            | FrontState::Disconnected
+            | FrontState::Unavailable
+            | FrontState::Connected { non_desktop: true } => return,
+            FrontState::Connected { non_desktop: false } => {}
+        }
+        self.send_event(ConnectorEvent::HdrChanged(self.hdr_active.get()));
+    }
+
     fn send_vrr_enabled(&self) {
         match self.frontend_state.get() {
             FrontState::Removed
@@ -605,7 +769,8 @@ impl MetalConnector {
             | FrontState::Connected { non_desktop: true } => return,
             FrontState::Connected { non_desktop: false } => {}
         }
-        let hc = self.cursor_buffers.is_some().then(|| {
+        // The contents of mirrored connectors must include the cursor.
+        let hc = (self.cursor_buffers.is_some() && self.mirrors.is_empty()).then(|| {
             Rc::new(MetalHardwareCursor {
                 connector: self.clone(),
             }) as _
@@ -614,6 +779,24 @@ impl MetalConnector {
             .send_event(ConnectorEvent::HardwareCursor(hc));
     }
 
+    /// Updates the connector after connectors have started or stopped mirroring it.
+    fn update_mirrors(self: &Rc<Self>) {
+        if self.mirrors.is_not_empty() {
+            self.cursor_enabled.set(false);
+            self.cursor_changed.set(true);
+        }
+        self.send_hardware_cursor();
+        self.damage();
+    }
+
+    pub fn damage_mirrors(&self) {
+        for mirror in self.mirrors.lock().values() {
+            if let Some(mirror) = mirror.upgrade() {
+                mirror.damage();
+            }
+        }
+    }
+
     fn connected(&self) -> bool {
         let dd = self.display.borrow_mut();
         self.enabled.get() && dd.connection == ConnectorStatus::Connected
@@ -626,7 +809,21 @@ impl MetalConnector {
 
     fn compute_drm_feedback(&self) -> Option<Rc<DrmFeedback>> {
         if !self.dev.is_render_device() {
-            return None;
+            let render_ctx = self.backend.ctx.get()?;
+            let dev_ctx = self.dev.ctx.get();
+            let res = DrmFeedback::for_device(
+                &self.state.drm_feedback_ids,
+                &*render_ctx.gfx,
+                self.dev.devnum,
+                &*dev_ctx.gfx,
+            );
+            return match res {
+                Ok(fb) => fb.map(Rc::new),
+                Err(e) => {
+                    log::error!("Could not compute connector feedback: {}", ErrorFmt(e));
+                    None
+                }
+            };
         }
         let default = self.backend.default_feedback.get()?;
         let plane = self.primary_plane.get()?;
@@ -659,7 +856,10 @@ impl MetalConnector {
                     log::error!("Tried to send connected event in invalid state: {state:?}");
                 }
             },
-            ConnectorEvent::HardwareCursor(_) | ConnectorEvent::ModeChanged(_) => match state {
+            ConnectorEvent::HardwareCursor(_)
+            | ConnectorEvent::ModeChanged(_)
+            | ConnectorEvent::ModeFallback(_)
+            | ConnectorEvent::HdrChanged(_) => match state {
                 FrontState::Connected { non_desktop: false } => {
                     self.on_change.send_event(event);
                 }
@@ -669,6 +869,8 @@ impl MetalConnector {
                 | FrontState::Unavailable => {
                     let name = match &event {
                         ConnectorEvent::HardwareCursor(_) => "hardware cursor",
+                        ConnectorEvent::ModeFallback(_) => "mode fallback",
+                        ConnectorEvent::HdrChanged(_) => "hdr change",
                         _ => "mode change",
                     };
                     log::error!("Tried to send {name} event in invalid state: {state:?}");
@@ -785,6 +987,11 @@ impl Connector for MetalConnector {
         }
     }
 
+    fn damage_software_cursor(&self) {
+        self.software_cursor_damage.fetch_add(1);
+        self.damage();
+    }
+
     fn drm_dev(&self) -> Option<DrmDeviceId> {
         Some(self.dev.id)
     }
@@ -794,15 +1001,27 @@ impl Connector for MetalConnector {
     }
 
     fn set_enabled(&self, enabled: bool) {
-        if self.enabled.replace(enabled) != enabled {
-            if self.display.borrow_mut().connection == ConnectorStatus::Connected {
-                if let Some(dev) = self.backend.device_holder.drm_devices.get(&self.dev.devnum) {
-                    if let Err(e) = self.backend.handle_drm_change_(&dev, true) {
-                        dev.unprocessed_change.set(true);
-                        log::error!("Could not dis/enable connector: {}", ErrorFmt(e));
-                    }
-                }
-            }
+        if self.enabled.get() == enabled {
+            return;
+        }
+        if self.display.borrow().connection != ConnectorStatus::Connected {
+            self.enabled.set(enabled);
+            return;
+        }
+        let Some(dev) = self.backend.device_holder.drm_devices.get(&self.dev.devnum) else {
+            self.enabled.set(enabled);
+            return;
+        };
+        let deferred = self.backend.with_connector_transaction(|t| {
+            t.add(&dev, self);
+        });
+        self.enabled.set(enabled);
+        if deferred {
+            return;
+        }
+        if let Err(e) = self.backend.handle_drm_change_(&dev, true) {
+            dev.unprocessed_change.set(true);
+            log::error!("Could not dis/enable connector: {}", ErrorFmt(e));
         }
     }
 
@@ -818,13 +1037,13 @@ impl Connector for MetalConnector {
             | FrontState::Disconnected
             | FrontState::Unavailable => return,
         }
-        let mut dd = self.display.borrow_mut();
-        let Some(mode) = dd.modes.iter().find(|m| m.to_backend() == be_mode) else {
+        let dd = self.display.borrow();
+        let Some(mode) = dd.modes.iter().find(|m| m.to_backend() == be_mode).cloned() else {
             log::warn!("Connector does not support mode {:?}", be_mode);
             return;
         };
         let prev = dd.mode.clone();
-        if prev.as_ref() == Some(mode) {
+        if prev.as_ref() == Some(&mode) && self.mode_fallback.is_none() {
             return;
         }
         if dd.connection != ConnectorStatus::Connected {
@@ -837,15 +1056,23 @@ impl Connector for MetalConnector {
         };
         log::info!("Trying to change mode from {:?} to {:?}", prev, mode);
         let persistent = dd.persistent.clone();
-        *persistent.mode.borrow_mut() = Some(mode.clone());
-        dd.mode = Some(mode.clone());
         drop(dd);
+        let deferred = self.backend.with_connector_transaction(|t| {
+            t.add(&dev, self).mode_changed = true;
+        });
+        let prev_persistent = persistent.mode.replace(Some(mode.clone()));
+        let prev_fallback = self.mode_fallback.take();
+        self.display.borrow_mut().mode = Some(mode);
+        if deferred {
+            return;
+        }
         let Err(e) = self.backend.handle_drm_change_(&dev, true) else {
-            self.send_event(ConnectorEvent::ModeChanged(be_mode));
+            self.send_mode_changed();
             return;
         };
         log::warn!("Could not change mode: {}", ErrorFmt(&e));
-        *persistent.mode.borrow_mut() = prev.clone();
+        *persistent.mode.borrow_mut() = prev_persistent;
+        self.mode_fallback.set(prev_fallback);
         self.display.borrow_mut().mode = prev;
         if let MetalError::Modeset(DrmError::Atomic(OsError(c::EACCES))) = e {
             log::warn!("Failed due to access denied. Resetting in memory only.");
@@ -857,6 +1084,59 @@ impl Connector for MetalConnector {
         };
     }
 
+    fn set_custom_mode(&self, mode: &CustomMode) -> Result<(), Box<dyn Error>> {
+        if self.frontend_state.get() != (FrontState::Connected { non_desktop: false }) {
+            return Err("Connector is not connected to a desktop display".into());
+        }
+        let mode = DrmModeInfo::custom(mode);
+        let dd = self.display.borrow();
+        if dd.connection != ConnectorStatus::Connected {
+            return Err("Cannot change mode of connector that is not connected".into());
+        }
+        let Some(dev) = self.backend.device_holder.drm_devices.get(&self.dev.devnum) else {
+            return Err("Underlying device does not exist".into());
+        };
+        log::info!("Trying to change mode from {:?} to {:?}", dd.mode, mode);
+        let prev = dd.mode.clone();
+        let persistent = dd.persistent.clone();
+        drop(dd);
+        let deferred = self.backend.with_connector_transaction(|t| {
+            t.add(&dev, self).mode_changed = true;
+        });
+        let added = {
+            let custom_modes = &mut *persistent.custom_modes.borrow_mut();
+            let added = !custom_modes.contains(&mode);
+            if added {
+                custom_modes.push(mode.clone());
+            }
+            added
+        };
+        let prev_persistent = persistent.mode.replace(Some(mode.clone()));
+        let prev_fallback = self.mode_fallback.take();
+        self.display.borrow_mut().mode = Some(mode.clone());
+        if deferred {
+            return Ok(());
+        }
+        let Err(e) = self.backend.try_custom_mode(&dev, self, &mode) else {
+            self.send_mode_changed();
+            return Ok(());
+        };
+        log::warn!("Could not apply the custom mode: {}", ErrorFmt(&e));
+        if added {
+            persistent.custom_modes.borrow_mut().retain(|m| m != &mode);
+        }
+        *persistent.mode.borrow_mut() = prev_persistent;
+        self.mode_fallback.set(prev_fallback);
+        self.display.borrow_mut().mode = prev;
+        if let MetalError::Modeset(DrmError::Atomic(OsError(c::EACCES))) = e {
+            log::warn!("Failed due to access denied. Resetting in memory only.");
+        } else if let Err(e) = self.backend.handle_drm_change_(&dev, true) {
+            dev.unprocessed_change.set(true);
+            log::warn!("Could not restore the previous mode: {}", ErrorFmt(e));
+        }
+        Err(e.into())
+    }
+
     fn set_non_desktop_override(&self, non_desktop: Option<bool>) {
         if self.non_desktop_override.replace(non_desktop) == non_desktop {
             return;
@@ -906,6 +1186,32 @@ impl Connector for MetalConnector {
         self.send_vrr_enabled();
     }
 
+    fn set_hdr_enabled(&self, enabled: bool) {
+        {
+            let dd = self.display.borrow();
+            let old_enabled = dd.should_enable_hdr();
+            dd.persistent.hdr_requested.set(enabled);
+            if dd.should_enable_hdr() == old_enabled {
+                return;
+            }
+        }
+        if let Some(dev) = self.backend.device_holder.drm_devices.get(&self.dev.devnum) {
+            if let Err(e) = self.backend.handle_drm_change_(&dev, true) {
+                dev.unprocessed_change.set(true);
+                log::error!("Could not change HDR mode: {}", ErrorFmt(e));
+            }
+        }
+    }
+
+    fn set_render_margin(&self, margin_ns: u64) {
+        self.display
+            .borrow()
+            .persistent
+            .render_margin
+            .set(margin_ns);
+        self.update_render_margin();
+    }
+
     fn set_tearing_enabled(&self, enabled: bool) {
         if !self.dev.supports_async_commit {
             return;
@@ -936,6 +1242,110 @@ impl Connector for MetalConnector {
             }
         }
     }
+
+    fn set_powered(&self, powered: bool) {
+        if self.powered.replace(powered) == powered || self.backend.idle.get() {
+            return;
+        }
+        let Some(crtc) = self.crtc.get() else {
+            return;
+        };
+        if crtc.active.value.get() == powered {
+            return;
+        }
+        let mut change = self.master.change();
+        change.change_object(crtc.id, |c| {
+            c.change(crtc.active.id, powered as _);
+        });
+        if let Err(e) = change.commit(DRM_MODE_ATOMIC_ALLOW_MODESET, 0) {
+            log::error!("Could not set the crtc power state: {}", ErrorFmt(e));
+            return;
+        }
+        crtc.active.value.set(powered);
+        if powered {
+            self.schedule_present();
+        }
+    }
+
+    fn gamma_lut_size(&self) -> u32 {
+        match self.crtc.get() {
+            Some(crtc) if crtc.gamma_lut.is_some() => crtc.gamma_lut_size,
+            _ => 0,
+        }
+    }
+
+    fn set_gamma_lut(&self, lut: Option<Rc<GammaLut>>) {
+        self.gamma_lut.set(lut);
+        let Some(crtc) = self.crtc.get() else {
+            return;
+        };
+        let mut change = self.master.change();
+        if let Err(e) = self.apply_gamma_lut(&crtc, &mut change) {
+            log::error!("Could not create the gamma blob: {}", ErrorFmt(e));
+            return;
+        }
+        if let Err(e) = change.commit(0, 0) {
+            log::error!("Could not set the gamma lut: {}", ErrorFmt(e));
+        }
+    }
+
+    fn set_mirror_source(&self, source: Option<ConnectorId>) {
+        let Some(slf) = self.backend.connector(self.connector_id) else {
+            return;
+        };
+        let source = source.and_then(|id| self.backend.connector(id));
+        if let Some(old) = self.mirror_source.set(source.clone()) {
+            old.mirrors.remove(&self.connector_id);
+            old.update_mirrors();
+        }
+        match &source {
+            Some(source) => {
+                source.mirrors.set(self.connector_id, Rc::downgrade(&slf));
+                source.update_mirrors();
+                self.cursor_enabled.set(false);
+                self.cursor_changed.set(true);
+            }
+            None => {
+                self.send_mode_changed();
+                slf.send_hardware_cursor();
+                self.send_vrr_enabled();
+                self.send_hdr_enabled();
+                self.send_formats();
+            }
+        }
+        self.damage();
+    }
+
+    fn backlight(&self) -> Option<(u32, u32)> {
+        let backlight = self.find_backlight()?;
+        let brightness = match self.backlight_pending.get() {
+            Some(b) => b,
+            None => backlight.brightness()?,
+        };
+        Some((
+            brightness.min(backlight.max_brightness),
+            backlight.max_brightness,
+        ))
+    }
+
+    fn set_backlight(&self, brightness: u32) {
+        let Some(backlight) = self.find_backlight() else {
+            return;
+        };
+        let brightness = brightness.min(backlight.max_brightness);
+        self.backlight_pending.set(Some(brightness));
+        let pending = self.backlight_pending.clone();
+        self.backend
+            .session
+            .set_brightness("backlight", &backlight.name, brightness, move |res| {
+                if let Err(e) = res {
+                    log::error!("Could not set the brightness: {}", ErrorFmt(e));
+                }
+                if pending.get() == Some(brightness) {
+                    pending.set(None);
+                }
+            });
+    }
 }
 
 pub struct MetalCrtc {
@@ -953,8 +1363,11 @@ pub struct MetalCrtc {
     pub mode_id: MutableProperty<DrmBlob>,
     pub out_fence_ptr: DrmProperty,
     pub vrr_enabled: MutableProperty<bool>,
+    pub gamma_lut: Option<DrmProperty>,
+    pub gamma_lut_size: u32,
 
     pub mode_blob: CloneCell<Option<Rc<PropBlob>>>,
+    pub gamma_blob: CloneCell<Option<Rc<PropBlob>>>,
     pub have_queued_sequence: Cell<bool>,
     pub needs_vblank_emulation: Cell<bool>,
 }
@@ -971,6 +1384,17 @@ pub struct MetalEncoder {
     pub crtcs: AHashMap<DrmCrtc, Rc<MetalCrtc>>,
 }
 
+/// A connector that writes the output of a CRTC into a framebuffer.
+#[derive(Debug)]
+pub struct MetalWritebackConnector {
+    pub id: DrmConnector,
+    pub crtcs: AHashMap<DrmCrtc, Rc<MetalCrtc>>,
+    pub formats: AHashSet<u32>,
+    pub crtc_id: MutableProperty<DrmCrtc>,
+    pub fb_id: DrmProperty,
+    pub out_fence_ptr: DrmProperty,
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum PlaneType {
     Overlay,
@@ -1061,10 +1485,16 @@ fn create_connector(
         buffers: Default::default(),
         next_buffer: Default::default(),
         enabled: Cell::new(true),
+        powered: Cell::new(true),
+        gamma_lut: Default::default(),
+        hdr_active: Cell::new(false),
+        hdr_metadata_blob: Default::default(),
         non_desktop_override: Default::default(),
         lease: Cell::new(None),
         can_present: Cell::new(true),
         has_damage: NumCell::new(1),
+        software_cursor_damage: Default::default(),
+        cached_frame: Default::default(),
         primary_plane: Default::default(),
         cursor_plane: Default::default(),
         crtc: Default::default(),
@@ -1089,6 +1519,8 @@ fn create_connector(
         next_vblank_nsec: Cell::new(0),
         tearing_requested: Cell::new(false),
         try_switch_format: Cell::new(false),
+        mode_fallback: Default::default(),
+        reported_mode_fallback: Default::default(),
         version: Default::default(),
         sequence: Default::default(),
         expected_sequence: Default::default(),
@@ -1100,6 +1532,11 @@ fn create_connector(
         vblank_miss_this_sec: Default::default(),
         presentation_is_sync: Cell::new(false),
         presentation_is_zero_copy: Cell::new(false),
+        writeback_unsupported: Cell::new(false),
+        writeback_jobs: Default::default(),
+        mirror_source: Default::default(),
+        mirrors: Default::default(),
+        backlight_pending: Default::default(),
     });
     let futures = ConnectorFutures {
         _present: backend.state.eng.spawn2(
@@ -1107,6 +1544,10 @@ fn create_connector(
             Phase::Present,
             slf.clone().present_loop(),
         ),
+        _writeback: backend
+            .state
+            .eng
+            .spawn("writeback loop", slf.clone().writeback_loop()),
     };
     Ok((slf, futures))
 }
@@ -1116,7 +1557,7 @@ fn create_connector_display_data(
     dev: &Rc<MetalDrmDevice>,
     non_desktop_override: Option<bool>,
 ) -> Result<ConnectorDisplayData, DrmError> {
-    let info = dev.master.get_connector_info(connector, true)?;
+    let mut info = dev.master.get_connector_info(connector, true)?;
     let mut crtcs = AHashMap::new();
     for encoder in info.encoders {
         if let Some(encoder) = dev.encoders.get(&encoder) {
@@ -1131,6 +1572,8 @@ fn create_connector_display_data(
     let mut manufacturer = String::new();
     let mut serial_number = String::new();
     let mut vrr_refresh_max_nsec = u64::MAX;
+    let mut hdr_metadata = None;
+    let mut edid_info = None;
     let connector_id = ConnectorKernelId {
         ty: ConnectorType::from_drm(info.connector_type),
         idx: info.connector_type_id,
@@ -1172,6 +1615,7 @@ fn create_connector_display_data(
             }
         };
         manufacturer = edid.base_block.id_manufacturer_name.to_string();
+        edid_info = Some(Rc::new(create_edid_info(&edid)));
         for descriptor in edid.base_block.descriptors.iter().flatten() {
             match descriptor {
                 Descriptor::DisplayProductSerialNumber(s) => {
@@ -1218,6 +1662,15 @@ fn create_connector_display_data(
         if min_vrr_hz > 0 {
             vrr_refresh_max_nsec = 1_000_000_000 / min_vrr_hz;
         }
+        for ext in &edid.extension_blocks {
+            if let EdidExtension::CtaV3(cta) = ext {
+                for data_block in &cta.data_blocks {
+                    if let CtaDataBlock::HdrStaticMetadata(hdr) = data_block {
+                        hdr_metadata = Some(*hdr);
+                    }
+                }
+            }
+        }
     }
     let output_id = Rc::new(OutputId::new(
         connector_id.to_string(),
@@ -1233,8 +1686,11 @@ fn create_connector_display_data(
         None => {
             let ds = Rc::new(PersistentDisplayData {
                 mode: RefCell::new(info.modes.first().cloned()),
+                custom_modes: Default::default(),
                 vrr_requested: Default::default(),
+                hdr_requested: Default::default(),
                 format: Cell::new(XRGB8888),
+                render_margin: Cell::new(DEFAULT_RENDER_MARGIN),
             });
             dev.backend
                 .persistent_display_data
@@ -1242,6 +1698,11 @@ fn create_connector_display_data(
             ds
         }
     };
+    for mode in &*desired_state.custom_modes.borrow() {
+        if !info.modes.contains(mode) {
+            info.modes.push(mode.clone());
+        }
+    }
     let mut mode_opt = desired_state.mode.borrow_mut();
     if let Some(mode) = &*mode_opt {
         if !info.modes.contains(mode) {
@@ -1261,6 +1722,8 @@ fn create_connector_display_data(
         Ok(c) => c.value.get() == 1,
         Err(_) => false,
     };
+    let hdr_props = get_hdr_properties(&props);
+    let hdr_capable = hdr_props.is_some() && hdr_metadata.is_some_and(|m| m.supports_st2084);
     let mode = mode_opt.clone();
     drop(mode_opt);
     Ok(ConnectorDisplayData {
@@ -1274,15 +1737,118 @@ fn create_connector_display_data(
         non_desktop_effective: non_desktop_override.unwrap_or(non_desktop),
         vrr_capable,
         _vrr_refresh_max_nsec: vrr_refresh_max_nsec,
+        hdr_capable,
+        hdr_metadata,
+        hdr_props,
         connection,
         mm_width: info.mm_width,
         mm_height: info.mm_height,
         _subpixel: info.subpixel,
         connector_id,
         output_id,
+        edid: edid_info,
+        properties: Rc::new(create_connector_properties(&props)),
+    })
+}
+
+fn create_edid_info(edid: &EdidFile) -> EdidInfo {
+    let base = &edid.base_block;
+    let mut range_limits = None;
+    for desc in base.descriptors.iter().flatten() {
+        if let Descriptor::DisplayRangeLimitsAndAdditionalTiming(limits) = desc {
+            range_limits = Some(EdidRangeLimits {
+                min_vertical_hz: limits.vertical_field_rate_min,
+                max_vertical_hz: limits.vertical_field_rate_max,
+                min_horizontal_khz: limits.horizontal_field_rate_min,
+                max_horizontal_khz: limits.horizontal_field_rate_max,
+                max_pixel_clock_mhz: limits.maximum_pixel_clock_mhz,
+            });
+        }
+    }
+    EdidInfo {
+        product_code: base.id_product_code,
+        serial_number: base.id_serial_number,
+        week_of_manufacture: base.week_of_manufacture,
+        year_of_manufacture: base.year_of_manufacture,
+        version: base.edid_version,
+        revision: base.edid_revision,
+        range_limits,
+    }
+}
+
+fn create_connector_properties(props: &CollectedProperties) -> Vec<ConnectorProperty> {
+    let mut res = vec![];
+    for (name, (def, value)) in &props.props {
+        let value = match &def.ty {
+            DrmPropertyType::Blob => continue,
+            DrmPropertyType::SignedRange { .. } => (*value as i64).to_string(),
+            DrmPropertyType::Range { .. } | DrmPropertyType::Object { .. } => value.to_string(),
+            DrmPropertyType::Enum { values, bitmask } => {
+                let names: Vec<_> = values
+                    .iter()
+                    .filter(|v| {
+                        if *bitmask {
+                            v.value < 64 && *value & (1 << v.value) != 0
+                        } else {
+                            v.value == *value
+                        }
+                    })
+                    .map(|v| v.name.to_string())
+                    .collect();
+                match names.is_empty() {
+                    true => value.to_string(),
+                    false => names.join(" | "),
+                }
+            }
+        };
+        res.push(ConnectorProperty {
+            name: name.to_string(),
+            value,
+        });
+    }
+    res.sort_by(|l, r| l.name.cmp(&r.name));
+    res
+}
+
+fn get_hdr_properties(props: &CollectedProperties) -> Option<HdrProperties> {
+    let (def, _) = props.props.get(b"Colorspace".as_bstr())?;
+    let DrmPropertyType::Enum { values, .. } = &def.ty else {
+        return None;
+    };
+    let bt2020 = values.iter().find(|v| v.name.as_bytes() == b"BT2020_RGB")?;
+    Some(HdrProperties {
+        colorspace: props.get("Colorspace").ok()?,
+        colorspace_bt2020: bt2020.value,
+        hdr_output_metadata: props
+            .get("HDR_OUTPUT_METADATA")
+            .ok()?
+            .map(|v| DrmBlob(v as u32)),
     })
 }
 
+fn create_hdr_output_metadata(edid: Option<&CtaHdrStaticMetadataDataBlock>) -> hdr_output_metadata {
+    let primaries = NamedPrimaries::Bt2020.primaries();
+    let xy = |(x, y): (f64, f64)| [(x * 50000.0).round() as u16, (y * 50000.0).round() as u16];
+    let max = edid.and_then(|m| m.max_luminance).unwrap_or(1000.0);
+    let max_fall = edid
+        .and_then(|m| m.max_frame_average_luminance)
+        .unwrap_or(max);
+    let min = edid.and_then(|m| m.min_luminance).unwrap_or(0.005);
+    hdr_output_metadata {
+        metadata_type: HDMI_STATIC_METADATA_TYPE1 as u32,
+        hdmi_metadata_type1: hdr_metadata_infoframe {
+            eotf: HDMI_EOTF_SMPTE_ST2084,
+            metadata_type: HDMI_STATIC_METADATA_TYPE1,
+            display_primaries: [xy(primaries.r), xy(primaries.g), xy(primaries.b)],
+            white_point: xy(primaries.wp),
+            max_display_mastering_luminance: max.round() as u16,
+            min_display_mastering_luminance: (min * 10000.0).round() as u16,
+            max_cll: max.round() as u16,
+            max_fall: max_fall.round() as u16,
+        },
+    }
+}
+
 fn create_encoder(
     encoder: DrmEncoder,
     master: &Rc<DrmMaster>,
@@ -1301,6 +1867,33 @@ fn create_encoder(
     })
 }
 
+fn create_writeback_connector(
+    connector: DrmConnector,
+    info: &DrmConnectorInfo,
+    master: &Rc<DrmMaster>,
+    encoders: &AHashMap<DrmEncoder, Rc<MetalEncoder>>,
+) -> Result<MetalWritebackConnector, DrmError> {
+    let mut crtcs = AHashMap::new();
+    for encoder in &info.encoders {
+        if let Some(encoder) = encoders.get(encoder) {
+            for crtc in encoder.crtcs.values() {
+                crtcs.insert(crtc.id, crtc.clone());
+            }
+        }
+    }
+    let props = collect_properties(master, connector)?;
+    let formats = props.get("WRITEBACK_PIXEL_FORMATS")?.value.get();
+    let formats = master.getblob_vec::<u32>(DrmBlob(formats as _))?;
+    Ok(MetalWritebackConnector {
+        id: connector,
+        crtcs,
+        formats: formats.into_iter().collect(),
+        crtc_id: props.get("CRTC_ID")?.map(|v| DrmCrtc(v as _)),
+        fb_id: props.get("WRITEBACK_FB_ID")?.id,
+        out_fence_ptr: props.get("WRITEBACK_OUT_FENCE_PTR")?.id,
+    })
+}
+
 fn create_crtc(
     crtc: DrmCrtc,
     idx: usize,
@@ -1326,7 +1919,13 @@ fn create_crtc(
         mode_id: props.get("MODE_ID")?.map(|v| DrmBlob(v as u32)),
         out_fence_ptr: props.get("OUT_FENCE_PTR")?.id,
         vrr_enabled: props.get("VRR_ENABLED")?.map(|v| v == 1),
+        gamma_lut: props.get("GAMMA_LUT").ok().map(|p| p.id),
+        gamma_lut_size: match props.get("GAMMA_LUT_SIZE") {
+            Ok(p) => p.value.get() as u32,
+            Err(_) => 0,
+        },
         mode_blob: Default::default(),
+        gamma_blob: Default::default(),
         have_queued_sequence: Cell::new(false),
         needs_vblank_emulation: Cell::new(false),
     })
@@ -1471,6 +2070,8 @@ impl<T: Copy> MutableProperty<T> {
     }
 }
 
+const MAX_MODE_FALLBACKS: usize = 16;
+
 #[derive(Default)]
 struct Preserve {
     connectors: AHashSet<DrmConnector>,
@@ -1478,6 +2079,64 @@ struct Preserve {
     planes: AHashSet<DrmPlane>,
 }
 
+/// An atomic commit that configures all connectors of a device.
+struct DrmDeviceModeset {
+    changes: Change,
+    flags: u32,
+    _old_buffers: Vec<Rc<dyn Any>>,
+}
+
+struct PendingDrmChange {
+    preserve: Preserve,
+    modeset: Option<DrmDeviceModeset>,
+    _removed_buffers: Vec<Rc<dyn Any>>,
+}
+
+/// Connector changes that are deferred until the end of a transaction.
+#[derive(Default)]
+pub struct ConnectorTransaction {
+    devices: AHashMap<dev_t, Rc<MetalDrmDeviceData>>,
+    connectors: AHashMap<(dev_t, DrmConnector), TransactionConnector>,
+}
+
+/// The state of a connector before the transaction.
+struct TransactionConnector {
+    enabled: bool,
+    mode: Option<DrmModeInfo>,
+    persistent_mode: Option<DrmModeInfo>,
+    mode_fallback: Option<Rc<ModeFallback>>,
+    mode_changed: bool,
+}
+
+impl ConnectorTransaction {
+    /// Records the state of the connector unless it is already part of the transaction.
+    fn add(
+        &mut self,
+        dev: &Rc<MetalDrmDeviceData>,
+        connector: &MetalConnector,
+    ) -> &mut TransactionConnector {
+        let devnum = dev.dev.devnum;
+        self.devices.entry(devnum).or_insert_with(|| dev.clone());
+        self.connectors
+            .entry((devnum, connector.id))
+            .or_insert_with(|| {
+                let dd = connector.display.borrow();
+                let persistent_mode = dd.persistent.mode.borrow().clone();
+                TransactionConnector {
+                    enabled: connector.enabled.get(),
+                    mode: dd.mode.clone(),
+                    persistent_mode,
+                    mode_fallback: connector.mode_fallback.get(),
+                    mode_changed: false,
+                }
+            })
+    }
+
+    fn connector(&self, devnum: dev_t, connector: DrmConnector) -> Option<Rc<MetalConnector>> {
+        self.devices.get(&devnum)?.connectors.get(&connector)
+    }
+}
+
 impl MetalBackend {
     pub fn check_render_context(&self, dev: &Rc<MetalDrmDevice>) -> bool {
         let ctx = match self.ctx.get() {
@@ -1544,10 +2203,21 @@ impl MetalBackend {
         dev: &Rc<MetalDrmDeviceData>,
         preserve_any: bool,
     ) -> Result<(), MetalError> {
+        let change = self.prepare_drm_change(dev, preserve_any)?;
+        self.complete_drm_change(dev, change)
+    }
+
+    fn prepare_drm_change(
+        self: &Rc<Self>,
+        dev: &Rc<MetalDrmDeviceData>,
+        preserve_any: bool,
+    ) -> Result<PendingDrmChange, MetalError> {
         if let Err(e) = self.update_device_properties(dev) {
             return Err(MetalError::UpdateProperties(e));
         }
-        let res = dev.dev.master.get_resources()?;
+        let mut res = dev.dev.master.get_resources()?;
+        res.connectors
+            .retain(|c| !dev.dev.writeback_connectors.contains_key(c));
         let current_connectors: AHashSet<_> = res.connectors.iter().copied().collect();
         let mut new_connectors = AHashSet::new();
         let mut removed_connectors = AHashSet::new();
@@ -1561,6 +2231,8 @@ impl MetalBackend {
                 removed_connectors.insert(*c);
             }
         }
+        let mut head_removed = !removed_connectors.is_empty();
+        let mut removed_buffers = vec![];
         for c in removed_connectors {
             dev.futures.remove(&c);
             if let Some(c) = dev.connectors.remove(&c) {
@@ -1578,6 +2250,7 @@ impl MetalBackend {
                     }
                 }
                 c.send_event(ConnectorEvent::Removed);
+                c.release_resources(&mut removed_buffers);
             }
         }
         let mut preserve = Preserve::default();
@@ -1617,6 +2290,8 @@ impl MetalBackend {
                         disconnect |= old.output_id != dd.output_id;
                     }
                     if disconnect {
+                        head_removed = true;
+                        c.mode_fallback.take();
                         c.tearing_requested.set(false);
                         if let Some(lease_id) = c.lease.get() {
                             if let Some(lease) = dev.dev.leases.remove(&lease_id) {
@@ -1631,13 +2306,32 @@ impl MetalBackend {
                     }
                 }
             }
+            if let Some(fallback) = c.mode_fallback.get() {
+                if old.mode.as_ref() == Some(&fallback.requested) {
+                    old.use_mode(fallback.chosen.clone());
+                } else {
+                    c.mode_fallback.take();
+                }
+            }
             if c.try_switch_format.get() && old.persistent.format.get() != c.buffer_format.get() {
                 preserve_connector = false;
             }
+            if old.should_enable_hdr() != c.hdr_active.get() {
+                preserve_connector = false;
+            }
             if preserve_connector {
                 preserve.connectors.insert(c.id);
             }
         }
+        if head_removed {
+            // The link bandwidth of the removed heads might allow the requested modes now.
+            for c in dev.connectors.lock().values() {
+                if let Some(fallback) = c.mode_fallback.take() {
+                    c.display.borrow_mut().use_mode(fallback.requested.clone());
+                    preserve.connectors.remove(&c.id);
+                }
+            }
+        }
         for c in new_connectors {
             let (connector, future) = match create_connector(self, c, &dev.dev) {
                 Ok(c) => c,
@@ -1652,7 +2346,23 @@ impl MetalBackend {
             dev.futures.set(c, future);
             dev.connectors.set(c, connector);
         }
-        self.init_drm_device(dev, &mut preserve)?;
+        let modeset = self.prepare_drm_device(dev, &mut preserve)?;
+        Ok(PendingDrmChange {
+            preserve,
+            modeset,
+            _removed_buffers: removed_buffers,
+        })
+    }
+
+    fn complete_drm_change(
+        &self,
+        dev: &Rc<MetalDrmDeviceData>,
+        change: PendingDrmChange,
+    ) -> Result<(), MetalError> {
+        let preserve = &change.preserve;
+        if let Some(modeset) = &change.modeset {
+            self.commit_drm_device(dev, modeset, preserve)?;
+        }
         for connector in dev.connectors.lock().values() {
             if connector.connected() {
                 if !preserve.connectors.contains(&connector.id) {
@@ -1665,6 +2375,145 @@ impl MetalBackend {
         Ok(())
     }
 
+    /// Applies the custom mode of a connector after testing the modeset.
+    ///
+    /// Fails if the test commit fails or if the mode was replaced by a fallback mode.
+    fn try_custom_mode(
+        self: &Rc<Self>,
+        dev: &Rc<MetalDrmDeviceData>,
+        connector: &MetalConnector,
+        mode: &DrmModeInfo,
+    ) -> Result<(), MetalError> {
+        let change = self.prepare_drm_change(dev, true)?;
+        if connector.display.borrow().mode.as_ref() != Some(mode) {
+            return Err(MetalError::CustomModeRejected);
+        }
+        if let Some(modeset) = &change.modeset {
+            if let Err(e) = modeset.changes.test(modeset.flags) {
+                return Err(MetalError::Modeset(e));
+            }
+        }
+        self.complete_drm_change(dev, change)
+    }
+
+    fn with_connector_transaction(&self, f: impl FnOnce(&mut ConnectorTransaction)) -> bool {
+        match &mut *self.connector_transaction.borrow_mut() {
+            Some(t) => {
+                f(t);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn commit_connector_transaction(
+        self: &Rc<Self>,
+        transaction: ConnectorTransaction,
+    ) -> Result<(), MetalError> {
+        if let Err(e) = self.try_commit_connector_transaction(&transaction) {
+            log::warn!("Could not apply connector changes: {}", ErrorFmt(&e));
+            self.roll_back_connector_transaction(&transaction, Some(&e));
+            return Err(e);
+        }
+        for (&(devnum, id), tc) in &transaction.connectors {
+            if tc.mode_changed {
+                if let Some(connector) = transaction.connector(devnum, id) {
+                    connector.send_mode_changed();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn try_commit_connector_transaction(
+        self: &Rc<Self>,
+        transaction: &ConnectorTransaction,
+    ) -> Result<(), MetalError> {
+        let mut changes = vec![];
+        for dev in transaction.devices.values() {
+            let change = self.prepare_drm_change(dev, true)?;
+            if let Some(modeset) = &change.modeset {
+                if let Err(e) = modeset.changes.test(modeset.flags) {
+                    return Err(MetalError::Modeset(e));
+                }
+            }
+            changes.push((dev, change));
+        }
+        for (dev, change) in changes {
+            self.complete_drm_change(dev, change)?;
+        }
+        Ok(())
+    }
+
+    /// Tests the connector changes of the transaction with a TEST_ONLY commit.
+    ///
+    /// The connectors are restored to their state before the transaction afterwards.
+    pub fn check_connector_transaction(
+        self: &Rc<Self>,
+        mut transaction: ConnectorTransaction,
+    ) -> Result<(), MetalError> {
+        // The mode fallback can change connectors that are not part of the transaction.
+        let devices: Vec<_> = transaction.devices.values().cloned().collect();
+        for dev in &devices {
+            for connector in dev.connectors.lock().values() {
+                transaction.add(dev, connector);
+            }
+        }
+        let res = self.try_check_connector_transaction(&transaction);
+        if let Err(e) = &res {
+            log::warn!("Connector changes failed the test commit: {}", ErrorFmt(e));
+        }
+        self.roll_back_connector_transaction(&transaction, res.as_ref().err());
+        res
+    }
+
+    fn try_check_connector_transaction(
+        self: &Rc<Self>,
+        transaction: &ConnectorTransaction,
+    ) -> Result<(), MetalError> {
+        for dev in transaction.devices.values() {
+            if let Err(e) = self.update_device_properties(dev) {
+                return Err(MetalError::UpdateProperties(e));
+            }
+            if let Some(modeset) = self.prepare_drm_device(dev, &mut Preserve::default())? {
+                if let Err(e) = modeset.changes.test(modeset.flags) {
+                    return Err(MetalError::Modeset(e));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn roll_back_connector_transaction(
+        self: &Rc<Self>,
+        transaction: &ConnectorTransaction,
+        e: Option<&MetalError>,
+    ) {
+        for (&(devnum, id), tc) in &transaction.connectors {
+            let Some(connector) = transaction.connector(devnum, id) else {
+                continue;
+            };
+            connector.enabled.set(tc.enabled);
+            connector.mode_fallback.set(tc.mode_fallback.clone());
+            let dd = &mut *connector.display.borrow_mut();
+            *dd.persistent.mode.borrow_mut() = tc.persistent_mode.clone();
+            dd.mode = tc.mode.clone();
+        }
+        if let Some(MetalError::Modeset(DrmError::Atomic(OsError(c::EACCES)))) = e {
+            log::warn!("Failed due to access denied. Resetting in memory only.");
+            return;
+        }
+        for dev in transaction.devices.values() {
+            if let Err(e) = self.handle_drm_change_(dev, true) {
+                dev.unprocessed_change.set(true);
+                log::error!(
+                    "Could not restore the previous connector configuration: {}",
+                    ErrorFmt(e)
+                );
+            }
+        }
+    }
+
     fn send_connected(&self, connector: &Rc<MetalConnector>, dd: &ConnectorDisplayData) {
         match connector.frontend_state.get() {
             FrontState::Removed | FrontState::Connected { .. } | FrontState::Unavailable => {
@@ -1687,9 +2536,14 @@ impl MetalBackend {
             height_mm: dd.mm_height as _,
             non_desktop: dd.non_desktop_effective,
             vrr_capable: dd.vrr_capable,
+            hdr_capable: dd.hdr_capable,
+            edid: dd.edid.clone(),
+            properties: dd.properties.clone(),
         }));
+        connector.reported_mode_fallback.set(None);
         connector.send_hardware_cursor();
         connector.send_vrr_enabled();
+        connector.send_hdr_enabled();
         connector.send_formats();
     }
 
@@ -1701,6 +2555,9 @@ impl MetalBackend {
         if let Err(e) = master.set_client_cap(DRM_CLIENT_CAP_ATOMIC, 2) {
             return Err(MetalError::AtomicModesetting(e));
         }
+        if let Err(e) = master.set_client_cap(DRM_CLIENT_CAP_WRITEBACK_CONNECTORS, 1) {
+            log::debug!("Could not enable writeback connectors: {}", ErrorFmt(e));
+        }
         let resources = master.get_resources()?;
 
         let (cursor_width, cursor_height) = match master.get_cursor_size() {
@@ -1741,6 +2598,27 @@ impl MetalBackend {
             }
         }
 
+        let mut connector_ids = vec![];
+        let mut writeback_connectors = AHashMap::new();
+        for connector in resources.connectors.iter().copied() {
+            let info = master.get_connector_info(connector, false)?;
+            if !matches!(
+                ConnectorType::from_drm(info.connector_type),
+                ConnectorType::WRITEBACK
+            ) {
+                connector_ids.push(connector);
+                continue;
+            }
+            match create_writeback_connector(connector, &info, master, &encoders) {
+                Ok(c) => {
+                    writeback_connectors.insert(c.id, Rc::new(c));
+                }
+                Err(e) => {
+                    log::warn!("Could not create writeback connector: {}", ErrorFmt(e));
+                }
+            }
+        }
+
         let gbm = match GbmDevice::new(master) {
             Ok(g) => Rc::new(g),
             Err(e) => return Err(MetalError::GbmDevice(e)),
@@ -1783,6 +2661,7 @@ impl MetalBackend {
             crtcs,
             encoders,
             planes,
+            writeback_connectors,
             _min_width: resources.min_width,
             _max_width: resources.max_width,
             _min_height: resources.min_height,
@@ -1806,7 +2685,7 @@ impl MetalBackend {
             min_post_commit_margin: Cell::new(DEFAULT_POST_COMMIT_MARGIN),
         });
 
-        let (connectors, futures) = get_connectors(self, &dev, &resources.connectors)?;
+        let (connectors, futures) = get_connectors(self, &dev, &connector_ids)?;
 
         let slf = Rc::new(MetalDrmDeviceData {
             dev: dev.clone(),
@@ -2010,6 +2889,9 @@ impl MetalBackend {
             let actual = connector.sequence.get();
             if expected < actual {
                 connector.vblank_miss_this_sec.fetch_add(1);
+                if let Some(g) = &global {
+                    g.stats.missed_vblanks.fetch_add(1);
+                }
             }
         }
         if connector.has_damage.is_not_zero()
@@ -2100,16 +2982,28 @@ impl MetalBackend {
                 continue;
             }
             connector.buffers.set(None);
+            connector.cached_frame.set(None);
             connector.cursor_buffers.set(None);
             connector.primary_plane.set(None);
             connector.cursor_plane.set(None);
             connector.cursor_enabled.set(false);
             connector.crtc.set(None);
+            connector.writeback_unsupported.set(false);
             connector.version.fetch_add(1);
+            connector.hdr_active.set(false);
+            connector.hdr_metadata_blob.set(None);
             let dd = connector.display.borrow_mut();
             dd.crtc_id.value.set(DrmCrtc::NONE);
+            if let Some(hdr) = &dd.hdr_props {
+                hdr.colorspace.value.set(0);
+                hdr.hdr_output_metadata.value.set(DrmBlob::NONE);
+            }
             changes.change_object(connector.id, |c| {
                 c.change(dd.crtc_id.id, 0);
+                if let Some(hdr) = &dd.hdr_props {
+                    c.change(hdr.colorspace.id, 0);
+                    c.change(hdr.hdr_output_metadata.id, 0);
+                }
             })
         }
         for crtc in dev.dev.crtcs.values() {
@@ -2120,11 +3014,25 @@ impl MetalBackend {
             crtc.active.value.set(false);
             crtc.mode_id.value.set(DrmBlob::NONE);
             crtc.vrr_enabled.value.set(false);
+            crtc.gamma_blob.set(None);
             changes.change_object(crtc.id, |c| {
                 c.change(crtc.active.id, 0);
                 c.change(crtc.mode_id.id, 0);
                 c.change(crtc.out_fence_ptr, 0);
                 c.change(crtc.vrr_enabled.id, 0);
+                if let Some(gamma_lut) = crtc.gamma_lut {
+                    c.change(gamma_lut, 0);
+                }
+            })
+        }
+        for connector in dev.dev.writeback_connectors.values() {
+            let crtc = connector.crtc_id.value.get();
+            if crtc == DrmCrtc::NONE || preserve.crtcs.contains(&crtc) {
+                continue;
+            }
+            connector.crtc_id.value.set(DrmCrtc::NONE);
+            changes.change_object(connector.id, |c| {
+                c.change(connector.crtc_id.id, 0);
             })
         }
     }
@@ -2297,38 +3205,140 @@ impl MetalBackend {
         dev: &Rc<MetalDrmDeviceData>,
         preserve: &mut Preserve,
     ) -> Result<(), MetalError> {
+        if let Some(modeset) = self.prepare_drm_device(dev, preserve)? {
+            self.commit_drm_device(dev, &modeset, preserve)?;
+        }
+        Ok(())
+    }
+
+    fn prepare_drm_device(
+        &self,
+        dev: &Rc<MetalDrmDeviceData>,
+        preserve: &mut Preserve,
+    ) -> Result<Option<DrmDeviceModeset>, MetalError> {
         self.break_leases(dev);
         let ctx = match self.ctx.get() {
             Some(ctx) => ctx,
-            _ => return Ok(()),
+            _ => return Ok(None),
         };
         self.validate_preserve(dev, preserve);
         let mut flags = 0;
         let mut changes = dev.dev.master.change();
-        if !self.can_use_current_drm_mode(dev) {
+        let modeset = !self.can_use_current_drm_mode(dev, &mut changes, &mut flags);
+        if modeset {
             log::warn!("Cannot use existing connector configuration. Trying to perform modeset.");
             flags = DRM_MODE_ATOMIC_ALLOW_MODESET;
-            self.reset_connectors_and_crtcs(dev, &mut changes, preserve);
+        }
+        let mut old_buffers = vec![];
+        let mut fallbacks = 0;
+        loop {
+            if modeset {
+                changes = dev.dev.master.change();
+                self.reset_connectors_and_crtcs(dev, &mut changes, preserve);
+                for connector in dev.connectors.lock().values() {
+                    if !preserve.connectors.contains(&connector.id) {
+                        if let Err(e) = self.assign_connector_crtc(connector, &mut changes) {
+                            log::error!("Could not assign a crtc: {}", ErrorFmt(e));
+                        }
+                    }
+                }
+            }
+            self.reset_planes(dev, &mut changes, preserve);
             for connector in dev.connectors.lock().values() {
                 if !preserve.connectors.contains(&connector.id) {
-                    if let Err(e) = self.assign_connector_crtc(connector, &mut changes) {
-                        log::error!("Could not assign a crtc: {}", ErrorFmt(e));
+                    if let Err(e) = self.assign_connector_planes(
+                        connector,
+                        &mut changes,
+                        &ctx,
+                        &mut old_buffers,
+                    ) {
+                        log::error!("Could not assign a plane: {}", ErrorFmt(e));
                     }
                 }
             }
+            if modeset && fallbacks < MAX_MODE_FALLBACKS {
+                if let Err(e) = changes.test(flags) {
+                    log::warn!("Modeset test failed: {}", ErrorFmt(&e));
+                    match e {
+                        DrmError::Atomic(OsError(c::EINVAL | c::ENOSPC)) => {
+                            if self.apply_mode_fallback(dev, preserve) {
+                                fallbacks += 1;
+                                continue;
+                            }
+                        }
+                        DrmError::Atomic(OsError(c::EACCES)) => {
+                            log::warn!("Access denied. Not falling back to lower modes.");
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            break;
         }
-        self.reset_planes(dev, &mut changes, preserve);
-        let mut old_buffers = vec![];
+        Ok(Some(DrmDeviceModeset {
+            changes,
+            flags,
+            _old_buffers: old_buffers,
+        }))
+    }
+
+    /// Replaces the mode of the connector with the highest pixel clock by the next lower
+    /// mode.
+    ///
+    /// This is used when a modeset fails with EINVAL or ENOSPC, usually because the link
+    /// bandwidth of the device is not sufficient for the requested modes. Returns `false`
+    /// if no mode can be lowered.
+    fn apply_mode_fallback(&self, dev: &MetalDrmDeviceData, preserve: &Preserve) -> bool {
+        let mut fallback = None;
+        let mut max_clock = 0;
         for connector in dev.connectors.lock().values() {
-            if !preserve.connectors.contains(&connector.id) {
-                if let Err(e) =
-                    self.assign_connector_planes(connector, &mut changes, &ctx, &mut old_buffers)
-                {
-                    log::error!("Could not assign a plane: {}", ErrorFmt(e));
-                }
+            if preserve.connectors.contains(&connector.id) {
+                continue;
+            }
+            let dd = connector.display.borrow();
+            if should_ignore(connector, &dd) {
+                continue;
+            }
+            let Some(mode) = &dd.mode else {
+                continue;
+            };
+            if mode.clock <= max_clock {
+                continue;
+            }
+            if let Some(lower) = lower_mode(&dd.modes, mode) {
+                max_clock = mode.clock;
+                fallback = Some((connector.clone(), lower.clone()));
             }
         }
-        if let Err(e) = changes.commit(flags, 0) {
+        let Some((connector, chosen)) = fallback else {
+            return false;
+        };
+        let dd = &mut *connector.display.borrow_mut();
+        let current = dd.mode.clone().unwrap();
+        log::warn!(
+            "Falling back from mode {:?} to mode {:?} on connector {}",
+            current.to_backend(),
+            chosen.to_backend(),
+            dd.connector_id,
+        );
+        let requested = match connector.mode_fallback.get() {
+            Some(f) => f.requested.clone(),
+            None => current,
+        };
+        dd.use_mode(chosen.clone());
+        connector
+            .mode_fallback
+            .set(Some(Rc::new(ModeFallback { requested, chosen })));
+        true
+    }
+
+    fn commit_drm_device(
+        &self,
+        dev: &Rc<MetalDrmDeviceData>,
+        modeset: &DrmDeviceModeset,
+        preserve: &Preserve,
+    ) -> Result<(), MetalError> {
+        if let Err(e) = modeset.changes.commit(modeset.flags, 0) {
             return Err(MetalError::Modeset(e));
         }
         for connector in dev.connectors.lock().values() {
@@ -2337,13 +3347,19 @@ impl MetalBackend {
             }
             connector.send_hardware_cursor();
             connector.send_vrr_enabled();
+            connector.send_hdr_enabled();
             connector.update_drm_feedback();
             connector.send_formats();
         }
         Ok(())
     }
 
-    fn can_use_current_drm_mode(&self, dev: &Rc<MetalDrmDeviceData>) -> bool {
+    fn can_use_current_drm_mode(
+        &self,
+        dev: &Rc<MetalDrmDeviceData>,
+        changes: &mut Change,
+        flags: &mut u32,
+    ) -> bool {
         let mut used_crtcs = AHashSet::new();
         let mut vrr_crtcs = AHashSet::new();
         let mut used_planes = AHashSet::new();
@@ -2396,6 +3412,14 @@ impl MetalBackend {
                 log::debug!("Connector mode differs from desired mode");
                 return false;
             }
+            if let Some(hdr) = &dd.hdr_props {
+                let hdr_active = hdr.colorspace.value.get() == hdr.colorspace_bt2020;
+                if hdr_active != dd.should_enable_hdr() {
+                    log::debug!("Connector HDR state differs from desired state");
+                    return false;
+                }
+                connector.hdr_active.set(hdr_active);
+            }
             let mut have_primary_plane = false;
             for plane in crtc.possible_planes.values() {
                 if plane.ty == PlaneType::Primary && used_planes.insert(plane.id) {
@@ -2409,12 +3433,10 @@ impl MetalBackend {
             }
         }
 
-        let mut changes = dev.dev.master.change();
-        let mut flags = 0;
         for crtc in dev.dev.crtcs.values() {
             changes.change_object(crtc.id, |c| {
                 if !used_crtcs.contains(&crtc.id) && crtc.active.value.take() {
-                    flags |= DRM_MODE_ATOMIC_ALLOW_MODESET;
+                    *flags |= DRM_MODE_ATOMIC_ALLOW_MODESET;
                     c.change(crtc.active.id, 0);
                 }
                 c.change(crtc.out_fence_ptr, 0);
@@ -2425,7 +3447,7 @@ impl MetalBackend {
                 }
             });
         }
-        if let Err(e) = changes.commit(flags, 0) {
+        if let Err(e) = changes.test(*flags) {
             log::debug!("Could not deactivate crtcs: {}", ErrorFmt(e));
             return false;
         }
@@ -2623,8 +3645,9 @@ impl MetalBackend {
         changes.change_object(connector.id, |c| {
             c.change(dd.crtc_id.id, crtc.id.0 as _);
         });
+        let active = connector.powered.get();
         changes.change_object(crtc.id, |c| {
-            c.change(crtc.active.id, 1);
+            c.change(crtc.active.id, active as _);
             c.change(crtc.mode_id.id, mode_blob.id().0 as _);
             c.change(crtc.vrr_enabled.id, dd.should_enable_vrr() as _);
         });
@@ -2632,10 +3655,12 @@ impl MetalBackend {
         connector.version.fetch_add(1);
         dd.crtc_id.value.set(crtc.id);
         crtc.connector.set(Some(connector.clone()));
-        crtc.active.value.set(true);
+        crtc.active.value.set(active);
         crtc.mode_id.value.set(mode_blob.id());
         crtc.mode_blob.set(Some(Rc::new(mode_blob)));
         crtc.vrr_enabled.value.set(dd.should_enable_vrr() as _);
+        connector.apply_gamma_lut(&crtc, changes)?;
+        connector.apply_hdr(&dd, changes)?;
         Ok(())
     }
 
@@ -2687,7 +3712,11 @@ impl MetalBackend {
         let buffers;
         let buffer_format;
         'primary_plane: {
-            let format = dd.persistent.format.get();
+            let mut format = dd.persistent.format.get();
+            if format == XRGB8888 && connector.hdr_active.get() {
+                // 8 bits per channel cause visible banding with the PQ transfer function.
+                format = XRGB2101010;
+            }
             if format != XRGB8888 {
                 match allocate_primary_plane(format) {
                     Ok(v) => {
@@ -2709,8 +3738,10 @@ impl MetalBackend {
         }
         let mut cursor_plane = None;
         let mut cursor_modifiers = &IndexSet::new();
+        // The cursor plane is not color managed. Render the cursor in software in HDR mode.
         for plane in crtc.possible_planes.values() {
             if plane.ty == PlaneType::Cursor
+                && !connector.hdr_active.get()
                 && !plane.assigned.get()
                 && plane.lease.is_none()
                 && plane.formats.contains_key(&ARGB8888.drm)
@@ -2771,6 +3802,7 @@ impl MetalBackend {
         if let Some(old) = connector.buffers.set(Some(buffers)) {
             old_buffers.push(old);
         }
+        connector.cached_frame.set(None);
         connector.next_buffer.set(1);
         connector.primary_plane.set(Some(primary_plane.clone()));
         if let Some(cp) = &cursor_plane {
@@ -2804,6 +3836,16 @@ impl MetalBackend {
                 dd.mode.as_ref().unwrap(),
             );
         }
+        let fallback = connector
+            .mode_fallback
+            .get()
+            .map(|f| (f.requested.to_backend(), f.chosen.to_backend()));
+        if connector.reported_mode_fallback.replace(fallback) != fallback {
+            if let Some(mode) = &dd.mode {
+                connector.send_event(ConnectorEvent::ModeChanged(mode.to_backend()));
+            }
+            connector.send_event(ConnectorEvent::ModeFallback(fallback.map(|f| f.0)));
+        }
         connector.has_damage.fetch_add(1);
         connector.cursor_changed.set(true);
         connector.schedule_present();
@@ -2871,6 +3913,23 @@ fn modes_equal(a: &DrmModeInfo, b: &DrmModeInfo) -> bool {
         && a.flags == b.flags
 }
 
+/// Returns the mode that should be tried if `mode` cannot be used.
+///
+/// Modes with the same size and a lower refresh rate are preferred over smaller modes.
+fn lower_mode<'a>(modes: &'a [DrmModeInfo], mode: &DrmModeInfo) -> Option<&'a DrmModeInfo> {
+    let size = |m: &DrmModeInfo| m.hdisplay as u32 * m.vdisplay as u32;
+    let lower = modes.iter().filter(|m| m.clock < mode.clock);
+    lower
+        .clone()
+        .filter(|m| (m.hdisplay, m.vdisplay) == (mode.hdisplay, mode.vdisplay))
+        .max_by_key(|m| m.refresh_rate_millihz())
+        .or_else(|| {
+            lower
+                .filter(|m| size(m) < size(mode))
+                .max_by_key(|m| (size(m), m.refresh_rate_millihz()))
+        })
+}
+
 fn should_ignore(connector: &MetalConnector, dd: &ConnectorDisplayData) -> bool {
     !connector.enabled.get()
         || dd.connection != ConnectorStatus::Connected
@@ -11,7 +11,11 @@ use {
         fixed::Fixed,
         format::XRGB8888,
         gfx_api::{AcquireSync, GfxContext, GfxError, GfxFramebuffer, GfxTexture, ReleaseSync},
-        ifs::wl_output::OutputId,
+        ifs::{
+            wl_output::OutputId,
+            wp_presentation_feedback::{KIND_HW_COMPLETION, KIND_VSYNC},
+        },
+        kbvm::KbvmMap,
         state::State,
         time::Time,
         utils::{
@@ -24,31 +28,36 @@ use {
         },
         wire_xcon::{
             ChangeProperty, ChangeWindowAttributes, ConfigureNotify, CreateCursor, CreatePixmap,
-            CreateWindow, CreateWindowValues, DestroyNotify, Dri3Open, Dri3PixmapFromBuffers,
-            Dri3QueryVersion, Extension, FreePixmap, MapWindow, PresentCompleteNotify,
-            PresentIdleNotify, PresentPixmap, PresentQueryVersion, PresentSelectInput,
-            XiButtonPress, XiButtonRelease, XiDeviceInfo, XiEnter, XiEventMask,
-            XiGetDeviceButtonMapping, XiGrabDevice, XiHierarchy, XiKeyPress, XiKeyRelease,
-            XiMotion, XiQueryDevice, XiQueryVersion, XiSelectEvents, XiUngrabDevice,
-            XkbPerClientFlags, XkbUseExtension,
+            CreateWindow, CreateWindowValues, DestroyNotify, DestroyWindow, Dri3Open,
+            Dri3PixmapFromBuffers, Dri3QueryVersion, Extension, FreePixmap, GetAtomName,
+            InternAtom, MapWindow, PresentCompleteNotify, PresentIdleNotify, PresentPixmap,
+            PresentQueryVersion, PresentSelectInput, PropertyNotify, XiButtonPress,
+            XiButtonRelease, XiDeviceInfo, XiEnter, XiEventMask, XiGetDeviceButtonMapping,
+            XiGrabDevice, XiHierarchy, XiKeyPress, XiKeyRelease, XiMotion, XiQueryDevice,
+            XiQueryVersion, XiSelectEvents, XiUngrabDevice, XkbGetNames, XkbPerClientFlags,
+            XkbUseExtension,
         },
         xcon::{
             consts::{
-                ATOM_STRING, ATOM_WM_CLASS, EVENT_MASK_EXPOSURE, EVENT_MASK_STRUCTURE_NOTIFY,
-                EVENT_MASK_VISIBILITY_CHANGE, GRAB_MODE_ASYNC, GRAB_STATUS_SUCCESS,
-                INPUT_DEVICE_ALL, INPUT_DEVICE_ALL_MASTER, INPUT_DEVICE_TYPE_MASTER_KEYBOARD,
-                INPUT_HIERARCHY_MASK_MASTER_ADDED, INPUT_HIERARCHY_MASK_MASTER_REMOVED,
-                PRESENT_EVENT_MASK_COMPLETE_NOTIFY, PRESENT_EVENT_MASK_IDLE_NOTIFY,
-                PROP_MODE_REPLACE, WINDOW_CLASS_INPUT_OUTPUT, XI_EVENT_MASK_BUTTON_PRESS,
-                XI_EVENT_MASK_BUTTON_RELEASE, XI_EVENT_MASK_ENTER, XI_EVENT_MASK_FOCUS_IN,
-                XI_EVENT_MASK_FOCUS_OUT, XI_EVENT_MASK_HIERARCHY, XI_EVENT_MASK_KEY_PRESS,
-                XI_EVENT_MASK_KEY_RELEASE, XI_EVENT_MASK_LEAVE, XI_EVENT_MASK_MOTION,
-                XI_EVENT_MASK_TOUCH_BEGIN, XI_EVENT_MASK_TOUCH_END, XI_EVENT_MASK_TOUCH_UPDATE,
+                ATOM_STRING, ATOM_WM_CLASS, EVENT_MASK_EXPOSURE, EVENT_MASK_PROPERTY_CHANGE,
+                EVENT_MASK_STRUCTURE_NOTIFY, EVENT_MASK_VISIBILITY_CHANGE, GRAB_MODE_ASYNC,
+                GRAB_STATUS_SUCCESS, INPUT_DEVICE_ALL, INPUT_DEVICE_ALL_MASTER,
+                INPUT_DEVICE_TYPE_MASTER_KEYBOARD, INPUT_HIERARCHY_MASK_MASTER_ADDED,
+                INPUT_HIERARCHY_MASK_MASTER_REMOVED, PRESENT_COMPLETE_KIND_PIXMAP,
+                PRESENT_COMPLETE_MODE_SKIP, PRESENT_EVENT_MASK_COMPLETE_NOTIFY,
+                PRESENT_EVENT_MASK_IDLE_NOTIFY, PROP_MODE_REPLACE, WINDOW_CLASS_INPUT_OUTPUT,
+                XI_EVENT_MASK_BUTTON_PRESS, XI_EVENT_MASK_BUTTON_RELEASE, XI_EVENT_MASK_ENTER,
+                XI_EVENT_MASK_FOCUS_IN, XI_EVENT_MASK_FOCUS_OUT, XI_EVENT_MASK_HIERARCHY,
+                XI_EVENT_MASK_KEY_PRESS, XI_EVENT_MASK_KEY_RELEASE, XI_EVENT_MASK_LEAVE,
+                XI_EVENT_MASK_MOTION, XI_EVENT_MASK_TOUCH_BEGIN, XI_EVENT_MASK_TOUCH_END,
+                XI_EVENT_MASK_TOUCH_UPDATE, XKB_NAME_DETAIL_COMPAT, XKB_NAME_DETAIL_KEYCODES,
+                XKB_NAME_DETAIL_SYMBOLS, XKB_NAME_DETAIL_TYPES,
                 XKB_PER_CLIENT_FLAG_DETECTABLE_AUTO_REPEAT,
             },
-            Event, XEvent, Xcon, XconError,
+            AsyncReply, Event, XEvent, Xcon, XconError,
         },
     },
+    bstr::ByteSlice,
     jay_config::video::GfxApi,
     std::{
         any::Any,
@@ -63,6 +72,12 @@ use {
     uapi::c::dev_t,
 };
 
+const DEFAULT_REFRESH_RATE_MILLIHZ: u32 = 60_000;
+const DEFAULT_WINDOW_SIZE: (i32, i32) = (800, 600);
+/// Measured refresh rates that differ by less than this from the current rate are
+/// ignored to avoid mode changes due to jitter.
+const REFRESH_RATE_TOLERANCE_PERMILLE: u64 = 5;
+
 #[derive(Debug, Error)]
 pub enum XBackendError {
     #[error("Could not connect to the X server")]
@@ -115,8 +130,29 @@ pub enum XBackendError {
     MapWindow(#[source] XconError),
     #[error("Could not query device")]
     QueryDevice(#[source] XconError),
+    #[error("Could not intern an atom")]
+    InternAtom(#[source] XconError),
+    #[error("Could not select root window events")]
+    RootWindowEvents(#[source] XconError),
     #[error("Render device does not support XRGB8888 format")]
     XRGB8888,
+    #[error("Invalid window size {0}x{1}")]
+    InvalidWindowSize(i32, i32),
+    #[error("Connector {0} is not an X window")]
+    UnknownOutput(ConnectorId),
+}
+
+/// A request that has been sent to the X server but whose result has not yet been
+/// checked, together with the error to return if it failed.
+type PendingRequest = (AsyncReply<()>, fn(XconError) -> XBackendError);
+
+async fn check_requests(requests: Vec<PendingRequest>) -> Result<(), XBackendError> {
+    for (reply, map_err) in requests {
+        if let Err(e) = reply.await {
+            return Err(map_err(e));
+        }
+    }
+    Ok(())
 }
 
 pub async fn create(state: &Rc<State>) -> Result<Rc<XBackend>, XBackendError> {
@@ -221,6 +257,30 @@ pub async fn create(state: &Rc<State>) -> Result<Rc<XBackend>, XBackendError> {
             return Err(XBackendError::SelectHierarchyEvents(e));
         }
     }
+    let xkb_rules_names = {
+        let ia = InternAtom {
+            only_if_exists: 0,
+            name: b"_XKB_RULES_NAMES".as_bstr(),
+        };
+        match c.call(&ia).await {
+            Ok(r) => r.get().atom,
+            Err(e) => return Err(XBackendError::InternAtom(e)),
+        }
+    };
+    {
+        // The X server updates this property whenever the keymap is changed, e.g. via
+        // setxkbmap.
+        let cwa = ChangeWindowAttributes {
+            window: root,
+            values: CreateWindowValues {
+                event_mask: Some(EVENT_MASK_PROPERTY_CHANGE),
+                ..Default::default()
+            },
+        };
+        if let Err(e) = c.call(&cwa).await {
+            return Err(XBackendError::RootWindowEvents(e));
+        }
+    }
 
     let data = Rc::new(XBackend {
         state: state.clone(),
@@ -236,8 +296,22 @@ pub async fn create(state: &Rc<State>) -> Result<Rc<XBackend>, XBackendError> {
         grab_requests: Default::default(),
         drm_device_id: state.drm_dev_ids.next(),
         drm_dev,
+        running: Cell::new(false),
+        window_setups: Default::default(),
+        xkb_rules_names,
     });
-    data.add_output().await?;
+    let mut sizes: Vec<_> = state
+        .run_args
+        .x11_windows
+        .iter()
+        .map(|s| (s.width, s.height))
+        .collect();
+    if sizes.is_empty() {
+        sizes.push(DEFAULT_WINDOW_SIZE);
+    }
+    for (width, height) in sizes {
+        data.add_output(width, height).await?;
+    }
 
     Ok(data)
 }
@@ -254,6 +328,28 @@ impl Backend for XBackend {
     fn into_any(self: Rc<Self>) -> Rc<dyn Any> {
         self
     }
+
+    fn create_virtual_output(
+        self: Rc<Self>,
+        mode: Mode,
+    ) -> Result<Rc<dyn Connector>, Box<dyn Error>> {
+        let mut requests = vec![];
+        let output = self.create_output(mode.width, mode.height, &mut requests)?;
+        self.window_setups.push((output.clone(), requests));
+        if self.running.get() {
+            self.active_output(&output);
+        }
+        Ok(output)
+    }
+
+    fn remove_virtual_output(&self, id: ConnectorId) -> Result<(), Box<dyn Error>> {
+        let output = self.outputs.lock().values().find(|o| o.id == id).cloned();
+        let Some(output) = output else {
+            return Err(Box::new(XBackendError::UnknownOutput(id)));
+        };
+        self.remove_output(&output);
+        Ok(())
+    }
 }
 
 pub struct XBackend {
@@ -270,6 +366,9 @@ pub struct XBackend {
     grab_requests: AsyncQueue<(Rc<XSeat>, bool)>,
     drm_device_id: DrmDeviceId,
     drm_dev: dev_t,
+    running: Cell<bool>,
+    window_setups: AsyncQueue<(Rc<XOutput>, Vec<PendingRequest>)>,
+    xkb_rules_names: u32,
 }
 
 impl XBackend {
@@ -289,6 +388,10 @@ impl XBackend {
             Phase::Present,
             self.clone().present_handler(),
         );
+        let _setup = self
+            .state
+            .eng
+            .spawn("window setup handler", self.clone().window_setup_handler());
 
         self.state.set_render_ctx(Some(self.ctx.clone()));
         self.state
@@ -298,8 +401,9 @@ impl XBackend {
                 id: self.drm_device_id,
                 dev: self.drm_dev,
             })));
+        self.running.set(true);
         for (_, output) in self.outputs.lock().iter() {
-            self.active_output(output).await;
+            self.active_output(output);
         }
 
         self.state
@@ -330,6 +434,16 @@ impl XBackend {
         }
     }
 
+    async fn window_setup_handler(self: Rc<Self>) {
+        loop {
+            let (output, requests) = self.window_setups.pop().await;
+            if let Err(e) = check_requests(requests).await {
+                log::error!("Could not create a window: {}", ErrorFmt(e));
+                self.remove_output(&output);
+            }
+        }
+    }
+
     async fn grab_handler(self: Rc<Self>) {
         loop {
             let (dev, grab) = self.grab_requests.pop().await;
@@ -371,11 +485,12 @@ impl XBackend {
         }
     }
 
-    async fn create_images(
+    fn create_images(
         &self,
         window: u32,
         width: i32,
         height: i32,
+        requests: &mut Vec<PendingRequest>,
     ) -> Result<[XImage; 2], XBackendError> {
         let mut images = [None, None];
         let formats = self.ctx.formats();
@@ -431,9 +546,7 @@ impl XBackend {
                     modifier: dma.modifier,
                     buffers: buffers.into(),
                 };
-                if let Err(e) = self.c.call(&pfb).await {
-                    return Err(XBackendError::ImportBuffer(e));
-                }
+                requests.push((self.c.call(&pfb), XBackendError::ImportBuffer));
                 pfb.pixmap
             };
             *image = Some(XImage {
@@ -449,9 +562,25 @@ impl XBackend {
         Ok([images[0].take().unwrap(), images[1].take().unwrap()])
     }
 
-    async fn add_output(self: &Rc<Self>) -> Result<(), XBackendError> {
-        const WIDTH: i32 = 800;
-        const HEIGHT: i32 = 600;
+    async fn add_output(self: &Rc<Self>, width: i32, height: i32) -> Result<(), XBackendError> {
+        let mut requests = vec![];
+        self.create_output(width, height, &mut requests)?;
+        check_requests(requests).await
+    }
+
+    /// Creates a window and the output displayed in it.
+    ///
+    /// The requests are sent without waiting for their results. The caller must check
+    /// the results with `check_requests`.
+    fn create_output(
+        self: &Rc<Self>,
+        width: i32,
+        height: i32,
+        requests: &mut Vec<PendingRequest>,
+    ) -> Result<Rc<XOutput>, XBackendError> {
+        if width <= 0 || height <= 0 || width > u16::MAX as i32 || height > u16::MAX as i32 {
+            return Err(XBackendError::InvalidWindowSize(width, height));
+        }
         let window_id = {
             let cw = CreateWindow {
                 depth: 0,
@@ -459,28 +588,29 @@ impl XBackend {
                 parent: self.root,
                 x: 0,
                 y: 0,
-                width: WIDTH as _,
-                height: HEIGHT as _,
+                width: width as _,
+                height: height as _,
                 border_width: 0,
                 class: WINDOW_CLASS_INPUT_OUTPUT,
                 visual: 0,
                 values: Default::default(),
             };
-            if let Err(e) = self.c.call(&cw).await {
-                return Err(XBackendError::CreateWindow(e));
-            }
+            requests.push((self.c.call(&cw), XBackendError::CreateWindow));
             cw.wid
         };
-        let images = self.create_images(window_id, WIDTH, HEIGHT).await?;
+        let images = self.create_images(window_id, width, height, requests)?;
         let output = Rc::new(XOutput {
             id: self.state.connector_ids.next(),
             backend: self.clone(),
             window: window_id,
             events: Default::default(),
-            width: Cell::new(0),
-            height: Cell::new(0),
+            width: Cell::new(width),
+            height: Cell::new(height),
             serial: Default::default(),
             next_msc: Cell::new(0),
+            last_msc: Cell::new(0),
+            last_ust: Cell::new(0),
+            refresh_rate_millihz: Cell::new(DEFAULT_REFRESH_RATE_MILLIHZ),
             next_image: Default::default(),
             cb: CloneCell::new(None),
             images,
@@ -495,9 +625,7 @@ impl XBackend {
                 format: 8,
                 data: class.as_bytes(),
             };
-            if let Err(e) = self.c.call(&cp).await {
-                return Err(XBackendError::WmClass(e));
-            };
+            requests.push((self.c.call(&cp), XBackendError::WmClass));
         }
         {
             let cwa = ChangeWindowAttributes {
@@ -512,13 +640,12 @@ impl XBackend {
                     ..Default::default()
                 },
             };
-            if let Err(e) = self.c.call(&cwa).await {
-                return Err(XBackendError::WindowEvents(e));
-            }
-        }
-        if let Err(e) = self.c.call(&MapWindow { window: window_id }).await {
-            return Err(XBackendError::MapWindow(e));
+            requests.push((self.c.call(&cwa), XBackendError::WindowEvents));
         }
+        requests.push((
+            self.c.call(&MapWindow { window: window_id }),
+            XBackendError::MapWindow,
+        ));
         {
             let mask = 0
                 | XI_EVENT_MASK_MOTION
@@ -541,9 +668,7 @@ impl XBackend {
                 window: window_id,
                 masks: Cow::Borrowed(&mask[..]),
             };
-            if let Err(e) = self.c.call(&xs).await {
-                return Err(XBackendError::CannotSelectInputEvents(e));
-            }
+            requests.push((self.c.call(&xs), XBackendError::CannotSelectInputEvents));
         }
         {
             let mask = 0 | PRESENT_EVENT_MASK_IDLE_NOTIFY | PRESENT_EVENT_MASK_COMPLETE_NOTIFY;
@@ -552,15 +677,32 @@ impl XBackend {
                 window: window_id,
                 event_mask: mask,
             };
-            if let Err(e) = self.c.call(&si).await {
-                return Err(XBackendError::CannotSelectPresentEvents(e));
-            }
+            requests.push((self.c.call(&si), XBackendError::CannotSelectPresentEvents));
         }
         self.outputs.set(window_id, output.clone());
-        Ok(())
+        Ok(output)
+    }
+
+    fn remove_output(&self, output: &XOutput) {
+        if self.outputs.remove(&output.window).is_none() {
+            return;
+        }
+        for image in &output.images {
+            #[expect(clippy::let_underscore_future)]
+            let _ = self.c.call(&FreePixmap {
+                pixmap: image.pixmap.get(),
+            });
+        }
+        #[expect(clippy::let_underscore_future)]
+        let _ = self.c.call(&DestroyWindow {
+            window: output.window,
+        });
+        output.events.push(ConnectorEvent::Disconnected);
+        output.events.push(ConnectorEvent::Removed);
+        output.changed();
     }
 
-    async fn active_output(&self, output: &Rc<XOutput>) {
+    fn active_output(&self, output: &Rc<XOutput>) {
         self.state
             .backend_events
             .push(BackendEvent::NewConnector(output.clone()));
@@ -575,15 +717,18 @@ impl XBackend {
             initial_mode: Mode {
                 width: output.width.get(),
                 height: output.height.get(),
-                refresh_rate_millihz: 60_000, // TODO
+                refresh_rate_millihz: output.refresh_rate_millihz.get(),
             },
             width_mm: output.width.get(),
             height_mm: output.height.get(),
             non_desktop: false,
             vrr_capable: false,
+            hdr_capable: false,
+            edid: None,
+            properties: Default::default(),
         }));
         output.changed();
-        self.present(output).await;
+        self.schedule_present(output);
     }
 
     async fn query_devices(self: &Rc<Self>, deviceid: u16) -> Result<(), XBackendError> {
@@ -634,10 +779,12 @@ impl XBackend {
             kb_events: RefCell::new(Default::default()),
             mouse_events: RefCell::new(Default::default()),
             button_map: Default::default(),
+            keymap: Default::default(),
             kb_name: Rc::new(format!("kb{}", info.deviceid)),
             mouse_name: Rc::new(format!("mouse{}", info.deviceid)),
         });
         seat.update_button_map().await;
+        seat.keymap.set(self.download_keymap(info.deviceid).await);
         self.seats.set(info.deviceid, seat.clone());
         self.mouse_seats.set(info.attachment, seat.clone());
         self.state
@@ -652,6 +799,69 @@ impl XBackend {
             ))));
     }
 
+    /// Creates a keymap from the XKB components of a keyboard of the X server.
+    async fn download_keymap(&self, deviceid: u16) -> Option<Rc<KbvmMap>> {
+        let which = XKB_NAME_DETAIL_KEYCODES
+            | XKB_NAME_DETAIL_SYMBOLS
+            | XKB_NAME_DETAIL_TYPES
+            | XKB_NAME_DETAIL_COMPAT;
+        let gn = XkbGetNames {
+            device_spec: deviceid,
+            which,
+        };
+        let atoms = match self.c.call(&gn).await {
+            Ok(r) if r.get().which == which => r.get().values.to_vec(),
+            Ok(_) => {
+                log::warn!(
+                    "X server did not return the keymap of keyboard {}",
+                    deviceid
+                );
+                return None;
+            }
+            Err(e) => {
+                log::warn!(
+                    "Could not query the keymap of keyboard {}: {}",
+                    deviceid,
+                    ErrorFmt(e),
+                );
+                return None;
+            }
+        };
+        // The names are ordered by their bit in `which`.
+        let mut names = vec![];
+        for atom in atoms {
+            match self.c.call(&GetAtomName { atom }).await {
+                Ok(r) => names.push(r.get().name.to_string()),
+                Err(e) => {
+                    log::warn!("Could not get the name of atom {}: {}", atom, ErrorFmt(e));
+                    return None;
+                }
+            }
+        }
+        let [keycodes, symbols, types, compat] = &names[..] else {
+            return None;
+        };
+        let keymap = format!(
+            "xkb_keymap {{ \
+                xkb_keycodes {{ include \"{keycodes}\" }}; \
+                xkb_types {{ include \"{types}\" }}; \
+                xkb_compat {{ include \"{compat}\" }}; \
+                xkb_symbols {{ include \"{symbols}\" }}; \
+            }};"
+        );
+        match self.state.kb_ctx.parse_keymap(keymap.as_bytes()) {
+            Ok(m) => Some(m),
+            Err(e) => {
+                log::warn!(
+                    "Could not compile the keymap of keyboard {}: {}",
+                    deviceid,
+                    ErrorFmt(e),
+                );
+                None
+            }
+        }
+    }
+
     async fn handle_event(self: &Rc<Self>, event: &Event) -> Result<(), XBackendError> {
         match event.ext() {
             Some(ext) => self.handle_ext_event(ext, event).await,
@@ -675,6 +885,7 @@ impl XBackend {
         match event.code() {
             ConfigureNotify::OPCODE => self.handle_configure(event).await,
             DestroyNotify::OPCODE => self.handle_destroy(event),
+            PropertyNotify::OPCODE => self.handle_property(event).await,
             _ => Ok(()),
         }
     }
@@ -695,7 +906,24 @@ impl XBackend {
             Some(o) => o,
             _ => return Ok(()),
         };
+        if event.kind != PRESENT_COMPLETE_KIND_PIXMAP {
+            return Ok(());
+        }
+        self.update_refresh_rate(&output, event.ust, event.msc);
         output.next_msc.set(event.msc + 1);
+        if event.mode != PRESENT_COMPLETE_MODE_SKIP {
+            if let Some(node) = self.state.root.outputs.get(&output.id) {
+                let refresh_nsec = 1_000_000_000_000 / output.refresh_rate_millihz.get() as u64;
+                node.presented(
+                    event.ust / 1_000_000,
+                    (event.ust % 1_000_000 * 1000) as u32,
+                    refresh_nsec as u32,
+                    event.msc,
+                    KIND_VSYNC | KIND_HW_COMPLETION,
+                    false,
+                );
+            }
+        }
         let image = &output.images[output.next_image.get() % output.images.len()];
         if image.idle.get() {
             self.schedule_present(&output);
@@ -706,6 +934,33 @@ impl XBackend {
         Ok(())
     }
 
+    /// Derives the refresh rate of the host output from the UST/MSC pairs reported by the
+    /// X server.
+    fn update_refresh_rate(&self, output: &XOutput, ust: u64, msc: u64) {
+        let last_ust = output.last_ust.replace(ust);
+        let last_msc = output.last_msc.replace(msc);
+        if last_ust == 0 || msc <= last_msc || ust <= last_ust {
+            return;
+        }
+        let nsec_per_frame = (ust - last_ust) * 1000 / (msc - last_msc);
+        if nsec_per_frame == 0 {
+            return;
+        }
+        let millihz = (1_000_000_000_000 / nsec_per_frame).min(u32::MAX as u64) as u32;
+        let old = output.refresh_rate_millihz.get();
+        let diff = old.abs_diff(millihz) as u64;
+        if diff * 1000 < old as u64 * REFRESH_RATE_TOLERANCE_PERMILLE {
+            return;
+        }
+        output.refresh_rate_millihz.set(millihz);
+        output.events.push(ConnectorEvent::ModeChanged(Mode {
+            width: output.width.get(),
+            height: output.height.get(),
+            refresh_rate_millihz: millihz,
+        }));
+        output.changed();
+    }
+
     fn handle_present_idle(self: &Rc<Self>, event: &Event) -> Result<(), XBackendError> {
         let event: PresentIdleNotify = event.parse()?;
         let output = match self.outputs.get(&event.window) {
@@ -739,6 +994,9 @@ impl XBackend {
     }
 
     async fn present(&self, output: &Rc<XOutput>) {
+        if !self.outputs.contains(&output.window) {
+            return;
+        }
         let serial = output.serial.fetch_add(1);
 
         let image = &output.images[output.next_image.fetch_add(1) % output.images.len()];
@@ -775,7 +1033,7 @@ impl XBackend {
             idle_fence: 0,
             options: 0,
             target_msc: output.next_msc.get(),
-            divisor: 1,
+            divisor: 0,
             remainder: 0,
             notifies: Default::default(),
         };
@@ -922,7 +1180,6 @@ impl XBackend {
     }
 
     fn handle_destroy(&self, event: &Event) -> Result<(), XBackendError> {
-        self.state.ring.stop();
         let event: DestroyNotify = event.parse()?;
         let output = match self.outputs.remove(&event.event) {
             Some(o) => o,
@@ -931,6 +1188,33 @@ impl XBackend {
         output.events.push(ConnectorEvent::Disconnected);
         output.events.push(ConnectorEvent::Removed);
         output.changed();
+        if self.outputs.is_empty() {
+            self.state.ring.stop();
+        }
+        Ok(())
+    }
+
+    async fn handle_property(&self, event: &Event) -> Result<(), XBackendError> {
+        let event: PropertyNotify = event.parse()?;
+        if event.window != self.root || event.atom != self.xkb_rules_names {
+            return Ok(());
+        }
+        let seats: Vec<_> = self.seats.lock().values().cloned().collect();
+        for seat in seats {
+            let Some(keymap) = self.download_keymap(seat.kb).await else {
+                continue;
+            };
+            seat.keymap.set(Some(keymap.clone()));
+            let data = self
+                .state
+                .input_device_handlers
+                .borrow()
+                .get(&seat.kb_id)
+                .map(|d| d.data.clone());
+            if let Some(data) = data {
+                data.set_keymap(Some(keymap));
+            }
+        }
         Ok(())
     }
 
@@ -946,7 +1230,9 @@ impl XBackend {
         changed |= output.width.replace(width) != width;
         changed |= output.height.replace(height) != height;
         if changed {
-            let images = self.create_images(output.window, width, height).await?;
+            let mut requests = vec![];
+            let images = self.create_images(output.window, width, height, &mut requests)?;
+            check_requests(requests).await?;
             for (new, old) in images.iter().zip(output.images.iter()) {
                 #[expect(clippy::let_underscore_future)]
                 let _ = self.c.call(&FreePixmap {
@@ -959,7 +1245,7 @@ impl XBackend {
             output.events.push(ConnectorEvent::ModeChanged(Mode {
                 width,
                 height,
-                refresh_rate_millihz: 60, // TODO
+                refresh_rate_millihz: output.refresh_rate_millihz.get(),
             }));
             output.changed();
         }
@@ -1026,6 +1312,9 @@ struct XOutput {
     height: Cell<i32>,
     serial: NumCell<u32>,
     next_msc: Cell<u64>,
+    last_msc: Cell<u64>,
+    last_ust: Cell<u64>,
+    refresh_rate_millihz: Cell<u32>,
     next_image: NumCell<usize>,
     images: [XImage; 2],
     cb: CloneCell<Option<Rc<dyn Fn()>>>,
@@ -1094,6 +1383,7 @@ struct XSeat {
     kb_events: RefCell<VecDeque<InputEvent>>,
     mouse_events: RefCell<VecDeque<InputEvent>>,
     button_map: CopyHashMap<u32, u32>,
+    keymap: CloneCell<Option<Rc<KbvmMap>>>,
     kb_name: Rc<String>,
     mouse_name: Rc<String>,
 }
@@ -1214,6 +1504,10 @@ impl InputDevice for XSeatKeyboard {
     fn set_natural_scrolling_enabled(&self, enabled: bool) {
         let _ = enabled;
     }
+
+    fn keymap(&self) -> Option<Rc<KbvmMap>> {
+        self.0.keymap.get()
+    }
 }
 
 impl InputDevice for XSeatMouse {
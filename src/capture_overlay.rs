@@ -0,0 +1,279 @@
+use {
+    crate::{
+        format::ARGB8888,
+        gfx_api::GfxTexture,
+        rect::Rect,
+        scale::Scale,
+        state::State,
+        text::TextTexture,
+        utils::{
+            asyncevent::AsyncEvent, errorfmt::ErrorFmt, on_drop_event::OnDropEvent,
+            smallmap::SmallMapMut,
+        },
+    },
+    png::{ColorType, DecodingError, Transformations},
+    std::{
+        cell::{Cell, RefCell},
+        fs::File,
+        io::{self, BufReader},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+const CAPTURE_OVERLAY_PADDING: i32 = 5;
+const GAP: i32 = 10;
+
+#[derive(Debug, Error)]
+pub enum CaptureOverlayError {
+    #[error("Could not open the image")]
+    Open(#[source] io::Error),
+    #[error("Could not decode the image")]
+    Decode(#[source] DecodingError),
+    #[error("The image has an unsupported color type {0:?}")]
+    ColorType(ColorType),
+}
+
+/// Text and an image that are shown in the bottom-right corner of screen captures but
+/// not on the outputs.
+#[derive(Default)]
+pub struct CaptureOverlay {
+    text: RefCell<Option<String>>,
+    image: RefCell<Option<OverlayImage>>,
+    textures: RefCell<SmallMapMut<Scale, TextTexture, 2>>,
+    render_scheduled: Cell<bool>,
+    changed: AsyncEvent,
+}
+
+struct OverlayImage {
+    width: i32,
+    height: i32,
+    data: Vec<Cell<u8>>,
+    tex: RefCell<Option<(u32, Rc<dyn GfxTexture>)>>,
+}
+
+/// The positions of the overlay relative to the captured area.
+pub struct CaptureOverlayLayout {
+    pub text: Option<(Rect, Rect, Rc<dyn GfxTexture>)>,
+    pub image: Option<(Rect, Rc<dyn GfxTexture>)>,
+}
+
+pub async fn capture_overlay_textures(state: Rc<State>) {
+    let overlay = &state.capture_overlay;
+    loop {
+        overlay.changed.triggered().await;
+        if overlay.render_scheduled.replace(false) {
+            overlay.render_phase1(&state).triggered().await;
+            overlay.render_phase2(&state);
+        }
+    }
+}
+
+impl CaptureOverlay {
+    pub fn clear(&self) {
+        self.text.take();
+        self.image.take();
+        self.textures.borrow_mut().clear();
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.text.borrow().is_some() || self.image.borrow().is_some()
+    }
+
+    pub fn set(
+        &self,
+        state: &State,
+        text: Option<String>,
+        image: Option<&str>,
+    ) -> Result<(), CaptureOverlayError> {
+        *self.text.borrow_mut() = text.filter(|t| !t.is_empty());
+        self.render_textures();
+        self.image.take();
+        state.damage(state.root.extents.get());
+        if let Some(path) = image {
+            *self.image.borrow_mut() = Some(load_png(path)?);
+        }
+        Ok(())
+    }
+
+    pub fn render_textures(&self) {
+        self.textures.borrow_mut().clear();
+        if self.text.borrow().is_some() && !self.render_scheduled.replace(true) {
+            self.changed.trigger();
+        }
+    }
+
+    fn render_phase1(&self, state: &State) -> Rc<AsyncEvent> {
+        let on_completed = Rc::new(OnDropEvent::default());
+        let Some(ctx) = state.render_ctx.get() else {
+            return on_completed.event();
+        };
+        let text = self.text.borrow();
+        let Some(text) = &*text else {
+            return on_completed.event();
+        };
+        let theme = &state.theme;
+        let font = theme.font.get();
+        let color = theme.colors.bar_text.get();
+        let th = theme.sizes.title_height.get();
+        let scales = state.scales.lock();
+        let textures = &mut *self.textures.borrow_mut();
+        for (scale, _) in scales.iter() {
+            let tex =
+                textures.get_or_insert_with(*scale, || TextTexture::new(&state.cpu_worker, &ctx));
+            let mut height = th;
+            let mut scalef = None;
+            if *scale != 1 {
+                let scale = scale.to_f64();
+                height = (height as f64 * scale).round() as _;
+                scalef = Some(scale);
+            }
+            tex.schedule_render_fitting(
+                on_completed.clone(),
+                Some(height),
+                &font,
+                text,
+                color,
+                false,
+                scalef,
+            );
+        }
+        on_completed.event()
+    }
+
+    fn render_phase2(&self, state: &State) {
+        for (_, tex) in &*self.textures.borrow() {
+            if let Err(e) = tex.flip() {
+                log::error!("Could not render the capture overlay: {}", ErrorFmt(e));
+            }
+        }
+        state.damage(state.root.extents.get());
+    }
+
+    fn image_texture(&self, state: &State) -> Option<Rc<dyn GfxTexture>> {
+        let image = self.image.borrow();
+        let image = image.as_ref()?;
+        let ctx = state.render_ctx.get()?;
+        let version = state.render_ctx_version.get();
+        let tex = &mut *image.tex.borrow_mut();
+        if let Some((v, tex)) = tex {
+            if *v == version {
+                return Some(tex.clone());
+            }
+        }
+        let res = ctx.shmem_texture(
+            None,
+            &image.data,
+            ARGB8888,
+            image.width,
+            image.height,
+            image.width * 4,
+            None,
+        );
+        match res {
+            Ok(t) => {
+                let t = t.into_texture();
+                *tex = Some((version, t.clone()));
+                Some(t)
+            }
+            Err(e) => {
+                log::error!(
+                    "Could not upload the capture overlay image: {}",
+                    ErrorFmt(e)
+                );
+                None
+            }
+        }
+    }
+
+    /// Returns the layout of the overlay in a captured area of the given size.
+    pub fn layout(
+        &self,
+        state: &State,
+        width: i32,
+        height: i32,
+        scale: Scale,
+    ) -> Option<CaptureOverlayLayout> {
+        let text_texture = match self.text.borrow().is_some() {
+            true => self.textures.borrow().get(&scale).and_then(|t| t.texture()),
+            false => None,
+        };
+        let image_texture = self.image_texture(state);
+        if text_texture.is_none() && image_texture.is_none() {
+            return None;
+        }
+        let scalef = scale.to_f64();
+        let mut y2 = height - GAP;
+        let mut text = None;
+        if let Some(texture) = text_texture {
+            let theme = &state.theme;
+            let th = theme.sizes.title_height.get();
+            let (tex_width, _) = texture.size();
+            let text_width = (tex_width as f64 / scalef).ceil() as i32;
+            let bg_width = text_width + 2 * CAPTURE_OVERLAY_PADDING;
+            let bg_height = th + 2 * CAPTURE_OVERLAY_PADDING;
+            let bg = Rect::new_sized(width - GAP - bg_width, y2 - bg_height, bg_width, bg_height)?;
+            let tex = Rect::new_sized(
+                bg.x1() + CAPTURE_OVERLAY_PADDING,
+                bg.y1() + CAPTURE_OVERLAY_PADDING,
+                text_width,
+                th,
+            )?;
+            y2 = bg.y1() - GAP;
+            text = Some((bg, tex, texture));
+        }
+        let mut image = None;
+        if let Some(texture) = image_texture {
+            let (tex_width, tex_height) = texture.size();
+            let img_width = (tex_width as f64 / scalef).ceil() as i32;
+            let img_height = (tex_height as f64 / scalef).ceil() as i32;
+            let rect = Rect::new_sized(
+                width - GAP - img_width,
+                y2 - img_height,
+                img_width,
+                img_height,
+            )?;
+            image = Some((rect, texture));
+        }
+        Some(CaptureOverlayLayout { text, image })
+    }
+}
+
+fn load_png(path: &str) -> Result<OverlayImage, CaptureOverlayError> {
+    let file = File::open(path).map_err(CaptureOverlayError::Open)?;
+    let mut decoder = png::Decoder::new(BufReader::new(file));
+    decoder.set_transformations(Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(CaptureOverlayError::Decode)?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader
+        .next_frame(&mut buf)
+        .map_err(CaptureOverlayError::Decode)?;
+    let buf = &buf[..info.buffer_size()];
+    let channels = match info.color_type {
+        ColorType::Grayscale => 1,
+        ColorType::GrayscaleAlpha => 2,
+        ColorType::Rgb => 3,
+        ColorType::Rgba => 4,
+        ct => return Err(CaptureOverlayError::ColorType(ct)),
+    };
+    let mut data = Vec::with_capacity(buf.len() / channels * 4);
+    for px in buf.chunks_exact(channels) {
+        let (r, g, b, a) = match *px {
+            [l] => (l, l, l, 255),
+            [l, a] => (l, l, l, a),
+            [r, g, b] => (r, g, b, 255),
+            [r, g, b, a] => (r, g, b, a),
+            _ => unreachable!(),
+        };
+        let premultiply = |c: u8| ((c as u32 * a as u32 + 127) / 255) as u8;
+        for c in [premultiply(b), premultiply(g), premultiply(r), a] {
+            data.push(Cell::new(c));
+        }
+    }
+    Ok(OverlayImage {
+        width: info.width as _,
+        height: info.height as _,
+        data,
+        tex: Default::default(),
+    })
+}
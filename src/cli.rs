@@ -1,5 +1,7 @@
+mod bench;
 mod color;
 mod damage_tracking;
+mod dump_state;
 mod duration;
 mod generate;
 mod idle;
@@ -11,13 +13,15 @@ mod run_privileged;
 pub mod screenshot;
 mod seat_test;
 mod set_log_level;
+mod stats;
 mod unlock;
 mod xwayland;
 
 use {
     crate::{
         cli::{
-            damage_tracking::DamageTrackingArgs, idle::IdleCmd, input::InputArgs, randr::RandrArgs,
+            bench::BenchArgs, damage_tracking::DamageTrackingArgs, dump_state::DumpStateArgs,
+            idle::IdleCmd, input::InputArgs, randr::RandrArgs, stats::StatsArgs,
             xwayland::XwaylandArgs,
         },
         compositor::start_compositor,
@@ -27,6 +31,7 @@ use {
     ::log::Level,
     clap::{builder::PossibleValue, Args, Parser, Subcommand, ValueEnum, ValueHint},
     clap_complete::Shell,
+    std::str::FromStr,
 };
 
 /// A wayland compositor.
@@ -78,6 +83,21 @@ pub enum Cmd {
     DamageTracking(DamageTrackingArgs),
     /// Inspect/modify xwayland settings.
     Xwayland(XwaylandArgs),
+    /// Inspect frame timing and GPU statistics.
+    Stats(StatsArgs),
+    /// Benchmark composition with synthetic surfaces.
+    ///
+    /// The benchmark creates overlay surfaces that redraw themselves in every frame and
+    /// reports the achieved frame rates, the CPU time of the compositor, and the GPU time
+    /// of each output. To benchmark without display hardware, start the compositor with
+    /// `--backends headless`.
+    Bench(BenchArgs),
+    /// Create an archive with the compositor state for bug reports.
+    ///
+    /// The archive contains the node tree, the output configuration, the input devices,
+    /// the supported formats, the objects of each client, and the end of the log file.
+    /// The home directory, user name, and host name are redacted.
+    DumpState(DumpStateArgs),
     #[cfg(feature = "it")]
     RunTests,
 }
@@ -136,6 +156,23 @@ pub struct RunArgs {
     /// which they will be tried. Multiple backends can be supplied as a comma-separated list.
     #[clap(value_enum, use_value_delimiter = true, long)]
     pub backends: Vec<CliBackend>,
+    /// The session implementations to try in the metal backend.
+    ///
+    /// The session grants access to DRM and input devices. By default, jay will try the
+    /// available implementations in this order: logind,libseat. The first implementation
+    /// that can be started will be used.
+    ///
+    /// Multiple implementations can be supplied as a comma-separated list.
+    #[clap(value_enum, use_value_delimiter = true, long)]
+    pub sessions: Vec<CliSession>,
+    /// The sizes of the windows created by the x11 backend.
+    ///
+    /// By default, the x11 backend creates a single window of size 800x600. Using this
+    /// option, you can create one output per window, e.g. `1920x1080,1280x720`.
+    ///
+    /// Further windows can be created and removed at runtime via `jay randr virtual`.
+    #[clap(use_value_delimiter = true, long)]
+    pub x11_windows: Vec<CliWindowSize>,
 }
 
 #[derive(Args, Debug)]
@@ -171,6 +208,40 @@ pub struct SeatTestArgs {
 pub enum CliBackend {
     X11,
     Metal,
+    /// Renders into virtual outputs without any display hardware.
+    Headless,
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum CliSession {
+    /// Uses systemd-logind or elogind via D-Bus.
+    Logind,
+    /// Uses libseat, which supports seatd, logind, and builtin session management.
+    Libseat,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct CliWindowSize {
+    pub width: i32,
+    pub height: i32,
+}
+
+impl FromStr for CliWindowSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |v: &str| match v.parse::<i32>() {
+            Ok(v) if v > 0 => Ok(v),
+            _ => Err(format!("`{}` is not of the form WIDTHxHEIGHT", s)),
+        };
+        let Some((width, height)) = s.split_once('x') else {
+            return Err(format!("`{}` is not of the form WIDTHxHEIGHT", s));
+        };
+        Ok(Self {
+            width: parse(width)?,
+            height: parse(height)?,
+        })
+    }
 }
 
 #[derive(ValueEnum, Debug, Copy, Clone, Hash)]
@@ -235,6 +306,9 @@ pub fn main() {
         Cmd::Input(a) => input::main(cli.global, a),
         Cmd::DamageTracking(a) => damage_tracking::main(cli.global, a),
         Cmd::Xwayland(a) => xwayland::main(cli.global, a),
+        Cmd::Stats(a) => stats::main(cli.global, a),
+        Cmd::Bench(a) => bench::main(cli.global, a),
+        Cmd::DumpState(a) => dump_state::main(cli.global, a),
         #[cfg(feature = "it")]
         Cmd::RunTests => crate::it::run_tests(),
     }
@@ -0,0 +1,515 @@
+use {
+    crate::{
+        cli::{CliWindowSize, GlobalArgs},
+        format::XRGB8888,
+        ifs::zwlr_layer_shell_v1::OVERLAY,
+        object::{ObjectId, WL_DISPLAY_ID},
+        time::Time,
+        tools::tool_client::{with_tool_client, Handle, ToolClient},
+        utils::{
+            errorfmt::ErrorFmt,
+            mmap::{mmap, Mmapped},
+            numcell::NumCell,
+            oserror::OsError,
+            stack::Stack,
+        },
+        wire::{
+            jay_compositor, jay_stats, wl_buffer, wl_callback, wl_compositor, wl_display,
+            wl_registry, wl_shm, wl_shm_pool, wl_surface, zwlr_layer_shell_v1,
+            zwlr_layer_surface_v1, JayStatsId, WlBufferId, WlCallbackId, WlCompositor,
+            WlCompositorId, WlOutputId, WlRegistryId, WlShm, WlShmId, WlShmPoolId, WlSurfaceId,
+            ZwlrLayerShellV1, ZwlrLayerShellV1Id, ZwlrLayerSurfaceV1Id,
+        },
+    },
+    clap::{Args, ValueEnum},
+    std::{cell::Cell, rc::Rc},
+    uapi::c,
+};
+
+const ANCHOR_TOP: u32 = 1;
+const ANCHOR_LEFT: u32 = 4;
+const NUM_BUFFERS: usize = 3;
+const WARMUP_MS: u64 = 500;
+const BACKGROUND: u32 = 0x202020;
+const FOREGROUND: u32 = 0xe0e0e0;
+
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    /// The numbers of surfaces to create.
+    ///
+    /// Each number is benchmarked separately. Multiple numbers can be supplied as a
+    /// comma-separated list.
+    #[clap(
+        use_value_delimiter = true,
+        long,
+        default_values_t = [1, 4, 16],
+        value_parser = clap::value_parser!(u32).range(1..),
+    )]
+    pub surfaces: Vec<u32>,
+    /// The sizes of the surfaces, e.g. `256x256,1920x1080`.
+    #[clap(use_value_delimiter = true, long, default_value = "512x512")]
+    pub sizes: Vec<CliWindowSize>,
+    /// The damage patterns to benchmark.
+    #[clap(
+        value_enum,
+        use_value_delimiter = true,
+        long,
+        default_values_t = [BenchDamage::Full, BenchDamage::Partial],
+    )]
+    pub damage: Vec<BenchDamage>,
+    /// The number of seconds each scenario runs.
+    #[clap(
+        long,
+        default_value_t = 5,
+        value_parser = clap::value_parser!(u64).range(1..),
+    )]
+    pub duration: u64,
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone, PartialEq)]
+pub enum BenchDamage {
+    /// Redraw and damage the entire surface in every frame.
+    Full,
+    /// Redraw and damage a small square that moves across the surface.
+    Partial,
+}
+
+pub fn main(global: GlobalArgs, args: BenchArgs) {
+    with_tool_client(global.log_level.into(), |tc| async move {
+        let bench = Bench::new(&tc).await;
+        bench.run(args).await;
+    });
+}
+
+struct Bench {
+    tc: Rc<ToolClient>,
+    compositor: WlCompositorId,
+    shm: WlShmId,
+    layer_shell: ZwlrLayerShellV1Id,
+    stats: JayStatsId,
+    outputs: Rc<Stack<Output>>,
+    cpu_time: Rc<Cell<Option<u64>>>,
+}
+
+struct Output {
+    name: String,
+    frames: u64,
+    missed_vblanks: u64,
+    render_time_avg_ns: u64,
+    render_time_max_ns: u64,
+}
+
+struct Snapshot {
+    outputs: Vec<Output>,
+    cpu_time_ns: Option<u64>,
+}
+
+struct Surface {
+    tc: Rc<ToolClient>,
+    id: WlSurfaceId,
+    layer_surface: ZwlrLayerSurfaceV1Id,
+    pool: WlShmPoolId,
+    width: i32,
+    height: i32,
+    damage: BenchDamage,
+    mem: Mmapped,
+    buffers: Vec<Rc<Buffer>>,
+    configured: Cell<bool>,
+    running: Cell<bool>,
+    frame: NumCell<u64>,
+    frames: Rc<NumCell<u64>>,
+    last_square: Cell<Option<Square>>,
+}
+
+struct Buffer {
+    id: WlBufferId,
+    offset: usize,
+    busy: Cell<bool>,
+    square: Cell<Option<Square>>,
+}
+
+#[derive(Copy, Clone)]
+struct Square {
+    x: i32,
+    y: i32,
+    size: i32,
+}
+
+impl Bench {
+    async fn new(tc: &Rc<ToolClient>) -> Rc<Self> {
+        #[derive(Default)]
+        struct S {
+            compositor: Cell<Option<u32>>,
+            shm: Cell<Option<u32>>,
+            layer_shell: Cell<Option<u32>>,
+        }
+        let s = Rc::new(S::default());
+        let registry: WlRegistryId = tc.id();
+        tc.send(wl_display::GetRegistry {
+            self_id: WL_DISPLAY_ID,
+            registry,
+        });
+        wl_registry::Global::handle(tc, registry, s.clone(), |s, g| {
+            if g.interface == WlCompositor.name() {
+                s.compositor.set(Some(g.name));
+            } else if g.interface == WlShm.name() {
+                s.shm.set(Some(g.name));
+            } else if g.interface == ZwlrLayerShellV1.name() {
+                s.layer_shell.set(Some(g.name));
+            }
+        });
+        tc.round_trip().await;
+        macro_rules! bind {
+            ($field:ident, $if:expr, $version:expr) => {{
+                let Some(name) = s.$field.get() else {
+                    fatal!("Compositor does not provide the {} global", $if.name());
+                };
+                let id: ObjectId = tc.id();
+                tc.send(wl_registry::Bind {
+                    self_id: registry,
+                    name,
+                    interface: $if.name(),
+                    version: $version,
+                    id,
+                });
+                id.into()
+            }};
+        }
+        let compositor: WlCompositorId = bind!(compositor, WlCompositor, 4);
+        let shm: WlShmId = bind!(shm, WlShm, 1);
+        let layer_shell: ZwlrLayerShellV1Id = bind!(layer_shell, ZwlrLayerShellV1, 1);
+        wl_shm::Format::handle(tc, shm, (), |_, _| ());
+        let comp = tc.jay_compositor().await;
+        let stats = tc.id();
+        tc.send(jay_compositor::GetStats {
+            self_id: comp,
+            id: stats,
+        });
+        let slf = Rc::new(Self {
+            tc: tc.clone(),
+            compositor,
+            shm,
+            layer_shell,
+            stats,
+            outputs: Default::default(),
+            cpu_time: Default::default(),
+        });
+        jay_stats::Output::handle(tc, stats, slf.outputs.clone(), |o, msg| {
+            o.push(Output {
+                name: msg.name.to_string(),
+                frames: msg.frames,
+                missed_vblanks: msg.missed_vblanks,
+                render_time_avg_ns: msg.render_time_avg_ns,
+                render_time_max_ns: msg.render_time_max_ns,
+            });
+        });
+        jay_stats::CpuTime::handle(tc, stats, slf.cpu_time.clone(), |c, msg| {
+            c.set(Some(msg.nsec));
+        });
+        slf
+    }
+
+    async fn run(&self, args: BenchArgs) {
+        for &size in &args.sizes {
+            for &damage in &args.damage {
+                for &count in &args.surfaces {
+                    self.run_scenario(count, size, damage, args.duration).await;
+                }
+            }
+        }
+    }
+
+    async fn run_scenario(&self, count: u32, size: CliWindowSize, damage: BenchDamage, secs: u64) {
+        let frames = Rc::new(NumCell::new(0));
+        let surfaces: Vec<_> = (0..count)
+            .map(|idx| self.create_surface(idx, size, damage, &frames))
+            .collect();
+        self.tc.round_trip().await;
+        self.sleep(WARMUP_MS).await;
+        self.tc.send(jay_stats::Reset {
+            self_id: self.stats,
+        });
+        let start = self.snapshot().await;
+        let start_time = Time::now_unchecked();
+        let start_frames = frames.get();
+        self.sleep(secs * 1000).await;
+        let end = self.snapshot().await;
+        let elapsed = start_time.elapsed().as_secs_f64();
+        let client_frames = frames.get() - start_frames;
+        for surface in &surfaces {
+            surface.destroy();
+        }
+        self.tc.round_trip().await;
+        let damage = match damage {
+            BenchDamage::Full => "full",
+            BenchDamage::Partial => "partial",
+        };
+        println!(
+            "{} surface(s), {}x{}, {} damage:",
+            count, size.width, size.height, damage
+        );
+        println!(
+            "  client frames per second: {:.2} (per surface)",
+            client_frames as f64 / count as f64 / elapsed,
+        );
+        if let (Some(start), Some(end)) = (start.cpu_time_ns, end.cpu_time_ns) {
+            let cpu_ns = end.saturating_sub(start) as f64;
+            println!(
+                "  compositor cpu time: {:.3} ms ({:.1}% of one core)",
+                cpu_ns / 1_000_000.0,
+                cpu_ns / (elapsed * 10_000_000.0),
+            );
+        }
+        let mut outputs = end.outputs;
+        outputs.sort_by(|l, r| l.name.cmp(&r.name));
+        let ms = |ns: u64| ns as f64 / 1_000_000.0;
+        for o in &outputs {
+            println!(
+                "  {}: {:.2} fps, {} missed vblanks, gpu time avg {:.3} ms, max {:.3} ms",
+                o.name,
+                o.frames as f64 / elapsed,
+                o.missed_vblanks,
+                ms(o.render_time_avg_ns),
+                ms(o.render_time_max_ns),
+            );
+        }
+    }
+
+    async fn snapshot(&self) -> Snapshot {
+        self.tc.send(jay_stats::Get {
+            self_id: self.stats,
+        });
+        self.tc.round_trip().await;
+        Snapshot {
+            outputs: self.outputs.take(),
+            cpu_time_ns: self.cpu_time.take(),
+        }
+    }
+
+    async fn sleep(&self, ms: u64) {
+        if let Err(e) = self.tc.wheel.timeout(ms).await {
+            fatal!("Could not wait for the benchmark: {}", ErrorFmt(e));
+        }
+    }
+
+    fn create_surface(
+        &self,
+        idx: u32,
+        size: CliWindowSize,
+        damage: BenchDamage,
+        frames: &Rc<NumCell<u64>>,
+    ) -> Rc<Surface> {
+        let tc = &self.tc;
+        let CliWindowSize { width, height } = size;
+        let stride = width as usize * 4;
+        let buffer_size = stride * height as usize;
+        let pool_size = buffer_size * NUM_BUFFERS;
+        if pool_size > i32::MAX as usize {
+            fatal!("The surface size {}x{} is too large", width, height);
+        }
+        let fd = match uapi::memfd_create("jay-bench", c::MFD_CLOEXEC) {
+            Ok(fd) => Rc::new(fd),
+            Err(e) => fatal!("Could not create a memfd: {}", ErrorFmt(OsError::from(e))),
+        };
+        if let Err(e) = uapi::ftruncate(fd.raw(), pool_size as _) {
+            fatal!("Could not resize the memfd: {}", ErrorFmt(OsError::from(e)));
+        }
+        let mem = match mmap(
+            pool_size,
+            c::PROT_READ | c::PROT_WRITE,
+            c::MAP_SHARED,
+            fd.raw(),
+            0,
+        ) {
+            Ok(m) => m,
+            Err(e) => fatal!("Could not map the memfd: {}", ErrorFmt(e)),
+        };
+        let pool = tc.id();
+        tc.send(wl_shm::CreatePool {
+            self_id: self.shm,
+            id: pool,
+            fd,
+            size: pool_size as _,
+        });
+        let mut buffers = vec![];
+        for i in 0..NUM_BUFFERS {
+            let buffer = Rc::new(Buffer {
+                id: tc.id(),
+                offset: i * buffer_size,
+                busy: Cell::new(false),
+                square: Cell::new(None),
+            });
+            tc.send(wl_shm_pool::CreateBuffer {
+                self_id: pool,
+                id: buffer.id,
+                offset: buffer.offset as _,
+                width,
+                height,
+                stride: stride as _,
+                format: XRGB8888.wl_id.unwrap(),
+            });
+            wl_buffer::Release::handle(tc, buffer.id, buffer.clone(), |b, _| {
+                b.busy.set(false);
+            });
+            buffers.push(buffer);
+        }
+        let id = tc.id();
+        tc.send(wl_compositor::CreateSurface {
+            self_id: self.compositor,
+            id,
+        });
+        wl_surface::Enter::handle(tc, id, (), |_, _| ());
+        wl_surface::Leave::handle(tc, id, (), |_, _| ());
+        let layer_surface = tc.id();
+        tc.send(zwlr_layer_shell_v1::GetLayerSurface {
+            self_id: self.layer_shell,
+            id: layer_surface,
+            surface: id,
+            output: WlOutputId::NONE,
+            layer: OVERLAY,
+            namespace: "jay-bench",
+        });
+        tc.send(zwlr_layer_surface_v1::SetSize {
+            self_id: layer_surface,
+            width: width as _,
+            height: height as _,
+        });
+        tc.send(zwlr_layer_surface_v1::SetAnchor {
+            self_id: layer_surface,
+            anchor: ANCHOR_TOP | ANCHOR_LEFT,
+        });
+        tc.send(zwlr_layer_surface_v1::SetMargin {
+            self_id: layer_surface,
+            top: (idx / 8 % 8) as i32 * 48,
+            right: 0,
+            bottom: 0,
+            left: (idx % 8) as i32 * 48,
+        });
+        let surface = Rc::new(Surface {
+            tc: tc.clone(),
+            id,
+            layer_surface,
+            pool,
+            width,
+            height,
+            damage,
+            mem,
+            buffers,
+            configured: Cell::new(false),
+            running: Cell::new(true),
+            frame: NumCell::new(0),
+            frames: frames.clone(),
+            last_square: Cell::new(None),
+        });
+        for buffer in &surface.buffers {
+            surface.fill(buffer, 0, 0, width, height, BACKGROUND);
+        }
+        zwlr_layer_surface_v1::Configure::handle(tc, layer_surface, surface.clone(), |s, ev| {
+            s.tc.send(zwlr_layer_surface_v1::AckConfigure {
+                self_id: s.layer_surface,
+                serial: ev.serial,
+            });
+            if !s.configured.replace(true) {
+                s.draw();
+            }
+        });
+        zwlr_layer_surface_v1::Closed::handle(tc, layer_surface, surface.clone(), |s, _| {
+            s.running.set(false);
+        });
+        tc.send(wl_surface::Commit { self_id: id });
+        surface
+    }
+}
+
+impl Surface {
+    fn draw(self: &Rc<Self>) {
+        let tc = &self.tc;
+        let callback: WlCallbackId = tc.id();
+        tc.send(wl_surface::Frame {
+            self_id: self.id,
+            callback,
+        });
+        wl_callback::Done::handle(tc, callback, self.clone(), |s, _| {
+            if s.running.get() {
+                s.frames.fetch_add(1);
+                s.draw();
+            }
+        });
+        if let Some(buffer) = self.buffers.iter().find(|b| !b.busy.get()) {
+            let frame = self.frame.fetch_add(1);
+            match self.damage {
+                BenchDamage::Full => self.draw_full(buffer, frame),
+                BenchDamage::Partial => self.draw_partial(buffer, frame),
+            }
+            buffer.busy.set(true);
+            tc.send(wl_surface::Attach {
+                self_id: self.id,
+                buffer: buffer.id,
+                x: 0,
+                y: 0,
+            });
+        }
+        tc.send(wl_surface::Commit { self_id: self.id });
+    }
+
+    fn draw_full(&self, buffer: &Buffer, frame: u64) {
+        let v = (frame % 256) as u32;
+        let color = (v << 16) | ((255 - v) << 8) | 0x80;
+        self.fill(buffer, 0, 0, self.width, self.height, color);
+        self.damage(0, 0, self.width, self.height);
+    }
+
+    fn draw_partial(&self, buffer: &Buffer, frame: u64) {
+        let size = (self.width.min(self.height) / 8).max(1);
+        let square = Square {
+            x: (frame * 7 % (self.width - size + 1) as u64) as i32,
+            y: (frame * 5 % (self.height - size + 1) as u64) as i32,
+            size,
+        };
+        if let Some(old) = buffer.square.replace(Some(square)) {
+            self.fill(buffer, old.x, old.y, old.size, old.size, BACKGROUND);
+        }
+        self.fill(buffer, square.x, square.y, size, size, FOREGROUND);
+        if let Some(last) = self.last_square.replace(Some(square)) {
+            self.damage(last.x, last.y, last.size, last.size);
+        }
+        self.damage(square.x, square.y, size, size);
+    }
+
+    fn damage(&self, x: i32, y: i32, width: i32, height: i32) {
+        self.tc.send(wl_surface::DamageBuffer {
+            self_id: self.id,
+            x,
+            y,
+            width,
+            height,
+        });
+    }
+
+    fn fill(&self, buffer: &Buffer, x: i32, y: i32, width: i32, height: i32, color: u32) {
+        let stride = self.width as usize;
+        let pixels = unsafe {
+            std::slice::from_raw_parts_mut(
+                self.mem.ptr.cast::<u32>().cast_mut().add(buffer.offset / 4),
+                stride * self.height as usize,
+            )
+        };
+        for row in y as usize..(y + height) as usize {
+            let start = row * stride + x as usize;
+            pixels[start..start + width as usize].fill(color);
+        }
+    }
+
+    fn destroy(&self) {
+        let tc = &self.tc;
+        self.running.set(false);
+        tc.send(zwlr_layer_surface_v1::Destroy {
+            self_id: self.layer_surface,
+        });
+        tc.send(wl_surface::Destroy { self_id: self.id });
+        for buffer in &self.buffers {
+            tc.send(wl_buffer::Destroy { self_id: buffer.id });
+        }
+        tc.send(wl_shm_pool::Destroy { self_id: self.pool });
+    }
+}
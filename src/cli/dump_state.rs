@@ -0,0 +1,214 @@
+use {
+    crate::{
+        cli::GlobalArgs,
+        tools::tool_client::{with_tool_client, Handle, ToolClient},
+        utils::{errorfmt::ErrorFmt, oserror::OsError},
+        wire::{jay_compositor, jay_log_file, jay_state_dump},
+    },
+    bstr::{BString, ByteSlice},
+    chrono::Local,
+    clap::{Args, ValueHint},
+    std::{
+        cell::{Cell, RefCell},
+        fs::File,
+        io::{Read, Seek, SeekFrom},
+        rc::Rc,
+        time::{SystemTime, UNIX_EPOCH},
+    },
+    uapi::OwnedFd,
+};
+
+/// The maximum number of bytes read from the end of the log file.
+const LOG_TAIL: u64 = 1024 * 1024;
+
+#[derive(Args, Debug)]
+pub struct DumpStateArgs {
+    /// Do not replace the home directory, user name, and host name in the archive.
+    #[clap(long)]
+    pub no_redact: bool,
+    /// The filename of the archive
+    ///
+    /// If no filename is given, the archive will be saved under
+    /// %Y-%m-%d-%H%M%S_jay-state.tar in the current directory.
+    ///
+    /// The filename can contain the usual strftime parameters.
+    #[clap(value_hint = ValueHint::FilePath)]
+    pub filename: Option<String>,
+}
+
+pub fn main(global: GlobalArgs, args: DumpStateArgs) {
+    with_tool_client(global.log_level.into(), |tc| async move {
+        let dump = Rc::new(DumpState {
+            tc: tc.clone(),
+            files: Default::default(),
+            log_path: Default::default(),
+            done: Cell::new(false),
+        });
+        run(dump, args).await;
+    });
+}
+
+struct DumpState {
+    tc: Rc<ToolClient>,
+    files: RefCell<Vec<(String, Rc<OwnedFd>)>>,
+    log_path: RefCell<Option<BString>>,
+    done: Cell<bool>,
+}
+
+async fn run(ds: Rc<DumpState>, args: DumpStateArgs) {
+    let tc = &ds.tc;
+    let comp = tc.jay_compositor().await;
+    let log_file = tc.id();
+    tc.send(jay_compositor::GetLogFile {
+        self_id: comp,
+        id: log_file,
+    });
+    jay_log_file::Path::handle(tc, log_file, ds.clone(), |ds, path| {
+        *ds.log_path.borrow_mut() = Some(path.path.to_vec().into());
+    });
+    let dump = tc.id();
+    tc.send(jay_compositor::DumpState {
+        self_id: comp,
+        id: dump,
+    });
+    jay_state_dump::File::handle(tc, dump, ds.clone(), |ds, file| {
+        ds.files
+            .borrow_mut()
+            .push((file.name.to_string(), file.contents));
+    });
+    jay_state_dump::Done::handle(tc, dump, ds.clone(), |ds, _| {
+        ds.done.set(true);
+    });
+    tc.round_trip().await;
+    tc.send(jay_state_dump::Destroy { self_id: dump });
+    if !ds.done.get() {
+        fatal!("The compositor did not send a state dump");
+    }
+    let mut entries = vec![];
+    for (name, fd) in ds.files.take() {
+        match read_all(&fd) {
+            Ok(c) => entries.push((name, c)),
+            Err(e) => fatal!("Could not read `{}`: {}", name, ErrorFmt(e)),
+        }
+    }
+    if let Some(path) = ds.log_path.take() {
+        match read_log_tail(&path) {
+            Ok(c) => entries.push(("log.txt".to_string(), c)),
+            Err(e) => log::warn!("Could not read the log file `{}`: {}", path, ErrorFmt(e)),
+        }
+    }
+    if !args.no_redact {
+        let redactions = redactions();
+        for (_, contents) in &mut entries {
+            for (needle, replacement) in &redactions {
+                *contents = contents.replace(needle, replacement);
+            }
+        }
+    }
+    let filename = match &args.filename {
+        Some(f) => f.clone(),
+        _ => "%Y-%m-%d-%H%M%S_jay-state.tar".to_string(),
+    };
+    let filename = Local::now().format(&filename).to_string();
+    let archive = create_tar(&entries);
+    if let Err(e) = std::fs::write(&filename, archive) {
+        fatal!("Could not write `{}`: {}", filename, ErrorFmt(e));
+    }
+    println!("{}", filename);
+}
+
+fn read_all(fd: &OwnedFd) -> Result<Vec<u8>, OsError> {
+    let mut res = vec![];
+    let mut buf = [0u8; 4096];
+    loop {
+        let n = uapi::read(fd.raw(), &mut buf[..])?.len();
+        if n == 0 {
+            return Ok(res);
+        }
+        res.extend_from_slice(&buf[..n]);
+    }
+}
+
+fn read_log_tail(path: &BString) -> Result<Vec<u8>, std::io::Error> {
+    let mut file = File::open(path.to_os_str_lossy())?;
+    let len = file.metadata()?.len();
+    let mut res = vec![];
+    if len > LOG_TAIL {
+        file.seek(SeekFrom::Start(len - LOG_TAIL))?;
+        file.read_to_end(&mut res)?;
+        if let Some(pos) = res.find_byte(b'\n') {
+            res.drain(..=pos);
+        }
+    } else {
+        file.read_to_end(&mut res)?;
+    }
+    Ok(res)
+}
+
+/// The minimum length of a user or host name that gets redacted.
+///
+/// Shorter names would replace unrelated parts of the dump.
+const MIN_REDACTED_NAME_LEN: usize = 3;
+
+/// Returns the strings that identify the user, longest first.
+fn redactions() -> Vec<(Vec<u8>, &'static str)> {
+    let mut res = vec![];
+    if let Ok(home) = std::env::var("HOME") {
+        let home = home.trim_end_matches('/');
+        if home.len() > 1 {
+            res.push((home.as_bytes().to_vec(), "~"));
+        }
+    }
+    if let Ok(host) = std::fs::read_to_string("/proc/sys/kernel/hostname") {
+        let host = host.trim();
+        if host.len() >= MIN_REDACTED_NAME_LEN {
+            res.push((host.as_bytes().to_vec(), "<hostname>"));
+        }
+    }
+    if let Ok(user) = std::env::var("USER") {
+        if user.len() >= MIN_REDACTED_NAME_LEN {
+            res.push((user.into_bytes(), "<user>"));
+        }
+    }
+    res.sort_by_key(|e| std::cmp::Reverse(e.0.len()));
+    res
+}
+
+/// Creates an uncompressed ustar archive with all entries in the `jay-state` directory.
+fn create_tar(entries: &[(String, Vec<u8>)]) -> Vec<u8> {
+    let mtime = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let mut res = vec![];
+    for (name, contents) in entries {
+        let mut header = [0u8; 512];
+        let name = format!("jay-state/{}", name);
+        let name = &name.as_bytes()[..name.len().min(99)];
+        header[..name.len()].copy_from_slice(name);
+        write_octal(&mut header[100..108], 0o644);
+        write_octal(&mut header[108..116], 0);
+        write_octal(&mut header[116..124], 0);
+        write_octal(&mut header[124..136], contents.len() as u64);
+        write_octal(&mut header[136..148], mtime);
+        header[148..156].fill(b' ');
+        header[156] = b'0';
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[263..265].copy_from_slice(b"00");
+        let checksum: u64 = header.iter().map(|&b| b as u64).sum();
+        write_octal(&mut header[148..155], checksum);
+        res.extend_from_slice(&header);
+        res.extend_from_slice(contents);
+        res.resize(res.len().next_multiple_of(512), 0);
+    }
+    res.resize(res.len() + 1024, 0);
+    res
+}
+
+/// Writes a zero-padded, NUL-terminated octal number into the field.
+fn write_octal(field: &mut [u8], value: u64) {
+    let digits = field.len() - 1;
+    let s = format!("{:0digits$o}", value);
+    field[..digits].copy_from_slice(&s.as_bytes()[s.len() - digits..]);
+    field[digits] = 0;
+}
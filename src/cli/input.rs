@@ -81,6 +81,10 @@ pub enum SeatCommand {
     UseHardwareCursor(UseHardwareCursorArgs),
     /// Set the size of the cursor.
     SetCursorSize(SetCursorSizeArgs),
+    /// Create the seat if it does not already exist.
+    Create,
+    /// Restrict the cursor of this seat to outputs.
+    SetOutputs(SetOutputsArgs),
 }
 
 impl Default for SeatCommand {
@@ -133,6 +137,18 @@ pub enum DeviceCommand {
     RemoveMapping,
     /// Set the calibration matrix.
     SetCalibrationMatrix(SetCalibrationMatrixArgs),
+    /// Set the button debounce timeout.
+    SetButtonDebounce(SetButtonDebounceArgs),
+    /// Set whether input from a gamepad resets the idle timer.
+    SetGamepadResetsIdle(SetGamepadResetsIdleArgs),
+    /// Set whether the touchpad is disabled while typing.
+    SetDisableWhileTyping(SetDisableWhileTypingArgs),
+    /// Set whether the touchpad is disabled while the trackpoint is in use.
+    SetDisableWhileTrackpointing(SetDisableWhileTrackpointingArgs),
+    /// Set the time after a key press during which the device is ignored.
+    SetDisableWhileTypingWindow(SetDisableWhileTypingWindowArgs),
+    /// Set whether pressing the left and right buttons at once produces a middle click.
+    SetMiddleButtonEmulation(SetMiddleButtonEmulationArgs),
 }
 
 #[derive(ValueEnum, Debug, Clone)]
@@ -212,6 +228,48 @@ pub struct SetCalibrationMatrixArgs {
     pub m12: f32,
 }
 
+#[derive(Args, Debug, Clone)]
+pub struct SetButtonDebounceArgs {
+    /// Button presses that follow a release of the same button within this many
+    /// milliseconds are ignored together with the release. `0` disables debouncing.
+    pub timeout_ms: u32,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetGamepadResetsIdleArgs {
+    /// Whether input from the gamepad resets the idle timer.
+    #[arg(action = clap::ArgAction::Set)]
+    pub enabled: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetDisableWhileTypingArgs {
+    /// Whether the touchpad is disabled while typing.
+    #[arg(action = clap::ArgAction::Set)]
+    pub enabled: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetDisableWhileTrackpointingArgs {
+    /// Whether the touchpad is disabled while the trackpoint is in use.
+    #[arg(action = clap::ArgAction::Set)]
+    pub enabled: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetDisableWhileTypingWindowArgs {
+    /// Motion, button presses, and scrolling are ignored for this many milliseconds
+    /// after a key was pressed. `0` disables this.
+    pub window_ms: u32,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetMiddleButtonEmulationArgs {
+    /// Whether middle button emulation is enabled.
+    #[arg(action = clap::ArgAction::Set)]
+    pub enabled: bool,
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct MapToOutputArgs {
     /// The output to map to.
@@ -238,6 +296,12 @@ pub struct SetCursorSizeArgs {
     pub size: u32,
 }
 
+#[derive(Args, Debug, Clone)]
+pub struct SetOutputsArgs {
+    /// The outputs to restrict the cursor to. Omit to remove the restriction.
+    pub outputs: Vec<String>,
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct SetKeymapArgs {
     /// The file to read the keymap from. Omit for stdin.
@@ -265,6 +329,7 @@ struct Seat {
     pub repeat_rate: i32,
     pub repeat_delay: i32,
     pub hardware_cursor: bool,
+    pub outputs: Vec<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -286,6 +351,12 @@ struct InputDevice {
     pub transform_matrix: Option<[[f64; 2]; 2]>,
     pub output: Option<String>,
     pub calibration_matrix: Option<[[f32; 3]; 2]>,
+    pub button_debounce: Option<(u32, u64)>,
+    pub gamepad_resets_idle: Option<bool>,
+    pub disable_while_typing: Option<bool>,
+    pub disable_while_trackpointing: Option<bool>,
+    pub disable_while_typing_window: Option<u32>,
+    pub middle_button_emulation: Option<bool>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -432,6 +503,31 @@ impl Input {
                     size: a.size,
                 });
             }
+            SeatCommand::Create => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not create the seat: {}", e);
+                });
+                tc.send(jay_input::CreateSeat {
+                    self_id: input,
+                    name: &args.seat,
+                });
+            }
+            SeatCommand::SetOutputs(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set the outputs: {}", e);
+                });
+                tc.send(jay_input::ClearSeatOutputs {
+                    self_id: input,
+                    seat: &args.seat,
+                });
+                for output in &a.outputs {
+                    tc.send(jay_input::AddSeatOutput {
+                        self_id: input,
+                        seat: &args.seat,
+                        output,
+                    });
+                }
+            }
         }
         tc.round_trip().await;
     }
@@ -626,6 +722,72 @@ impl Input {
                     m12: a.m12,
                 });
             }
+            DeviceCommand::SetButtonDebounce(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not modify the button debounce timeout: {}", e);
+                });
+                tc.send(jay_input::SetButtonDebounce {
+                    self_id: input,
+                    id: args.device,
+                    timeout_ms: a.timeout_ms,
+                });
+            }
+            DeviceCommand::SetGamepadResetsIdle(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not modify the gamepad-resets-idle setting: {}", e);
+                });
+                tc.send(jay_input::SetGamepadResetsIdle {
+                    self_id: input,
+                    id: args.device,
+                    enabled: a.enabled as _,
+                });
+            }
+            DeviceCommand::SetDisableWhileTyping(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not modify the disable-while-typing setting: {}", e);
+                });
+                tc.send(jay_input::SetDisableWhileTypingEnabled {
+                    self_id: input,
+                    id: args.device,
+                    enabled: a.enabled as _,
+                });
+            }
+            DeviceCommand::SetDisableWhileTrackpointing(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!(
+                        "Could not modify the disable-while-trackpointing setting: {}",
+                        e
+                    );
+                });
+                tc.send(jay_input::SetDisableWhileTrackpointingEnabled {
+                    self_id: input,
+                    id: args.device,
+                    enabled: a.enabled as _,
+                });
+            }
+            DeviceCommand::SetDisableWhileTypingWindow(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not modify the disable-while-typing window: {}", e);
+                });
+                tc.send(jay_input::SetDisableWhileTypingWindow {
+                    self_id: input,
+                    id: args.device,
+                    window_ms: a.window_ms,
+                });
+            }
+            DeviceCommand::SetMiddleButtonEmulation(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!(
+                        "Could not modify the middle button emulation setting: {}",
+                        e
+                    );
+                });
+                tc.send(jay_input::SetMiddleButtonEmulationEnabled {
+                    self_id: input,
+                    id: args.device,
+                    enabled: a.enabled as _,
+                });
+            }
         }
         tc.round_trip().await;
     }
@@ -687,6 +849,9 @@ impl Input {
         if !seat.hardware_cursor {
             println!("  hardware cursor disabled");
         }
+        if seat.outputs.is_not_empty() {
+            println!("  outputs: {}", seat.outputs.join(", "));
+        }
     }
 
     fn print_device(&self, prefix: &str, print_seat: bool, device: &InputDevice) {
@@ -762,6 +927,30 @@ impl Input {
         if let Some(v) = &device.calibration_matrix {
             println!("{prefix}  calibration matrix: {:?}", v);
         }
+        if let Some((timeout, suppressed)) = device.button_debounce {
+            if timeout > 0 {
+                println!(
+                    "{prefix}  button debounce: {timeout} ms ({suppressed} bounces suppressed)"
+                );
+            }
+        }
+        if let Some(v) = &device.gamepad_resets_idle {
+            println!("{prefix}  gamepad resets idle: {}", v);
+        }
+        if let Some(v) = &device.disable_while_typing {
+            println!("{prefix}  disable while typing: {}", v);
+        }
+        if let Some(v) = &device.disable_while_trackpointing {
+            println!("{prefix}  disable while trackpointing: {}", v);
+        }
+        if let Some(window) = device.disable_while_typing_window {
+            if window > 0 {
+                println!("{prefix}  disable while typing window: {window} ms");
+            }
+        }
+        if let Some(v) = &device.middle_button_emulation {
+            println!("{prefix}  middle button emulation: {}", v);
+        }
     }
 
     async fn get(self: &Rc<Self>, input: JayInputId) -> Data {
@@ -773,8 +962,15 @@ impl Input {
                 repeat_rate: msg.repeat_rate,
                 repeat_delay: msg.repeat_delay,
                 hardware_cursor: msg.hardware_cursor != 0,
+                outputs: vec![],
             });
         });
+        jay_input::SeatOutput::handle(tc, input, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.seats.last_mut() {
+                last.outputs.push(msg.output.to_string());
+            }
+        });
         jay_input::InputDevice::handle(tc, input, data.clone(), |data, msg| {
             use crate::{backend::InputDeviceCapability::*, libinput::consts::*};
             let mut capabilities = vec![];
@@ -827,6 +1023,12 @@ impl Input {
                 transform_matrix: uapi::pod_read(msg.transform_matrix).ok(),
                 output: None,
                 calibration_matrix: None,
+                button_debounce: None,
+                gamepad_resets_idle: None,
+                disable_while_typing: None,
+                disable_while_trackpointing: None,
+                disable_while_typing_window: None,
+                middle_button_emulation: None,
             });
         });
         jay_input::InputDeviceOutput::handle(tc, input, data.clone(), |data, msg| {
@@ -842,6 +1044,42 @@ impl Input {
                     Some([[msg.m00, msg.m01, msg.m02], [msg.m10, msg.m11, msg.m12]]);
             }
         });
+        jay_input::ButtonDebounce::handle(tc, input, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.input_device.last_mut() {
+                last.button_debounce = Some((msg.timeout_ms, msg.suppressed));
+            }
+        });
+        jay_input::GamepadResetsIdle::handle(tc, input, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.input_device.last_mut() {
+                last.gamepad_resets_idle = Some(msg.enabled != 0);
+            }
+        });
+        jay_input::DisableWhileTyping::handle(tc, input, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.input_device.last_mut() {
+                last.disable_while_typing = Some(msg.enabled != 0);
+            }
+        });
+        jay_input::DisableWhileTrackpointing::handle(tc, input, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.input_device.last_mut() {
+                last.disable_while_trackpointing = Some(msg.enabled != 0);
+            }
+        });
+        jay_input::DisableWhileTypingWindow::handle(tc, input, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.input_device.last_mut() {
+                last.disable_while_typing_window = Some(msg.window_ms);
+            }
+        });
+        jay_input::MiddleButtonEmulation::handle(tc, input, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.input_device.last_mut() {
+                last.middle_button_emulation = Some(msg.enabled != 0);
+            }
+        });
         tc.round_trip().await;
         let x = data.borrow_mut().clone();
         x
@@ -9,7 +9,7 @@ use {
     },
     clap::{Args, Subcommand, ValueEnum},
     isnt::std_1::vec::IsntVecExt,
-    jay_config::video::{TearingMode, Transform, VrrMode},
+    jay_config::video::{CustomMode, TearingMode, Transform, VrrMode},
     std::{
         cell::RefCell,
         fmt::{Display, Formatter},
@@ -33,6 +33,12 @@ pub enum RandrCmd {
     Card(CardArgs),
     /// Modify the settings of an output.
     Output(OutputArgs),
+    /// Turn all outputs on or off.
+    Power(PowerArgs),
+    /// Turn off all outputs except one.
+    Present(PresentArgs),
+    /// Create or remove virtual outputs of the headless or x11 backend.
+    Virtual(VirtualArgs),
 }
 
 impl Default for RandrCmd {
@@ -49,6 +55,62 @@ pub struct ShowArgs {
     /// Show all available formats.
     #[arg(long)]
     pub formats: bool,
+    /// Show the properties of connectors.
+    #[arg(long)]
+    pub properties: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct PowerArgs {
+    #[clap(subcommand)]
+    pub command: PowerCmd,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PowerCmd {
+    /// Turn all outputs on.
+    On,
+    /// Turn all outputs off.
+    Off,
+    /// Turn all outputs off if any output is on. Otherwise turn all outputs on.
+    Toggle,
+}
+
+#[derive(Args, Debug)]
+pub struct PresentArgs {
+    /// The output to keep on, e.g. DP-1. If omitted, all outputs are turned on again.
+    pub output: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct VirtualArgs {
+    #[clap(subcommand)]
+    pub command: VirtualCmd,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum VirtualCmd {
+    /// Create a virtual output.
+    Create(CreateVirtualArgs),
+    /// Remove a virtual output.
+    Remove(RemoveVirtualArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct CreateVirtualArgs {
+    /// The width.
+    pub width: i32,
+    /// The height.
+    pub height: i32,
+    /// The refresh rate.
+    #[clap(default_value_t = 60.0)]
+    pub refresh_rate: f64,
+}
+
+#[derive(Args, Debug)]
+pub struct RemoveVirtualArgs {
+    /// The virtual output to remove, e.g. Virtual-2.
+    pub output: String,
 }
 
 #[derive(Args, Debug)]
@@ -140,6 +202,8 @@ pub enum OutputCommand {
     Scale(ScaleArgs),
     /// Modify the mode of the output.
     Mode(ModeArgs),
+    /// Use a mode that is not advertised by the output.
+    CustomMode(CustomModeArgs),
     /// Modify the position of the output.
     Position(PositionArgs),
     /// Enable the output.
@@ -154,6 +218,12 @@ pub enum OutputCommand {
     Tearing(TearingArgs),
     /// Change format settings.
     Format(FormatSettings),
+    /// Change HDR settings.
+    Hdr(HdrArgs),
+    /// Show the contents of another output on this output.
+    Mirror(MirrorArgs),
+    /// Stop mirroring another output.
+    Unmirror,
 }
 
 #[derive(ValueEnum, Debug, Clone)]
@@ -224,6 +294,26 @@ pub enum FormatCommand {
     },
 }
 
+#[derive(Args, Debug, Clone)]
+pub struct MirrorArgs {
+    /// The output to mirror, e.g. DP-1.
+    pub source: String,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct HdrArgs {
+    #[clap(subcommand)]
+    pub command: HdrCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum HdrCommand {
+    /// Enables HDR10 if the display supports it.
+    Enable,
+    /// Disables HDR10.
+    Disable,
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct TearingArgs {
     #[clap(subcommand)]
@@ -277,6 +367,45 @@ pub struct ModeArgs {
     pub refresh_rate: f64,
 }
 
+#[derive(Args, Debug, Clone)]
+pub struct CustomModeArgs {
+    #[clap(subcommand)]
+    pub command: CustomModeCommand,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum CustomModeCommand {
+    /// Compute the timings with the VESA CVT formula.
+    Cvt {
+        /// The width.
+        width: u16,
+        /// The height.
+        height: u16,
+        /// The refresh rate.
+        refresh_rate: f64,
+        /// Use reduced blanking.
+        #[clap(long, short)]
+        reduced_blanking: bool,
+    },
+    /// Compute the timings with the VESA GTF formula.
+    Gtf {
+        /// The width.
+        width: u16,
+        /// The height.
+        height: u16,
+        /// The refresh rate.
+        refresh_rate: f64,
+    },
+    /// Use the timings of an X11 modeline.
+    ///
+    /// E.g. `173.00 1920 2048 2248 2576 1080 1083 1088 1120 -hsync +vsync`.
+    Modeline {
+        /// The modeline.
+        #[clap(required = true, allow_hyphen_values = true, num_args = 1..)]
+        modeline: Vec<String>,
+    },
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct ScaleArgs {
     /// The new scale.
@@ -368,6 +497,32 @@ struct Output {
     pub formats: Vec<String>,
     pub format: Option<String>,
     pub flip_margin_ns: Option<u64>,
+    pub requested_mode: Option<Mode>,
+    pub hdr_capable: bool,
+    pub hdr_enabled: bool,
+    pub mirror_source: Option<String>,
+    pub edid: Option<Edid>,
+    pub properties: Vec<(String, String)>,
+}
+
+#[derive(Clone, Debug)]
+struct Edid {
+    pub product_code: u32,
+    pub serial_number: u32,
+    pub week_of_manufacture: u32,
+    pub year_of_manufacture: u32,
+    pub version: u32,
+    pub revision: u32,
+    pub range_limits: Option<EdidRangeLimits>,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct EdidRangeLimits {
+    pub min_vertical_hz: u32,
+    pub max_vertical_hz: u32,
+    pub min_horizontal_khz: u32,
+    pub max_horizontal_khz: u32,
+    pub max_pixel_clock_mhz: u32,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -420,7 +575,69 @@ impl Randr {
             RandrCmd::Show(args) => self.show(randr, args).await,
             RandrCmd::Card(args) => self.card(randr, args).await,
             RandrCmd::Output(args) => self.output(randr, args).await,
+            RandrCmd::Power(args) => self.power(randr, args).await,
+            RandrCmd::Present(args) => self.present(randr, args).await,
+            RandrCmd::Virtual(args) => self.virtual_output(randr, args).await,
+        }
+    }
+
+    async fn power(self: &Rc<Self>, randr: JayRandrId, args: PowerArgs) {
+        let tc = &self.tc;
+        match args.command {
+            PowerCmd::On | PowerCmd::Off => {
+                let powered = matches!(args.command, PowerCmd::On);
+                tc.send(jay_randr::SetOutputsPowered {
+                    self_id: randr,
+                    powered: powered as _,
+                });
+            }
+            PowerCmd::Toggle => {
+                tc.send(jay_randr::ToggleOutputsPowered { self_id: randr });
+            }
+        }
+        tc.round_trip().await;
+    }
+
+    async fn present(self: &Rc<Self>, randr: JayRandrId, args: PresentArgs) {
+        let tc = &self.tc;
+        self.handle_error(randr, |msg| {
+            eprintln!("Could not enter presentation mode: {}", msg);
+        });
+        tc.send(jay_randr::SetPresentationOutput {
+            self_id: randr,
+            output: args.output.as_deref().unwrap_or_default(),
+        });
+        tc.round_trip().await;
+    }
+
+    async fn virtual_output(self: &Rc<Self>, randr: JayRandrId, args: VirtualArgs) {
+        let tc = &self.tc;
+        match args.command {
+            VirtualCmd::Create(args) => {
+                self.handle_error(randr, |msg| {
+                    eprintln!("Could not create a virtual output: {}", msg);
+                });
+                jay_randr::VirtualOutputCreated::handle(tc, randr, (), |_, ev| {
+                    println!("{}", ev.name);
+                });
+                tc.send(jay_randr::CreateVirtualOutput {
+                    self_id: randr,
+                    width: args.width,
+                    height: args.height,
+                    refresh_rate_millihz: (args.refresh_rate * 1000.0).round() as u32,
+                });
+            }
+            VirtualCmd::Remove(args) => {
+                self.handle_error(randr, |msg| {
+                    eprintln!("Could not remove the virtual output: {}", msg);
+                });
+                tc.send(jay_randr::RemoveVirtualOutput {
+                    self_id: randr,
+                    output: &args.output,
+                });
+            }
         }
+        tc.round_trip().await;
     }
 
     fn handle_error<F: Fn(&str) + 'static>(&self, randr: JayRandrId, f: F) {
@@ -499,6 +716,49 @@ impl Randr {
                     refresh_rate_millihz: mode.refresh_rate_millihz,
                 });
             }
+            OutputCommand::CustomMode(t) => {
+                let refresh = |hz: f64| (hz * 1000.0).round() as u32;
+                let mode = match t.command {
+                    CustomModeCommand::Cvt {
+                        width,
+                        height,
+                        refresh_rate,
+                        reduced_blanking,
+                    } => CustomMode::cvt(width, height, refresh(refresh_rate), reduced_blanking),
+                    CustomModeCommand::Gtf {
+                        width,
+                        height,
+                        refresh_rate,
+                    } => CustomMode::gtf(width, height, refresh(refresh_rate)),
+                    CustomModeCommand::Modeline { modeline } => {
+                        match CustomMode::from_modeline(&modeline.join(" ")) {
+                            Some(m) => m,
+                            None => {
+                                log::error!("Could not parse the modeline");
+                                return;
+                            }
+                        }
+                    }
+                };
+                self.handle_error(randr, |msg| {
+                    eprintln!("Could not set the custom mode: {}", msg);
+                });
+                tc.send(jay_randr::SetCustomMode {
+                    self_id: randr,
+                    output: &args.output,
+                    clock_khz: mode.clock_khz,
+                    hdisplay: mode.hdisplay as _,
+                    hsync_start: mode.hsync_start as _,
+                    hsync_end: mode.hsync_end as _,
+                    htotal: mode.htotal as _,
+                    vdisplay: mode.vdisplay as _,
+                    vsync_start: mode.vsync_start as _,
+                    vsync_end: mode.vsync_end as _,
+                    vtotal: mode.vtotal as _,
+                    hsync_positive: mode.hsync_positive as _,
+                    vsync_positive: mode.vsync_positive as _,
+                });
+            }
             OutputCommand::Position(t) => {
                 self.handle_error(randr, |msg| {
                     eprintln!("Could not modify the position: {}", msg);
@@ -610,6 +870,40 @@ impl Randr {
                     }
                 }
             }
+            OutputCommand::Hdr(a) => {
+                self.handle_error(randr, move |msg| {
+                    eprintln!("Could not change the HDR setting: {}", msg);
+                });
+                let enabled = match a.command {
+                    HdrCommand::Enable => true,
+                    HdrCommand::Disable => false,
+                };
+                tc.send(jay_randr::SetHdrEnabled {
+                    self_id: randr,
+                    output: &args.output,
+                    enabled: enabled as _,
+                });
+            }
+            OutputCommand::Mirror(a) => {
+                self.handle_error(randr, move |msg| {
+                    eprintln!("Could not mirror the output: {}", msg);
+                });
+                tc.send(jay_randr::SetMirror {
+                    self_id: randr,
+                    output: &args.output,
+                    source: Some(&a.source),
+                });
+            }
+            OutputCommand::Unmirror => {
+                self.handle_error(randr, move |msg| {
+                    eprintln!("Could not stop mirroring: {}", msg);
+                });
+                tc.send(jay_randr::SetMirror {
+                    self_id: randr,
+                    output: &args.output,
+                    source: None,
+                });
+            }
         }
         tc.round_trip().await;
     }
@@ -685,7 +979,7 @@ impl Randr {
                 .collect();
             connectors.sort_by_key(|c| &c.name);
             for c in connectors {
-                self.print_connector(c, args.modes, args.formats);
+                self.print_connector(c, &args);
             }
         }
         {
@@ -698,7 +992,7 @@ impl Randr {
                 connectors.sort_by_key(|c| &c.name);
                 println!("unbound connectors:");
                 for c in connectors {
-                    self.print_connector(c, args.modes, args.formats);
+                    self.print_connector(c, &args);
                 }
             }
         }
@@ -715,7 +1009,7 @@ impl Randr {
         }
     }
 
-    fn print_connector(&self, connector: &Connector, modes: bool, formats: bool) {
+    fn print_connector(&self, connector: &Connector, args: &ShowArgs) {
         println!("      {}:", connector.name);
         let Some(o) = &connector.output else {
             if !connector.enabled {
@@ -732,10 +1026,23 @@ impl Randr {
             "        physical size: {}mm x {}mm",
             o.width_mm, o.height_mm
         );
+        if let Some(edid) = &o.edid {
+            self.print_edid(edid);
+        }
+        if o.properties.is_not_empty() && args.properties {
+            println!("        properties:");
+            for (name, value) in &o.properties {
+                println!("          {name}: {value}");
+            }
+        }
         if o.non_desktop {
             println!("        non-desktop");
             return;
         }
+        if let Some(source) = &o.mirror_source {
+            println!("        mirror of: {}", source);
+            return;
+        }
         println!("        VRR capable: {}", o.vrr_capable);
         if o.vrr_capable {
             println!("        VRR enabled: {}", o.vrr_enabled);
@@ -771,12 +1078,19 @@ impl Randr {
             };
             println!("        Tearing mode: {}", mode);
         }
+        println!("        HDR capable: {}", o.hdr_capable);
+        if o.hdr_capable {
+            println!("        HDR enabled: {}", o.hdr_enabled);
+        }
         println!("        position: {} x {}", o.x, o.y);
         println!("        logical size: {} x {}", o.width, o.height);
         if let Some(mode) = &o.current_mode {
             print!("        mode: ");
             self.print_mode(mode, false);
         }
+        if let Some(mode) = &o.requested_mode {
+            println!("        requested mode: {mode} (fallback active)");
+        }
         if let Some(format) = &o.format {
             if format != XRGB8888.name {
                 println!("        format: {format}");
@@ -806,14 +1120,14 @@ impl Randr {
                 );
             }
         }
-        if o.modes.is_not_empty() && modes {
+        if o.modes.is_not_empty() && args.modes {
             println!("        modes:");
             for mode in &o.modes {
                 print!("          ");
                 self.print_mode(mode, true);
             }
         }
-        if o.formats.is_not_empty() && formats {
+        if o.formats.is_not_empty() && args.formats {
             println!("        formats:");
             for format in &o.formats {
                 println!("          {format}");
@@ -821,6 +1135,37 @@ impl Randr {
         }
     }
 
+    fn print_edid(&self, edid: &Edid) {
+        println!("        product code: {:04x}", edid.product_code);
+        if edid.serial_number != 0 {
+            println!("        EDID serial number: {}", edid.serial_number);
+        }
+        if edid.year_of_manufacture != 0 {
+            if edid.week_of_manufacture != 0 {
+                println!(
+                    "        manufactured: week {} of {}",
+                    edid.week_of_manufacture, edid.year_of_manufacture
+                );
+            } else {
+                println!("        manufactured: {}", edid.year_of_manufacture);
+            }
+        }
+        println!("        EDID version: {}.{}", edid.version, edid.revision);
+        if let Some(l) = &edid.range_limits {
+            println!(
+                "        vertical rate: {} - {} Hz",
+                l.min_vertical_hz, l.max_vertical_hz
+            );
+            println!(
+                "        horizontal rate: {} - {} kHz",
+                l.min_horizontal_khz, l.max_horizontal_khz
+            );
+            if l.max_pixel_clock_mhz != 0 {
+                println!("        max pixel clock: {} MHz", l.max_pixel_clock_mhz);
+            }
+        }
+    }
+
     fn print_mode(&self, m: &Mode, print_current: bool) {
         print!("{}", m);
         if print_current && m.current {
@@ -886,6 +1231,12 @@ impl Randr {
                 formats: vec![],
                 format: None,
                 flip_margin_ns: None,
+                requested_mode: None,
+                hdr_capable: false,
+                hdr_enabled: false,
+                mirror_source: None,
+                edid: None,
+                properties: vec![],
             });
         });
         jay_randr::NonDesktopOutput::handle(tc, randr, data.clone(), |data, msg| {
@@ -914,8 +1265,84 @@ impl Randr {
                 formats: vec![],
                 format: None,
                 flip_margin_ns: None,
+                requested_mode: None,
+                hdr_capable: false,
+                hdr_enabled: false,
+                mirror_source: None,
+                edid: None,
+                properties: vec![],
             });
         });
+        jay_randr::MirrorOutput::handle(tc, randr, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            let c = data.connectors.last_mut().unwrap();
+            c.output = Some(Output {
+                scale: 1.0,
+                width: 0,
+                height: 0,
+                x: 0,
+                y: 0,
+                transform: Transform::None,
+                manufacturer: msg.manufacturer.to_string(),
+                product: msg.product.to_string(),
+                serial_number: msg.serial_number.to_string(),
+                width_mm: msg.width_mm,
+                height_mm: msg.height_mm,
+                modes: Default::default(),
+                current_mode: None,
+                non_desktop: false,
+                vrr_capable: false,
+                vrr_enabled: false,
+                vrr_mode: VrrMode::NEVER,
+                vrr_cursor_hz: None,
+                tearing_mode: TearingMode::NEVER,
+                formats: vec![],
+                format: None,
+                flip_margin_ns: None,
+                requested_mode: None,
+                hdr_capable: false,
+                hdr_enabled: false,
+                mirror_source: Some(msg.source.to_string()),
+                edid: None,
+                properties: vec![],
+            });
+        });
+        jay_randr::Edid::handle(tc, randr, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            let c = data.connectors.last_mut().unwrap();
+            let output = c.output.as_mut().unwrap();
+            output.edid = Some(Edid {
+                product_code: msg.product_code,
+                serial_number: msg.serial_number,
+                week_of_manufacture: msg.week_of_manufacture,
+                year_of_manufacture: msg.year_of_manufacture,
+                version: msg.version,
+                revision: msg.revision,
+                range_limits: None,
+            });
+        });
+        jay_randr::EdidRangeLimits::handle(tc, randr, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            let c = data.connectors.last_mut().unwrap();
+            let output = c.output.as_mut().unwrap();
+            if let Some(edid) = &mut output.edid {
+                edid.range_limits = Some(EdidRangeLimits {
+                    min_vertical_hz: msg.min_vertical_hz,
+                    max_vertical_hz: msg.max_vertical_hz,
+                    min_horizontal_khz: msg.min_horizontal_khz,
+                    max_horizontal_khz: msg.max_horizontal_khz,
+                    max_pixel_clock_mhz: msg.max_pixel_clock_mhz,
+                });
+            }
+        });
+        jay_randr::ConnectorProperty::handle(tc, randr, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            let c = data.connectors.last_mut().unwrap();
+            let output = c.output.as_mut().unwrap();
+            output
+                .properties
+                .push((msg.name.to_string(), msg.value.to_string()));
+        });
         jay_randr::VrrState::handle(tc, randr, data.clone(), |data, msg| {
             let mut data = data.borrow_mut();
             let c = data.connectors.last_mut().unwrap();
@@ -966,6 +1393,24 @@ impl Randr {
             }
             o.modes.push(mode);
         });
+        jay_randr::HdrState::handle(tc, randr, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            let c = data.connectors.last_mut().unwrap();
+            let o = c.output.as_mut().unwrap();
+            o.hdr_capable = msg.capable != 0;
+            o.hdr_enabled = msg.enabled != 0;
+        });
+        jay_randr::ModeFallback::handle(tc, randr, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            let c = data.connectors.last_mut().unwrap();
+            let o = c.output.as_mut().unwrap();
+            o.requested_mode = Some(Mode {
+                width: msg.width,
+                height: msg.height,
+                refresh_rate_millihz: msg.refresh_rate_millihz,
+                current: false,
+            });
+        });
         tc.round_trip().await;
         let x = data.borrow_mut().clone();
         x
@@ -4,9 +4,10 @@ use {
         cli::{GlobalArgs, ScreenshotArgs, ScreenshotFormat},
         format::XRGB8888,
         gfx_apis,
+        screenshoter::xrgb8888_encode_png,
         tools::tool_client::{with_tool_client, Handle, ToolClient},
         udmabuf::{Udmabuf, UdmabufError},
-        utils::{errorfmt::ErrorFmt, queue::AsyncQueue, windows::WindowsExt},
+        utils::{errorfmt::ErrorFmt, queue::AsyncQueue},
         video::{
             dmabuf::{DmaBuf, DmaBufIds, DmaBufPlane, PlaneVec},
             drm::{Drm, DrmError},
@@ -19,7 +20,6 @@ use {
     },
     chrono::Local,
     jay_algorithms::qoi::xrgb8888_encode_qoi,
-    png::{BitDepth, ColorType, Encoder, SrgbRenderingIntent},
     std::{
         cell::{Cell, RefCell},
         rc::Rc,
@@ -215,23 +215,10 @@ pub fn buf_to_bytes(
             bo_map.stride() as u32,
         ));
     }
-
-    let mut out = vec![];
-    {
-        let mut image_data = Vec::with_capacity((buf.width * buf.height * 4) as usize);
-        let lines = data[..(buf.height as usize * bo_map.stride() as usize)]
-            .chunks_exact(bo_map.stride() as usize);
-        for line in lines {
-            for pixel in line[..(buf.width as usize * 4)].array_chunks_ext::<4>() {
-                image_data.extend_from_slice(&[pixel[2], pixel[1], pixel[0], 255])
-            }
-        }
-        let mut encoder = Encoder::new(&mut out, buf.width as _, buf.height as _);
-        encoder.set_color(ColorType::Rgba);
-        encoder.set_depth(BitDepth::Eight);
-        encoder.set_source_srgb(SrgbRenderingIntent::Perceptual);
-        let mut writer = encoder.write_header().unwrap();
-        writer.write_image_data(&image_data).unwrap();
-    }
-    Ok(out)
+    Ok(xrgb8888_encode_png(
+        data,
+        buf.width,
+        buf.height,
+        bo_map.stride(),
+    ))
 }
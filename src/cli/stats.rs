@@ -0,0 +1,152 @@
+use {
+    crate::{
+        cli::GlobalArgs,
+        tools::tool_client::{with_tool_client, Handle, ToolClient},
+        utils::stack::Stack,
+        wire::{jay_compositor, jay_stats, JayStatsId},
+    },
+    clap::{Args, Subcommand},
+    std::{cell::Cell, rc::Rc},
+};
+
+#[derive(Args, Debug)]
+pub struct StatsArgs {
+    #[clap(subcommand)]
+    pub command: Option<StatsCmd>,
+}
+
+#[derive(Subcommand, Debug, Default)]
+pub enum StatsCmd {
+    /// Print the frame timing, GPU, and client statistics.
+    #[default]
+    Show,
+    /// Reset all statistics.
+    Reset,
+}
+
+pub fn main(global: GlobalArgs, args: StatsArgs) {
+    with_tool_client(global.log_level.into(), |tc| async move {
+        let stats = Stats { tc: tc.clone() };
+        stats.run(args).await;
+    });
+}
+
+struct Stats {
+    tc: Rc<ToolClient>,
+}
+
+struct Output {
+    name: String,
+    frames: u64,
+    missed_vblanks: u64,
+    frame_time_avg_ns: u64,
+    frame_time_max_ns: u64,
+    render_time_avg_ns: u64,
+    render_time_max_ns: u64,
+}
+
+struct ClientResources {
+    id: u64,
+    pid: u32,
+    comm: String,
+    fds: u64,
+    memory: u64,
+}
+
+impl Stats {
+    async fn run(self, args: StatsArgs) {
+        let tc = &self.tc;
+        let comp = tc.jay_compositor().await;
+        let stats = tc.id();
+        tc.send(jay_compositor::GetStats {
+            self_id: comp,
+            id: stats,
+        });
+        match args.command.unwrap_or_default() {
+            StatsCmd::Show => self.show(stats).await,
+            StatsCmd::Reset => self.reset(stats).await,
+        }
+    }
+
+    async fn show(self, stats: JayStatsId) {
+        let tc = &self.tc;
+        tc.send(jay_stats::Get { self_id: stats });
+        let outputs = Rc::new(Stack::default());
+        jay_stats::Output::handle(tc, stats, outputs.clone(), |o, msg| {
+            o.push(Output {
+                name: msg.name.to_string(),
+                frames: msg.frames,
+                missed_vblanks: msg.missed_vblanks,
+                frame_time_avg_ns: msg.frame_time_avg_ns,
+                frame_time_max_ns: msg.frame_time_max_ns,
+                render_time_avg_ns: msg.render_time_avg_ns,
+                render_time_max_ns: msg.render_time_max_ns,
+            });
+        });
+        let uploads = Rc::new(Cell::new(0));
+        jay_stats::TextureUploads::handle(tc, stats, uploads.clone(), |u, msg| {
+            u.set(msg.count);
+        });
+        let cpu_time = Rc::new(Cell::new(None));
+        jay_stats::CpuTime::handle(tc, stats, cpu_time.clone(), |c, msg| {
+            c.set(Some(msg.nsec));
+        });
+        let clients = Rc::new(Stack::default());
+        jay_stats::ClientResources::handle(tc, stats, clients.clone(), |c, msg| {
+            c.push(ClientResources {
+                id: msg.id,
+                pid: msg.pid,
+                comm: msg.comm.to_string(),
+                fds: msg.fds,
+                memory: msg.memory,
+            });
+        });
+        tc.round_trip().await;
+        let mut outputs = outputs.take();
+        outputs.sort_by(|l, r| l.name.cmp(&r.name));
+        let ms = |ns: u64| ns as f64 / 1_000_000.0;
+        for o in &outputs {
+            println!("{}:", o.name);
+            println!("  frames: {}", o.frames);
+            println!("  missed vblanks: {}", o.missed_vblanks);
+            println!(
+                "  frame time: avg {:.3} ms, max {:.3} ms",
+                ms(o.frame_time_avg_ns),
+                ms(o.frame_time_max_ns),
+            );
+            println!(
+                "  render time: avg {:.3} ms, max {:.3} ms",
+                ms(o.render_time_avg_ns),
+                ms(o.render_time_max_ns),
+            );
+        }
+        println!("texture uploads: {}", uploads.get());
+        if let Some(nsec) = cpu_time.get() {
+            println!(
+                "compositor cpu time: {:.3} s",
+                nsec as f64 / 1_000_000_000.0
+            );
+        }
+        let mut clients = clients.take();
+        if !clients.is_empty() {
+            clients.sort_by_key(|c| c.id);
+            println!("clients:");
+            for c in &clients {
+                println!(
+                    "  {} ({}, pid {}): {} fds, {:.1} MiB",
+                    c.id,
+                    c.comm,
+                    c.pid,
+                    c.fds,
+                    c.memory as f64 / (1024.0 * 1024.0),
+                );
+            }
+        }
+    }
+
+    async fn reset(self, stats: JayStatsId) {
+        let tc = &self.tc;
+        tc.send(jay_stats::Reset { self_id: stats });
+        tc.round_trip().await;
+    }
+}
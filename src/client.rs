@@ -1,7 +1,11 @@
 use {
     crate::{
         async_engine::SpawnedFuture,
-        client::{error::LookupError, objects::Objects},
+        client::{
+            error::LookupError,
+            objects::Objects,
+            resources::{dmabuf_resources, ClientResources},
+        },
         ifs::{
             wl_display::WlDisplay,
             wl_registry::WlRegistry,
@@ -20,6 +24,7 @@ use {
             pending_serial::PendingSerial,
             pid_info::{get_pid_info, get_socket_creds, PidInfo},
         },
+        video::dmabuf::DmaBuf,
         wire::WlRegistryId,
     },
     ahash::AHashMap,
@@ -37,10 +42,12 @@ use {
 pub use {
     error::{ClientError, ParserError},
     objects::MIN_SERVER_ID,
+    resources::{ClientLimits, ResourceCharge},
 };
 
 mod error;
 mod objects;
+mod resources;
 mod tasks;
 
 bitflags! {
@@ -57,6 +64,10 @@ bitflags! {
         CAP_DRM_LEASE                = 1 << 9,
         CAP_INPUT_METHOD             = 1 << 10,
         CAP_WORKSPACE                = 1 << 11,
+        CAP_OUTPUT_MANAGER           = 1 << 12,
+        CAP_OUTPUT_POWER_MANAGER     = 1 << 13,
+        CAP_GAMMA_CONTROL_MANAGER    = 1 << 14,
+        CAP_FOREIGN_TOPLEVEL_MANAGER = 1 << 15,
 }
 
 pub const CAPS_DEFAULT: ClientCaps = ClientCaps(CAP_LAYER_SHELL.0 | CAP_DRM_LEASE.0);
@@ -149,6 +160,19 @@ impl Clients {
         bounding_caps: ClientCaps,
         is_xwayland: bool,
     ) -> Result<Rc<Client>, ClientError> {
+        let pid_info = get_pid_info(uid, pid);
+        let mut effective_caps = effective_caps;
+        if !is_xwayland
+            && effective_caps.contains(CAP_JAY_COMPOSITOR)
+            && !global.may_use_jay_protocols(&pid_info)
+        {
+            log::info!(
+                "Client {} ({:?}) is not in the jay protocol allowlist",
+                id,
+                pid_info.exe(),
+            );
+            effective_caps &= !CAP_JAY_COMPOSITOR;
+        }
         let data = Rc::new_cyclic(|slf| Client {
             id,
             state: global.clone(),
@@ -163,7 +187,7 @@ impl Clients {
             effective_caps,
             bounding_caps,
             last_enter_serial: Default::default(),
-            pid_info: get_pid_info(uid, pid),
+            pid_info,
             serials: Default::default(),
             symmetric_delete: Cell::new(false),
             last_xwayland_serial: Cell::new(0),
@@ -177,6 +201,7 @@ impl Clients {
             )),
             wire_scale: Default::default(),
             focus_stealing_serial: Default::default(),
+            resources: Default::default(),
         });
         track!(data, data);
         let display = Rc::new(WlDisplay::new(&data));
@@ -245,11 +270,13 @@ pub struct ClientHolder {
 
 impl Drop for ClientHolder {
     fn drop(&mut self) {
+        for seat in self.data.state.globals.seats.lock().values() {
+            seat.client_destroyed(self.data.id);
+        }
         self.data.objects.destroy();
         self.data.flush_request.clear();
         self.data.shutdown.clear();
         self.data.surfaces_by_xwayland_serial.clear();
-        self.data.remove_activation_tokens();
         self.data.commit_timelines.clear();
     }
 }
@@ -289,6 +316,7 @@ pub struct Client {
     pub commit_timelines: Rc<CommitTimelines>,
     pub wire_scale: Cell<Option<i32>>,
     pub focus_stealing_serial: Cell<Option<u64>>,
+    pub resources: Rc<ClientResources>,
 }
 
 pub const NUM_CACHED_SERIAL_RANGES: usize = 64;
@@ -299,6 +327,29 @@ pub struct SerialRange {
 }
 
 impl Client {
+    /// Attributes fds and memory to this client.
+    ///
+    /// Fails if this would exceed the configured limits. The resources are released
+    /// when the returned object is dropped.
+    pub fn charge_resources(&self, fds: u64, memory: u64) -> Result<ResourceCharge, ClientError> {
+        let limits = self.state.client_limits.get();
+        let res = self.resources.charge(limits, fds, memory);
+        if let Err(e) = &res {
+            log::warn!(
+                "Client {} ({}) exceeded its resource limits: {}",
+                self.id,
+                self.pid_info.comm,
+                ErrorFmt(e),
+            );
+        }
+        res
+    }
+
+    pub fn charge_dmabuf(&self, dmabuf: &DmaBuf) -> Result<ResourceCharge, ClientError> {
+        let (fds, memory) = dmabuf_resources(dmabuf);
+        self.charge_resources(fds, memory)
+    }
+
     pub fn invalid_request(&self, obj: &dyn Object, request: u32) {
         log::error!(
             "Client {} sent an invalid request {} on object {} of type {}",
@@ -488,12 +539,6 @@ impl Client {
             })),
         }
     }
-
-    fn remove_activation_tokens(&self) {
-        for token in &*self.activation_tokens.borrow() {
-            self.state.activation_tokens.remove(token);
-        }
-    }
 }
 
 pub trait WaylandObject: Object {
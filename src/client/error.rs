@@ -49,6 +49,10 @@ pub enum ClientError {
     LookupError(LookupError),
     #[error("Could not add object {0} to the client")]
     AddObjectError(ObjectId, #[source] Box<ClientError>),
+    #[error("The client exceeded its limit of {0} file descriptors")]
+    FdLimitExceeded(u64),
+    #[error("The client exceeded its limit of {0} bytes of memory")]
+    MemoryLimitExceeded(u64),
 }
 
 #[derive(Debug, Error)]
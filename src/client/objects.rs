@@ -2,6 +2,7 @@ use {
     crate::{
         client::{Client, ClientError},
         ifs::{
+            color_management::wp_image_description_v1::WpImageDescriptionV1,
             ext_foreign_toplevel_handle_v1::ExtForeignToplevelHandleV1,
             ext_image_capture_source_v1::ExtImageCaptureSourceV1,
             ext_image_copy::ext_image_copy_capture_session_v1::ExtImageCopyCaptureSessionV1,
@@ -27,11 +28,16 @@ use {
                 xdg_surface::{xdg_popup::XdgPopup, xdg_toplevel::XdgToplevel, XdgSurface},
                 WlSurface,
             },
+            wlr_output_manager::{
+                zwlr_output_head_v1::ZwlrOutputHeadV1, zwlr_output_mode_v1::ZwlrOutputModeV1,
+            },
             workspace_manager::ext_workspace_group_handle_v1::ExtWorkspaceGroupHandleV1,
             wp_drm_lease_connector_v1::WpDrmLeaseConnectorV1,
             wp_linux_drm_syncobj_timeline_v1::WpLinuxDrmSyncobjTimelineV1,
             xdg_positioner::XdgPositioner,
+            xdg_toplevel_icon_v1::XdgToplevelIconV1,
             xdg_wm_base::XdgWmBase,
+            zwlr_foreign_toplevel_handle_v1::ZwlrForeignToplevelHandleV1,
         },
         object::{Object, ObjectId},
         utils::{
@@ -43,12 +49,13 @@ use {
             ExtImageCopyCaptureSessionV1Id, ExtWorkspaceGroupHandleV1Id, JayOutputId,
             JayScreencastId, JayToplevelId, JayWorkspaceId, WlBufferId, WlDataSourceId, WlOutputId,
             WlPointerId, WlRegionId, WlRegistryId, WlSeatId, WlSurfaceId, WpDrmLeaseConnectorV1Id,
-            WpLinuxDrmSyncobjTimelineV1Id, XdgPopupId, XdgPositionerId, XdgSurfaceId,
-            XdgToplevelId, XdgWmBaseId, ZwlrDataControlSourceV1Id, ZwpPrimarySelectionSourceV1Id,
-            ZwpTabletToolV2Id,
+            WpImageDescriptionV1Id, WpLinuxDrmSyncobjTimelineV1Id, XdgPopupId, XdgPositionerId,
+            XdgSurfaceId, XdgToplevelIconV1Id, XdgToplevelId, XdgWmBaseId,
+            ZwlrDataControlSourceV1Id, ZwlrForeignToplevelHandleV1Id, ZwlrOutputHeadV1Id,
+            ZwlrOutputModeV1Id, ZwpPrimarySelectionSourceV1Id, ZwpTabletToolV2Id,
         },
     },
-    std::{cell::RefCell, rc::Rc},
+    std::{cell::RefCell, collections::BTreeMap, rc::Rc},
 };
 
 pub struct Objects {
@@ -85,6 +92,12 @@ pub struct Objects {
     pub ext_data_sources: CopyHashMap<ExtDataControlSourceV1Id, Rc<ExtDataControlSourceV1>>,
     pub ext_workspace_groups:
         CopyHashMap<ExtWorkspaceGroupHandleV1Id, Rc<ExtWorkspaceGroupHandleV1>>,
+    pub wlr_output_heads: CopyHashMap<ZwlrOutputHeadV1Id, Rc<ZwlrOutputHeadV1>>,
+    pub wlr_output_modes: CopyHashMap<ZwlrOutputModeV1Id, Rc<ZwlrOutputModeV1>>,
+    pub wlr_foreign_toplevel_handles:
+        CopyHashMap<ZwlrForeignToplevelHandleV1Id, Rc<ZwlrForeignToplevelHandleV1>>,
+    pub image_descriptions: CopyHashMap<WpImageDescriptionV1Id, Rc<WpImageDescriptionV1>>,
+    pub xdg_toplevel_icons: CopyHashMap<XdgToplevelIconV1Id, Rc<XdgToplevelIconV1>>,
     ids: RefCell<Vec<usize>>,
 }
 
@@ -123,6 +136,11 @@ impl Objects {
             ext_copy_sessions: Default::default(),
             ext_data_sources: Default::default(),
             ext_workspace_groups: Default::default(),
+            wlr_output_heads: Default::default(),
+            wlr_output_modes: Default::default(),
+            wlr_foreign_toplevel_handles: Default::default(),
+            image_descriptions: Default::default(),
+            xdg_toplevel_icons: Default::default(),
             ids: RefCell::new(vec![]),
         }
     }
@@ -165,6 +183,11 @@ impl Objects {
         self.ext_copy_sessions.clear();
         self.ext_data_sources.clear();
         self.ext_workspace_groups.clear();
+        self.wlr_output_heads.clear();
+        self.wlr_output_modes.clear();
+        self.wlr_foreign_toplevel_handles.clear();
+        self.image_descriptions.clear();
+        self.xdg_toplevel_icons.clear();
     }
 
     pub fn id<T>(&self, client_data: &Client) -> Result<T, ClientError>
@@ -243,6 +266,15 @@ impl Objects {
         self.registries.lock()
     }
 
+    /// Returns the number of objects of each interface.
+    pub fn interface_counts(&self) -> BTreeMap<&'static str, usize> {
+        let mut counts = BTreeMap::new();
+        for obj in self.registry.lock().values() {
+            *counts.entry(obj.interface().name()).or_default() += 1;
+        }
+        counts
+    }
+
     fn id_offset(&self) -> u32 {
         let mut ids = self.ids.borrow_mut();
         for (pos, seg) in ids.iter_mut().enumerate() {
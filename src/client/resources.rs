@@ -0,0 +1,71 @@
+use {
+    crate::{client::ClientError, utils::numcell::NumCell, video::dmabuf::DmaBuf},
+    std::rc::Rc,
+};
+
+/// Limits for the resources that can be attributed to a single client.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ClientLimits {
+    pub max_fds: Option<u64>,
+    pub max_memory: Option<u64>,
+}
+
+/// The file descriptors and the approximate memory currently attributed to a client.
+///
+/// Only long-lived resources are counted: shm pools and their mappings, dmabuf buffers,
+/// and imported syncobj timelines.
+#[derive(Default)]
+pub struct ClientResources {
+    pub fds: NumCell<u64>,
+    pub memory: NumCell<u64>,
+}
+
+/// Resources that are attributed to a client until this object is dropped.
+pub struct ResourceCharge {
+    resources: Rc<ClientResources>,
+    fds: u64,
+    memory: u64,
+}
+
+impl ClientResources {
+    pub fn charge(
+        self: &Rc<Self>,
+        limits: ClientLimits,
+        fds: u64,
+        memory: u64,
+    ) -> Result<ResourceCharge, ClientError> {
+        if let Some(max) = limits.max_fds {
+            if self.fds.get().saturating_add(fds) > max {
+                return Err(ClientError::FdLimitExceeded(max));
+            }
+        }
+        if let Some(max) = limits.max_memory {
+            if self.memory.get().saturating_add(memory) > max {
+                return Err(ClientError::MemoryLimitExceeded(max));
+            }
+        }
+        self.fds.fetch_add(fds);
+        self.memory.fetch_add(memory);
+        Ok(ResourceCharge {
+            resources: self.clone(),
+            fds,
+            memory,
+        })
+    }
+}
+
+impl Drop for ResourceCharge {
+    fn drop(&mut self) {
+        self.resources.fds.fetch_sub(self.fds);
+        self.resources.memory.fetch_sub(self.memory);
+    }
+}
+
+/// Returns the number of fds and the approximate memory of a dmabuf.
+pub fn dmabuf_resources(dmabuf: &DmaBuf) -> (u64, u64) {
+    let mut memory = 0u64;
+    for plane in &dmabuf.planes {
+        memory = memory.saturating_add(plane.stride as u64 * dmabuf.height.max(0) as u64);
+    }
+    (dmabuf.planes.len() as u64, memory)
+}
@@ -0,0 +1,434 @@
+use {
+    crate::{gfx_api::ColorTransform, theme::Color, utils::numcell::NumCell},
+    std::rc::Rc,
+};
+
+/// The chromaticity coordinates of the primaries and the white point of a color
+/// space.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Primaries {
+    pub r: (f64, f64),
+    pub g: (f64, f64),
+    pub b: (f64, f64),
+    pub wp: (f64, f64),
+}
+
+const D65: (f64, f64) = (0.3127, 0.3290);
+
+/// The luminance in cd/m² that SDR white is mapped to on outputs in HDR mode.
+const HDR_REFERENCE_WHITE: f64 = 203.0;
+
+impl Primaries {
+    pub const SRGB: Self = Self {
+        r: (0.64, 0.33),
+        g: (0.30, 0.60),
+        b: (0.15, 0.06),
+        wp: D65,
+    };
+
+    /// Creates primaries from coordinates that have been multiplied by 1_000_000.
+    pub fn from_protocol(c: [i32; 8]) -> Self {
+        let f = |i: usize| (c[i] as f64 / 1_000_000.0, c[i + 1] as f64 / 1_000_000.0);
+        Self {
+            r: f(0),
+            g: f(2),
+            b: f(4),
+            wp: f(6),
+        }
+    }
+
+    pub fn to_protocol(&self) -> [i32; 8] {
+        let f = |v: f64| (v * 1_000_000.0).round() as i32;
+        [
+            f(self.r.0),
+            f(self.r.1),
+            f(self.g.0),
+            f(self.g.1),
+            f(self.b.0),
+            f(self.b.1),
+            f(self.wp.0),
+            f(self.wp.1),
+        ]
+    }
+
+    /// Returns the matrix that converts linear RGB in these primaries to CIE XYZ.
+    fn to_xyz(&self) -> Option<Matrix> {
+        let col = |(x, y): (f64, f64)| [x, y, 1.0 - x - y];
+        let [r, g, b] = [col(self.r), col(self.g), col(self.b)];
+        let m = Matrix([[r[0], g[0], b[0]], [r[1], g[1], b[1]], [r[2], g[2], b[2]]]);
+        let (x, y) = self.wp;
+        let s = m.invert()?.apply([x / y, 1.0, (1.0 - x - y) / y]);
+        let mut res = m;
+        for row in &mut res.0 {
+            for (v, s) in row.iter_mut().zip(s) {
+                *v *= s;
+            }
+        }
+        Some(res)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum NamedPrimaries {
+    Srgb,
+    PalM,
+    Pal,
+    Ntsc,
+    GenericFilm,
+    Bt2020,
+    Cie1931Xyz,
+    DciP3,
+    DisplayP3,
+    AdobeRgb,
+}
+
+impl NamedPrimaries {
+    pub const ALL: [Self; 10] = [
+        Self::Srgb,
+        Self::PalM,
+        Self::Pal,
+        Self::Ntsc,
+        Self::GenericFilm,
+        Self::Bt2020,
+        Self::Cie1931Xyz,
+        Self::DciP3,
+        Self::DisplayP3,
+        Self::AdobeRgb,
+    ];
+
+    pub fn primaries(self) -> Primaries {
+        let c = |r, g, b, wp| Primaries { r, g, b, wp };
+        match self {
+            Self::Srgb => Primaries::SRGB,
+            Self::PalM => c((0.67, 0.33), (0.21, 0.71), (0.14, 0.08), (0.310, 0.316)),
+            Self::Pal => c((0.64, 0.33), (0.29, 0.60), (0.15, 0.06), D65),
+            Self::Ntsc => c((0.630, 0.340), (0.310, 0.595), (0.155, 0.070), D65),
+            Self::GenericFilm => c(
+                (0.243, 0.692),
+                (0.145, 0.049),
+                (0.681, 0.319),
+                (0.310, 0.316),
+            ),
+            Self::Bt2020 => c((0.708, 0.292), (0.170, 0.797), (0.131, 0.046), D65),
+            Self::Cie1931Xyz => c((1.0, 0.0), (0.0, 1.0), (0.0, 0.0), (1.0 / 3.0, 1.0 / 3.0)),
+            Self::DciP3 => c(
+                (0.680, 0.320),
+                (0.265, 0.690),
+                (0.150, 0.060),
+                (0.314, 0.351),
+            ),
+            Self::DisplayP3 => c((0.680, 0.320), (0.265, 0.690), (0.150, 0.060), D65),
+            Self::AdobeRgb => c((0.64, 0.33), (0.21, 0.71), (0.15, 0.06), D65),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TransferFunction {
+    Srgb,
+    ExtLinear,
+    Bt1886,
+    Gamma22,
+    Gamma28,
+    St2084Pq,
+    /// A pure power function with the given exponent.
+    Power(f64),
+}
+
+impl TransferFunction {
+    fn default_luminance(self) -> Luminance {
+        match self {
+            Self::St2084Pq => Luminance {
+                min: 0.005,
+                max: 10000.0,
+                reference: 203.0,
+            },
+            Self::Bt1886 => Luminance {
+                min: 0.01,
+                max: 100.0,
+                reference: 100.0,
+            },
+            _ => Luminance {
+                min: 0.2,
+                max: 80.0,
+                reference: 80.0,
+            },
+        }
+    }
+}
+
+/// Luminances in cd/m².
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Luminance {
+    pub min: f64,
+    pub max: f64,
+    pub reference: f64,
+}
+
+/// A parametric image description.
+#[derive(Debug)]
+pub struct ColorDescription {
+    pub identity: u32,
+    pub named_primaries: Option<NamedPrimaries>,
+    pub primaries: Primaries,
+    pub transfer_function: TransferFunction,
+    pub luminance: Luminance,
+    pub target_primaries: Option<Primaries>,
+    pub target_luminance: Option<(f64, f64)>,
+    pub max_cll: Option<u32>,
+    pub max_fall: Option<u32>,
+    /// The transformation to the sRGB color space used for compositing, or `None` if
+    /// the description is equivalent to sRGB.
+    pub transform: Option<ColorTransform>,
+}
+
+pub struct ColorDescriptionParams {
+    pub named_primaries: Option<NamedPrimaries>,
+    pub primaries: Primaries,
+    pub transfer_function: TransferFunction,
+    pub luminance: Option<Luminance>,
+    pub target_primaries: Option<Primaries>,
+    pub target_luminance: Option<(f64, f64)>,
+    pub max_cll: Option<u32>,
+    pub max_fall: Option<u32>,
+}
+
+pub struct ColorManager {
+    ids: NumCell<u32>,
+    srgb: Rc<ColorDescription>,
+    /// Converts linear sRGB to linear BT.2020 scaled to the PQ range.
+    hdr_matrix: Matrix,
+    /// Converts the contents of outputs in HDR mode back to sRGB.
+    hdr_capture_transform: ColorTransform,
+}
+
+impl Default for ColorManager {
+    fn default() -> Self {
+        let tf = TransferFunction::Srgb;
+        let bt2020 = NamedPrimaries::Bt2020.primaries();
+        let to_bt2020 = bt2020.to_xyz().unwrap().invert().unwrap();
+        let hdr_matrix = to_bt2020
+            .mul(&Primaries::SRGB.to_xyz().unwrap())
+            .scale(HDR_REFERENCE_WHITE / 10000.0);
+        let pq = TransferFunction::St2084Pq;
+        let hdr_capture_transform = color_transform(&bt2020, pq, &pq.default_luminance())
+            .flatten()
+            .unwrap();
+        Self {
+            ids: NumCell::new(2),
+            srgb: Rc::new(ColorDescription {
+                identity: 1,
+                named_primaries: Some(NamedPrimaries::Srgb),
+                primaries: Primaries::SRGB,
+                transfer_function: tf,
+                luminance: tf.default_luminance(),
+                target_primaries: None,
+                target_luminance: None,
+                max_cll: None,
+                max_fall: None,
+                transform: None,
+            }),
+            hdr_matrix,
+            hdr_capture_transform,
+        }
+    }
+}
+
+impl ColorManager {
+    /// The color space used for compositing.
+    pub fn srgb(&self) -> &Rc<ColorDescription> {
+        &self.srgb
+    }
+
+    /// Creates a description from parameters. Returns `None` if the primaries do not
+    /// describe a valid color space.
+    pub fn create(&self, params: ColorDescriptionParams) -> Option<Rc<ColorDescription>> {
+        let tf = params.transfer_function;
+        let luminance = params.luminance.unwrap_or_else(|| tf.default_luminance());
+        let transform = color_transform(&params.primaries, tf, &luminance)?;
+        Some(Rc::new(ColorDescription {
+            identity: self.ids.fetch_add(1),
+            named_primaries: params.named_primaries,
+            primaries: params.primaries,
+            transfer_function: tf,
+            luminance,
+            target_primaries: params.target_primaries,
+            target_luminance: params.target_luminance,
+            max_cll: params.max_cll,
+            max_fall: params.max_fall,
+            transform,
+        }))
+    }
+
+    /// Combines a texture transform with the conversion to the BT.2020 PQ color space
+    /// used by outputs in HDR mode. SDR white is mapped to the HDR reference white.
+    pub fn hdr_transform(&self, ct: Option<ColorTransform>) -> ColorTransform {
+        let (matrix, eotf, eotf_arg) = match ct {
+            Some(ct) => {
+                let m = Matrix(ct.matrix.map(|row| row.map(|v| v as f64)));
+                (self.hdr_matrix.mul(&m), ct.eotf, ct.eotf_arg)
+            }
+            None => (self.hdr_matrix, Eotf::Srgb, 1.0),
+        };
+        ColorTransform {
+            matrix: matrix.0.map(|row| row.map(|v| v as f32)),
+            eotf,
+            eotf_arg,
+            oetf: Oetf::St2084Pq,
+        }
+    }
+
+    /// Converts a premultiplied sRGB color to the BT.2020 PQ color space.
+    pub fn hdr_color(&self, c: Color) -> Color {
+        if c.a <= 0.0 {
+            return c;
+        }
+        let a = c.a as f64;
+        let rgb = [c.r, c.g, c.b].map(|v| srgb_eotf(v as f64 / a));
+        let [r, g, b] = self
+            .hdr_matrix
+            .apply(rgb)
+            .map(|v| (pq_inv_eotf(v) * a) as f32);
+        Color { r, g, b, a: c.a }
+    }
+
+    /// The transform that converts the contents of outputs in HDR mode to sRGB.
+    pub fn hdr_capture_transform(&self) -> ColorTransform {
+        self.hdr_capture_transform
+    }
+}
+
+fn srgb_eotf(c: f64) -> f64 {
+    let c = c.clamp(0.0, 1.0);
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn pq_inv_eotf(c: f64) -> f64 {
+    const M1: f64 = 0.1593017578125;
+    const M2: f64 = 78.84375;
+    const C1: f64 = 0.8359375;
+    const C2: f64 = 18.8515625;
+    const C3: f64 = 18.6875;
+    let p = c.clamp(0.0, 1.0).powf(M1);
+    ((C1 + C2 * p) / (1.0 + C3 * p)).powf(M2)
+}
+
+fn color_transform(
+    primaries: &Primaries,
+    tf: TransferFunction,
+    luminance: &Luminance,
+) -> Option<Option<ColorTransform>> {
+    let matrix = if *primaries == Primaries::SRGB {
+        Matrix::IDENTITY
+    } else {
+        let to_xyz = primaries.to_xyz()?;
+        let from_xyz = Primaries::SRGB.to_xyz()?.invert()?;
+        let adapt = bradford(primaries.wp, Primaries::SRGB.wp)?;
+        from_xyz.mul(&adapt).mul(&to_xyz)
+    };
+    if matrix == Matrix::IDENTITY && tf == TransferFunction::Srgb {
+        return Some(None);
+    }
+    let (eotf, eotf_arg) = match tf {
+        TransferFunction::Srgb => (Eotf::Srgb, 1.0),
+        TransferFunction::ExtLinear => (Eotf::Linear, 1.0),
+        TransferFunction::Bt1886 => (Eotf::Power, 2.4),
+        TransferFunction::Gamma22 => (Eotf::Power, 2.2),
+        TransferFunction::Gamma28 => (Eotf::Power, 2.8),
+        TransferFunction::Power(exp) => (Eotf::Power, exp),
+        TransferFunction::St2084Pq => (Eotf::St2084Pq, 10000.0 / luminance.reference),
+    };
+    let m = matrix.0.map(|row| row.map(|v| v as f32));
+    Some(Some(ColorTransform {
+        matrix: m,
+        eotf,
+        eotf_arg: eotf_arg as f32,
+        oetf: Oetf::Srgb,
+    }))
+}
+
+/// The electro-optical transfer function applied before the matrix. The values must
+/// match the constants in the shaders.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Eotf {
+    Srgb = 0,
+    Linear = 1,
+    /// `x^arg`
+    Power = 2,
+    /// PQ, scaled by `arg` so that the reference white maps to 1.0.
+    St2084Pq = 3,
+}
+
+/// The inverse electro-optical transfer function applied after the matrix. The values
+/// must match the constants in the shaders.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Oetf {
+    Srgb = 0,
+    /// PQ of values where 1.0 corresponds to 10000 cd/m².
+    St2084Pq = 1,
+}
+
+/// Returns the Bradford chromatic adaptation from one white point to another.
+fn bradford(from: (f64, f64), to: (f64, f64)) -> Option<Matrix> {
+    if from == to {
+        return Some(Matrix::IDENTITY);
+    }
+    const M: Matrix = Matrix([
+        [0.8951, 0.2664, -0.1614],
+        [-0.7502, 1.7135, 0.0367],
+        [0.0389, -0.0685, 1.0296],
+    ]);
+    let xyz = |(x, y): (f64, f64)| [x / y, 1.0, (1.0 - x - y) / y];
+    let src = M.apply(xyz(from));
+    let dst = M.apply(xyz(to));
+    let scale = Matrix([
+        [dst[0] / src[0], 0.0, 0.0],
+        [0.0, dst[1] / src[1], 0.0],
+        [0.0, 0.0, dst[2] / src[2]],
+    ]);
+    Some(M.invert()?.mul(&scale).mul(&M))
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Matrix([[f64; 3]; 3]);
+
+impl Matrix {
+    const IDENTITY: Self = Self([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+
+    fn mul(&self, other: &Self) -> Self {
+        let mut res = [[0.0; 3]; 3];
+        for (i, row) in res.iter_mut().enumerate() {
+            for (j, v) in row.iter_mut().enumerate() {
+                *v = (0..3).map(|k| self.0[i][k] * other.0[k][j]).sum();
+            }
+        }
+        Self(res)
+    }
+
+    fn scale(&self, s: f64) -> Self {
+        Self(self.0.map(|row| row.map(|v| v * s)))
+    }
+
+    fn apply(&self, v: [f64; 3]) -> [f64; 3] {
+        self.0
+            .map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+    }
+
+    fn invert(&self) -> Option<Self> {
+        let [[a, b, c], [d, e, f], [g, h, i]] = self.0;
+        let co = [
+            [e * i - f * h, c * h - b * i, b * f - c * e],
+            [f * g - d * i, a * i - c * g, c * d - a * f],
+            [d * h - e * g, b * g - a * h, a * e - b * d],
+        ];
+        let det = a * co[0][0] + b * co[1][0] + c * co[2][0];
+        if !det.is_finite() || det.abs() < 1e-12 {
+            return None;
+        }
+        Some(Self(co.map(|row| row.map(|v| v / det))))
+    }
+}
@@ -7,8 +7,9 @@ use {
         backend::{self, Backend, Connector},
         backends::{
             dummy::{DummyBackend, DummyOutput},
-            metal, x,
+            headless, metal, x,
         },
+        capture_overlay::capture_overlay_textures,
         cli::{CliBackend, GlobalArgs, RunArgs},
         client::{ClientId, Clients},
         clientmem::{self, ClientMemError},
@@ -16,19 +17,22 @@ use {
         cpu_worker::{CpuWorker, CpuWorkerError},
         damage::{visualize_damage, DamageVisualizer},
         dbus::Dbus,
-        ei::ei_client::EiClients,
+        ei::{ei_authorization::persist_ei_permissions, ei_client::EiClients},
         forker,
         globals::Globals,
         ifs::{
             jay_screencast::{perform_screencast_realloc, perform_toplevel_screencasts},
             wl_output::{OutputId, PersistentOutputState, WlOutputGlobal},
             wl_surface::{zwp_input_popup_surface_v2::input_popup_positioning, NoneSurfaceExt},
+            wlr_output_manager::wlr_output_manager_update,
             workspace_manager::workspace_manager_done,
         },
         io_uring::{IoUring, IoUringError},
         kbvm::KbvmContext,
         leaks,
         logger::Logger,
+        night_light::night_light,
+        notifications::notification_textures,
         output_schedule::OutputSchedule,
         portal::{self, PortalStartup},
         scale::Scale,
@@ -45,16 +49,25 @@ use {
         utils::{
             clonecell::CloneCell, errorfmt::ErrorFmt, fdcloser::FdCloser, numcell::NumCell,
             oserror::OsError, queue::AsyncQueue, refcounted::RefCounted, run_toplevel::RunToplevel,
-            tri::Try,
+            tri::Try, workspace_identifier::workspace_identifier,
         },
         version::VERSION,
         video::drm::wait_for_sync_obj::WaitForSyncObj,
         wheel::{Wheel, WheelError},
+        window_state::persist_window_states,
     },
     ahash::AHashSet,
     forker::ForkerProxy,
     jay_config::{_private::DEFAULT_SEAT_NAME, video::GfxApi},
-    std::{cell::Cell, env, future::Future, ops::Deref, rc::Rc, sync::Arc, time::Duration},
+    std::{
+        cell::{Cell, RefCell},
+        env,
+        future::Future,
+        ops::Deref,
+        rc::Rc,
+        sync::Arc,
+        time::Duration,
+    },
     thiserror::Error,
     uapi::c,
 };
@@ -182,6 +195,7 @@ fn start_compositor2(
         input_device_ids: Default::default(),
         input_device_handlers: Default::default(),
         theme: Default::default(),
+        color_manager: Default::default(),
         pending_container_layout: Default::default(),
         pending_container_render_positions: Default::default(),
         pending_container_render_title: Default::default(),
@@ -192,6 +206,7 @@ fn start_compositor2(
         pending_toplevel_screencasts: Default::default(),
         pending_screencast_reallocs_or_reconfigures: Default::default(),
         pending_placeholder_render_textures: Default::default(),
+        pending_notification_textures: Default::default(),
         dbus: Dbus::new(&engine, &ring, &run_toplevel),
         fdcloser: FdCloser::new(),
         logger: logger.clone(),
@@ -199,6 +214,11 @@ fn start_compositor2(
         outputs: Default::default(),
         drm_devs: Default::default(),
         status: Default::default(),
+        default_bar_clock_format: Default::default(),
+        bar_clock_future: Default::default(),
+        default_bar_show_focused_title: Cell::new(false),
+        notifications: Default::default(),
+        window_states: Default::default(),
         idle: IdleState {
             input: Default::default(),
             change: Default::default(),
@@ -210,6 +230,8 @@ fn start_compositor2(
             inhibited_idle_notifications: Default::default(),
             backend_idle: Cell::new(true),
             in_grace_period: Cell::new(false),
+            audio: Default::default(),
+            audio_inhibited: Default::default(),
         },
         run_args,
         xwayland: XWaylandState {
@@ -234,6 +256,9 @@ fn start_compositor2(
         lock: ScreenlockState {
             locked: Cell::new(false),
             lock: Default::default(),
+            fallback: Default::default(),
+            grace_period: Default::default(),
+            locked_usec: Default::default(),
         },
         scales,
         cursor_sizes: Default::default(),
@@ -241,10 +266,14 @@ fn start_compositor2(
         testers: Default::default(),
         render_ctx_watchers: Default::default(),
         workspace_watchers: Default::default(),
+        binding_mode_watchers: Default::default(),
         default_workspace_capture: Cell::new(true),
         default_gfx_api: Cell::new(GfxApi::Vulkan),
         activation_tokens: Default::default(),
+        activation_token_lifetime: Cell::new(Duration::from_secs(30)),
+        launch_watchers: Default::default(),
         toplevel_lists: Default::default(),
+        toplevel_managers: Default::default(),
         dma_buf_ids: Default::default(),
         drm_feedback_ids: Default::default(),
         direct_scanout_enabled: Cell::new(true),
@@ -255,6 +284,8 @@ fn start_compositor2(
         subsurface_ids: Default::default(),
         wait_for_sync_obj: Rc::new(WaitForSyncObj::new(&ring, &engine)),
         explicit_sync_enabled: Cell::new(true),
+        force_implicit_sync: Cell::new(false),
+        key_remap: Default::default(),
         keyboard_state_ids: Default::default(),
         physical_keyboard_ids: Default::default(),
         security_context_acceptors: Default::default(),
@@ -267,22 +298,38 @@ fn start_compositor2(
         tablet_tool_ids: Default::default(),
         tablet_pad_ids: Default::default(),
         damage_visualizer: DamageVisualizer::new(&engine),
+        texture_uploads: Default::default(),
+        show_surface_regions: Default::default(),
+        interactive_resize: Default::default(),
         default_vrr_mode: Cell::new(VrrMode::NEVER),
         default_vrr_cursor_hz: Cell::new(None),
         default_tearing_mode: Cell::new(TearingMode::VARIANT_3),
         ei_acceptor: Default::default(),
         ei_acceptor_future: Default::default(),
         enable_ei_acceptor: Default::default(),
+        jay_protocol_allowlist: Default::default(),
         ei_clients: EiClients::new(),
+        ei_authorization: Default::default(),
         slow_ei_clients: Default::default(),
         cpu_worker,
         ui_drag_enabled: Cell::new(true),
         ui_drag_threshold_squared: Cell::new(10),
+        empty_workspace_behavior: Default::default(),
+        workspace_back_and_forth: Cell::new(false),
+        autotiling: Cell::new(false),
         toplevels: Default::default(),
+        marks: Default::default(),
+        show_marks: Cell::new(false),
+        capture_redactions: Default::default(),
+        capture_overlay: Default::default(),
+        night_light: Default::default(),
         const_40hz_latch: Default::default(),
         tray_item_ids: Default::default(),
         data_control_device_ids: Default::default(),
         workspace_managers: Default::default(),
+        wlr_output_managers: Default::default(),
+        window_screenshots: Default::default(),
+        client_limits: Default::default(),
     });
     state.tracker.register(ClientId::from_raw(0));
     create_dummy_output(&state);
@@ -406,6 +453,11 @@ fn start_global_event_handlers(
             Phase::PostLayout,
             placeholder_render_textures(state.clone()),
         ),
+        eng.spawn2(
+            "notification textures",
+            Phase::PostLayout,
+            notification_textures(state.clone()),
+        ),
         eng.spawn2(
             "output render",
             Phase::PostLayout,
@@ -455,6 +507,24 @@ fn start_global_event_handlers(
             "workspace manager done",
             workspace_manager_done(state.clone()),
         ),
+        eng.spawn2(
+            "wlr output manager update",
+            Phase::PostLayout,
+            wlr_output_manager_update(state.clone()),
+        ),
+        eng.spawn(
+            "persist ei permissions",
+            persist_ei_permissions(state.clone()),
+        ),
+        eng.spawn(
+            "persist window states",
+            persist_window_states(state.clone()),
+        ),
+        eng.spawn(
+            "capture overlay textures",
+            capture_overlay_textures(state.clone()),
+        ),
+        eng.spawn("night light", night_light(state.clone())),
     ]
 }
 
@@ -494,6 +564,15 @@ async fn create_backend(
                     }
                 }
             }
+            CliBackend::Headless => {
+                log::info!("Trying to create headless backend");
+                match headless::create(state) {
+                    Ok(b) => return Some(b),
+                    Err(e) => {
+                        log::error!("Could not create headless backend: {}", ErrorFmt(e));
+                    }
+                }
+            }
         }
     }
     None
@@ -532,6 +611,8 @@ fn create_dummy_output(state: &Rc<State>) {
         vrr_mode: Cell::new(VrrMode::NEVER),
         vrr_cursor_hz: Default::default(),
         tearing_mode: Cell::new(&TearingMode::Never),
+        bar_clock_format: Default::default(),
+        bar_show_focused_title: Default::default(),
     });
     let connector = Rc::new(DummyOutput {
         id: state.connector_ids.next(),
@@ -545,6 +626,8 @@ fn create_dummy_output(state: &Rc<State>) {
         async_event: Default::default(),
         damaged: Cell::new(false),
         needs_vblank_emulation: Cell::new(false),
+        monitor_info: Default::default(),
+        mirror_source: Default::default(),
     });
     let schedule = Rc::new(OutputSchedule::new(
         &state.ring,
@@ -591,21 +674,35 @@ fn create_dummy_output(state: &Rc<State>) {
         screencasts: Default::default(),
         hardware_cursor_needs_render: Cell::new(false),
         screencopies: Default::default(),
+        screencopy_damage: Default::default(),
         title_visible: Cell::new(false),
+        focused_toplevel: Default::default(),
+        bar_clock: Default::default(),
         schedule,
         vblank_event: Default::default(),
         latch_event: Default::default(),
         presentation_event: Default::default(),
         render_margin_ns: Default::default(),
         flip_margin_ns: Default::default(),
+        expected_present_nsec: Default::default(),
+        previous_workspace: Default::default(),
+        previous_workspace_name: Default::default(),
+        software_cursor_damage: Default::default(),
+        stats: Default::default(),
         ext_copy_sessions: Default::default(),
         before_latch_event: Default::default(),
         tray_start_rel: Default::default(),
         tray_items: Default::default(),
         ext_workspace_groups: Default::default(),
+        powered: Cell::new(true),
+        power_controls: Default::default(),
+        gamma_control: Default::default(),
+        client_gamma_lut: Default::default(),
+        workspace_swipe_offset: Default::default(),
     });
     let dummy_workspace = Rc::new(WorkspaceNode {
         id: state.node_ids.next(),
+        identifier: workspace_identifier(),
         state: state.clone(),
         is_dummy: true,
         output: CloneCell::new(dummy_output.clone()),
@@ -613,7 +710,7 @@ fn create_dummy_output(state: &Rc<State>) {
         container: Default::default(),
         stacked: Default::default(),
         seat_state: Default::default(),
-        name: "dummy".to_string(),
+        name: RefCell::new("dummy".to_string()),
         output_link: Default::default(),
         visible: Default::default(),
         fullscreen: Default::default(),
@@ -627,6 +724,7 @@ fn create_dummy_output(state: &Rc<State>) {
         render_highlight: Default::default(),
         ext_workspaces: Default::default(),
         opt: Default::default(),
+        thumbnail: Default::default(),
     });
     *dummy_workspace.output_link.borrow_mut() =
         Some(dummy_output.workspaces.add_last(dummy_workspace.clone()));
@@ -4,7 +4,7 @@ mod handler;
 use crate::it::test_config::TEST_CONFIG_ENTRY;
 use {
     crate::{
-        backend::{ConnectorId, DrmDeviceId, InputDeviceId},
+        backend::{ConnectorId, DrmDeviceId, InputDeviceId, Mode},
         config::handler::ConfigProxyHandler,
         ifs::wl_seat::SeatId,
         state::State,
@@ -18,10 +18,14 @@ use {
         _private::{
             bincode_ops,
             ipc::{InitMessage, ServerFeature, ServerMessage, V1InitMessage},
-            ConfigEntry, VERSION,
+            ConfigEntry, WireMode, VERSION,
         },
-        input::{InputDevice, Seat, SwitchEvent},
-        keyboard::{mods::Modifiers, syms::KeySym},
+        input::{
+            InputDevice, PointerGesture, PointerInput, PointerTarget, Seat, SwitchEvent,
+            TouchGesture,
+        },
+        keyboard::{mods::Modifiers, syms::KeySym, ModifiedKeySym},
+        layout::{Layout, LayoutGeometry, LayoutRequest},
         video::{Connector, DrmDevice},
     },
     libloading::Library,
@@ -117,6 +121,61 @@ impl ConfigProxy {
         });
     }
 
+    pub fn mode_fallback(&self, connector: ConnectorId, requested: Mode, chosen: Mode) {
+        let wire = |mode: Mode| WireMode {
+            width: mode.width,
+            height: mode.height,
+            refresh_millihz: mode.refresh_rate_millihz,
+        };
+        self.send(&ServerMessage::ModeFallback {
+            connector: Connector(connector.raw() as _),
+            requested: wire(requested),
+            chosen: wire(chosen),
+        });
+    }
+
+    pub fn connector_mode_changed(&self, connector: ConnectorId, mode: Mode) {
+        self.send(&ServerMessage::ConnectorModeChanged {
+            connector: Connector(connector.raw() as _),
+            mode: WireMode {
+                width: mode.width,
+                height: mode.height,
+                refresh_millihz: mode.refresh_rate_millihz,
+            },
+        });
+    }
+
+    pub fn brightness_changed(&self, connector: ConnectorId, brightness: f64) {
+        self.send(&ServerMessage::BrightnessChanged {
+            connector: Connector(connector.raw() as _),
+            brightness,
+        });
+    }
+
+    pub fn workspace_layout(&self, workspace: &str) -> Option<Layout> {
+        self.handler.get()?.workspace_layouts.get(workspace)
+    }
+
+    pub fn workspace_inner_gap(&self, workspace: &str) -> Option<i32> {
+        self.handler.get()?.workspace_inner_gaps.get(workspace)
+    }
+
+    pub fn workspace_outer_gap(&self, workspace: &str) -> Option<i32> {
+        self.handler.get()?.workspace_outer_gaps.get(workspace)
+    }
+
+    pub fn app_workspace(&self, app_id: &str) -> Option<Rc<String>> {
+        self.handler.get()?.app_workspaces.get(app_id)
+    }
+
+    /// Invokes a layout of the config and returns the geometries of the windows.
+    pub fn layout(&self, layout: Layout, request: LayoutRequest) -> Option<Vec<LayoutGeometry>> {
+        let handler = self.handler.get()?;
+        handler.layout_geometries.take();
+        handler.send(&ServerMessage::Layout { layout, request });
+        handler.layout_geometries.take()
+    }
+
     pub fn new_input_device(&self, dev: InputDeviceId) {
         self.send(&ServerMessage::NewInputDevice {
             device: InputDevice(dev.raw() as _),
@@ -145,6 +204,24 @@ impl ConfigProxy {
         self.send(&ServerMessage::Idle);
     }
 
+    pub fn locked(&self) {
+        self.send(&ServerMessage::Locked);
+    }
+
+    pub fn unlocked(&self) {
+        self.send(&ServerMessage::Unlocked);
+    }
+
+    pub fn locker_crashed(&self) {
+        self.send(&ServerMessage::LockerCrashed);
+    }
+
+    pub fn window_screenshot_saved(&self, path: &str) {
+        self.send(&ServerMessage::WindowScreenshotSaved {
+            path: path.to_string(),
+        });
+    }
+
     pub fn switch_event(&self, seat: SeatId, input_device: InputDeviceId, event: SwitchEvent) {
         self.send(&ServerMessage::SwitchEvent {
             seat: Seat(seat.raw() as _),
@@ -152,6 +229,35 @@ impl ConfigProxy {
             event,
         });
     }
+
+    pub fn invoke_gesture(&self, seat: SeatId, gesture: PointerGesture) {
+        self.send(&ServerMessage::InvokeGesture {
+            seat: Seat(seat.raw() as _),
+            gesture,
+        });
+    }
+
+    pub fn invoke_shortcut_sequence(&self, seat: SeatId, keys: Vec<ModifiedKeySym>) {
+        self.send(&ServerMessage::InvokeShortcutSequence {
+            seat: Seat(seat.raw() as _),
+            keys,
+        });
+    }
+
+    pub fn invoke_pointer_binding(&self, seat: SeatId, target: PointerTarget, input: PointerInput) {
+        self.send(&ServerMessage::InvokePointerBinding {
+            seat: Seat(seat.raw() as _),
+            target,
+            input,
+        });
+    }
+
+    pub fn touch_gesture(&self, seat: SeatId, gesture: TouchGesture) {
+        self.send(&ServerMessage::TouchGesture {
+            seat: Seat(seat.raw() as _),
+            gesture,
+        });
+    }
 }
 
 impl Drop for ConfigProxy {
@@ -203,6 +309,11 @@ impl ConfigProxy {
             timers_by_id: Default::default(),
             pollable_id: Default::default(),
             pollables: Default::default(),
+            workspace_layouts: Default::default(),
+            workspace_inner_gaps: Default::default(),
+            workspace_outer_gaps: Default::default(),
+            app_workspaces: Default::default(),
+            layout_geometries: Default::default(),
         });
         let init_msg = bincode_ops()
             .serialize(&InitMessage::V1(V1InitMessage {}))
@@ -3,21 +3,25 @@ use {
         async_engine::SpawnedFuture,
         backend::{
             self, ConnectorId, DrmDeviceId, InputDeviceAccelProfile, InputDeviceCapability,
-            InputDeviceId,
+            InputDeviceId, KeyboardLeds,
         },
+        capture_overlay::CaptureOverlayError,
+        client::ClientLimits,
         compositor::MAX_EXTENTS,
         config::ConfigProxy,
         format::config_formats,
         ifs::wl_seat::{SeatId, WlSeatGlobal},
         io_uring::TaskResultExt,
         kbvm::{KbvmError, KbvmMap},
+        notifications::NotificationPosition,
         output_schedule::map_cursor_hz,
         scale::Scale,
-        state::{ConnectorData, DeviceHandlerData, DrmDevData, OutputData, State},
+        screenshoter::ScreenshooterError,
+        state::{ConnectorData, DeviceHandlerData, DrmDevData, MirrorError, OutputData, State},
         theme::{Color, ThemeSized},
         tree::{
-            move_ws_to_output, ContainerNode, ContainerSplit, FloatNode, Node, NodeVisitorBase,
-            OutputNode, TearingMode, VrrMode, WsMoveConfig,
+            move_ws_to_output, ContainerNode, ContainerSplit, EmptyWorkspaceBehavior, FloatNode,
+            Node, NodeVisitorBase, OutputNode, TearingMode, VrrMode, WsMoveConfig,
         },
         utils::{
             asyncevent::AsyncEvent,
@@ -30,7 +34,9 @@ use {
             timer::{TimerError, TimerFd},
         },
     },
+    ahash::AHashSet,
     bincode::Options,
+    chrono::format::{Item, StrftimeItems},
     jay_config::{
         _private::{
             bincode_ops,
@@ -43,22 +49,32 @@ use {
                 Capability, CAP_GESTURE, CAP_KEYBOARD, CAP_POINTER, CAP_SWITCH, CAP_TABLET_PAD,
                 CAP_TABLET_TOOL, CAP_TOUCH,
             },
-            FocusFollowsMouseMode, InputDevice, Seat,
+            led::Leds,
+            FocusFollowsMouseMode, InputDevice, PointerGesture, PointerInput, PointerTarget, Seat,
+            DEFAULT_BINDING_MODE,
         },
-        keyboard::{mods::Modifiers, syms::KeySym, Keymap},
+        keyboard::{mods::Modifiers, syms::KeySym, Keymap, ModifiedKeySym},
+        layout::{Layout, LayoutGeometry},
         logging::LogLevel,
+        notifications::NotificationPosition as ConfigNotificationPosition,
         theme::{colors::Colorable, sized::Resizable},
         timer::Timer as JayTimer,
         video::{
-            Connector, DrmDevice, Format as ConfigFormat, GfxApi, TearingMode as ConfigTearingMode,
-            Transform, VrrMode as ConfigVrrMode,
+            Connector, CustomMode, DrmDevice, Format as ConfigFormat, GfxApi, NightLightSchedule,
+            TearingMode as ConfigTearingMode, Transform, VrrMode as ConfigVrrMode,
         },
         xwayland::XScalingMode,
-        Axis, Direction, Workspace,
+        Axis, Direction, EmptyWorkspaceBehavior as ConfigEmptyWorkspaceBehavior, Workspace,
     },
     libloading::Library,
     log::Level,
-    std::{cell::Cell, ops::Deref, rc::Rc, sync::Arc, time::Duration},
+    std::{
+        cell::{Cell, RefCell},
+        ops::Deref,
+        rc::Rc,
+        sync::Arc,
+        time::Duration,
+    },
     thiserror::Error,
     uapi::{c, fcntl_dupfd_cloexec, OwnedFd},
 };
@@ -86,6 +102,12 @@ pub(super) struct ConfigProxyHandler {
 
     pub pollable_id: NumCell<u64>,
     pub pollables: CopyHashMap<PollableId, Rc<Pollable>>,
+
+    pub workspace_layouts: CopyHashMap<String, Layout>,
+    pub workspace_inner_gaps: CopyHashMap<String, i32>,
+    pub workspace_outer_gaps: CopyHashMap<String, i32>,
+    pub app_workspaces: CopyHashMap<String, Rc<String>>,
+    pub layout_geometries: RefCell<Option<Vec<LayoutGeometry>>>,
 }
 
 pub struct Pollable {
@@ -111,6 +133,18 @@ impl ConfigProxyHandler {
 
         self.pollables.clear();
 
+        for name in self.workspace_layouts.clear().into_keys() {
+            self.relayout_workspace(&name);
+        }
+
+        let inner = self.workspace_inner_gaps.clear().into_keys();
+        let outer = self.workspace_outer_gaps.clear().into_keys();
+        for name in inner.chain(outer) {
+            self.workspace_gaps_changed(&name);
+        }
+
+        self.app_workspaces.clear();
+
         if let Some(path) = &self.path {
             if let Err(e) = uapi::unlink(path.as_str()) {
                 log::error!("Could not unlink {}: {}", path, ErrorFmt(OsError(e.0)));
@@ -180,7 +214,27 @@ impl ConfigProxyHandler {
     }
 
     fn handle_parse_keymap(&self, keymap: &str) -> Result<(), CphError> {
-        let (keymap, res) = match self.state.kb_ctx.parse_keymap(keymap.as_bytes()) {
+        let res = self.state.kb_ctx.parse_keymap(keymap.as_bytes());
+        self.respond_keymap(res)
+    }
+
+    fn handle_keymap_from_names(
+        &self,
+        rules: Option<&str>,
+        model: Option<&str>,
+        layout: Option<&str>,
+        variant: Option<&str>,
+        options: Option<&str>,
+    ) -> Result<(), CphError> {
+        let res = self
+            .state
+            .kb_ctx
+            .keymap_from_names(rules, model, layout, variant, options);
+        self.respond_keymap(res)
+    }
+
+    fn respond_keymap(&self, res: Result<Rc<KbvmMap>, KbvmError>) -> Result<(), CphError> {
+        let (keymap, res) = match res {
             Ok(keymap) => {
                 let id = Keymap(self.id());
                 self.keymaps.set(id, keymap);
@@ -278,6 +332,7 @@ impl ConfigProxyHandler {
             config.destroy();
             for seat in self.state.globals.seats.lock().values() {
                 seat.clear_shortcuts();
+                seat.set_binding_mode(DEFAULT_BINDING_MODE);
             }
         }
         config.configure(true);
@@ -344,6 +399,135 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_pointer_motion_coalescing(
+        &self,
+        seat: Seat,
+        enabled: bool,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_motion_coalescing(enabled);
+        Ok(())
+    }
+
+    fn handle_set_touch_gestures_enabled(&self, seat: Seat, enabled: bool) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_touch_gestures_enabled(enabled);
+        Ok(())
+    }
+
+    fn handle_add_gesture_binding(
+        &self,
+        seat: Seat,
+        gesture: PointerGesture,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.add_gesture_binding(gesture);
+        Ok(())
+    }
+
+    fn handle_remove_gesture_binding(
+        &self,
+        seat: Seat,
+        gesture: PointerGesture,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.remove_gesture_binding(gesture);
+        Ok(())
+    }
+
+    fn handle_add_shortcut_sequence(
+        &self,
+        seat: Seat,
+        keys: Vec<ModifiedKeySym>,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.add_shortcut_sequence(keys);
+        Ok(())
+    }
+
+    fn handle_remove_shortcut_sequence(
+        &self,
+        seat: Seat,
+        keys: Vec<ModifiedKeySym>,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.remove_shortcut_sequence(&keys);
+        Ok(())
+    }
+
+    fn handle_set_shortcut_sequence_timeout(
+        &self,
+        seat: Seat,
+        timeout: Duration,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_shortcut_sequence_timeout(timeout);
+        Ok(())
+    }
+
+    fn handle_set_break_out_key(&self, seat: Seat, key: ModifiedKeySym) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_break_out_key(key);
+        Ok(())
+    }
+
+    fn handle_set_gesture_passthrough(
+        &self,
+        seat: Seat,
+        app_id: &str,
+        passthrough: bool,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_gesture_passthrough(app_id, passthrough);
+        Ok(())
+    }
+
+    fn handle_set_binding_mode(&self, seat: Seat, mode: &str) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_binding_mode(mode);
+        Ok(())
+    }
+
+    fn handle_seat_screenshot_window(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.screenshot_window()?;
+        Ok(())
+    }
+
+    fn handle_seat_change_brightness(&self, seat: Seat, delta: f64) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.change_brightness(delta);
+        Ok(())
+    }
+
+    fn handle_set_workspace_swipe_fingers(&self, seat: Seat, fingers: u32) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_workspace_swipe_fingers(fingers);
+        Ok(())
+    }
+
+    fn handle_add_pointer_binding(
+        &self,
+        seat: Seat,
+        target: PointerTarget,
+        input: PointerInput,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.add_pointer_binding(target, input);
+        Ok(())
+    }
+
+    fn handle_remove_pointer_binding(
+        &self,
+        seat: Seat,
+        target: PointerTarget,
+        input: PointerInput,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.remove_pointer_binding(target, input);
+        Ok(())
+    }
+
     fn handle_set_window_management_enabled(
         &self,
         seat: Seat,
@@ -375,6 +559,94 @@ impl ConfigProxyHandler {
         self.state.set_status(status);
     }
 
+    fn handle_set_bar_clock_format(
+        &self,
+        connector: Option<Connector>,
+        format: Option<&str>,
+    ) -> Result<(), CphError> {
+        if let Some(format) = format {
+            if StrftimeItems::new(format).any(|i| i == Item::Error) {
+                return Err(CphError::InvalidClockFormat(format.to_owned()));
+            }
+        }
+        match connector {
+            Some(c) => {
+                let connector = self.get_output_node(c)?;
+                let format = format.map(|f| Rc::new(f.to_owned()));
+                connector
+                    .global
+                    .persistent
+                    .bar_clock_format
+                    .set(Some(format));
+                self.state.update_bar_clock();
+            }
+            _ => self.state.set_bar_clock_format(format),
+        }
+        Ok(())
+    }
+
+    fn handle_set_bar_show_focused_title(
+        &self,
+        connector: Option<Connector>,
+        show: bool,
+    ) -> Result<(), CphError> {
+        match connector {
+            Some(c) => {
+                let connector = self.get_output_node(c)?;
+                connector
+                    .global
+                    .persistent
+                    .bar_show_focused_title
+                    .set(Some(show));
+                connector.schedule_update_render_data();
+            }
+            _ => self.state.set_bar_show_focused_title(show),
+        }
+        Ok(())
+    }
+
+    fn handle_set_notifications_enabled(&self, enabled: bool) {
+        self.state.notifications.set_enabled(&self.state, enabled);
+    }
+
+    fn handle_set_do_not_disturb(&self, dnd: bool) {
+        self.state
+            .notifications
+            .set_do_not_disturb(&self.state, dnd);
+    }
+
+    fn handle_toggle_do_not_disturb(&self) {
+        let dnd = !self.state.notifications.do_not_disturb.get();
+        self.state
+            .notifications
+            .set_do_not_disturb(&self.state, dnd);
+    }
+
+    fn handle_set_notification_position(&self, position: ConfigNotificationPosition) {
+        let position = match position {
+            ConfigNotificationPosition::TopLeft => NotificationPosition::TopLeft,
+            ConfigNotificationPosition::TopRight => NotificationPosition::TopRight,
+            ConfigNotificationPosition::BottomLeft => NotificationPosition::BottomLeft,
+            ConfigNotificationPosition::BottomRight => NotificationPosition::BottomRight,
+        };
+        self.state.notifications.set_position(&self.state, position);
+    }
+
+    fn handle_set_notification_timeout(&self, timeout: Duration) {
+        self.state.notifications.default_timeout.set(timeout);
+    }
+
+    fn handle_dismiss_notification(&self, all: bool) {
+        match all {
+            true => self.state.notifications.dismiss_all(&self.state),
+            false => self.state.notifications.dismiss(&self.state),
+        }
+    }
+
+    fn handle_invoke_notification_action(&self) {
+        self.state.notifications.invoke_default_action(&self.state);
+    }
+
     fn get_timer(&self, timer: JayTimer) -> Result<Rc<TimerData>, CphError> {
         match self.timers_by_id.get(&timer.0) {
             Some(t) => Ok(t),
@@ -409,10 +681,10 @@ impl ConfigProxyHandler {
     fn handle_get_workspaces(&self) {
         let mut workspaces = vec![];
         for ws in self.state.workspaces.lock().values() {
-            let id = match self.workspaces_by_name.get(&ws.name) {
+            let id = match self.workspaces_by_name.get(&*ws.name.borrow()) {
                 None => {
                     let id = self.workspace_ids.fetch_add(1);
-                    let name = Rc::new(ws.name.clone());
+                    let name = Rc::new(ws.name.borrow().clone());
                     self.workspaces_by_name.set(name.clone(), id);
                     self.workspaces_by_id.set(id, name);
                     id
@@ -485,6 +757,46 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_force_quit(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.force_quit();
+        Ok(())
+    }
+
+    fn handle_mark(&self, seat: Seat, mark: &str) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.mark(mark);
+        Ok(())
+    }
+
+    fn handle_unmark(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.unmark();
+        Ok(())
+    }
+
+    fn handle_focus_mark(&self, seat: Seat, mark: &str) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.focus_mark(mark);
+        Ok(())
+    }
+
+    fn handle_swap_with_mark(&self, seat: Seat, mark: &str) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.swap_with_mark(mark);
+        Ok(())
+    }
+
+    fn handle_move_to_mark(&self, seat: Seat, mark: &str) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.move_to_mark(mark);
+        Ok(())
+    }
+
+    fn handle_set_show_marks(&self, show: bool) {
+        self.state.set_show_marks(show);
+    }
+
     fn handle_focus(&self, seat: Seat, direction: Direction) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         seat.move_focus(direction.into());
@@ -516,6 +828,25 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_seat_set_led_override(
+        &self,
+        seat: Seat,
+        leds: Leds,
+        enabled: Option<bool>,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_led_override(KeyboardLeds(leds.0) & KeyboardLeds::all(), enabled);
+        Ok(())
+    }
+
+    fn handle_seat_get_leds(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        self.respond(Response::SeatGetLeds {
+            leds: Leds(seat.leds().0),
+        });
+        Ok(())
+    }
+
     fn get_workspace(&self, ws: Workspace) -> Result<Rc<String>, CphError> {
         match self.workspaces_by_id.get(&ws.0) {
             Some(ws) => Ok(ws),
@@ -649,6 +980,84 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_button_debounce(
+        &self,
+        device: InputDevice,
+        timeout: Duration,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        dev.button_debounce
+            .timeout_usec
+            .set(timeout.as_micros().try_into().unwrap_or(u64::MAX));
+        Ok(())
+    }
+
+    fn handle_set_device_key_remap(
+        &self,
+        device: InputDevice,
+        remap: Vec<(u32, u32)>,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        *dev.key_remap.map.borrow_mut() = remap.into_iter().collect();
+        Ok(())
+    }
+
+    fn handle_set_key_remap(&self, remap: Vec<(u32, u32)>) {
+        *self.state.key_remap.borrow_mut() = remap.into_iter().collect();
+    }
+
+    fn handle_set_gamepad_resets_idle(
+        &self,
+        device: InputDevice,
+        enabled: bool,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        dev.device.set_gamepad_resets_idle(enabled);
+        Ok(())
+    }
+
+    fn handle_set_disable_while_typing_enabled(
+        &self,
+        device: InputDevice,
+        enabled: bool,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        dev.device.set_dwt_enabled(enabled);
+        Ok(())
+    }
+
+    fn handle_set_disable_while_trackpointing_enabled(
+        &self,
+        device: InputDevice,
+        enabled: bool,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        dev.device.set_dwtp_enabled(enabled);
+        Ok(())
+    }
+
+    fn handle_set_middle_button_emulation_enabled(
+        &self,
+        device: InputDevice,
+        enabled: bool,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        dev.device.set_middle_emulation_enabled(enabled);
+        Ok(())
+    }
+
+    fn handle_set_disable_while_typing_window(
+        &self,
+        device: InputDevice,
+        window: Duration,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        dev.disable_while_typing
+            .window_usec
+            .set(window.as_micros().try_into().unwrap_or(u64::MAX));
+        Ok(())
+    }
+
     fn handle_set_tap_enabled(&self, device: InputDevice, enabled: bool) -> Result<(), CphError> {
         let dev = self.get_device_handler_data(device)?;
         dev.device.set_tap_enabled(enabled);
@@ -706,6 +1115,273 @@ impl ConfigProxyHandler {
         self.state.update_ei_acceptor();
     }
 
+    fn handle_set_ei_authorization_prompt(&self, enabled: bool) {
+        self.state.ei_authorization.set_prompt_enabled(enabled);
+    }
+
+    fn handle_get_ei_permissions(&self) {
+        self.respond(Response::GetEiPermissions {
+            executables: self.state.ei_authorization.permissions(),
+        });
+    }
+
+    fn handle_revoke_ei_permission(&self, executable: &str) {
+        self.state.ei_authorization.revoke_permission(executable);
+    }
+
+    fn handle_create_virtual_output(&self, mode: WireMode) -> Result<(), CphError> {
+        let res = self
+            .state
+            .backend
+            .get()
+            .create_virtual_output(backend::Mode {
+                width: mode.width,
+                height: mode.height,
+                refresh_rate_millihz: mode.refresh_millihz,
+            });
+        let (connector, res) = match res {
+            Ok(c) => (Some(Connector(c.id().raw() as _)), Ok(())),
+            Err(e) => (None, Err(CphError::CreateVirtualOutput(e))),
+        };
+        self.respond(Response::CreateVirtualOutput { connector });
+        res
+    }
+
+    fn handle_remove_virtual_output(&self, connector: Connector) -> Result<(), CphError> {
+        self.state
+            .backend
+            .get()
+            .remove_virtual_output(ConnectorId::from_raw(connector.0 as _))
+            .map_err(CphError::RemoveVirtualOutput)
+    }
+
+    fn handle_set_jay_protocol_allowlist(&self, executables: Option<Vec<String>>) {
+        *self.state.jay_protocol_allowlist.borrow_mut() =
+            executables.map(|e| e.into_iter().collect());
+    }
+
+    fn handle_set_remember_window_state(&self, remember: bool) {
+        self.state.window_states.set_enabled(remember);
+    }
+
+    fn handle_set_window_state_exclusions(&self, app_ids: Vec<String>) {
+        self.state.window_states.set_exclusions(app_ids);
+    }
+
+    fn handle_set_capture_redactions(&self, app_ids: Vec<String>) {
+        self.state.set_capture_redactions(app_ids);
+    }
+
+    fn handle_set_capture_overlay(
+        &self,
+        text: Option<String>,
+        image: Option<String>,
+    ) -> Result<(), CphError> {
+        self.state
+            .capture_overlay
+            .set(&self.state, text, image.as_deref())?;
+        Ok(())
+    }
+
+    fn handle_set_window_screenshot_directory(&self, directory: Option<&str>) {
+        *self.state.window_screenshots.directory.borrow_mut() = directory.map(|d| d.to_string());
+    }
+
+    fn handle_set_window_screenshot_filename(&self, filename: Option<&str>) {
+        *self.state.window_screenshots.filename.borrow_mut() = filename.map(|f| f.to_string());
+    }
+
+    fn handle_set_client_limits(&self, max_fds: Option<u64>, max_memory: Option<u64>) {
+        self.state.client_limits.set(ClientLimits {
+            max_fds,
+            max_memory,
+        });
+    }
+
+    fn handle_set_outputs_powered(&self, powered: bool) {
+        self.state.set_outputs_powered(powered);
+    }
+
+    fn handle_toggle_outputs_powered(&self) {
+        self.state.toggle_outputs_powered();
+    }
+
+    fn handle_set_show_surface_regions(&self, show: bool) {
+        self.state.set_show_surface_regions(show);
+    }
+
+    fn handle_toggle_show_surface_regions(&self) {
+        let show = !self.state.show_surface_regions.get();
+        self.state.set_show_surface_regions(show);
+    }
+
+    fn handle_set_throttle_interactive_resize(&self, enabled: bool) {
+        self.state.interactive_resize.throttle.set(enabled);
+    }
+
+    fn handle_set_empty_workspace_behavior(&self, behavior: ConfigEmptyWorkspaceBehavior) {
+        self.state
+            .empty_workspace_behavior
+            .set(EmptyWorkspaceBehavior::from_config(behavior));
+    }
+
+    fn handle_set_autotiling(&self, enabled: bool) {
+        self.state.autotiling.set(enabled);
+    }
+
+    fn handle_set_night_light_schedule(&self, schedule: NightLightSchedule) {
+        self.state.night_light.set_schedule(schedule);
+    }
+
+    fn handle_set_night_light_temperature(&self, kelvin: u32) {
+        self.state.night_light.set_temperature(kelvin);
+    }
+
+    fn handle_set_night_light_transition(&self, duration: Duration) {
+        self.state.night_light.set_transition(duration);
+    }
+
+    fn handle_set_workspace_layout(
+        &self,
+        workspace: Workspace,
+        layout: Option<Layout>,
+    ) -> Result<(), CphError> {
+        let name = self.get_workspace(workspace)?;
+        match layout {
+            Some(layout) => self.workspace_layouts.set(name.to_string(), layout),
+            None => self.workspace_layouts.remove(name.as_str()),
+        };
+        self.relayout_workspace(&name);
+        Ok(())
+    }
+
+    fn handle_set_workspace_gap(
+        &self,
+        workspace: Workspace,
+        gap: Option<i32>,
+        sized: ThemeSized,
+        gaps: &CopyHashMap<String, i32>,
+    ) -> Result<(), CphError> {
+        let name = self.get_workspace(workspace)?;
+        match gap {
+            Some(gap) => {
+                if gap < sized.min() || gap > sized.max() {
+                    return Err(CphError::InvalidSize(gap, sized));
+                }
+                gaps.set(name.to_string(), gap);
+            }
+            None => {
+                gaps.remove(name.as_str());
+            }
+        }
+        self.workspace_gaps_changed(&name);
+        Ok(())
+    }
+
+    fn workspace_gaps_changed(&self, name: &str) {
+        if let Some(ws) = self.state.workspaces.get(name) {
+            ws.on_gaps_changed();
+        }
+    }
+
+    fn handle_set_layout_geometries(&self, geometries: Vec<LayoutGeometry>) {
+        *self.layout_geometries.borrow_mut() = Some(geometries);
+    }
+
+    fn handle_rename_workspace(&self, workspace: Workspace, name: &str) -> Result<(), CphError> {
+        let old = self.get_workspace(workspace)?;
+        if old.as_str() == name {
+            return Ok(());
+        }
+        if self.state.workspaces.contains(name) {
+            return Err(CphError::WorkspaceAlreadyExists(name.to_string()));
+        }
+        if let Some(ws) = self.state.workspaces.get(old.as_str()) {
+            ws.rename(name);
+        }
+        let name = Rc::new(name.to_string());
+        self.workspaces_by_name.remove(&old);
+        if !self.workspaces_by_name.contains(&name) {
+            self.workspaces_by_name.set(name.clone(), workspace.0);
+        }
+        self.workspaces_by_id.set(workspace.0, name.clone());
+        if let Some(layout) = self.workspace_layouts.remove(old.as_str()) {
+            self.workspace_layouts.set(name.to_string(), layout);
+        }
+        if let Some(gap) = self.workspace_inner_gaps.remove(old.as_str()) {
+            self.workspace_inner_gaps.set(name.to_string(), gap);
+        }
+        if let Some(gap) = self.workspace_outer_gaps.remove(old.as_str()) {
+            self.workspace_outer_gaps.set(name.to_string(), gap);
+        }
+        for ws in self.app_workspaces.lock().values_mut() {
+            if *ws == old {
+                *ws = name.clone();
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_create_workspace(
+        &self,
+        workspace: Workspace,
+        connector: Connector,
+    ) -> Result<(), CphError> {
+        let name = self.get_workspace(workspace)?;
+        let output = self.get_output_node(connector)?;
+        if !self.state.workspaces.contains(name.as_str()) {
+            output.create_workspace(&name);
+        }
+        Ok(())
+    }
+
+    fn handle_set_app_workspace(
+        &self,
+        app_id: &str,
+        workspace: Option<Workspace>,
+    ) -> Result<(), CphError> {
+        match workspace {
+            Some(ws) => {
+                let name = self.get_workspace(ws)?;
+                self.app_workspaces.set(app_id.to_string(), name);
+            }
+            None => {
+                self.app_workspaces.remove(app_id);
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_relayout(&self, layout: Layout) {
+        let names: Vec<_> = self
+            .workspace_layouts
+            .lock()
+            .iter()
+            .filter(|(_, l)| **l == layout)
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in names {
+            self.relayout_workspace(&name);
+        }
+    }
+
+    fn relayout_workspace(&self, name: &str) {
+        if let Some(ws) = self.state.workspaces.get(name) {
+            if let Some(container) = ws.container.get() {
+                container.schedule_layout();
+            }
+        }
+    }
+
+    fn handle_set_presentation_output(&self, connector: Option<Connector>) -> Result<(), CphError> {
+        let output = match connector {
+            Some(c) => Some(self.get_output_node(c)?),
+            None => None,
+        };
+        self.state.set_presentation_output(output.as_deref());
+        Ok(())
+    }
+
     fn handle_get_workspace(&self, name: &str) {
         let name = Rc::new(name.to_owned());
         let ws = match self.workspaces_by_name.get(&name) {
@@ -820,7 +1496,7 @@ impl ConfigProxyHandler {
         let mut workspace = 0;
         if !output.is_dummy {
             if let Some(ws) = output.workspace.get() {
-                if let Some(ws) = self.workspaces_by_name.get(&ws.name) {
+                if let Some(ws) = self.workspaces_by_name.get(&*ws.name.borrow()) {
                     workspace = ws;
                 }
             }
@@ -834,10 +1510,28 @@ impl ConfigProxyHandler {
     fn handle_show_workspace(&self, seat: Seat, ws: Workspace) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         let name = self.get_workspace(ws)?;
+        if self.state.workspace_back_and_forth.get() {
+            let current = seat.get_output().workspace.get();
+            if let Some(current) = current {
+                if *current.name.borrow() == *name && self.state.show_previous_workspace(&seat) {
+                    return Ok(());
+                }
+            }
+        }
         self.state.show_workspace(&seat, &name);
         Ok(())
     }
 
+    fn handle_show_previous_workspace(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        self.state.show_previous_workspace(&seat);
+        Ok(())
+    }
+
+    fn handle_set_workspace_back_and_forth(&self, enabled: bool) {
+        self.state.workspace_back_and_forth.set(enabled);
+    }
+
     fn handle_set_workspace(&self, seat: Seat, ws: Workspace) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         let name = self.get_workspace(ws)?;
@@ -874,6 +1568,32 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_get_input_device_ids(&self, device: InputDevice) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        self.respond(Response::GetInputDeviceIds {
+            vendor: dev.device.vendor_id().unwrap_or_default(),
+            product: dev.device.product_id().unwrap_or_default(),
+        });
+        Ok(())
+    }
+
+    fn handle_set_input_device_enabled(
+        &self,
+        device: InputDevice,
+        enabled: bool,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        dev.device.set_events_enabled(enabled);
+        Ok(())
+    }
+
+    fn handle_get_input_device_enabled(&self, device: InputDevice) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        let enabled = dev.device.events_enabled().unwrap_or(true);
+        self.respond(Response::GetInputDeviceEnabled { enabled });
+        Ok(())
+    }
+
     fn handle_move_to_output(
         &self,
         workspace: WorkspaceSource,
@@ -923,10 +1643,35 @@ impl ConfigProxyHandler {
         self.state.idle.set_grace_period(period);
     }
 
+    fn handle_set_lock_grace_period(&self, period: Duration) {
+        self.state.lock.grace_period.set(period);
+    }
+
+    fn handle_set_fallback_lock_screen_prompt(&self, enabled: bool) {
+        self.state
+            .lock
+            .fallback
+            .set_prompt_enabled(&self.state, enabled);
+    }
+
+    fn handle_set_idle_inhibit_on_audio(&self, enabled: bool, roles: Vec<String>) {
+        let audio = &self.state.idle.audio;
+        audio.set_roles(roles);
+        audio.set_enabled(&self.state, enabled);
+    }
+
     fn handle_set_explicit_sync_enabled(&self, enabled: bool) {
         self.state.explicit_sync_enabled.set(enabled);
     }
 
+    fn handle_set_force_implicit_sync(&self, force: bool) {
+        self.state.force_implicit_sync.set(force);
+    }
+
+    fn handle_set_activation_token_lifetime(&self, lifetime: Duration) {
+        self.state.activation_token_lifetime.set(lifetime);
+    }
+
     fn handle_get_socket_path(&self) {
         match self.state.acceptor.get() {
             Some(a) => {
@@ -981,6 +1726,47 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_connector_set_custom_mode(
+        &self,
+        connector: Connector,
+        mode: CustomMode,
+    ) -> Result<(), CphError> {
+        let connector = self.get_output(connector)?;
+        connector
+            .connector
+            .connector
+            .set_custom_mode(&mode)
+            .map_err(CphError::CustomMode)
+    }
+
+    fn handle_connector_get_brightness(&self, connector: Connector) -> Result<(), CphError> {
+        let connector = self.get_output(connector)?;
+        self.respond(Response::ConnectorGetBrightness {
+            brightness: self.state.brightness(&connector.connector),
+        });
+        Ok(())
+    }
+
+    fn handle_connector_set_brightness(
+        &self,
+        connector: Connector,
+        brightness: f64,
+    ) -> Result<(), CphError> {
+        let connector = self.get_output(connector)?;
+        self.state.set_brightness(&connector.connector, brightness);
+        Ok(())
+    }
+
+    fn handle_connector_change_brightness(
+        &self,
+        connector: Connector,
+        delta: f64,
+    ) -> Result<(), CphError> {
+        let connector = self.get_output(connector)?;
+        self.state.change_brightness(&connector.connector, delta);
+        Ok(())
+    }
+
     fn handle_connector_modes(&self, connector: Connector) -> Result<(), CphError> {
         let connector = self.get_output_node(connector)?;
         self.respond(Response::ConnectorModes {
@@ -1039,6 +1825,20 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_seat_outputs(
+        &self,
+        seat: Seat,
+        connectors: Vec<Connector>,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        let mut outputs = AHashSet::new();
+        for connector in connectors {
+            outputs.insert(self.get_connector(connector)?.name.clone());
+        }
+        seat.set_outputs(outputs);
+        Ok(())
+    }
+
     fn handle_disable_pointer_constraint(&self, seat: Seat) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         seat.disable_pointer_constraint();
@@ -1100,6 +1900,40 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_connector_set_render_margin(
+        &self,
+        connector: Connector,
+        margin: Duration,
+    ) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        connector
+            .connector
+            .set_render_margin(margin.as_nanos().try_into().unwrap_or(u64::MAX));
+        Ok(())
+    }
+
+    fn handle_connector_set_hdr_enabled(
+        &self,
+        connector: Connector,
+        enabled: bool,
+    ) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        connector.connector.set_hdr_enabled(enabled);
+        Ok(())
+    }
+
+    fn handle_connector_set_mirror(
+        &self,
+        connector: Connector,
+        source: Option<Connector>,
+    ) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        let source = source.map(|s| self.get_connector(s)).transpose()?;
+        self.state
+            .set_mirror_source(&connector, source.as_deref())?;
+        Ok(())
+    }
+
     fn handle_set_vrr_mode(
         &self,
         connector: Option<Connector>,
@@ -1493,6 +2327,7 @@ impl ConfigProxyHandler {
             }
         }
         self.state.root.clone().node_visit(&mut V);
+        self.state.notifications.render_textures(&self.state);
         self.state.damage(self.state.root.extents.get());
     }
 
@@ -1509,6 +2344,7 @@ impl ConfigProxyHandler {
             }
         }
         self.state.root.clone().node_visit(&mut V);
+        self.state.notifications.render_textures(&self.state);
         self.state.damage(self.state.root.extents.get());
     }
 
@@ -1517,6 +2353,8 @@ impl ConfigProxyHandler {
         let sized = match sized {
             TITLE_HEIGHT => ThemeSized::title_height,
             BORDER_WIDTH => ThemeSized::border_width,
+            INNER_GAP => ThemeSized::inner_gap,
+            OUTER_GAP => ThemeSized::outer_gap,
             _ => return Err(CphError::UnknownSized(sized.0)),
         };
         Ok(sized)
@@ -1589,6 +2427,9 @@ impl ConfigProxyHandler {
             BAR_STATUS_TEXT_COLOR => &colors.bar_text,
             ATTENTION_REQUESTED_BACKGROUND_COLOR => &colors.attention_requested_background,
             HIGHLIGHT_COLOR => &colors.highlight,
+            NOTIFICATION_BACKGROUND_COLOR => &colors.notification_background,
+            NOTIFICATION_BORDER_COLOR => &colors.notification_border,
+            NOTIFICATION_TEXT_COLOR => &colors.notification_text,
             _ => return Err(CphError::UnknownColor(colorable.0)),
         };
         Ok(colorable)
@@ -1987,6 +2828,293 @@ impl ConfigProxyHandler {
             ClientMessage::SetIdleGracePeriod { period } => {
                 self.handle_set_idle_grace_period(period)
             }
+            ClientMessage::SetTouchGesturesEnabled { seat, enabled } => self
+                .handle_set_touch_gestures_enabled(seat, enabled)
+                .wrn("set_touch_gestures_enabled")?,
+            ClientMessage::AddGestureBinding { seat, gesture } => self
+                .handle_add_gesture_binding(seat, gesture)
+                .wrn("add_gesture_binding")?,
+            ClientMessage::RemoveGestureBinding { seat, gesture } => self
+                .handle_remove_gesture_binding(seat, gesture)
+                .wrn("remove_gesture_binding")?,
+            ClientMessage::SetGesturePassthrough {
+                seat,
+                app_id,
+                passthrough,
+            } => self
+                .handle_set_gesture_passthrough(seat, app_id, passthrough)
+                .wrn("set_gesture_passthrough")?,
+            ClientMessage::AddPointerBinding {
+                seat,
+                target,
+                input,
+            } => self
+                .handle_add_pointer_binding(seat, target, input)
+                .wrn("add_pointer_binding")?,
+            ClientMessage::RemovePointerBinding {
+                seat,
+                target,
+                input,
+            } => self
+                .handle_remove_pointer_binding(seat, target, input)
+                .wrn("remove_pointer_binding")?,
+            ClientMessage::SetBarClockFormat { connector, format } => self
+                .handle_set_bar_clock_format(connector, format)
+                .wrn("set_bar_clock_format")?,
+            ClientMessage::SetBarShowFocusedTitle { connector, show } => self
+                .handle_set_bar_show_focused_title(connector, show)
+                .wrn("set_bar_show_focused_title")?,
+            ClientMessage::SetNotificationsEnabled { enabled } => {
+                self.handle_set_notifications_enabled(enabled)
+            }
+            ClientMessage::SetDoNotDisturb { dnd } => self.handle_set_do_not_disturb(dnd),
+            ClientMessage::ToggleDoNotDisturb => self.handle_toggle_do_not_disturb(),
+            ClientMessage::SetNotificationPosition { position } => {
+                self.handle_set_notification_position(position)
+            }
+            ClientMessage::SetNotificationTimeout { timeout } => {
+                self.handle_set_notification_timeout(timeout)
+            }
+            ClientMessage::DismissNotification { all } => self.handle_dismiss_notification(all),
+            ClientMessage::InvokeNotificationAction => self.handle_invoke_notification_action(),
+            ClientMessage::SetJayProtocolAllowlist { executables } => {
+                self.handle_set_jay_protocol_allowlist(executables)
+            }
+            ClientMessage::SetRememberWindowState { remember } => {
+                self.handle_set_remember_window_state(remember)
+            }
+            ClientMessage::SetWindowStateExclusions { app_ids } => {
+                self.handle_set_window_state_exclusions(app_ids)
+            }
+            ClientMessage::SetOutputsPowered { powered } => {
+                self.handle_set_outputs_powered(powered)
+            }
+            ClientMessage::ToggleOutputsPowered => self.handle_toggle_outputs_powered(),
+            ClientMessage::SetPresentationOutput { connector } => self
+                .handle_set_presentation_output(connector)
+                .wrn("set_presentation_output")?,
+            ClientMessage::AddShortcutSequence { seat, keys } => self
+                .handle_add_shortcut_sequence(seat, keys)
+                .wrn("add_shortcut_sequence")?,
+            ClientMessage::RemoveShortcutSequence { seat, keys } => self
+                .handle_remove_shortcut_sequence(seat, keys)
+                .wrn("remove_shortcut_sequence")?,
+            ClientMessage::SetShortcutSequenceTimeout { seat, timeout } => self
+                .handle_set_shortcut_sequence_timeout(seat, timeout)
+                .wrn("set_shortcut_sequence_timeout")?,
+            ClientMessage::SetBreakOutKey { seat, key } => self
+                .handle_set_break_out_key(seat, key)
+                .wrn("set_break_out_key")?,
+            ClientMessage::SetShowSurfaceRegions { show } => {
+                self.handle_set_show_surface_regions(show)
+            }
+            ClientMessage::ToggleShowSurfaceRegions => self.handle_toggle_show_surface_regions(),
+            ClientMessage::SetThrottleInteractiveResize { enabled } => {
+                self.handle_set_throttle_interactive_resize(enabled)
+            }
+            ClientMessage::SetButtonDebounce { device, timeout } => self
+                .handle_set_button_debounce(device, timeout)
+                .wrn("set_button_debounce")?,
+            ClientMessage::SetGamepadResetsIdle { device, enabled } => self
+                .handle_set_gamepad_resets_idle(device, enabled)
+                .wrn("set_gamepad_resets_idle")?,
+            ClientMessage::SetDisableWhileTypingEnabled { device, enabled } => self
+                .handle_set_disable_while_typing_enabled(device, enabled)
+                .wrn("set_disable_while_typing_enabled")?,
+            ClientMessage::SetDisableWhileTrackpointingEnabled { device, enabled } => self
+                .handle_set_disable_while_trackpointing_enabled(device, enabled)
+                .wrn("set_disable_while_trackpointing_enabled")?,
+            ClientMessage::SetDisableWhileTypingWindow { device, window } => self
+                .handle_set_disable_while_typing_window(device, window)
+                .wrn("set_disable_while_typing_window")?,
+            ClientMessage::GetInputDeviceIds { device } => self
+                .handle_get_input_device_ids(device)
+                .wrn("get_input_device_ids")?,
+            ClientMessage::SetInputDeviceEnabled { device, enabled } => self
+                .handle_set_input_device_enabled(device, enabled)
+                .wrn("set_input_device_enabled")?,
+            ClientMessage::SeatSetLedOverride {
+                seat,
+                leds,
+                enabled,
+            } => self
+                .handle_seat_set_led_override(seat, leds, enabled)
+                .wrn("seat_set_led_override")?,
+            ClientMessage::SeatGetLeds { seat } => {
+                self.handle_seat_get_leds(seat).wrn("seat_get_leds")?
+            }
+            ClientMessage::SetIdleInhibitOnAudio { enabled, roles } => {
+                self.handle_set_idle_inhibit_on_audio(enabled, roles)
+            }
+            ClientMessage::SetLockGracePeriod { period } => {
+                self.handle_set_lock_grace_period(period)
+            }
+            ClientMessage::SetFallbackLockScreenPrompt { enabled } => {
+                self.handle_set_fallback_lock_screen_prompt(enabled)
+            }
+            ClientMessage::SetCaptureRedactions { app_ids } => {
+                self.handle_set_capture_redactions(app_ids)
+            }
+            ClientMessage::SetCaptureOverlay { text, image } => self
+                .handle_set_capture_overlay(text, image)
+                .wrn("set_capture_overlay")?,
+            ClientMessage::ConnectorSetHdrEnabled { connector, enabled } => self
+                .handle_connector_set_hdr_enabled(connector, enabled)
+                .wrn("connector_set_hdr_enabled")?,
+            ClientMessage::SetEiAuthorizationPrompt { enabled } => {
+                self.handle_set_ei_authorization_prompt(enabled)
+            }
+            ClientMessage::GetEiPermissions => self.handle_get_ei_permissions(),
+            ClientMessage::RevokeEiPermission { executable } => {
+                self.handle_revoke_ei_permission(executable)
+            }
+            ClientMessage::CreateVirtualOutput { mode } => self
+                .handle_create_virtual_output(mode)
+                .wrn("create_virtual_output")?,
+            ClientMessage::RemoveVirtualOutput { connector } => self
+                .handle_remove_virtual_output(connector)
+                .wrn("remove_virtual_output")?,
+            ClientMessage::GetInputDeviceEnabled { device } => self
+                .handle_get_input_device_enabled(device)
+                .wrn("get_input_device_enabled")?,
+            ClientMessage::ConnectorSetRenderMargin { connector, margin } => self
+                .handle_connector_set_render_margin(connector, margin)
+                .wrn("connector_set_render_margin")?,
+            ClientMessage::SetEmptyWorkspaceBehavior { behavior } => {
+                self.handle_set_empty_workspace_behavior(behavior)
+            }
+            ClientMessage::SetAutotiling { enabled } => self.handle_set_autotiling(enabled),
+            ClientMessage::SetNightLightSchedule { schedule } => {
+                self.handle_set_night_light_schedule(schedule)
+            }
+            ClientMessage::SetNightLightTemperature { kelvin } => {
+                self.handle_set_night_light_temperature(kelvin)
+            }
+            ClientMessage::SetNightLightTransition { duration } => {
+                self.handle_set_night_light_transition(duration)
+            }
+            ClientMessage::SetWorkspaceLayout { workspace, layout } => self
+                .handle_set_workspace_layout(workspace, layout)
+                .wrn("set_workspace_layout")?,
+            ClientMessage::SetLayoutGeometries { geometries } => {
+                self.handle_set_layout_geometries(geometries)
+            }
+            ClientMessage::ConnectorSetMirror { connector, source } => self
+                .handle_connector_set_mirror(connector, source)
+                .wrn("connector_set_mirror")?,
+            ClientMessage::SetWorkspaceSwipeFingers { seat, fingers } => self
+                .handle_set_workspace_swipe_fingers(seat, fingers)
+                .wrn("set_workspace_swipe_fingers")?,
+            ClientMessage::SeatScreenshotWindow { seat } => self
+                .handle_seat_screenshot_window(seat)
+                .wrn("seat_screenshot_window")?,
+            ClientMessage::SetWindowScreenshotDirectory { directory } => {
+                self.handle_set_window_screenshot_directory(directory)
+            }
+            ClientMessage::SetWindowScreenshotFilename { filename } => {
+                self.handle_set_window_screenshot_filename(filename)
+            }
+            ClientMessage::ConnectorSetCustomMode { connector, mode } => self
+                .handle_connector_set_custom_mode(connector, mode)
+                .wrn("connector_set_custom_mode")?,
+            ClientMessage::ConnectorGetBrightness { connector } => self
+                .handle_connector_get_brightness(connector)
+                .wrn("connector_get_brightness")?,
+            ClientMessage::ConnectorSetBrightness {
+                connector,
+                brightness,
+            } => self
+                .handle_connector_set_brightness(connector, brightness)
+                .wrn("connector_set_brightness")?,
+            ClientMessage::ConnectorChangeBrightness { connector, delta } => self
+                .handle_connector_change_brightness(connector, delta)
+                .wrn("connector_change_brightness")?,
+            ClientMessage::SeatChangeBrightness { seat, delta } => self
+                .handle_seat_change_brightness(seat, delta)
+                .wrn("seat_change_brightness")?,
+            ClientMessage::SetClientLimits {
+                max_fds,
+                max_memory,
+            } => self.handle_set_client_limits(max_fds, max_memory),
+            ClientMessage::SetPointerMotionCoalescing { seat, enabled } => self
+                .handle_set_pointer_motion_coalescing(seat, enabled)
+                .wrn("set_pointer_motion_coalescing")?,
+            ClientMessage::SetMiddleButtonEmulationEnabled { device, enabled } => self
+                .handle_set_middle_button_emulation_enabled(device, enabled)
+                .wrn("set_middle_button_emulation_enabled")?,
+            ClientMessage::KeymapFromNames {
+                rules,
+                model,
+                layout,
+                variant,
+                options,
+            } => self
+                .handle_keymap_from_names(rules, model, layout, variant, options)
+                .wrn("keymap_from_names")?,
+            ClientMessage::SetBindingMode { seat, mode } => self
+                .handle_set_binding_mode(seat, mode)
+                .wrn("set_binding_mode")?,
+            ClientMessage::SetForceImplicitSync { force } => {
+                self.handle_set_force_implicit_sync(force)
+            }
+            ClientMessage::SetActivationTokenLifetime { lifetime } => {
+                self.handle_set_activation_token_lifetime(lifetime)
+            }
+            ClientMessage::ForceQuit { seat } => self.handle_force_quit(seat).wrn("force_quit")?,
+            ClientMessage::SetDeviceKeyRemap { device, remap } => self
+                .handle_set_device_key_remap(device, remap)
+                .wrn("set_device_key_remap")?,
+            ClientMessage::SetKeyRemap { remap } => self.handle_set_key_remap(remap),
+            ClientMessage::SetSeatOutputs { seat, connectors } => self
+                .handle_set_seat_outputs(seat, connectors)
+                .wrn("set_seat_outputs")?,
+            ClientMessage::Relayout { layout } => self.handle_relayout(layout),
+            ClientMessage::RenameWorkspace { workspace, name } => self
+                .handle_rename_workspace(workspace, name)
+                .wrn("rename_workspace")?,
+            ClientMessage::CreateWorkspace {
+                workspace,
+                connector,
+            } => self
+                .handle_create_workspace(workspace, connector)
+                .wrn("create_workspace")?,
+            ClientMessage::SetAppWorkspace { app_id, workspace } => self
+                .handle_set_app_workspace(app_id, workspace)
+                .wrn("set_app_workspace")?,
+            ClientMessage::ShowPreviousWorkspace { seat } => self
+                .handle_show_previous_workspace(seat)
+                .wrn("show_previous_workspace")?,
+            ClientMessage::SetWorkspaceBackAndForth { enabled } => {
+                self.handle_set_workspace_back_and_forth(enabled)
+            }
+            ClientMessage::Mark { seat, mark } => self.handle_mark(seat, mark).wrn("mark")?,
+            ClientMessage::Unmark { seat } => self.handle_unmark(seat).wrn("unmark")?,
+            ClientMessage::FocusMark { seat, mark } => {
+                self.handle_focus_mark(seat, mark).wrn("focus_mark")?
+            }
+            ClientMessage::SwapWithMark { seat, mark } => self
+                .handle_swap_with_mark(seat, mark)
+                .wrn("swap_with_mark")?,
+            ClientMessage::MoveToMark { seat, mark } => {
+                self.handle_move_to_mark(seat, mark).wrn("move_to_mark")?
+            }
+            ClientMessage::SetShowMarks { show } => self.handle_set_show_marks(show),
+            ClientMessage::SetWorkspaceInnerGap { workspace, gap } => self
+                .handle_set_workspace_gap(
+                    workspace,
+                    gap,
+                    ThemeSized::inner_gap,
+                    &self.workspace_inner_gaps,
+                )
+                .wrn("set_workspace_inner_gap")?,
+            ClientMessage::SetWorkspaceOuterGap { workspace, gap } => self
+                .handle_set_workspace_gap(
+                    workspace,
+                    gap,
+                    ThemeSized::outer_gap,
+                    &self.workspace_outer_gaps,
+                )
+                .wrn("set_workspace_outer_gap")?,
         }
         Ok(())
     }
@@ -2028,6 +3156,8 @@ enum CphError {
     DrmDeviceDoesNotExist(DrmDevice),
     #[error("Workspace {0:?} does not exist")]
     WorkspaceDoesNotExist(Workspace),
+    #[error("A workspace called {0} already exists")]
+    WorkspaceAlreadyExists(String),
     #[error("Keyboard {0:?} does not exist")]
     KeyboardDoesNotExist(InputDevice),
     #[error("Colorable element {0} is not known")]
@@ -2052,12 +3182,26 @@ enum CphError {
     UnknownVrrMode(ConfigVrrMode),
     #[error("Invalid cursor hz {0}")]
     InvalidCursorHz(f64),
+    #[error("Invalid clock format {0:?}")]
+    InvalidClockFormat(String),
     #[error("Unknown tearing mode {0:?}")]
     UnknownTearingMode(ConfigTearingMode),
     #[error("The format {0:?} is unknown")]
     UnknownFormat(ConfigFormat),
     #[error("Unknown x scaling mode {0:?}")]
     UnknownXScalingMode(XScalingMode),
+    #[error("Could not load the capture overlay image")]
+    CaptureOverlay(#[from] CaptureOverlayError),
+    #[error("Could not create a virtual output")]
+    CreateVirtualOutput(#[source] Box<dyn std::error::Error>),
+    #[error("Could not remove a virtual output")]
+    RemoveVirtualOutput(#[source] Box<dyn std::error::Error>),
+    #[error(transparent)]
+    MirrorError(#[from] MirrorError),
+    #[error("Could not take a window screenshot")]
+    WindowScreenshot(#[from] ScreenshooterError),
+    #[error("Could not set a custom mode")]
+    CustomMode(#[source] Box<dyn std::error::Error>),
 }
 
 trait WithRequestName {
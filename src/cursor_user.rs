@@ -13,7 +13,12 @@ use {
             hash_map_ext::HashMapExt, rc_eq::rc_eq, transform_ext::TransformExt,
         },
     },
-    std::{cell::Cell, ops::Deref, rc::Rc},
+    ahash::AHashSet,
+    std::{
+        cell::{Cell, RefCell},
+        ops::Deref,
+        rc::Rc,
+    },
 };
 
 linear_ids!(CursorUserGroupIds, CursorUserGroupId, u64);
@@ -32,6 +37,7 @@ pub struct CursorUserGroup {
     hardware_cursor: Cell<bool>,
     size: Cell<u32>,
     latest_output: CloneCell<Rc<OutputNode>>,
+    outputs: RefCell<AHashSet<String>>,
 }
 
 pub struct CursorUser {
@@ -66,6 +72,7 @@ impl CursorUserGroup {
             hardware_cursor: Cell::new(hardware_cursor),
             size: Cell::new(*DEFAULT_CURSOR_SIZE),
             latest_output: CloneCell::new(output),
+            outputs: Default::default(),
         });
         state.add_cursor_size(*DEFAULT_CURSOR_SIZE);
         state.cursor_user_groups.set(group.id, group.clone());
@@ -198,6 +205,26 @@ impl CursorUserGroup {
         }
     }
 
+    /// Restricts the cursors to the outputs with the given connector names.
+    ///
+    /// If the set is empty, the cursors can move across all outputs.
+    pub fn set_outputs(&self, outputs: AHashSet<String>) {
+        *self.outputs.borrow_mut() = outputs;
+        for user in self.users.lock().values() {
+            let (x, y) = user.pos.get();
+            user.set_position(x, y);
+        }
+    }
+
+    pub fn outputs(&self) -> Vec<String> {
+        self.outputs.borrow().iter().cloned().collect()
+    }
+
+    fn allows_output(&self, output: &OutputNode) -> bool {
+        let outputs = self.outputs.borrow();
+        outputs.is_empty() || outputs.contains(&output.global.connector.name)
+    }
+
     fn output_center(&self, output: &Rc<OutputNode>) -> (Fixed, Fixed) {
         let pos = output.global.pos.get();
         let x = Fixed::from_int((pos.x1() + pos.x2()) / 2);
@@ -401,8 +428,13 @@ impl CursorUser {
     pub fn set_position(&self, mut x: Fixed, mut y: Fixed) -> (Fixed, Fixed) {
         let x_int = x.round_down();
         let y_int = y.round_down();
-        if !self.output_pos.get().contains(x_int, y_int) {
-            let (output, x_tmp, y_tmp) = self.group.state.find_closest_output(x_int, y_int);
+        if !self.output_pos.get().contains(x_int, y_int)
+            || !self.group.allows_output(&self.output.get())
+        {
+            let (output, x_tmp, y_tmp) =
+                self.group
+                    .state
+                    .find_closest_output_where(x_int, y_int, |o| self.group.allows_output(o));
             self.set_output(&output);
             x = x.apply_fract(x_tmp);
             y = y.apply_fract(y_tmp);
@@ -447,6 +479,7 @@ impl CursorUser {
                 if render {
                     output.hardware_cursor_needs_render.set(true);
                 }
+                output.damage_screencopies(None);
                 let defer = output.schedule.defer_cursor_updates();
                 if defer {
                     output.schedule.hardware_cursor_changed();
@@ -106,6 +106,10 @@ impl DamageVisualizer {
         }
     }
 
+    pub fn enabled(&self) -> bool {
+        self.enabled.get()
+    }
+
     pub fn set_enabled(&self, state: &State, enabled: bool) {
         self.enabled.set(enabled);
         if !enabled {
@@ -0,0 +1,184 @@
+use {
+    crate::{
+        allocator::{AllocatorError, BufferObject, BO_USE_LINEAR, BO_USE_RENDERING},
+        gfx_api::{
+            needs_render_usage, AcquireSync, GfxContext, GfxError, GfxFramebuffer, GfxImage,
+            GfxTexture, ReleaseSync, SyncFile,
+        },
+        state::State,
+        utils::errorfmt::ErrorFmt,
+        video::{dmabuf::DmaBuf, Modifier},
+    },
+    indexmap::IndexMap,
+    std::{cell::RefCell, rc::Rc},
+    thiserror::Error,
+};
+
+/// Copies a dmabuf that can only be accessed by a secondary GPU into a buffer that can
+/// be sampled by the render device.
+pub struct DmabufBridge {
+    // ctx = source
+    // buffer location = client
+    src_tex: Rc<dyn GfxTexture>,
+    // ctx = source
+    // buffer location = source
+    bridge_fb: Rc<dyn GfxFramebuffer>,
+    _bridge_bo: Rc<dyn BufferObject>,
+    sync_file: RefCell<Option<SyncFile>>,
+}
+
+#[derive(Debug, Error)]
+enum DmabufBridgeError {
+    #[error("The source device does not support the format {0}")]
+    MissingSourceFormat(&'static str),
+    #[error("The render device does not support the format {0}")]
+    MissingRenderFormat(&'static str),
+    #[error("The devices have no common modifier for the format {0}")]
+    MissingModifier(&'static str),
+    #[error("Could not allocate the bridge buffer")]
+    Allocate(#[source] AllocatorError),
+    #[error("Could not import the client buffer into the source device")]
+    ImportSource(#[source] GfxError),
+    #[error("Could not import the bridge buffer into the source device")]
+    ImportBridge(#[source] GfxError),
+    #[error("Could not import the bridge buffer into the render device")]
+    ImportRender(#[source] GfxError),
+}
+
+/// Imports a client dmabuf into the render context.
+///
+/// If the render context cannot import the dmabuf, it is imported into the context of
+/// another DRM device and the returned image is a copy on the render device that must be
+/// refreshed with [`DmabufBridge::copy`].
+pub fn import_dmabuf(
+    state: &State,
+    ctx: &Rc<dyn GfxContext>,
+    dmabuf: &DmaBuf,
+) -> Result<(Rc<dyn GfxImage>, Option<Rc<DmabufBridge>>), GfxError> {
+    let e = match ctx.clone().dmabuf_img(dmabuf) {
+        Ok(img) => return Ok((img, None)),
+        Err(e) => e,
+    };
+    for (devnode, src_ctx) in secondary_contexts(state) {
+        let Ok(src_img) = src_ctx.clone().dmabuf_img(dmabuf) else {
+            continue;
+        };
+        match create_bridge(state, ctx, &src_ctx, src_img) {
+            Ok((img, bridge)) => return Ok((img, Some(Rc::new(bridge)))),
+            Err(e) => {
+                log::warn!(
+                    "Could not create a bridge from {:?}: {}",
+                    devnode,
+                    ErrorFmt(e),
+                );
+            }
+        }
+    }
+    Err(e)
+}
+
+/// Returns whether the render context or the context of another DRM device can import
+/// dmabufs with this format and modifier.
+pub fn is_importable(
+    state: &State,
+    ctx: &Rc<dyn GfxContext>,
+    format: u32,
+    modifier: Modifier,
+) -> bool {
+    let supports = |ctx: &Rc<dyn GfxContext>| match ctx.formats().get(&format) {
+        Some(f) => f.read_modifiers.contains(&modifier),
+        None => false,
+    };
+    supports(ctx) || secondary_contexts(state).iter().any(|(_, c)| supports(c))
+}
+
+fn secondary_contexts(state: &State) -> Vec<(Option<String>, Rc<dyn GfxContext>)> {
+    let mut res = vec![];
+    for dev in state.drm_devs.lock().values() {
+        if dev.dev.is_render_device() {
+            continue;
+        }
+        if let Some(ctx) = dev.dev.gfx_ctx() {
+            res.push((dev.devnode.clone(), ctx));
+        }
+    }
+    res
+}
+
+fn create_bridge(
+    state: &State,
+    render_ctx: &Rc<dyn GfxContext>,
+    src_ctx: &Rc<dyn GfxContext>,
+    src_img: Rc<dyn GfxImage>,
+) -> Result<(Rc<dyn GfxImage>, DmabufBridge), DmabufBridgeError> {
+    let (width, height) = (src_img.width(), src_img.height());
+    let src_tex = src_img
+        .to_texture()
+        .map_err(DmabufBridgeError::ImportSource)?;
+    let format = src_tex.format();
+    let src_formats = src_ctx.formats();
+    let Some(src_format) = src_formats.get(&format.drm) else {
+        return Err(DmabufBridgeError::MissingSourceFormat(format.name));
+    };
+    let render_formats = render_ctx.formats();
+    let Some(render_format) = render_formats.get(&format.drm) else {
+        return Err(DmabufBridgeError::MissingRenderFormat(format.name));
+    };
+    let possible_modifiers: IndexMap<_, _> = src_format
+        .write_modifiers
+        .iter()
+        .filter(|(m, _)| render_format.read_modifiers.contains(*m))
+        .collect();
+    if possible_modifiers.is_empty() {
+        return Err(DmabufBridgeError::MissingModifier(format.name));
+    }
+    let mut usage = BO_USE_RENDERING | BO_USE_LINEAR;
+    if !needs_render_usage(possible_modifiers.values().copied()) {
+        usage = BO_USE_LINEAR;
+    }
+    let modifiers: Vec<_> = possible_modifiers.keys().copied().copied().collect();
+    let bridge_bo = src_ctx
+        .allocator()
+        .create_bo(&state.dma_buf_ids, width, height, format, &modifiers, usage)
+        .map_err(DmabufBridgeError::Allocate)?;
+    let bridge_fb = src_ctx
+        .clone()
+        .dmabuf_fb(bridge_bo.dmabuf())
+        .map_err(DmabufBridgeError::ImportBridge)?;
+    let render_img = render_ctx
+        .clone()
+        .dmabuf_img(bridge_bo.dmabuf())
+        .map_err(DmabufBridgeError::ImportRender)?;
+    let bridge = DmabufBridge {
+        src_tex,
+        bridge_fb,
+        _bridge_bo: bridge_bo,
+        sync_file: Default::default(),
+    };
+    Ok((render_img, bridge))
+}
+
+impl DmabufBridge {
+    /// Copies the current contents of the client buffer to the render device.
+    ///
+    /// `acquire_sync` is the synchronization the client attached to the buffer.
+    pub fn copy(&self, acquire_sync: AcquireSync) -> Result<(), GfxError> {
+        let sync_file = self.bridge_fb.copy_texture(
+            acquire_sync,
+            ReleaseSync::Explicit,
+            &self.src_tex,
+            None,
+            AcquireSync::Unnecessary,
+            ReleaseSync::Implicit,
+            0,
+            0,
+        )?;
+        *self.sync_file.borrow_mut() = sync_file;
+        Ok(())
+    }
+
+    /// Returns the synchronization to use when the render device samples the copy.
+    pub fn acquire_sync(&self) -> AcquireSync {
+        AcquireSync::from_sync_file(self.sync_file.borrow().clone())
+    }
+}
@@ -36,36 +36,62 @@ impl DrmFeedback {
         ids: &DrmFeedbackIds,
         render_ctx: &dyn GfxContext,
     ) -> Result<Self, DrmFeedbackError> {
-        let main_device = match render_ctx.allocator().drm() {
-            Some(drm) => drm.dev(),
-            _ => return Err(DrmFeedbackError::NoDrmDevice),
-        };
-        let (data, index_map) = create_fd_data(render_ctx);
-        let mut memfd =
-            uapi::memfd_create("drm_feedback", c::MFD_CLOEXEC | c::MFD_ALLOW_SEALING).unwrap();
-        memfd.write_all(&data).unwrap();
-        uapi::lseek(memfd.raw(), 0, c::SEEK_SET).unwrap();
-        uapi::fcntl_add_seals(
-            memfd.raw(),
-            c::F_SEAL_SEAL | c::F_SEAL_GROW | c::F_SEAL_SHRINK | c::F_SEAL_WRITE,
-        )
-        .unwrap();
+        let main_device = main_device(render_ctx)?;
+        let shared = DrmFeedbackShared::new(main_device, &[render_ctx]);
         Ok(Self {
             id: ids.next(),
-            tranches: vec![DrmFeedbackTranche {
-                device: main_device,
-                indices: (0..index_map.len()).map(|v| v as u16).collect(),
-                scanout: false,
-            }],
-            shared: Rc::new(DrmFeedbackShared {
-                fd: Rc::new(memfd),
-                size: data.len(),
-                main_device,
-                indices: index_map,
-            }),
+            tranches: vec![shared.render_tranche(render_ctx)],
+            shared: Rc::new(shared),
         })
     }
 
+    /// Creates the feedback for outputs of a DRM device that is not the render device.
+    ///
+    /// Clients are asked to prefer buffers that can be imported by the device. Such
+    /// buffers are copied to the render device before they are composited.
+    pub fn for_device(
+        ids: &DrmFeedbackIds,
+        render_ctx: &dyn GfxContext,
+        devnum: c::dev_t,
+        dev_ctx: &dyn GfxContext,
+    ) -> Result<Option<Self>, DrmFeedbackError> {
+        let main_device = main_device(render_ctx)?;
+        let shared = DrmFeedbackShared::new(main_device, &[render_ctx, dev_ctx]);
+        let render_formats = render_ctx.formats();
+        let mut indices = vec![];
+        for (format, info) in &*dev_ctx.formats() {
+            let Some(render_format) = render_formats.get(format) else {
+                continue;
+            };
+            let can_copy = info
+                .write_modifiers
+                .keys()
+                .any(|m| render_format.read_modifiers.contains(m));
+            if !can_copy {
+                continue;
+            }
+            for modifier in &info.read_modifiers {
+                indices.push(shared.indices[&(*format, *modifier)]);
+            }
+        }
+        if indices.is_empty() {
+            return Ok(None);
+        }
+        let tranches = vec![
+            DrmFeedbackTranche {
+                device: devnum,
+                indices,
+                scanout: false,
+            },
+            shared.render_tranche(render_ctx),
+        ];
+        Ok(Some(Self {
+            id: ids.next(),
+            shared: Rc::new(shared),
+            tranches,
+        }))
+    }
+
     pub fn for_scanout(
         &self,
         ids: &DrmFeedbackIds,
@@ -99,17 +125,64 @@ impl DrmFeedback {
     }
 }
 
-fn create_fd_data(ctx: &dyn GfxContext) -> (Vec<u8>, AHashMap<(u32, Modifier), u16>) {
+impl DrmFeedbackShared {
+    fn new(main_device: c::dev_t, ctxs: &[&dyn GfxContext]) -> Self {
+        let (data, indices) = create_fd_data(ctxs);
+        let mut memfd =
+            uapi::memfd_create("drm_feedback", c::MFD_CLOEXEC | c::MFD_ALLOW_SEALING).unwrap();
+        memfd.write_all(&data).unwrap();
+        uapi::lseek(memfd.raw(), 0, c::SEEK_SET).unwrap();
+        uapi::fcntl_add_seals(
+            memfd.raw(),
+            c::F_SEAL_SEAL | c::F_SEAL_GROW | c::F_SEAL_SHRINK | c::F_SEAL_WRITE,
+        )
+        .unwrap();
+        Self {
+            fd: Rc::new(memfd),
+            size: data.len(),
+            main_device,
+            indices,
+        }
+    }
+
+    fn render_tranche(&self, render_ctx: &dyn GfxContext) -> DrmFeedbackTranche {
+        let mut indices = vec![];
+        for (format, info) in &*render_ctx.formats() {
+            for modifier in &info.read_modifiers {
+                indices.push(self.indices[&(*format, *modifier)]);
+            }
+        }
+        DrmFeedbackTranche {
+            device: self.main_device,
+            indices,
+            scanout: false,
+        }
+    }
+}
+
+fn main_device(render_ctx: &dyn GfxContext) -> Result<c::dev_t, DrmFeedbackError> {
+    match render_ctx.allocator().drm() {
+        Some(drm) => Ok(drm.dev()),
+        _ => Err(DrmFeedbackError::NoDrmDevice),
+    }
+}
+
+fn create_fd_data(ctxs: &[&dyn GfxContext]) -> (Vec<u8>, AHashMap<(u32, Modifier), u16>) {
     let mut vec = vec![];
     let mut map = AHashMap::new();
     let mut pos = 0;
-    for (format, info) in &*ctx.formats() {
-        for modifier in &info.read_modifiers {
-            vec.write_u32::<NativeEndian>(*format).unwrap();
-            vec.write_u32::<NativeEndian>(0).unwrap();
-            vec.write_u64::<NativeEndian>(*modifier).unwrap();
-            map.insert((*format, *modifier), pos);
-            pos += 1;
+    for ctx in ctxs {
+        for (format, info) in &*ctx.formats() {
+            for modifier in &info.read_modifiers {
+                if map.contains_key(&(*format, *modifier)) {
+                    continue;
+                }
+                vec.write_u32::<NativeEndian>(*format).unwrap();
+                vec.write_u32::<NativeEndian>(0).unwrap();
+                vec.write_u64::<NativeEndian>(*modifier).unwrap();
+                map.insert((*format, *modifier), pos);
+                pos += 1;
+            }
         }
     }
     (vec, map)
@@ -1035,9 +1035,42 @@ impl<'a> EdidParser<'a> {
         }
     }
 
+    fn parse_cta_hdr_static_metadata_data_block(&mut self) -> Result<CtaDataBlock, EdidError> {
+        let eotfs = self.read_u8()?;
+        let _static_metadata_descriptors = self.read_u8()?;
+        let mut read_opt = || match self.is_empty() {
+            true => Ok(None),
+            false => self.read_u8().map(Some),
+        };
+        let max_cv = read_opt()?;
+        let max_frame_average_cv = read_opt()?;
+        let min_cv = read_opt()?;
+        let luminance = |cv: u8| 50.0 * 2.0f64.powf(cv as f64 / 32.0);
+        let max_luminance = max_cv.map(luminance);
+        Ok(CtaDataBlock::HdrStaticMetadata(
+            CtaHdrStaticMetadataDataBlock {
+                supports_st2084: eotfs & 0x4 != 0,
+                max_luminance,
+                max_frame_average_luminance: max_frame_average_cv.map(luminance),
+                min_luminance: min_cv.map(|cv| {
+                    let cv = cv as f64 / 255.0;
+                    max_luminance.unwrap_or(0.0) * cv * cv / 100.0
+                }),
+            },
+        ))
+    }
+
+    fn parse_cta_extended_data_block(&mut self) -> Result<CtaDataBlock, EdidError> {
+        match self.read_u8()? {
+            0x6 => self.parse_cta_hdr_static_metadata_data_block(),
+            _ => Ok(CtaDataBlock::Unknown),
+        }
+    }
+
     fn parse_cta_data_block(&mut self, tag: u8) -> Result<CtaDataBlock, EdidError> {
         match tag {
             0x3 => self.parse_cta_vendor_data_block(),
+            0x7 => self.parse_cta_extended_data_block(),
             _ => Ok(CtaDataBlock::Unknown),
         }
     }
@@ -1173,6 +1206,7 @@ pub struct CtaExtensionV3 {
 pub enum CtaDataBlock {
     Unknown,
     VendorAmd(CtaAmdVendorDataBlock),
+    HdrStaticMetadata(CtaHdrStaticMetadataDataBlock),
 }
 
 #[derive(Debug)]
@@ -1182,6 +1216,15 @@ pub struct CtaAmdVendorDataBlock {
     pub maximum_refresh_hz: u8,
 }
 
+/// Luminances are in cd/m².
+#[derive(Copy, Clone, Debug)]
+pub struct CtaHdrStaticMetadataDataBlock {
+    pub supports_st2084: bool,
+    pub max_luminance: Option<f64>,
+    pub max_frame_average_luminance: Option<f64>,
+    pub min_luminance: Option<f64>,
+}
+
 #[derive(Debug)]
 pub struct EdidFile {
     pub base_block: EdidBaseBlock,
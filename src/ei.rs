@@ -1,6 +1,7 @@
 use {crate::ei::ei_object::EiVersion, std::cell::Cell};
 
 pub mod ei_acceptor;
+pub mod ei_authorization;
 pub mod ei_client;
 pub mod ei_ifs;
 pub mod ei_object;
@@ -0,0 +1,423 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        cpu_worker::{AsyncCpuWork, CpuJob, CpuWork, PendingJob},
+        ifs::wl_seat::seat_hint::{SeatHint, SeatHintPosition},
+        state::State,
+        utils::{asyncevent::AsyncEvent, errorfmt::ErrorFmt, numcell::NumCell, pid_info::PidInfo},
+    },
+    jay_config::keyboard::syms::{KeySym, SYM_Escape},
+    std::{
+        cell::{Cell, RefCell},
+        collections::{BTreeSet, VecDeque},
+        fs,
+        path::{Path, PathBuf},
+        rc::{Rc, Weak},
+    },
+    thiserror::Error,
+    uapi::OwnedFd,
+};
+
+/// Asks the user whether clients connecting via the unauthenticated libei socket may
+/// emulate input.
+///
+/// Executables that the user has always allowed are recorded in the state directory.
+pub struct EiAuthorization {
+    prompt: Cell<bool>,
+    next_id: NumCell<u64>,
+    pending: RefCell<VecDeque<PendingEiClient>>,
+    permissions: RefCell<BTreeSet<String>>,
+    loaded: Cell<bool>,
+    revoked_before_load: RefCell<BTreeSet<String>>,
+    save_scheduled: Cell<bool>,
+    changed: AsyncEvent,
+    job: Cell<Option<PendingJob>>,
+    job_completed: AsyncEvent,
+    pub hint: Rc<SeatHint>,
+}
+
+struct PendingEiClient {
+    id: u64,
+    socket: Rc<OwnedFd>,
+    pid_info: PidInfo,
+    _hangup: SpawnedFuture<()>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Decision {
+    Allow,
+    AlwaysAllow,
+    Deny,
+}
+
+#[derive(Debug, Error)]
+enum EiPermissionsError {
+    #[error("$HOME is not set")]
+    NoStateDir,
+    #[error("Could not read {0}")]
+    Read(String, #[source] std::io::Error),
+    #[error("Could not parse {0}")]
+    Parse(String, #[source] serde_json::Error),
+    #[error("Could not serialize the libei permissions")]
+    Serialize(#[source] serde_json::Error),
+    #[error("Could not create {0}")]
+    CreateDir(String, #[source] std::io::Error),
+    #[error("Could not write {0}")]
+    Write(String, #[source] std::io::Error),
+}
+
+impl Default for EiAuthorization {
+    fn default() -> Self {
+        Self {
+            prompt: Cell::new(true),
+            next_id: Default::default(),
+            pending: Default::default(),
+            permissions: Default::default(),
+            loaded: Default::default(),
+            revoked_before_load: Default::default(),
+            save_scheduled: Default::default(),
+            changed: Default::default(),
+            job: Default::default(),
+            job_completed: Default::default(),
+            hint: Rc::new(SeatHint::new(SeatHintPosition::Top)),
+        }
+    }
+}
+
+fn permissions_file() -> Result<PathBuf, EiPermissionsError> {
+    let mut path = dirs::state_dir().ok_or(EiPermissionsError::NoStateDir)?;
+    path.push("jay");
+    path.push("ei-permissions.json");
+    Ok(path)
+}
+
+impl EiAuthorization {
+    pub fn clear(&self, state: &State) {
+        self.pending.borrow_mut().clear();
+        self.hint.hide(state);
+        self.changed.clear();
+        self.job_completed.clear();
+        if let Some(job) = self.job.take() {
+            job.detach();
+        }
+    }
+
+    pub fn set_prompt_enabled(&self, enabled: bool) {
+        self.prompt.set(enabled);
+    }
+
+    pub fn is_prompting(&self) -> bool {
+        !self.pending.borrow().is_empty()
+    }
+
+    /// Returns whether the client must be confirmed by the user before it is spawned.
+    ///
+    /// Until the permissions have been loaded, every client is prompted for. Clients
+    /// that turn out to be allowed are spawned once the load completes.
+    pub fn needs_prompt(&self, pid_info: &PidInfo) -> bool {
+        if !self.prompt.get() {
+            return false;
+        }
+        !self.is_allowed(pid_info)
+    }
+
+    fn is_allowed(&self, pid_info: &PidInfo) -> bool {
+        match pid_info.exe() {
+            Some(exe) => self.permissions.borrow().contains(exe),
+            None => false,
+        }
+    }
+
+    pub fn prompt(self: &Rc<Self>, state: &Rc<State>, socket: Rc<OwnedFd>, pid_info: PidInfo) {
+        log::info!(
+            "Asking the user whether {:?} (pid {}) may emulate input",
+            pid_info.comm,
+            pid_info.pid,
+        );
+        let id = self.next_id.fetch_add(1);
+        let hangup = {
+            let slf = self.clone();
+            let state2 = state.clone();
+            let socket = socket.clone();
+            state.eng.spawn("ei authorization hangup", async move {
+                let _ = state2.ring.poll(&socket, 0).await;
+                slf.remove(&state2, id);
+            })
+        };
+        let pending = PendingEiClient {
+            id,
+            socket,
+            pid_info,
+            _hangup: hangup,
+        };
+        self.pending.borrow_mut().push_back(pending);
+        self.update_hint(state);
+    }
+
+    fn remove(&self, state: &Rc<State>, id: u64) {
+        let (idx, removed) = {
+            let pending = &mut *self.pending.borrow_mut();
+            let Some(idx) = pending.iter().position(|p| p.id == id) else {
+                return;
+            };
+            (idx, pending.remove(idx))
+        };
+        if let Some(removed) = removed {
+            log::info!(
+                "Client {:?} disconnected before being authorized",
+                removed.pid_info.comm,
+            );
+        }
+        if idx == 0 {
+            self.update_hint(state);
+        }
+    }
+
+    fn update_hint(&self, state: &Rc<State>) {
+        let text = {
+            let pending = self.pending.borrow();
+            let Some(first) = pending.front() else {
+                self.hint.hide(state);
+                return;
+            };
+            let name = first.pid_info.exe().unwrap_or(&first.pid_info.comm);
+            let mut text = format!(
+                "Allow {} (pid {}) to emulate input? [y] allow, [a] always allow, [n] deny",
+                name, first.pid_info.pid,
+            );
+            if pending.len() > 1 {
+                text.push_str(&format!(" ({} more)", pending.len() - 1));
+            }
+            text
+        };
+        let output = match state.seat_queue.last() {
+            Some(seat) => seat.get_output(),
+            None => match state.dummy_output.get() {
+                Some(o) => o,
+                None => return,
+            },
+        };
+        self.hint.show(state, output, &text);
+    }
+
+    /// Handles a key press while the prompt is shown.
+    pub fn handle_key(&self, state: &Rc<State>, sym: KeySym, char: Option<char>) {
+        let decision = match char {
+            Some('y' | 'Y') => Decision::Allow,
+            Some('a' | 'A') => Decision::AlwaysAllow,
+            Some('n' | 'N') => Decision::Deny,
+            _ if sym == SYM_Escape => Decision::Deny,
+            _ => return,
+        };
+        let Some(pending) = self.pending.borrow_mut().pop_front() else {
+            return;
+        };
+        self.update_hint(state);
+        let pid_info = pending.pid_info;
+        log::info!(
+            "User decision for {:?} (pid {}): {:?}",
+            pid_info.comm,
+            pid_info.pid,
+            decision,
+        );
+        match decision {
+            Decision::Deny => return,
+            Decision::Allow => {}
+            Decision::AlwaysAllow => match pid_info.exe() {
+                Some(exe) => self.modify_permissions(|p| p.insert(exe.to_owned())),
+                None => log::warn!("Cannot remember the decision: executable is unknown"),
+            },
+        }
+        if let Err(e) = state
+            .ei_clients
+            .spawn2(state, pending.socket, Some(pid_info), None)
+        {
+            log::error!("Could not spawn a client: {}", ErrorFmt(e));
+        }
+    }
+
+    /// Returns the executables that are always allowed to emulate input.
+    pub fn permissions(&self) -> Vec<String> {
+        self.permissions.borrow().iter().cloned().collect()
+    }
+
+    pub fn revoke_permission(&self, exe: &str) {
+        if !self.loaded.get() {
+            self.revoked_before_load.borrow_mut().insert(exe.to_owned());
+        }
+        self.modify_permissions(|p| p.remove(exe));
+    }
+
+    fn modify_permissions(&self, f: impl FnOnce(&mut BTreeSet<String>) -> bool) {
+        let changed = f(&mut self.permissions.borrow_mut());
+        if changed && !self.save_scheduled.replace(true) {
+            self.changed.trigger();
+        }
+    }
+
+    /// Merges the permissions loaded from disk with the changes made since startup and
+    /// spawns the pending clients that are allowed.
+    fn merge_loaded(&self, state: &Rc<State>, permissions: BTreeSet<String>) {
+        {
+            let revoked = self.revoked_before_load.take();
+            let current = &mut *self.permissions.borrow_mut();
+            for exe in permissions {
+                if !revoked.contains(&exe) {
+                    current.insert(exe);
+                }
+            }
+        }
+        self.loaded.set(true);
+        let allowed: VecDeque<_> = {
+            let pending = &mut *self.pending.borrow_mut();
+            let (allowed, denied) = pending
+                .drain(..)
+                .partition(|p: &PendingEiClient| self.is_allowed(&p.pid_info));
+            *pending = denied;
+            allowed
+        };
+        if allowed.is_empty() {
+            return;
+        }
+        self.update_hint(state);
+        for pending in allowed {
+            if let Err(e) =
+                state
+                    .ei_clients
+                    .spawn2(state, pending.socket, Some(pending.pid_info), None)
+            {
+                log::error!("Could not spawn a client: {}", ErrorFmt(e));
+            }
+        }
+    }
+}
+
+fn load(path: &Path) -> Result<BTreeSet<String>, EiPermissionsError> {
+    let contents = match fs::read(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Default::default()),
+        Err(e) => return Err(EiPermissionsError::Read(path.display().to_string(), e)),
+    };
+    serde_json::from_slice(&contents)
+        .map_err(|e| EiPermissionsError::Parse(path.display().to_string(), e))
+}
+
+fn save(path: &Path, contents: &[u8]) -> Result<(), EiPermissionsError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| EiPermissionsError::CreateDir(dir.display().to_string(), e))?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, contents)
+        .and_then(|_| fs::rename(&tmp, path))
+        .map_err(|e| EiPermissionsError::Write(path.display().to_string(), e))
+}
+
+fn create_save_job(state: &Rc<State>) -> Result<Box<EiPermissionsJob>, EiPermissionsError> {
+    let permissions = state.ei_authorization.permissions.borrow();
+    let contents = serde_json::to_vec(&*permissions).map_err(EiPermissionsError::Serialize)?;
+    Ok(Box::new(EiPermissionsJob {
+        state: Rc::downgrade(state),
+        work: EiPermissionsWork::Save {
+            path: permissions_file()?,
+            contents,
+            result: None,
+        },
+    }))
+}
+
+pub async fn persist_ei_permissions(state: Rc<State>) {
+    let auth = &state.ei_authorization;
+    match permissions_file() {
+        Ok(path) => {
+            let job = Box::new(EiPermissionsJob {
+                state: Rc::downgrade(&state),
+                work: EiPermissionsWork::Load { path, result: None },
+            });
+            auth.job.set(Some(state.cpu_worker.submit(job)));
+            auth.job_completed.triggered().await;
+        }
+        Err(e) => {
+            log::warn!("Could not load the libei permissions: {}", ErrorFmt(e));
+            auth.merge_loaded(&state, Default::default());
+        }
+    }
+    loop {
+        auth.changed.triggered().await;
+        auth.save_scheduled.set(false);
+        let job = match create_save_job(&state) {
+            Ok(job) => job,
+            Err(e) => {
+                log::warn!("Could not save the libei permissions: {}", ErrorFmt(e));
+                continue;
+            }
+        };
+        auth.job.set(Some(state.cpu_worker.submit(job)));
+        auth.job_completed.triggered().await;
+    }
+}
+
+enum EiPermissionsWork {
+    Load {
+        path: PathBuf,
+        result: Option<Result<BTreeSet<String>, EiPermissionsError>>,
+    },
+    Save {
+        path: PathBuf,
+        contents: Vec<u8>,
+        result: Option<Result<(), EiPermissionsError>>,
+    },
+}
+
+struct EiPermissionsJob {
+    state: Weak<State>,
+    work: EiPermissionsWork,
+}
+
+impl CpuWork for EiPermissionsWork {
+    fn run(&mut self) -> Option<Box<dyn AsyncCpuWork>> {
+        zone!("EiPermissions");
+        match self {
+            EiPermissionsWork::Load { path, result } => *result = Some(load(path)),
+            EiPermissionsWork::Save {
+                path,
+                contents,
+                result,
+            } => *result = Some(save(path, contents)),
+        }
+        None
+    }
+}
+
+impl CpuJob for EiPermissionsJob {
+    fn work(&mut self) -> &mut dyn CpuWork {
+        &mut self.work
+    }
+
+    fn completed(self: Box<Self>) {
+        let Some(state) = self.state.upgrade() else {
+            return;
+        };
+        let auth = &state.ei_authorization;
+        auth.job.take();
+        match self.work {
+            EiPermissionsWork::Load { result, .. } => {
+                let permissions = match result {
+                    Some(Ok(p)) => p,
+                    Some(Err(e)) => {
+                        log::warn!("Could not load the libei permissions: {}", ErrorFmt(e));
+                        Default::default()
+                    }
+                    None => Default::default(),
+                };
+                auth.merge_loaded(&state, permissions);
+            }
+            EiPermissionsWork::Save { result, .. } => {
+                if let Some(Err(e)) = result {
+                    log::warn!("Could not save the libei permissions: {}", ErrorFmt(e));
+                }
+            }
+        }
+        auth.job_completed.trigger();
+    }
+}
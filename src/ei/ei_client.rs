@@ -70,6 +70,10 @@ impl EiClients {
             return Ok(());
         };
         let pid_info = get_pid_info(uid, pid);
+        if global.ei_authorization.needs_prompt(&pid_info) {
+            global.ei_authorization.prompt(global, socket, pid_info);
+            return Ok(());
+        }
         self.spawn2(global, socket, Some(pid_info), None)?;
         Ok(())
     }
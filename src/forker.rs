@@ -6,6 +6,7 @@ use {
         compositor::{DISPLAY, LIBEI_SOCKET, WAYLAND_DISPLAY},
         forker::io::{IoIn, IoOut},
         io_uring::IoUring,
+        pam,
         state::State,
         utils::{
             buffd::BufFdError,
@@ -46,6 +47,7 @@ pub struct ForkerProxy {
     outgoing: AsyncQueue<ServerMessage>,
     next_id: NumCell<u32>,
     pending_pidfds: CopyHashMap<u32, Weak<PidfdHandoff>>,
+    pending_auths: CopyHashMap<u32, Weak<AuthHandoff>>,
     fds: RefCell<Vec<Rc<OwnedFd>>>,
 }
 
@@ -54,6 +56,15 @@ struct PidfdHandoff {
     waiter: Cell<Option<Waker>>,
 }
 
+struct AuthHandoff {
+    success: Cell<Option<bool>>,
+    waiter: Cell<Option<Waker>>,
+}
+
+const PAM_SERVICE: &str = "jay";
+/// Written by the authentication child after PAM has accepted the password.
+const AUTH_SUCCESS: &str = "success";
+
 #[derive(Debug, Error)]
 pub enum ForkerError {
     #[error("Could not create a socketpair")]
@@ -98,6 +109,7 @@ impl ForkerProxy {
                 outgoing: Default::default(),
                 next_id: Default::default(),
                 pending_pidfds: Default::default(),
+                pending_auths: Default::default(),
                 fds: Default::default(),
             }),
             Forked::Child { .. } => {
@@ -156,6 +168,26 @@ impl ForkerProxy {
         .await
     }
 
+    pub async fn authenticate(&self, password: String) -> bool {
+        let id = self.next_id.fetch_add(1);
+        let handoff = Rc::new(AuthHandoff {
+            success: Cell::new(None),
+            waiter: Cell::new(None),
+        });
+        self.pending_auths.set(id, Rc::downgrade(&handoff));
+        self.outgoing
+            .push(ServerMessage::Authenticate { id, password });
+        futures_util::future::poll_fn(|ctx| {
+            if let Some(success) = handoff.success.take() {
+                Poll::Ready(success)
+            } else {
+                handoff.waiter.set(Some(ctx.waker().clone()));
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
     pub async fn xwayland(
         &self,
         state: &State,
@@ -232,6 +264,18 @@ impl ForkerProxy {
         match msg {
             ForkerMessage::Log { level, msg } => self.handle_log(level, &msg),
             ForkerMessage::PidFd { id, success, pid } => self.handle_pidfd(id, success, io, pid),
+            ForkerMessage::Authenticated { id, success } => self.handle_authenticated(id, success),
+        }
+    }
+
+    fn handle_authenticated(&self, id: u32, success: bool) {
+        if let Some(handoff) = self.pending_auths.remove(&id) {
+            if let Some(handoff) = handoff.upgrade() {
+                handoff.success.set(Some(success));
+                if let Some(w) = handoff.waiter.take() {
+                    w.wake();
+                }
+            }
         }
     }
 
@@ -306,6 +350,10 @@ enum ServerMessage {
         fds: Vec<i32>,
         pidfd_id: Option<u32>,
     },
+    Authenticate {
+        id: u32,
+        password: String,
+    },
 }
 
 #[derive(Serialize, Deserialize)]
@@ -319,6 +367,10 @@ enum ForkerMessage {
         success: bool,
         pid: c::pid_t,
     },
+    Authenticated {
+        id: u32,
+        success: bool,
+    },
 }
 
 struct Forker {
@@ -328,6 +380,7 @@ struct Forker {
     fds: RefCell<Vec<Rc<OwnedFd>>>,
     outgoing: AsyncQueue<ForkerMessage>,
     pending_spawns: CopyHashMap<c::pid_t, SpawnedFuture<()>>,
+    pending_auths: CopyHashMap<u32, SpawnedFuture<()>>,
 }
 
 impl Forker {
@@ -361,6 +414,7 @@ impl Forker {
             fds: RefCell::new(vec![]),
             outgoing: Default::default(),
             pending_spawns: Default::default(),
+            pending_auths: Default::default(),
         });
         let _f1 = ae.spawn("forker incoming", forker.clone().incoming());
         let _f2 = ae.spawn("forker outgoing", forker.clone().outgoing());
@@ -406,6 +460,84 @@ impl Forker {
                 fds,
                 pidfd_id,
             } => self.handle_spawn(prog, args, env, fds, io, pidfd_id),
+            ServerMessage::Authenticate { id, password } => self.handle_authenticate(id, password),
+        }
+    }
+
+    fn handle_authenticate(self: &Rc<Self>, id: u32, password: String) {
+        let fail = |msg: String| {
+            self.outgoing
+                .push(ForkerMessage::Authenticated { id, success: false });
+            self.outgoing.push(ForkerMessage::Log {
+                level: log::Level::Error as usize,
+                msg,
+            });
+        };
+        let (read, mut write) = match pipe2(c::O_CLOEXEC) {
+            Ok(p) => p,
+            Err(e) => {
+                fail(format!(
+                    "Could not create a pipe for authentication: {}",
+                    ErrorFmt(crate::utils::oserror::OsError::from(e))
+                ));
+                return;
+            }
+        };
+        let res = match fork_with_pidfd(false) {
+            Ok(o) => o,
+            Err(e) => {
+                fail(ErrorFmt(e).to_string());
+                return;
+            }
+        };
+        match res {
+            Forked::Parent { .. } => {
+                drop(write);
+                let slf = self.clone();
+                let future = self.ae.spawn("await authentication", async move {
+                    let read = Rc::new(read);
+                    let mut s = String::new();
+                    let res = match slf.ring.readable(&read).await {
+                        Err(e) => Err(format!(
+                            "Cannot wait for the child fd to become readable: {}",
+                            ErrorFmt(e)
+                        )),
+                        Ok(_) => match Fd::new(read.raw()).read_to_string(&mut s) {
+                            Err(e) => Err(format!(
+                                "Could not read the authentication result: {}",
+                                ErrorFmt(e)
+                            )),
+                            Ok(_) if s == AUTH_SUCCESS => Ok(()),
+                            Ok(_) if s.is_empty() => {
+                                Err("The authentication process exited without a result"
+                                    .to_string())
+                            }
+                            Ok(_) => Err(s),
+                        },
+                    };
+                    let success = res.is_ok();
+                    if let Err(e) = res {
+                        slf.outgoing.push(ForkerMessage::Log {
+                            level: log::Level::Warn as _,
+                            msg: format!("Could not authenticate: {}", e),
+                        });
+                    }
+                    slf.outgoing
+                        .push(ForkerMessage::Authenticated { id, success });
+                    slf.pending_auths.remove(&id);
+                });
+                self.pending_auths.set(id, future);
+            }
+            Forked::Child { .. } => {
+                let msg = match pam::authenticate(PAM_SERVICE, &password) {
+                    Ok(()) => AUTH_SUCCESS.to_string(),
+                    Err(e) => ErrorFmt(e).to_string(),
+                };
+                if write.write_all(msg.as_bytes()).is_err() {
+                    std::process::exit(1);
+                }
+                std::process::exit(0);
+            }
         }
     }
 
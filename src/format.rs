@@ -1,6 +1,10 @@
 use {
     crate::{
-        gfx_apis::gl::sys::{GLenum, GLint, GL_BGRA_EXT, GL_RGBA, GL_RGBA8, GL_UNSIGNED_BYTE},
+        gfx_apis::gl::sys::{
+            GLenum, GLint, GL_BGRA_EXT, GL_HALF_FLOAT_OES, GL_RGB, GL_RGB10_A2, GL_RGB565, GL_RGBA,
+            GL_RGBA16F, GL_RGBA8, GL_UNSIGNED_BYTE, GL_UNSIGNED_INT_2_10_10_10_REV_EXT,
+            GL_UNSIGNED_SHORT_5_6_5,
+        },
         pipewire::pw_pod::{
             SPA_VIDEO_FORMAT_BGRx, SPA_VIDEO_FORMAT_RGBx, SPA_VIDEO_FORMAT_xBGR_210LE,
             SPA_VIDEO_FORMAT_xRGB_210LE, SpaVideoFormat, SPA_VIDEO_FORMAT_ABGR_210LE,
@@ -293,6 +297,12 @@ static BGRX4444: &Format = &Format {
 
 static RGB565: &Format = &Format {
     name: "rgb565",
+    shm_info: Some(FormatShmInfo {
+        bpp: 2,
+        gl_format: GL_RGB,
+        gl_internal_format: GL_RGB565,
+        gl_type: GL_UNSIGNED_SHORT_5_6_5,
+    }),
     vk_format: vk::Format::R5G6B5_UNORM_PACK16,
     drm: fourcc_code('R', 'G', '1', '6'),
     pipewire: SPA_VIDEO_FORMAT_BGR16,
@@ -366,7 +376,7 @@ static ARGB2101010: &Format = &Format {
     ..default(ConfigFormat::ARGB2101010)
 };
 
-static XRGB2101010: &Format = &Format {
+pub static XRGB2101010: &Format = &Format {
     name: "xrgb2101010",
     vk_format: vk::Format::A2R10G10B10_UNORM_PACK32,
     drm: fourcc_code('X', 'R', '3', '0'),
@@ -376,6 +386,12 @@ static XRGB2101010: &Format = &Format {
 
 static ABGR2101010: &Format = &Format {
     name: "abgr2101010",
+    shm_info: Some(FormatShmInfo {
+        bpp: 4,
+        gl_format: GL_RGBA,
+        gl_internal_format: GL_RGB10_A2,
+        gl_type: GL_UNSIGNED_INT_2_10_10_10_REV_EXT,
+    }),
     vk_format: vk::Format::A2B10G10R10_UNORM_PACK32,
     drm: fourcc_code('A', 'B', '3', '0'),
     has_alpha: true,
@@ -386,6 +402,12 @@ static ABGR2101010: &Format = &Format {
 
 static XBGR2101010: &Format = &Format {
     name: "xbgr2101010",
+    shm_info: Some(FormatShmInfo {
+        bpp: 4,
+        gl_format: GL_RGBA,
+        gl_internal_format: GL_RGB10_A2,
+        gl_type: GL_UNSIGNED_INT_2_10_10_10_REV_EXT,
+    }),
     vk_format: vk::Format::A2B10G10R10_UNORM_PACK32,
     drm: fourcc_code('X', 'B', '3', '0'),
     pipewire: SPA_VIDEO_FORMAT_xBGR_210LE,
@@ -410,6 +432,12 @@ static XBGR16161616: &Format = &Format {
 
 static ABGR16161616F: &Format = &Format {
     name: "abgr16161616f",
+    shm_info: Some(FormatShmInfo {
+        bpp: 8,
+        gl_format: GL_RGBA,
+        gl_internal_format: GL_RGBA16F,
+        gl_type: GL_HALF_FLOAT_OES,
+    }),
     vk_format: vk::Format::R16G16B16A16_SFLOAT,
     drm: fourcc_code('A', 'B', '4', 'H'),
     has_alpha: true,
@@ -419,6 +447,12 @@ static ABGR16161616F: &Format = &Format {
 
 static XBGR16161616F: &Format = &Format {
     name: "xbgr16161616f",
+    shm_info: Some(FormatShmInfo {
+        bpp: 8,
+        gl_format: GL_RGBA,
+        gl_internal_format: GL_RGBA16F,
+        gl_type: GL_HALF_FLOAT_OES,
+    }),
     vk_format: vk::Format::R16G16B16A16_SFLOAT,
     drm: fourcc_code('X', 'B', '4', 'H'),
     ..default(ConfigFormat::XBGR16161616F)
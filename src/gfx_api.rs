@@ -1,6 +1,7 @@
 use {
     crate::{
         allocator::Allocator,
+        cmm::{ColorManager, Eotf, Oetf},
         cpu_worker::CpuWorker,
         cursor::Cursor,
         damage::DamageVisualizer,
@@ -11,7 +12,7 @@ use {
         scale::Scale,
         state::State,
         theme::Color,
-        tree::{Node, OutputNode},
+        tree::{Node, OutputNode, WorkspaceNode},
         utils::{clonecell::UnsafeCellCloneSafe, transform_ext::TransformExt},
         video::{dmabuf::DmaBuf, drm::sync_obj::SyncObjCtx, Modifier},
     },
@@ -43,6 +44,66 @@ pub struct GfxRenderPass {
     pub clear: Option<Color>,
 }
 
+impl GfxRenderPass {
+    /// Converts the pass to the BT.2020 PQ color space used by outputs in HDR mode.
+    pub fn convert_to_hdr(&mut self, cm: &ColorManager) {
+        for op in &mut self.ops {
+            match op {
+                GfxApiOpt::Sync => {}
+                GfxApiOpt::FillRect(f) => f.color = cm.hdr_color(f.color),
+                GfxApiOpt::CopyTexture(c) => {
+                    c.color_transform = Some(cm.hdr_transform(c.color_transform));
+                }
+            }
+        }
+        if let Some(clear) = &mut self.clear {
+            *clear = cm.hdr_color(*clear);
+        }
+    }
+
+    /// Restricts the pass to the area of `clip`.
+    ///
+    /// The pass no longer clears the framebuffer. Instead, the clear color is filled into
+    /// the clipped area. Returns `false` if the clear color is not opaque.
+    pub fn clip(&mut self, clip: &FramebufferRect) -> bool {
+        if let Some(clear) = &self.clear {
+            if clear.a < 1.0 {
+                return false;
+            }
+        }
+        self.ops.retain_mut(|op| match op {
+            GfxApiOpt::Sync => true,
+            GfxApiOpt::FillRect(f) => f.rect.clip(clip).is_some(),
+            GfxApiOpt::CopyTexture(c) => {
+                let Some([fx1, fy1, fx2, fy2]) = c.target.clip(clip) else {
+                    return false;
+                };
+                let s = &mut c.source;
+                let (sw, sh) = (s.x2 - s.x1, s.y2 - s.y1);
+                (s.x1, s.y1, s.x2, s.y2) = (
+                    s.x1 + fx1 * sw,
+                    s.y1 + fy1 * sh,
+                    s.x1 + fx2 * sw,
+                    s.y1 + fy2 * sh,
+                );
+                true
+            }
+        });
+        if let Some(color) = self.clear.take() {
+            let rect = FramebufferRect {
+                x1: clip.x1,
+                x2: clip.x2,
+                y1: clip.y1,
+                y2: clip.y2,
+                output_transform: clip.output_transform,
+            };
+            self.ops
+                .insert(0, GfxApiOpt::FillRect(FillRect { rect, color }));
+        }
+        true
+    }
+}
+
 #[derive(Default, Debug, Copy, Clone, PartialEq)]
 pub struct SampleRect {
     pub x1: f32,
@@ -165,6 +226,29 @@ impl FramebufferRect {
     pub fn is_covering(&self) -> bool {
         self.x1 == -1.0 && self.y1 == -1.0 && self.x2 == 1.0 && self.y2 == 1.0
     }
+
+    /// Intersects this rect with `clip`.
+    ///
+    /// Returns the position of the intersection relative to the original rect as
+    /// fractions `[x1, y1, x2, y2]` or `None` if the intersection is empty.
+    fn clip(&mut self, clip: &FramebufferRect) -> Option<[f32; 4]> {
+        let (width, height) = (self.x2 - self.x1, self.y2 - self.y1);
+        let x1 = self.x1.max(clip.x1);
+        let y1 = self.y1.max(clip.y1);
+        let x2 = self.x2.min(clip.x2);
+        let y2 = self.y2.min(clip.y2);
+        if x1 >= x2 || y1 >= y2 {
+            return None;
+        }
+        let fractions = [
+            (x1 - self.x1) / width,
+            (y1 - self.y1) / height,
+            (x2 - self.x1) / width,
+            (y2 - self.y1) / height,
+        ];
+        (self.x1, self.y1, self.x2, self.y2) = (x1, y1, x2, y2);
+        Some(fractions)
+    }
 }
 
 #[derive(Debug)]
@@ -181,6 +265,17 @@ pub struct CopyTexture {
     pub acquire_sync: AcquireSync,
     pub release_sync: ReleaseSync,
     pub alpha: Option<f32>,
+    pub color_transform: Option<ColorTransform>,
+}
+
+/// Converts texture contents to the color space of the framebuffer.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ColorTransform {
+    /// Converts linear RGB from the source primaries to the linear framebuffer color space.
+    pub matrix: [[f32; 3]; 3],
+    pub eotf: Eotf,
+    pub eotf_arg: f32,
+    pub oetf: Oetf,
 }
 
 #[derive(Clone, Debug)]
@@ -343,6 +438,7 @@ impl dyn GfxFramebuffer {
             resv.cloned(),
             acquire_sync,
             release_sync,
+            None,
         );
         let clear = self.format().has_alpha.then_some(&Color::TRANSPARENT);
         self.render(fb_acquire_sync, fb_release_sync, &ops, clear)
@@ -372,6 +468,7 @@ impl dyn GfxFramebuffer {
         render_hardware_cursor: bool,
         black_background: bool,
         fill_black_in_grace_period: bool,
+        for_capture: bool,
         transform: Transform,
         visualizer: Option<&DamageVisualizer>,
     ) -> GfxRenderPass {
@@ -385,6 +482,7 @@ impl dyn GfxFramebuffer {
             render_hardware_cursor,
             black_background,
             fill_black_in_grace_period,
+            for_capture,
             transform,
             visualizer,
         )
@@ -421,6 +519,7 @@ impl dyn GfxFramebuffer {
             render_hardware_cursor,
             node.has_fullscreen(),
             fill_black_in_grace_period,
+            false,
             node.global.persistent.transform.get(),
         )
     }
@@ -437,6 +536,7 @@ impl dyn GfxFramebuffer {
         render_hardware_cursor: bool,
         black_background: bool,
         fill_black_in_grace_period: bool,
+        for_capture: bool,
         transform: Transform,
     ) -> Result<Option<SyncFile>, GfxError> {
         let pass = self.create_render_pass(
@@ -448,6 +548,7 @@ impl dyn GfxFramebuffer {
             render_hardware_cursor,
             black_background,
             fill_black_in_grace_period,
+            for_capture,
             transform,
             None,
         );
@@ -476,6 +577,28 @@ impl dyn GfxFramebuffer {
         cursor.render_hardware_cursor(&mut renderer);
         self.render(acquire_sync, release_sync, &ops, Some(&Color::TRANSPARENT))
     }
+
+    pub fn render_workspace_thumbnail(
+        &self,
+        acquire_sync: AcquireSync,
+        release_sync: ReleaseSync,
+        workspace: &WorkspaceNode,
+        state: &State,
+        scale: Scale,
+    ) -> Result<Option<SyncFile>, GfxError> {
+        let mut ops = vec![];
+        let mut renderer = Renderer {
+            base: self.renderer_base(&mut ops, scale, Transform::None),
+            state,
+            logical_extents: workspace.position.get().at_point(0, 0),
+            pixel_extents: {
+                let (width, height) = self.logical_size(Transform::None);
+                Rect::new(0, 0, width, height).unwrap()
+            },
+        };
+        renderer.render_workspace_thumbnail(workspace);
+        self.render(acquire_sync, release_sync, &ops, Some(&Color::SOLID_BLACK))
+    }
 }
 
 pub trait GfxImage {
@@ -729,6 +852,7 @@ pub fn create_render_pass(
     render_hardware_cursor: bool,
     black_background: bool,
     fill_black_in_grace_period: bool,
+    for_capture: bool,
     transform: Transform,
     visualizer: Option<&DamageVisualizer>,
 ) -> GfxRenderPass {
@@ -790,6 +914,11 @@ pub fn create_render_pass(
             }
         }
     }
+    if for_capture {
+        let area = node.node_absolute_position();
+        renderer.render_capture_redactions(&area, 0, 0);
+        renderer.render_capture_overlay(&area, 0, 0);
+    }
     if let Some(visualizer) = visualizer {
         if let Some(cursor_rect) = cursor_rect {
             visualizer.render(&cursor_rect, &mut renderer.base);
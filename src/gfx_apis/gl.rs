@@ -345,9 +345,10 @@ fn render_texture(ctx: &GlRenderContext, tex: &CopyTexture) {
             },
             false => &ctx.tex_internal,
         };
-        let copy_type = match tex.alpha.is_some() {
-            true => TexCopyType::Multiply,
-            false => TexCopyType::Identity,
+        let copy_type = match (tex.color_transform.is_some(), tex.alpha.is_some()) {
+            (true, _) => TexCopyType::ColorManaged,
+            (false, true) => TexCopyType::Multiply,
+            (false, false) => TexCopyType::Identity,
         };
         let source_type = match texture.gl.format.has_alpha {
             true => TexSourceType::HasAlpha,
@@ -367,7 +368,15 @@ fn render_texture(ctx: &GlRenderContext, tex: &CopyTexture) {
         let texcoord = tex.source.to_points();
         let pos = tex.target.to_points();
 
-        if let Some(alpha) = tex.alpha {
+        if let Some(ct) = &tex.color_transform {
+            let m = &ct.matrix;
+            let column_major: [f32; 9] = std::array::from_fn(|i| m[i % 3][i / 3]);
+            (gles.glUniformMatrix3fv)(prog.color_matrix, 1, GL_FALSE, column_major.as_ptr());
+            (gles.glUniform1i)(prog.eotf, ct.eotf as _);
+            (gles.glUniform1f)(prog.eotf_arg, ct.eotf_arg);
+            (gles.glUniform1i)(prog.oetf, ct.oetf as _);
+            (gles.glUniform1f)(prog.alpha, tex.alpha.unwrap_or(1.0));
+        } else if let Some(alpha) = tex.alpha {
             (gles.glUniform1f)(prog.alpha, alpha);
         }
 
@@ -112,12 +112,19 @@ bitflags! {
     GlExt: u32;
         GL_OES_EGL_IMAGE          = 1 << 0,
         GL_OES_EGL_IMAGE_EXTERNAL = 1 << 1,
+        GL_EXT_TEXTURE_TYPE_2_10_10_10_REV = 1 << 2,
+        GL_OES_TEXTURE_HALF_FLOAT = 1 << 3,
 }
 
 pub fn get_gl_ext() -> Result<GlExt, RenderError> {
     let map = [
         ("GL_OES_EGL_image", GL_OES_EGL_IMAGE),
         ("GL_OES_EGL_image_external", GL_OES_EGL_IMAGE_EXTERNAL),
+        (
+            "GL_EXT_texture_type_2_10_10_10_REV",
+            GL_EXT_TEXTURE_TYPE_2_10_10_10_REV,
+        ),
+        ("GL_OES_texture_half_float", GL_OES_TEXTURE_HALF_FLOAT),
     ];
     let Some(gles) = GLESV2.as_ref() else {
         return Err(RenderError::LoadGlesV2);
@@ -13,9 +13,13 @@ pub type GLuint = c::c_uint;
 
 egl_transparent!(GLeglImageOES);
 
+pub const GL_RGB: GLint = 0x1907;
 pub const GL_RGBA: GLint = 0x1908;
 pub const GL_RGBA8: GLenum = 0x8058;
 pub const GL_BGRA_EXT: GLint = 0x80E1;
+pub const GL_RGB565: GLenum = 0x8D62;
+pub const GL_RGB10_A2: GLenum = 0x8059;
+pub const GL_RGBA16F: GLenum = 0x881A;
 pub const GL_CLAMP_TO_EDGE: GLint = 0x812F;
 pub const GL_COLOR_ATTACHMENT0: GLenum = 0x8CE0;
 pub const GL_COLOR_BUFFER_BIT: GLbitfield = 0x00004000;
@@ -39,8 +43,12 @@ pub const GL_TEXTURE_WRAP_S: GLenum = 0x2802;
 pub const GL_TEXTURE_WRAP_T: GLenum = 0x2803;
 pub const GL_TRIANGLE_STRIP: GLenum = 0x0005;
 pub const GL_TRIANGLES: GLenum = 0x0004;
+pub const GL_UNPACK_ALIGNMENT: GLenum = 0x0CF5;
 pub const GL_UNPACK_ROW_LENGTH_EXT: GLenum = 0x0CF2;
 pub const GL_UNSIGNED_BYTE: GLint = 0x1401;
+pub const GL_UNSIGNED_SHORT_5_6_5: GLint = 0x8363;
+pub const GL_UNSIGNED_INT_2_10_10_10_REV_EXT: GLint = 0x8368;
+pub const GL_HALF_FLOAT_OES: GLint = 0x8D61;
 pub const GL_VERTEX_SHADER: GLenum = 0x8B31;
 pub const GL_BLEND: GLenum = 0x0BE2;
 pub const GL_ONE: GLenum = 1;
@@ -126,6 +134,12 @@ dynload! {
         glUniform1i: unsafe fn(location: GLint, v0: GLint),
         glUniform1f: unsafe fn(location: GLint, v0: GLfloat),
         glUniform4f: unsafe fn(location: GLint, v0: GLfloat, v1: GLfloat, v2: GLfloat, v3: GLfloat),
+        glUniformMatrix3fv: unsafe fn(
+            location: GLint,
+            count: GLsizei,
+            transpose: GLboolean,
+            value: *const GLfloat,
+        ),
         glVertexAttribPointer: unsafe fn(
             index: GLuint,
             size: GLint,
@@ -1,12 +1,16 @@
 use {
     crate::{
-        format::Format,
+        format::{Format, FormatShmInfo},
         gfx_apis::gl::{
             egl::{context::EglContext, image::EglImage},
-            ext::GL_OES_EGL_IMAGE_EXTERNAL,
+            ext::{
+                GL_EXT_TEXTURE_TYPE_2_10_10_10_REV, GL_OES_EGL_IMAGE_EXTERNAL,
+                GL_OES_TEXTURE_HALF_FLOAT,
+            },
             gl::sys::{
-                GLint, GLuint, GL_CLAMP_TO_EDGE, GL_TEXTURE_2D, GL_TEXTURE_WRAP_S,
-                GL_TEXTURE_WRAP_T, GL_UNPACK_ROW_LENGTH_EXT,
+                GLint, GLuint, GL_CLAMP_TO_EDGE, GL_HALF_FLOAT_OES, GL_TEXTURE_2D,
+                GL_TEXTURE_WRAP_S, GL_TEXTURE_WRAP_T, GL_UNPACK_ALIGNMENT,
+                GL_UNPACK_ROW_LENGTH_EXT, GL_UNSIGNED_INT_2_10_10_10_REV_EXT,
             },
             sys::{GLeglImageOES, GLenum, GL_TEXTURE_EXTERNAL_OES},
             RenderError,
@@ -27,6 +31,24 @@ pub struct GlTexture {
     pub contents_valid: Cell<bool>,
 }
 
+pub fn shm_info(
+    ctx: &EglContext,
+    format: &'static Format,
+) -> Result<&'static FormatShmInfo, RenderError> {
+    let Some(shm_info) = &format.shm_info else {
+        return Err(RenderError::UnsupportedShmFormat(format.name));
+    };
+    let required = match shm_info.gl_type {
+        GL_UNSIGNED_INT_2_10_10_10_REV_EXT => GL_EXT_TEXTURE_TYPE_2_10_10_10_REV,
+        GL_HALF_FLOAT_OES => GL_OES_TEXTURE_HALF_FLOAT,
+        _ => return Ok(shm_info),
+    };
+    if !ctx.ext.contains(required) {
+        return Err(RenderError::UnsupportedShmFormat(format.name));
+    }
+    Ok(shm_info)
+}
+
 pub fn image_target(external_only: bool) -> GLenum {
     match external_only {
         true => GL_TEXTURE_EXTERNAL_OES,
@@ -77,10 +99,8 @@ impl GlTexture {
         height: i32,
         stride: i32,
     ) -> Result<GlTexture, RenderError> {
-        let Some(shm_info) = &format.shm_info else {
-            return Err(RenderError::UnsupportedShmFormat(format.name));
-        };
-        if (stride * height) as usize > data.len() {
+        let shm_info = shm_info(ctx, format)?;
+        if stride < 0 || height < 0 || stride as u64 * height as u64 > data.len() as u64 {
             return Err(RenderError::SmallImageBuffer);
         }
         let gles = ctx.dpy.gles;
@@ -90,6 +110,7 @@ impl GlTexture {
             (gles.glBindTexture)(GL_TEXTURE_2D, tex);
             (gles.glTexParameteri)(GL_TEXTURE_2D, GL_TEXTURE_WRAP_S, GL_CLAMP_TO_EDGE);
             (gles.glTexParameteri)(GL_TEXTURE_2D, GL_TEXTURE_WRAP_T, GL_CLAMP_TO_EDGE);
+            (gles.glPixelStorei)(GL_UNPACK_ALIGNMENT, 1);
             (gles.glPixelStorei)(GL_UNPACK_ROW_LENGTH_EXT, stride / shm_info.bpp as GLint);
             (gles.glTexImage2D)(
                 GL_TEXTURE_2D,
@@ -103,6 +124,7 @@ impl GlTexture {
                 data.as_ptr() as _,
             );
             (gles.glPixelStorei)(GL_UNPACK_ROW_LENGTH_EXT, 0);
+            (gles.glPixelStorei)(GL_UNPACK_ALIGNMENT, 4);
             (gles.glBindTexture)(GL_TEXTURE_2D, 0);
             Ok(tex)
         })?;
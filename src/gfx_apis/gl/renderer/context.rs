@@ -11,7 +11,10 @@ use {
             egl::{context::EglContext, display::EglDisplay, image::EglImage},
             ext::GL_OES_EGL_IMAGE_EXTERNAL,
             gl::{
-                program::GlProgram, render_buffer::GlRenderBuffer, sys::GLint, texture::GlTexture,
+                program::GlProgram,
+                render_buffer::GlRenderBuffer,
+                sys::GLint,
+                texture::{shm_info, GlTexture},
             },
             renderer::{framebuffer::Framebuffer, image::Image},
             GfxGlState, RenderError, Texture,
@@ -40,20 +43,37 @@ pub(crate) struct TexProg {
     pub(crate) texcoord: GLint,
     pub(crate) tex: GLint,
     pub(crate) alpha: GLint,
+    pub(crate) color_matrix: GLint,
+    pub(crate) eotf: GLint,
+    pub(crate) eotf_arg: GLint,
+    pub(crate) oetf: GLint,
 }
 
 impl TexProg {
-    unsafe fn from(prog: GlProgram, alpha_multiplier: bool) -> Self {
+    unsafe fn from(prog: GlProgram, alpha_multiplier: bool, color_management: bool) -> Self {
         unsafe {
             let alpha = match alpha_multiplier {
                 true => prog.get_uniform_location(c"alpha"),
                 false => 0,
             };
+            let (color_matrix, eotf, eotf_arg, oetf) = match color_management {
+                true => (
+                    prog.get_uniform_location(c"color_matrix"),
+                    prog.get_uniform_location(c"eotf"),
+                    prog.get_uniform_location(c"eotf_arg"),
+                    prog.get_uniform_location(c"oetf"),
+                ),
+                false => (0, 0, 0, 0),
+            };
             Self {
                 pos: prog.get_attrib_location(c"pos"),
                 texcoord: prog.get_attrib_location(c"texcoord"),
                 tex: prog.get_uniform_location(c"tex"),
                 alpha,
+                color_matrix,
+                eotf,
+                eotf_arg,
+                oetf,
                 prog,
             }
         }
@@ -64,6 +84,7 @@ impl TexProg {
 pub(in crate::gfx_apis::gl) enum TexCopyType {
     Identity,
     Multiply,
+    ColorManaged,
 }
 
 #[derive(Copy, Clone, PartialEq, Linearize)]
@@ -119,7 +140,9 @@ impl GlRenderContext {
         let tex_vert = include_str!("../shaders/tex.vert.glsl");
         let tex_frag = include_str!("../shaders/tex.frag.glsl");
         let create_programs = |external: bool| {
-            let create_program = |alpha_multiplier: bool, alpha: bool| {
+            let create_program = |copy_type: TexCopyType, alpha: bool| {
+                let alpha_multiplier = copy_type != TexCopyType::Identity;
+                let color_management = copy_type == TexCopyType::ColorManaged;
                 let mut tex_frac_src = String::new();
                 if external {
                     tex_frac_src.push_str("#define EXTERNAL\n");
@@ -127,23 +150,30 @@ impl GlRenderContext {
                 if alpha_multiplier {
                     tex_frac_src.push_str("#define ALPHA_MULTIPLIER\n");
                 }
+                if color_management {
+                    tex_frac_src.push_str("#define COLOR_MANAGEMENT\n");
+                }
                 if alpha {
                     tex_frac_src.push_str("#define ALPHA\n");
                 }
                 tex_frac_src.push_str(tex_frag);
                 unsafe {
                     let prog = GlProgram::from_shaders(ctx, tex_vert, &tex_frac_src)?;
-                    Ok::<_, RenderError>(TexProg::from(prog, alpha_multiplier))
+                    Ok::<_, RenderError>(TexProg::from(prog, alpha_multiplier, color_management))
                 }
             };
             Ok::<_, RenderError>(static_map! {
                 TexCopyType::Identity => static_map! {
-                    TexSourceType::Opaque => create_program(false, false)?,
-                    TexSourceType::HasAlpha => create_program(false, true)?,
+                    TexSourceType::Opaque => create_program(TexCopyType::Identity, false)?,
+                    TexSourceType::HasAlpha => create_program(TexCopyType::Identity, true)?,
                 },
                 TexCopyType::Multiply => static_map! {
-                    TexSourceType::Opaque => create_program(true, false)?,
-                    TexSourceType::HasAlpha => create_program(true, true)?,
+                    TexSourceType::Opaque => create_program(TexCopyType::Multiply, false)?,
+                    TexSourceType::HasAlpha => create_program(TexCopyType::Multiply, true)?,
+                },
+                TexCopyType::ColorManaged => static_map! {
+                    TexSourceType::Opaque => create_program(TexCopyType::ColorManaged, false)?,
+                    TexSourceType::HasAlpha => create_program(TexCopyType::ColorManaged, true)?,
                 },
             })
         };
@@ -292,6 +322,7 @@ impl GfxContext for GlRenderContext {
         stride: i32,
         _cpu_worker: &Rc<CpuWorker>,
     ) -> Result<Rc<dyn AsyncShmGfxTexture>, GfxError> {
+        shm_info(&self.ctx, format)?;
         let tex = self.ctx.with_current(|| unsafe {
             let mut tex = 0;
             (self.ctx.dpy.gles.glGenTextures)(1, &mut tex);
@@ -10,7 +10,7 @@ use {
             renderer::context::GlRenderContext,
             sys::{
                 GLint, GL_CLAMP_TO_EDGE, GL_TEXTURE_2D, GL_TEXTURE_WRAP_S, GL_TEXTURE_WRAP_T,
-                GL_UNPACK_ROW_LENGTH_EXT,
+                GL_UNPACK_ALIGNMENT, GL_UNPACK_ROW_LENGTH_EXT,
             },
             RenderError,
         },
@@ -93,7 +93,7 @@ impl AsyncShmGfxTexture for Texture {
 
     fn sync_upload(self: Rc<Self>, data: &[Cell<u8>], _damage: Region) -> Result<(), GfxError> {
         let shm_info = self.format.shm_info.as_ref().unwrap();
-        if (self.gl.stride * self.gl.height) as usize > data.len() {
+        if self.gl.stride as u64 * self.gl.height as u64 > data.len() as u64 {
             return Err(RenderError::SmallImageBuffer.into());
         }
         let gles = self.ctx.ctx.dpy.gles;
@@ -101,6 +101,7 @@ impl AsyncShmGfxTexture for Texture {
             (gles.glBindTexture)(GL_TEXTURE_2D, self.gl.tex);
             (gles.glTexParameteri)(GL_TEXTURE_2D, GL_TEXTURE_WRAP_S, GL_CLAMP_TO_EDGE);
             (gles.glTexParameteri)(GL_TEXTURE_2D, GL_TEXTURE_WRAP_T, GL_CLAMP_TO_EDGE);
+            (gles.glPixelStorei)(GL_UNPACK_ALIGNMENT, 1);
             (gles.glPixelStorei)(
                 GL_UNPACK_ROW_LENGTH_EXT,
                 self.gl.stride / shm_info.bpp as GLint,
@@ -117,6 +118,7 @@ impl AsyncShmGfxTexture for Texture {
                 data.as_ptr() as _,
             );
             (gles.glPixelStorei)(GL_UNPACK_ROW_LENGTH_EXT, 0);
+            (gles.glPixelStorei)(GL_UNPACK_ALIGNMENT, 4);
             (gles.glBindTexture)(GL_TEXTURE_2D, 0);
             Ok(())
         })?;
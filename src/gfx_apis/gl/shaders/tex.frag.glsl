@@ -3,6 +3,9 @@
 #endif
 
 precision mediump float;
+#if defined(COLOR_MANAGEMENT) && defined(GL_FRAGMENT_PRECISION_HIGH)
+precision highp float;
+#endif
 varying vec2 v_texcoord;
 #ifdef EXTERNAL
 uniform samplerExternalOES tex;
@@ -13,8 +16,59 @@ uniform sampler2D tex;
 uniform float alpha;
 #endif
 
+#ifdef COLOR_MANAGEMENT
+#define EOTF_SRGB 0
+#define EOTF_LINEAR 1
+#define EOTF_POWER 2
+#define EOTF_ST2084_PQ 3
+#define OETF_SRGB 0
+#define OETF_ST2084_PQ 1
+
+uniform mat3 color_matrix;
+uniform int eotf;
+uniform float eotf_arg;
+uniform int oetf;
+
+vec3 apply_eotf(vec3 c) {
+	c = max(c, vec3(0.0));
+	if (eotf == EOTF_SRGB) {
+		return mix(c / 12.92, pow((c + 0.055) / 1.055, vec3(2.4)), step(vec3(0.04045), c));
+	} else if (eotf == EOTF_POWER) {
+		return pow(c, vec3(eotf_arg));
+	} else if (eotf == EOTF_ST2084_PQ) {
+		vec3 p = pow(c, vec3(1.0 / 78.84375));
+		vec3 n = max(p - 0.8359375, vec3(0.0)) / (18.8515625 - 18.6875 * p);
+		return pow(n, vec3(1.0 / 0.1593017578125)) * eotf_arg;
+	}
+	return c;
+}
+
+vec3 srgb_inv_eotf(vec3 c) {
+	c = clamp(c, 0.0, 1.0);
+	return mix(c * 12.92, 1.055 * pow(c, vec3(1.0 / 2.4)) - 0.055, step(vec3(0.0031308), c));
+}
+
+vec3 apply_inv_eotf(vec3 c) {
+	if (oetf == OETF_ST2084_PQ) {
+		vec3 p = pow(clamp(c, 0.0, 1.0), vec3(0.1593017578125));
+		return pow((0.8359375 + 18.8515625 * p) / (1.0 + 18.6875 * p), vec3(78.84375));
+	}
+	return srgb_inv_eotf(c);
+}
+#endif
+
 void main() {
-#ifdef ALPHA
+#ifdef COLOR_MANAGEMENT
+
+	vec4 c = texture2D(tex, v_texcoord);
+#ifndef ALPHA
+	c.a = 1.0;
+#endif
+	vec3 rgb = c.a > 0.0 ? c.rgb / c.a : vec3(0.0);
+	rgb = apply_inv_eotf(color_matrix * apply_eotf(rgb));
+	gl_FragColor = vec4(rgb * c.a, c.a) * alpha;
+
+#elif defined(ALPHA)
 
 #ifdef ALPHA_MULTIPLIER
 	gl_FragColor = texture2D(tex, v_texcoord) * alpha;
@@ -13,6 +13,10 @@ pub const TEX_FRAG_MULT_OPAQUE: &[u8] =
     include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.mult+opaque.spv"));
 pub const TEX_FRAG_MULT_ALPHA: &[u8] =
     include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.mult+alpha.spv"));
+pub const TEX_FRAG_CM_OPAQUE: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.cm+opaque.spv"));
+pub const TEX_FRAG_CM_ALPHA: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.cm+alpha.spv"));
 
 pub struct VulkanShader {
     pub(super) device: Rc<VulkanDevice>,
@@ -48,8 +52,19 @@ pub struct TexFragPushConstants {
     pub alpha: f32,
 }
 
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct TexColorFragPushConstants {
+    pub matrix: [[f32; 4]; 3],
+    pub eotf: u32,
+    pub eotf_arg: f32,
+    pub alpha: f32,
+    pub oetf: u32,
+}
+
 unsafe impl Packed for TexVertPushConstants {}
 unsafe impl Packed for TexFragPushConstants {}
+unsafe impl Packed for TexColorFragPushConstants {}
 
 impl VulkanDevice {
     pub(super) fn create_shader(
@@ -1,6 +1,20 @@
 #version 450
 
-#ifdef ALPHA_MULTIPLIER
+#ifdef COLOR_MANAGEMENT
+#define EOTF_SRGB 0
+#define EOTF_LINEAR 1
+#define EOTF_POWER 2
+#define EOTF_ST2084_PQ 3
+#define OETF_SRGB 0
+#define OETF_ST2084_PQ 1
+layout(push_constant, std430) uniform Data {
+	layout(offset = 64) vec4 matrix[3];
+	layout(offset = 112) uint eotf;
+	layout(offset = 116) float eotf_arg;
+	layout(offset = 120) float mul;
+	layout(offset = 124) uint oetf;
+} data;
+#elif defined(ALPHA_MULTIPLIER)
 layout(push_constant, std430) uniform Data {
 	layout(offset = 64) float mul;
 } data;
@@ -9,8 +23,62 @@ layout(set = 0, binding = 0) uniform sampler2D tex;
 layout(location = 0) in vec2 tex_pos;
 layout(location = 0) out vec4 out_color;
 
+#ifdef COLOR_MANAGEMENT
+vec3 eotf(vec3 c) {
+	c = max(c, vec3(0.0));
+	switch (data.eotf) {
+	case EOTF_SRGB:
+		return mix(c / 12.92, pow((c + 0.055) / 1.055, vec3(2.4)), step(vec3(0.04045), c));
+	case EOTF_POWER:
+		return pow(c, vec3(data.eotf_arg));
+	case EOTF_ST2084_PQ: {
+		const float m1 = 0.1593017578125;
+		const float m2 = 78.84375;
+		const float c1 = 0.8359375;
+		const float c2 = 18.8515625;
+		const float c3 = 18.6875;
+		vec3 p = pow(c, vec3(1.0 / m2));
+		return pow(max(p - c1, 0.0) / (c2 - c3 * p), vec3(1.0 / m1)) * data.eotf_arg;
+	}
+	default:
+		return c;
+	}
+}
+
+vec3 srgb_inv_eotf(vec3 c) {
+	c = clamp(c, 0.0, 1.0);
+	return mix(c * 12.92, 1.055 * pow(c, vec3(1.0 / 2.4)) - 0.055, step(vec3(0.0031308), c));
+}
+
+vec3 inv_eotf(vec3 c) {
+	switch (data.oetf) {
+	case OETF_ST2084_PQ: {
+		const float m1 = 0.1593017578125;
+		const float m2 = 78.84375;
+		const float c1 = 0.8359375;
+		const float c2 = 18.8515625;
+		const float c3 = 18.6875;
+		vec3 p = pow(clamp(c, 0.0, 1.0), vec3(m1));
+		return pow((c1 + c2 * p) / (1.0 + c3 * p), vec3(m2));
+	}
+	default:
+		return srgb_inv_eotf(c);
+	}
+}
+#endif
+
 void main() {
-#ifdef ALPHA_MULTIPLIER
+#ifdef COLOR_MANAGEMENT
+	vec4 c = textureLod(tex, tex_pos, 0);
+#ifndef ALPHA
+	c.a = 1.0;
+#endif
+	vec3 rgb = c.a > 0.0 ? c.rgb / c.a : vec3(0.0);
+	rgb = eotf(rgb);
+	rgb = vec3(dot(data.matrix[0].xyz, rgb), dot(data.matrix[1].xyz, rgb), dot(data.matrix[2].xyz, rgb));
+	rgb = inv_eotf(rgb);
+	out_color = vec4(rgb * c.a, c.a) * data.mul;
+#elif defined(ALPHA_MULTIPLIER)
 #ifdef ALPHA
 	out_color = textureLod(tex, tex_pos, 0) * data.mul;
 #endif // !ALPHA
@@ -0,0 +1,199 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        pipewire::{
+            pw_con::{PwCon, PwConHolder, PwConOwner},
+            pw_ifs::{
+                pw_node::{PwNode, PwNodeOwner, PW_NODE_INTERFACE, PW_NODE_STATE_RUNNING},
+                pw_registry::{PwRegistry, PwRegistryOwner},
+            },
+        },
+        state::State,
+        utils::{
+            asyncevent::AsyncEvent, clonecell::CloneCell, copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt, hash_map_ext::HashMapExt,
+        },
+    },
+    ahash::AHashMap,
+    bstr::{BStr, BString, ByteSlice},
+    std::{
+        cell::{Cell, RefCell},
+        rc::{Rc, Weak},
+    },
+};
+
+const AUDIO_OUTPUT_STREAM: &str = "Stream/Output/Audio";
+const RECONNECT_DELAY_MS: u64 = 5000;
+
+/// Inhibits idle while PipeWire audio output streams are running.
+#[derive(Default)]
+pub struct AudioIdleInhibitor {
+    enabled: Cell<bool>,
+    roles: RefCell<Vec<String>>,
+    task: Cell<Option<SpawnedFuture<()>>>,
+    monitor: CloneCell<Option<Rc<Monitor>>>,
+}
+
+struct Monitor {
+    state: Rc<State>,
+    con: Rc<PwCon>,
+    registry: CloneCell<Option<Rc<PwRegistry>>>,
+    streams: CopyHashMap<u32, Rc<Stream>>,
+    killed: AsyncEvent,
+}
+
+struct Stream {
+    monitor: Weak<Monitor>,
+    node: Rc<PwNode>,
+    running: Cell<bool>,
+    role: RefCell<Option<String>>,
+}
+
+impl AudioIdleInhibitor {
+    pub fn set_enabled(self: &Rc<Self>, state: &Rc<State>, enabled: bool) {
+        if self.enabled.replace(enabled) == enabled {
+            return;
+        }
+        if enabled {
+            let future = state
+                .eng
+                .spawn("audio idle inhibitor", self.clone().run(state.clone()));
+            self.task.set(Some(future));
+        } else {
+            self.clear();
+            state.idle.set_audio_inhibited(false);
+        }
+    }
+
+    /// Sets the media roles of the streams that inhibit idle. If the list is empty, all
+    /// audio output streams inhibit idle.
+    pub fn set_roles(&self, roles: Vec<String>) {
+        *self.roles.borrow_mut() = roles;
+        if let Some(monitor) = self.monitor.get() {
+            monitor.update();
+        }
+    }
+
+    pub fn clear(&self) {
+        self.task.take();
+        if let Some(monitor) = self.monitor.take() {
+            monitor.clear();
+        }
+    }
+
+    fn role_matches(&self, role: Option<&str>) -> bool {
+        let roles = self.roles.borrow();
+        if roles.is_empty() {
+            return true;
+        }
+        match role {
+            Some(role) => roles.iter().any(|r| r.eq_ignore_ascii_case(role)),
+            None => false,
+        }
+    }
+
+    async fn run(self: Rc<Self>, state: Rc<State>) {
+        let mut log_errors = true;
+        loop {
+            match PwConHolder::new(&state.eng, &state.ring, "jay").await {
+                Ok(holder) => {
+                    log_errors = true;
+                    let monitor = Rc::new(Monitor {
+                        state: state.clone(),
+                        con: holder.con.clone(),
+                        registry: Default::default(),
+                        streams: Default::default(),
+                        killed: Default::default(),
+                    });
+                    holder.con.owner.set(Some(monitor.clone()));
+                    let registry = holder.con.get_registry();
+                    registry.owner.set(Some(monitor.clone()));
+                    monitor.registry.set(Some(registry));
+                    self.monitor.set(Some(monitor.clone()));
+                    monitor.killed.triggered().await;
+                    log::warn!("The pipewire connection has been closed");
+                    self.monitor.take();
+                    monitor.clear();
+                    state.idle.set_audio_inhibited(false);
+                }
+                Err(e) => {
+                    if log_errors {
+                        log::warn!("Could not connect to pipewire: {}", ErrorFmt(e));
+                        log_errors = false;
+                    }
+                }
+            }
+            if let Err(e) = state.wheel.timeout(RECONNECT_DELAY_MS).await {
+                log::error!("Could not wait for the reconnect delay: {}", ErrorFmt(e));
+                return;
+            }
+        }
+    }
+}
+
+impl Monitor {
+    fn clear(&self) {
+        self.registry.take();
+        for stream in self.streams.lock().drain_values() {
+            stream.node.owner.take();
+        }
+    }
+
+    fn update(&self) {
+        let inhibitor = &self.state.idle.audio;
+        let active = self
+            .streams
+            .lock()
+            .values()
+            .any(|s| s.running.get() && inhibitor.role_matches(s.role.borrow().as_deref()));
+        self.state.idle.set_audio_inhibited(active);
+    }
+}
+
+impl PwConOwner for Monitor {
+    fn killed(&self) {
+        self.killed.trigger();
+    }
+}
+
+impl PwRegistryOwner for Monitor {
+    fn global(self: Rc<Self>, id: u32, ty: &BStr, props: &AHashMap<BString, BString>) {
+        if ty != PW_NODE_INTERFACE {
+            return;
+        }
+        let class = props.get(b"media.class".as_bstr());
+        if class.map(|c| c == AUDIO_OUTPUT_STREAM) != Some(true) {
+            return;
+        }
+        let Some(registry) = self.registry.get() else {
+            return;
+        };
+        let stream = Rc::new(Stream {
+            monitor: Rc::downgrade(&self),
+            node: self.con.bind_node(&registry, id),
+            running: Cell::new(false),
+            role: Default::default(),
+        });
+        stream.node.owner.set(Some(stream.clone()));
+        self.streams.set(id, stream);
+    }
+
+    fn global_remove(&self, id: u32) {
+        if let Some(stream) = self.streams.remove(&id) {
+            stream.node.owner.take();
+            self.update();
+        }
+    }
+}
+
+impl PwNodeOwner for Stream {
+    fn info(&self, state: u32, props: &AHashMap<BString, BString>) {
+        self.running.set(state == PW_NODE_STATE_RUNNING);
+        *self.role.borrow_mut() = props
+            .get(b"media.role".as_bstr())
+            .map(|r| r.to_str_lossy().into_owned());
+        if let Some(monitor) = self.monitor.upgrade() {
+            monitor.update();
+        }
+    }
+}
@@ -1,3 +1,4 @@
+pub mod color_management;
 pub mod ext_foreign_toplevel_handle_v1;
 pub mod ext_foreign_toplevel_image_capture_source_manager_v1;
 pub mod ext_foreign_toplevel_list_v1;
@@ -9,12 +10,14 @@ pub mod ext_output_image_capture_source_manager_v1;
 pub mod ext_session_lock_manager_v1;
 pub mod ext_session_lock_v1;
 pub mod ipc;
+pub mod jay_binding_mode_watcher;
 pub mod jay_compositor;
 pub mod jay_damage_tracking;
 pub mod jay_ei_session;
 pub mod jay_ei_session_builder;
 pub mod jay_idle;
 pub mod jay_input;
+pub mod jay_launch_watcher;
 pub mod jay_log_file;
 pub mod jay_output;
 pub mod jay_pointer;
@@ -25,6 +28,8 @@ pub mod jay_screenshot;
 pub mod jay_seat_events;
 pub mod jay_select_toplevel;
 pub mod jay_select_workspace;
+pub mod jay_state_dump;
+pub mod jay_stats;
 pub mod jay_toplevel;
 pub mod jay_tray_v1;
 pub mod jay_workspace;
@@ -46,6 +51,7 @@ pub mod wl_shm;
 pub mod wl_shm_pool;
 pub mod wl_subcompositor;
 pub mod wl_surface;
+pub mod wlr_output_manager;
 pub mod workspace_manager;
 pub mod wp_alpha_modifier_v1;
 pub mod wp_commit_timing_manager_v1;
@@ -61,6 +67,7 @@ pub mod wp_fifo_manager_v1;
 pub mod wp_fractional_scale_manager_v1;
 pub mod wp_linux_drm_syncobj_manager_v1;
 pub mod wp_linux_drm_syncobj_timeline_v1;
+pub mod wp_pointer_warp_v1;
 pub mod wp_presentation;
 pub mod wp_presentation_feedback;
 pub mod wp_security_context_manager_v1;
@@ -73,15 +80,26 @@ pub mod xdg_activation_v1;
 pub mod xdg_positioner;
 pub mod xdg_toplevel_drag_manager_v1;
 pub mod xdg_toplevel_drag_v1;
+pub mod xdg_toplevel_icon_manager_v1;
+pub mod xdg_toplevel_icon_v1;
 pub mod xdg_wm_base;
 pub mod xdg_wm_dialog_v1;
+pub mod zwlr_foreign_toplevel_handle_v1;
+pub mod zwlr_foreign_toplevel_manager_v1;
+pub mod zwlr_gamma_control_manager_v1;
+pub mod zwlr_gamma_control_v1;
 pub mod zwlr_layer_shell_v1;
+pub mod zwlr_output_power_manager_v1;
+pub mod zwlr_output_power_v1;
 pub mod zwlr_screencopy_frame_v1;
 pub mod zwlr_screencopy_manager_v1;
 pub mod zwp_idle_inhibit_manager_v1;
+pub mod zwp_keyboard_shortcuts_inhibit_manager_v1;
 pub mod zwp_linux_buffer_params_v1;
 pub mod zwp_linux_dmabuf_feedback_v1;
 pub mod zwp_linux_dmabuf_v1;
+pub mod zwp_linux_explicit_synchronization_v1;
+pub mod zwp_xwayland_keyboard_grab_manager_v1;
 pub mod zxdg_decoration_manager_v1;
 pub mod zxdg_output_manager_v1;
 pub mod zxdg_output_v1;
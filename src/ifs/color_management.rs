@@ -0,0 +1,104 @@
+use crate::cmm::{NamedPrimaries, TransferFunction};
+
+pub mod wp_color_management_output_v1;
+pub mod wp_color_manager_v1;
+pub mod wp_image_description_creator_params_v1;
+pub mod wp_image_description_info_v1;
+pub mod wp_image_description_v1;
+
+pub const RENDER_INTENT_PERCEPTUAL: u32 = 0;
+
+pub const FEATURE_PARAMETRIC: u32 = 1;
+pub const FEATURE_SET_PRIMARIES: u32 = 2;
+pub const FEATURE_SET_TF_POWER: u32 = 3;
+pub const FEATURE_SET_LUMINANCES: u32 = 4;
+
+const PRIMARIES_SRGB: u32 = 1;
+const PRIMARIES_PAL_M: u32 = 2;
+const PRIMARIES_PAL: u32 = 3;
+const PRIMARIES_NTSC: u32 = 4;
+const PRIMARIES_GENERIC_FILM: u32 = 5;
+const PRIMARIES_BT2020: u32 = 6;
+const PRIMARIES_CIE1931_XYZ: u32 = 7;
+const PRIMARIES_DCI_P3: u32 = 8;
+const PRIMARIES_DISPLAY_P3: u32 = 9;
+const PRIMARIES_ADOBE_RGB: u32 = 10;
+
+const TRANSFER_FUNCTION_BT1886: u32 = 1;
+const TRANSFER_FUNCTION_GAMMA22: u32 = 2;
+const TRANSFER_FUNCTION_GAMMA28: u32 = 3;
+const TRANSFER_FUNCTION_EXT_LINEAR: u32 = 5;
+const TRANSFER_FUNCTION_SRGB: u32 = 9;
+const TRANSFER_FUNCTION_ST2084_PQ: u32 = 11;
+
+pub const SUPPORTED_TRANSFER_FUNCTIONS: [u32; 6] = [
+    TRANSFER_FUNCTION_BT1886,
+    TRANSFER_FUNCTION_GAMMA22,
+    TRANSFER_FUNCTION_GAMMA28,
+    TRANSFER_FUNCTION_EXT_LINEAR,
+    TRANSFER_FUNCTION_SRGB,
+    TRANSFER_FUNCTION_ST2084_PQ,
+];
+
+impl NamedPrimaries {
+    pub fn from_protocol(primaries: u32) -> Option<Self> {
+        let res = match primaries {
+            PRIMARIES_SRGB => Self::Srgb,
+            PRIMARIES_PAL_M => Self::PalM,
+            PRIMARIES_PAL => Self::Pal,
+            PRIMARIES_NTSC => Self::Ntsc,
+            PRIMARIES_GENERIC_FILM => Self::GenericFilm,
+            PRIMARIES_BT2020 => Self::Bt2020,
+            PRIMARIES_CIE1931_XYZ => Self::Cie1931Xyz,
+            PRIMARIES_DCI_P3 => Self::DciP3,
+            PRIMARIES_DISPLAY_P3 => Self::DisplayP3,
+            PRIMARIES_ADOBE_RGB => Self::AdobeRgb,
+            _ => return None,
+        };
+        Some(res)
+    }
+
+    pub fn to_protocol(self) -> u32 {
+        match self {
+            Self::Srgb => PRIMARIES_SRGB,
+            Self::PalM => PRIMARIES_PAL_M,
+            Self::Pal => PRIMARIES_PAL,
+            Self::Ntsc => PRIMARIES_NTSC,
+            Self::GenericFilm => PRIMARIES_GENERIC_FILM,
+            Self::Bt2020 => PRIMARIES_BT2020,
+            Self::Cie1931Xyz => PRIMARIES_CIE1931_XYZ,
+            Self::DciP3 => PRIMARIES_DCI_P3,
+            Self::DisplayP3 => PRIMARIES_DISPLAY_P3,
+            Self::AdobeRgb => PRIMARIES_ADOBE_RGB,
+        }
+    }
+}
+
+impl TransferFunction {
+    pub fn from_protocol(tf: u32) -> Option<Self> {
+        let res = match tf {
+            TRANSFER_FUNCTION_BT1886 => Self::Bt1886,
+            TRANSFER_FUNCTION_GAMMA22 => Self::Gamma22,
+            TRANSFER_FUNCTION_GAMMA28 => Self::Gamma28,
+            TRANSFER_FUNCTION_EXT_LINEAR => Self::ExtLinear,
+            TRANSFER_FUNCTION_SRGB => Self::Srgb,
+            TRANSFER_FUNCTION_ST2084_PQ => Self::St2084Pq,
+            _ => return None,
+        };
+        Some(res)
+    }
+
+    /// Returns the named transfer function or the exponent multiplied by 10000.
+    pub fn to_protocol(self) -> Result<u32, u32> {
+        let res = match self {
+            Self::Bt1886 => TRANSFER_FUNCTION_BT1886,
+            Self::Gamma22 => TRANSFER_FUNCTION_GAMMA22,
+            Self::Gamma28 => TRANSFER_FUNCTION_GAMMA28,
+            Self::ExtLinear => TRANSFER_FUNCTION_EXT_LINEAR,
+            Self::Srgb => TRANSFER_FUNCTION_SRGB,
+            Self::St2084Pq => TRANSFER_FUNCTION_ST2084_PQ,
+            Self::Power(exp) => return Err((exp * 10000.0).round() as u32),
+        };
+        Ok(res)
+    }
+}
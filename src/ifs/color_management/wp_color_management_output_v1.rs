@@ -0,0 +1,70 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{
+            color_management::wp_image_description_v1::{WpImageDescriptionV1, CAUSE_NO_OUTPUT},
+            wl_output::OutputGlobalOpt,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{wp_color_management_output_v1::*, WpColorManagementOutputV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct WpColorManagementOutputV1 {
+    pub id: WpColorManagementOutputV1Id,
+    pub client: Rc<Client>,
+    pub version: Version,
+    pub tracker: Tracker<Self>,
+    pub output: Rc<OutputGlobalOpt>,
+}
+
+impl WpColorManagementOutputV1RequestHandler for WpColorManagementOutputV1 {
+    type Error = WpColorManagementOutputV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get_image_description(
+        &self,
+        req: GetImageDescription,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        // Outputs are always composited in sRGB.
+        let description = self
+            .output
+            .get()
+            .map(|_| self.client.state.color_manager.srgb().clone());
+        let obj = Rc::new(WpImageDescriptionV1::new(
+            req.image_description,
+            &self.client,
+            self.version,
+            description,
+            true,
+        ));
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        obj.send_status(CAUSE_NO_OUTPUT, "The output has been removed");
+        Ok(())
+    }
+}
+
+object_base! {
+    self = WpColorManagementOutputV1;
+    version = self.version;
+}
+
+impl Object for WpColorManagementOutputV1 {}
+
+simple_add_obj!(WpColorManagementOutputV1);
+
+#[derive(Debug, Error)]
+pub enum WpColorManagementOutputV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(WpColorManagementOutputV1Error, ClientError);
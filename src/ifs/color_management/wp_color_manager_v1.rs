@@ -0,0 +1,247 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        cmm::NamedPrimaries,
+        globals::{Global, GlobalName},
+        ifs::{
+            color_management::{
+                wp_color_management_output_v1::WpColorManagementOutputV1,
+                wp_image_description_creator_params_v1::WpImageDescriptionCreatorParamsV1,
+                FEATURE_PARAMETRIC, FEATURE_SET_LUMINANCES, FEATURE_SET_PRIMARIES,
+                FEATURE_SET_TF_POWER, RENDER_INTENT_PERCEPTUAL, SUPPORTED_TRANSFER_FUNCTIONS,
+            },
+            wl_surface::{
+                wp_color_management_surface_feedback_v1::WpColorManagementSurfaceFeedbackV1,
+                wp_color_management_surface_v1::{
+                    WpColorManagementSurfaceV1, WpColorManagementSurfaceV1Error,
+                },
+            },
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{wp_color_manager_v1::*, WpColorManagerV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+const UNSUPPORTED_FEATURE: u32 = 0;
+
+pub struct WpColorManagerV1Global {
+    name: GlobalName,
+}
+
+pub struct WpColorManagerV1 {
+    id: WpColorManagerV1Id,
+    client: Rc<Client>,
+    version: Version,
+    pub tracker: Tracker<Self>,
+}
+
+impl WpColorManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: WpColorManagerV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), WpColorManagerV1Error> {
+        let obj = Rc::new(WpColorManagerV1 {
+            id,
+            client: client.clone(),
+            version,
+            tracker: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        obj.send_capabilities();
+        Ok(())
+    }
+}
+
+impl WpColorManagerV1 {
+    fn send_capabilities(&self) {
+        self.send_supported_intent(RENDER_INTENT_PERCEPTUAL);
+        for feature in [
+            FEATURE_PARAMETRIC,
+            FEATURE_SET_PRIMARIES,
+            FEATURE_SET_TF_POWER,
+            FEATURE_SET_LUMINANCES,
+        ] {
+            self.send_supported_feature(feature);
+        }
+        for tf in SUPPORTED_TRANSFER_FUNCTIONS {
+            self.send_supported_tf_named(tf);
+        }
+        for primaries in NamedPrimaries::ALL {
+            self.send_supported_primaries_named(primaries.to_protocol());
+        }
+        self.send_done();
+    }
+
+    fn send_supported_intent(&self, render_intent: u32) {
+        self.client.event(SupportedIntent {
+            self_id: self.id,
+            render_intent,
+        });
+    }
+
+    fn send_supported_feature(&self, feature: u32) {
+        self.client.event(SupportedFeature {
+            self_id: self.id,
+            feature,
+        });
+    }
+
+    fn send_supported_tf_named(&self, tf: u32) {
+        self.client.event(SupportedTfNamed {
+            self_id: self.id,
+            tf,
+        });
+    }
+
+    fn send_supported_primaries_named(&self, primaries: u32) {
+        self.client.event(SupportedPrimariesNamed {
+            self_id: self.id,
+            primaries,
+        });
+    }
+
+    fn send_done(&self) {
+        self.client.event(Done { self_id: self.id });
+    }
+
+    fn unsupported_feature(&self, feature: &'static str) -> WpColorManagerV1Error {
+        self.client.protocol_error(
+            self,
+            UNSUPPORTED_FEATURE,
+            &format!("{} is not supported", feature),
+        );
+        WpColorManagerV1Error::UnsupportedFeature(feature)
+    }
+}
+
+impl WpColorManagerV1RequestHandler for WpColorManagerV1 {
+    type Error = WpColorManagerV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get_output(&self, req: GetOutput, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let output = self.client.lookup(req.output)?;
+        let obj = Rc::new(WpColorManagementOutputV1 {
+            id: req.id,
+            client: self.client.clone(),
+            version: self.version,
+            tracker: Default::default(),
+            output: output.global.clone(),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        Ok(())
+    }
+
+    fn get_surface(&self, req: GetSurface, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let surface = self.client.lookup(req.surface)?;
+        let obj = Rc::new(WpColorManagementSurfaceV1::new(
+            req.id,
+            &surface,
+            self.version,
+        ));
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        obj.install()?;
+        Ok(())
+    }
+
+    fn get_surface_feedback(
+        &self,
+        req: GetSurfaceFeedback,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let _surface = self.client.lookup(req.surface)?;
+        let obj = Rc::new(WpColorManagementSurfaceFeedbackV1 {
+            id: req.id,
+            version: self.version,
+            client: self.client.clone(),
+            tracker: Default::default(),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        Ok(())
+    }
+
+    fn create_icc_creator(
+        &self,
+        _req: CreateIccCreator,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        Err(self.unsupported_feature("ICC"))
+    }
+
+    fn create_parametric_creator(
+        &self,
+        req: CreateParametricCreator,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let obj = Rc::new(WpImageDescriptionCreatorParamsV1::new(
+            req.obj,
+            &self.client,
+            self.version,
+        ));
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        Ok(())
+    }
+
+    fn create_windows_scrgb(
+        &self,
+        _req: CreateWindowsScrgb,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        Err(self.unsupported_feature("Windows scRGB"))
+    }
+}
+
+global_base!(
+    WpColorManagerV1Global,
+    WpColorManagerV1,
+    WpColorManagerV1Error
+);
+
+impl Global for WpColorManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+simple_add_global!(WpColorManagerV1Global);
+
+object_base! {
+    self = WpColorManagerV1;
+    version = self.version;
+}
+
+impl Object for WpColorManagerV1 {}
+
+simple_add_obj!(WpColorManagerV1);
+
+#[derive(Debug, Error)]
+pub enum WpColorManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error(transparent)]
+    WpColorManagementSurfaceV1Error(#[from] WpColorManagementSurfaceV1Error),
+    #[error("{0} is not supported")]
+    UnsupportedFeature(&'static str),
+}
+efrom!(WpColorManagerV1Error, ClientError);
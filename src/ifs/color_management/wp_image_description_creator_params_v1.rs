@@ -0,0 +1,216 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        cmm::{ColorDescriptionParams, Luminance, NamedPrimaries, Primaries, TransferFunction},
+        ifs::color_management::wp_image_description_v1::{WpImageDescriptionV1, CAUSE_UNSUPPORTED},
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{wp_image_description_creator_params_v1::*, WpImageDescriptionCreatorParamsV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct WpImageDescriptionCreatorParamsV1 {
+    pub id: WpImageDescriptionCreatorParamsV1Id,
+    pub client: Rc<Client>,
+    pub version: Version,
+    pub tracker: Tracker<Self>,
+    pub transfer_function: Cell<Option<TransferFunction>>,
+    pub primaries: Cell<Option<(Primaries, Option<NamedPrimaries>)>>,
+    pub luminance: Cell<Option<Luminance>>,
+    pub target_primaries: Cell<Option<Primaries>>,
+    pub target_luminance: Cell<Option<(f64, f64)>>,
+    pub max_cll: Cell<Option<u32>>,
+    pub max_fall: Cell<Option<u32>>,
+}
+
+impl WpImageDescriptionCreatorParamsV1 {
+    pub fn new(
+        id: WpImageDescriptionCreatorParamsV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            version,
+            tracker: Default::default(),
+            transfer_function: Default::default(),
+            primaries: Default::default(),
+            luminance: Default::default(),
+            target_primaries: Default::default(),
+            target_luminance: Default::default(),
+            max_cll: Default::default(),
+            max_fall: Default::default(),
+        }
+    }
+
+    fn set_once<T: Copy>(
+        cell: &Cell<Option<T>>,
+        value: T,
+        name: &'static str,
+    ) -> Result<(), WpImageDescriptionCreatorParamsV1Error> {
+        if cell.get().is_some() {
+            return Err(WpImageDescriptionCreatorParamsV1Error::AlreadySet(name));
+        }
+        cell.set(Some(value));
+        Ok(())
+    }
+}
+
+impl WpImageDescriptionCreatorParamsV1RequestHandler for WpImageDescriptionCreatorParamsV1 {
+    type Error = WpImageDescriptionCreatorParamsV1Error;
+
+    fn create(&self, req: Create, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(transfer_function) = self.transfer_function.get() else {
+            return Err(WpImageDescriptionCreatorParamsV1Error::Incomplete);
+        };
+        let Some((primaries, named_primaries)) = self.primaries.get() else {
+            return Err(WpImageDescriptionCreatorParamsV1Error::Incomplete);
+        };
+        let description = self
+            .client
+            .state
+            .color_manager
+            .create(ColorDescriptionParams {
+                named_primaries,
+                primaries,
+                transfer_function,
+                luminance: self.luminance.get(),
+                target_primaries: self.target_primaries.get(),
+                target_luminance: self.target_luminance.get(),
+                max_cll: self.max_cll.get(),
+                max_fall: self.max_fall.get(),
+            });
+        let obj = Rc::new(WpImageDescriptionV1::new(
+            req.image_description,
+            &self.client,
+            self.version,
+            description,
+            false,
+        ));
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        obj.send_status(CAUSE_UNSUPPORTED, "The primaries do not span a color space");
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn set_tf_named(&self, req: SetTfNamed, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(tf) = TransferFunction::from_protocol(req.tf) else {
+            return Err(WpImageDescriptionCreatorParamsV1Error::InvalidTf(req.tf));
+        };
+        Self::set_once(&self.transfer_function, tf, "transfer function")
+    }
+
+    fn set_tf_power(&self, req: SetTfPower, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if !(10000..=100000).contains(&req.eexp) {
+            return Err(WpImageDescriptionCreatorParamsV1Error::InvalidTfPower(
+                req.eexp,
+            ));
+        }
+        let tf = TransferFunction::Power(req.eexp as f64 / 10000.0);
+        Self::set_once(&self.transfer_function, tf, "transfer function")
+    }
+
+    fn set_primaries_named(
+        &self,
+        req: SetPrimariesNamed,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let Some(named) = NamedPrimaries::from_protocol(req.primaries) else {
+            return Err(WpImageDescriptionCreatorParamsV1Error::InvalidPrimaries(
+                req.primaries,
+            ));
+        };
+        Self::set_once(
+            &self.primaries,
+            (named.primaries(), Some(named)),
+            "primaries",
+        )
+    }
+
+    fn set_primaries(&self, req: SetPrimaries, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let primaries = Primaries::from_protocol([
+            req.r_x, req.r_y, req.g_x, req.g_y, req.b_x, req.b_y, req.w_x, req.w_y,
+        ]);
+        Self::set_once(&self.primaries, (primaries, None), "primaries")
+    }
+
+    fn set_luminances(&self, req: SetLuminances, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let min = req.min_lum as f64 / 10000.0;
+        let max = req.max_lum as f64;
+        let reference = req.reference_lum as f64;
+        if max <= min || reference <= min {
+            return Err(WpImageDescriptionCreatorParamsV1Error::InvalidLuminance);
+        }
+        let lum = Luminance {
+            min,
+            max,
+            reference,
+        };
+        Self::set_once(&self.luminance, lum, "luminances")
+    }
+
+    fn set_mastering_display_primaries(
+        &self,
+        _req: SetMasteringDisplayPrimaries,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        Err(WpImageDescriptionCreatorParamsV1Error::UnsupportedFeature(
+            "set_mastering_display_primaries",
+        ))
+    }
+
+    fn set_mastering_luminance(
+        &self,
+        req: SetMasteringLuminance,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let min = req.min_lum as f64 / 10000.0;
+        let max = req.max_lum as f64;
+        if max <= min {
+            return Err(WpImageDescriptionCreatorParamsV1Error::InvalidLuminance);
+        }
+        Self::set_once(&self.target_luminance, (min, max), "mastering luminance")
+    }
+
+    fn set_max_cll(&self, req: SetMaxCll, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        Self::set_once(&self.max_cll, req.max_cll, "max_cll")
+    }
+
+    fn set_max_fall(&self, req: SetMaxFall, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        Self::set_once(&self.max_fall, req.max_fall, "max_fall")
+    }
+}
+
+object_base! {
+    self = WpImageDescriptionCreatorParamsV1;
+    version = self.version;
+}
+
+impl Object for WpImageDescriptionCreatorParamsV1 {}
+
+simple_add_obj!(WpImageDescriptionCreatorParamsV1);
+
+#[derive(Debug, Error)]
+pub enum WpImageDescriptionCreatorParamsV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The transfer function and the primaries must be set")]
+    Incomplete,
+    #[error("The {0} has already been set")]
+    AlreadySet(&'static str),
+    #[error("{0} is not supported")]
+    UnsupportedFeature(&'static str),
+    #[error("Transfer function {0} is not supported")]
+    InvalidTf(u32),
+    #[error("Transfer function exponent {0} is out of range")]
+    InvalidTfPower(u32),
+    #[error("Unknown primaries {0}")]
+    InvalidPrimaries(u32),
+    #[error("The luminances are invalid")]
+    InvalidLuminance,
+}
+efrom!(WpImageDescriptionCreatorParamsV1Error, ClientError);
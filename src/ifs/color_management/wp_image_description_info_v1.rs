@@ -0,0 +1,145 @@
+use {
+    crate::{
+        client::Client,
+        cmm::{self, ColorDescription},
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{wp_image_description_info_v1::*, WpImageDescriptionInfoV1Id},
+    },
+    std::{convert::Infallible, rc::Rc},
+};
+
+pub struct WpImageDescriptionInfoV1 {
+    pub id: WpImageDescriptionInfoV1Id,
+    pub client: Rc<Client>,
+    pub version: Version,
+    pub tracker: Tracker<Self>,
+}
+
+impl WpImageDescriptionInfoV1 {
+    pub fn send_description(&self, d: &ColorDescription) {
+        self.send_primaries(&d.primaries);
+        if let Some(named) = d.named_primaries {
+            self.send_primaries_named(named.to_protocol());
+        }
+        match d.transfer_function.to_protocol() {
+            Ok(tf) => self.send_tf_named(tf),
+            Err(eexp) => self.send_tf_power(eexp),
+        }
+        let lum = &d.luminance;
+        self.send_luminances(
+            (lum.min * 10000.0).round() as u32,
+            lum.max.round() as u32,
+            lum.reference.round() as u32,
+        );
+        self.send_target_primaries(d.target_primaries.as_ref().unwrap_or(&d.primaries));
+        let (min, max) = d.target_luminance.unwrap_or((lum.min, lum.max));
+        self.send_target_luminance((min * 10000.0).round() as u32, max.round() as u32);
+        if let Some(max_cll) = d.max_cll {
+            self.send_target_max_cll(max_cll);
+        }
+        if let Some(max_fall) = d.max_fall {
+            self.send_target_max_fall(max_fall);
+        }
+        self.send_done();
+    }
+
+    fn send_done(&self) {
+        self.client.event(Done { self_id: self.id });
+    }
+
+    fn send_primaries(&self, p: &cmm::Primaries) {
+        let [r_x, r_y, g_x, g_y, b_x, b_y, w_x, w_y] = p.to_protocol();
+        self.client.event(Primaries {
+            self_id: self.id,
+            r_x,
+            r_y,
+            g_x,
+            g_y,
+            b_x,
+            b_y,
+            w_x,
+            w_y,
+        });
+    }
+
+    fn send_primaries_named(&self, primaries: u32) {
+        self.client.event(PrimariesNamed {
+            self_id: self.id,
+            primaries,
+        });
+    }
+
+    fn send_tf_power(&self, eexp: u32) {
+        self.client.event(TfPower {
+            self_id: self.id,
+            eexp,
+        });
+    }
+
+    fn send_tf_named(&self, tf: u32) {
+        self.client.event(TfNamed {
+            self_id: self.id,
+            tf,
+        });
+    }
+
+    fn send_luminances(&self, min_lum: u32, max_lum: u32, reference_lum: u32) {
+        self.client.event(Luminances {
+            self_id: self.id,
+            min_lum,
+            max_lum,
+            reference_lum,
+        });
+    }
+
+    fn send_target_primaries(&self, p: &cmm::Primaries) {
+        let [r_x, r_y, g_x, g_y, b_x, b_y, w_x, w_y] = p.to_protocol();
+        self.client.event(TargetPrimaries {
+            self_id: self.id,
+            r_x,
+            r_y,
+            g_x,
+            g_y,
+            b_x,
+            b_y,
+            w_x,
+            w_y,
+        });
+    }
+
+    fn send_target_luminance(&self, min_lum: u32, max_lum: u32) {
+        self.client.event(TargetLuminance {
+            self_id: self.id,
+            min_lum,
+            max_lum,
+        });
+    }
+
+    fn send_target_max_cll(&self, max_cll: u32) {
+        self.client.event(TargetMaxCll {
+            self_id: self.id,
+            max_cll,
+        });
+    }
+
+    fn send_target_max_fall(&self, max_fall: u32) {
+        self.client.event(TargetMaxFall {
+            self_id: self.id,
+            max_fall,
+        });
+    }
+}
+
+impl WpImageDescriptionInfoV1RequestHandler for WpImageDescriptionInfoV1 {
+    type Error = Infallible;
+}
+
+object_base! {
+    self = WpImageDescriptionInfoV1;
+    version = self.version;
+}
+
+impl Object for WpImageDescriptionInfoV1 {}
+
+simple_add_obj!(WpImageDescriptionInfoV1);
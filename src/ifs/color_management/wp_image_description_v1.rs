@@ -0,0 +1,121 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        cmm::ColorDescription,
+        ifs::color_management::wp_image_description_info_v1::WpImageDescriptionInfoV1,
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{wp_image_description_v1::*, WpImageDescriptionV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub const CAUSE_UNSUPPORTED: u32 = 1;
+pub const CAUSE_NO_OUTPUT: u32 = 3;
+
+pub struct WpImageDescriptionV1 {
+    pub id: WpImageDescriptionV1Id,
+    pub client: Rc<Client>,
+    pub version: Version,
+    pub tracker: Tracker<Self>,
+    /// `None` if the description has failed.
+    pub description: Option<Rc<ColorDescription>>,
+    /// Whether the client may request the parameters of the description.
+    pub has_information: bool,
+}
+
+impl WpImageDescriptionV1 {
+    pub fn new(
+        id: WpImageDescriptionV1Id,
+        client: &Rc<Client>,
+        version: Version,
+        description: Option<Rc<ColorDescription>>,
+        has_information: bool,
+    ) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            version,
+            tracker: Default::default(),
+            description,
+            has_information,
+        }
+    }
+
+    /// Sends `ready` or `failed` depending on the state of the description.
+    pub fn send_status(&self, cause: u32, msg: &str) {
+        match &self.description {
+            Some(d) => self.send_ready(d.identity),
+            None => self.send_failed(cause, msg),
+        }
+    }
+
+    fn send_failed(&self, cause: u32, msg: &str) {
+        self.client.event(Failed {
+            self_id: self.id,
+            cause,
+            msg,
+        });
+    }
+
+    pub fn send_ready(&self, identity: u32) {
+        self.client.event(Ready {
+            self_id: self.id,
+            identity,
+        });
+    }
+}
+
+impl WpImageDescriptionV1RequestHandler for WpImageDescriptionV1 {
+    type Error = WpImageDescriptionV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get_information(&self, req: GetInformation, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(description) = &self.description else {
+            return Err(WpImageDescriptionV1Error::NotReady);
+        };
+        if !self.has_information {
+            return Err(WpImageDescriptionV1Error::NoInformation);
+        }
+        let info = Rc::new(WpImageDescriptionInfoV1 {
+            id: req.information,
+            client: self.client.clone(),
+            version: self.version,
+            tracker: Default::default(),
+        });
+        track!(self.client, info);
+        self.client.add_client_obj(&info)?;
+        info.send_description(description);
+        self.client.remove_obj(&*info)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = WpImageDescriptionV1;
+    version = self.version;
+}
+
+impl Object for WpImageDescriptionV1 {}
+
+dedicated_add_obj!(
+    WpImageDescriptionV1,
+    WpImageDescriptionV1Id,
+    image_descriptions
+);
+
+#[derive(Debug, Error)]
+pub enum WpImageDescriptionV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The image description is not ready")]
+    NotReady,
+    #[error("The image description does not allow querying its parameters")]
+    NoInformation,
+}
+efrom!(WpImageDescriptionV1Error, ClientError);
@@ -118,7 +118,7 @@ async fn run(n: Rc<ExtIdleNotificationV1>, skip_if_inhibited: bool) {
             }
         } else {
             let idle = &n.client.state.idle;
-            let send_idle = !skip_if_inhibited || idle.inhibitors.is_empty();
+            let send_idle = !skip_if_inhibited || !idle.is_inhibited();
             if send_idle {
                 n.send_idled();
                 n.seat.add_idle_notification(&n);
@@ -44,6 +44,7 @@ impl ExtImageCopyCaptureCursorSessionV1RequestHandler for ExtImageCopyCaptureCur
                 &self.client,
                 self.version,
                 &self.source,
+                true,
                 slf,
             )
         });
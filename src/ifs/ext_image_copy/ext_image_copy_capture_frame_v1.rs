@@ -226,6 +226,7 @@ impl ExtImageCopyCaptureFrameV1 {
                 size,
                 transform,
                 on.global.persistent.scale.get(),
+                on.capture_color_transform(),
             )
         });
     }
@@ -240,10 +241,11 @@ impl ExtImageCopyCaptureFrameV1 {
                 &self.client.state,
                 Some(node.node_absolute_position()),
                 scale,
-                true,
+                self.session.paint_cursors,
                 true,
                 true,
                 false,
+                true,
                 jay_config::video::Transform::None,
             )
         });
@@ -17,6 +17,8 @@ use {
     thiserror::Error,
 };
 
+const PAINT_CURSORS: u32 = 1;
+
 pub struct ExtImageCopyCaptureManagerV1Global {
     pub name: GlobalName,
 }
@@ -62,6 +64,7 @@ impl ExtImageCopyCaptureManagerV1RequestHandler for ExtImageCopyCaptureManagerV1
                 &self.client,
                 self.version,
                 &source.ty,
+                req.options & PAINT_CURSORS != 0,
                 slf,
             )
         });
@@ -38,6 +38,7 @@ pub struct ExtImageCopyCaptureSessionV1 {
     pub(super) shm_bridge: CloneCell<Option<Rc<dyn GfxInternalFramebuffer>>>,
     pub(super) shm_staging: CloneCell<Option<Rc<dyn GfxStagingBuffer>>>,
     pub(super) source: ImageCaptureSource,
+    pub(super) paint_cursors: bool,
     pub(super) force_capture: Cell<bool>,
     pub(super) stopped: Cell<bool>,
     pub(super) latch_listener: EventListener<dyn LatchListener>,
@@ -55,6 +56,7 @@ impl ExtImageCopyCaptureSessionV1 {
         client: &Rc<Client>,
         version: Version,
         source: &ImageCaptureSource,
+        paint_cursors: bool,
         slf: &Weak<Self>,
     ) -> Self {
         ExtImageCopyCaptureSessionV1 {
@@ -66,6 +68,7 @@ impl ExtImageCopyCaptureSessionV1 {
             shm_bridge: Default::default(),
             shm_staging: Default::default(),
             source: source.clone(),
+            paint_cursors,
             force_capture: Cell::new(true),
             stopped: Default::default(),
             latch_listener: EventListener::new(slf.clone()),
@@ -229,7 +232,7 @@ impl ExtImageCopyCaptureSessionV1 {
                     resv,
                     acquire_sync,
                     release_sync,
-                    render_hardware_cursors,
+                    render_hardware_cursors && self.paint_cursors,
                     x_off,
                     y_off,
                     size,
@@ -54,7 +54,9 @@ impl ExtSessionLockManagerV1RequestHandler for ExtSessionLockManagerV1 {
     }
 
     fn lock(&self, req: Lock, _slf: &Rc<Self>) -> Result<(), Self::Error> {
-        let did_lock = self.client.state.lock.locked.get() == false;
+        let lock = &self.client.state.lock;
+        let takeover = lock.locked.get() && lock.lock.is_none();
+        let did_lock = lock.locked.get() == false || takeover;
         let new = Rc::new(ExtSessionLockV1 {
             id: req.id,
             client: self.client.clone(),
@@ -73,6 +75,13 @@ impl ExtSessionLockManagerV1RequestHandler for ExtSessionLockManagerV1 {
             }
             state.lock.locked.set(true);
             state.lock.lock.set(Some(new.clone()));
+            state.lock.fallback.deactivate(state);
+            if !takeover {
+                state.lock.locked_usec.set(state.now_usec());
+                if let Some(config) = state.config.get() {
+                    config.locked();
+                }
+            }
             state.tree_changed();
             state.damage(state.root.extents.get());
             new.send_locked();
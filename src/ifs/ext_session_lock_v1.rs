@@ -34,15 +34,31 @@ impl ExtSessionLockV1 {
         self.send_finished();
         self.finished.set(true);
     }
+
+    fn abandon(&self) {
+        if self.finished.get() {
+            return;
+        }
+        let state = &self.client.state;
+        state.lock.lock.take();
+        if self.did_lock && state.lock.locked.get() {
+            log::warn!(
+                "Client {} abandoned the session lock. Showing the fallback lock screen.",
+                self.client.id
+            );
+            state.lock.fallback.activate(state);
+            if let Some(config) = state.config.get() {
+                config.locker_crashed();
+            }
+        }
+    }
 }
 
 impl ExtSessionLockV1RequestHandler for ExtSessionLockV1 {
     type Error = ExtSessionLockV1Error;
 
     fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
-        if !self.finished.get() {
-            self.client.state.lock.lock.take();
-        }
+        self.abandon();
         self.client.remove_obj(self)?;
         Ok(())
     }
@@ -102,9 +118,7 @@ object_base! {
 
 impl Object for ExtSessionLockV1 {
     fn break_loops(&self) {
-        if !self.finished.get() {
-            self.client.state.lock.lock.take();
-        }
+        self.abandon();
     }
 }
 
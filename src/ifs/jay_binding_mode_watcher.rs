@@ -0,0 +1,65 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::wl_seat::WlSeatGlobal,
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{jay_binding_mode_watcher::*, JayBindingModeWatcherId},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct JayBindingModeWatcher {
+    pub id: JayBindingModeWatcherId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl JayBindingModeWatcher {
+    pub fn send_mode(&self, seat: &WlSeatGlobal) {
+        self.client.event(Mode {
+            self_id: self.id,
+            seat: seat.seat_name(),
+            mode: &seat.binding_mode(),
+        });
+    }
+
+    fn remove_from_state(&self) {
+        self.client
+            .state
+            .binding_mode_watchers
+            .remove(&(self.client.id, self.id));
+    }
+}
+
+impl JayBindingModeWatcherRequestHandler for JayBindingModeWatcher {
+    type Error = JayBindingModeWatcherError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.remove_from_state();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = JayBindingModeWatcher;
+    version = self.version;
+}
+
+impl Object for JayBindingModeWatcher {
+    fn break_loops(&self) {
+        self.remove_from_state();
+    }
+}
+
+simple_add_obj!(JayBindingModeWatcher);
+
+#[derive(Debug, Error)]
+pub enum JayBindingModeWatcherError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(JayBindingModeWatcherError, ClientError);
@@ -4,9 +4,11 @@ use {
         client::{Client, ClientCaps, ClientError, CAP_JAY_COMPOSITOR},
         globals::{Global, GlobalName},
         ifs::{
+            jay_binding_mode_watcher::JayBindingModeWatcher,
             jay_ei_session_builder::JayEiSessionBuilder,
             jay_idle::JayIdle,
             jay_input::JayInput,
+            jay_launch_watcher::JayLaunchWatcher,
             jay_log_file::JayLogFile,
             jay_output::JayOutput,
             jay_pointer::JayPointer,
@@ -17,12 +19,14 @@ use {
             jay_seat_events::JaySeatEvents,
             jay_select_toplevel::{JaySelectToplevel, JayToplevelSelector},
             jay_select_workspace::{JaySelectWorkspace, JayWorkspaceSelector},
+            jay_state_dump::JayStateDump,
+            jay_stats::JayStats,
             jay_workspace_watcher::JayWorkspaceWatcher,
             jay_xwayland::JayXwayland,
         },
         leaks::Tracker,
         object::{Object, Version},
-        screenshoter::take_screenshot,
+        screenshoter::{take_screenshot, take_workspace_thumbnail, ScreenshooterError, Screenshot},
         utils::{errorfmt::ErrorFmt, toplevel_identifier::ToplevelIdentifier},
         wire::{jay_compositor::*, JayCompositorId, JayScreenshotId},
     },
@@ -72,7 +76,7 @@ impl Global for JayCompositorGlobal {
     }
 
     fn version(&self) -> u32 {
-        13
+        35
     }
 
     fn required_caps(&self) -> ClientCaps {
@@ -109,6 +113,15 @@ impl JayCompositor {
         &self,
         id: JayScreenshotId,
         include_cursor: bool,
+    ) -> Result<(), JayCompositorError> {
+        let res = take_screenshot(&self.client.state, include_cursor);
+        self.send_screenshot(id, res)
+    }
+
+    fn send_screenshot(
+        &self,
+        id: JayScreenshotId,
+        res: Result<Screenshot, ScreenshooterError>,
     ) -> Result<(), JayCompositorError> {
         let ss = Rc::new(JayScreenshot {
             id,
@@ -117,7 +130,7 @@ impl JayCompositor {
         });
         track!(self.client, ss);
         self.client.add_client_obj(&ss)?;
-        match take_screenshot(&self.client.state, include_cursor) {
+        match res {
             Ok(s) => {
                 let dmabuf = s.bo.dmabuf();
                 if self.version < SCREENSHOT_SPLITUP_SINCE {
@@ -238,13 +251,7 @@ impl JayCompositorRequestHandler for JayCompositor {
     }
 
     fn unlock(&self, _req: Unlock, _slf: &Rc<Self>) -> Result<(), Self::Error> {
-        let state = &self.client.state;
-        if state.lock.locked.get() {
-            if let Some(lock) = state.lock.lock.get() {
-                lock.finish();
-            }
-            state.do_unlock();
-        }
+        self.client.state.force_unlock();
         Ok(())
     }
 
@@ -330,6 +337,7 @@ impl JayCompositorRequestHandler for JayCompositor {
             id: req.id,
             client: self.client.clone(),
             tracker: Default::default(),
+            version: self.version,
         });
         track!(self.client, watcher);
         self.client.add_client_obj(&watcher)?;
@@ -347,6 +355,7 @@ impl JayCompositorRequestHandler for JayCompositor {
         let sc = Rc::new_cyclic(|slf| JayScreencast::new(req.id, &self.client, slf, self.version));
         track!(self.client, sc);
         self.client.add_client_obj(&sc)?;
+        sc.send_clock_id();
         Ok(())
     }
 
@@ -384,6 +393,7 @@ impl JayCompositorRequestHandler for JayCompositor {
             client: self.client.clone(),
             tracker: Default::default(),
             destroyed: Cell::new(false),
+            version: self.version,
         });
         track!(self.client, obj);
         self.client.add_client_obj(&obj)?;
@@ -439,6 +449,87 @@ impl JayCompositorRequestHandler for JayCompositor {
         obj.done(tl);
         Ok(())
     }
+
+    fn get_workspace_thumbnail(
+        &self,
+        req: GetWorkspaceThumbnail,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let ws = self.client.lookup(req.workspace)?;
+        let res = match ws.workspace.get() {
+            Some(ws) => {
+                take_workspace_thumbnail(&self.client.state, &ws, req.max_width, req.max_height)
+            }
+            None => Err(ScreenshooterError::WorkspaceDestroyed),
+        };
+        self.send_screenshot(req.id, res)
+    }
+
+    fn get_stats(&self, req: GetStats, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let obj = Rc::new(JayStats {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        Ok(())
+    }
+
+    fn watch_binding_modes(
+        &self,
+        req: WatchBindingModes,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let watcher = Rc::new(JayBindingModeWatcher {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+        });
+        track!(self.client, watcher);
+        self.client.add_client_obj(&watcher)?;
+        self.client
+            .state
+            .binding_mode_watchers
+            .set((self.client.id, req.id), watcher.clone());
+        for seat in self.client.state.globals.seats.lock().values() {
+            watcher.send_mode(seat);
+        }
+        Ok(())
+    }
+
+    fn watch_launches(&self, req: WatchLaunches, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let watcher = Rc::new(JayLaunchWatcher {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+        });
+        track!(self.client, watcher);
+        self.client.add_client_obj(&watcher)?;
+        self.client
+            .state
+            .launch_watchers
+            .set((self.client.id, req.id), watcher.clone());
+        for pending in self.client.state.activation_tokens.lock().values() {
+            watcher.send_started(pending);
+        }
+        Ok(())
+    }
+
+    fn dump_state(&self, req: DumpState, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let dump = Rc::new(JayStateDump {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+        });
+        track!(self.client, dump);
+        self.client.add_client_obj(&dump)?;
+        dump.send_dump();
+        Ok(())
+    }
 }
 
 object_base! {
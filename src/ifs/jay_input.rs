@@ -15,6 +15,7 @@ use {
         utils::errorfmt::ErrorFmt,
         wire::{jay_input::*, JayInputId},
     },
+    ahash::AHashSet,
     std::rc::Rc,
     thiserror::Error,
     uapi::OwnedFd,
@@ -28,6 +29,11 @@ pub struct JayInput {
 }
 
 const CALIBRATION_MATRIX_SINCE: Version = Version(4);
+const BUTTON_DEBOUNCE_SINCE: Version = Version(16);
+const GAMEPAD_RESETS_IDLE_SINCE: Version = Version(17);
+const DISABLE_WHILE_TYPING_SINCE: Version = Version(18);
+const MIDDLE_BUTTON_EMULATION_SINCE: Version = Version(30);
+const SEAT_OUTPUTS_SINCE: Version = Version(35);
 
 impl JayInput {
     pub fn new(id: JayInputId, client: &Rc<Client>, version: Version) -> Self {
@@ -63,6 +69,16 @@ impl JayInput {
             repeat_delay: data.get_rate().1,
             hardware_cursor: data.cursor_group().hardware_cursor() as _,
         });
+        if self.version >= SEAT_OUTPUTS_SINCE {
+            let mut outputs = data.cursor_group().outputs();
+            outputs.sort();
+            for output in outputs {
+                self.client.event(SeatOutput {
+                    self_id: self.id,
+                    output: &output,
+                });
+            }
+        }
     }
 
     fn send_error(&self, error: &str) {
@@ -155,6 +171,51 @@ impl JayInput {
                 });
             }
         }
+        if self.version >= BUTTON_DEBOUNCE_SINCE && dev.has_capability(Pointer) {
+            let debounce = &data.data.button_debounce;
+            self.client.event(ButtonDebounce {
+                self_id: self.id,
+                timeout_ms: (debounce.timeout_usec.get() / 1000) as u32,
+                suppressed: debounce.suppressed.get(),
+            });
+        }
+        if self.version >= GAMEPAD_RESETS_IDLE_SINCE {
+            if let Some(enabled) = dev.gamepad_resets_idle() {
+                self.client.event(GamepadResetsIdle {
+                    self_id: self.id,
+                    enabled: enabled as _,
+                });
+            }
+        }
+        if self.version >= DISABLE_WHILE_TYPING_SINCE {
+            if let Some(enabled) = dev.dwt_enabled() {
+                self.client.event(DisableWhileTyping {
+                    self_id: self.id,
+                    enabled: enabled as _,
+                });
+            }
+            if let Some(enabled) = dev.dwtp_enabled() {
+                self.client.event(DisableWhileTrackpointing {
+                    self_id: self.id,
+                    enabled: enabled as _,
+                });
+            }
+            if dev.has_capability(Pointer) {
+                let window = data.data.disable_while_typing.window_usec.get();
+                self.client.event(DisableWhileTypingWindow {
+                    self_id: self.id,
+                    window_ms: (window / 1000) as u32,
+                });
+            }
+        }
+        if self.version >= MIDDLE_BUTTON_EMULATION_SINCE {
+            if let Some(enabled) = dev.middle_emulation_enabled() {
+                self.client.event(MiddleButtonEmulation {
+                    self_id: self.id,
+                    enabled: enabled as _,
+                });
+            }
+        }
     }
 
     fn device(&self, id: u32) -> Result<Rc<DeviceHandlerData>, JayInputError> {
@@ -461,6 +522,123 @@ impl JayInputRequestHandler for JayInput {
             Ok(())
         })
     }
+
+    fn set_button_debounce(
+        &self,
+        req: SetButtonDebounce,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            dev.button_debounce
+                .timeout_usec
+                .set(req.timeout_ms as u64 * 1000);
+            Ok(())
+        })
+    }
+
+    fn set_gamepad_resets_idle(
+        &self,
+        req: SetGamepadResetsIdle,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            dev.device.set_gamepad_resets_idle(req.enabled != 0);
+            Ok(())
+        })
+    }
+
+    fn set_disable_while_typing_enabled(
+        &self,
+        req: SetDisableWhileTypingEnabled,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            dev.device.set_dwt_enabled(req.enabled != 0);
+            Ok(())
+        })
+    }
+
+    fn set_disable_while_trackpointing_enabled(
+        &self,
+        req: SetDisableWhileTrackpointingEnabled,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            dev.device.set_dwtp_enabled(req.enabled != 0);
+            Ok(())
+        })
+    }
+
+    fn set_disable_while_typing_window(
+        &self,
+        req: SetDisableWhileTypingWindow,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            dev.disable_while_typing
+                .window_usec
+                .set(req.window_ms as u64 * 1000);
+            Ok(())
+        })
+    }
+
+    fn set_middle_button_emulation_enabled(
+        &self,
+        req: SetMiddleButtonEmulationEnabled,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            dev.device.set_middle_emulation_enabled(req.enabled != 0);
+            Ok(())
+        })
+    }
+
+    fn create_seat(&self, req: CreateSeat, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.seat(req.name).is_err() {
+            self.client.state.create_seat(req.name);
+        }
+        Ok(())
+    }
+
+    fn clear_seat_outputs(
+        &self,
+        req: ClearSeatOutputs,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let seat = self.seat(req.seat)?;
+            seat.set_outputs(Default::default());
+            Ok(())
+        })
+    }
+
+    fn add_seat_output(&self, req: AddSeatOutput, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let seat = self.seat(req.seat)?;
+            let namelc = req.output.to_ascii_lowercase();
+            let connector = self
+                .client
+                .state
+                .connectors
+                .lock()
+                .values()
+                .find(|c| c.name.to_ascii_lowercase() == namelc)
+                .cloned();
+            let Some(connector) = connector else {
+                return Err(JayInputError::ConnectorDoesNotExist(req.output.to_string()));
+            };
+            let mut outputs: AHashSet<_> = seat.cursor_group().outputs().into_iter().collect();
+            outputs.insert(connector.name.clone());
+            seat.set_outputs(outputs);
+            Ok(())
+        })
+    }
 }
 
 object_base! {
@@ -492,5 +670,7 @@ pub enum JayInputError {
     ParseKeymap(#[from] KbvmError),
     #[error("Output is not connected")]
     OutputNotConnected,
+    #[error("There is no connector called {0}")]
+    ConnectorDoesNotExist(String),
 }
 efrom!(JayInputError, ClientError);
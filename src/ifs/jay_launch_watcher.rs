@@ -0,0 +1,82 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::xdg_activation_token_v1::{ActivationEnd, PendingActivation},
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{jay_launch_watcher::*, JayLaunchWatcherId},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct JayLaunchWatcher {
+    pub id: JayLaunchWatcherId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl JayLaunchWatcher {
+    pub fn send_started(&self, pending: &PendingActivation) {
+        let token = pending.token.to_string();
+        self.client.event(Started {
+            self_id: self.id,
+            token: &token,
+            app_id: pending.app_id.as_deref(),
+            pid: pending.pid,
+            seat: pending.seat.as_ref().map(|seat| seat.seat_name()),
+        });
+    }
+
+    pub fn send_end(&self, pending: &PendingActivation, end: ActivationEnd) {
+        let token = pending.token.to_string();
+        match end {
+            ActivationEnd::Activated => self.client.event(Activated {
+                self_id: self.id,
+                token: &token,
+            }),
+            ActivationEnd::Expired => self.client.event(Expired {
+                self_id: self.id,
+                token: &token,
+            }),
+        }
+    }
+
+    fn remove_from_state(&self) {
+        self.client
+            .state
+            .launch_watchers
+            .remove(&(self.client.id, self.id));
+    }
+}
+
+impl JayLaunchWatcherRequestHandler for JayLaunchWatcher {
+    type Error = JayLaunchWatcherError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.remove_from_state();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = JayLaunchWatcher;
+    version = self.version;
+}
+
+impl Object for JayLaunchWatcher {
+    fn break_loops(&self) {
+        self.remove_from_state();
+    }
+}
+
+simple_add_obj!(JayLaunchWatcher);
+
+#[derive(Debug, Error)]
+pub enum JayLaunchWatcherError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(JayLaunchWatcherError, ClientError);
@@ -9,11 +9,11 @@ use {
         scale::Scale,
         state::{ConnectorData, DrmDevData, OutputData},
         tree::{OutputNode, TearingMode, VrrMode},
-        utils::{gfx_api_ext::GfxApiExt, transform_ext::TransformExt},
+        utils::{errorfmt::ErrorFmt, gfx_api_ext::GfxApiExt, transform_ext::TransformExt},
         wire::{jay_randr::*, JayRandrId},
     },
     jay_config::video::{
-        GfxApi, TearingMode as ConfigTearingMode, Transform, VrrMode as ConfigVrrMode,
+        CustomMode, GfxApi, TearingMode as ConfigTearingMode, Transform, VrrMode as ConfigVrrMode,
     },
     std::rc::Rc,
     thiserror::Error,
@@ -30,6 +30,10 @@ const VRR_CAPABLE_SINCE: Version = Version(2);
 const TEARING_SINCE: Version = Version(3);
 const FORMAT_SINCE: Version = Version(8);
 const FLIP_MARGIN_SINCE: Version = Version(10);
+const MODE_FALLBACK_SINCE: Version = Version(15);
+const HDR_SINCE: Version = Version(20);
+const MIRROR_SINCE: Version = Version(24);
+const EDID_SINCE: Version = Version(26);
 
 impl JayRandr {
     pub fn new(id: JayRandrId, client: &Rc<Client>, version: Version) -> Self {
@@ -80,6 +84,27 @@ impl JayRandr {
         };
         let node = match &output.node {
             Some(n) => n,
+            None if !output.monitor_info.non_desktop => {
+                let state = &self.client.state;
+                let source = state
+                    .active_mirror_source(data)
+                    .and_then(|s| state.connectors.get(&s));
+                if let Some(source) = source {
+                    if self.version >= MIRROR_SINCE {
+                        self.client.event(MirrorOutput {
+                            self_id: self.id,
+                            manufacturer: &output.monitor_info.output_id.manufacturer,
+                            product: &output.monitor_info.output_id.model,
+                            serial_number: &output.monitor_info.output_id.serial_number,
+                            width_mm: output.monitor_info.width_mm,
+                            height_mm: output.monitor_info.height_mm,
+                            source: &source.name,
+                        });
+                        self.send_monitor_details(&output.monitor_info);
+                    }
+                }
+                return;
+            }
             None => {
                 self.client.event(NonDesktopOutput {
                     self_id: self.id,
@@ -89,6 +114,7 @@ impl JayRandr {
                     width_mm: output.monitor_info.width_mm,
                     height_mm: output.monitor_info.height_mm,
                 });
+                self.send_monitor_details(&output.monitor_info);
                 return;
             }
         };
@@ -108,6 +134,7 @@ impl JayRandr {
             width_mm: global.width_mm,
             height_mm: global.height_mm,
         });
+        self.send_monitor_details(&output.monitor_info);
         if self.version >= VRR_CAPABLE_SINCE {
             self.client.event(VrrState {
                 self_id: self.id,
@@ -163,6 +190,57 @@ impl JayRandr {
                 current: (mode == &current_mode) as _,
             });
         }
+        if self.version >= MODE_FALLBACK_SINCE {
+            if let Some(mode) = global.requested_mode.get() {
+                self.client.event(ModeFallback {
+                    self_id: self.id,
+                    width: mode.width,
+                    height: mode.height,
+                    refresh_rate_millihz: mode.refresh_rate_millihz,
+                });
+            }
+        }
+        if self.version >= HDR_SINCE {
+            self.client.event(HdrState {
+                self_id: self.id,
+                capable: output.monitor_info.hdr_capable as _,
+                enabled: global.hdr_enabled.get() as _,
+            });
+        }
+    }
+
+    fn send_monitor_details(&self, info: &backend::MonitorInfo) {
+        if self.version < EDID_SINCE {
+            return;
+        }
+        if let Some(edid) = &info.edid {
+            self.client.event(Edid {
+                self_id: self.id,
+                product_code: edid.product_code as _,
+                serial_number: edid.serial_number,
+                week_of_manufacture: edid.week_of_manufacture.unwrap_or_default() as _,
+                year_of_manufacture: edid.year_of_manufacture.unwrap_or_default() as _,
+                version: edid.version as _,
+                revision: edid.revision as _,
+            });
+            if let Some(limits) = &edid.range_limits {
+                self.client.event(EdidRangeLimits {
+                    self_id: self.id,
+                    min_vertical_hz: limits.min_vertical_hz as _,
+                    max_vertical_hz: limits.max_vertical_hz as _,
+                    min_horizontal_khz: limits.min_horizontal_khz as _,
+                    max_horizontal_khz: limits.max_horizontal_khz as _,
+                    max_pixel_clock_mhz: limits.max_pixel_clock_mhz as _,
+                });
+            }
+        }
+        for prop in &*info.properties {
+            self.client.event(ConnectorProperty {
+                self_id: self.id,
+                name: &prop.name,
+                value: &prop.value,
+            });
+        }
     }
 
     fn send_error(&self, msg: &str) {
@@ -412,6 +490,134 @@ impl JayRandrRequestHandler for JayRandr {
         dev.dev.set_flip_margin(req.margin_ns);
         Ok(())
     }
+
+    fn set_outputs_powered(
+        &self,
+        req: SetOutputsPowered,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.client.state.set_outputs_powered(req.powered != 0);
+        Ok(())
+    }
+
+    fn toggle_outputs_powered(
+        &self,
+        _req: ToggleOutputsPowered,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.client.state.toggle_outputs_powered();
+        Ok(())
+    }
+
+    fn set_presentation_output(
+        &self,
+        req: SetPresentationOutput<'_>,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        if req.output.is_empty() {
+            self.client.state.set_presentation_output(None);
+            return Ok(());
+        }
+        let Some(node) = self.get_output_node(req.output) else {
+            return Ok(());
+        };
+        self.client.state.set_presentation_output(Some(&node));
+        Ok(())
+    }
+
+    fn set_hdr_enabled(&self, req: SetHdrEnabled<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(c) = self.get_output_node(req.output) else {
+            return Ok(());
+        };
+        c.global
+            .connector
+            .connector
+            .set_hdr_enabled(req.enabled != 0);
+        Ok(())
+    }
+
+    fn create_virtual_output(
+        &self,
+        req: CreateVirtualOutput,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let backend = self.client.state.backend.get();
+        let res = backend.create_virtual_output(backend::Mode {
+            width: req.width,
+            height: req.height,
+            refresh_rate_millihz: req.refresh_rate_millihz,
+        });
+        match res {
+            Ok(c) => self.client.event(VirtualOutputCreated {
+                self_id: self.id,
+                name: &c.kernel_id().to_string(),
+            }),
+            Err(e) => self.send_error(&ErrorFmt(&*e).to_string()),
+        }
+        Ok(())
+    }
+
+    fn remove_virtual_output(
+        &self,
+        req: RemoveVirtualOutput<'_>,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let Some(c) = self.get_connector(req.output) else {
+            return Ok(());
+        };
+        let backend = self.client.state.backend.get();
+        if let Err(e) = backend.remove_virtual_output(c.connector.id()) {
+            self.send_error(&ErrorFmt(&*e).to_string());
+        }
+        Ok(())
+    }
+
+    fn set_mirror(&self, req: SetMirror<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(c) = self.get_connector(req.output) else {
+            return Ok(());
+        };
+        let source = match req.source {
+            Some(source) => match self.get_connector(source) {
+                Some(s) => Some(s),
+                _ => return Ok(()),
+            },
+            None => None,
+        };
+        if let Err(e) = self.client.state.set_mirror_source(&c, source.as_deref()) {
+            self.send_error(&ErrorFmt(e).to_string());
+        }
+        Ok(())
+    }
+
+    fn set_custom_mode(&self, req: SetCustomMode<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(c) = self.get_output(req.output) else {
+            return Ok(());
+        };
+        let timing = |v: u32| u16::try_from(v).ok();
+        let mode = (|| {
+            Some(CustomMode {
+                clock_khz: req.clock_khz,
+                hdisplay: timing(req.hdisplay)?,
+                hsync_start: timing(req.hsync_start)?,
+                hsync_end: timing(req.hsync_end)?,
+                htotal: timing(req.htotal)?,
+                vdisplay: timing(req.vdisplay)?,
+                vsync_start: timing(req.vsync_start)?,
+                vsync_end: timing(req.vsync_end)?,
+                vtotal: timing(req.vtotal)?,
+                hsync_positive: req.hsync_positive != 0,
+                vsync_positive: req.vsync_positive != 0,
+            })
+        })();
+        let Some(mode) = mode.filter(|m| m.is_valid()) else {
+            self.send_error("The mode timings are invalid");
+            return Ok(());
+        };
+        if let Err(e) = c.connector.connector.set_custom_mode(&mode) {
+            self.send_error(&ErrorFmt(&*e).to_string());
+        }
+        Ok(())
+    }
 }
 
 object_base! {
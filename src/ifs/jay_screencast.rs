@@ -1,6 +1,7 @@
 use {
     crate::{
         allocator::{AllocatorError, BufferObject, BO_USE_LINEAR, BO_USE_RENDERING},
+        async_engine::SpawnedFuture,
         client::{Client, ClientError},
         format::XRGB8888,
         gfx_api::{
@@ -13,11 +14,13 @@ use {
         state::State,
         tree::{LatchListener, OutputNode, ToplevelNode, WorkspaceNode, WorkspaceNodeId},
         utils::{
+            cell_ext::CellExt,
             clonecell::{CloneCell, UnsafeCellCloneSafe},
             errorfmt::ErrorFmt,
             event_listener::EventListener,
             numcell::NumCell,
             option_ext::OptionExt,
+            transform_ext::TransformExt,
         },
         video::{dmabuf::DmaBuf, INVALID_MODIFIER, LINEAR_MODIFIER},
         wire::{jay_screencast::*, JayScreencastId},
@@ -30,6 +33,7 @@ use {
         rc::{Rc, Weak},
     },
     thiserror::Error,
+    uapi::c,
 };
 
 pub async fn perform_toplevel_screencasts(state: Rc<State>) {
@@ -46,6 +50,9 @@ pub async fn perform_screencast_realloc(state: Rc<State>) {
             .pop()
             .await;
         screencast.realloc_or_reconfigure_scheduled.set(false);
+        if screencast.destroyed.get() {
+            continue;
+        }
         match state.render_ctx.get() {
             None => screencast.do_destroy(),
             Some(ctx) => {
@@ -58,6 +65,7 @@ pub async fn perform_screencast_realloc(state: Rc<State>) {
 }
 
 pub const CLIENT_BUFFERS_SINCE: Version = Version(7);
+pub const PRESENTATION_TIME_SINCE: Version = Version(27);
 
 pub struct JayScreencast {
     pub id: JayScreencastId,
@@ -70,6 +78,8 @@ pub struct JayScreencast {
     buffers_acked: Cell<bool>,
     buffers: RefCell<Vec<ScreencastBuffer>>,
     missed_frame: Cell<bool>,
+    writeback_claimed: Cell<bool>,
+    writeback_copy_skipped: Cell<bool>,
     target: CloneCell<Option<Target>>,
     destroyed: Cell<bool>,
     running: Cell<bool>,
@@ -80,6 +90,11 @@ pub struct JayScreencast {
     need_realloc_or_reconfigure: Cell<bool>,
     realloc_or_reconfigure_scheduled: Cell<bool>,
     latch_listener: EventListener<dyn LatchListener>,
+    max_fps: Cell<u32>,
+    target_size: Cell<Option<(i32, i32)>>,
+    last_frame_nsec: Cell<u64>,
+    frame_timer: Cell<Option<SpawnedFuture<()>>>,
+    slf: Weak<Self>,
 }
 
 #[derive(Clone)]
@@ -110,6 +125,8 @@ struct Pending {
     show_workspaces: RefCell<Option<AHashSet<WorkspaceNodeId>>>,
     clear_buffers: Cell<bool>,
     buffers: RefCell<Vec<Rc<dyn GfxFramebuffer>>>,
+    max_fps: Cell<Option<u32>>,
+    target_size: Cell<Option<Option<(i32, i32)>>>,
 }
 
 struct ScreencastBuffer {
@@ -149,6 +166,8 @@ impl JayScreencast {
             buffers_acked: Cell::new(true),
             buffers: Default::default(),
             missed_frame: Cell::new(false),
+            writeback_claimed: Cell::new(false),
+            writeback_copy_skipped: Cell::new(false),
             target: Default::default(),
             destroyed: Cell::new(false),
             running: Cell::new(false),
@@ -159,9 +178,103 @@ impl JayScreencast {
             need_realloc_or_reconfigure: Cell::new(false),
             realloc_or_reconfigure_scheduled: Cell::new(false),
             latch_listener: EventListener::new(slf.clone()),
+            max_fps: Cell::new(0),
+            target_size: Cell::new(None),
+            last_frame_nsec: Cell::new(0),
+            frame_timer: Default::default(),
+            slf: slf.clone(),
+        }
+    }
+
+    /// Returns whether the frame rate limit allows another frame to be sent.
+    ///
+    /// If it does not, the screencast is damaged once the next frame is due.
+    fn frame_due(&self) -> bool {
+        let max_fps = self.max_fps.get();
+        if max_fps == 0 {
+            return true;
+        }
+        let state = &self.client.state;
+        let now = state.now_nsec();
+        let next = self.last_frame_nsec.get() + 1_000_000_000 / max_fps as u64;
+        if now >= next {
+            return true;
+        }
+        if self.frame_timer.is_none() {
+            let slf = self.slf.clone();
+            let state2 = state.clone();
+            let ms = (next - now).div_ceil(1_000_000);
+            let future = state.eng.spawn("screencast frame timer", async move {
+                if let Err(e) = state2.wheel.timeout(ms).await {
+                    log::error!("Could not wait for the next frame: {}", ErrorFmt(e));
+                }
+                if let Some(slf) = slf.upgrade() {
+                    slf.frame_timer.take();
+                    slf.damage();
+                }
+            });
+            self.frame_timer.set(Some(future));
+        }
+        false
+    }
+
+    pub fn send_clock_id(&self) {
+        if self.version >= PRESENTATION_TIME_SINCE {
+            self.client.event(ClockId {
+                self_id: self.id,
+                clock_id: c::CLOCK_MONOTONIC as _,
+            });
+        }
+    }
+
+    /// Sends a buffer to the client.
+    ///
+    /// `present_nsec` is the time at which the contents of the buffer were shown on
+    /// screen, not the time at which the buffer was filled.
+    fn send_ready(&self, idx: usize, present_nsec: u64) {
+        if self.version >= PRESENTATION_TIME_SINCE {
+            let tv_sec = present_nsec / 1_000_000_000;
+            self.client.event(PresentationTime {
+                self_id: self.id,
+                tv_sec_hi: (tv_sec >> 32) as u32,
+                tv_sec_lo: tv_sec as u32,
+                tv_nsec: (present_nsec % 1_000_000_000) as u32,
+            });
+        }
+        self.client.event(Ready {
+            self_id: self.id,
+            idx: idx as _,
+        });
+    }
+
+    fn frame_sent(&self) {
+        if self.max_fps.get() != 0 {
+            self.last_frame_nsec.set(self.client.state.now_nsec());
         }
     }
 
+    /// Returns the size of the buffers.
+    fn buffer_size(&self) -> (i32, i32) {
+        let Some(target) = self.target.get() else {
+            return (0, 0);
+        };
+        self.target_size
+            .get()
+            .unwrap_or_else(|| target_size(Some(&target)))
+    }
+
+    /// Returns the scale that fits content of the given pixel size into the buffers.
+    fn buffer_scale(&self, scale: Scale, (width, height): (i32, i32)) -> Scale {
+        let Some((tw, th)) = self.target_size.get() else {
+            return scale;
+        };
+        if width <= 0 || height <= 0 {
+            return scale;
+        }
+        let f = (tw as f64 / width as f64).min(th as f64 / height as f64);
+        Scale::from_wl(Scale::from_f64(scale.to_f64() * f).to_wl().max(1))
+    }
+
     fn schedule_toplevel_screencast(self: &Rc<Self>) {
         if !self.running.get() {
             return;
@@ -179,14 +292,22 @@ impl JayScreencast {
         let Some(target) = self.target.get() else {
             return;
         };
+        let native_size = target_size(Some(&target));
         let Target::Toplevel(tl) = target else {
             log::warn!("Tried to perform window screencast for output screencast");
             return;
         };
-        let scale = match tl.tl_data().workspace.get() {
-            None => Scale::default(),
-            Some(w) => w.output.get().global.persistent.scale.get(),
+        if !self.frame_due() {
+            return;
+        }
+        let (mut scale, present_nsec) = match tl.tl_data().workspace.get() {
+            None => (Scale::default(), self.client.state.now_nsec()),
+            Some(w) => {
+                let output = w.output.get();
+                (output.global.persistent.scale.get(), output.present_nsec())
+            }
         };
+        scale = self.buffer_scale(scale, native_size);
         let mut buffer = self.buffers.borrow_mut();
         for (idx, buffer) in buffer.deref_mut().iter_mut().enumerate() {
             if buffer.free {
@@ -201,14 +322,13 @@ impl JayScreencast {
                     true,
                     false,
                     false,
+                    true,
                     Transform::None,
                 );
                 match res {
                     Ok(_) => {
-                        self.client.event(Ready {
-                            self_id: self.id,
-                            idx: idx as _,
-                        });
+                        self.frame_sent();
+                        self.send_ready(idx, present_nsec);
                         buffer.free = false;
                         return;
                     }
@@ -260,7 +380,7 @@ impl JayScreencast {
         self.config_acked.set(false);
         let serial = self.config_serial.fetch_add(1) + 1;
         if let Some(target) = self.target.get() {
-            let (width, height) = target_size(Some(&target));
+            let (width, height) = self.buffer_size();
             if self.version >= CLIENT_BUFFERS_SINCE {
                 self.client.event(ConfigSize {
                     self_id: self.id,
@@ -311,17 +431,28 @@ impl JayScreencast {
         y_off: i32,
         size: Option<(i32, i32)>,
     ) {
-        if !self.running.get() {
+        if !self.running.get() || !self.shows_output(on) {
             return;
         }
-        if !self.show_all.get() {
-            let ws = match on.workspace.get() {
-                Some(ws) => ws,
-                _ => return,
-            };
-            if !self.show_workspaces.borrow_mut().contains(&ws.id) {
-                return;
-            }
+        if self.writeback_claimed.get() {
+            self.writeback_copy_skipped.set(true);
+            return;
+        }
+        if !self.frame_due() {
+            return;
+        }
+        let mut scale = on.global.persistent.scale.get();
+        let (mut x_off, mut y_off, mut size) = (x_off, y_off, size);
+        if self.target_size.is_some() {
+            let transform = on.global.persistent.transform.get();
+            let (width, height) = size.unwrap_or(transform.maybe_swap(texture.size()));
+            let buffer_scale = self.buffer_scale(scale, on.global.pixel_size());
+            let f = buffer_scale.to_f64() / scale.to_f64();
+            let apply = |v: i32| (v as f64 * f).round() as i32;
+            size = Some((apply(width), apply(height)));
+            x_off = apply(x_off);
+            y_off = apply(y_off);
+            scale = buffer_scale;
         }
         let mut buffer = self.buffers.borrow_mut();
         for (idx, buffer) in buffer.deref_mut().iter_mut().enumerate() {
@@ -341,14 +472,13 @@ impl JayScreencast {
                     y_off,
                     size,
                     on.global.persistent.transform.get(),
-                    on.global.persistent.scale.get(),
+                    scale,
+                    on.capture_color_transform(),
                 );
                 match res {
                     Ok(_) => {
-                        self.client.event(Ready {
-                            self_id: self.id,
-                            idx: idx as _,
-                        });
+                        self.frame_sent();
+                        self.send_ready(idx, on.present_nsec());
                         buffer.free = false;
                         return;
                     }
@@ -363,8 +493,59 @@ impl JayScreencast {
         self.client.event(MissedFrame { self_id: self.id })
     }
 
+    fn shows_output(&self, on: &OutputNode) -> bool {
+        if self.show_all.get() {
+            return true;
+        }
+        match on.workspace.get() {
+            Some(ws) => self.show_workspaces.borrow_mut().contains(&ws.id),
+            _ => false,
+        }
+    }
+
+    /// Claims a free buffer that can be filled by a writeback connector.
+    ///
+    /// Writeback connectors write the contents of the CRTC, therefore the buffer must
+    /// have the size of the mode and one of the formats supported by the connector.
+    pub fn claim_writeback_buffer(
+        self: &Rc<Self>,
+        on: &OutputNode,
+        formats: &AHashSet<u32>,
+    ) -> Option<ScreencastWriteback> {
+        if !self.running.get() || self.writeback_claimed.get() || !self.shows_output(on) {
+            return None;
+        }
+        if !self.frame_due() {
+            return None;
+        }
+        let mode = on.global.mode.get();
+        for buffer in self.buffers.borrow_mut().iter_mut() {
+            let Some(dmabuf) = &buffer.dmabuf else {
+                continue;
+            };
+            if !buffer.free
+                || (dmabuf.width, dmabuf.height) != (mode.width, mode.height)
+                || !formats.contains(&dmabuf.format.drm)
+            {
+                continue;
+            }
+            buffer.free = false;
+            self.writeback_claimed.set(true);
+            self.frame_sent();
+            return Some(ScreencastWriteback {
+                screencast: self.clone(),
+                dmabuf: dmabuf.clone(),
+                present_nsec: on.present_nsec(),
+                committed: Cell::new(false),
+                done: Cell::new(false),
+            });
+        }
+        None
+    }
+
     fn detach(&self) {
         self.latch_listener.detach();
+        self.frame_timer.take();
         if let Some(target) = self.target.take() {
             match target {
                 Target::Output(output) => {
@@ -419,8 +600,8 @@ impl JayScreencast {
             Some(f) => f,
             _ => return Err(JayScreencastError::XRGB8888),
         };
-        if let Some(target) = self.target.get() {
-            let (width, height) = target_size(Some(&target));
+        if self.target.is_some() {
+            let (width, height) = self.buffer_size();
             let num = 3;
             for _ in 0..num {
                 if width == 0 || height == 0 {
@@ -580,6 +761,7 @@ impl JayScreencastRequestHandler for JayScreencast {
         }
 
         let mut need_realloc_or_reconfigure = false;
+        let old_size = self.buffer_size();
 
         if let Some(target) = self.pending.target.take() {
             self.detach();
@@ -610,11 +792,18 @@ impl JayScreencastRequestHandler for JayScreencast {
                     }
                 }
             }
-            if target_size(new_target.as_ref()) != target_size(self.target.get().as_ref()) {
-                need_realloc_or_reconfigure = true;
-            }
             self.target.set(new_target);
         }
+        if let Some(target_size) = self.pending.target_size.take() {
+            self.target_size.set(target_size);
+        }
+        if self.buffer_size() != old_size {
+            need_realloc_or_reconfigure = true;
+        }
+        if let Some(max_fps) = self.pending.max_fps.take() {
+            self.max_fps.set(max_fps);
+            self.frame_timer.take();
+        }
         if let Some(linear) = self.pending.linear.take() {
             if self.linear.replace(linear) != linear {
                 need_realloc_or_reconfigure = true;
@@ -728,7 +917,10 @@ impl JayScreencastRequestHandler for JayScreencast {
             return Ok(());
         }
         let buffer = self.client.lookup(req.buffer)?;
-        if let Some(WlBufferStorage::Dmabuf { img, .. }) = &*buffer.storage.borrow() {
+        if let Some(WlBufferStorage::Dmabuf {
+            img, bridge: None, ..
+        }) = &*buffer.storage.borrow()
+        {
             match img.clone().to_framebuffer() {
                 Ok(fb) => self.pending.buffers.borrow_mut().push(fb),
                 Err(e) => {
@@ -743,6 +935,27 @@ impl JayScreencastRequestHandler for JayScreencast {
         }
         Err(JayScreencastError::NotDmabuf)
     }
+
+    fn set_max_fps(&self, req: SetMaxFps, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.destroyed.get() {
+            return Ok(());
+        }
+        self.pending.max_fps.set(Some(req.max_fps));
+        Ok(())
+    }
+
+    fn set_target_size(&self, req: SetTargetSize, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let size = match (req.width, req.height) {
+            (0, 0) => None,
+            (w, h) if w > 0 && h > 0 => Some((w, h)),
+            (w, h) => return Err(JayScreencastError::InvalidTargetSize(w, h)),
+        };
+        if self.destroyed.get() {
+            return Ok(());
+        }
+        self.pending.target_size.set(Some(size));
+        Ok(())
+    }
 }
 
 object_base! {
@@ -753,6 +966,9 @@ object_base! {
 impl Object for JayScreencast {
     fn break_loops(&self) {
         self.detach();
+        self.buffers.borrow_mut().clear();
+        self.pending.target.take();
+        self.destroyed.set(true);
     }
 }
 
@@ -776,9 +992,72 @@ pub enum JayScreencastError {
     Modifier,
     #[error("Buffer is not a dmabuf")]
     NotDmabuf,
+    #[error("{0}x{1} is not a valid target size")]
+    InvalidTargetSize(i32, i32),
 }
 efrom!(JayScreencastError, ClientError);
 
+/// A screencast buffer that is filled by a writeback connector.
+///
+/// If this object is dropped before the writeback has completed, the buffer is released
+/// and, if the frame was not copied otherwise, the client is informed about the missed
+/// frame.
+pub struct ScreencastWriteback {
+    screencast: Rc<JayScreencast>,
+    pub dmabuf: DmaBuf,
+    present_nsec: u64,
+    committed: Cell<bool>,
+    done: Cell<bool>,
+}
+
+impl ScreencastWriteback {
+    /// Marks the writeback job as committed to the kernel.
+    pub fn committed(&self) {
+        self.committed.set(true);
+        self.screencast.writeback_claimed.set(false);
+        self.screencast.writeback_copy_skipped.set(false);
+    }
+
+    /// Marks the writeback job as completed and sends the buffer to the client.
+    pub fn complete(&self) {
+        self.done.set(true);
+        let sc = &self.screencast;
+        if let Some(idx) = sc.writeback_buffer_idx(&self.dmabuf) {
+            sc.send_ready(idx, self.present_nsec);
+        }
+    }
+}
+
+impl Drop for ScreencastWriteback {
+    fn drop(&mut self) {
+        if self.done.get() {
+            return;
+        }
+        let sc = &self.screencast;
+        let mut missed = self.committed.get();
+        if !missed {
+            sc.writeback_claimed.set(false);
+            missed = sc.writeback_copy_skipped.take();
+        }
+        if let Some(idx) = sc.writeback_buffer_idx(&self.dmabuf) {
+            sc.buffers.borrow_mut()[idx].free = true;
+        }
+        if missed && !sc.destroyed.get() {
+            sc.missed_frame.set(true);
+            sc.client.event(MissedFrame { self_id: sc.id });
+        }
+    }
+}
+
+impl JayScreencast {
+    fn writeback_buffer_idx(&self, dmabuf: &DmaBuf) -> Option<usize> {
+        self.buffers
+            .borrow()
+            .iter()
+            .position(|b| b.dmabuf.as_ref().map(|d| d.id) == Some(dmabuf.id))
+    }
+}
+
 fn target_size(target: Option<&Target>) -> (i32, i32) {
     if let Some(target) = target {
         return match target {
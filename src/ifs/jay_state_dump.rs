@@ -0,0 +1,410 @@
+use {
+    crate::{
+        backend::InputDeviceCapability,
+        client::{Client, ClientError},
+        ifs::wl_surface::{
+            ext_session_lock_surface_v1::ExtSessionLockSurfaceV1,
+            tray::jay_tray_item_v1::JayTrayItemV1,
+            x_surface::xwindow::Xwindow,
+            xdg_surface::{xdg_popup::XdgPopup, xdg_toplevel::XdgToplevel},
+            zwlr_layer_surface_v1::ZwlrLayerSurfaceV1,
+            WlSurface,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        state::State,
+        tree::{
+            ContainerNode, DisplayNode, FloatNode, Node, NodeVisitorBase, OutputNode,
+            PlaceholderNode, ToplevelNodeBase, WorkspaceNode,
+        },
+        utils::{errorfmt::ErrorFmt, oserror::OsError},
+        wire::{jay_state_dump::*, JayStateDumpId},
+    },
+    std::{
+        fmt::{Display, Write as _},
+        io::Write as _,
+        rc::Rc,
+    },
+    thiserror::Error,
+    uapi::{c, OwnedFd},
+};
+
+/// Sends a snapshot of the compositor state for bug reports.
+///
+/// The snapshot does not contain window titles or serial numbers of monitors.
+pub struct JayStateDump {
+    pub id: JayStateDumpId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+}
+
+impl JayStateDump {
+    pub fn send_dump(&self) {
+        let state = &self.client.state;
+        let files = [
+            ("compositor.txt", dump_compositor(state)),
+            ("tree.txt", dump_tree(state)),
+            ("outputs.txt", dump_outputs(state)),
+            ("input-devices.txt", dump_input_devices(state)),
+            ("formats.txt", dump_formats(state)),
+            ("clients.txt", dump_clients(state)),
+        ];
+        for (name, contents) in files {
+            match create_memfd(&contents) {
+                Ok(fd) => self.client.event(File {
+                    self_id: self.id,
+                    name,
+                    contents: Rc::new(fd),
+                }),
+                Err(e) => log::error!("Could not create a memfd for {}: {}", name, ErrorFmt(e)),
+            }
+        }
+        self.client.event(Done { self_id: self.id });
+    }
+}
+
+fn create_memfd(contents: &str) -> Result<OwnedFd, OsError> {
+    let mut memfd = uapi::memfd_create("jay-state-dump", c::MFD_CLOEXEC)?;
+    memfd.write_all(contents.as_bytes())?;
+    uapi::lseek(memfd.raw(), 0, c::SEEK_SET)?;
+    Ok(memfd)
+}
+
+fn dump_compositor(state: &State) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "version: {}", env!("CARGO_PKG_VERSION"));
+    match state.render_ctx.get() {
+        Some(ctx) => {
+            let _ = writeln!(out, "gfx api: {:?}", ctx.gfx_api());
+        }
+        None => {
+            let _ = writeln!(out, "gfx api: none");
+        }
+    }
+    let mut devs: Vec<_> = state.drm_devs.lock().values().cloned().collect();
+    devs.sort_by(|a, b| a.devnode.cmp(&b.devnode));
+    for dev in devs {
+        let _ = writeln!(
+            out,
+            "drm device: {} ({} {})",
+            dev.devnode.as_deref().unwrap_or("unknown"),
+            dev.vendor.as_deref().unwrap_or("unknown"),
+            dev.model.as_deref().unwrap_or("unknown"),
+        );
+    }
+    let _ = writeln!(out, "explicit sync: {}", state.explicit_sync_enabled.get());
+    let _ = writeln!(
+        out,
+        "force implicit sync: {}",
+        state.force_implicit_sync.get()
+    );
+    let _ = writeln!(
+        out,
+        "direct scanout: {}",
+        state.direct_scanout_enabled.get()
+    );
+    out
+}
+
+struct TreeDumper {
+    out: String,
+    depth: usize,
+}
+
+impl TreeDumper {
+    fn node(&mut self, node: &dyn Node, desc: impl Display) {
+        let pos = node.node_absolute_position();
+        let _ = writeln!(
+            self.out,
+            "{:indent$}{} {}x{}+{}+{}{}",
+            "",
+            desc,
+            pos.width(),
+            pos.height(),
+            pos.x1(),
+            pos.y1(),
+            if node.node_visible() { "" } else { " hidden" },
+            indent = self.depth * 2,
+        );
+        self.depth += 1;
+        node.node_visit_children(self);
+        self.depth -= 1;
+    }
+}
+
+impl NodeVisitorBase for TreeDumper {
+    fn visit_surface(&mut self, node: &Rc<WlSurface>) {
+        self.node(&**node, format_args!("surface client={}", node.client.id));
+    }
+
+    fn visit_container(&mut self, node: &Rc<ContainerNode>) {
+        let mono = match node.mono_child.is_some() {
+            true => " mono",
+            false => "",
+        };
+        self.node(
+            &**node,
+            format_args!(
+                "container identifier={} {:?}{}",
+                node.tl_data().identifier.get(),
+                node.split.get(),
+                mono,
+            ),
+        );
+    }
+
+    fn visit_toplevel(&mut self, node: &Rc<XdgToplevel>) {
+        let data = node.tl_data();
+        let app_id = data.app_id.borrow().clone();
+        self.node(
+            &**node,
+            format_args!(
+                "xdg-toplevel identifier={} app-id={:?}",
+                data.identifier.get(),
+                app_id,
+            ),
+        );
+    }
+
+    fn visit_popup(&mut self, node: &Rc<XdgPopup>) {
+        self.node(&**node, "xdg-popup");
+    }
+
+    fn visit_display(&mut self, node: &Rc<DisplayNode>) {
+        self.node(&**node, "display");
+    }
+
+    fn visit_output(&mut self, node: &Rc<OutputNode>) {
+        self.node(
+            &**node,
+            format_args!("output {}", node.global.connector.name),
+        );
+    }
+
+    fn visit_float(&mut self, node: &Rc<FloatNode>) {
+        self.node(&**node, "float");
+    }
+
+    fn visit_workspace(&mut self, node: &Rc<WorkspaceNode>) {
+        self.node(
+            &**node,
+            format_args!(
+                "workspace identifier={} {:?}",
+                node.identifier,
+                node.name.borrow(),
+            ),
+        );
+    }
+
+    fn visit_layer_surface(&mut self, node: &Rc<ZwlrLayerSurfaceV1>) {
+        self.node(&**node, "layer-surface");
+    }
+
+    fn visit_xwindow(&mut self, node: &Rc<Xwindow>) {
+        let class = node.data.info.class.borrow().clone();
+        self.node(
+            &**node,
+            format_args!(
+                "xwindow identifier={} class={:?}",
+                node.tl_data().identifier.get(),
+                class,
+            ),
+        );
+    }
+
+    fn visit_placeholder(&mut self, node: &Rc<PlaceholderNode>) {
+        self.node(
+            &**node,
+            format_args!("placeholder identifier={}", node.tl_data().identifier.get()),
+        );
+    }
+
+    fn visit_lock_surface(&mut self, node: &Rc<ExtSessionLockSurfaceV1>) {
+        self.node(&**node, "lock-surface");
+    }
+
+    fn visit_tray_item(&mut self, node: &Rc<JayTrayItemV1>) {
+        self.node(&**node, "tray-item");
+    }
+}
+
+fn dump_tree(state: &State) -> String {
+    let mut dumper = TreeDumper {
+        out: String::new(),
+        depth: 0,
+    };
+    dumper.visit_display(&state.root);
+    dumper.out
+}
+
+fn dump_outputs(state: &State) -> String {
+    let mut out = String::new();
+    let mut connectors: Vec<_> = state.connectors.lock().values().cloned().collect();
+    connectors.sort_by(|a, b| a.name.cmp(&b.name));
+    for connector in connectors {
+        let _ = writeln!(out, "{}:", connector.name);
+        let _ = writeln!(out, "  connected: {}", connector.connected.get());
+        if let Some(dev) = &connector.drm_dev {
+            if let Some(devnode) = &dev.devnode {
+                let _ = writeln!(out, "  drm device: {}", devnode);
+            }
+        }
+        let Some(output) = state.outputs.get(&connector.connector.id()) else {
+            continue;
+        };
+        let info = &output.monitor_info;
+        let _ = writeln!(
+            out,
+            "  monitor: {} {}",
+            info.output_id.manufacturer, info.output_id.model,
+        );
+        let _ = writeln!(out, "  size: {}x{} mm", info.width_mm, info.height_mm);
+        let _ = writeln!(out, "  non-desktop: {}", info.non_desktop);
+        let _ = writeln!(out, "  vrr capable: {}", info.vrr_capable);
+        let _ = writeln!(out, "  hdr capable: {}", info.hdr_capable);
+        if let Some(node) = &output.node {
+            let global = &node.global;
+            let mode = global.mode.get();
+            let pos = global.pos.get();
+            let persistent = &global.persistent;
+            let _ = writeln!(
+                out,
+                "  mode: {}x{}@{}",
+                mode.width,
+                mode.height,
+                mode.refresh_rate_millihz as f64 / 1000.0,
+            );
+            let _ = writeln!(out, "  position: {}x{}", pos.x1(), pos.y1());
+            let _ = writeln!(out, "  scale: {}", persistent.scale.get());
+            let _ = writeln!(out, "  transform: {:?}", persistent.transform.get());
+            let _ = writeln!(out, "  vrr mode: {:?}", persistent.vrr_mode.get());
+            let _ = writeln!(out, "  tearing mode: {:?}", persistent.tearing_mode.get());
+            let _ = writeln!(out, "  format: {}", global.format.get().name);
+            let _ = writeln!(out, "  hdr enabled: {}", global.hdr_enabled.get());
+            let formats: Vec<_> = global.formats.get().iter().map(|f| f.name).collect();
+            let _ = writeln!(out, "  scanout formats: {}", formats.join(", "));
+        }
+        let _ = writeln!(out, "  modes:");
+        for mode in &info.modes {
+            let _ = writeln!(
+                out,
+                "    {}x{}@{}",
+                mode.width,
+                mode.height,
+                mode.refresh_rate_millihz as f64 / 1000.0,
+            );
+        }
+    }
+    out
+}
+
+fn dump_input_devices(state: &State) -> String {
+    use InputDeviceCapability::*;
+    let mut out = String::new();
+    let devices = state.input_device_handlers.borrow();
+    let mut devices: Vec<_> = devices.values().collect();
+    devices.sort_by_key(|d| d.id.raw());
+    for dev in devices {
+        let data = &dev.data;
+        let _ = writeln!(out, "{}: {}", dev.id, data.device.name());
+        if let Some(syspath) = &data.syspath {
+            let _ = writeln!(out, "  syspath: {}", syspath);
+        }
+        if let Some(devnode) = &data.devnode {
+            let _ = writeln!(out, "  devnode: {}", devnode);
+        }
+        let caps: Vec<_> = [
+            Keyboard, Pointer, Touch, TabletTool, TabletPad, Gesture, Switch,
+        ]
+        .into_iter()
+        .filter(|c| data.device.has_capability(*c))
+        .map(|c| format!("{:?}", c))
+        .collect();
+        let _ = writeln!(out, "  capabilities: {}", caps.join(", "));
+        if let Some(seat) = data.seat.get() {
+            let _ = writeln!(out, "  seat: {}", seat.seat_name());
+        }
+        let _ = writeln!(out, "  custom keymap: {}", data.keymap.is_some());
+        let mut remap: Vec<_> = data
+            .key_remap
+            .map
+            .borrow()
+            .iter()
+            .map(|(k, v)| (*k, *v))
+            .collect();
+        if !remap.is_empty() {
+            remap.sort();
+            let _ = writeln!(out, "  key remap: {:?}", remap);
+        }
+    }
+    out
+}
+
+fn dump_formats(state: &State) -> String {
+    let mut out = String::new();
+    let Some(ctx) = state.render_ctx.get() else {
+        return out;
+    };
+    let formats = ctx.formats();
+    let mut formats: Vec<_> = formats.values().collect();
+    formats.sort_by_key(|f| f.format.name);
+    for format in formats {
+        let _ = writeln!(out, "{} (0x{:08x}):", format.format.name, format.format.drm);
+        let _ = writeln!(out, "  read modifiers:");
+        for modifier in &format.read_modifiers {
+            let _ = writeln!(out, "    0x{:016x}", modifier);
+        }
+        let _ = writeln!(out, "  write modifiers:");
+        for modifier in format.write_modifiers.keys() {
+            let _ = writeln!(out, "    0x{:016x}", modifier);
+        }
+    }
+    out
+}
+
+fn dump_clients(state: &State) -> String {
+    let mut out = String::new();
+    let clients = state.clients.clients.borrow();
+    let mut clients: Vec<_> = clients.values().map(|c| &c.data).collect();
+    clients.sort_by_key(|c| c.id);
+    for client in clients {
+        let pid_info = &client.pid_info;
+        let _ = writeln!(
+            out,
+            "{}: {} (pid {}){}",
+            client.id,
+            pid_info.comm,
+            pid_info.pid,
+            if client.is_xwayland { " xwayland" } else { "" },
+        );
+        for (interface, count) in client.objects.interface_counts() {
+            let _ = writeln!(out, "  {}: {}", interface, count);
+        }
+    }
+    out
+}
+
+impl JayStateDumpRequestHandler for JayStateDump {
+    type Error = JayStateDumpError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = JayStateDump;
+    version = Version(1);
+}
+
+impl Object for JayStateDump {}
+
+simple_add_obj!(JayStateDump);
+
+#[derive(Debug, Error)]
+pub enum JayStateDumpError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(JayStateDumpError, ClientError);
@@ -0,0 +1,115 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        leaks::Tracker,
+        object::{Object, Version},
+        tree::OutputNode,
+        wire::{jay_stats::*, JayStatsId},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+    uapi::c,
+};
+
+const CLIENT_SINCE: Version = Version(28);
+const CPU_TIME_SINCE: Version = Version(31);
+
+pub struct JayStats {
+    pub id: JayStatsId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl JayStats {
+    fn send_output(&self, output: &OutputNode) {
+        let stats = &output.stats;
+        self.client.event(Output {
+            self_id: self.id,
+            name: &output.global.connector.name,
+            frames: stats.frames.get(),
+            missed_vblanks: stats.missed_vblanks.get(),
+            frame_time_avg_ns: stats.frame_time.avg_nsec(),
+            frame_time_max_ns: stats.frame_time.max_nsec(),
+            render_time_avg_ns: stats.render_time.avg_nsec(),
+            render_time_max_ns: stats.render_time.max_nsec(),
+        });
+    }
+
+    fn send_client_resources(&self, client: &Client) {
+        self.client.event(ClientResources {
+            self_id: self.id,
+            id: client.id.raw(),
+            pid: client.pid_info.pid as u32,
+            comm: &client.pid_info.comm,
+            fds: client.resources.fds.get(),
+            memory: client.resources.memory.get(),
+        });
+    }
+
+    fn send_texture_uploads(&self) {
+        self.client.event(TextureUploads {
+            self_id: self.id,
+            count: self.client.state.texture_uploads.get(),
+        });
+    }
+
+    fn send_cpu_time(&self) {
+        let mut time: c::timespec = uapi::pod_zeroed();
+        let _ = uapi::clock_gettime(c::CLOCK_PROCESS_CPUTIME_ID, &mut time);
+        self.client.event(CpuTime {
+            self_id: self.id,
+            nsec: time.tv_sec as u64 * 1_000_000_000 + time.tv_nsec as u64,
+        });
+    }
+}
+
+impl JayStatsRequestHandler for JayStats {
+    type Error = JayStatsError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get(&self, _req: Get, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        for output in self.client.state.root.outputs.lock().values() {
+            self.send_output(output);
+        }
+        self.send_texture_uploads();
+        if self.version >= CLIENT_SINCE {
+            for client in self.client.state.clients.clients.borrow().values() {
+                self.send_client_resources(&client.data);
+            }
+        }
+        if self.version >= CPU_TIME_SINCE {
+            self.send_cpu_time();
+        }
+        Ok(())
+    }
+
+    fn reset(&self, _req: Reset, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let state = &self.client.state;
+        for output in state.root.outputs.lock().values() {
+            output.stats.reset();
+        }
+        state.texture_uploads.set(0);
+        Ok(())
+    }
+}
+
+object_base! {
+    self = JayStats;
+    version = self.version;
+}
+
+impl Object for JayStats {}
+
+simple_add_obj!(JayStats);
+
+#[derive(Debug, Error)]
+pub enum JayStatsError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(JayStatsError, ClientError);
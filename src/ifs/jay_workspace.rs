@@ -11,16 +11,22 @@ use {
     thiserror::Error,
 };
 
+pub const ID_SINCE: Version = Version(29);
+
 pub struct JayWorkspace {
     pub id: JayWorkspaceId,
     pub client: Rc<Client>,
     pub workspace: CloneCell<Option<Rc<WorkspaceNode>>>,
     pub tracker: Tracker<Self>,
+    pub version: Version,
 }
 
 impl JayWorkspace {
     pub fn send_initial_properties(&self, workspace: &WorkspaceNode) {
         self.send_linear_id(workspace);
+        if self.version >= ID_SINCE {
+            self.send_id(workspace);
+        }
         self.send_name(workspace);
         self.send_output(&workspace.output.get());
         self.send_visible(workspace.visible.get());
@@ -34,10 +40,18 @@ impl JayWorkspace {
         });
     }
 
+    pub fn send_id(&self, ws: &WorkspaceNode) {
+        let s = ws.identifier.to_string();
+        self.client.event(Id {
+            self_id: self.id,
+            id: &s,
+        });
+    }
+
     pub fn send_name(&self, ws: &WorkspaceNode) {
         self.client.event(Name {
             self_id: self.id,
-            name: &ws.name,
+            name: &ws.name.borrow(),
         });
     }
 
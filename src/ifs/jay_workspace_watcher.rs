@@ -16,6 +16,7 @@ pub struct JayWorkspaceWatcher {
     pub id: JayWorkspaceWatcherId,
     pub client: Rc<Client>,
     pub tracker: Tracker<Self>,
+    pub version: Version,
 }
 
 impl JayWorkspaceWatcher {
@@ -25,6 +26,7 @@ impl JayWorkspaceWatcher {
             client: self.client.clone(),
             workspace: CloneCell::new(Some(workspace.clone())),
             tracker: Default::default(),
+            version: self.version,
         });
         track!(self.client, jw);
         self.client.add_server_obj(&jw);
@@ -1,9 +1,10 @@
 use {
     crate::{
-        client::{Client, ClientError},
+        client::{Client, ClientError, ResourceCharge},
         clientmem::{ClientMem, ClientMemError, ClientMemOffset},
+        dmabuf_bridge::{self, DmabufBridge},
         format::{Format, ARGB8888},
-        gfx_api::{GfxError, GfxFramebuffer, GfxImage, GfxTexture},
+        gfx_api::{AcquireSync, GfxError, GfxFramebuffer, GfxImage, GfxTexture, ReleaseSync},
         ifs::wl_surface::WlSurface,
         leaks::Tracker,
         object::{Object, Version},
@@ -29,6 +30,7 @@ pub enum WlBufferStorage {
         img: Rc<dyn GfxImage>,
         tex: Option<Rc<dyn GfxTexture>>,
         fb: Option<Rc<dyn GfxFramebuffer>>,
+        bridge: Option<Rc<DmabufBridge>>,
     },
 }
 
@@ -45,6 +47,7 @@ pub struct WlBuffer {
     pub color: Option<Color>,
     width: i32,
     height: i32,
+    _charge: Option<Rc<ResourceCharge>>,
     pub tracker: Tracker<Self>,
 }
 
@@ -63,6 +66,8 @@ impl WlBuffer {
         format: &'static Format,
         dmabuf: DmaBuf,
         img: &Rc<dyn GfxImage>,
+        bridge: Option<Rc<DmabufBridge>>,
+        charge: ResourceCharge,
     ) -> Self {
         let width = img.width();
         let height = img.height();
@@ -80,8 +85,10 @@ impl WlBuffer {
                 img: img.clone(),
                 tex: None,
                 fb: None,
+                bridge,
             })),
             shm: false,
+            _charge: Some(Rc::new(charge)),
             tracker: Default::default(),
             color: None,
         }
@@ -97,6 +104,7 @@ impl WlBuffer {
         stride: i32,
         format: &'static Format,
         mem: &Rc<ClientMem>,
+        charge: &Rc<ResourceCharge>,
     ) -> Result<Self, WlBufferError> {
         let Some(shm_info) = &format.shm_info else {
             return Err(WlBufferError::UnsupportedShmFormat(format.name));
@@ -123,6 +131,7 @@ impl WlBuffer {
             shm: true,
             width,
             height,
+            _charge: Some(charge.clone()),
             tracker: Default::default(),
             color: None,
         })
@@ -148,6 +157,7 @@ impl WlBuffer {
             shm: false,
             width: 1,
             height: 1,
+            _charge: None,
             tracker: Default::default(),
             color: Some(Color::from_u32_rgba_premultiplied(r, g, b, a)),
         }
@@ -161,7 +171,12 @@ impl WlBuffer {
         let had_texture = self.reset_gfx_objects(surface);
         if had_texture {
             if let Some(surface) = surface {
-                self.update_texture_or_log(surface, true);
+                let acquire_sync = match surface.buffer.get().map(|b| b.release_sync) {
+                    // The commit timeline has already waited for the acquire point.
+                    Some(ReleaseSync::Explicit) => AcquireSync::Unnecessary,
+                    _ => AcquireSync::Implicit,
+                };
+                self.update_texture_or_log(surface, true, acquire_sync);
             }
         }
     }
@@ -191,8 +206,8 @@ impl WlBuffer {
         let Some(dmabuf) = &self.dmabuf else {
             return false;
         };
-        let img = match ctx.dmabuf_img(dmabuf) {
-            Ok(image) => image,
+        let (img, bridge) = match dmabuf_bridge::import_dmabuf(&self.client.state, &ctx, dmabuf) {
+            Ok(res) => res,
             Err(e) => {
                 log::error!(
                     "Cannot re-import wl_buffer after graphics context change: {}",
@@ -205,6 +220,7 @@ impl WlBuffer {
             img,
             tex: None,
             fb: None,
+            bridge,
         });
         had_texture
     }
@@ -224,13 +240,35 @@ impl WlBuffer {
         }
     }
 
-    pub fn update_texture_or_log(&self, surface: &WlSurface, sync_shm: bool) {
-        if let Err(e) = self.update_texture(surface, sync_shm) {
+    /// Returns the synchronization to use when sampling the texture of this buffer.
+    pub fn texture_sync(&self, release_sync: ReleaseSync) -> (AcquireSync, ReleaseSync) {
+        if let Some(WlBufferStorage::Dmabuf {
+            bridge: Some(bridge),
+            ..
+        }) = &*self.storage.borrow()
+        {
+            return (bridge.acquire_sync(), ReleaseSync::Implicit);
+        }
+        (AcquireSync::Unnecessary, release_sync)
+    }
+
+    pub fn update_texture_or_log(
+        &self,
+        surface: &WlSurface,
+        sync_shm: bool,
+        acquire_sync: AcquireSync,
+    ) {
+        if let Err(e) = self.update_texture(surface, sync_shm, acquire_sync) {
             log::warn!("Could not update texture: {}", ErrorFmt(e));
         }
     }
 
-    fn update_texture(&self, surface: &WlSurface, sync_shm: bool) -> Result<(), WlBufferError> {
+    fn update_texture(
+        &self,
+        surface: &WlSurface,
+        sync_shm: bool,
+        acquire_sync: AcquireSync,
+    ) -> Result<(), WlBufferError> {
         let storage = &mut *self.storage.borrow_mut();
         let storage = match storage {
             Some(s) => s,
@@ -248,15 +286,21 @@ impl WlBuffer {
                             &self.client.state.cpu_worker,
                         )?;
                         mem.access(|mem| tex.clone().sync_upload(mem, Region::new2(self.rect)))??;
+                        self.client.state.texture_uploads.fetch_add(1);
                         surface.shm_textures.front().tex.set(Some(tex));
                         surface.shm_textures.front().damage.clear();
                     }
                 }
             }
-            WlBufferStorage::Dmabuf { img, tex, .. } => {
+            WlBufferStorage::Dmabuf {
+                img, tex, bridge, ..
+            } => {
                 if tex.is_none() {
                     *tex = Some(img.clone().to_texture()?);
                 }
+                if let Some(bridge) = bridge {
+                    bridge.copy(acquire_sync)?;
+                }
             }
         }
         Ok(())
@@ -272,7 +316,12 @@ impl WlBuffer {
             WlBufferStorage::Shm { .. } => {
                 // nothing
             }
-            WlBufferStorage::Dmabuf { img, fb, .. } => {
+            WlBufferStorage::Dmabuf {
+                img, fb, bridge, ..
+            } => {
+                if bridge.is_some() {
+                    return Err(WlBufferError::CrossDevice);
+                }
                 if fb.is_none() {
                     *fb = Some(img.clone().to_framebuffer()?);
                 }
@@ -319,6 +368,8 @@ pub enum WlBufferError {
     ClientError(Box<ClientError>),
     #[error("Buffer format {0} is not supported for shm buffers")]
     UnsupportedShmFormat(&'static str),
+    #[error("The buffer is located on a device that the renderer cannot write to")]
+    CrossDevice,
 }
 efrom!(WlBufferError, ClientMemError);
 efrom!(WlBufferError, ClientError);
@@ -1,6 +1,7 @@
 use {
     crate::{
         client::{Client, ClientError},
+        dmabuf_bridge,
         gfx_api::GfxError,
         globals::{Global, GlobalName},
         ifs::wl_buffer::WlBuffer,
@@ -156,13 +157,16 @@ impl WlDrmRequestHandler for WlDrm {
                 }
             }
         }
-        let img = ctx.dmabuf_img(&dmabuf)?;
+        let charge = self.client.charge_dmabuf(&dmabuf)?;
+        let (img, bridge) = dmabuf_bridge::import_dmabuf(&self.client.state, &ctx, &dmabuf)?;
         let buffer = Rc::new(WlBuffer::new_dmabuf(
             req.id,
             &self.client,
             format,
             dmabuf,
             &img,
+            bridge,
+            charge,
         ));
         track!(self.client, buffer);
         self.client.add_client_obj(&buffer)?;
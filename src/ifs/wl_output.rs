@@ -60,10 +60,13 @@ pub struct WlOutputGlobal {
     pub pos: Cell<Rect>,
     pub output_id: Rc<OutputId>,
     pub mode: Cell<backend::Mode>,
+    /// The requested mode if the current mode was chosen as a fallback.
+    pub requested_mode: Cell<Option<backend::Mode>>,
     pub refresh_nsec: Cell<u64>,
     pub modes: Vec<backend::Mode>,
     pub formats: CloneCell<Rc<Vec<&'static Format>>>,
     pub format: Cell<&'static Format>,
+    pub hdr_enabled: Cell<bool>,
     pub width_mm: i32,
     pub height_mm: i32,
     pub bindings: RefCell<AHashMap<ClientId, AHashMap<WlOutputId, Rc<WlOutput>>>>,
@@ -101,6 +104,8 @@ pub struct PersistentOutputState {
     pub vrr_mode: Cell<&'static VrrMode>,
     pub vrr_cursor_hz: Cell<Option<f64>>,
     pub tearing_mode: Cell<&'static TearingMode>,
+    pub bar_clock_format: CloneCell<Option<Option<Rc<String>>>>,
+    pub bar_show_focused_title: Cell<Option<bool>>,
 }
 
 #[derive(Eq, PartialEq, Hash, Debug)]
@@ -158,10 +163,12 @@ impl WlOutputGlobal {
             pos: Cell::new(Rect::new_sized(x, y, width, height).unwrap()),
             output_id: output_id.clone(),
             mode: Cell::new(*mode),
+            requested_mode: Cell::new(None),
             refresh_nsec: Cell::new(mode.refresh_nsec()),
             modes,
             formats: CloneCell::new(Rc::new(vec![])),
             format: Cell::new(XRGB8888),
+            hdr_enabled: Cell::new(false),
             width_mm,
             height_mm,
             bindings: Default::default(),
@@ -243,6 +250,13 @@ impl WlOutputGlobal {
                 group.handle_new_output(&obj);
             }
         }
+        for handle in client.objects.wlr_foreign_toplevel_handles.lock().values() {
+            if let Some(output) = handle.output.get() {
+                if rc_eq(&output, &self.opt) {
+                    handle.handle_new_output(obj.id);
+                }
+            }
+        }
         Ok(())
     }
 
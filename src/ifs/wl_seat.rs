@@ -1,15 +1,22 @@
 mod event_handling;
 pub mod ext_transient_seat_manager_v1;
 pub mod ext_transient_seat_v1;
+mod force_quit;
 mod gesture_owner;
 mod kb_owner;
+pub mod key_sequences;
+mod marks;
+mod motion_coalescing;
 mod pointer_owner;
+pub mod seat_hint;
+mod shortcuts_inhibit;
 pub mod tablet;
 pub mod text_input;
 mod touch_owner;
 pub mod wl_keyboard;
 pub mod wl_pointer;
 pub mod wl_touch;
+mod xwayland_keyboard_grab;
 pub mod zwp_pointer_constraints_v1;
 pub mod zwp_pointer_gesture_hold_v1;
 pub mod zwp_pointer_gesture_pinch_v1;
@@ -23,7 +30,7 @@ pub mod zwp_virtual_keyboard_v1;
 use {
     crate::{
         async_engine::SpawnedFuture,
-        backend::KeyState,
+        backend::{InputDeviceCapability, KeyState, KeyboardLeds},
         client::{Client, ClientError, ClientId},
         cursor_user::{CursorUser, CursorUserGroup, CursorUserOwner},
         ei::ei_ifs::ei_seat::EiSeat,
@@ -46,9 +53,13 @@ use {
             },
             wl_output::WlOutputGlobal,
             wl_seat::{
+                force_quit::ForceQuit,
                 gesture_owner::GestureOwnerHolder,
                 kb_owner::KbOwnerHolder,
+                key_sequences::ShortcutSequences,
+                motion_coalescing::MotionCoalescing,
                 pointer_owner::PointerOwnerHolder,
+                shortcuts_inhibit::ShortcutsInhibit,
                 tablet::TabletSeatData,
                 text_input::{
                     zwp_input_method_keyboard_grab_v2::ZwpInputMethodKeyboardGrabV2,
@@ -68,6 +79,7 @@ use {
                 dnd_icon::DndIcon,
                 tray::{DynTrayItem, TrayItemId},
                 xdg_surface::xdg_popup::XdgPopup,
+                zwp_xwayland_keyboard_grab_v1::ZwpXwaylandKeyboardGrabV1,
                 WlSurface,
             },
             xdg_toplevel_drag_v1::XdgToplevelDragV1,
@@ -77,6 +89,7 @@ use {
         leaks::Tracker,
         object::{Object, Version},
         rect::Rect,
+        screenshoter::{save_window_screenshot, ScreenshooterError},
         state::{DeviceHandlerData, State},
         tree::{
             generic_node_visitor, ContainerNode, ContainerSplit, Direction, FoundNode, Node,
@@ -94,7 +107,8 @@ use {
         },
         wire_ei::EiSeatId,
     },
-    ahash::AHashMap,
+    ahash::{AHashMap, AHashSet},
+    jay_config::input::{PointerGesture, PointerInput, PointerTarget, DEFAULT_BINDING_MODE},
     smallvec::SmallVec,
     std::{
         cell::{Cell, RefCell},
@@ -195,18 +209,30 @@ pub struct WlSeatGlobal {
     touch_owner: TouchOwnerHolder,
     dropped_dnd: RefCell<Option<DroppedDnd>>,
     shortcuts: RefCell<AHashMap<u32, SmallMap<u32, u32, 2>>>,
+    binding_mode: CloneCell<Rc<String>>,
+    shortcut_sequences: ShortcutSequences,
+    shortcuts_inhibit: ShortcutsInhibit,
+    force_quit: ForceQuit,
+    xwayland_keyboard_grab: CloneCell<Option<Rc<ZwpXwaylandKeyboardGrabV1>>>,
     queue_link: RefCell<Option<LinkedNode<Rc<Self>>>>,
     tree_changed_handler: Cell<Option<SpawnedFuture<()>>>,
     changes: NumCell<u32>,
     constraint: CloneCell<Option<Rc<SeatConstraint>>>,
     idle_notifications: CopyHashMap<(ClientId, ExtIdleNotificationV1Id), Rc<ExtIdleNotificationV1>>,
     last_input_usec: Cell<u64>,
+    last_typing_usec: Cell<u64>,
     text_inputs: RefCell<AHashMap<ClientId, CopyHashMap<ZwpTextInputV3Id, Rc<ZwpTextInputV3>>>>,
     text_input: CloneCell<Option<Rc<ZwpTextInputV3>>>,
     input_method: CloneCell<Option<Rc<ZwpInputMethodV2>>>,
     input_method_grab: CloneCell<Option<Rc<ZwpInputMethodKeyboardGrabV2>>>,
     forward: Cell<bool>,
     focus_follows_mouse: Cell<bool>,
+    motion_coalescing: MotionCoalescing,
+    touch_gestures_enabled: Cell<bool>,
+    gesture_bindings: RefCell<AHashSet<PointerGesture>>,
+    gesture_passthrough: RefCell<AHashSet<String>>,
+    workspace_swipe_fingers: Cell<u32>,
+    pointer_bindings: RefCell<AHashSet<(PointerTarget, PointerInput)>>,
     swipe_bindings: PerClientBindings<ZwpPointerGestureSwipeV1>,
     pinch_bindings: PerClientBindings<ZwpPointerGesturePinchV1>,
     hold_bindings: PerClientBindings<ZwpPointerGestureHoldV1>,
@@ -215,6 +241,8 @@ pub struct WlSeatGlobal {
     ui_drag_highlight: Cell<Option<Rect>>,
     keyboard_node_serial: Cell<u64>,
     tray_popups: CopyHashMap<(TrayItemId, XdgPopupId), Rc<dyn DynTrayItem>>,
+    led_override_mask: Cell<KeyboardLeds>,
+    led_override: Cell<KeyboardLeds>,
 }
 
 const CHANGE_CURSOR_MOVED: u32 = 1 << 0;
@@ -268,12 +296,18 @@ impl WlSeatGlobal {
             touch_owner: Default::default(),
             dropped_dnd: RefCell::new(None),
             shortcuts: Default::default(),
+            binding_mode: CloneCell::new(Rc::new(DEFAULT_BINDING_MODE.to_string())),
+            shortcut_sequences: Default::default(),
+            shortcuts_inhibit: Default::default(),
+            force_quit: Default::default(),
+            xwayland_keyboard_grab: Default::default(),
             queue_link: Default::default(),
             tree_changed_handler: Cell::new(None),
             changes: NumCell::new(CHANGE_CURSOR_MOVED | CHANGE_TREE),
             constraint: Default::default(),
             idle_notifications: Default::default(),
             last_input_usec: Cell::new(state.now_usec()),
+            last_typing_usec: Cell::new(0),
             data_control_devices: Default::default(),
             text_inputs: Default::default(),
             text_input: Default::default(),
@@ -281,6 +315,12 @@ impl WlSeatGlobal {
             input_method_grab: Default::default(),
             forward: Cell::new(false),
             focus_follows_mouse: Cell::new(true),
+            motion_coalescing: Default::default(),
+            touch_gestures_enabled: Cell::new(false),
+            gesture_bindings: Default::default(),
+            gesture_passthrough: Default::default(),
+            workspace_swipe_fingers: Cell::new(0),
+            pointer_bindings: Default::default(),
             swipe_bindings: Default::default(),
             pinch_bindings: Default::default(),
             hold_bindings: Default::default(),
@@ -288,6 +328,8 @@ impl WlSeatGlobal {
             ei_seats: Default::default(),
             ui_drag_highlight: Default::default(),
             tray_popups: Default::default(),
+            led_override_mask: Default::default(),
+            led_override: Default::default(),
         });
         slf.pointer_cursor.set_owner(slf.clone());
         let seat = slf.clone();
@@ -304,6 +346,10 @@ impl WlSeatGlobal {
             }
         });
         slf.tree_changed_handler.set(Some(future));
+        let future = state
+            .eng
+            .spawn("motion coalescing", slf.clone().handle_coalesced_motion());
+        slf.motion_coalescing.handler.set(Some(future));
         slf.update_capabilities();
         slf
     }
@@ -511,8 +557,16 @@ impl WlSeatGlobal {
         if rc_eq(&old, &new) {
             return;
         }
-        self.kb_devices.lock().retain(|_, p| p.has_custom_map.get());
+        for kb in self.kb_devices.lock().values_mut() {
+            if !kb.has_custom_map.get() {
+                *kb = Rc::new(PhysicalKeyboard {
+                    has_custom_map: Cell::new(false),
+                    phy_state: kb.phy_state.transfer(&new),
+                });
+            }
+        }
         self.handle_keyboard_state_change(&old.borrow().kb_state, &new.borrow().kb_state);
+        self.update_leds(None);
     }
 
     fn handle_keyboard_state_change(&self, old: &KeyboardState, new: &KeyboardState) {
@@ -546,6 +600,60 @@ impl WlSeatGlobal {
         s
     }
 
+    pub fn set_led_override(&self, leds: KeyboardLeds, enabled: Option<bool>) {
+        let mut mask = self.led_override_mask.get();
+        let mut value = self.led_override.get();
+        match enabled {
+            None => mask &= !leds,
+            Some(enabled) => {
+                mask |= leds;
+                match enabled {
+                    true => value |= leds,
+                    false => value &= !leds,
+                }
+            }
+        }
+        self.led_override_mask.set(mask);
+        self.led_override.set(value);
+        self.update_leds(None);
+    }
+
+    pub fn leds(&self) -> KeyboardLeds {
+        self.keymap_leds(&self.seat_kb_map.get(), None)
+    }
+
+    fn keymap_leds(&self, map: &Rc<KbvmMap>, current: Option<&KbvmState>) -> KeyboardLeds {
+        let leds = match current {
+            Some(current) if current.map.id == map.id => map.leds(&current.kb_state.mods),
+            _ => match self.kb_states.get(&map.id).and_then(|s| s.upgrade()) {
+                Some(s) => map.leds(&s.borrow().kb_state.mods),
+                _ => map.leds(&Default::default()),
+            },
+        };
+        let mask = self.led_override_mask.get();
+        (leds & !mask) | (self.led_override.get() & mask)
+    }
+
+    // `current` must be set if the caller is holding a mutable borrow of a keyboard state.
+    pub fn update_leds(&self, current: Option<&KbvmState>) {
+        let mut devices = SmallVec::<[_; 4]>::new();
+        for dev in self.state.input_device_handlers.borrow().values() {
+            if let Some(seat) = dev.data.seat.get() {
+                if seat.id == self.id {
+                    devices.push(dev.data.clone());
+                }
+            }
+        }
+        let seat_map = self.seat_kb_map.get();
+        for dev in devices {
+            if !dev.device.has_capability(InputDeviceCapability::Keyboard) {
+                continue;
+            }
+            let map = dev.keymap.get().unwrap_or_else(|| seat_map.clone());
+            dev.device.set_leds(self.keymap_leds(&map, current));
+        }
+    }
+
     pub fn prepare_for_lock(self: &Rc<Self>) {
         self.pointer_owner.revert_to_default(self);
         self.kb_owner.ungrab(self);
@@ -800,6 +908,18 @@ impl WlSeatGlobal {
         self.pointer_owner.cancel_dnd(self);
     }
 
+    pub fn client_destroyed(self: &Rc<Self>, client: ClientId) {
+        self.pointer_owner.client_destroyed(self, client);
+        let dropped = {
+            let mut dropped = self.dropped_dnd.borrow_mut();
+            match &*dropped {
+                Some(d) if d.dnd.client.id == client => dropped.take(),
+                _ => None,
+            }
+        };
+        drop(dropped);
+    }
+
     pub fn unset_selection(self: &Rc<Self>) {
         let _ = self.set_wl_data_source_selection(None, None);
     }
@@ -921,6 +1041,7 @@ impl WlSeatGlobal {
         *self.dropped_dnd.borrow_mut() = None;
         self.queue_link.take();
         self.tree_changed_handler.set(None);
+        self.motion_coalescing.clear();
         self.constraint.take();
         self.text_inputs.borrow_mut().clear();
         self.text_input.take();
@@ -932,6 +1053,10 @@ impl WlSeatGlobal {
         self.cursor_user_group.detach();
         self.tablet_clear();
         self.ei_seats.clear();
+        self.cancel_shortcut_sequence();
+        self.clear_force_quit();
+        self.deactivate_shortcuts_inhibitor();
+        self.deactivate_xwayland_keyboard_grab();
     }
 
     pub fn id(&self) -> SeatId {
@@ -989,6 +1114,11 @@ impl WlSeatGlobal {
         self.last_input_usec.get()
     }
 
+    /// Returns the time of the last press of a non-modifier key.
+    pub fn last_typing(&self) -> u64 {
+        self.last_typing_usec.get()
+    }
+
     pub fn set_visible(&self, visible: bool) {
         self.cursor_user_group.set_visible(visible);
         if let Some(icon) = self.dnd_icon() {
@@ -1022,6 +1152,86 @@ impl WlSeatGlobal {
         self.focus_follows_mouse.set(focus_follows_mouse);
     }
 
+    pub fn set_motion_coalescing(&self, enabled: bool) {
+        self.motion_coalescing.enabled.set(enabled);
+        if !enabled {
+            self.flush_coalesced_motion();
+        }
+    }
+
+    pub fn set_touch_gestures_enabled(&self, enabled: bool) {
+        self.touch_gestures_enabled.set(enabled);
+    }
+
+    pub fn add_gesture_binding(&self, gesture: PointerGesture) {
+        self.gesture_bindings.borrow_mut().insert(gesture);
+    }
+
+    pub fn remove_gesture_binding(&self, gesture: PointerGesture) {
+        self.gesture_bindings.borrow_mut().remove(&gesture);
+    }
+
+    pub fn set_gesture_passthrough(&self, app_id: &str, passthrough: bool) {
+        let mut apps = self.gesture_passthrough.borrow_mut();
+        match passthrough {
+            true => apps.insert(app_id.to_string()),
+            false => apps.remove(app_id),
+        };
+    }
+
+    pub fn screenshot_window(&self) -> Result<(), ScreenshooterError> {
+        match self.keyboard_node.get().node_toplevel() {
+            Some(tl) => save_window_screenshot(&self.state, &*tl),
+            None => Ok(()),
+        }
+    }
+
+    pub fn change_brightness(&self, delta: f64) {
+        let output = self.get_output();
+        self.state
+            .change_brightness(&output.global.connector, delta);
+    }
+
+    pub fn set_workspace_swipe_fingers(&self, fingers: u32) {
+        self.workspace_swipe_fingers.set(fingers);
+    }
+
+    pub fn add_pointer_binding(&self, target: PointerTarget, input: PointerInput) {
+        self.pointer_bindings.borrow_mut().insert((target, input));
+    }
+
+    pub fn remove_pointer_binding(&self, target: PointerTarget, input: PointerInput) {
+        self.pointer_bindings.borrow_mut().remove(&(target, input));
+    }
+
+    pub fn has_pointer_binding(&self, target: PointerTarget, input: PointerInput) -> bool {
+        self.pointer_bindings.borrow().contains(&(target, input))
+    }
+
+    /// Invokes the pointer binding. Returns `false` if the input is not bound.
+    pub fn invoke_pointer_binding(&self, target: PointerTarget, input: PointerInput) -> bool {
+        if !self.has_pointer_binding(target, input) {
+            return false;
+        }
+        if let Some(config) = self.state.config.get() {
+            config.invoke_pointer_binding(self.id, target, input);
+        }
+        true
+    }
+
+    /// Invokes the scroll binding once per step. Returns `false` if the direction is not
+    /// bound.
+    pub fn invoke_scroll_binding(&self, target: PointerTarget, steps: i32) -> bool {
+        let input = scroll_pointer_input(steps);
+        if !self.has_pointer_binding(target, input) {
+            return false;
+        }
+        for _ in 0..steps.abs() {
+            self.invoke_pointer_binding(target, input);
+        }
+        true
+    }
+
     pub fn set_window_management_enabled(self: &Rc<Self>, enabled: bool) {
         self.pointer_owner
             .set_window_management_enabled(self, enabled);
@@ -1094,6 +1304,14 @@ impl WlSeatGlobal {
         self.focus_node_with_serial(node, serial);
     }
 
+    pub fn handle_activation_request(self: &Rc<Self>, node: Rc<dyn Node>, serial: u64) -> bool {
+        if serial <= self.keyboard_node_serial.get() {
+            return false;
+        }
+        self.focus_node_with_serial(node, serial);
+        true
+    }
+
     pub fn get_physical_keyboard(
         &self,
         id: PhysicalKeyboardId,
@@ -1284,6 +1502,13 @@ pub enum WlSeatError {
 efrom!(WlSeatError, ClientError);
 efrom!(WlSeatError, WlKeyboardError);
 
+pub fn scroll_pointer_input(steps: i32) -> PointerInput {
+    match steps < 0 {
+        true => PointerInput::ScrollUp,
+        false => PointerInput::ScrollDown,
+    }
+}
+
 pub fn collect_kb_foci2(node: Rc<dyn Node>, seats: &mut SmallVec<[Rc<WlSeatGlobal>; 3]>) {
     node.node_visit(&mut generic_node_visitor(|node| {
         node.node_seat_state().for_each_kb_focus(|s| seats.push(s));
@@ -1335,6 +1560,10 @@ impl DeviceHandlerData {
                 seat.update_capabilities();
             }
         }
+        match &seat {
+            Some(seat) => seat.update_leds(None),
+            _ => self.device.set_leds(KeyboardLeds::none()),
+        }
     }
 
     fn destroy_physical_keyboard_state(&self) {
@@ -1346,6 +1575,9 @@ impl DeviceHandlerData {
     pub fn set_keymap(&self, keymap: Option<Rc<KbvmMap>>) {
         self.destroy_physical_keyboard_state();
         self.keymap.set(keymap);
+        if let Some(seat) = self.seat.get() {
+            seat.update_leds(None);
+        }
     }
 
     pub fn set_output(&self, output: Option<&WlOutputGlobal>) {
@@ -17,6 +17,7 @@ use {
                 },
             },
             wl_seat::{
+                key_sequences::SequenceAction,
                 tablet::{TabletPad, TabletPadId, TabletTool, TabletToolId},
                 text_input::TextDisconnectReason,
                 wl_keyboard::WlKeyboard,
@@ -45,6 +46,7 @@ use {
         },
         wire::WlDataOfferId,
     },
+    ahash::AHashSet,
     isnt::std_1::primitive::IsntSliceExt,
     jay_config::{
         input::SwitchEvent,
@@ -259,6 +261,7 @@ impl WlSeatGlobal {
             | InputEvent::HoldBegin { time_usec, .. }
             | InputEvent::HoldEnd { time_usec, .. }
             | InputEvent::SwitchEvent { time_usec, .. }
+            | InputEvent::GamepadActivity { time_usec }
             | InputEvent::TabletToolChanged { time_usec, .. }
             | InputEvent::TabletToolButton { time_usec, .. }
             | InputEvent::TabletPadButton { time_usec, .. }
@@ -284,6 +287,18 @@ impl WlSeatGlobal {
             | InputEvent::TouchMotion { .. }
             | InputEvent::TouchCancel { .. } => {}
         }
+        match event {
+            InputEvent::Key {
+                state: KeyState::Pressed,
+                ..
+            }
+            | InputEvent::Button {
+                state: KeyState::Pressed,
+                ..
+            }
+            | InputEvent::TouchDown { .. } => self.state.unlock_if_in_grace_period(),
+            _ => {}
+        }
         match event {
             InputEvent::ConnectorPosition { .. }
             | InputEvent::Motion { .. }
@@ -305,6 +320,7 @@ impl WlSeatGlobal {
             InputEvent::AxisStop { .. } => {}
             InputEvent::Axis120 { .. } => {}
             InputEvent::SwitchEvent { .. } => {}
+            InputEvent::GamepadActivity { .. } => {}
             InputEvent::TabletToolAdded { .. } => {}
             InputEvent::TabletToolChanged { .. } => {}
             InputEvent::TabletToolButton { .. } => {}
@@ -325,6 +341,9 @@ impl WlSeatGlobal {
                 key,
                 state,
             } => {
+                if state == KeyState::Pressed && !is_modifier_key(key) {
+                    self.last_typing_usec.set(time_usec);
+                }
                 self.get_physical_keyboard(dev.keyboard_id, dev.keymap.get().as_ref())
                     .phy_state
                     .update(time_usec, self, key, state);
@@ -414,6 +433,7 @@ impl WlSeatGlobal {
             InputEvent::SwitchEvent { time_usec, event } => {
                 self.switch_event(dev.device.id(), time_usec, event)
             }
+            InputEvent::GamepadActivity { .. } => {}
             InputEvent::TabletToolAdded { time_usec, init } => {
                 self.tablet_handle_new_tool(time_usec, &init)
             }
@@ -526,6 +546,39 @@ impl WlSeatGlobal {
         self.cursor_moved(time_usec);
     }
 
+    /// Moves the pointer to a surface-local position on behalf of the client that has the
+    /// pointer focus.
+    pub fn warp_pointer(self: &Rc<Self>, surface: &WlSurface, x: Fixed, y: Fixed) {
+        let Some(node) = self.pointer_node() else {
+            return;
+        };
+        if node.node_id() != surface.node_id() {
+            return;
+        }
+        let pos = surface.buffer_abs_pos.get();
+        let (x_int, y_int) = (x.round_down(), y.round_down());
+        if x < 0 || y < 0 || x_int >= pos.width() || y_int >= pos.height() {
+            return;
+        }
+        let x = x + Fixed::from_int(pos.x1());
+        let y = y + Fixed::from_int(pos.y1());
+        if let Some(c) = self.constraint.get() {
+            if c.ty == ConstraintType::Lock || !c.contains(x.round_down(), y.round_down()) {
+                return;
+            }
+        }
+        self.set_pointer_cursor_position(x, y);
+        self.cursor_moved(self.state.now_usec());
+    }
+
+    /// Restricts the cursors of this seat to the outputs with the given connector names.
+    ///
+    /// If the set is empty, the cursors can move across all outputs.
+    pub fn set_outputs(self: &Rc<Self>, outputs: AHashSet<String>) {
+        self.cursor_user_group.set_outputs(outputs);
+        self.cursor_moved(self.state.now_usec());
+    }
+
     pub fn motion_event(
         self: &Rc<Self>,
         time_usec: u64,
@@ -799,7 +852,8 @@ impl WlSeatGlobal {
         let mut shortcuts = SmallVec::<[_; 1]>::new();
         let mut components_changed = false;
         while let Some(event) = events.pop() {
-            components_changed |= kbvm_state.kb_state.mods.apply_event(event);
+            let mods_changed = kbvm_state.kb_state.mods.apply_event(event);
+            components_changed |= mods_changed;
             let (key_state, kc) = match event {
                 Event::KeyDown(kc) => (KeyState::Pressed, kc),
                 Event::KeyUp(kc) => (KeyState::Released, kc),
@@ -813,6 +867,8 @@ impl WlSeatGlobal {
                 }
             };
             shortcuts.clear();
+            let mut sequence = SequenceAction::Ignored;
+            let mut break_out = false;
             {
                 let mut mods = kbvm_state.kb_state.mods.mods.0 & !(CAPS.0 | NUM.0);
                 if key_state == KeyState::Released {
@@ -825,12 +881,14 @@ impl WlSeatGlobal {
                     kc,
                 );
                 let mut revert_pointer_to_default = false;
+                let mut syms = SmallVec::<[u32; 1]>::new();
                 for props in keysyms {
                     let sym = props.keysym().0;
+                    syms.push(sym);
                     if sym == SYM_Escape.0 && mods == 0 {
                         revert_pointer_to_default = true;
                     }
-                    if !self.state.lock.locked.get() {
+                    if !self.state.lock.locked.get() && !self.shortcuts_inhibited() {
                         if let Some(key_mods) = scs.get(&sym) {
                             for (key_mods, mask) in key_mods {
                                 if mods & mask == key_mods {
@@ -849,10 +907,116 @@ impl WlSeatGlobal {
                     self.pointer_owner.revert_to_default(self);
                     kbvm_state = kbvm_state_rc.borrow_mut();
                 }
+                if key_state == KeyState::Pressed && !mods_changed && !self.state.lock.locked.get()
+                {
+                    if self.is_break_out_key(mods, &syms) {
+                        break_out = true;
+                    } else if !self.shortcuts_inhibited() {
+                        sequence = self.handle_sequence_key(mods, &syms);
+                    }
+                }
             }
             self.state.for_each_seat_tester(|t| {
                 t.send_key(self.id, time_usec, kc.to_evdev(), key_state);
             });
+            if break_out {
+                drop(kbvm_state);
+                self.break_out();
+                kbvm_state = kbvm_state_rc.borrow_mut();
+                update_pressed_keys(&mut kbvm_state);
+                continue;
+            }
+            if self.state.lock.fallback.is_active() {
+                if key_state == KeyState::Pressed {
+                    let props = kbvm_state
+                        .map
+                        .lookup_table
+                        .lookup(
+                            kbvm_state.kb_state.mods.group,
+                            kbvm_state.kb_state.mods.mods,
+                            kc,
+                        )
+                        .into_iter()
+                        .next();
+                    if let Some(props) = props {
+                        let (sym, char) = (KeySym(props.keysym().0), props.char());
+                        drop(kbvm_state);
+                        self.state.lock.fallback.handle_key(&self.state, sym, char);
+                        kbvm_state = kbvm_state_rc.borrow_mut();
+                    }
+                }
+                update_pressed_keys(&mut kbvm_state);
+                continue;
+            }
+            if key_state == KeyState::Pressed
+                && !self.state.lock.locked.get()
+                && self.state.ei_authorization.is_prompting()
+            {
+                let props = kbvm_state
+                    .map
+                    .lookup_table
+                    .lookup(
+                        kbvm_state.kb_state.mods.group,
+                        kbvm_state.kb_state.mods.mods,
+                        kc,
+                    )
+                    .into_iter()
+                    .next();
+                if let Some(props) = props {
+                    let (sym, char) = (KeySym(props.keysym().0), props.char());
+                    drop(kbvm_state);
+                    self.state
+                        .ei_authorization
+                        .handle_key(&self.state, sym, char);
+                    kbvm_state = kbvm_state_rc.borrow_mut();
+                }
+                update_pressed_keys(&mut kbvm_state);
+                continue;
+            }
+            if key_state == KeyState::Pressed
+                && !self.state.lock.locked.get()
+                && self.force_quit.is_prompting()
+            {
+                let props = kbvm_state
+                    .map
+                    .lookup_table
+                    .lookup(
+                        kbvm_state.kb_state.mods.group,
+                        kbvm_state.kb_state.mods.mods,
+                        kc,
+                    )
+                    .into_iter()
+                    .next();
+                if let Some(props) = props {
+                    let (sym, char) = (KeySym(props.keysym().0), props.char());
+                    drop(kbvm_state);
+                    self.handle_force_quit_key(sym, char);
+                    kbvm_state = kbvm_state_rc.borrow_mut();
+                }
+                update_pressed_keys(&mut kbvm_state);
+                continue;
+            }
+            match sequence {
+                SequenceAction::Ignored => {}
+                SequenceAction::Consumed => {
+                    update_pressed_keys(&mut kbvm_state);
+                    continue;
+                }
+                SequenceAction::Complete(keys) => {
+                    if let Some(config) = self.state.config.get() {
+                        drop(kbvm_state);
+                        config.invoke_shortcut_sequence(self.id(), keys);
+                        kbvm_state = kbvm_state_rc.borrow_mut();
+                        if kbvm_state.kb_state.id != self.latest_kb_state_id.get() {
+                            update_pressed_keys(&mut kbvm_state);
+                            kbvm_state.apply_events(events);
+                            return;
+                        }
+                    }
+                    update_pressed_keys(&mut kbvm_state);
+                    continue;
+                }
+            }
             if shortcuts.is_not_empty() {
                 self.forward.set(key_state == KeyState::Released);
                 if let Some(config) = self.state.config.get() {
@@ -895,6 +1059,7 @@ impl WlSeatGlobal {
         if !mem::take(components_changed) {
             return;
         }
+        self.update_leds(Some(kbvm_state));
         let kb_state = &kbvm_state.kb_state;
         self.for_each_ei_seat(|ei_seat| {
             ei_seat.handle_modifiers_changed(kb_state);
@@ -964,7 +1129,7 @@ impl WlSeatGlobal {
         }
     }
 
-    fn for_each_pointer<C>(&self, ver: Version, client: ClientId, mut f: C)
+    pub(super) fn for_each_pointer<C>(&self, ver: Version, client: ClientId, mut f: C)
     where
         C: FnMut(&Rc<WlPointer>),
     {
@@ -976,7 +1141,7 @@ impl WlSeatGlobal {
         })
     }
 
-    fn for_each_relative_pointer<C>(&self, client: ClientId, mut f: C)
+    pub(super) fn for_each_relative_pointer<C>(&self, client: ClientId, mut f: C)
     where
         C: FnMut(&Rc<ZwpRelativePointerV1>),
     {
@@ -1048,6 +1213,7 @@ impl WlSeatGlobal {
     where
         F: FnMut(&Rc<WlPointer>),
     {
+        self.flush_coalesced_motion();
         let client = &surface.client;
         self.for_each_pointer(ver, client.id, |p| {
             f(p);
@@ -1059,6 +1225,7 @@ impl WlSeatGlobal {
     where
         F: FnMut(&Rc<ZwpRelativePointerV1>),
     {
+        self.flush_coalesced_motion();
         let client = &surface.client;
         self.for_each_relative_pointer(client.id, |p| {
             f(p);
@@ -1094,6 +1261,7 @@ impl WlSeatGlobal {
 
     pub fn clear_shortcuts(&self) {
         self.shortcuts.borrow_mut().clear();
+        self.clear_shortcut_sequences();
     }
 
     pub fn add_shortcut(&self, mod_mask: Modifiers, mods: Modifiers, keysym: KeySym) {
@@ -1113,6 +1281,20 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn binding_mode(&self) -> Rc<String> {
+        self.binding_mode.get()
+    }
+
+    pub fn set_binding_mode(&self, mode: &str) {
+        if *self.binding_mode.get() == mode {
+            return;
+        }
+        self.binding_mode.set(Rc::new(mode.to_string()));
+        for watcher in self.state.binding_mode_watchers.lock().values() {
+            watcher.send_mode(self);
+        }
+    }
+
     pub fn trigger_tree_changed(&self, needs_layout: bool) {
         // log::info!("trigger_tree_changed");
         if needs_layout {
@@ -1213,6 +1395,10 @@ impl WlSeatGlobal {
                 }
             }
             let time = (self.pos_time_usec.get() / 1000) as u32;
+            if self.coalesce_motion(n, time, x, y) {
+                self.maybe_constrain(n, x, y);
+                return;
+            }
             self.surface_pointer_event(Version::ALL, n, |p| p.send_motion(time, x, y));
         }
         self.surface_pointer_frame(n);
@@ -1231,6 +1417,16 @@ impl WlSeatGlobal {
         dx_unaccelerated: Fixed,
         dy_unaccelerated: Fixed,
     ) {
+        if self.coalesce_relative_motion(
+            surface,
+            time_usec,
+            dx,
+            dy,
+            dx_unaccelerated,
+            dy_unaccelerated,
+        ) {
+            return;
+        }
         self.surface_relative_pointer_event(surface, |p| {
             p.send_relative_motion(time_usec, dx, dy, dx_unaccelerated, dy_unaccelerated);
         });
@@ -1281,6 +1477,8 @@ impl WlSeatGlobal {
 // Unfocus callbacks
 impl WlSeatGlobal {
     pub fn unfocus_surface(&self, surface: &WlSurface) {
+        self.shortcuts_inhibitor_unfocus(surface);
+        self.xwayland_keyboard_grab_unfocus(surface);
         if let Some(ti) = self.text_input.take() {
             if let Some(con) = ti.connection.get() {
                 con.disconnect(TextDisconnectReason::FocusLost);
@@ -1301,6 +1499,8 @@ impl WlSeatGlobal {
 // Focus callbacks
 impl WlSeatGlobal {
     pub fn focus_surface(&self, surface: &WlSurface) {
+        self.shortcuts_inhibitor_focus(surface);
+        self.xwayland_keyboard_grab_focus(surface);
         let kb_state = self.latest_kb_state.get();
         let kb_state = &*kb_state.borrow();
         let serial = surface.client.next_serial();
@@ -1540,3 +1740,27 @@ impl WlSeatGlobal {
             })
     }
 }
+
+fn is_modifier_key(key: u32) -> bool {
+    const KEY_LEFTCTRL: u32 = 29;
+    const KEY_LEFTSHIFT: u32 = 42;
+    const KEY_RIGHTSHIFT: u32 = 54;
+    const KEY_LEFTALT: u32 = 56;
+    const KEY_CAPSLOCK: u32 = 58;
+    const KEY_RIGHTCTRL: u32 = 97;
+    const KEY_RIGHTALT: u32 = 100;
+    const KEY_LEFTMETA: u32 = 125;
+    const KEY_RIGHTMETA: u32 = 126;
+    matches!(
+        key,
+        KEY_LEFTCTRL
+            | KEY_LEFTSHIFT
+            | KEY_RIGHTSHIFT
+            | KEY_LEFTALT
+            | KEY_CAPSLOCK
+            | KEY_RIGHTCTRL
+            | KEY_RIGHTALT
+            | KEY_LEFTMETA
+            | KEY_RIGHTMETA
+    )
+}
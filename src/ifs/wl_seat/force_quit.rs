@@ -0,0 +1,181 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        ifs::wl_seat::{
+            seat_hint::{SeatHint, SeatHintPosition},
+            WlSeatGlobal,
+        },
+        utils::{
+            errorfmt::ErrorFmt,
+            numcell::NumCell,
+            oserror::OsError,
+            pid_info::{get_pid_info, PidInfo},
+        },
+    },
+    ahash::AHashMap,
+    jay_config::keyboard::syms::{KeySym, SYM_Escape},
+    std::{cell::RefCell, ptr, rc::Rc},
+    uapi::{c, Errno, OwnedFd},
+};
+
+/// The time between SIGTERM and SIGKILL.
+const KILL_TIMEOUT_MS: u64 = 3000;
+
+/// Asks the user whether the process of the focused window should be killed.
+pub struct ForceQuit {
+    pending: RefCell<Option<PendingForceQuit>>,
+    next_id: NumCell<u64>,
+    kills: RefCell<AHashMap<u64, SpawnedFuture<()>>>,
+    pub(super) hint: Rc<SeatHint>,
+}
+
+struct PendingForceQuit {
+    pidfd: Rc<OwnedFd>,
+    pid_info: PidInfo,
+    _exit: SpawnedFuture<()>,
+}
+
+impl Default for ForceQuit {
+    fn default() -> Self {
+        Self {
+            pending: Default::default(),
+            next_id: Default::default(),
+            kills: Default::default(),
+            hint: Rc::new(SeatHint::new(SeatHintPosition::Top)),
+        }
+    }
+}
+
+impl ForceQuit {
+    pub fn is_prompting(&self) -> bool {
+        self.pending.borrow().is_some()
+    }
+}
+
+impl WlSeatGlobal {
+    /// Shows a prompt offering to kill the process of the focused window.
+    pub fn force_quit(self: &Rc<Self>) {
+        let Some(tl) = self.keyboard_node.get().node_toplevel() else {
+            return;
+        };
+        let Some(pid) = tl.tl_pid() else {
+            log::warn!("Cannot force quit: the process of the focused window is unknown");
+            return;
+        };
+        if pid <= 1 || pid == uapi::getpid() {
+            log::warn!("Refusing to force quit pid {}", pid);
+            return;
+        }
+        let pidfd = match uapi::pidfd_open(pid, 0) {
+            Ok(fd) => Rc::new(fd),
+            Err(e) => {
+                log::error!(
+                    "Could not open a pidfd for pid {}: {}",
+                    pid,
+                    ErrorFmt(OsError::from(e))
+                );
+                return;
+            }
+        };
+        let pid_info = get_pid_info(uapi::getuid(), pid);
+        let exit = {
+            let slf = self.clone();
+            let pidfd = pidfd.clone();
+            self.state.eng.spawn("force quit exit", async move {
+                if let Err(e) = slf.state.ring.readable(&pidfd).await {
+                    log::error!("Could not wait for the pidfd: {}", ErrorFmt(e));
+                    return;
+                }
+                slf.cancel_force_quit();
+            })
+        };
+        let name = pid_info.exe().unwrap_or(&pid_info.comm);
+        let text = format!(
+            "Force quit {} (pid {})? [y] kill, [n] cancel",
+            name, pid_info.pid,
+        );
+        self.force_quit
+            .hint
+            .show(&self.state, self.get_output(), &text);
+        *self.force_quit.pending.borrow_mut() = Some(PendingForceQuit {
+            pidfd,
+            pid_info,
+            _exit: exit,
+        });
+    }
+
+    pub fn cancel_force_quit(&self) {
+        let fq = &self.force_quit;
+        fq.pending.take();
+        fq.hint.hide(&self.state);
+    }
+
+    pub(super) fn clear_force_quit(&self) {
+        self.cancel_force_quit();
+        self.force_quit.kills.borrow_mut().clear();
+    }
+
+    /// Handles a key press while the prompt is shown.
+    pub(super) fn handle_force_quit_key(self: &Rc<Self>, sym: KeySym, char: Option<char>) {
+        let kill = match char {
+            Some('y' | 'Y') => true,
+            Some('n' | 'N') => false,
+            _ if sym == SYM_Escape => false,
+            _ => return,
+        };
+        let fq = &self.force_quit;
+        let Some(pending) = fq.pending.take() else {
+            return;
+        };
+        fq.hint.hide(&self.state);
+        if !kill {
+            return;
+        }
+        let pid = pending.pid_info.pid;
+        log::info!(
+            "Sending SIGTERM to {:?} (pid {})",
+            pending.pid_info.comm,
+            pid,
+        );
+        if let Err(e) = pidfd_send_signal(&pending.pidfd, c::SIGTERM) {
+            if e.0 != c::ESRCH {
+                log::error!("Could not send SIGTERM: {}", ErrorFmt(OsError::from(e)));
+            }
+            return;
+        }
+        let id = fq.next_id.fetch_add(1);
+        let slf = self.clone();
+        let pidfd = pending.pidfd;
+        let future = self.state.eng.spawn("force quit kill", async move {
+            if let Err(e) = slf.state.wheel.timeout(KILL_TIMEOUT_MS).await {
+                log::error!("Could not wait for the kill timeout: {}", ErrorFmt(e));
+            } else {
+                match pidfd_send_signal(&pidfd, c::SIGKILL) {
+                    Ok(()) => log::info!("Sent SIGKILL to pid {}", pid),
+                    Err(Errno(c::ESRCH)) => {}
+                    Err(e) => {
+                        log::error!("Could not send SIGKILL: {}", ErrorFmt(OsError::from(e)))
+                    }
+                }
+            }
+            slf.force_quit.kills.borrow_mut().remove(&id);
+        });
+        fq.kills.borrow_mut().insert(id, future);
+    }
+}
+
+fn pidfd_send_signal(pidfd: &OwnedFd, sig: c::c_int) -> Result<(), Errno> {
+    let res = unsafe {
+        c::syscall(
+            c::SYS_pidfd_send_signal,
+            pidfd.raw(),
+            sig,
+            ptr::null::<c::siginfo_t>(),
+            0,
+        )
+    };
+    if res < 0 {
+        return Err(Errno::default());
+    }
+    Ok(())
+}
@@ -1,6 +1,12 @@
 use {
-    crate::{fixed::Fixed, ifs::wl_seat::WlSeatGlobal, tree::Node, utils::clonecell::CloneCell},
-    std::rc::Rc,
+    crate::{
+        fixed::Fixed,
+        ifs::wl_seat::WlSeatGlobal,
+        tree::{Direction, Node, OutputNode},
+        utils::clonecell::CloneCell,
+    },
+    jay_config::{input::PointerGesture, Direction as JayDirection},
+    std::{cell::Cell, rc::Rc},
 };
 
 pub struct GestureOwnerHolder {
@@ -143,24 +149,53 @@ impl GestureOwner for NoGesture {
     }
 
     fn swipe_begin(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, finger_count: u32) {
-        let Some(node) = seat.pointer_node() else {
+        let bound = |g: &PointerGesture| matches!(*g, PointerGesture::Swipe { fingers, .. } if fingers == finger_count);
+        if consume_gesture(seat, bound) {
+            seat.gesture_owner.owner.set(Rc::new(BoundSwipeGesture {
+                fingers: finger_count,
+                time_usec,
+                dx: Cell::new(0.0),
+                dy: Cell::new(0.0),
+                gesture: Cell::new(None),
+            }));
             return;
-        };
-        node.node_seat_state().gesture_begin(seat);
-        node.node_on_swipe_begin(seat, time_usec, finger_count);
-        seat.gesture_owner.owner.set(Rc::new(SwipeGesture { node }));
+        }
+        begin_swipe(seat, time_usec, finger_count);
     }
 
     fn pinch_begin(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, finger_count: u32) {
-        let Some(node) = seat.pointer_node() else {
-            return;
+        let bound = |g: &PointerGesture| match *g {
+            PointerGesture::PinchIn { fingers } => fingers == finger_count,
+            PointerGesture::PinchOut { fingers } => fingers == finger_count,
+            _ => false,
         };
-        node.node_seat_state().gesture_begin(seat);
-        node.node_on_pinch_begin(seat, time_usec, finger_count);
-        seat.gesture_owner.owner.set(Rc::new(PinchGesture { node }));
+        if consume_gesture(seat, bound) {
+            seat.gesture_owner.owner.set(Rc::new(BoundPinchGesture {
+                fingers: finger_count,
+                time_usec,
+                dx: Cell::new(0.0),
+                dy: Cell::new(0.0),
+                scale: Cell::new(1.0),
+                rotation: Cell::new(0.0),
+                gesture: Cell::new(None),
+            }));
+            return;
+        }
+        begin_pinch(seat, time_usec, finger_count);
     }
 
     fn hold_begin(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, finger_count: u32) {
+        let bound = |g: &PointerGesture| {
+            *g == PointerGesture::Hold {
+                fingers: finger_count,
+            }
+        };
+        if consume_gesture(seat, bound) {
+            seat.gesture_owner.owner.set(Rc::new(BoundHoldGesture {
+                fingers: finger_count,
+            }));
+            return;
+        }
         let Some(node) = seat.pointer_node() else {
             return;
         };
@@ -170,6 +205,33 @@ impl GestureOwner for NoGesture {
     }
 }
 
+fn begin_swipe(seat: &Rc<WlSeatGlobal>, time_usec: u64, finger_count: u32) {
+    if finger_count == seat.workspace_swipe_fingers.get() {
+        if let Some(output) = workspace_swipe_output(seat) {
+            seat.gesture_owner.owner.set(Rc::new(WorkspaceSwipeGesture {
+                output,
+                dx: Cell::new(0.0),
+            }));
+            return;
+        }
+    }
+    let Some(node) = seat.pointer_node() else {
+        return;
+    };
+    node.node_seat_state().gesture_begin(seat);
+    node.node_on_swipe_begin(seat, time_usec, finger_count);
+    seat.gesture_owner.owner.set(Rc::new(SwipeGesture { node }));
+}
+
+fn begin_pinch(seat: &Rc<WlSeatGlobal>, time_usec: u64, finger_count: u32) {
+    let Some(node) = seat.pointer_node() else {
+        return;
+    };
+    node.node_seat_state().gesture_begin(seat);
+    node.node_on_pinch_begin(seat, time_usec, finger_count);
+    seat.gesture_owner.owner.set(Rc::new(PinchGesture { node }));
+}
+
 struct SwipeGesture {
     node: Rc<dyn Node>,
 }
@@ -234,3 +296,254 @@ impl GestureOwner for HoldGesture {
         seat.gesture_owner.set_default_owner();
     }
 }
+
+fn consume_gesture(seat: &Rc<WlSeatGlobal>, bound: impl FnMut(&PointerGesture) -> bool) -> bool {
+    if !seat.gesture_bindings.borrow().iter().any(bound) {
+        return false;
+    }
+    let passthrough = seat.gesture_passthrough.borrow();
+    if passthrough.is_empty() {
+        return true;
+    }
+    let tl = seat
+        .pointer_node()
+        .and_then(|n| n.node_into_surface())
+        .and_then(|s| s.get_toplevel());
+    if let Some(tl) = tl {
+        if passthrough.contains(&*tl.tl_data().app_id.borrow()) {
+            return false;
+        }
+    }
+    true
+}
+
+fn invoke_gesture(seat: &Rc<WlSeatGlobal>, gesture: PointerGesture) {
+    seat.gesture_owner.set_default_owner();
+    if let Some(config) = seat.state.config.get() {
+        config.invoke_gesture(seat.id, gesture);
+    }
+}
+
+/// The distance a bound swipe must travel before its direction is decided.
+const SWIPE_THRESHOLD: f64 = 10.0;
+/// The scale change a bound pinch must reach before its direction is decided.
+const PINCH_THRESHOLD: f64 = 0.1;
+
+fn is_bound(seat: &WlSeatGlobal, gesture: PointerGesture) -> bool {
+    seat.gesture_bindings.borrow().contains(&gesture)
+}
+
+struct BoundSwipeGesture {
+    fingers: u32,
+    time_usec: u64,
+    dx: Cell<f64>,
+    dy: Cell<f64>,
+    gesture: Cell<Option<PointerGesture>>,
+}
+
+impl BoundSwipeGesture {
+    fn decide(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64) {
+        let (dx, dy) = (self.dx.get(), self.dy.get());
+        let direction = match dx.abs() >= dy.abs() {
+            true if dx < 0.0 => JayDirection::Left,
+            true => JayDirection::Right,
+            false if dy < 0.0 => JayDirection::Up,
+            false => JayDirection::Down,
+        };
+        let gesture = PointerGesture::Swipe {
+            fingers: self.fingers,
+            direction,
+        };
+        if is_bound(seat, gesture) {
+            self.gesture.set(Some(gesture));
+            return;
+        }
+        seat.gesture_owner.set_default_owner();
+        begin_swipe(seat, self.time_usec, self.fingers);
+        seat.gesture_owner
+            .swipe_update(seat, time_usec, Fixed::from_f64(dx), Fixed::from_f64(dy));
+    }
+}
+
+impl GestureOwner for BoundSwipeGesture {
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        let _ = seat;
+    }
+
+    fn swipe_update(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, dx: Fixed, dy: Fixed) {
+        self.dx.set(self.dx.get() + dx.to_f64());
+        self.dy.set(self.dy.get() + dy.to_f64());
+        if self.gesture.get().is_none() && self.dx.get().hypot(self.dy.get()) >= SWIPE_THRESHOLD {
+            self.decide(seat, time_usec);
+        }
+    }
+
+    fn swipe_end(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, cancelled: bool) {
+        if cancelled || (self.dx.get() == 0.0 && self.dy.get() == 0.0) {
+            seat.gesture_owner.set_default_owner();
+            return;
+        }
+        if self.gesture.get().is_none() {
+            self.decide(seat, time_usec);
+        }
+        match self.gesture.get() {
+            Some(gesture) => invoke_gesture(seat, gesture),
+            None => seat.gesture_owner.swipe_end(seat, time_usec, cancelled),
+        }
+    }
+}
+
+fn workspace_swipe_output(seat: &Rc<WlSeatGlobal>) -> Option<Rc<OutputNode>> {
+    if seat.state.lock.locked.get() {
+        return None;
+    }
+    let output = seat.get_output();
+    if output.is_dummy {
+        return None;
+    }
+    let ws = output.workspace.get()?;
+    if ws.fullscreen.is_some() {
+        return None;
+    }
+    Some(output)
+}
+
+struct WorkspaceSwipeGesture {
+    output: Rc<OutputNode>,
+    dx: Cell<f64>,
+}
+
+impl WorkspaceSwipeGesture {
+    fn offset(&self) -> i32 {
+        let width = self.output.global.pos.get().width();
+        let offset = (self.dx.get() as i32).clamp(-width, width);
+        match self.output.workspace_swipe_neighbor(offset) {
+            Some(_) => offset,
+            None => 0,
+        }
+    }
+}
+
+impl GestureOwner for WorkspaceSwipeGesture {
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        self.swipe_end(seat, seat.state.now_usec(), true);
+    }
+
+    fn swipe_update(&self, _seat: &Rc<WlSeatGlobal>, _time_usec: u64, dx: Fixed, _dy: Fixed) {
+        self.dx.set(self.dx.get() + dx.to_f64());
+        self.output.set_workspace_swipe_offset(self.offset());
+    }
+
+    fn swipe_end(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, cancelled: bool) {
+        seat.gesture_owner.set_default_owner();
+        let offset = self.offset();
+        self.output.set_workspace_swipe_offset(0);
+        let width = self.output.global.pos.get().width();
+        if cancelled || offset.abs() <= width / 4 {
+            return;
+        }
+        let Some(ws) = self.output.workspace_swipe_neighbor(offset) else {
+            return;
+        };
+        if !self.output.show_workspace(&ws) {
+            return;
+        }
+        ws.flush_jay_workspaces();
+        ws.node_do_focus(seat, Direction::Unspecified);
+        self.output.schedule_update_render_data();
+        seat.state.tree_changed();
+    }
+}
+
+struct BoundPinchGesture {
+    fingers: u32,
+    time_usec: u64,
+    dx: Cell<f64>,
+    dy: Cell<f64>,
+    scale: Cell<f64>,
+    rotation: Cell<f64>,
+    gesture: Cell<Option<PointerGesture>>,
+}
+
+impl BoundPinchGesture {
+    fn decide(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64) {
+        let fingers = self.fingers;
+        let gesture = match self.scale.get() < 1.0 {
+            true => PointerGesture::PinchIn { fingers },
+            false => PointerGesture::PinchOut { fingers },
+        };
+        if is_bound(seat, gesture) {
+            self.gesture.set(Some(gesture));
+            return;
+        }
+        seat.gesture_owner.set_default_owner();
+        begin_pinch(seat, self.time_usec, fingers);
+        seat.gesture_owner.pinch_update(
+            seat,
+            time_usec,
+            Fixed::from_f64(self.dx.get()),
+            Fixed::from_f64(self.dy.get()),
+            Fixed::from_f64(self.scale.get()),
+            Fixed::from_f64(self.rotation.get()),
+        );
+    }
+}
+
+impl GestureOwner for BoundPinchGesture {
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        let _ = seat;
+    }
+
+    fn pinch_update(
+        &self,
+        seat: &Rc<WlSeatGlobal>,
+        time_usec: u64,
+        dx: Fixed,
+        dy: Fixed,
+        scale: Fixed,
+        rotation: Fixed,
+    ) {
+        self.dx.set(self.dx.get() + dx.to_f64());
+        self.dy.set(self.dy.get() + dy.to_f64());
+        self.scale.set(scale.to_f64());
+        self.rotation.set(self.rotation.get() + rotation.to_f64());
+        if self.gesture.get().is_none() && (self.scale.get() - 1.0).abs() >= PINCH_THRESHOLD {
+            self.decide(seat, time_usec);
+        }
+    }
+
+    fn pinch_end(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, cancelled: bool) {
+        if cancelled || self.scale.get() == 1.0 {
+            seat.gesture_owner.set_default_owner();
+            return;
+        }
+        if self.gesture.get().is_none() {
+            self.decide(seat, time_usec);
+        }
+        match self.gesture.get() {
+            Some(gesture) => invoke_gesture(seat, gesture),
+            None => seat.gesture_owner.pinch_end(seat, time_usec, cancelled),
+        }
+    }
+}
+
+struct BoundHoldGesture {
+    fingers: u32,
+}
+
+impl GestureOwner for BoundHoldGesture {
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        let _ = seat;
+    }
+
+    fn hold_end(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, cancelled: bool) {
+        if cancelled {
+            seat.gesture_owner.set_default_owner();
+            return;
+        }
+        let gesture = PointerGesture::Hold {
+            fingers: self.fingers,
+        };
+        invoke_gesture(seat, gesture);
+    }
+}
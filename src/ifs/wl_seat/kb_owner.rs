@@ -33,6 +33,12 @@ impl KbOwnerHolder {
         self.owner.get().set_kb_node(seat, node, serial);
     }
 
+    /// Locks the keyboard focus to the current node. Fails if the focus is already
+    /// grabbed.
+    pub fn grab_current(&self) -> bool {
+        self.owner.get().grab_current(self)
+    }
+
     pub fn clear(&self) {
         self.owner.set(self.default.clone());
     }
@@ -44,6 +50,7 @@ struct GrabKbOwner;
 
 trait KbOwner {
     fn grab(&self, seat: &Rc<WlSeatGlobal>, node: Rc<dyn Node>) -> bool;
+    fn grab_current(&self, holder: &KbOwnerHolder) -> bool;
     fn ungrab(&self, seat: &Rc<WlSeatGlobal>);
     fn set_kb_node(&self, seat: &Rc<WlSeatGlobal>, node: Rc<dyn Node>, serial: u64);
 }
@@ -56,6 +63,11 @@ impl KbOwner for DefaultKbOwner {
         true
     }
 
+    fn grab_current(&self, holder: &KbOwnerHolder) -> bool {
+        holder.owner.set(Rc::new(GrabKbOwner));
+        true
+    }
+
     fn ungrab(&self, _seat: &Rc<WlSeatGlobal>) {
         // nothing
     }
@@ -90,6 +102,10 @@ impl KbOwner for GrabKbOwner {
         false
     }
 
+    fn grab_current(&self, _holder: &KbOwnerHolder) -> bool {
+        false
+    }
+
     fn ungrab(&self, seat: &Rc<WlSeatGlobal>) {
         seat.kb_owner.owner.set(seat.kb_owner.default.clone());
     }
@@ -0,0 +1,187 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        ifs::wl_seat::{
+            seat_hint::{SeatHint, SeatHintPosition},
+            WlSeatGlobal,
+        },
+        utils::errorfmt::ErrorFmt,
+    },
+    ahash::AHashSet,
+    jay_config::keyboard::{
+        mods::{Modifiers, ALT, CTRL, LOGO, SHIFT},
+        syms::KeySym,
+        ModifiedKeySym,
+    },
+    std::{
+        cell::{Cell, RefCell},
+        fmt::Write,
+        rc::Rc,
+        time::Duration,
+    },
+};
+
+pub struct ShortcutSequences {
+    sequences: RefCell<AHashSet<Vec<ModifiedKeySym>>>,
+    pending: RefCell<Vec<ModifiedKeySym>>,
+    timeout: Cell<Duration>,
+    timer: Cell<Option<SpawnedFuture<()>>>,
+    pub(super) hint: Rc<SeatHint>,
+}
+
+impl Default for ShortcutSequences {
+    fn default() -> Self {
+        Self {
+            sequences: Default::default(),
+            pending: Default::default(),
+            timeout: Cell::new(Duration::from_secs(2)),
+            timer: Default::default(),
+            hint: Rc::new(SeatHint::new(SeatHintPosition::Bottom)),
+        }
+    }
+}
+
+pub enum SequenceAction {
+    /// The key is not part of a sequence.
+    Ignored,
+    /// The key started or extended a sequence or cancelled the pending sequence.
+    Consumed,
+    /// The key completed the sequence.
+    Complete(Vec<ModifiedKeySym>),
+}
+
+impl WlSeatGlobal {
+    pub fn add_shortcut_sequence(&self, keys: Vec<ModifiedKeySym>) {
+        if keys.is_empty() {
+            return;
+        }
+        self.shortcut_sequences.sequences.borrow_mut().insert(keys);
+    }
+
+    pub fn remove_shortcut_sequence(&self, keys: &[ModifiedKeySym]) {
+        self.shortcut_sequences.sequences.borrow_mut().remove(keys);
+    }
+
+    pub fn set_shortcut_sequence_timeout(&self, timeout: Duration) {
+        self.shortcut_sequences.timeout.set(timeout);
+    }
+
+    pub(super) fn clear_shortcut_sequences(&self) {
+        self.shortcut_sequences.sequences.borrow_mut().clear();
+        self.cancel_shortcut_sequence();
+    }
+
+    pub fn cancel_shortcut_sequence(&self) {
+        let ss = &self.shortcut_sequences;
+        ss.pending.borrow_mut().clear();
+        ss.timer.take();
+        ss.hint.hide(&self.state);
+    }
+
+    /// Handles a key press that is not a modifier.
+    pub(super) fn handle_sequence_key(self: &Rc<Self>, mods: u32, syms: &[u32]) -> SequenceAction {
+        let ss = &self.shortcut_sequences;
+        let mut candidate = ss.pending.borrow().clone();
+        let had_pending = !candidate.is_empty();
+        {
+            let sequences = ss.sequences.borrow();
+            if sequences.is_empty() && !had_pending {
+                return SequenceAction::Ignored;
+            }
+            for &sym in syms {
+                candidate.push(ModifiedKeySym {
+                    mods: Modifiers(mods),
+                    sym: KeySym(sym),
+                });
+                if sequences.contains(&candidate) {
+                    drop(sequences);
+                    self.cancel_shortcut_sequence();
+                    return SequenceAction::Complete(candidate);
+                }
+                let is_prefix = sequences
+                    .iter()
+                    .any(|s| s.len() > candidate.len() && s.starts_with(&candidate));
+                if is_prefix {
+                    drop(sequences);
+                    *ss.pending.borrow_mut() = candidate;
+                    self.restart_sequence_timer();
+                    self.update_sequence_hint();
+                    return SequenceAction::Consumed;
+                }
+                candidate.pop();
+            }
+        }
+        if had_pending {
+            self.cancel_shortcut_sequence();
+            return SequenceAction::Consumed;
+        }
+        SequenceAction::Ignored
+    }
+
+    fn restart_sequence_timer(self: &Rc<Self>) {
+        let slf = self.clone();
+        let ms = self.shortcut_sequences.timeout.get().as_millis() as u64;
+        let future = self
+            .state
+            .eng
+            .spawn("shortcut sequence timeout", async move {
+                if let Err(e) = slf.state.wheel.timeout(ms).await {
+                    log::error!(
+                        "Could not wait for the shortcut sequence timeout: {}",
+                        ErrorFmt(e)
+                    );
+                    return;
+                }
+                slf.cancel_shortcut_sequence();
+            });
+        self.shortcut_sequences.timer.set(Some(future));
+    }
+
+    fn sequence_hint_text(&self) -> String {
+        let ss = &self.shortcut_sequences;
+        let pending = ss.pending.borrow();
+        let mut text = String::new();
+        for (idx, key) in pending.iter().enumerate() {
+            if idx > 0 {
+                text.push(' ');
+            }
+            format_key(&mut text, key);
+        }
+        text.push(':');
+        let mut continuations: Vec<_> = ss
+            .sequences
+            .borrow()
+            .iter()
+            .filter(|s| s.len() > pending.len() && s.starts_with(&pending))
+            .map(|s| s[pending.len()])
+            .collect();
+        continuations.sort_by_key(|k| (k.mods.0, k.sym.0));
+        continuations.dedup();
+        for key in &continuations {
+            text.push(' ');
+            format_key(&mut text, key);
+        }
+        text
+    }
+
+    fn update_sequence_hint(self: &Rc<Self>) {
+        let text = self.sequence_hint_text();
+        self.shortcut_sequences
+            .hint
+            .show(&self.state, self.get_output(), &text);
+    }
+}
+
+pub(super) fn format_key(s: &mut String, key: &ModifiedKeySym) {
+    for (m, name) in [
+        (LOGO, "logo"),
+        (CTRL, "ctrl"),
+        (ALT, "alt"),
+        (SHIFT, "shift"),
+    ] {
+        if key.mods.0 & m.0 != 0 {
+            let _ = write!(s, "{}-", name);
+        }
+    }
+    let _ = write!(s, "{}", kbvm::Keysym(key.sym.0));
+}
@@ -0,0 +1,109 @@
+use {
+    crate::{
+        ifs::wl_seat::WlSeatGlobal,
+        tree::{PlaceholderNode, ToplevelNode},
+    },
+    std::rc::Rc,
+};
+
+impl WlSeatGlobal {
+    fn focused_toplevel(&self) -> Option<Rc<dyn ToplevelNode>> {
+        self.keyboard_node.get().node_toplevel()
+    }
+
+    /// Returns the focused toplevel and the toplevel with the mark if they are
+    /// different windows that can be moved.
+    fn mark_operands(&self, mark: &str) -> Option<(Rc<dyn ToplevelNode>, Rc<dyn ToplevelNode>)> {
+        let src = self.focused_toplevel()?;
+        let dst = self.state.marked_toplevel(mark)?;
+        if src.node_id() == dst.node_id() {
+            return None;
+        }
+        for tl in [&src, &dst] {
+            if tl.node_is_container() || tl.tl_data().is_fullscreen.get() {
+                return None;
+            }
+        }
+        Some((src, dst))
+    }
+
+    /// Adds a mark to the focused window.
+    pub fn mark(&self, mark: &str) {
+        if let Some(tl) = self.focused_toplevel() {
+            self.state.mark_toplevel(&tl, mark);
+        }
+    }
+
+    /// Removes all marks from the focused window.
+    pub fn unmark(&self) {
+        if let Some(tl) = self.focused_toplevel() {
+            tl.tl_data().remove_marks();
+        }
+    }
+
+    /// Focuses the window with the mark, showing its workspace if necessary.
+    pub fn focus_mark(self: &Rc<Self>, mark: &str) {
+        let Some(tl) = self.state.marked_toplevel(mark) else {
+            return;
+        };
+        let data = tl.tl_data();
+        if let Some(parent) = data.parent.get() {
+            parent.cnode_make_visible(tl.tl_as_node());
+        }
+        if data.visible.get() {
+            self.focus_toplevel(tl);
+        }
+    }
+
+    /// Swaps the positions of the focused window and the window with the mark.
+    pub fn swap_with_mark(&self, mark: &str) {
+        let Some((src, dst)) = self.mark_operands(mark) else {
+            return;
+        };
+        let Some(src_parent) = src.tl_data().parent.get() else {
+            return;
+        };
+        let Some(dst_parent) = dst.tl_data().parent.get() else {
+            return;
+        };
+        let placeholder = Rc::new_cyclic(|weak| PlaceholderNode::new_empty(&self.state, weak));
+        src_parent
+            .clone()
+            .cnode_replace_child(src.tl_as_node(), placeholder.clone());
+        dst_parent.cnode_replace_child(dst.tl_as_node(), src);
+        src_parent.cnode_replace_child(placeholder.tl_as_node(), dst);
+    }
+
+    /// Moves the focused window next to the window with the mark.
+    ///
+    /// If the window with the mark is floating, the focused window is tiled on its
+    /// workspace instead.
+    pub fn move_to_mark(&self, mark: &str) {
+        let Some((src, dst)) = self.mark_operands(mark) else {
+            return;
+        };
+        let Some(src_parent) = src.tl_data().parent.get() else {
+            return;
+        };
+        let dst_data = dst.tl_data();
+        let container = dst_data.parent.get().and_then(|p| p.node_into_container());
+        match container {
+            Some(container) => {
+                let placeholder =
+                    Rc::new_cyclic(|weak| PlaceholderNode::new_empty(&self.state, weak));
+                src_parent
+                    .clone()
+                    .cnode_replace_child(src.tl_as_node(), placeholder.clone());
+                container.add_child_after(dst.tl_as_node(), src);
+                src_parent.cnode_remove_child(placeholder.tl_as_node());
+            }
+            _ => {
+                let Some(ws) = dst_data.workspace.get() else {
+                    return;
+                };
+                src_parent.cnode_remove_child(src.tl_as_node());
+                self.state.map_tiled_on(src, &ws);
+            }
+        }
+    }
+}
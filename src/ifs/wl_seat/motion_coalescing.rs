@@ -0,0 +1,190 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        client::Client,
+        fixed::Fixed,
+        ifs::{
+            wl_seat::{wl_pointer::POINTER_FRAME_SINCE_VERSION, WlSeatGlobal},
+            wl_surface::WlSurface,
+        },
+        object::Version,
+        utils::{asyncevent::AsyncEvent, errorfmt::ErrorFmt},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+/// Coalesces pointer motion events that arrive faster than the refresh rate of the
+/// output of the focused surface.
+///
+/// The first motion event of each frame interval is sent immediately. Later events in
+/// the same interval are merged and sent at the end of the interval or before the next
+/// pointer event that is not a motion event, whichever comes first.
+#[derive(Default)]
+pub struct MotionCoalescing {
+    pub enabled: Cell<bool>,
+    event_usec: Cell<u64>,
+    deadline_usec: Cell<u64>,
+    motion: RefCell<Option<PendingMotion>>,
+    relative_motion: RefCell<Option<PendingRelativeMotion>>,
+    flush_requested: AsyncEvent,
+    pub handler: Cell<Option<SpawnedFuture<()>>>,
+}
+
+struct PendingMotion {
+    client: Rc<Client>,
+    time: u32,
+    x: Fixed,
+    y: Fixed,
+}
+
+struct PendingRelativeMotion {
+    client: Rc<Client>,
+    time_usec: u64,
+    dx: Fixed,
+    dy: Fixed,
+    dx_unaccelerated: Fixed,
+    dy_unaccelerated: Fixed,
+}
+
+impl MotionCoalescing {
+    pub fn clear(&self) {
+        self.handler.take();
+        self.motion.take();
+        self.relative_motion.take();
+    }
+}
+
+impl WlSeatGlobal {
+    pub(super) async fn handle_coalesced_motion(self: Rc<Self>) {
+        let mc = &self.motion_coalescing;
+        loop {
+            mc.flush_requested.triggered().await;
+            let deadline = mc.deadline_usec.get();
+            if deadline > self.state.now_usec() {
+                if let Err(e) = self.state.ring.timeout(deadline * 1000).await {
+                    log::error!("Could not wait for the motion deadline: {}", ErrorFmt(e));
+                }
+            }
+            self.flush_coalesced_motion();
+        }
+    }
+
+    /// Returns whether a motion event belonging to the input event at `event_usec` must
+    /// be deferred.
+    fn defer_motion(&self, surface: &WlSurface, event_usec: u64) -> bool {
+        let mc = &self.motion_coalescing;
+        if !mc.enabled.get() {
+            return false;
+        }
+        // Absolute and relative motion of the same input event are sent together.
+        if mc.event_usec.get() == event_usec {
+            return false;
+        }
+        let now = self.state.now_usec();
+        if now < mc.deadline_usec.get() {
+            mc.flush_requested.trigger();
+            return true;
+        }
+        let mode = surface.get_output().global.mode.get();
+        if mode.refresh_rate_millihz == 0 {
+            return false;
+        }
+        mc.event_usec.set(event_usec);
+        mc.deadline_usec.set(now + mode.refresh_nsec() / 1000);
+        false
+    }
+
+    /// Defers a motion event if it arrives too early. Returns whether it was deferred.
+    pub(super) fn coalesce_motion(
+        &self,
+        surface: &WlSurface,
+        time: u32,
+        x: Fixed,
+        y: Fixed,
+    ) -> bool {
+        let mc = &self.motion_coalescing;
+        if let Some(m) = &mut *mc.motion.borrow_mut() {
+            if m.client.id == surface.client.id {
+                m.time = time;
+                m.x = x;
+                m.y = y;
+                return true;
+            }
+        }
+        if !self.defer_motion(surface, self.pos_time_usec.get()) {
+            return false;
+        }
+        self.flush_coalesced_motion();
+        *mc.motion.borrow_mut() = Some(PendingMotion {
+            client: surface.client.clone(),
+            time,
+            x,
+            y,
+        });
+        true
+    }
+
+    /// Defers a relative motion event if it arrives too early. Returns whether it was
+    /// deferred.
+    pub(super) fn coalesce_relative_motion(
+        &self,
+        surface: &WlSurface,
+        time_usec: u64,
+        dx: Fixed,
+        dy: Fixed,
+        dx_unaccelerated: Fixed,
+        dy_unaccelerated: Fixed,
+    ) -> bool {
+        let mc = &self.motion_coalescing;
+        if let Some(m) = &mut *mc.relative_motion.borrow_mut() {
+            if m.client.id == surface.client.id {
+                m.time_usec = time_usec;
+                m.dx += dx;
+                m.dy += dy;
+                m.dx_unaccelerated += dx_unaccelerated;
+                m.dy_unaccelerated += dy_unaccelerated;
+                return true;
+            }
+        }
+        if !self.defer_motion(surface, time_usec) {
+            return false;
+        }
+        self.flush_coalesced_motion();
+        *mc.relative_motion.borrow_mut() = Some(PendingRelativeMotion {
+            client: surface.client.clone(),
+            time_usec,
+            dx,
+            dy,
+            dx_unaccelerated,
+            dy_unaccelerated,
+        });
+        true
+    }
+
+    /// Sends all deferred motion events.
+    pub(super) fn flush_coalesced_motion(&self) {
+        let mc = &self.motion_coalescing;
+        if let Some(m) = mc.relative_motion.take() {
+            self.for_each_relative_pointer(m.client.id, |p| {
+                p.send_relative_motion(
+                    m.time_usec,
+                    m.dx,
+                    m.dy,
+                    m.dx_unaccelerated,
+                    m.dy_unaccelerated,
+                );
+            });
+        }
+        if let Some(m) = mc.motion.take() {
+            self.for_each_pointer(Version::ALL, m.client.id, |p| {
+                p.send_motion(m.time, m.x, m.y);
+                if p.seat.version >= POINTER_FRAME_SINCE_VERSION {
+                    p.send_frame();
+                }
+            });
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use {
     crate::{
         backend::{AxisSource, KeyState, ScrollAxis, AXIS_120},
+        client::ClientId,
         cursor::KnownCursor,
         fixed::Fixed,
         ifs::{
@@ -151,6 +152,10 @@ impl PointerOwnerHolder {
         self.owner.get().dnd_target_removed(seat);
     }
 
+    pub fn client_destroyed(&self, seat: &Rc<WlSeatGlobal>, client: ClientId) {
+        self.owner.get().client_destroyed(seat, client);
+    }
+
     pub fn dnd_icon(&self) -> Option<Rc<DndIcon>> {
         self.owner.get().dnd_icon()
     }
@@ -250,6 +255,10 @@ trait PointerOwner {
     fn dnd_target_removed(&self, seat: &Rc<WlSeatGlobal>) {
         self.cancel_dnd(seat);
     }
+    fn client_destroyed(&self, seat: &Rc<WlSeatGlobal>, client: ClientId) {
+        let _ = seat;
+        let _ = client;
+    }
     fn dnd_icon(&self) -> Option<Rc<DndIcon>> {
         None
     }
@@ -589,6 +598,12 @@ impl PointerOwner for DndPointerOwner {
         seat.state.tree_changed();
     }
 
+    fn client_destroyed(&self, seat: &Rc<WlSeatGlobal>, client: ClientId) {
+        if self.dnd.client.id == client {
+            self.cancel_dnd(seat);
+        }
+    }
+
     fn dnd_icon(&self) -> Option<Rc<DndIcon>> {
         self.icon.get()
     }
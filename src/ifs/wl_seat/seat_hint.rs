@@ -0,0 +1,149 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        gfx_api::GfxTexture,
+        ifs::wl_seat::WlSeatGlobal,
+        rect::Rect,
+        scale::Scale,
+        state::State,
+        text::TextTexture,
+        tree::OutputNode,
+        utils::{
+            clonecell::CloneCell, errorfmt::ErrorFmt, on_drop_event::OnDropEvent,
+            smallmap::SmallMapMut,
+        },
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub const SEAT_HINT_PADDING: i32 = 5;
+const GAP: i32 = 10;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SeatHintPosition {
+    Top,
+    Bottom,
+}
+
+/// A single line of text that is shown in a box centered at the top or bottom of an
+/// output.
+pub struct SeatHint {
+    position: SeatHintPosition,
+    output: CloneCell<Option<Rc<OutputNode>>>,
+    textures: RefCell<SmallMapMut<Scale, TextTexture, 2>>,
+    render: Cell<Option<SpawnedFuture<()>>>,
+}
+
+impl WlSeatGlobal {
+    pub fn hints(&self) -> [&Rc<SeatHint>; 3] {
+        [
+            &self.shortcuts_inhibit.hint,
+            &self.shortcut_sequences.hint,
+            &self.force_quit.hint,
+        ]
+    }
+}
+
+impl SeatHint {
+    pub fn new(position: SeatHintPosition) -> Self {
+        Self {
+            position,
+            output: Default::default(),
+            textures: Default::default(),
+            render: Default::default(),
+        }
+    }
+
+    pub fn show(self: &Rc<Self>, state: &Rc<State>, output: Rc<OutputNode>, text: &str) {
+        if let Some(old) = self.output.set(Some(output)) {
+            state.damage(old.global.pos.get());
+        }
+        let Some(ctx) = state.render_ctx.get() else {
+            return;
+        };
+        let on_completed = Rc::new(OnDropEvent::default());
+        {
+            let theme = &state.theme;
+            let font = theme.font.get();
+            let color = theme.colors.notification_text.get();
+            let th = theme.sizes.title_height.get();
+            let scales = state.scales.lock();
+            let textures = &mut *self.textures.borrow_mut();
+            for (scale, _) in scales.iter() {
+                let tex = textures
+                    .get_or_insert_with(*scale, || TextTexture::new(&state.cpu_worker, &ctx));
+                let mut height = th;
+                let mut scalef = None;
+                if *scale != 1 {
+                    let scale = scale.to_f64();
+                    height = (height as f64 * scale).round() as _;
+                    scalef = Some(scale);
+                }
+                tex.schedule_render_fitting(
+                    on_completed.clone(),
+                    Some(height),
+                    &font,
+                    text,
+                    color,
+                    false,
+                    scalef,
+                );
+            }
+        }
+        let completed = on_completed.event();
+        let slf = self.clone();
+        let state2 = state.clone();
+        let future = state.eng.spawn("seat hint", async move {
+            completed.triggered().await;
+            for (_, tex) in &*slf.textures.borrow() {
+                if let Err(e) = tex.flip() {
+                    log::warn!("Could not render a seat hint: {}", ErrorFmt(e));
+                }
+            }
+            if let Some(output) = slf.output.get() {
+                state2.damage(output.global.pos.get());
+            }
+        });
+        self.render.set(Some(future));
+    }
+
+    pub fn hide(&self, state: &State) {
+        self.render.take();
+        self.textures.borrow_mut().clear();
+        if let Some(output) = self.output.take() {
+            state.damage(output.global.pos.get());
+        }
+    }
+
+    /// Returns the position of the hint relative to the output and the texture
+    /// containing the text.
+    pub fn layout(
+        &self,
+        state: &State,
+        output: &OutputNode,
+        scale: Scale,
+    ) -> Option<(Rect, Rc<dyn GfxTexture>)> {
+        if self.output.get()?.id != output.id {
+            return None;
+        }
+        let texture = self.textures.borrow().get(&scale)?.texture()?;
+        let theme = &state.theme;
+        let bw = theme.sizes.border_width.get();
+        let th = theme.sizes.title_height.get();
+        let (tex_width, _) = texture.size();
+        let text_width = (tex_width as f64 / scale.to_f64()).ceil() as i32;
+        let width = text_width + 2 * bw + 2 * SEAT_HINT_PADDING;
+        let height = th + 2 * bw + 2 * SEAT_HINT_PADDING;
+        let area = output.non_exclusive_rect_rel.get();
+        let x = area.x1() + (area.width() - width) / 2;
+        let y = match self.position {
+            SeatHintPosition::Top => area.y1() + th + 1 + GAP,
+            SeatHintPosition::Bottom => area.y2() - GAP - height,
+        };
+        let rect = Rect::new_sized(x, y, width, height)?;
+        Some((rect, texture))
+    }
+}
@@ -0,0 +1,127 @@
+use {
+    crate::{
+        ifs::{
+            wl_seat::{
+                key_sequences::format_key,
+                seat_hint::{SeatHint, SeatHintPosition},
+                WlSeatGlobal,
+            },
+            wl_surface::{
+                zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1, WlSurface,
+            },
+        },
+        tree::Node,
+        utils::clonecell::CloneCell,
+    },
+    jay_config::keyboard::{
+        mods::{Modifiers, LOGO},
+        syms::{KeySym, SYM_Escape},
+        ModifiedKeySym,
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct ShortcutsInhibit {
+    inhibitor: CloneCell<Option<Rc<ZwpKeyboardShortcutsInhibitorV1>>>,
+    break_out_key: Cell<ModifiedKeySym>,
+    pub(super) hint: Rc<SeatHint>,
+}
+
+impl Default for ShortcutsInhibit {
+    fn default() -> Self {
+        Self {
+            inhibitor: Default::default(),
+            break_out_key: Cell::new(LOGO | SYM_Escape),
+            hint: Rc::new(SeatHint::new(SeatHintPosition::Top)),
+        }
+    }
+}
+
+impl WlSeatGlobal {
+    pub fn set_break_out_key(&self, key: ModifiedKeySym) {
+        self.shortcuts_inhibit.break_out_key.set(key);
+        if self.shortcuts_inhibited() {
+            self.show_shortcuts_inhibited_hint();
+        }
+    }
+
+    pub fn shortcuts_inhibitor_installed(&self, inhibitor: &Rc<ZwpKeyboardShortcutsInhibitorV1>) {
+        if self.keyboard_node.get().node_id() == inhibitor.surface.node_id() {
+            self.activate_shortcuts_inhibitor(inhibitor);
+        }
+    }
+
+    pub(super) fn activate_shortcuts_inhibitor(
+        &self,
+        inhibitor: &Rc<ZwpKeyboardShortcutsInhibitorV1>,
+    ) {
+        self.cancel_shortcut_sequence();
+        self.shortcuts_inhibit
+            .inhibitor
+            .set(Some(inhibitor.clone()));
+        if !inhibitor.active.replace(true) {
+            inhibitor.send_active();
+        }
+        self.show_shortcuts_inhibited_hint();
+    }
+
+    /// Deactivates the active inhibitor. It is activated again when its surface
+    /// receives the keyboard focus.
+    pub(super) fn deactivate_shortcuts_inhibitor(&self) {
+        if let Some(inhibitor) = self.shortcuts_inhibit.inhibitor.take() {
+            if inhibitor.active.replace(false) {
+                inhibitor.send_inactive();
+            }
+        }
+        self.shortcuts_inhibit.hint.hide(&self.state);
+    }
+
+    pub fn remove_shortcuts_inhibitor(&self, inhibitor: &ZwpKeyboardShortcutsInhibitorV1) {
+        if let Some(active) = self.shortcuts_inhibit.inhibitor.get() {
+            if active.id == inhibitor.id && active.client.id == inhibitor.client.id {
+                self.shortcuts_inhibit.inhibitor.take();
+                self.shortcuts_inhibit.hint.hide(&self.state);
+            }
+        }
+    }
+
+    pub(super) fn shortcuts_inhibitor_focus(&self, surface: &WlSurface) {
+        if let Some(inhibitor) = surface.shortcuts_inhibitors.get(&self.id) {
+            self.activate_shortcuts_inhibitor(&inhibitor);
+        }
+    }
+
+    pub(super) fn shortcuts_inhibitor_unfocus(&self, surface: &WlSurface) {
+        if let Some(inhibitor) = self.shortcuts_inhibit.inhibitor.get() {
+            if inhibitor.surface.id == surface.id && inhibitor.client.id == surface.client.id {
+                self.deactivate_shortcuts_inhibitor();
+            }
+        }
+    }
+
+    pub(super) fn shortcuts_inhibited(&self) -> bool {
+        self.shortcuts_inhibit.inhibitor.is_some() || self.xwayland_keyboard_grab.is_some()
+    }
+
+    pub(super) fn is_break_out_key(&self, mods: u32, syms: &[u32]) -> bool {
+        let key = self.shortcuts_inhibit.break_out_key.get();
+        key.mods == Modifiers(mods) && syms.iter().any(|&sym| KeySym(sym) == key.sym)
+    }
+
+    /// Releases the keyboard from shortcut inhibitors and keyboard grabs.
+    pub(super) fn break_out(self: &Rc<Self>) {
+        self.cancel_shortcut_sequence();
+        self.deactivate_shortcuts_inhibitor();
+        self.deactivate_xwayland_keyboard_grab();
+        self.kb_owner.ungrab(self);
+    }
+
+    pub(super) fn show_shortcuts_inhibited_hint(&self) {
+        let mut key = String::new();
+        format_key(&mut key, &self.shortcuts_inhibit.break_out_key.get());
+        let text = format!("Shortcuts are inhibited. Press {key} to restore them.");
+        self.shortcuts_inhibit
+            .hint
+            .show(&self.state, self.get_output(), &text);
+    }
+}
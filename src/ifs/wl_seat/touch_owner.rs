@@ -1,37 +1,90 @@
 use {
     crate::{
+        async_engine::SpawnedFuture,
         fixed::Fixed,
         ifs::wl_seat::WlSeatGlobal,
         tree::{FindTreeUsecase, FoundNode, Node},
-        utils::{clonecell::CloneCell, smallmap::SmallMap},
+        utils::{
+            clonecell::{CloneCell, UnsafeCellCloneSafe},
+            errorfmt::ErrorFmt,
+            smallmap::SmallMap,
+        },
+    },
+    jay_config::{input::TouchGesture, Direction},
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
     },
-    std::rc::Rc,
 };
 
+const MIN_SWIPE_FINGERS: usize = 3;
+const SWIPE_DISTANCE: f64 = 100.0;
+const EDGE_SIZE: i32 = 16;
+const EDGE_SWIPE_DISTANCE: f64 = 50.0;
+/// How long touch events are held back while waiting for more fingers.
+const HOLD_MS: u64 = 150;
+/// How far the fingers can move while waiting for more fingers.
+const HOLD_SLOP: f64 = 16.0;
+
 pub struct TouchOwnerHolder {
     default: Rc<DefaultTouchOwner>,
     owner: CloneCell<Rc<dyn TouchOwner>>,
+    points: SmallMap<i32, TouchPoint, 10>,
+    edge: Cell<Option<Direction>>,
+}
+
+#[derive(Copy, Clone)]
+struct TouchPoint {
+    start_x: f64,
+    start_y: f64,
+    x: f64,
+    y: f64,
 }
 
+unsafe impl UnsafeCellCloneSafe for TouchPoint {}
+
 impl Default for TouchOwnerHolder {
     fn default() -> Self {
         Self {
             default: Rc::new(DefaultTouchOwner),
             owner: CloneCell::new(Rc::new(DefaultTouchOwner)),
+            points: Default::default(),
+            edge: Default::default(),
         }
     }
 }
 
 impl TouchOwnerHolder {
     pub fn down(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
-        self.owner.get().down(seat, time_usec, id, x, y)
+        self.owner.get().down(seat, time_usec, id, x, y);
+        if seat.touch_gestures_enabled.get() {
+            let (x, y) = (x.to_f64(), y.to_f64());
+            let point = TouchPoint {
+                start_x: x,
+                start_y: y,
+                x,
+                y,
+            };
+            self.points.insert(id, point);
+            let edge = match self.points.len() {
+                1 => find_edge(seat, x, y),
+                _ => None,
+            };
+            self.edge.set(edge);
+        }
     }
 
     pub fn up(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32) {
-        self.owner.get().up(seat, time_usec, id)
+        self.owner.get().up(seat, time_usec, id);
+        self.points.remove(&id);
     }
 
     pub fn motion(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
+        if let Some(mut point) = self.points.get(&id) {
+            point.x = x.to_f64();
+            point.y = y.to_f64();
+            self.points.insert(id, point);
+        }
         self.owner.get().motion(seat, time_usec, id, x, y)
     }
 
@@ -40,35 +93,220 @@ impl TouchOwnerHolder {
     }
 
     pub fn cancel(&self, seat: &Rc<WlSeatGlobal>) {
-        self.owner.get().cancel(seat)
+        self.owner.get().cancel(seat);
+        self.points.clear();
     }
 
     pub fn clear(&self) {
         self.set_default_owner();
+        self.points.clear();
     }
 
     fn set_default_owner(&self) {
         self.owner.set(self.default.clone());
     }
+
+    fn mean_delta(&self) -> (f64, f64) {
+        let mut dx = 0.0;
+        let mut dy = 0.0;
+        let mut n = 0;
+        for (_, p) in &self.points {
+            dx += p.x - p.start_x;
+            dy += p.y - p.start_y;
+            n += 1;
+        }
+        if n == 0 {
+            return (0.0, 0.0);
+        }
+        (dx / n as f64, dy / n as f64)
+    }
+
+    fn detect_gesture(&self) -> Option<TouchGesture> {
+        let fingers = self.points.len();
+        let (dx, dy) = self.mean_delta();
+        if fingers >= MIN_SWIPE_FINGERS {
+            let direction = match dx.abs() >= dy.abs() {
+                true if dx < 0.0 => Direction::Left,
+                true => Direction::Right,
+                false if dy < 0.0 => Direction::Up,
+                false => Direction::Down,
+            };
+            if progress(direction, dx, dy) >= SWIPE_DISTANCE {
+                return Some(TouchGesture::Swipe {
+                    fingers: fingers as u32,
+                    direction,
+                });
+            }
+        } else if fingers == 1 {
+            if let Some(edge) = self.edge.get() {
+                if progress(opposite(edge), dx, dy) >= EDGE_SWIPE_DISTANCE {
+                    return Some(TouchGesture::EdgeSwipe { edge });
+                }
+            }
+        }
+        None
+    }
+
+    fn moved_beyond(&self, distance: f64) -> bool {
+        self.points
+            .iter()
+            .any(|(_, p)| (p.x - p.start_x).hypot(p.y - p.start_y) > distance)
+    }
+
+    /// Returns whether the current touch sequence can still turn into a gesture.
+    fn gesture_possible(&self, hold_expired: bool) -> bool {
+        let fingers = self.points.len();
+        if fingers >= MIN_SWIPE_FINGERS {
+            return true;
+        }
+        if !hold_expired && !self.moved_beyond(HOLD_SLOP) {
+            return true;
+        }
+        if fingers != 1 {
+            return false;
+        }
+        let Some(edge) = self.edge.get() else {
+            return false;
+        };
+        let (dx, dy) = self.mean_delta();
+        let off_axis = match edge {
+            Direction::Left | Direction::Right => dy.abs(),
+            Direction::Up | Direction::Down => dx.abs(),
+        };
+        progress(opposite(edge), dx, dy) > off_axis
+    }
+
+    fn gesture_still_valid(&self, gesture: TouchGesture) -> bool {
+        let (dx, dy) = self.mean_delta();
+        match gesture {
+            TouchGesture::Swipe { direction, .. } => {
+                progress(direction, dx, dy) >= SWIPE_DISTANCE / 2.0
+            }
+            TouchGesture::EdgeSwipe { edge } => {
+                progress(opposite(edge), dx, dy) >= EDGE_SWIPE_DISTANCE / 2.0
+            }
+        }
+    }
+}
+
+fn find_edge(seat: &WlSeatGlobal, x: f64, y: f64) -> Option<Direction> {
+    let (x, y) = (x as i32, y as i32);
+    for output in seat.state.root.outputs.lock().values() {
+        let rect = output.node_absolute_position();
+        if !rect.contains(x, y) {
+            continue;
+        }
+        let edge = if x < rect.x1() + EDGE_SIZE {
+            Direction::Left
+        } else if x >= rect.x2() - EDGE_SIZE {
+            Direction::Right
+        } else if y < rect.y1() + EDGE_SIZE {
+            Direction::Up
+        } else if y >= rect.y2() - EDGE_SIZE {
+            Direction::Down
+        } else {
+            return None;
+        };
+        return Some(edge);
+    }
+    None
+}
+
+fn opposite(direction: Direction) -> Direction {
+    match direction {
+        Direction::Left => Direction::Right,
+        Direction::Down => Direction::Up,
+        Direction::Up => Direction::Down,
+        Direction::Right => Direction::Left,
+    }
+}
+
+fn progress(direction: Direction, dx: f64, dy: f64) -> f64 {
+    match direction {
+        Direction::Left => -dx,
+        Direction::Down => dy,
+        Direction::Up => -dy,
+        Direction::Right => dx,
+    }
 }
 
 struct DefaultTouchOwner;
 
+/// Holds back touch events while the touch sequence might still become a gesture.
+struct PendingTouchOwner {
+    events: RefCell<Vec<HeldTouchEvent>>,
+    hold_expired: Cell<bool>,
+    _timeout: SpawnedFuture<()>,
+}
+
+enum HeldTouchEvent {
+    Down {
+        time_usec: u64,
+        id: i32,
+        x: Fixed,
+        y: Fixed,
+    },
+    Up {
+        time_usec: u64,
+        id: i32,
+    },
+    Motion {
+        time_usec: u64,
+        id: i32,
+        x: Fixed,
+        y: Fixed,
+    },
+    Frame,
+}
+
 struct GrabTouchOwner {
     node: Rc<dyn Node>,
     down_ids: SmallMap<i32, (), 10>,
 }
 
+struct GestureTouchOwner {
+    gesture: TouchGesture,
+    valid: Cell<bool>,
+}
+
 trait TouchOwner {
     fn down(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed);
     fn up(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32);
     fn motion(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed);
     fn frame(&self, seat: &Rc<WlSeatGlobal>);
     fn cancel(&self, seat: &Rc<WlSeatGlobal>);
+
+    fn hold_expired(&self, seat: &Rc<WlSeatGlobal>) {
+        let _ = seat;
+    }
+
+    /// Delivers a down event that was previously held back.
+    fn replay_down(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
+        self.down(seat, time_usec, id, x, y);
+    }
 }
 
-impl TouchOwner for DefaultTouchOwner {
-    fn down(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
+impl DefaultTouchOwner {
+    fn hold(seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
+        let timeout = {
+            let seat = seat.clone();
+            seat.state.eng.clone().spawn("touch hold", async move {
+                if let Err(e) = seat.state.wheel.timeout(HOLD_MS).await {
+                    log::error!("Could not wait for the touch hold timeout: {}", ErrorFmt(e));
+                }
+                seat.touch_owner.owner.get().hold_expired(&seat);
+            })
+        };
+        let owner = Rc::new(PendingTouchOwner {
+            events: Default::default(),
+            hold_expired: Cell::new(false),
+            _timeout: timeout,
+        });
+        seat.touch_owner.owner.set(owner.clone());
+        owner.down(seat, time_usec, id, x, y);
+    }
+
+    fn grab(seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
         let mut found_tree = seat.found_tree.borrow_mut();
         let x_int = x.round_down();
         let y_int = y.round_down();
@@ -93,6 +331,17 @@ impl TouchOwner for DefaultTouchOwner {
             owner.down(seat, time_usec, id, x, y);
         }
     }
+}
+
+impl TouchOwner for DefaultTouchOwner {
+    fn down(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
+        seat.touch_owner.points.clear();
+        if seat.touch_gestures_enabled.get() {
+            Self::hold(seat, time_usec, id, x, y);
+        } else {
+            Self::grab(seat, time_usec, id, x, y);
+        }
+    }
 
     fn up(&self, _seat: &Rc<WlSeatGlobal>, _time_usec: u64, _id: i32) {
         // nothing
@@ -109,6 +358,94 @@ impl TouchOwner for DefaultTouchOwner {
     fn cancel(&self, _seat: &Rc<WlSeatGlobal>) {
         // nothing
     }
+
+    fn replay_down(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
+        Self::grab(seat, time_usec, id, x, y);
+    }
+}
+
+impl PendingTouchOwner {
+    fn push(&self, event: HeldTouchEvent) {
+        self.events.borrow_mut().push(event);
+    }
+
+    fn update(&self, seat: &Rc<WlSeatGlobal>) {
+        let holder = &seat.touch_owner;
+        if !seat.touch_gestures_enabled.get()
+            || holder.points.is_empty()
+            || !holder.gesture_possible(self.hold_expired.get())
+        {
+            self.release(seat);
+        }
+    }
+
+    /// Delivers the held events to the client.
+    fn release(&self, seat: &Rc<WlSeatGlobal>) {
+        seat.touch_owner.set_default_owner();
+        for event in self.events.take() {
+            let owner = seat.touch_owner.owner.get();
+            match event {
+                HeldTouchEvent::Down {
+                    time_usec,
+                    id,
+                    x,
+                    y,
+                } => owner.replay_down(seat, time_usec, id, x, y),
+                HeldTouchEvent::Up { time_usec, id } => owner.up(seat, time_usec, id),
+                HeldTouchEvent::Motion {
+                    time_usec,
+                    id,
+                    x,
+                    y,
+                } => owner.motion(seat, time_usec, id, x, y),
+                HeldTouchEvent::Frame => owner.frame(seat),
+            }
+        }
+    }
+}
+
+impl TouchOwner for PendingTouchOwner {
+    fn down(&self, _seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
+        self.push(HeldTouchEvent::Down {
+            time_usec,
+            id,
+            x,
+            y,
+        });
+    }
+
+    fn up(&self, _seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32) {
+        self.push(HeldTouchEvent::Up { time_usec, id });
+    }
+
+    fn motion(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, id: i32, x: Fixed, y: Fixed) {
+        self.push(HeldTouchEvent::Motion {
+            time_usec,
+            id,
+            x,
+            y,
+        });
+        if let Some(gesture) = seat.touch_owner.detect_gesture() {
+            seat.touch_owner.owner.set(Rc::new(GestureTouchOwner {
+                gesture,
+                valid: Cell::new(true),
+            }));
+        }
+    }
+
+    fn frame(&self, seat: &Rc<WlSeatGlobal>) {
+        self.push(HeldTouchEvent::Frame);
+        self.update(seat);
+    }
+
+    fn cancel(&self, seat: &Rc<WlSeatGlobal>) {
+        seat.touch_owner.set_default_owner();
+    }
+
+    fn hold_expired(&self, seat: &Rc<WlSeatGlobal>) {
+        self.hold_expired.set(true);
+        self.update(seat);
+    }
 }
 
 impl GrabTouchOwner {
@@ -151,6 +488,13 @@ impl TouchOwner for GrabTouchOwner {
         self.node
             .clone()
             .node_on_touch_motion(seat, time_usec, id, x, y);
+        if let Some(gesture) = seat.touch_owner.detect_gesture() {
+            self.cancel(seat);
+            seat.touch_owner.owner.set(Rc::new(GestureTouchOwner {
+                gesture,
+                valid: Cell::new(true),
+            }));
+        }
     }
 
     fn frame(&self, seat: &Rc<WlSeatGlobal>) {
@@ -165,3 +509,34 @@ impl TouchOwner for GrabTouchOwner {
         self.revert_to_default(seat);
     }
 }
+
+impl TouchOwner for GestureTouchOwner {
+    fn down(&self, _seat: &Rc<WlSeatGlobal>, _time_usec: u64, _id: i32, _x: Fixed, _y: Fixed) {
+        // nothing
+    }
+
+    fn up(&self, _seat: &Rc<WlSeatGlobal>, _time_usec: u64, _id: i32) {
+        // nothing
+    }
+
+    fn motion(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, _id: i32, _x: Fixed, _y: Fixed) {
+        self.valid
+            .set(seat.touch_owner.gesture_still_valid(self.gesture));
+    }
+
+    fn frame(&self, seat: &Rc<WlSeatGlobal>) {
+        if seat.touch_owner.points.is_not_empty() {
+            return;
+        }
+        seat.touch_owner.set_default_owner();
+        if self.valid.get() {
+            if let Some(config) = seat.state.config.get() {
+                config.touch_gesture(seat.id, self.gesture);
+            }
+        }
+    }
+
+    fn cancel(&self, seat: &Rc<WlSeatGlobal>) {
+        seat.touch_owner.set_default_owner();
+    }
+}
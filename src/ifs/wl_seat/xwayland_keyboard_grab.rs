@@ -0,0 +1,69 @@
+use {
+    crate::{
+        ifs::{
+            wl_seat::WlSeatGlobal,
+            wl_surface::{zwp_xwayland_keyboard_grab_v1::ZwpXwaylandKeyboardGrabV1, WlSurface},
+        },
+        tree::Node,
+    },
+    std::rc::Rc,
+};
+
+impl WlSeatGlobal {
+    pub fn xwayland_keyboard_grab_installed(self: &Rc<Self>, grab: &Rc<ZwpXwaylandKeyboardGrabV1>) {
+        if self.keyboard_node.get().node_id() == grab.surface.node_id() {
+            self.activate_xwayland_keyboard_grab(grab);
+            return;
+        }
+        if self.state.lock.locked.get() {
+            return;
+        }
+        if let Some(tl) = grab.surface.get_toplevel() {
+            if tl.tl_data().visible.get() {
+                self.focus_toplevel(tl);
+            }
+        }
+    }
+
+    fn activate_xwayland_keyboard_grab(&self, grab: &Rc<ZwpXwaylandKeyboardGrabV1>) {
+        if !self.kb_owner.grab_current() {
+            return;
+        }
+        self.cancel_shortcut_sequence();
+        self.xwayland_keyboard_grab.set(Some(grab.clone()));
+        self.show_shortcuts_inhibited_hint();
+    }
+
+    /// Releases the active grab. The grab is activated again when its surface
+    /// receives the keyboard focus.
+    pub(super) fn deactivate_xwayland_keyboard_grab(&self) {
+        if self.xwayland_keyboard_grab.take().is_some() {
+            self.kb_owner.clear();
+            if !self.shortcuts_inhibited() {
+                self.shortcuts_inhibit.hint.hide(&self.state);
+            }
+        }
+    }
+
+    pub fn remove_xwayland_keyboard_grab(&self, grab: &ZwpXwaylandKeyboardGrabV1) {
+        if let Some(active) = self.xwayland_keyboard_grab.get() {
+            if active.id == grab.id && active.client.id == grab.client.id {
+                self.deactivate_xwayland_keyboard_grab();
+            }
+        }
+    }
+
+    pub(super) fn xwayland_keyboard_grab_focus(&self, surface: &WlSurface) {
+        if let Some(grab) = surface.xwayland_keyboard_grabs.get(&self.id) {
+            self.activate_xwayland_keyboard_grab(&grab);
+        }
+    }
+
+    pub(super) fn xwayland_keyboard_grab_unfocus(&self, surface: &WlSurface) {
+        if let Some(grab) = self.xwayland_keyboard_grab.get() {
+            if grab.surface.id == surface.id && grab.client.id == surface.client.id {
+                self.deactivate_xwayland_keyboard_grab();
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use {
     crate::{
-        client::{Client, ClientError},
+        client::{Client, ClientError, ResourceCharge},
         clientmem::{ClientMem, ClientMemError},
         format::{formats, map_wayland_format_id},
         ifs::wl_buffer::{WlBuffer, WlBufferError},
@@ -19,6 +19,8 @@ pub struct WlShmPool {
     client: Rc<Client>,
     fd: Rc<OwnedFd>,
     mem: CloneCell<Rc<ClientMem>>,
+    mem_charge: CloneCell<Rc<ResourceCharge>>,
+    _fd_charge: ResourceCharge,
     pub tracker: Tracker<Self>,
     version: Version,
 }
@@ -31,6 +33,8 @@ impl WlShmPool {
         len: usize,
         version: Version,
     ) -> Result<Self, WlShmPoolError> {
+        let fd_charge = client.charge_resources(1, 0)?;
+        let mem_charge = client.charge_resources(0, len as u64)?;
         Ok(Self {
             id,
             client: client.clone(),
@@ -41,6 +45,8 @@ impl WlShmPool {
                 Some(client),
                 Some(&client.state.cpu_worker),
             )?)),
+            mem_charge: CloneCell::new(Rc::new(mem_charge)),
+            _fd_charge: fd_charge,
             fd,
             tracker: Default::default(),
             version,
@@ -69,6 +75,7 @@ impl WlShmPoolRequestHandler for WlShmPool {
             req.stride,
             format,
             &self.mem.get(),
+            &self.mem_charge.get(),
         )?);
         track!(self.client, buffer);
         self.client.add_client_obj(&buffer)?;
@@ -84,9 +91,18 @@ impl WlShmPoolRequestHandler for WlShmPool {
         if req.size < 0 {
             return Err(WlShmPoolError::NegativeSize);
         }
-        if (req.size as usize) < self.mem.get().len() {
+        let old_len = self.mem.get().len();
+        if (req.size as usize) < old_len {
             return Err(WlShmPoolError::CannotShrink);
         }
+        if req.size as usize == old_len {
+            return Ok(());
+        }
+        // The old mapping is not remapped in place since buffers created from it and
+        // pending uploads might still reference it. They keep it alive until they are
+        // destroyed.
+        let mem_charge = self.client.charge_resources(0, req.size as u64)?;
+        self.mem_charge.set(Rc::new(mem_charge));
         self.mem.set(Rc::new(ClientMem::new(
             &self.fd,
             req.size as usize,
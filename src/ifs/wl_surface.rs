@@ -5,6 +5,8 @@ pub mod ext_session_lock_surface_v1;
 pub mod tray;
 pub mod wl_subsurface;
 pub mod wp_alpha_modifier_surface_v1;
+pub mod wp_color_management_surface_feedback_v1;
+pub mod wp_color_management_surface_v1;
 pub mod wp_commit_timer_v1;
 pub mod wp_fifo_v1;
 pub mod wp_fractional_scale_v1;
@@ -17,17 +19,22 @@ pub mod xwayland_shell_v1;
 pub mod zwlr_layer_surface_v1;
 pub mod zwp_idle_inhibitor_v1;
 pub mod zwp_input_popup_surface_v2;
+pub mod zwp_keyboard_shortcuts_inhibitor_v1;
+pub mod zwp_linux_buffer_release_v1;
+pub mod zwp_linux_surface_synchronization_v1;
+pub mod zwp_xwayland_keyboard_grab_v1;
 
 use {
     crate::{
         backend::KeyState,
         client::{Client, ClientError},
+        cmm::ColorDescription,
         cursor_user::{CursorUser, CursorUserId},
         drm_feedback::DrmFeedback,
         fixed::Fixed,
         gfx_api::{
-            AsyncShmGfxTexture, BufferResv, BufferResvUser, GfxError, GfxStagingBuffer,
-            ReleaseSync, SampleRect, SyncFile,
+            AcquireSync, AsyncShmGfxTexture, BufferResv, BufferResvUser, ColorTransform, GfxError,
+            GfxStagingBuffer, ReleaseSync, SampleRect, SyncFile,
         },
         ifs::{
             wl_buffer::WlBuffer,
@@ -50,6 +57,7 @@ use {
                 tray::TrayItemId,
                 wl_subsurface::{PendingSubsurfaceData, SubsurfaceId, WlSubsurface},
                 wp_alpha_modifier_surface_v1::WpAlphaModifierSurfaceV1,
+                wp_color_management_surface_v1::WpColorManagementSurfaceV1,
                 wp_commit_timer_v1::WpCommitTimerV1,
                 wp_fifo_v1::WpFifoV1,
                 wp_fractional_scale_v1::WpFractionalScaleV1,
@@ -59,6 +67,8 @@ use {
                 x_surface::{xwindow::Xwindow, XSurface},
                 xdg_surface::{xdg_toplevel::XdgToplevel, PendingXdgSurfaceData, XdgSurfaceError},
                 zwlr_layer_surface_v1::{PendingLayerSurfaceData, ZwlrLayerSurfaceV1Error},
+                zwp_linux_buffer_release_v1::ZwpLinuxBufferReleaseV1,
+                zwp_linux_surface_synchronization_v1::ZwpLinuxSurfaceSynchronizationV1,
             },
             wp_content_type_v1::ContentType,
             wp_presentation_feedback::{WpPresentationFeedback, VRR_REFRESH_SINCE},
@@ -104,6 +114,8 @@ use {
     },
     thiserror::Error,
     zwp_idle_inhibitor_v1::ZwpIdleInhibitorV1,
+    zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1,
+    zwp_xwayland_keyboard_grab_v1::ZwpXwaylandKeyboardGrabV1,
 };
 
 #[expect(dead_code)]
@@ -207,11 +219,19 @@ pub struct SurfaceBuffer {
     sync_files: SmallMap<BufferResvUser, SyncFile, 1>,
     pub release_sync: ReleaseSync,
     release: Option<SurfaceBufferExplicitRelease>,
+    buffer_release: Option<Rc<ZwpLinuxBufferReleaseV1>>,
 }
 
 impl Drop for SurfaceBuffer {
     fn drop(&mut self) {
         let sync_files = self.sync_files.take();
+        if let Some(buffer_release) = &self.buffer_release {
+            buffer_release.release(sync_files.iter().map(|f| &f.1));
+            if !self.buffer.destroyed() {
+                self.buffer.send_release();
+            }
+            return;
+        }
         if let Some(release) = &self.release {
             let Some(ctx) = self.buffer.client.state.render_ctx.get() else {
                 log::error!("Cannot signal release point because there is no render context");
@@ -277,7 +297,7 @@ pub struct WlSurface {
     role: Cell<SurfaceRole>,
     pending: RefCell<Box<PendingState>>,
     input_region: CloneCell<Option<Rc<Region>>>,
-    opaque_region: Cell<Option<Rc<Region>>>,
+    opaque_region: CloneCell<Option<Rc<Region>>>,
     buffer_points: RefCell<BufferPoints>,
     pub buffer_points_norm: RefCell<SampleRect>,
     damage_matrix: Cell<DamageMatrix>,
@@ -304,6 +324,8 @@ pub struct WlSurface {
     dnd_icons: SmallMap<SeatId, Rc<DndIcon>, 1>,
     pub tracker: Tracker<Self>,
     idle_inhibitors: SmallMap<ZwpIdleInhibitorV1Id, Rc<ZwpIdleInhibitorV1>, 1>,
+    pub shortcuts_inhibitors: SmallMap<SeatId, Rc<ZwpKeyboardShortcutsInhibitorV1>, 1>,
+    pub xwayland_keyboard_grabs: SmallMap<SeatId, Rc<ZwpXwaylandKeyboardGrabV1>, 1>,
     viewporter: CloneCell<Option<Rc<WpViewport>>>,
     output: CloneCell<Rc<OutputNode>>,
     fractional_scale: CloneCell<Option<Rc<WpFractionalScaleV1>>>,
@@ -316,10 +338,13 @@ pub struct WlSurface {
     pub content_type: Cell<Option<ContentType>>,
     pub drm_feedback: CopyHashMap<ZwpLinuxDmabufFeedbackV1Id, Rc<ZwpLinuxDmabufFeedbackV1>>,
     sync_obj_surface: CloneCell<Option<Rc<WpLinuxDrmSyncobjSurfaceV1>>>,
+    surface_synchronization: CloneCell<Option<Rc<ZwpLinuxSurfaceSynchronizationV1>>>,
     destroyed: Cell<bool>,
     commit_timeline: CommitTimeline,
     alpha_modifier: CloneCell<Option<Rc<WpAlphaModifierSurfaceV1>>>,
     alpha: Cell<Option<f32>>,
+    pub color_management_surface: CloneCell<Option<Rc<WpColorManagementSurfaceV1>>>,
+    color_description: CloneCell<Option<Rc<ColorDescription>>>,
     pub text_input_connections: SmallMap<SeatId, Rc<TextInputConnection>, 1>,
     vblank_listener: EventListener<dyn VblankListener>,
     latch_listener: EventListener<dyn LatchListener>,
@@ -453,7 +478,9 @@ struct PendingState {
     subsurfaces: AHashMap<SubsurfaceId, AttachedSubsurfaceState>,
     acquire_point: Option<(Rc<SyncObj>, SyncObjPoint)>,
     release_point: Option<(Rc<SyncObj>, SyncObjPoint)>,
+    buffer_release: Option<Rc<ZwpLinuxBufferReleaseV1>>,
     alpha_multiplier: Option<Option<f32>>,
+    color_description: Option<Option<Rc<ColorDescription>>>,
     explicit_sync: bool,
     fifo_barrier_set: bool,
     fifo_barrier_wait: bool,
@@ -474,6 +501,9 @@ impl PendingState {
             if let Some((sync_obj, point)) = self.release_point.take() {
                 client.state.signal_point(&sync_obj, point);
             } else if let Some(Some(prev)) = self.buffer.take() {
+                if let Some(buffer_release) = self.buffer_release.take() {
+                    buffer_release.release([]);
+                }
                 if !prev.destroyed() {
                     prev.send_release();
                 }
@@ -490,6 +520,7 @@ impl PendingState {
             self.buffer = Some(buffer);
             self.acquire_point = next.acquire_point.take();
             self.release_point = next.release_point.take();
+            self.buffer_release = next.buffer_release.take();
             self.explicit_sync = mem::take(&mut next.explicit_sync);
         }
         macro_rules! opt {
@@ -509,6 +540,7 @@ impl PendingState {
         opt!(tearing);
         opt!(content_type);
         opt!(alpha_multiplier);
+        opt!(color_description);
         opt!(commit_time);
         opt!(tray_item_ack_serial);
         {
@@ -641,6 +673,8 @@ impl WlSurface {
             dnd_icons: Default::default(),
             tracker: Default::default(),
             idle_inhibitors: Default::default(),
+            shortcuts_inhibitors: Default::default(),
+            xwayland_keyboard_grabs: Default::default(),
             viewporter: Default::default(),
             output: CloneCell::new(client.state.dummy_output.get().unwrap()),
             fractional_scale: Default::default(),
@@ -653,10 +687,13 @@ impl WlSurface {
             content_type: Default::default(),
             drm_feedback: Default::default(),
             sync_obj_surface: Default::default(),
+            surface_synchronization: Default::default(),
             destroyed: Cell::new(false),
             commit_timeline: client.commit_timelines.create_timeline(),
             alpha_modifier: Default::default(),
             alpha: Default::default(),
+            color_management_surface: Default::default(),
+            color_description: Default::default(),
             text_input_connections: Default::default(),
             vblank_listener: EventListener::new(slf.clone()),
             latch_listener: EventListener::new(slf.clone()),
@@ -697,6 +734,10 @@ impl WlSurface {
         if old.id == output.id {
             return;
         }
+        if old.global.destroyed.get() {
+            // The frame that was latched for the old output will never be presented.
+            self.discard_latched_presentation_feedback();
+        }
         if self.visible.get() {
             self.attach_events_to_output(output);
         }
@@ -716,6 +757,14 @@ impl WlSurface {
         }
     }
 
+    fn discard_latched_presentation_feedback(&self) {
+        for pf in self.latched_presentation_feedback.borrow_mut().drain(..) {
+            pf.send_discarded();
+            let _ = pf.client.remove_obj(&*pf);
+        }
+        self.presentation_listener.detach();
+    }
+
     fn on_scale_change(&self) {
         if let Some(fs) = self.fractional_scale.get() {
             fs.send_preferred_scale();
@@ -763,6 +812,16 @@ impl WlSurface {
         self.role.get() == SurfaceRole::Cursor
     }
 
+    /// The input region in surface-local coordinates. `None` means infinite.
+    pub fn input_region(&self) -> Option<Rc<Region>> {
+        self.input_region.get()
+    }
+
+    /// The opaque region in surface-local coordinates. `None` means empty.
+    pub fn opaque_region(&self) -> Option<Rc<Region>> {
+        self.opaque_region.get()
+    }
+
     pub fn get_cursor(
         self: &Rc<Self>,
         user: &Rc<CursorUser>,
@@ -994,6 +1053,8 @@ impl WlSurfaceRequestHandler for WlSurface {
         self.toplevel.set(None);
         self.client.remove_obj(self)?;
         self.idle_inhibitors.clear();
+        self.shortcuts_inhibitors.clear();
+        self.xwayland_keyboard_grabs.clear();
         self.constraints.take();
         self.destroyed.set(true);
         Ok(())
@@ -1059,6 +1120,10 @@ impl WlSurfaceRequestHandler for WlSurface {
         let ext = self.ext.get();
         let pending = &mut *self.pending.borrow_mut();
         self.verify_explicit_sync(pending)?;
+        if pending.explicit_sync && self.client.state.force_implicit_sync.get() {
+            pending.explicit_sync = false;
+            pending.acquire_point = None;
+        }
         if ext.commit_requested(pending) == CommitAction::ContinueCommit {
             self.commit_timeline.commit(slf, pending)?;
         }
@@ -1139,10 +1204,18 @@ impl WlSurface {
             alpha_changed = true;
             self.alpha.set(alpha);
         }
+        let mut color_description_changed = false;
+        if let Some(cd) = pending.color_description.take() {
+            color_description_changed = true;
+            self.color_description.set(cd);
+        }
         let buffer_abs_pos = self.buffer_abs_pos.get();
         let mut max_surface_size = buffer_abs_pos.size();
-        let mut damage_full =
-            scale_changed || buffer_transform_changed || viewport_changed || alpha_changed;
+        let mut damage_full = scale_changed
+            || buffer_transform_changed
+            || viewport_changed
+            || alpha_changed
+            || color_description_changed;
         let mut buffer_changed = false;
         let mut old_raw_size = None;
         let (mut dx, mut dy) = mem::take(&mut pending.offset);
@@ -1158,11 +1231,12 @@ impl WlSurface {
                 } else {
                     self.reset_shm_textures();
                 }
-                buffer.update_texture_or_log(self, false);
-                let release_sync = match pending.explicit_sync {
-                    false => ReleaseSync::Implicit,
-                    true => ReleaseSync::Explicit,
+                let (acquire_sync, release_sync) = match pending.explicit_sync {
+                    false => (AcquireSync::Implicit, ReleaseSync::Implicit),
+                    // The commit timeline has already waited for the acquire point.
+                    true => (AcquireSync::Unnecessary, ReleaseSync::Explicit),
                 };
+                buffer.update_texture_or_log(self, false, acquire_sync);
                 let release = pending
                     .release_point
                     .take()
@@ -1172,6 +1246,7 @@ impl WlSurface {
                     sync_files: Default::default(),
                     release_sync,
                     release,
+                    buffer_release: pending.buffer_release.take(),
                 };
                 self.buffer.set(Some(Rc::new(surface_buffer)));
             } else {
@@ -1358,6 +1433,10 @@ impl WlSurface {
                 // If we have a fifo barrier, must trigger latching.
                 output.global.connector.damage();
             }
+            let defer_damage = match self.toplevel.get() {
+                Some(tl) => self.client.state.interactive_resize.defers_damage_of(&*tl),
+                None => false,
+            };
             if damage_full {
                 let mut damage = buffer_abs_pos
                     .with_size(max_surface_size.0, max_surface_size.1)
@@ -1365,10 +1444,14 @@ impl WlSurface {
                 if let Some(tl) = self.toplevel.get() {
                     damage = damage.intersect(tl.node_absolute_position());
                 }
-                self.client.state.damage(damage);
+                if defer_damage {
+                    self.client.state.interactive_resize.defer_damage(damage);
+                } else {
+                    self.client.state.damage(damage);
+                }
             } else if pending.has_damage() {
-                self.apply_damage(pending);
-                if has_new_frame_requests {
+                self.apply_damage(pending, defer_damage);
+                if has_new_frame_requests && !defer_damage {
                     output.global.connector.damage();
                 }
             } else if has_new_frame_requests && output.schedule.vrr_enabled() {
@@ -1406,16 +1489,24 @@ impl WlSurface {
         }
     }
 
-    fn apply_damage(&self, pending: &PendingState) {
+    fn apply_damage(&self, pending: &PendingState, defer: bool) {
         let bounds = self.toplevel.get().map(|tl| tl.node_absolute_position());
         let pos = self.buffer_abs_pos.get();
+        let add_damage = |damage: Rect| {
+            let state = &self.client.state;
+            if defer {
+                state.interactive_resize.defer_damage(damage);
+            } else {
+                state.damage(damage);
+            }
+        };
         let apply_damage = |pos: Rect| {
             if pending.damage_full {
                 let mut damage = pos;
                 if let Some(bounds) = bounds {
                     damage = damage.intersect(bounds);
                 }
-                self.client.state.damage(damage);
+                add_damage(damage);
             } else {
                 let matrix = self.damage_matrix.get();
                 if let Some(buffer) = self.buffer.get() {
@@ -1425,7 +1516,7 @@ impl WlSurface {
                         if let Some(bounds) = bounds {
                             damage = damage.intersect(bounds);
                         }
-                        self.client.state.damage(damage);
+                        add_damage(damage);
                     }
                 }
                 for damage in &pending.surface_damage {
@@ -1438,7 +1529,7 @@ impl WlSurface {
                         damage = Rect::new(x1, y1, x2, y2).unwrap();
                     }
                     damage = damage.intersect(bounds.unwrap_or(pos));
-                    self.client.state.damage(damage);
+                    add_damage(damage);
                 }
             }
         };
@@ -1463,6 +1554,9 @@ impl WlSurface {
     }
 
     fn verify_explicit_sync(&self, pending: &mut PendingState) -> Result<(), WlSurfaceError> {
+        if self.surface_synchronization.is_some() {
+            return self.verify_legacy_explicit_sync(pending);
+        }
         pending.explicit_sync = self.sync_obj_surface.is_some();
         if !pending.explicit_sync {
             return Ok(());
@@ -1483,6 +1577,27 @@ impl WlSurface {
         }
     }
 
+    fn verify_legacy_explicit_sync(
+        &self,
+        pending: &mut PendingState,
+    ) -> Result<(), WlSurfaceError> {
+        pending.explicit_sync = pending.acquire_point.is_some();
+        let buffer = match &pending.buffer {
+            Some(Some(b)) => Some(b),
+            _ => None,
+        };
+        if pending.acquire_point.is_none() && pending.buffer_release.is_none() {
+            return Ok(());
+        }
+        let Some(buffer) = buffer else {
+            return Err(WlSurfaceError::UnexpectedSyncPoints);
+        };
+        if pending.acquire_point.is_some() && buffer.dmabuf.is_none() {
+            return Err(WlSurfaceError::AcquireFenceWithoutDmabuf);
+        }
+        Ok(())
+    }
+
     fn accepts_input_at(&self, mut x: i32, mut y: i32) -> bool {
         let rect = self.buffer_abs_pos.get().at_point(0, 0);
         if !rect.contains(x, y) {
@@ -1626,6 +1741,19 @@ impl WlSurface {
         }
     }
 
+    /// Focuses the visible toplevel of this surface if the input serial that triggered
+    /// the activation is newer than the keyboard focus of the seat.
+    pub fn request_activation_with_serial(&self, seat: &Rc<WlSeatGlobal>, serial: u64) {
+        if let Some(tl) = self.toplevel.get() {
+            if tl.tl_data().visible.get()
+                && seat.handle_activation_request(tl.tl_into_node(), serial)
+            {
+                return;
+            }
+        }
+        self.request_activation();
+    }
+
     pub fn send_feedback(&self, fb: &DrmFeedback) {
         for consumer in self.drm_feedback.lock().values() {
             consumer.send_feedback(fb);
@@ -1647,6 +1775,10 @@ impl WlSurface {
     pub fn alpha(&self) -> Option<f32> {
         self.alpha.get()
     }
+
+    pub fn color_transform(&self) -> Option<ColorTransform> {
+        self.color_description.get()?.transform
+    }
 }
 
 object_base! {
@@ -1665,6 +1797,8 @@ impl Object for WlSurface {
         self.buffer.set(None);
         self.toplevel.set(None);
         self.idle_inhibitors.clear();
+        self.shortcuts_inhibitors.clear();
+        self.xwayland_keyboard_grabs.clear();
         mem::take(self.pending.borrow_mut().deref_mut());
         self.presentation_feedback.borrow_mut().clear();
         self.latched_presentation_feedback.borrow_mut().clear();
@@ -1675,6 +1809,7 @@ impl Object for WlSurface {
         self.drm_feedback.clear();
         self.commit_timeline.clear(ClearReason::BreakLoops);
         self.alpha_modifier.take();
+        self.color_management_surface.take();
         self.text_input_connections.clear();
         self.fifo.take();
         self.commit_timer.take();
@@ -2018,6 +2153,8 @@ pub enum WlSurfaceError {
     MissingSyncPoints,
     #[error("No buffer is attached but acquire or release point is set")]
     UnexpectedSyncPoints,
+    #[error("An acquire fence is set but the attached buffer is not a dmabuf")]
+    AcquireFenceWithoutDmabuf,
     #[error("The supplied region is invalid")]
     InvalidRect,
     #[error("There is no render context")]
@@ -66,6 +66,17 @@ pub struct CommitTimeline {
     fifo_barrier_set: Cell<bool>,
     fifo_waiter: Cell<Option<NodeRef<Entry>>>,
     commit_time_waiter: RefCell<Option<CommitTimeWaiter>>,
+    configure_barrier: RefCell<Option<ConfigureBarrier>>,
+}
+
+/// Holds back the commits of desynchronized subsurfaces until the toplevel has applied
+/// the commit that acknowledges a configure sent during an interactive resize.
+///
+/// This ensures that the toplevel and its subsurfaces latch the new size together.
+struct ConfigureBarrier {
+    serial: u32,
+    acked: bool,
+    waiters: Vec<NodeRef<Entry>>,
 }
 
 struct Inner {
@@ -148,6 +159,7 @@ impl CommitTimelines {
             fifo_barrier_set: Cell::new(false),
             fifo_waiter: Default::default(),
             commit_time_waiter: Default::default(),
+            configure_barrier: Default::default(),
         }
     }
 
@@ -181,10 +193,17 @@ impl CommitTimeline {
             ClearReason::BreakLoops => {
                 self.fifo_waiter.take();
                 self.commit_time_waiter.take();
+                self.configure_barrier.take();
                 break_loops(&self.own_timeline.entries)
             }
             ClearReason::Destroy => {
                 self.clear_fifo_barrier();
+                if let Some(barrier) = self.configure_barrier.take() {
+                    for waiter in barrier.waiters {
+                        signal_wait(&waiter);
+                        self.shared.flush_requests.flush_waiters.push(waiter);
+                    }
+                }
                 if self.own_timeline.entries.is_not_empty() {
                     let list = LinkedList::new();
                     list.append_all(&self.own_timeline.entries);
@@ -216,18 +235,46 @@ impl CommitTimeline {
             || pending_uploads > 0
             || implicit_dmabufs.is_not_empty()
             || has_commit_time;
+        let releases_configure_barrier = match &*self.configure_barrier.borrow() {
+            Some(b) => b.acked,
+            None => false,
+        };
+        let configure_barrier_root = match surface.ext.get().subsurface_parent() {
+            Some(_) => {
+                let root = surface.get_root();
+                let has_barrier = root.commit_timeline.configure_barrier.borrow().is_some();
+                has_barrier.then_some(root)
+            }
+            None => None,
+        };
         let must_be_queued = has_dependencies
+            || configure_barrier_root.is_some()
             || self.own_timeline.entries.is_not_empty()
             || (pending.fifo_barrier_wait && self.fifo_barrier_set.get());
         if !must_be_queued {
-            return surface
+            surface
                 .apply_state(pending)
-                .map_err(CommitTimelineError::ImmediateCommit);
+                .map_err(CommitTimelineError::ImmediateCommit)?;
+            if releases_configure_barrier {
+                self.release_configure_barrier()
+                    .map_err(CommitTimelineError::DelayedCommit)?;
+            }
+            return Ok(());
         }
         if self.shared.depth.get() >= MAX_TIMELINE_DEPTH {
             return Err(CommitTimelineError::Depth);
         }
         set_effective_timeline(self, pending, &self.own_timeline);
+        if let Some(root) = configure_barrier_root {
+            let waiter = add_entry(
+                &self.own_timeline.entries,
+                &self.shared,
+                EntryKind::Wait(Cell::new(false)),
+            );
+            if let Some(barrier) = &mut *root.commit_timeline.configure_barrier.borrow_mut() {
+                barrier.waiters.push(waiter);
+            }
+        }
         let commit_fifo_state = match pending.fifo_barrier_wait {
             true => CommitFifoState::Queued,
             false => CommitFifoState::Mailbox,
@@ -246,6 +293,7 @@ impl CommitTimeline {
                 pending_polls: Cell::new(Default::default()),
                 fifo_state: Cell::new(commit_fifo_state),
                 commit_times: RefCell::new(CommitTimesState::Ready),
+                releases_configure_barrier,
             }),
         );
         let mut needs_flush = commit_fifo_state == CommitFifoState::Queued;
@@ -311,6 +359,38 @@ impl CommitTimeline {
         self.fifo_barrier_set.get()
     }
 
+    /// Holds back commits of desynchronized subsurfaces until the configure with this
+    /// serial has been acknowledged and committed.
+    pub fn set_configure_barrier(&self, serial: u32) {
+        let barrier = &mut *self.configure_barrier.borrow_mut();
+        if barrier.is_none() {
+            *barrier = Some(ConfigureBarrier {
+                serial,
+                acked: false,
+                waiters: vec![],
+            });
+        }
+    }
+
+    pub fn configure_acked(&self, serial: u32) {
+        if let Some(barrier) = &mut *self.configure_barrier.borrow_mut() {
+            if serial >= barrier.serial {
+                barrier.acked = true;
+            }
+        }
+    }
+
+    fn release_configure_barrier(&self) -> Result<(), WlSurfaceError> {
+        let Some(barrier) = self.configure_barrier.take() else {
+            return Ok(());
+        };
+        for waiter in barrier.waiters {
+            signal_wait(&waiter);
+            flush_from(waiter)?;
+        }
+        Ok(())
+    }
+
     pub fn before_latch(&self, surface: &WlSurface, present: u64) -> BeforeLatchResult {
         let waiter = &mut *self.commit_time_waiter.borrow_mut();
         if let Some(w) = waiter {
@@ -448,6 +528,7 @@ struct Commit {
     pending_polls: Cell<SmallVec<[PendingPoll; 1]>>,
     fifo_state: Cell<CommitFifoState>,
     commit_times: RefCell<CommitTimesState>,
+    releases_configure_barrier: bool,
 }
 
 fn flush_from(mut point: NodeRef<Entry>) -> Result<(), WlSurfaceError> {
@@ -514,6 +595,9 @@ impl NodeRef<Entry> {
                     return Ok(false);
                 }
                 c.surface.apply_state(c.pending.borrow_mut().deref_mut())?;
+                if c.releases_configure_barrier {
+                    tl.release_configure_barrier()?;
+                }
                 Ok(true)
             }
             EntryKind::Wait(signaled) => Ok(signaled.get()),
@@ -533,6 +617,12 @@ impl NodeRef<Entry> {
     }
 }
 
+fn signal_wait(waiter: &NodeRef<Entry>) {
+    if let EntryKind::Wait(signaled) = &waiter.kind {
+        signaled.set(true);
+    }
+}
+
 fn check_shm_uploads(c: &Commit) -> Result<(), WlSurfaceError> {
     let state = &mut *c.shm_upload.borrow_mut();
     if let ShmUploadState::Todo(node_ref) = state {
@@ -662,6 +752,7 @@ fn schedule_async_upload(
             s
         }
     };
+    surface.client.state.texture_uploads.fetch_add(1);
     back_tex
         .async_upload(&staging, node_ref.clone(), mem.clone(), back.damage.get())
         .map_err(WlSurfaceError::PrepareAsyncUpload)
@@ -0,0 +1,105 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{color_management::RENDER_INTENT_PERCEPTUAL, wl_surface::WlSurface},
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{wp_color_management_surface_v1::*, WpColorManagementSurfaceV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct WpColorManagementSurfaceV1 {
+    pub id: WpColorManagementSurfaceV1Id,
+    pub version: Version,
+    pub client: Rc<Client>,
+    pub surface: Rc<WlSurface>,
+    pub tracker: Tracker<Self>,
+}
+
+impl WpColorManagementSurfaceV1 {
+    pub fn new(
+        id: WpColorManagementSurfaceV1Id,
+        surface: &Rc<WlSurface>,
+        version: Version,
+    ) -> Self {
+        Self {
+            id,
+            version,
+            client: surface.client.clone(),
+            surface: surface.clone(),
+            tracker: Default::default(),
+        }
+    }
+
+    pub fn install(self: &Rc<Self>) -> Result<(), WpColorManagementSurfaceV1Error> {
+        if self.surface.color_management_surface.is_some() {
+            return Err(WpColorManagementSurfaceV1Error::Exists);
+        }
+        self.surface
+            .color_management_surface
+            .set(Some(self.clone()));
+        Ok(())
+    }
+}
+
+impl WpColorManagementSurfaceV1RequestHandler for WpColorManagementSurfaceV1 {
+    type Error = WpColorManagementSurfaceV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.surface.color_management_surface.take();
+        self.surface.pending.borrow_mut().color_description = Some(None);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn set_image_description(
+        &self,
+        req: SetImageDescription,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        if req.render_intent != RENDER_INTENT_PERCEPTUAL {
+            return Err(WpColorManagementSurfaceV1Error::RenderIntent(
+                req.render_intent,
+            ));
+        }
+        let desc = self.client.lookup(req.image_description)?;
+        let Some(description) = desc.description.clone() else {
+            return Err(WpColorManagementSurfaceV1Error::NotReady);
+        };
+        self.surface.pending.borrow_mut().color_description = Some(Some(description));
+        Ok(())
+    }
+
+    fn unset_image_description(
+        &self,
+        _req: UnsetImageDescription,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.surface.pending.borrow_mut().color_description = Some(None);
+        Ok(())
+    }
+}
+
+object_base! {
+    self = WpColorManagementSurfaceV1;
+    version = self.version;
+}
+
+impl Object for WpColorManagementSurfaceV1 {}
+
+simple_add_obj!(WpColorManagementSurfaceV1);
+
+#[derive(Debug, Error)]
+pub enum WpColorManagementSurfaceV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The surface already has a color management extension attached")]
+    Exists,
+    #[error("Render intent {0} is not supported")]
+    RenderIntent(u32),
+    #[error("The image description is not ready")]
+    NotReady,
+}
+efrom!(WpColorManagementSurfaceV1Error, ClientError);
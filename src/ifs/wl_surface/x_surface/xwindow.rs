@@ -27,6 +27,7 @@ use {
         rc::Rc,
     },
     thiserror::Error,
+    uapi::c,
 };
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -281,7 +282,7 @@ impl Xwindow {
                 self.data.title_changed();
             }
             Change::Map => {
-                self.data.state.map_tiled(self.clone());
+                self.data.state.map_toplevel(self.clone());
                 self.data.title_changed();
             }
         }
@@ -414,6 +415,10 @@ impl ToplevelNodeBase for Xwindow {
         self.x.surface.set_output(&ws.output.get());
     }
 
+    fn tl_pid(&self) -> Option<c::pid_t> {
+        self.data.info.pid.get().map(|pid| pid as c::pid_t)
+    }
+
     fn tl_change_extents_impl(self: Rc<Self>, rect: &Rect) {
         // log::info!("xwin {} change_extents {:?}", self.data.window_id, rect);
         let old = self.data.info.extents.replace(*rect);
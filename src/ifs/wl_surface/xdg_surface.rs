@@ -13,6 +13,7 @@ use {
                 },
                 PendingState, SurfaceExt, SurfaceRole, WlSurface, WlSurfaceError,
             },
+            xdg_toplevel_icon_v1::ToplevelIcon,
             xdg_wm_base::XdgWmBase,
         },
         leaks::Tracker,
@@ -131,7 +132,9 @@ impl XdgPopupParent for Popup {
                 self.popup.set_visible(self.parent.surface.visible.get());
             }
         } else {
-            if wl.take().is_some() {
+            let had_wl = wl.take().is_some();
+            let had_dl = dl.take().is_some();
+            if had_wl || had_dl {
                 drop(wl);
                 drop(dl);
                 self.popup.set_visible(false);
@@ -143,11 +146,19 @@ impl XdgPopupParent for Popup {
     fn tray_item(&self) -> Option<TrayItemId> {
         self.parent.clone().tray_item()
     }
+
+    fn allow_popup_focus(&self) -> bool {
+        self.parent
+            .ext
+            .get()
+            .is_some_and(|ext| ext.allow_popup_focus())
+    }
 }
 
 #[derive(Default, Debug)]
 pub struct PendingXdgSurfaceData {
     geometry: Option<Rect>,
+    pub icon: Option<Option<Rc<ToplevelIcon>>>,
 }
 
 impl PendingXdgSurfaceData {
@@ -160,6 +171,7 @@ impl PendingXdgSurfaceData {
             };
         }
         opt!(geometry);
+        opt!(icon);
     }
 }
 
@@ -180,6 +192,10 @@ pub trait XdgSurfaceExt: Debug {
         // nothing
     }
 
+    fn icon_changed(&self, icon: Option<Rc<ToplevelIcon>>) {
+        let _ = icon;
+    }
+
     fn focus_node(&self) -> Option<Rc<dyn Node>> {
         None
     }
@@ -187,6 +203,10 @@ pub trait XdgSurfaceExt: Debug {
     fn tray_item(&self) -> Option<TrayItemId> {
         None
     }
+
+    fn allow_popup_focus(&self) -> bool {
+        false
+    }
 }
 
 impl XdgSurface {
@@ -289,8 +309,17 @@ impl XdgSurface {
         self.geometry.get()
     }
 
+    /// Returns whether the client has not yet acknowledged the last configure event.
+    pub fn configure_in_flight(&self) -> bool {
+        let requested = self.requested_serial.get();
+        requested != 0 && self.acked_serial.get() != Some(requested)
+    }
+
     pub fn do_send_configure(&self) {
         let serial = self.requested_serial.fetch_add(1) + 1;
+        if self.surface.client.state.interactive_resize.in_progress() {
+            self.surface.commit_timeline.set_configure_barrier(serial);
+        }
         self.send_configure(serial);
     }
 
@@ -310,7 +339,7 @@ impl XdgSurface {
         Ok(())
     }
 
-    fn pending(&self) -> RefMut<Box<PendingXdgSurfaceData>> {
+    pub fn pending(&self) -> RefMut<Box<PendingXdgSurfaceData>> {
         RefMut::map(self.surface.pending.borrow_mut(), |p| {
             p.xdg_surface.get_or_insert_default_ext()
         })
@@ -431,6 +460,7 @@ impl XdgSurfaceRequestHandler for XdgSurface {
         if self.requested_serial.get() == req.serial {
             self.acked_serial.set(Some(req.serial));
         }
+        self.surface.commit_timeline.configure_acked(req.serial);
         Ok(())
     }
 }
@@ -526,6 +556,11 @@ impl SurfaceExt for XdgSurface {
                     }
                 }
             }
+            if let Some(icon) = pending.icon.take() {
+                if let Some(ext) = self.ext.get() {
+                    ext.icon_changed(icon);
+                }
+            }
         }
         Ok(())
     }
@@ -9,10 +9,7 @@ use {
                 tray::TrayItemId,
                 xdg_surface::{XdgSurface, XdgSurfaceError, XdgSurfaceExt},
             },
-            xdg_positioner::{
-                XdgPositioned, XdgPositioner, CA_FLIP_X, CA_FLIP_Y, CA_RESIZE_X, CA_RESIZE_Y,
-                CA_SLIDE_X, CA_SLIDE_Y,
-            },
+            xdg_positioner::{XdgPositioned, XdgPositioner},
         },
         leaks::Tracker,
         object::Object,
@@ -120,93 +117,9 @@ impl XdgPopup {
     fn update_position(&self, parent: &dyn XdgPopupParent) {
         let positioner = self.pos.borrow_mut();
         let parent_abs = parent.position();
-        let mut rel_pos = positioner.get_position(false, false);
-        let mut abs_pos = rel_pos.move_(parent_abs.x1(), parent_abs.y1());
-        {
-            let output_pos = parent.output().global.pos.get();
-            let mut overflow = output_pos.get_overflow(&abs_pos);
-            if !overflow.is_contained() {
-                let mut flip_x = positioner.ca.contains(CA_FLIP_X) && overflow.x_overflow();
-                let mut flip_y = positioner.ca.contains(CA_FLIP_Y) && overflow.y_overflow();
-                if flip_x || flip_y {
-                    let mut adj_rel = positioner.get_position(flip_x, flip_y);
-                    let mut adj_abs = adj_rel.move_(parent_abs.x1(), parent_abs.y1());
-                    let mut adj_overflow = output_pos.get_overflow(&adj_abs);
-                    let mut recalculate = false;
-                    if flip_x && adj_overflow.x_overflow() {
-                        flip_x = false;
-                        recalculate = true;
-                    }
-                    if flip_y && adj_overflow.y_overflow() {
-                        flip_y = false;
-                        recalculate = true;
-                    }
-                    if flip_x || flip_y {
-                        if recalculate {
-                            adj_rel = positioner.get_position(flip_x, flip_y);
-                            adj_abs = adj_rel.move_(parent_abs.x1(), parent_abs.y1());
-                            adj_overflow = output_pos.get_overflow(&adj_abs);
-                        }
-                        rel_pos = adj_rel;
-                        abs_pos = adj_abs;
-                        overflow = adj_overflow;
-                    }
-                }
-                let (mut dx, mut dy) = (0, 0);
-                if positioner.ca.contains(CA_SLIDE_X) && overflow.x_overflow() {
-                    dx = if overflow.left + overflow.right > 0 {
-                        parent_abs.x1() - abs_pos.x1()
-                    } else if overflow.left > 0 {
-                        overflow.left
-                    } else {
-                        -overflow.right
-                    };
-                }
-                if positioner.ca.contains(CA_SLIDE_Y) && overflow.y_overflow() {
-                    dy = if overflow.top + overflow.bottom > 0 {
-                        parent_abs.y1() - abs_pos.y1()
-                    } else if overflow.top > 0 {
-                        overflow.top
-                    } else {
-                        -overflow.bottom
-                    };
-                }
-                if dx != 0 || dy != 0 {
-                    rel_pos = rel_pos.move_(dx, dy);
-                    abs_pos = rel_pos.move_(parent_abs.x1(), parent_abs.y1());
-                    overflow = output_pos.get_overflow(&abs_pos);
-                }
-                let (mut dx1, mut dx2, mut dy1, mut dy2) = (0, 0, 0, 0);
-                if positioner.ca.contains(CA_RESIZE_X) {
-                    dx1 = overflow.left.max(0);
-                    dx2 = -overflow.right.max(0);
-                }
-                if positioner.ca.contains(CA_RESIZE_Y) {
-                    dy1 = overflow.top.max(0);
-                    dy2 = -overflow.bottom.max(0);
-                }
-                if dx1 > 0 || dx2 < 0 || dy1 > 0 || dy2 < 0 {
-                    let maybe_abs_pos = Rect::new(
-                        abs_pos.x1() + dx1,
-                        abs_pos.y1() + dy1,
-                        abs_pos.x2() + dx2,
-                        abs_pos.y2() + dy2,
-                    );
-                    // If the popup is completely outside the output, this will fail. Just
-                    // use its position as is.
-                    if let Some(maybe_abs_pos) = maybe_abs_pos {
-                        abs_pos = maybe_abs_pos;
-                        rel_pos = Rect::new_sized(
-                            abs_pos.x1() - parent_abs.x1(),
-                            abs_pos.y1() - parent_abs.y1(),
-                            abs_pos.width(),
-                            abs_pos.height(),
-                        )
-                        .unwrap();
-                    }
-                }
-            }
-        }
+        let output_pos = parent.output().global.pos.get();
+        let rel_pos = positioner.constrain(parent_abs, output_pos);
+        let abs_pos = rel_pos.move_(parent_abs.x1(), parent_abs.y1());
         self.relative_position.set(rel_pos);
         self.xdg.set_absolute_desired_extents(&abs_pos);
     }
@@ -412,6 +325,12 @@ impl XdgSurfaceExt for XdgPopup {
     fn tray_item(&self) -> Option<TrayItemId> {
         self.parent.get()?.tray_item()
     }
+
+    fn allow_popup_focus(&self) -> bool {
+        self.parent
+            .get()
+            .is_some_and(|parent| parent.allow_popup_focus())
+    }
 }
 
 #[derive(Debug, Error)]
@@ -18,6 +18,7 @@ use {
                 WlSurface,
             },
             xdg_toplevel_drag_v1::XdgToplevelDragV1,
+            xdg_toplevel_icon_v1::ToplevelIcon,
         },
         leaks::Tracker,
         object::{Object, Version},
@@ -106,6 +107,7 @@ pub struct XdgToplevel {
     is_mapped: Cell<bool>,
     dialog: CloneCell<Option<Rc<XdgDialogV1>>>,
     extents_set: Cell<bool>,
+    throttled_size: Cell<Option<(i32, i32)>>,
 }
 
 impl Debug for XdgToplevel {
@@ -147,6 +149,7 @@ impl XdgToplevel {
             is_mapped: Cell::new(false),
             dialog: Default::default(),
             extents_set: Cell::new(false),
+            throttled_size: Default::default(),
         }
     }
 
@@ -375,7 +378,7 @@ impl XdgToplevel {
     }
 
     fn map_tiled(self: &Rc<Self>) {
-        self.state.map_tiled(self.clone());
+        self.state.map_toplevel(self.clone());
     }
 
     pub fn prepare_toplevel_drag(&self) {
@@ -596,8 +599,13 @@ impl ToplevelNodeBase for XdgToplevel {
         let nh = rect.height();
         let de = self.xdg.absolute_desired_extents.get();
         if de.width() != nw || de.height() != nh {
-            self.send_configure_checked(nw, nh);
-            self.xdg.do_send_configure();
+            if self.state.interactive_resize.throttled() && self.xdg.configure_in_flight() {
+                self.throttled_size.set(Some((nw, nh)));
+            } else {
+                self.throttled_size.take();
+                self.send_configure_checked(nw, nh);
+                self.xdg.do_send_configure();
+            }
             // self.xdg.surface.client.flush();
         }
         self.xdg.set_absolute_desired_extents(rect);
@@ -693,6 +701,10 @@ impl XdgSurfaceExt for XdgToplevel {
     }
 
     fn post_commit(self: Rc<Self>) {
+        if let Some((width, height)) = self.throttled_size.take() {
+            self.send_configure_checked(width, height);
+            self.xdg.do_send_configure();
+        }
         self.after_commit(None);
     }
 
@@ -708,6 +720,10 @@ impl XdgSurfaceExt for XdgToplevel {
             .state
             .damage(self.node_absolute_position());
     }
+
+    fn icon_changed(&self, icon: Option<Rc<ToplevelIcon>>) {
+        self.toplevel_data.set_icon(icon);
+    }
 }
 
 #[derive(Debug, Error)]
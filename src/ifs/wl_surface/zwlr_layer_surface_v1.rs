@@ -681,6 +681,10 @@ impl XdgPopupParent for Popup {
         false
     }
 
+    fn allow_popup_focus(&self) -> bool {
+        self.parent.keyboard_interactivity.get() != KI_NONE
+    }
+
     fn post_commit(&self) {
         let mut dl = self.stack_link.borrow_mut();
         let output = self.output();
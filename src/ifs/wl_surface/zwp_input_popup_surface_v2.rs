@@ -4,6 +4,9 @@ use {
         ifs::{
             wl_seat::text_input::zwp_input_method_v2::ZwpInputMethodV2,
             wl_surface::{SurfaceExt, SurfaceRole, WlSurface, WlSurfaceError},
+            xdg_positioner::{
+                XdgPositioned, CA_FLIP_Y, CA_SLIDE_X, CA_SLIDE_Y, E_BOTTOM, E_LEFT, E_RIGHT,
+            },
         },
         leaks::Tracker,
         object::{Object, Version},
@@ -23,6 +26,7 @@ pub struct ZwpInputPopupSurfaceV2 {
     pub version: Version,
     pub tracker: Tracker<Self>,
     pub positioning_scheduled: Cell<bool>,
+    pub text_input_rectangle: Cell<Option<Rect>>,
 }
 
 impl SurfaceExt for ZwpInputPopupSurfaceV2 {
@@ -87,32 +91,34 @@ impl ZwpInputPopupSurfaceV2 {
         };
         let output = con.surface.output.get().global.pos.get();
         let surface_rect = con.surface.buffer_abs_pos.get();
-        let cursor_rect = con
-            .text_input
-            .cursor_rect()
-            .move_(surface_rect.x1(), surface_rect.y1());
+        let cursor_rect = con.text_input.cursor_rect();
         let extents = self.surface.extents.get();
-        let mut rect = extents.at_point(cursor_rect.x1(), cursor_rect.y2());
-        let overflow = output.get_overflow(&rect);
-        if overflow.right > 0 {
-            let dx = -overflow.right.min(rect.width());
-            let rect2 = rect.move_(dx, 0);
-            if !output.get_overflow(&rect2).x_overflow() {
-                rect = rect2;
-            }
-        }
-        if overflow.bottom > 0 {
-            let rect2 = rect.move_(0, -(cursor_rect.height() + rect.height()));
-            if !output.get_overflow(&rect2).y_overflow() {
-                rect = rect2;
-            }
+        let positioner = XdgPositioned {
+            size_width: extents.width(),
+            size_height: extents.height(),
+            ar: cursor_rect,
+            anchor: E_BOTTOM | E_LEFT,
+            gravity: E_BOTTOM | E_RIGHT,
+            ca: CA_FLIP_Y | CA_SLIDE_X | CA_SLIDE_Y,
+            ..Default::default()
+        };
+        let rect = positioner
+            .constrain(surface_rect, output)
+            .move_(surface_rect.x1(), surface_rect.y1());
+        let old_pos = self.surface.buffer_abs_pos.get();
+        let new_pos = old_pos.at_point(rect.x1() - extents.x1(), rect.y1() - extents.y1());
+        if old_pos != new_pos {
+            self.damage();
+            self.surface.buffer_abs_pos.set(new_pos);
+            self.damage();
         }
-        self.surface.buffer_abs_pos.set(
-            self.surface
-                .buffer_abs_pos
-                .get()
-                .at_point(rect.x1() - extents.x1(), rect.y1() - extents.y1()),
+        let text_input_rect = cursor_rect.move_(
+            surface_rect.x1() - new_pos.x1(),
+            surface_rect.y1() - new_pos.y1(),
         );
+        if self.text_input_rectangle.replace(Some(text_input_rect)) != Some(text_input_rect) {
+            self.send_text_input_rectangle(text_input_rect);
+        }
     }
 
     pub fn install(self: &Rc<Self>) -> Result<(), ZwpInputPopupSurfaceV2Error> {
@@ -127,8 +133,7 @@ impl ZwpInputPopupSurfaceV2 {
         Ok(())
     }
 
-    #[expect(dead_code)]
-    pub fn send_text_input_rectangle(&self, rect: Rect) {
+    fn send_text_input_rectangle(&self, rect: Rect) {
         self.client.event(TextInputRectangle {
             self_id: self.id,
             x: rect.x1(),
@@ -0,0 +1,80 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{wl_seat::WlSeatGlobal, wl_surface::WlSurface},
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{zwp_keyboard_shortcuts_inhibitor_v1::*, ZwpKeyboardShortcutsInhibitorV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwpKeyboardShortcutsInhibitorV1 {
+    pub id: ZwpKeyboardShortcutsInhibitorV1Id,
+    pub client: Rc<Client>,
+    pub surface: Rc<WlSurface>,
+    pub seat: Rc<WlSeatGlobal>,
+    pub active: Cell<bool>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl ZwpKeyboardShortcutsInhibitorV1 {
+    pub fn install(self: &Rc<Self>) -> Result<(), ZwpKeyboardShortcutsInhibitorV1Error> {
+        let seat_id = self.seat.id();
+        if self.surface.shortcuts_inhibitors.contains(&seat_id) {
+            return Err(ZwpKeyboardShortcutsInhibitorV1Error::AlreadyInhibited);
+        }
+        self.surface
+            .shortcuts_inhibitors
+            .insert(seat_id, self.clone());
+        self.seat.shortcuts_inhibitor_installed(self);
+        Ok(())
+    }
+
+    pub fn send_active(&self) {
+        self.client.event(Active { self_id: self.id });
+    }
+
+    pub fn send_inactive(&self) {
+        self.client.event(Inactive { self_id: self.id });
+    }
+
+    pub fn detach(&self) {
+        self.surface.shortcuts_inhibitors.remove(&self.seat.id());
+        self.seat.remove_shortcuts_inhibitor(self);
+    }
+}
+
+impl ZwpKeyboardShortcutsInhibitorV1RequestHandler for ZwpKeyboardShortcutsInhibitorV1 {
+    type Error = ZwpKeyboardShortcutsInhibitorV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwpKeyboardShortcutsInhibitorV1;
+    version = self.version;
+}
+
+impl Object for ZwpKeyboardShortcutsInhibitorV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwpKeyboardShortcutsInhibitorV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpKeyboardShortcutsInhibitorV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The surface already has a shortcuts inhibitor for this seat")]
+    AlreadyInhibited,
+}
+efrom!(ZwpKeyboardShortcutsInhibitorV1Error, ClientError);
@@ -0,0 +1,79 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        gfx_api::SyncFile,
+        leaks::Tracker,
+        object::{Object, Version},
+        utils::errorfmt::ErrorFmt,
+        video::drm::sync_ioc_merge,
+        wire::{zwp_linux_buffer_release_v1::*, ZwpLinuxBufferReleaseV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwpLinuxBufferReleaseV1 {
+    id: ZwpLinuxBufferReleaseV1Id,
+    client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    version: Version,
+}
+
+impl ZwpLinuxBufferReleaseV1 {
+    pub fn new(id: ZwpLinuxBufferReleaseV1Id, client: &Rc<Client>, version: Version) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+        }
+    }
+
+    /// Sends the release event and destroys the object.
+    ///
+    /// If `sync_files` is not empty, the buffer is released once all of them have been
+    /// signaled.
+    pub fn release<'a>(&self, sync_files: impl IntoIterator<Item = &'a SyncFile>) {
+        let mut fence = None::<Rc<_>>;
+        for sync_file in sync_files {
+            fence = match fence {
+                None => Some(sync_file.0.clone()),
+                Some(prev) => match sync_ioc_merge(prev.raw(), sync_file.0.raw()) {
+                    Ok(merged) => Some(Rc::new(merged)),
+                    Err(e) => {
+                        log::error!("Could not merge sync files: {}", ErrorFmt(e));
+                        Some(prev)
+                    }
+                },
+            };
+        }
+        match fence {
+            Some(fence) => self.client.event(FencedRelease {
+                self_id: self.id,
+                fence,
+            }),
+            None => self.client.event(ImmediateRelease { self_id: self.id }),
+        }
+        let _ = self.client.remove_obj(self);
+    }
+}
+
+impl ZwpLinuxBufferReleaseV1RequestHandler for ZwpLinuxBufferReleaseV1 {
+    type Error = ZwpLinuxBufferReleaseV1Error;
+}
+
+object_base! {
+    self = ZwpLinuxBufferReleaseV1;
+    version = self.version;
+}
+
+impl Object for ZwpLinuxBufferReleaseV1 {}
+
+simple_add_obj!(ZwpLinuxBufferReleaseV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpLinuxBufferReleaseV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwpLinuxBufferReleaseV1Error, ClientError);
@@ -0,0 +1,137 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        gfx_api::SyncFile,
+        ifs::wl_surface::{zwp_linux_buffer_release_v1::ZwpLinuxBufferReleaseV1, WlSurface},
+        leaks::Tracker,
+        object::{Object, Version},
+        video::drm::{
+            sync_obj::{SyncObjCtx, SyncObjPoint},
+            DrmError,
+        },
+        wire::{zwp_linux_surface_synchronization_v1::*, ZwpLinuxSurfaceSynchronizationV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwpLinuxSurfaceSynchronizationV1 {
+    id: ZwpLinuxSurfaceSynchronizationV1Id,
+    client: Rc<Client>,
+    surface: Rc<WlSurface>,
+    pub tracker: Tracker<Self>,
+    version: Version,
+}
+
+impl ZwpLinuxSurfaceSynchronizationV1 {
+    pub fn new(
+        id: ZwpLinuxSurfaceSynchronizationV1Id,
+        client: &Rc<Client>,
+        surface: &Rc<WlSurface>,
+        version: Version,
+    ) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            surface: surface.clone(),
+            version,
+        }
+    }
+
+    pub fn install(self: &Rc<Self>) -> Result<(), ZwpLinuxSurfaceSynchronizationV1Error> {
+        if self.surface.surface_synchronization.is_some() {
+            return Err(ZwpLinuxSurfaceSynchronizationV1Error::Exists);
+        }
+        self.surface.surface_synchronization.set(Some(self.clone()));
+        Ok(())
+    }
+
+    fn sync_obj_ctx(&self) -> Result<Rc<SyncObjCtx>, ZwpLinuxSurfaceSynchronizationV1Error> {
+        self.client
+            .state
+            .render_ctx
+            .get()
+            .and_then(|ctx| ctx.sync_obj_ctx().cloned())
+            .ok_or(ZwpLinuxSurfaceSynchronizationV1Error::NoSyncObjCtx)
+    }
+}
+
+impl ZwpLinuxSurfaceSynchronizationV1RequestHandler for ZwpLinuxSurfaceSynchronizationV1 {
+    type Error = ZwpLinuxSurfaceSynchronizationV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.surface.surface_synchronization.take();
+        let pending = &mut *self.surface.pending.borrow_mut();
+        pending.acquire_point.take();
+        pending.buffer_release.take();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn set_acquire_fence(&self, req: SetAcquireFence, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.surface.destroyed.get() {
+            return Err(ZwpLinuxSurfaceSynchronizationV1Error::NoSurface);
+        }
+        if self.surface.pending.borrow().acquire_point.is_some() {
+            return Err(ZwpLinuxSurfaceSynchronizationV1Error::DuplicateFence);
+        }
+        // The commit timeline only knows how to wait for sync obj points. Import the
+        // fence into a fresh sync obj so that the same machinery can be used.
+        let ctx = self.sync_obj_ctx()?;
+        let sync_obj = ctx.create_sync_obj()?;
+        let point = SyncObjPoint(1);
+        ctx.import_sync_files(&sync_obj, point, [&SyncFile(req.fd)])
+            .map_err(ZwpLinuxSurfaceSynchronizationV1Error::InvalidFence)?;
+        self.surface.pending.borrow_mut().acquire_point = Some((Rc::new(sync_obj), point));
+        Ok(())
+    }
+
+    fn get_release(&self, req: GetRelease, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.surface.destroyed.get() {
+            return Err(ZwpLinuxSurfaceSynchronizationV1Error::NoSurface);
+        }
+        if self.surface.pending.borrow().buffer_release.is_some() {
+            return Err(ZwpLinuxSurfaceSynchronizationV1Error::DuplicateRelease);
+        }
+        let release = Rc::new(ZwpLinuxBufferReleaseV1::new(
+            req.release,
+            &self.client,
+            self.version,
+        ));
+        track!(self.client, release);
+        self.client.add_client_obj(&release)?;
+        self.surface.pending.borrow_mut().buffer_release = Some(release);
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwpLinuxSurfaceSynchronizationV1;
+    version = self.version;
+}
+
+impl Object for ZwpLinuxSurfaceSynchronizationV1 {}
+
+simple_add_obj!(ZwpLinuxSurfaceSynchronizationV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpLinuxSurfaceSynchronizationV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The surface already has a synchronization object attached")]
+    Exists,
+    #[error("The surface has been destroyed")]
+    NoSurface,
+    #[error("An acquire fence has already been set for this commit")]
+    DuplicateFence,
+    #[error("A release object has already been requested for this commit")]
+    DuplicateRelease,
+    #[error("The acquire fence is invalid")]
+    InvalidFence(#[source] DrmError),
+    #[error("There is no render context with sync obj support")]
+    NoSyncObjCtx,
+    #[error(transparent)]
+    DrmError(#[from] DrmError),
+}
+efrom!(ZwpLinuxSurfaceSynchronizationV1Error, ClientError);
@@ -0,0 +1,73 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{wl_seat::WlSeatGlobal, wl_surface::WlSurface},
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{zwp_xwayland_keyboard_grab_v1::*, ZwpXwaylandKeyboardGrabV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwpXwaylandKeyboardGrabV1 {
+    pub id: ZwpXwaylandKeyboardGrabV1Id,
+    pub client: Rc<Client>,
+    pub surface: Rc<WlSurface>,
+    pub seat: Rc<WlSeatGlobal>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl ZwpXwaylandKeyboardGrabV1 {
+    pub fn install(self: &Rc<Self>) {
+        let seat_id = self.seat.id();
+        if let Some(old) = self.surface.xwayland_keyboard_grabs.remove(&seat_id) {
+            self.seat.remove_xwayland_keyboard_grab(&old);
+        }
+        self.surface
+            .xwayland_keyboard_grabs
+            .insert(seat_id, self.clone());
+        self.seat.xwayland_keyboard_grab_installed(self);
+    }
+
+    fn detach(&self) {
+        let seat_id = self.seat.id();
+        if let Some(grab) = self.surface.xwayland_keyboard_grabs.get(&seat_id) {
+            if grab.id == self.id {
+                self.surface.xwayland_keyboard_grabs.remove(&seat_id);
+            }
+        }
+        self.seat.remove_xwayland_keyboard_grab(self);
+    }
+}
+
+impl ZwpXwaylandKeyboardGrabV1RequestHandler for ZwpXwaylandKeyboardGrabV1 {
+    type Error = ZwpXwaylandKeyboardGrabV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwpXwaylandKeyboardGrabV1;
+    version = self.version;
+}
+
+impl Object for ZwpXwaylandKeyboardGrabV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwpXwaylandKeyboardGrabV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpXwaylandKeyboardGrabV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwpXwaylandKeyboardGrabV1Error, ClientError);
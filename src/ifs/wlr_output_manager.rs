@@ -0,0 +1,161 @@
+use {
+    crate::{
+        backend::{ConnectorId, Mode},
+        ifs::{
+            wl_output::OutputId,
+            wlr_output_manager::zwlr_output_manager_v1::{
+                WlrOutputManagerId, WlrOutputManagerIds, ZwlrOutputManagerV1,
+            },
+        },
+        scale::Scale,
+        state::State,
+        utils::{asyncevent::AsyncEvent, copyhashmap::CopyHashMap, numcell::NumCell},
+    },
+    jay_config::video::Transform,
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub mod zwlr_output_configuration_head_v1;
+pub mod zwlr_output_configuration_v1;
+pub mod zwlr_output_head_v1;
+pub mod zwlr_output_manager_v1;
+pub mod zwlr_output_mode_v1;
+
+#[derive(Default)]
+pub struct WlrOutputManagerState {
+    ids: WlrOutputManagerIds,
+    managers: CopyHashMap<WlrOutputManagerId, Rc<ZwlrOutputManagerV1>>,
+    serial: NumCell<u32>,
+    heads: RefCell<Vec<HeadState>>,
+    update_scheduled: Cell<bool>,
+    changed: AsyncEvent,
+}
+
+/// The state of a head as seen by wlr-output-management clients.
+///
+/// Disabled connectors are reported with the information of the monitor that was last
+/// connected to them so that clients can enable them again.
+#[derive(Clone, PartialEq)]
+pub struct HeadState {
+    pub connector: ConnectorId,
+    pub name: String,
+    pub output_id: Rc<OutputId>,
+    pub width_mm: i32,
+    pub height_mm: i32,
+    pub modes: Vec<Mode>,
+    pub preferred_mode: Mode,
+    pub enabled: bool,
+    pub current_mode: Mode,
+    pub position: (i32, i32),
+    pub transform: Transform,
+    pub scale: Scale,
+}
+
+impl HeadState {
+    fn description(&self) -> String {
+        let id = &self.output_id;
+        let mut desc = String::new();
+        for part in [&id.manufacturer, &id.model, &id.serial_number] {
+            if !part.is_empty() {
+                desc.push_str(part);
+                desc.push(' ');
+            }
+        }
+        desc.push('(');
+        desc.push_str(&self.name);
+        desc.push(')');
+        desc
+    }
+}
+
+impl WlrOutputManagerState {
+    pub fn clear(&self) {
+        self.managers.clear();
+        self.changed.clear();
+    }
+
+    pub fn schedule_update(&self) {
+        if !self.update_scheduled.replace(true) {
+            self.changed.trigger();
+        }
+    }
+
+    fn serial(&self) -> u32 {
+        self.serial.get()
+    }
+}
+
+pub async fn wlr_output_manager_update(state: Rc<State>) {
+    let mgrs = &state.wlr_output_managers;
+    loop {
+        mgrs.changed.triggered().await;
+        mgrs.update_scheduled.set(false);
+        let heads = head_states(&state);
+        if *mgrs.heads.borrow() == heads {
+            continue;
+        }
+        let serial = mgrs.serial.add_fetch(1);
+        for manager in mgrs.managers.lock().values() {
+            manager.update(&heads);
+            manager.send_done(serial);
+        }
+        *mgrs.heads.borrow_mut() = heads;
+    }
+}
+
+fn head_states(state: &State) -> Vec<HeadState> {
+    let mut heads = vec![];
+    for connector in state.connectors.lock().values() {
+        let id = connector.connector.id();
+        let head = if let Some(node) = state.outputs.get(&id).and_then(|o| o.node.clone()) {
+            let global = &node.global;
+            let Some(info) = connector.monitor_info.get() else {
+                continue;
+            };
+            let pos = global.pos.get();
+            HeadState {
+                connector: id,
+                name: connector.name.clone(),
+                output_id: global.output_id.clone(),
+                width_mm: global.width_mm,
+                height_mm: global.height_mm,
+                modes: global.modes.clone(),
+                preferred_mode: info.initial_mode,
+                enabled: true,
+                current_mode: global.mode.get(),
+                position: (pos.x1(), pos.y1()),
+                transform: global.persistent.transform.get(),
+                scale: global.persistent.scale.get(),
+            }
+        } else if !connector.connector.enabled() {
+            let Some(info) = connector.monitor_info.get() else {
+                continue;
+            };
+            let Some(persistent) = state.persistent_output_states.get(&info.output_id) else {
+                continue;
+            };
+            HeadState {
+                connector: id,
+                name: connector.name.clone(),
+                output_id: info.output_id.clone(),
+                width_mm: info.width_mm,
+                height_mm: info.height_mm,
+                modes: info.modes.clone(),
+                preferred_mode: info.initial_mode,
+                enabled: false,
+                current_mode: info.initial_mode,
+                position: persistent.pos.get(),
+                transform: persistent.transform.get(),
+                scale: persistent.scale.get(),
+            }
+        } else {
+            continue;
+        };
+        heads.push(head);
+    }
+    heads.sort_by_key(|h| h.connector.raw());
+    heads
+}
@@ -0,0 +1,169 @@
+use {
+    crate::{
+        backend::{ConnectorId, Mode},
+        client::{Client, ClientError},
+        compositor::MAX_EXTENTS,
+        ifs::wlr_output_manager::HeadState,
+        leaks::Tracker,
+        object::{Object, Version},
+        scale::Scale,
+        utils::transform_ext::TransformExt,
+        wire::{zwlr_output_configuration_head_v1::*, ZwlrOutputConfigurationHeadV1Id},
+    },
+    jay_config::video::Transform,
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwlrOutputConfigurationHeadV1 {
+    pub(super) id: ZwlrOutputConfigurationHeadV1Id,
+    pub(super) client: Rc<Client>,
+    pub(super) tracker: Tracker<Self>,
+    pub(super) version: Version,
+    pub(super) connector: ConnectorId,
+    mode: Cell<Option<Mode>>,
+    position: Cell<Option<(i32, i32)>>,
+    transform: Cell<Option<Transform>>,
+    scale: Cell<Option<Scale>>,
+}
+
+pub(super) struct HeadConfig {
+    pub mode: Option<Mode>,
+    pub position: Option<(i32, i32)>,
+    pub transform: Option<Transform>,
+    pub scale: Option<Scale>,
+}
+
+impl ZwlrOutputConfigurationHeadV1 {
+    pub(super) fn new(
+        id: ZwlrOutputConfigurationHeadV1Id,
+        client: &Rc<Client>,
+        version: Version,
+        connector: ConnectorId,
+    ) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+            connector,
+            mode: Default::default(),
+            position: Default::default(),
+            transform: Default::default(),
+            scale: Default::default(),
+        }
+    }
+
+    /// Validates the requested properties against the current state of the head.
+    ///
+    /// Custom modes must match one of the modes advertised by the head since the
+    /// backends cannot drive arbitrary modes. A refresh rate of 0 selects the
+    /// fastest mode with the requested size.
+    pub(super) fn resolve(&self, head: &HeadState) -> Option<HeadConfig> {
+        let mode = match self.mode.get() {
+            None => None,
+            Some(m) if head.modes.contains(&m) => Some(m),
+            Some(m) if m.refresh_rate_millihz == 0 => Some(
+                head.modes
+                    .iter()
+                    .filter(|h| (h.width, h.height) == (m.width, m.height))
+                    .max_by_key(|h| h.refresh_rate_millihz)
+                    .copied()?,
+            ),
+            Some(_) => return None,
+        };
+        let position = self.position.get();
+        if let Some((x, y)) = position {
+            if x < 0 || y < 0 || x > MAX_EXTENTS || y > MAX_EXTENTS {
+                return None;
+            }
+        }
+        Some(HeadConfig {
+            mode,
+            position,
+            transform: self.transform.get(),
+            scale: self.scale.get(),
+        })
+    }
+}
+
+fn set_once<T: Copy>(
+    cell: &Cell<Option<T>>,
+    value: T,
+) -> Result<(), ZwlrOutputConfigurationHeadV1Error> {
+    if cell.replace(Some(value)).is_some() {
+        return Err(ZwlrOutputConfigurationHeadV1Error::AlreadySet);
+    }
+    Ok(())
+}
+
+impl ZwlrOutputConfigurationHeadV1RequestHandler for ZwlrOutputConfigurationHeadV1 {
+    type Error = ZwlrOutputConfigurationHeadV1Error;
+
+    fn set_mode(&self, req: SetMode, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let mode = self.client.lookup(req.mode)?;
+        if mode.connector != self.connector {
+            return Err(ZwlrOutputConfigurationHeadV1Error::InvalidMode);
+        }
+        set_once(&self.mode, mode.mode)
+    }
+
+    fn set_custom_mode(&self, req: SetCustomMode, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if req.width <= 0 || req.height <= 0 || req.refresh < 0 {
+            return Err(ZwlrOutputConfigurationHeadV1Error::InvalidCustomMode);
+        }
+        let mode = Mode {
+            width: req.width,
+            height: req.height,
+            refresh_rate_millihz: req.refresh as u32,
+        };
+        set_once(&self.mode, mode)
+    }
+
+    fn set_position(&self, req: SetPosition, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        set_once(&self.position, (req.x, req.y))
+    }
+
+    fn set_transform(&self, req: SetTransform, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some(transform) = Transform::from_wl(req.transform) else {
+            return Err(ZwlrOutputConfigurationHeadV1Error::InvalidTransform(
+                req.transform,
+            ));
+        };
+        set_once(&self.transform, transform)
+    }
+
+    fn set_scale(&self, req: SetScale, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let scale = req.scale.to_f64();
+        if scale <= 0.0 {
+            return Err(ZwlrOutputConfigurationHeadV1Error::InvalidScale(scale));
+        }
+        set_once(&self.scale, Scale::from_f64(scale))
+    }
+}
+
+object_base! {
+    self = ZwlrOutputConfigurationHeadV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputConfigurationHeadV1 {}
+
+simple_add_obj!(ZwlrOutputConfigurationHeadV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputConfigurationHeadV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The property has already been set")]
+    AlreadySet,
+    #[error("The mode does not belong to the head")]
+    InvalidMode,
+    #[error("The custom mode is invalid")]
+    InvalidCustomMode,
+    #[error("Unknown transform {0}")]
+    InvalidTransform(i32),
+    #[error("Invalid scale {0}")]
+    InvalidScale(f64),
+}
+efrom!(ZwlrOutputConfigurationHeadV1Error, ClientError);
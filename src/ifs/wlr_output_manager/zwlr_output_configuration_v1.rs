@@ -0,0 +1,265 @@
+use {
+    crate::{
+        backend::ConnectorId,
+        client::{Client, ClientError},
+        ifs::wlr_output_manager::{
+            zwlr_output_configuration_head_v1::{HeadConfig, ZwlrOutputConfigurationHeadV1},
+            HeadState,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        state::State,
+        utils::errorfmt::ErrorFmt,
+        wire::{zwlr_output_configuration_v1::*, ZwlrOutputConfigurationV1Id},
+    },
+    ahash::AHashMap,
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+pub struct ZwlrOutputConfigurationV1 {
+    pub(super) id: ZwlrOutputConfigurationV1Id,
+    pub(super) client: Rc<Client>,
+    pub(super) tracker: Tracker<Self>,
+    pub(super) version: Version,
+    pub(super) serial: u32,
+    pub(super) heads: RefCell<AHashMap<ConnectorId, Option<Rc<ZwlrOutputConfigurationHeadV1>>>>,
+    pub(super) used: Cell<bool>,
+}
+
+impl ZwlrOutputConfigurationV1 {
+    fn send_succeeded(&self) {
+        self.client.event(Succeeded { self_id: self.id });
+    }
+
+    fn send_failed(&self) {
+        self.client.event(Failed { self_id: self.id });
+    }
+
+    fn send_cancelled(&self) {
+        self.client.event(Cancelled { self_id: self.id });
+    }
+
+    fn check_unused(&self) -> Result<(), ZwlrOutputConfigurationV1Error> {
+        if self.used.get() {
+            return Err(ZwlrOutputConfigurationV1Error::AlreadyUsed);
+        }
+        Ok(())
+    }
+
+    fn configure(
+        &self,
+        connector: ConnectorId,
+        head: Option<Rc<ZwlrOutputConfigurationHeadV1>>,
+    ) -> Result<(), ZwlrOutputConfigurationV1Error> {
+        self.check_unused()?;
+        if self.heads.borrow_mut().insert(connector, head).is_some() {
+            return Err(ZwlrOutputConfigurationV1Error::AlreadyConfiguredHead);
+        }
+        Ok(())
+    }
+
+    fn apply_or_test(&self, apply: bool) -> Result<(), ZwlrOutputConfigurationV1Error> {
+        self.check_unused()?;
+        self.used.set(true);
+        let state = &self.client.state;
+        let mgrs = &state.wlr_output_managers;
+        if self.serial != mgrs.serial() {
+            self.send_cancelled();
+            return Ok(());
+        }
+        let heads = self.heads.borrow();
+        let snapshot = mgrs.heads.borrow();
+        let mut changes = vec![];
+        for head in snapshot.iter() {
+            let Some(config) = heads.get(&head.connector) else {
+                return Err(ZwlrOutputConfigurationV1Error::UnconfiguredHead);
+            };
+            let config = match config {
+                None => None,
+                Some(c) => match c.resolve(head) {
+                    Some(c) => Some(c),
+                    None => {
+                        self.send_failed();
+                        return Ok(());
+                    }
+                },
+            };
+            changes.push((head, config));
+        }
+        let backend = state.backend.get();
+        let mut f = || {
+            for (head, config) in &changes {
+                configure_connector(state, head, config.as_ref(), apply);
+            }
+        };
+        let res = match apply {
+            true => backend.apply_connector_transaction(&mut f),
+            false => backend.test_connector_transaction(&mut f),
+        };
+        if let Err(e) = res {
+            let action = match apply {
+                true => "apply",
+                false => "test",
+            };
+            log::warn!(
+                "Could not {} output configuration: {}",
+                action,
+                ErrorFmt(&*e)
+            );
+            self.send_failed();
+            return Ok(());
+        }
+        if apply {
+            for (head, config) in changes {
+                if let Some(config) = config {
+                    configure_output(state, head, config);
+                }
+            }
+        }
+        self.send_succeeded();
+        Ok(())
+    }
+
+    fn detach(&self) {
+        self.heads.borrow_mut().clear();
+    }
+}
+
+/// Applies the parts of the configuration that require a modeset.
+///
+/// If `apply` is false, the configuration is only being tested and the persistent state of
+/// the output is left unchanged.
+fn configure_connector(state: &State, head: &HeadState, config: Option<&HeadConfig>, apply: bool) {
+    let Some(connector) = state.connectors.get(&head.connector) else {
+        return;
+    };
+    let Some(config) = config else {
+        if head.enabled {
+            connector.connector.set_enabled(false);
+        }
+        return;
+    };
+    if !head.enabled {
+        // The mode is chosen by the backend once the connector has been enabled.
+        let persistent = match apply {
+            true => state.persistent_output_states.get(&head.output_id),
+            false => None,
+        };
+        if let Some(persistent) = persistent {
+            if let Some(pos) = config.position {
+                persistent.pos.set(pos);
+            }
+            if let Some(transform) = config.transform {
+                persistent.transform.set(transform);
+            }
+            if let Some(scale) = config.scale {
+                persistent.scale.set(scale);
+            }
+        }
+        connector.connector.set_enabled(true);
+        return;
+    }
+    let Some(node) = state
+        .outputs
+        .get(&head.connector)
+        .and_then(|o| o.node.clone())
+    else {
+        return;
+    };
+    if let Some(mode) = config.mode {
+        if mode != node.global.mode.get() {
+            connector.connector.set_mode(mode);
+        }
+    }
+}
+
+/// Applies the parts of the configuration that are handled by the compositor.
+fn configure_output(state: &State, head: &HeadState, config: HeadConfig) {
+    if !head.enabled {
+        return;
+    }
+    let Some(node) = state
+        .outputs
+        .get(&head.connector)
+        .and_then(|o| o.node.clone())
+    else {
+        return;
+    };
+    if let Some(transform) = config.transform {
+        node.update_transform(transform);
+    }
+    if let Some(scale) = config.scale {
+        node.set_preferred_scale(scale);
+    }
+    if let Some((x, y)) = config.position {
+        node.set_position(x, y);
+    }
+}
+
+impl ZwlrOutputConfigurationV1RequestHandler for ZwlrOutputConfigurationV1 {
+    type Error = ZwlrOutputConfigurationV1Error;
+
+    fn enable_head(&self, req: EnableHead, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let head = self.client.lookup(req.head)?;
+        let obj = Rc::new(ZwlrOutputConfigurationHeadV1::new(
+            req.id,
+            &self.client,
+            self.version,
+            head.connector,
+        ));
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        self.configure(head.connector, Some(obj))
+    }
+
+    fn disable_head(&self, req: DisableHead, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let head = self.client.lookup(req.head)?;
+        self.configure(head.connector, None)
+    }
+
+    fn apply(&self, _req: Apply, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.apply_or_test(true)
+    }
+
+    fn test(&self, _req: Test, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.apply_or_test(false)
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        for head in self.heads.borrow_mut().drain().filter_map(|h| h.1) {
+            self.client.remove_obj(&*head)?;
+        }
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrOutputConfigurationV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputConfigurationV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwlrOutputConfigurationV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputConfigurationV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The head has already been configured")]
+    AlreadyConfiguredHead,
+    #[error("Not all heads have been configured")]
+    UnconfiguredHead,
+    #[error("The configuration has already been applied or tested")]
+    AlreadyUsed,
+}
+efrom!(ZwlrOutputConfigurationV1Error, ClientError);
@@ -0,0 +1,262 @@
+use {
+    crate::{
+        backend::ConnectorId,
+        client::{Client, ClientError},
+        fixed::Fixed,
+        ifs::wlr_output_manager::{
+            zwlr_output_mode_v1::{ZwlrOutputModeV1, RELEASE_SINCE},
+            HeadState,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        utils::transform_ext::TransformExt,
+        wire::{zwlr_output_head_v1::*, ZwlrOutputHeadV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+const MAKE_SINCE: Version = Version(2);
+
+pub struct ZwlrOutputHeadV1 {
+    pub(super) id: ZwlrOutputHeadV1Id,
+    pub(super) client: Rc<Client>,
+    pub(super) tracker: Tracker<Self>,
+    pub(super) version: Version,
+    pub(super) connector: ConnectorId,
+    pub(super) state: RefCell<HeadState>,
+    pub(super) modes: RefCell<Vec<Rc<ZwlrOutputModeV1>>>,
+    pub(super) released: Cell<bool>,
+}
+
+impl ZwlrOutputHeadV1 {
+    pub(super) fn send_all(&self) {
+        let state = &*self.state.borrow();
+        self.send_name(&state.name);
+        self.send_description(&state.description());
+        if state.width_mm > 0 && state.height_mm > 0 {
+            self.send_physical_size(state.width_mm, state.height_mm);
+        }
+        if self.version >= MAKE_SINCE {
+            let id = &state.output_id;
+            if !id.manufacturer.is_empty() {
+                self.send_make(&id.manufacturer);
+            }
+            if !id.model.is_empty() {
+                self.send_model(&id.model);
+            }
+            if !id.serial_number.is_empty() {
+                self.send_serial_number(&id.serial_number);
+            }
+        }
+        self.create_modes(state);
+        self.send_enabled(state.enabled);
+        if state.enabled {
+            self.send_current_mode(state);
+            self.send_position(state.position);
+            self.send_transform(state);
+            self.send_scale(state);
+        }
+    }
+
+    pub(super) fn update(&self, new: &HeadState) {
+        if self.released.get() {
+            return;
+        }
+        let old = self.state.replace(new.clone());
+        let modes_changed = old.modes != new.modes || old.preferred_mode != new.preferred_mode;
+        if modes_changed {
+            for mode in self.modes.take() {
+                mode.finish();
+            }
+            self.create_modes(new);
+        }
+        if old.enabled != new.enabled {
+            self.send_enabled(new.enabled);
+        }
+        if !new.enabled {
+            return;
+        }
+        let all = modes_changed || !old.enabled;
+        if all || old.current_mode != new.current_mode {
+            self.send_current_mode(new);
+        }
+        if all || old.position != new.position {
+            self.send_position(new.position);
+        }
+        if all || old.transform != new.transform {
+            self.send_transform(new);
+        }
+        if all || old.scale != new.scale {
+            self.send_scale(new);
+        }
+    }
+
+    pub(super) fn finish(&self) {
+        for mode in self.modes.take() {
+            mode.finish();
+        }
+        if self.released.get() {
+            return;
+        }
+        self.client.event(Finished { self_id: self.id });
+        if self.version < RELEASE_SINCE {
+            self.released.set(true);
+            if let Err(e) = self.client.remove_obj(self) {
+                self.client.error(e);
+            }
+        }
+    }
+
+    fn create_modes(&self, state: &HeadState) {
+        let mut modes = self.modes.borrow_mut();
+        for &mode in &state.modes {
+            let id = match self.client.new_id() {
+                Ok(id) => id,
+                Err(e) => {
+                    self.client.error(e);
+                    return;
+                }
+            };
+            let obj = Rc::new(ZwlrOutputModeV1 {
+                id,
+                client: self.client.clone(),
+                tracker: Default::default(),
+                version: self.version,
+                connector: self.connector,
+                mode,
+                released: Cell::new(false),
+            });
+            track!(self.client, obj);
+            self.client.add_server_obj(&obj);
+            self.client.event(Mode {
+                self_id: self.id,
+                mode: obj.id,
+            });
+            obj.send_size();
+            if mode.refresh_rate_millihz != 0 {
+                obj.send_refresh();
+            }
+            if mode == state.preferred_mode {
+                obj.send_preferred();
+            }
+            modes.push(obj);
+        }
+    }
+
+    fn send_name(&self, name: &str) {
+        self.client.event(Name {
+            self_id: self.id,
+            name,
+        });
+    }
+
+    fn send_description(&self, description: &str) {
+        self.client.event(Description {
+            self_id: self.id,
+            description,
+        });
+    }
+
+    fn send_physical_size(&self, width: i32, height: i32) {
+        self.client.event(PhysicalSize {
+            self_id: self.id,
+            width,
+            height,
+        });
+    }
+
+    fn send_make(&self, make: &str) {
+        self.client.event(Make {
+            self_id: self.id,
+            make,
+        });
+    }
+
+    fn send_model(&self, model: &str) {
+        self.client.event(Model {
+            self_id: self.id,
+            model,
+        });
+    }
+
+    fn send_serial_number(&self, serial_number: &str) {
+        self.client.event(SerialNumber {
+            self_id: self.id,
+            serial_number,
+        });
+    }
+
+    fn send_enabled(&self, enabled: bool) {
+        self.client.event(Enabled {
+            self_id: self.id,
+            enabled: enabled as i32,
+        });
+    }
+
+    fn send_current_mode(&self, state: &HeadState) {
+        let modes = self.modes.borrow();
+        let Some(mode) = modes.iter().find(|m| m.mode == state.current_mode) else {
+            return;
+        };
+        self.client.event(CurrentMode {
+            self_id: self.id,
+            mode: mode.id,
+        });
+    }
+
+    fn send_position(&self, (x, y): (i32, i32)) {
+        self.client.event(Position {
+            self_id: self.id,
+            x,
+            y,
+        });
+    }
+
+    fn send_transform(&self, state: &HeadState) {
+        self.client.event(Transform {
+            self_id: self.id,
+            transform: state.transform.to_wl(),
+        });
+    }
+
+    fn send_scale(&self, state: &HeadState) {
+        self.client.event(Scale {
+            self_id: self.id,
+            scale: Fixed::from_f64(state.scale.to_f64()),
+        });
+    }
+}
+
+impl ZwlrOutputHeadV1RequestHandler for ZwlrOutputHeadV1 {
+    type Error = ZwlrOutputHeadV1Error;
+
+    fn release(&self, _req: Release, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.released.set(true);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrOutputHeadV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputHeadV1 {
+    fn break_loops(&self) {
+        self.modes.take();
+    }
+}
+
+dedicated_add_obj!(ZwlrOutputHeadV1, ZwlrOutputHeadV1Id, wlr_output_heads);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputHeadV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrOutputHeadV1Error, ClientError);
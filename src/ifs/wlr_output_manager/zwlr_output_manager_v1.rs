@@ -0,0 +1,218 @@
+use {
+    crate::{
+        backend::ConnectorId,
+        client::{Client, ClientCaps, ClientError, CAP_OUTPUT_MANAGER},
+        globals::{Global, GlobalName},
+        ifs::wlr_output_manager::{
+            zwlr_output_configuration_v1::ZwlrOutputConfigurationV1,
+            zwlr_output_head_v1::ZwlrOutputHeadV1, HeadState,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        utils::{copyhashmap::CopyHashMap, hash_map_ext::HashMapExt},
+        wire::{zwlr_output_manager_v1::*, ZwlrOutputManagerV1Id},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+linear_ids!(WlrOutputManagerIds, WlrOutputManagerId, u64);
+
+pub struct ZwlrOutputManagerV1Global {
+    pub name: GlobalName,
+}
+
+pub struct ZwlrOutputManagerV1 {
+    id: ZwlrOutputManagerV1Id,
+    manager_id: WlrOutputManagerId,
+    client: Rc<Client>,
+    tracker: Tracker<Self>,
+    version: Version,
+    heads: CopyHashMap<ConnectorId, Rc<ZwlrOutputHeadV1>>,
+}
+
+impl ZwlrOutputManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwlrOutputManagerV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), ZwlrOutputManagerV1Error> {
+        let mgrs = &client.state.wlr_output_managers;
+        let obj = Rc::new(ZwlrOutputManagerV1 {
+            id,
+            manager_id: mgrs.ids.next(),
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+            heads: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        mgrs.managers.set(obj.manager_id, obj.clone());
+        obj.update(&mgrs.heads.borrow());
+        obj.send_done(mgrs.serial());
+        Ok(())
+    }
+}
+
+impl ZwlrOutputManagerV1 {
+    pub(super) fn update(&self, heads: &[HeadState]) {
+        let removed: Vec<_> = self
+            .heads
+            .lock()
+            .values()
+            .filter(|h| heads.iter().all(|s| s.connector != h.connector))
+            .cloned()
+            .collect();
+        for head in removed {
+            self.heads.remove(&head.connector);
+            head.finish();
+        }
+        for state in heads {
+            if let Some(head) = self.heads.get(&state.connector) {
+                if head.state.borrow().output_id == state.output_id {
+                    head.update(state);
+                    continue;
+                }
+                self.heads.remove(&state.connector);
+                head.finish();
+            }
+            self.create_head(state);
+        }
+    }
+
+    fn create_head(&self, state: &HeadState) {
+        let id = match self.client.new_id() {
+            Ok(id) => id,
+            Err(e) => {
+                self.client.error(e);
+                return;
+            }
+        };
+        let head = Rc::new(ZwlrOutputHeadV1 {
+            id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            connector: state.connector,
+            state: RefCell::new(state.clone()),
+            modes: Default::default(),
+            released: Cell::new(false),
+        });
+        track!(self.client, head);
+        self.client.add_server_obj(&head);
+        self.heads.set(state.connector, head.clone());
+        self.send_head(&head);
+        head.send_all();
+    }
+
+    fn send_head(&self, head: &ZwlrOutputHeadV1) {
+        self.client.event(Head {
+            self_id: self.id,
+            head: head.id,
+        });
+    }
+
+    pub(super) fn send_done(&self, serial: u32) {
+        self.client.event(Done {
+            self_id: self.id,
+            serial,
+        });
+    }
+
+    fn send_finished(&self) {
+        self.client.event(Finished { self_id: self.id });
+    }
+
+    fn detach(&self) {
+        self.client
+            .state
+            .wlr_output_managers
+            .managers
+            .remove(&self.manager_id);
+    }
+}
+
+global_base!(
+    ZwlrOutputManagerV1Global,
+    ZwlrOutputManagerV1,
+    ZwlrOutputManagerV1Error
+);
+
+impl Global for ZwlrOutputManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        3
+    }
+
+    fn required_caps(&self) -> ClientCaps {
+        CAP_OUTPUT_MANAGER
+    }
+}
+
+simple_add_global!(ZwlrOutputManagerV1Global);
+
+object_base! {
+    self = ZwlrOutputManagerV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputManagerV1 {
+    fn break_loops(&self) {
+        self.detach();
+        self.heads.clear();
+    }
+}
+
+simple_add_obj!(ZwlrOutputManagerV1);
+
+impl ZwlrOutputManagerV1RequestHandler for ZwlrOutputManagerV1 {
+    type Error = ZwlrOutputManagerV1Error;
+
+    fn create_configuration(
+        &self,
+        req: CreateConfiguration,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let obj = Rc::new(ZwlrOutputConfigurationV1 {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            serial: req.serial,
+            heads: Default::default(),
+            used: Cell::new(false),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        Ok(())
+    }
+
+    fn stop(&self, _req: Stop, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        for head in self.heads.lock().drain_values() {
+            head.finish();
+        }
+        self.send_finished();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrOutputManagerV1Error, ClientError);
@@ -0,0 +1,83 @@
+use {
+    crate::{
+        backend::{ConnectorId, Mode},
+        client::{Client, ClientError},
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{zwlr_output_mode_v1::*, ZwlrOutputModeV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub(super) const RELEASE_SINCE: Version = Version(3);
+
+pub struct ZwlrOutputModeV1 {
+    pub(super) id: ZwlrOutputModeV1Id,
+    pub(super) client: Rc<Client>,
+    pub(super) tracker: Tracker<Self>,
+    pub(super) version: Version,
+    pub(super) connector: ConnectorId,
+    pub(super) mode: Mode,
+    pub(super) released: Cell<bool>,
+}
+
+impl ZwlrOutputModeV1 {
+    pub(super) fn send_size(&self) {
+        self.client.event(Size {
+            self_id: self.id,
+            width: self.mode.width,
+            height: self.mode.height,
+        });
+    }
+
+    pub(super) fn send_refresh(&self) {
+        self.client.event(Refresh {
+            self_id: self.id,
+            refresh: self.mode.refresh_rate_millihz as i32,
+        });
+    }
+
+    pub(super) fn send_preferred(&self) {
+        self.client.event(Preferred { self_id: self.id });
+    }
+
+    pub(super) fn finish(&self) {
+        if self.released.get() {
+            return;
+        }
+        self.client.event(Finished { self_id: self.id });
+        if self.version < RELEASE_SINCE {
+            self.released.set(true);
+            if let Err(e) = self.client.remove_obj(self) {
+                self.client.error(e);
+            }
+        }
+    }
+}
+
+impl ZwlrOutputModeV1RequestHandler for ZwlrOutputModeV1 {
+    type Error = ZwlrOutputModeV1Error;
+
+    fn release(&self, _req: Release, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.released.set(true);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrOutputModeV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputModeV1 {}
+
+dedicated_add_obj!(ZwlrOutputModeV1, ZwlrOutputModeV1Id, wlr_output_modes);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputModeV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrOutputModeV1Error, ClientError);
@@ -150,8 +150,8 @@ impl ExtWorkspaceManagerV1 {
         workspace.ext_workspaces.set(self.manager_id, ws.clone());
         self.send_workspace(&ws);
         ws.send_capabilities();
-        ws.send_id(&workspace.name);
-        ws.send_name(&workspace.name);
+        ws.send_id(&workspace.identifier.to_string());
+        ws.send_name(&workspace.name.borrow());
         ws.send_current_state();
         if let Some(group) = group {
             group.send_workspace_enter(&ws);
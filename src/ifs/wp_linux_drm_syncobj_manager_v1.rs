@@ -92,12 +92,14 @@ impl WpLinuxDrmSyncobjManagerV1RequestHandler for WpLinuxDrmSyncobjManagerV1 {
     }
 
     fn import_timeline(&self, req: ImportTimeline, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let charge = self.client.charge_resources(1, 0)?;
         let sync_obj = Rc::new(SyncObj::new(&req.fd));
         let sync = Rc::new(WpLinuxDrmSyncobjTimelineV1::new(
             req.id,
             &self.client,
             &sync_obj,
             self.version,
+            charge,
         ));
         track!(self.client, sync);
         self.client.add_client_obj(&sync)?;
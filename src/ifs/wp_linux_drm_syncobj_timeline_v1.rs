@@ -1,6 +1,6 @@
 use {
     crate::{
-        client::{Client, ClientError},
+        client::{Client, ClientError, ResourceCharge},
         leaks::Tracker,
         object::{Object, Version},
         video::drm::sync_obj::SyncObj,
@@ -16,6 +16,7 @@ pub struct WpLinuxDrmSyncobjTimelineV1 {
     pub sync_obj: Rc<SyncObj>,
     pub tracker: Tracker<Self>,
     version: Version,
+    _charge: ResourceCharge,
 }
 
 impl WpLinuxDrmSyncobjTimelineV1 {
@@ -24,6 +25,7 @@ impl WpLinuxDrmSyncobjTimelineV1 {
         client: &Rc<Client>,
         sync_obj: &Rc<SyncObj>,
         version: Version,
+        charge: ResourceCharge,
     ) -> Self {
         Self {
             id,
@@ -31,6 +33,7 @@ impl WpLinuxDrmSyncobjTimelineV1 {
             tracker: Default::default(),
             sync_obj: sync_obj.clone(),
             version,
+            _charge: charge,
         }
     }
 }
@@ -0,0 +1,99 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{wp_pointer_warp_v1::*, WpPointerWarpV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct WpPointerWarpV1Global {
+    pub name: GlobalName,
+}
+
+impl WpPointerWarpV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: WpPointerWarpV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), WpPointerWarpV1Error> {
+        let obj = Rc::new(WpPointerWarpV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(WpPointerWarpV1Global, WpPointerWarpV1, WpPointerWarpV1Error);
+
+simple_add_global!(WpPointerWarpV1Global);
+
+impl Global for WpPointerWarpV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+pub struct WpPointerWarpV1 {
+    pub id: WpPointerWarpV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl WpPointerWarpV1RequestHandler for WpPointerWarpV1 {
+    type Error = WpPointerWarpV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn warp_pointer(&self, mut req: WarpPointer, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let surface = self.client.lookup(req.surface)?;
+        let pointer = self.client.lookup(req.pointer)?;
+        let Some(serial) = self.client.map_serial(req.serial) else {
+            log::warn!("Client tried to warp the pointer with an invalid serial");
+            return Ok(());
+        };
+        if self.client.last_enter_serial.get() != Some(serial) {
+            return Ok(());
+        }
+        client_wire_scale_to_logical!(self.client, req.x, req.y);
+        pointer.seat.global.warp_pointer(&surface, req.x, req.y);
+        Ok(())
+    }
+}
+
+object_base! {
+    self = WpPointerWarpV1;
+    version = self.version;
+}
+
+impl Object for WpPointerWarpV1 {}
+
+simple_add_obj!(WpPointerWarpV1);
+
+#[derive(Debug, Error)]
+pub enum WpPointerWarpV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(WpPointerWarpV1Error, ClientError);
@@ -1,13 +1,24 @@
 use {
     crate::{
+        async_engine::SpawnedFuture,
         client::{Client, ClientError},
+        ifs::wl_seat::WlSeatGlobal,
         leaks::Tracker,
         object::{Object, Version},
-        utils::activation_token::{activation_token, ActivationToken},
+        state::State,
+        utils::{
+            activation_token::{activation_token, ActivationToken},
+            clonecell::CloneCell,
+            errorfmt::ErrorFmt,
+        },
         wire::{xdg_activation_token_v1::*, XdgActivationTokenV1Id},
     },
-    std::{cell::Cell, rc::Rc},
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
     thiserror::Error,
+    uapi::c,
 };
 
 const MAX_TOKENS_PER_CLIENT: usize = 8;
@@ -17,9 +28,57 @@ pub struct XdgActivationTokenV1 {
     pub client: Rc<Client>,
     pub tracker: Tracker<Self>,
     already_used: Cell<bool>,
+    app_id: RefCell<Option<String>>,
+    seat: CloneCell<Option<Rc<WlSeatGlobal>>>,
+    serial: Cell<Option<u64>>,
     version: Version,
 }
 
+/// An activation token that has been handed out but not yet been used.
+pub struct PendingActivation {
+    pub token: ActivationToken,
+    pub app_id: Option<String>,
+    /// The pid of the client that requested the token.
+    pub pid: c::pid_t,
+    /// The seat whose input triggered the launch.
+    pub seat: Option<Rc<WlSeatGlobal>>,
+    /// The input serial that triggered the launch.
+    ///
+    /// The serial is already limited to the serials that would allow the launcher
+    /// itself to take the keyboard focus.
+    pub serial: Option<u64>,
+    timeout: Cell<Option<SpawnedFuture<()>>>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ActivationEnd {
+    Activated,
+    Expired,
+}
+
+impl PendingActivation {
+    fn start_timeout(&self, state: &Rc<State>) {
+        let state2 = state.clone();
+        let token = self.token;
+        let ms = state.activation_token_lifetime.get().as_millis() as u64;
+        let future = state.eng.spawn("activation token timeout", async move {
+            if let Err(e) = state2.wheel.timeout(ms).await {
+                log::error!(
+                    "Could not wait for the activation token timeout: {}",
+                    ErrorFmt(e)
+                );
+                return;
+            }
+            state2.remove_activation_token(&token, ActivationEnd::Expired);
+        });
+        self.timeout.set(Some(future));
+    }
+
+    pub fn cancel_timeout(&self) {
+        self.timeout.take();
+    }
+}
+
 impl XdgActivationTokenV1 {
     pub fn new(id: XdgActivationTokenV1Id, client: &Rc<Client>, version: Version) -> Self {
         Self {
@@ -27,6 +86,9 @@ impl XdgActivationTokenV1 {
             client: client.clone(),
             tracker: Default::default(),
             already_used: Cell::new(false),
+            app_id: Default::default(),
+            seat: Default::default(),
+            serial: Default::default(),
             version,
         }
     }
@@ -35,11 +97,15 @@ impl XdgActivationTokenV1 {
 impl XdgActivationTokenV1RequestHandler for XdgActivationTokenV1 {
     type Error = XdgActivationTokenV1Error;
 
-    fn set_serial(&self, _req: SetSerial, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+    fn set_serial(&self, req: SetSerial, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let seat = self.client.lookup(req.seat)?;
+        self.seat.set(Some(seat.global.clone()));
+        self.serial.set(self.client.map_serial(req.serial));
         Ok(())
     }
 
-    fn set_app_id(&self, _req: SetAppId, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+    fn set_app_id(&self, req: SetAppId, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        *self.app_id.borrow_mut() = Some(req.app_id.to_string());
         Ok(())
     }
 
@@ -52,12 +118,29 @@ impl XdgActivationTokenV1RequestHandler for XdgActivationTokenV1 {
         if self.already_used.replace(true) {
             return Err(XdgActivationTokenV1Error::AlreadyUsed);
         }
+        let state = &self.client.state;
         let token = activation_token();
-        self.client.state.activation_tokens.set(token, ());
+        let serial = match (self.serial.get(), self.client.focus_stealing_serial.get()) {
+            (Some(serial), Some(max)) => Some(serial.min(max)),
+            _ => None,
+        };
+        let pending = Rc::new(PendingActivation {
+            token,
+            app_id: self.app_id.take(),
+            pid: self.client.pid_info.pid,
+            seat: self.seat.take(),
+            serial,
+            timeout: Default::default(),
+        });
+        pending.start_timeout(state);
+        state.activation_tokens.set(token, pending.clone());
+        for watcher in state.launch_watchers.lock().values() {
+            watcher.send_started(&pending);
+        }
         let mut tokens = self.client.activation_tokens.borrow_mut();
         if tokens.len() >= MAX_TOKENS_PER_CLIENT {
             if let Some(oldest) = tokens.pop_front() {
-                self.client.state.activation_tokens.remove(&oldest);
+                state.remove_activation_token(&oldest, ActivationEnd::Expired);
             }
         }
         tokens.push_back(token);
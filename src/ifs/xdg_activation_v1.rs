@@ -2,7 +2,7 @@ use {
     crate::{
         client::{Client, ClientError},
         globals::{Global, GlobalName},
-        ifs::xdg_activation_token_v1::XdgActivationTokenV1,
+        ifs::xdg_activation_token_v1::{ActivationEnd, XdgActivationTokenV1},
         leaks::Tracker,
         object::{Object, Version},
         utils::{activation_token::ActivationToken, errorfmt::ErrorFmt, opaque::OpaqueError},
@@ -92,14 +92,21 @@ impl XdgActivationV1RequestHandler for XdgActivationV1 {
             }
         };
         let surface = self.client.lookup(req.surface)?;
-        if self.client.state.activation_tokens.remove(&token).is_none() {
+        let pending = self
+            .client
+            .state
+            .remove_activation_token(&token, ActivationEnd::Activated);
+        let Some(pending) = pending else {
             log::warn!(
                 "Client requested activation with unknown token {}",
                 req.token
             );
             return Ok(());
+        };
+        match (&pending.seat, pending.serial) {
+            (Some(seat), Some(serial)) => surface.request_activation_with_serial(seat, serial),
+            _ => surface.request_activation(),
         }
-        surface.request_activation();
         Ok(())
     }
 }
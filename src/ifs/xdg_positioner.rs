@@ -134,6 +134,96 @@ impl XdgPositioned {
 
         Rect::new_sized(x1, y1, self.size_width, self.size_height).unwrap()
     }
+
+    /// Computes the position of the popup relative to the parent, applying the
+    /// constraint adjustments if the popup would not be contained in the output.
+    pub fn constrain(&self, parent_abs: Rect, output_pos: Rect) -> Rect {
+        let mut rel_pos = self.get_position(false, false);
+        let mut abs_pos = rel_pos.move_(parent_abs.x1(), parent_abs.y1());
+        let mut overflow = output_pos.get_overflow(&abs_pos);
+        if !overflow.is_contained() {
+            let mut flip_x = self.ca.contains(CA_FLIP_X) && overflow.x_overflow();
+            let mut flip_y = self.ca.contains(CA_FLIP_Y) && overflow.y_overflow();
+            if flip_x || flip_y {
+                let mut adj_rel = self.get_position(flip_x, flip_y);
+                let mut adj_abs = adj_rel.move_(parent_abs.x1(), parent_abs.y1());
+                let mut adj_overflow = output_pos.get_overflow(&adj_abs);
+                let mut recalculate = false;
+                if flip_x && adj_overflow.x_overflow() {
+                    flip_x = false;
+                    recalculate = true;
+                }
+                if flip_y && adj_overflow.y_overflow() {
+                    flip_y = false;
+                    recalculate = true;
+                }
+                if flip_x || flip_y {
+                    if recalculate {
+                        adj_rel = self.get_position(flip_x, flip_y);
+                        adj_abs = adj_rel.move_(parent_abs.x1(), parent_abs.y1());
+                        adj_overflow = output_pos.get_overflow(&adj_abs);
+                    }
+                    rel_pos = adj_rel;
+                    abs_pos = adj_abs;
+                    overflow = adj_overflow;
+                }
+            }
+            let (mut dx, mut dy) = (0, 0);
+            if self.ca.contains(CA_SLIDE_X) && overflow.x_overflow() {
+                dx = if overflow.left + overflow.right > 0 {
+                    parent_abs.x1() - abs_pos.x1()
+                } else if overflow.left > 0 {
+                    overflow.left
+                } else {
+                    -overflow.right
+                };
+            }
+            if self.ca.contains(CA_SLIDE_Y) && overflow.y_overflow() {
+                dy = if overflow.top + overflow.bottom > 0 {
+                    parent_abs.y1() - abs_pos.y1()
+                } else if overflow.top > 0 {
+                    overflow.top
+                } else {
+                    -overflow.bottom
+                };
+            }
+            if dx != 0 || dy != 0 {
+                rel_pos = rel_pos.move_(dx, dy);
+                abs_pos = rel_pos.move_(parent_abs.x1(), parent_abs.y1());
+                overflow = output_pos.get_overflow(&abs_pos);
+            }
+            let (mut dx1, mut dx2, mut dy1, mut dy2) = (0, 0, 0, 0);
+            if self.ca.contains(CA_RESIZE_X) {
+                dx1 = overflow.left.max(0);
+                dx2 = -overflow.right.max(0);
+            }
+            if self.ca.contains(CA_RESIZE_Y) {
+                dy1 = overflow.top.max(0);
+                dy2 = -overflow.bottom.max(0);
+            }
+            if dx1 > 0 || dx2 < 0 || dy1 > 0 || dy2 < 0 {
+                let maybe_abs_pos = Rect::new(
+                    abs_pos.x1() + dx1,
+                    abs_pos.y1() + dy1,
+                    abs_pos.x2() + dx2,
+                    abs_pos.y2() + dy2,
+                );
+                // If the popup is completely outside the output, this will fail. Just
+                // use its position as is.
+                if let Some(maybe_abs_pos) = maybe_abs_pos {
+                    abs_pos = maybe_abs_pos;
+                    rel_pos = Rect::new_sized(
+                        abs_pos.x1() - parent_abs.x1(),
+                        abs_pos.y1() - parent_abs.y1(),
+                        abs_pos.width(),
+                        abs_pos.height(),
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        rel_pos
+    }
 }
 
 impl XdgPositioner {
@@ -0,0 +1,206 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        clientmem::ClientMemError,
+        format::Format,
+        gfx_api::GfxTexture,
+        ifs::wl_buffer::WlBufferStorage,
+        leaks::Tracker,
+        object::{Object, Version},
+        state::State,
+        utils::{clonecell::CloneCell, errorfmt::ErrorFmt},
+        wire::{xdg_toplevel_icon_v1::*, XdgToplevelIconV1Id},
+    },
+    isnt::std_1::vec::IsntVecExt,
+    std::{
+        cell::{Cell, RefCell},
+        fmt::{Debug, Formatter},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+/// An immutable toplevel icon as set via xdg-toplevel-icon.
+pub struct ToplevelIcon {
+    pub name: Option<String>,
+    buffers: Vec<ToplevelIconBuffer>,
+}
+
+struct ToplevelIconBuffer {
+    size: i32,
+    scale: i32,
+    stride: i32,
+    format: &'static Format,
+    data: Vec<Cell<u8>>,
+    tex: RefCell<Option<(u32, Rc<dyn GfxTexture>)>>,
+}
+
+impl Debug for ToplevelIcon {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let sizes: Vec<_> = self.buffers.iter().map(|b| (b.size, b.scale)).collect();
+        f.debug_struct("ToplevelIcon")
+            .field("name", &self.name)
+            .field("buffers", &sizes)
+            .finish()
+    }
+}
+
+impl ToplevelIcon {
+    /// Returns a texture for the buffer that best matches the given size in pixels.
+    ///
+    /// Returns `None` if the icon only has a name.
+    pub fn texture(&self, state: &State, size: i32) -> Option<Rc<dyn GfxTexture>> {
+        let buffer = self
+            .buffers
+            .iter()
+            .filter(|b| b.size >= size)
+            .min_by_key(|b| b.size)
+            .or_else(|| self.buffers.iter().max_by_key(|b| b.size))?;
+        let ctx = state.render_ctx.get()?;
+        let version = state.render_ctx_version.get();
+        let tex = &mut *buffer.tex.borrow_mut();
+        if let Some((v, tex)) = tex {
+            if *v == version {
+                return Some(tex.clone());
+            }
+        }
+        let res = ctx.shmem_texture(
+            None,
+            &buffer.data,
+            buffer.format,
+            buffer.size,
+            buffer.size,
+            buffer.stride,
+            None,
+        );
+        match res {
+            Ok(t) => {
+                let t = t.into_texture();
+                *tex = Some((version, t.clone()));
+                Some(t)
+            }
+            Err(e) => {
+                log::warn!("Could not create icon texture: {}", ErrorFmt(e));
+                None
+            }
+        }
+    }
+}
+
+pub struct XdgToplevelIconV1 {
+    pub id: XdgToplevelIconV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    name: RefCell<Option<String>>,
+    buffers: RefCell<Vec<ToplevelIconBuffer>>,
+    icon: CloneCell<Option<Rc<ToplevelIcon>>>,
+    immutable: Cell<bool>,
+}
+
+impl XdgToplevelIconV1 {
+    pub fn new(id: XdgToplevelIconV1Id, client: &Rc<Client>, version: Version) -> Self {
+        Self {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+            name: Default::default(),
+            buffers: Default::default(),
+            icon: Default::default(),
+            immutable: Default::default(),
+        }
+    }
+
+    /// Makes the icon immutable and returns the icon to assign to toplevels.
+    ///
+    /// Returns `None` if the icon has neither a name nor buffers.
+    pub fn freeze(&self) -> Option<Rc<ToplevelIcon>> {
+        if !self.immutable.replace(true) {
+            let name = self.name.take();
+            let buffers = self.buffers.take();
+            if name.is_some() || buffers.is_not_empty() {
+                self.icon.set(Some(Rc::new(ToplevelIcon { name, buffers })));
+            }
+        }
+        self.icon.get()
+    }
+
+    fn check_mutable(&self) -> Result<(), XdgToplevelIconV1Error> {
+        if self.immutable.get() {
+            return Err(XdgToplevelIconV1Error::Immutable);
+        }
+        Ok(())
+    }
+}
+
+impl XdgToplevelIconV1RequestHandler for XdgToplevelIconV1 {
+    type Error = XdgToplevelIconV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn set_name(&self, req: SetName<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.check_mutable()?;
+        *self.name.borrow_mut() = Some(req.icon_name.to_string());
+        Ok(())
+    }
+
+    fn add_buffer(&self, req: AddBuffer, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.check_mutable()?;
+        let buffer = self.client.lookup(req.buffer)?;
+        let size = buffer.rect.width();
+        if size != buffer.rect.height() {
+            return Err(XdgToplevelIconV1Error::NotSquare);
+        }
+        let storage = buffer.storage.borrow();
+        let Some(WlBufferStorage::Shm { mem, stride }) = &*storage else {
+            return Err(XdgToplevelIconV1Error::NotShm);
+        };
+        let len = *stride as usize * size as usize;
+        let data = mem.access(|d| {
+            d[..len]
+                .iter()
+                .map(|c| Cell::new(c.get()))
+                .collect::<Vec<_>>()
+        })?;
+        let buffers = &mut *self.buffers.borrow_mut();
+        buffers.retain(|b| b.size != size || b.scale != req.scale);
+        buffers.push(ToplevelIconBuffer {
+            size,
+            scale: req.scale,
+            stride: *stride,
+            format: buffer.format,
+            data,
+            tex: Default::default(),
+        });
+        Ok(())
+    }
+}
+
+object_base! {
+    self = XdgToplevelIconV1;
+    version = self.version;
+}
+
+impl Object for XdgToplevelIconV1 {}
+
+dedicated_add_obj!(XdgToplevelIconV1, XdgToplevelIconV1Id, xdg_toplevel_icons);
+
+#[derive(Debug, Error)]
+pub enum XdgToplevelIconV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error(transparent)]
+    ClientMemError(Box<ClientMemError>),
+    #[error("The icon has already been assigned to a toplevel")]
+    Immutable,
+    #[error("The buffer is not square")]
+    NotSquare,
+    #[error("The buffer is not a shm buffer")]
+    NotShm,
+}
+efrom!(XdgToplevelIconV1Error, ClientError);
+efrom!(XdgToplevelIconV1Error, ClientMemError);
@@ -0,0 +1,219 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::wl_output::{OutputGlobalOpt, WlOutputGlobal},
+        leaks::Tracker,
+        object::{Object, Version},
+        tree::{ToplevelData, ToplevelOpt},
+        utils::{clonecell::CloneCell, rc_eq::rc_eq},
+        wire::{zwlr_foreign_toplevel_handle_v1::*, WlOutputId, ZwlrForeignToplevelHandleV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+const STATE_ACTIVATED: u32 = 2;
+const STATE_FULLSCREEN: u32 = 3;
+
+pub struct ZwlrForeignToplevelHandleV1 {
+    pub id: ZwlrForeignToplevelHandleV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub toplevel: ToplevelOpt,
+    pub version: Version,
+    pub output: CloneCell<Option<Rc<OutputGlobalOpt>>>,
+}
+
+impl ZwlrForeignToplevelHandleV1 {
+    fn detach(&self) {
+        if let Some(tl) = self.toplevel.get() {
+            tl.tl_data().wlr_handles.remove(&(self.client.id, self.id));
+        }
+    }
+
+    pub fn send_closed(&self) {
+        self.client.event(Closed { self_id: self.id });
+    }
+
+    pub fn send_done(&self) {
+        self.client.event(Done { self_id: self.id });
+    }
+
+    pub fn send_title(&self, title: &str) {
+        self.client.event(Title {
+            self_id: self.id,
+            title,
+        });
+    }
+
+    pub fn send_app_id(&self, app_id: &str) {
+        self.client.event(AppId {
+            self_id: self.id,
+            app_id,
+        });
+    }
+
+    pub fn send_state(&self, data: &ToplevelData) {
+        let mut state = vec![];
+        if data.active() {
+            state.push(STATE_ACTIVATED);
+        }
+        if data.is_fullscreen.get() {
+            state.push(STATE_FULLSCREEN);
+        }
+        self.client.event(State {
+            self_id: self.id,
+            state: &state,
+        });
+    }
+
+    fn send_output_enter(&self, output: WlOutputId) {
+        self.client.event(OutputEnter {
+            self_id: self.id,
+            output,
+        });
+    }
+
+    fn send_output_leave(&self, output: WlOutputId) {
+        self.client.event(OutputLeave {
+            self_id: self.id,
+            output,
+        });
+    }
+
+    pub fn set_output(&self, output: Option<&Rc<WlOutputGlobal>>) {
+        let new = output.map(|o| o.opt.clone());
+        let old = self.output.set(new.clone());
+        match (&old, &new) {
+            (Some(old), Some(new)) if rc_eq(old, new) => return,
+            (None, None) => return,
+            _ => {}
+        }
+        if let Some(old) = old.and_then(|o| o.get()) {
+            old.for_each_binding(self.client.id, |b| self.send_output_leave(b.id));
+        }
+        if let Some(new) = output {
+            new.for_each_binding(self.client.id, |b| self.send_output_enter(b.id));
+        }
+    }
+
+    pub fn handle_new_output(&self, output: WlOutputId) {
+        self.send_output_enter(output);
+        self.send_done();
+    }
+}
+
+impl ZwlrForeignToplevelHandleV1RequestHandler for ZwlrForeignToplevelHandleV1 {
+    type Error = ZwlrForeignToplevelHandleV1Error;
+
+    fn set_maximized(&self, _req: SetMaximized, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn unset_maximized(&self, _req: UnsetMaximized, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_minimized(&self, _req: SetMinimized, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn unset_minimized(&self, _req: UnsetMinimized, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn activate(&self, req: Activate, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let seat = self.client.lookup(req.seat)?;
+        let Some(tl) = self.toplevel.get() else {
+            return Ok(());
+        };
+        if self.client.state.lock.locked.get() {
+            return Ok(());
+        }
+        let data = tl.tl_data();
+        if let Some(parent) = data.parent.get() {
+            parent.cnode_make_visible(tl.tl_as_node());
+        }
+        if data.visible.get() {
+            seat.global.focus_toplevel(tl);
+        } else {
+            data.request_attention(tl.tl_as_node());
+        }
+        Ok(())
+    }
+
+    fn close(&self, _req: Close, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(tl) = self.toplevel.get() {
+            tl.tl_close();
+        }
+        Ok(())
+    }
+
+    fn set_rectangle(&self, req: SetRectangle, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if req.surface.is_some() {
+            self.client.lookup(req.surface)?;
+        }
+        if req.width < 0 || req.height < 0 {
+            return Err(ZwlrForeignToplevelHandleV1Error::InvalidRectangle);
+        }
+        Ok(())
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn set_fullscreen(&self, req: SetFullscreen, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let output = if req.output.is_some() {
+            Some(self.client.lookup(req.output)?)
+        } else {
+            None
+        };
+        let Some(tl) = self.toplevel.get() else {
+            return Ok(());
+        };
+        match output.and_then(|o| o.global.node()) {
+            Some(output) => {
+                let data = tl.tl_data();
+                data.set_fullscreen(&data.state, tl.clone(), &output);
+            }
+            None => tl.tl_set_fullscreen(true),
+        }
+        Ok(())
+    }
+
+    fn unset_fullscreen(&self, _req: UnsetFullscreen, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(tl) = self.toplevel.get() {
+            tl.tl_set_fullscreen(false);
+        }
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrForeignToplevelHandleV1;
+    version = self.version;
+}
+
+impl Object for ZwlrForeignToplevelHandleV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+dedicated_add_obj!(
+    ZwlrForeignToplevelHandleV1,
+    ZwlrForeignToplevelHandleV1Id,
+    wlr_foreign_toplevel_handles
+);
+
+#[derive(Debug, Error)]
+pub enum ZwlrForeignToplevelHandleV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("The rectangle has a negative size")]
+    InvalidRectangle,
+}
+efrom!(ZwlrForeignToplevelHandleV1Error, ClientError);
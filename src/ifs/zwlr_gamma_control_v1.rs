@@ -0,0 +1,120 @@
+use {
+    crate::{
+        backend::GammaLut,
+        client::{Client, ClientError},
+        clientmem::{ClientMem, ClientMemError},
+        ifs::wl_output::OutputGlobalOpt,
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{zwlr_gamma_control_v1::*, ZwlrGammaControlV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwlrGammaControlV1 {
+    pub id: ZwlrGammaControlV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub output: Rc<OutputGlobalOpt>,
+    pub size: Cell<u32>,
+    pub active: Cell<bool>,
+}
+
+impl ZwlrGammaControlV1 {
+    pub fn send_gamma_size(&self, size: u32) {
+        self.client.event(GammaSize {
+            self_id: self.id,
+            size,
+        });
+    }
+
+    pub fn send_failed(&self) {
+        self.client.event(Failed { self_id: self.id });
+    }
+
+    /// Stops controlling the gamma of the output, e.g. because the output was removed.
+    pub fn deactivate(&self) {
+        if self.active.replace(false) {
+            self.send_failed();
+        }
+    }
+
+    fn detach(&self) {
+        if !self.active.replace(false) {
+            return;
+        }
+        if let Some(node) = self.output.node() {
+            node.gamma_control.take();
+            node.set_client_gamma_lut(None);
+        }
+    }
+}
+
+impl ZwlrGammaControlV1RequestHandler for ZwlrGammaControlV1 {
+    type Error = ZwlrGammaControlV1Error;
+
+    fn set_gamma(&self, req: SetGamma, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if !self.active.get() {
+            return Ok(());
+        }
+        let size = self.size.get() as usize;
+        let len = 3 * size * size_of::<u16>();
+        let mem = Rc::new(ClientMem::new_private(
+            &req.fd,
+            len,
+            true,
+            Some(&self.client),
+            None,
+        )?)
+        .offset(0);
+        let mut buf = vec![];
+        mem.read(&mut buf)?;
+        let mut values = buf
+            .chunks_exact(2)
+            .map(|c| u16::from_ne_bytes([c[0], c[1]]));
+        let lut = GammaLut {
+            red: values.by_ref().take(size).collect(),
+            green: values.by_ref().take(size).collect(),
+            blue: values.by_ref().take(size).collect(),
+        };
+        if lut.blue.len() != size {
+            return Err(ZwlrGammaControlV1Error::InvalidGamma);
+        }
+        if let Some(node) = self.output.node() {
+            node.set_client_gamma_lut(Some(Rc::new(lut)));
+        }
+        Ok(())
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrGammaControlV1;
+    version = self.version;
+}
+
+impl Object for ZwlrGammaControlV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwlrGammaControlV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrGammaControlV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error(transparent)]
+    ClientMemError(#[from] ClientMemError),
+    #[error("The gamma ramps are too short")]
+    InvalidGamma,
+}
+efrom!(ZwlrGammaControlV1Error, ClientError);
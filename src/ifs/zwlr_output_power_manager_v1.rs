@@ -0,0 +1,115 @@
+use {
+    crate::{
+        client::{Client, ClientCaps, ClientError, CAP_OUTPUT_POWER_MANAGER},
+        globals::{Global, GlobalName},
+        ifs::zwlr_output_power_v1::ZwlrOutputPowerV1,
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{zwlr_output_power_manager_v1::*, ZwlrOutputPowerManagerV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwlrOutputPowerManagerV1Global {
+    pub name: GlobalName,
+}
+
+impl ZwlrOutputPowerManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwlrOutputPowerManagerV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), ZwlrOutputPowerManagerV1Error> {
+        let mgr = Rc::new(ZwlrOutputPowerManagerV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+        });
+        track!(client, mgr);
+        client.add_client_obj(&mgr)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwlrOutputPowerManagerV1Global,
+    ZwlrOutputPowerManagerV1,
+    ZwlrOutputPowerManagerV1Error
+);
+
+simple_add_global!(ZwlrOutputPowerManagerV1Global);
+
+impl Global for ZwlrOutputPowerManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn required_caps(&self) -> ClientCaps {
+        CAP_OUTPUT_POWER_MANAGER
+    }
+}
+
+pub struct ZwlrOutputPowerManagerV1 {
+    pub id: ZwlrOutputPowerManagerV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl ZwlrOutputPowerManagerV1RequestHandler for ZwlrOutputPowerManagerV1 {
+    type Error = ZwlrOutputPowerManagerV1Error;
+
+    fn get_output_power(&self, req: GetOutputPower, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let output = self.client.lookup(req.output)?;
+        let obj = Rc::new(ZwlrOutputPowerV1 {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+            version: self.version,
+            output: output.global.clone(),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        match output.global.node() {
+            Some(node) => {
+                node.power_controls
+                    .set((self.client.id, req.id), obj.clone());
+                obj.send_mode(node.powered.get());
+            }
+            _ => obj.send_failed(),
+        }
+        Ok(())
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrOutputPowerManagerV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputPowerManagerV1 {}
+
+simple_add_obj!(ZwlrOutputPowerManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputPowerManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwlrOutputPowerManagerV1Error, ClientError);
@@ -0,0 +1,88 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::wl_output::OutputGlobalOpt,
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{zwlr_output_power_v1::*, ZwlrOutputPowerV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+const MODE_OFF: u32 = 0;
+const MODE_ON: u32 = 1;
+
+pub struct ZwlrOutputPowerV1 {
+    pub id: ZwlrOutputPowerV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+    pub output: Rc<OutputGlobalOpt>,
+}
+
+impl ZwlrOutputPowerV1 {
+    pub fn send_mode(&self, on: bool) {
+        self.client.event(Mode {
+            self_id: self.id,
+            mode: match on {
+                true => MODE_ON,
+                false => MODE_OFF,
+            },
+        });
+    }
+
+    pub fn send_failed(&self) {
+        self.client.event(Failed { self_id: self.id });
+    }
+
+    fn detach(&self) {
+        if let Some(node) = self.output.node() {
+            node.power_controls.remove(&(self.client.id, self.id));
+        }
+    }
+}
+
+impl ZwlrOutputPowerV1RequestHandler for ZwlrOutputPowerV1 {
+    type Error = ZwlrOutputPowerV1Error;
+
+    fn set_mode(&self, req: SetMode, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let on = match req.mode {
+            MODE_OFF => false,
+            MODE_ON => true,
+            _ => return Err(ZwlrOutputPowerV1Error::InvalidMode(req.mode)),
+        };
+        if let Some(node) = self.output.node() {
+            node.set_powered(on);
+        }
+        Ok(())
+    }
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwlrOutputPowerV1;
+    version = self.version;
+}
+
+impl Object for ZwlrOutputPowerV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwlrOutputPowerV1);
+
+#[derive(Debug, Error)]
+pub enum ZwlrOutputPowerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Unknown power mode {0}")]
+    InvalidMode(u32),
+}
+efrom!(ZwlrOutputPowerV1Error, ClientError);
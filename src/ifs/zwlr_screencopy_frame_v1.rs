@@ -10,23 +10,38 @@ use {
         leaks::Tracker,
         object::{Object, Version},
         rect::Rect,
+        scale::Scale,
         utils::errorfmt::ErrorFmt,
-        wire::{zwlr_screencopy_frame_v1::*, WlBufferId, ZwlrScreencopyFrameV1Id},
+        wire::{
+            zwlr_screencopy_frame_v1::*, WlBufferId, ZwlrScreencopyFrameV1Id,
+            ZwlrScreencopyManagerV1Id,
+        },
+    },
+    jay_config::video::Transform,
+    std::{
+        cell::{Cell, RefCell},
+        mem,
+        ops::Deref,
+        rc::Rc,
     },
-    std::{cell::Cell, ops::Deref, rc::Rc},
     thiserror::Error,
 };
 
+/// The number of damage rectangles after which the damage is collapsed to the full
+/// output.
+const MAX_DAMAGE_RECTS: usize = 32;
+
 #[expect(dead_code)]
 pub const FLAGS_Y_INVERT: u32 = 1;
 
 pub struct ZwlrScreencopyFrameV1 {
     pub id: ZwlrScreencopyFrameV1Id,
+    pub manager: ZwlrScreencopyManagerV1Id,
     pub client: Rc<Client>,
     pub tracker: Tracker<Self>,
     pub output: Rc<OutputGlobalOpt>,
     pub rect: Rect,
-    pub _overlay_cursor: bool,
+    pub overlay_cursor: bool,
     pub used: Cell<bool>,
     pub with_damage: Cell<bool>,
     pub buffer: Cell<Option<Rc<WlBuffer>>>,
@@ -34,6 +49,55 @@ pub struct ZwlrScreencopyFrameV1 {
     pub pending: Cell<Option<PendingShmTransfer>>,
 }
 
+/// The damage of an output accumulated since the last frame that a screencopy manager
+/// captured with damage.
+///
+/// Rectangles are stored in the logical coordinate space of the output.
+pub struct ScreencopyDamage {
+    full: Cell<bool>,
+    rects: RefCell<Vec<Rect>>,
+}
+
+impl ScreencopyDamage {
+    pub fn new() -> Self {
+        Self {
+            full: Cell::new(true),
+            rects: Default::default(),
+        }
+    }
+
+    pub fn damage(&self, rect: Rect) {
+        if self.full.get() || rect.is_empty() {
+            return;
+        }
+        let rects = &mut *self.rects.borrow_mut();
+        if rects.len() >= MAX_DAMAGE_RECTS {
+            rects.clear();
+            self.full.set(true);
+        } else {
+            rects.push(rect);
+        }
+    }
+
+    pub fn damage_full(&self) {
+        self.full.set(true);
+        self.rects.borrow_mut().clear();
+    }
+
+    pub fn is_damaged(&self) -> bool {
+        self.full.get() || !self.rects.borrow().is_empty()
+    }
+
+    /// Returns the accumulated damage or `None` if the full output is damaged.
+    fn take(&self) -> Option<Vec<Rect>> {
+        let rects = mem::take(&mut *self.rects.borrow_mut());
+        match self.full.replace(false) {
+            true => None,
+            false => Some(rects),
+        }
+    }
+}
+
 impl ZwlrScreencopyFrameV1 {
     pub fn send_ready(&self, tv_sec: u64, tv_nsec: u32) {
         self.client.event(Ready {
@@ -48,16 +112,41 @@ impl ZwlrScreencopyFrameV1 {
         self.client.event(Failed { self_id: self.id });
     }
 
-    pub fn send_damage(&self) {
-        if let Some(output) = self.output.get() {
-            let pos = output.pos.get();
-            self.client.event(Damage {
-                self_id: self.id,
-                x: 0,
-                y: 0,
-                width: pos.width() as _,
-                height: pos.height() as _,
-            });
+    fn send_damage_rect(&self, rect: Rect) {
+        self.client.event(Damage {
+            self_id: self.id,
+            x: rect.x1() as _,
+            y: rect.y1() as _,
+            width: rect.width() as _,
+            height: rect.height() as _,
+        });
+    }
+
+    /// Sends the damage accumulated in `damage` in buffer coordinates.
+    pub fn send_damage(&self, damage: &ScreencopyDamage, scale: Scale, transform: Transform) {
+        let full = Rect::new_sized_unchecked(0, 0, self.rect.width(), self.rect.height());
+        let rects = match damage.take() {
+            Some(rects) if transform == Transform::None => rects,
+            _ => {
+                self.send_damage_rect(full);
+                return;
+            }
+        };
+        let scale = scale.to_f64();
+        for rect in rects {
+            let x1 = (rect.x1() as f64 * scale).floor() as i32;
+            let y1 = (rect.y1() as f64 * scale).floor() as i32;
+            let x2 = (rect.x2() as f64 * scale).ceil() as i32;
+            let y2 = (rect.y2() as f64 * scale).ceil() as i32;
+            let Some(rect) = Rect::new(x1, y1, x2, y2) else {
+                continue;
+            };
+            let rect = rect
+                .intersect(self.rect)
+                .move_(-self.rect.x1(), -self.rect.y1());
+            if !rect.is_empty() {
+                self.send_damage_rect(rect);
+            }
         }
     }
 
@@ -118,7 +207,21 @@ impl ZwlrScreencopyFrameV1 {
             }
         }
         self.buffer.set(Some(buffer));
-        if !with_damage {
+        let mut needs_render = true;
+        if with_damage {
+            let key = (self.client.id, self.manager);
+            let damage = match node.screencopy_damage.get(&key) {
+                Some(d) => d,
+                _ => {
+                    let d = Rc::new(ScreencopyDamage::new());
+                    node.screencopy_damage.set(key, d.clone());
+                    d
+                }
+            };
+            // Otherwise the frame is completed once the output is next damaged.
+            needs_render = damage.is_damaged();
+        }
+        if needs_render {
             if let Some(global) = self.output.get() {
                 global.connector.damage();
             }
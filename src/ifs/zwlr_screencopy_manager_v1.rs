@@ -91,12 +91,22 @@ impl ZwlrScreencopyManagerV1RequestHandler for ZwlrScreencopyManagerV1 {
     }
 
     fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
         self.client.remove_obj(self)?;
         Ok(())
     }
 }
 
 impl ZwlrScreencopyManagerV1 {
+    fn detach(&self) {
+        let key = (self.client.id, self.id);
+        for output in self.client.state.root.outputs.lock().values() {
+            if output.screencopy_damage.remove(&key).is_some() {
+                output.screencast_changed();
+            }
+        }
+    }
+
     fn do_capture_output(
         &self,
         output: WlOutputId,
@@ -121,11 +131,12 @@ impl ZwlrScreencopyManagerV1 {
         }
         let frame = Rc::new(ZwlrScreencopyFrameV1 {
             id: frame,
+            manager: self.id,
             client: self.client.clone(),
             tracker: Default::default(),
             output: output.global.clone(),
             rect,
-            _overlay_cursor: overlay_cursor,
+            overlay_cursor,
             used: Cell::new(false),
             with_damage: Cell::new(false),
             buffer: Cell::new(None),
@@ -148,7 +159,11 @@ object_base! {
     version = self.version;
 }
 
-impl Object for ZwlrScreencopyManagerV1 {}
+impl Object for ZwlrScreencopyManagerV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
 
 simple_add_obj!(ZwlrScreencopyManagerV1);
 
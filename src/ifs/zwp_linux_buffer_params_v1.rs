@@ -1,6 +1,8 @@
 use {
     crate::{
         client::ClientError,
+        dmabuf_bridge,
+        format::formats,
         gfx_api::GfxError,
         ifs::{wl_buffer::WlBuffer, zwp_linux_dmabuf_v1::ZwpLinuxDmabufV1},
         leaks::Tracker,
@@ -70,23 +72,23 @@ impl ZwpLinuxBufferParamsV1 {
             Some(ctx) => ctx,
             None => return Err(ZwpLinuxBufferParamsV1Error::NoRenderContext),
         };
-        let formats = ctx.formats();
-        let format = match formats.get(&format) {
-            Some(f) => f,
+        let state = &self.parent.client.state;
+        let format = match formats().get(&format) {
+            Some(f) => *f,
             None => return Err(ZwpLinuxBufferParamsV1Error::InvalidFormat(format)),
         };
         let modifier = match self.modifier.get() {
             Some(m) => m,
             _ => return Err(ZwpLinuxBufferParamsV1Error::NoPlanes),
         };
-        if !format.read_modifiers.contains(&modifier) {
+        if !dmabuf_bridge::is_importable(state, &ctx, format.drm, modifier) {
             return Err(ZwpLinuxBufferParamsV1Error::InvalidModifier(modifier));
         }
         let mut dmabuf = DmaBuf {
             id: self.parent.client.state.dma_buf_ids.next(),
             width,
             height,
-            format: format.format,
+            format,
             modifier,
             planes: PlaneVec::new(),
         };
@@ -102,7 +104,8 @@ impl ZwpLinuxBufferParamsV1 {
                 fd: p.fd,
             });
         }
-        let img = ctx.dmabuf_img(&dmabuf)?;
+        let charge = self.parent.client.charge_dmabuf(&dmabuf)?;
+        let (img, bridge) = dmabuf_bridge::import_dmabuf(state, &ctx, &dmabuf)?;
         let (is_client_id, buffer_id) = match buffer_id {
             Some(i) => (true, i),
             None => (false, self.parent.client.new_id()?),
@@ -110,9 +113,11 @@ impl ZwpLinuxBufferParamsV1 {
         let buffer = Rc::new(WlBuffer::new_dmabuf(
             buffer_id,
             &self.parent.client,
-            format.format,
+            format,
             dmabuf,
             &img,
+            bridge,
+            charge,
         ));
         track!(self.parent.client, buffer);
         if is_client_id {
@@ -158,6 +163,7 @@ impl ZwpLinuxBufferParamsV1RequestHandler for ZwpLinuxBufferParamsV1 {
             Ok(id) => {
                 self.send_created(id);
             }
+            Err(e @ ZwpLinuxBufferParamsV1Error::ClientError(_)) => return Err(e),
             Err(e) => {
                 log::warn!("Could not create a dmabuf buffer: {}", ErrorFmt(e));
                 self.send_failed();
@@ -0,0 +1,111 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::wl_surface::zwp_linux_surface_synchronization_v1::{
+            ZwpLinuxSurfaceSynchronizationV1, ZwpLinuxSurfaceSynchronizationV1Error,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{zwp_linux_explicit_synchronization_v1::*, ZwpLinuxExplicitSynchronizationV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwpLinuxExplicitSynchronizationV1Global {
+    pub name: GlobalName,
+}
+
+pub struct ZwpLinuxExplicitSynchronizationV1 {
+    pub id: ZwpLinuxExplicitSynchronizationV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: Version,
+}
+
+impl ZwpLinuxExplicitSynchronizationV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwpLinuxExplicitSynchronizationV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), ZwpLinuxExplicitSynchronizationV1Error> {
+        let obj = Rc::new(ZwpLinuxExplicitSynchronizationV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwpLinuxExplicitSynchronizationV1Global,
+    ZwpLinuxExplicitSynchronizationV1,
+    ZwpLinuxExplicitSynchronizationV1Error
+);
+
+impl Global for ZwpLinuxExplicitSynchronizationV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        2
+    }
+}
+
+simple_add_global!(ZwpLinuxExplicitSynchronizationV1Global);
+
+impl ZwpLinuxExplicitSynchronizationV1RequestHandler for ZwpLinuxExplicitSynchronizationV1 {
+    type Error = ZwpLinuxExplicitSynchronizationV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get_synchronization(
+        &self,
+        req: GetSynchronization,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let surface = self.client.lookup(req.surface)?;
+        let sync = Rc::new(ZwpLinuxSurfaceSynchronizationV1::new(
+            req.id,
+            &self.client,
+            &surface,
+            self.version,
+        ));
+        track!(self.client, sync);
+        sync.install()?;
+        self.client.add_client_obj(&sync)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwpLinuxExplicitSynchronizationV1;
+    version = self.version;
+}
+
+impl Object for ZwpLinuxExplicitSynchronizationV1 {}
+
+simple_add_obj!(ZwpLinuxExplicitSynchronizationV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpLinuxExplicitSynchronizationV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error(transparent)]
+    ZwpLinuxSurfaceSynchronizationV1Error(#[from] ZwpLinuxSurfaceSynchronizationV1Error),
+}
+efrom!(ZwpLinuxExplicitSynchronizationV1Error, ClientError);
@@ -0,0 +1,110 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::wl_surface::zwp_xwayland_keyboard_grab_v1::ZwpXwaylandKeyboardGrabV1,
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{zwp_xwayland_keyboard_grab_manager_v1::*, ZwpXwaylandKeyboardGrabManagerV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwpXwaylandKeyboardGrabManagerV1Global {
+    name: GlobalName,
+}
+
+impl ZwpXwaylandKeyboardGrabManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwpXwaylandKeyboardGrabManagerV1Id,
+        client: &Rc<Client>,
+        version: Version,
+    ) -> Result<(), ZwpXwaylandKeyboardGrabManagerV1Error> {
+        let obj = Rc::new(ZwpXwaylandKeyboardGrabManagerV1 {
+            id,
+            client: client.clone(),
+            version,
+            tracker: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwpXwaylandKeyboardGrabManagerV1Global,
+    ZwpXwaylandKeyboardGrabManagerV1,
+    ZwpXwaylandKeyboardGrabManagerV1Error
+);
+
+impl Global for ZwpXwaylandKeyboardGrabManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn xwayland_only(&self) -> bool {
+        true
+    }
+}
+
+simple_add_global!(ZwpXwaylandKeyboardGrabManagerV1Global);
+
+pub struct ZwpXwaylandKeyboardGrabManagerV1 {
+    pub id: ZwpXwaylandKeyboardGrabManagerV1Id,
+    pub client: Rc<Client>,
+    pub version: Version,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwpXwaylandKeyboardGrabManagerV1RequestHandler for ZwpXwaylandKeyboardGrabManagerV1 {
+    type Error = ZwpXwaylandKeyboardGrabManagerV1Error;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn grab_keyboard(&self, req: GrabKeyboard, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let surface = self.client.lookup(req.surface)?;
+        let seat = self.client.lookup(req.seat)?;
+        let grab = Rc::new(ZwpXwaylandKeyboardGrabV1 {
+            id: req.id,
+            client: self.client.clone(),
+            surface,
+            seat: seat.global.clone(),
+            tracker: Default::default(),
+            version: self.version,
+        });
+        track!(self.client, grab);
+        self.client.add_client_obj(&grab)?;
+        grab.install();
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwpXwaylandKeyboardGrabManagerV1;
+    version = self.version;
+}
+
+impl Object for ZwpXwaylandKeyboardGrabManagerV1 {}
+
+simple_add_obj!(ZwpXwaylandKeyboardGrabManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpXwaylandKeyboardGrabManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwpXwaylandKeyboardGrabManagerV1Error, ClientError);
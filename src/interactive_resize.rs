@@ -0,0 +1,90 @@
+use {
+    crate::{
+        rect::Rect,
+        state::State,
+        tree::{NodeId, ToplevelNode},
+        utils::numcell::NumCell,
+    },
+    ahash::AHashSet,
+    std::{
+        cell::{Cell, RefCell},
+        mem,
+        rc::Rc,
+    },
+};
+
+const MAX_DEFERRED_DAMAGE: usize = 128;
+
+/// Tracks interactive resizes to reduce the work done while they are in progress.
+///
+/// If throttling is enabled, then, while a resize is in progress,
+///
+/// - toplevels only receive a new size after they have committed the previous one, and
+/// - damage from windows that are not being resized is deferred until the resize ends.
+#[derive(Default)]
+pub struct InteractiveResize {
+    pub throttle: Cell<bool>,
+    active: NumCell<u32>,
+    resized: RefCell<AHashSet<NodeId>>,
+    deferred_damage: RefCell<Vec<Rect>>,
+}
+
+/// Marks an interactive resize as in progress until it is dropped.
+pub struct InteractiveResizeGuard {
+    state: Rc<State>,
+}
+
+impl InteractiveResize {
+    pub fn begin(&self, state: &Rc<State>) -> InteractiveResizeGuard {
+        self.active.fetch_add(1);
+        InteractiveResizeGuard {
+            state: state.clone(),
+        }
+    }
+
+    pub fn in_progress(&self) -> bool {
+        self.active.get() > 0
+    }
+
+    pub fn throttled(&self) -> bool {
+        self.throttle.get() && self.in_progress()
+    }
+
+    pub fn toplevel_resized(&self, tl: &dyn ToplevelNode) {
+        if self.throttled() {
+            self.resized.borrow_mut().insert(tl.tl_as_node().node_id());
+        }
+    }
+
+    /// Returns whether damage caused by the toplevel should be deferred.
+    pub fn defers_damage_of(&self, tl: &dyn ToplevelNode) -> bool {
+        self.throttled() && !self.resized.borrow().contains(&tl.tl_as_node().node_id())
+    }
+
+    pub fn defer_damage(&self, rect: Rect) {
+        if rect.is_empty() {
+            return;
+        }
+        let damage = &mut *self.deferred_damage.borrow_mut();
+        damage.push(rect);
+        if damage.len() > MAX_DEFERRED_DAMAGE {
+            let extents = damage.iter().fold(rect, |a, b| a.union(*b));
+            damage.clear();
+            damage.push(extents);
+        }
+    }
+}
+
+impl Drop for InteractiveResizeGuard {
+    fn drop(&mut self) {
+        let ir = &self.state.interactive_resize;
+        if ir.active.fetch_sub(1) > 1 {
+            return;
+        }
+        ir.resized.borrow_mut().clear();
+        let damage = mem::take(&mut *ir.deferred_damage.borrow_mut());
+        for rect in damage {
+            self.state.damage(rect);
+        }
+    }
+}
@@ -4,7 +4,7 @@ use {
         async_engine::SpawnedFuture,
         backend::{
             AxisSource, Backend, BackendEvent, Connector, ConnectorEvent, ConnectorId,
-            ConnectorKernelId, DrmDeviceId, InputDevice, InputDeviceAccelProfile,
+            ConnectorKernelId, DrmDeviceId, GammaLut, InputDevice, InputDeviceAccelProfile,
             InputDeviceCapability, InputDeviceId, InputEvent, KeyState, Mode, MonitorInfo,
             ScrollAxis, TransformMatrix,
         },
@@ -76,6 +76,8 @@ impl TestBackend {
             },
             events: Default::default(),
             feedback: Default::default(),
+            gamma_lut: Default::default(),
+            mirror_source: Default::default(),
         });
         let default_mouse = Rc::new(TestBackendMouse {
             common: TestInputDeviceCommon {
@@ -129,6 +131,9 @@ impl TestBackend {
             height_mm: 60,
             non_desktop: false,
             vrr_capable: false,
+            hdr_capable: false,
+            edid: None,
+            properties: Default::default(),
         };
         Self {
             state: state.clone(),
@@ -302,6 +307,8 @@ pub struct TestConnector {
     pub kernel_id: ConnectorKernelId,
     pub events: OnChange<ConnectorEvent>,
     pub feedback: CloneCell<Option<Rc<DrmFeedback>>>,
+    pub gamma_lut: CloneCell<Option<Rc<GammaLut>>>,
+    pub mirror_source: Cell<Option<ConnectorId>>,
 }
 
 impl Connector for TestConnector {
@@ -336,6 +343,18 @@ impl Connector for TestConnector {
     fn drm_feedback(&self) -> Option<Rc<DrmFeedback>> {
         self.feedback.get()
     }
+
+    fn gamma_lut_size(&self) -> u32 {
+        256
+    }
+
+    fn set_gamma_lut(&self, lut: Option<Rc<GammaLut>>) {
+        self.gamma_lut.set(lut);
+    }
+
+    fn set_mirror_source(&self, source: Option<ConnectorId>) {
+        self.mirror_source.set(source);
+    }
 }
 
 pub struct TestMouseClick {
@@ -424,6 +443,27 @@ impl TestBackendMouse {
             time_usec: self.common.state.now_usec(),
         });
     }
+
+    pub fn swipe(&self, fingers: u32, dx: f64, dy: f64) {
+        self.common.event(InputEvent::SwipeBegin {
+            time_usec: self.common.state.now_usec(),
+            finger_count: fingers,
+        });
+        self.common.event(InputEvent::SwipeUpdate {
+            time_usec: self.common.state.now_usec(),
+            dx: Fixed::from_f64(dx),
+            dy: Fixed::from_f64(dy),
+            dx_unaccelerated: Fixed::from_f64(dx),
+            dy_unaccelerated: Fixed::from_f64(dy),
+        });
+    }
+
+    pub fn swipe_end(&self, cancelled: bool) {
+        self.common.event(InputEvent::SwipeEnd {
+            time_usec: self.common.state.now_usec(),
+            cancelled,
+        });
+    }
 }
 
 pub struct TestBackendKb {
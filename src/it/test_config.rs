@@ -1,23 +1,25 @@
 use {
     crate::{
-        backend::InputDeviceId,
+        backend::{ConnectorId, InputDeviceId},
         ifs::wl_seat::SeatId,
         it::test_error::{TestError, TestResult},
         tree::OutputNode,
-        utils::{copyhashmap::CopyHashMap, stack::Stack},
+        utils::{clonecell::CloneCell, copyhashmap::CopyHashMap, stack::Stack},
     },
     bincode::Options,
     isnt::std_1::primitive::IsntConstPtrExt,
     jay_config::{
         _private::{
             bincode_ops,
-            ipc::{ClientMessage, Response, ServerMessage},
+            ipc::{ClientMessage, Response, ServerMessage, WorkspaceSource},
             ConfigEntry, VERSION,
         },
         input::{InputDevice, Seat},
         keyboard::{Keymap, ModifiedKeySym},
-        video::{Connector, Transform},
-        Axis, Direction,
+        layout::{Layout, LayoutGeometry, LayoutRequest},
+        theme::sized::Resizable,
+        video::{Connector, NightLightSchedule, Transform},
+        Axis, Direction, EmptyWorkspaceBehavior,
     },
     std::{cell::Cell, ops::Deref, ptr, rc::Rc, time::Duration},
 };
@@ -42,6 +44,7 @@ where
         responses: Default::default(),
         invoked_shortcuts: Default::default(),
         graphics_initialized: Cell::new(false),
+        layout: Default::default(),
     });
     let old = CONFIG.get();
     CONFIG.set(tc.deref());
@@ -125,6 +128,23 @@ unsafe extern "C" fn handle_msg(data: *const u8, msg: *const u8, size: usize) {
         ServerMessage::InterestReady { .. } => {}
         ServerMessage::Features { .. } => {}
         ServerMessage::SwitchEvent { .. } => {}
+        ServerMessage::TouchGesture { .. } => {}
+        ServerMessage::InvokeGesture { .. } => {}
+        ServerMessage::InvokePointerBinding { .. } => {}
+        ServerMessage::InvokeShortcutSequence { .. } => {}
+        ServerMessage::ModeFallback { .. } => {}
+        ServerMessage::Locked => {}
+        ServerMessage::Unlocked => {}
+        ServerMessage::LockerCrashed => {}
+        ServerMessage::ConnectorModeChanged { .. } => {}
+        ServerMessage::Layout { request, .. } => {
+            if let Some(layout) = tc.layout.get() {
+                let geometries = layout(&request);
+                let _ = tc.send(ClientMessage::SetLayoutGeometries { geometries });
+            }
+        }
+        ServerMessage::WindowScreenshotSaved { .. } => {}
+        ServerMessage::BrightnessChanged { .. } => {}
     }
 }
 
@@ -140,6 +160,7 @@ pub struct TestConfig {
     responses: Stack<Response>,
     pub invoked_shortcuts: CopyHashMap<(SeatId, ModifiedKeySym), ()>,
     pub graphics_initialized: Cell<bool>,
+    pub layout: CloneCell<Option<Rc<dyn Fn(&LayoutRequest) -> Vec<LayoutGeometry>>>>,
 }
 
 macro_rules! get_response {
@@ -255,6 +276,13 @@ impl TestConfig {
         })
     }
 
+    pub fn set_workspace_swipe_fingers(&self, seat: SeatId, fingers: u32) -> TestResult {
+        self.send(ClientMessage::SetWorkspaceSwipeFingers {
+            seat: Seat(seat.raw() as _),
+            fingers,
+        })
+    }
+
     pub fn set_fullscreen(&self, seat: SeatId, fs: bool) -> TestResult {
         self.send(ClientMessage::SetFullscreen {
             seat: Seat(seat.raw() as _),
@@ -277,6 +305,101 @@ impl TestConfig {
         })
     }
 
+    pub fn set_empty_workspace_behavior(&self, behavior: EmptyWorkspaceBehavior) -> TestResult {
+        self.send(ClientMessage::SetEmptyWorkspaceBehavior { behavior })
+    }
+
+    pub fn set_autotiling(&self, enabled: bool) -> TestResult {
+        self.send(ClientMessage::SetAutotiling { enabled })
+    }
+
+    pub fn set_night_light_schedule(&self, schedule: NightLightSchedule) -> TestResult {
+        self.send(ClientMessage::SetNightLightSchedule { schedule })
+    }
+
+    pub fn set_night_light_temperature(&self, kelvin: u32) -> TestResult {
+        self.send(ClientMessage::SetNightLightTemperature { kelvin })
+    }
+
+    pub fn set_workspace_layout(&self, name: &str, layout: Option<Layout>) -> TestResult {
+        let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
+        get_response!(reply, GetWorkspace { workspace });
+        self.send(ClientMessage::SetWorkspaceLayout { workspace, layout })
+    }
+
+    pub fn rename_workspace(&self, old: &str, new: &str) -> TestResult {
+        let reply = self.send_with_reply(ClientMessage::GetWorkspace { name: old })?;
+        get_response!(reply, GetWorkspace { workspace });
+        self.send(ClientMessage::RenameWorkspace {
+            workspace,
+            name: new,
+        })
+    }
+
+    pub fn relayout(&self, layout: Layout) -> TestResult {
+        self.send(ClientMessage::Relayout { layout })
+    }
+
+    pub fn show_previous_workspace(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::ShowPreviousWorkspace {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
+    pub fn set_workspace_back_and_forth(&self, enabled: bool) -> TestResult {
+        self.send(ClientMessage::SetWorkspaceBackAndForth { enabled })
+    }
+
+    pub fn mark(&self, seat: SeatId, mark: &str) -> TestResult {
+        self.send(ClientMessage::Mark {
+            seat: Seat(seat.raw() as _),
+            mark,
+        })
+    }
+
+    pub fn unmark(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::Unmark {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
+    pub fn focus_mark(&self, seat: SeatId, mark: &str) -> TestResult {
+        self.send(ClientMessage::FocusMark {
+            seat: Seat(seat.raw() as _),
+            mark,
+        })
+    }
+
+    pub fn swap_with_mark(&self, seat: SeatId, mark: &str) -> TestResult {
+        self.send(ClientMessage::SwapWithMark {
+            seat: Seat(seat.raw() as _),
+            mark,
+        })
+    }
+
+    pub fn set_show_marks(&self, show: bool) -> TestResult {
+        self.send(ClientMessage::SetShowMarks { show })
+    }
+
+    pub fn set_size(&self, sized: Resizable, size: i32) -> TestResult {
+        self.send(ClientMessage::SetSize { sized, size })
+    }
+
+    pub fn set_workspace_inner_gap(&self, name: &str, gap: Option<i32>) -> TestResult {
+        let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
+        get_response!(reply, GetWorkspace { workspace });
+        self.send(ClientMessage::SetWorkspaceInnerGap { workspace, gap })
+    }
+
+    pub fn move_workspace_to_output(&self, name: &str, output: &OutputNode) -> TestResult {
+        let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
+        get_response!(reply, GetWorkspace { workspace });
+        self.send(ClientMessage::MoveToOutput {
+            workspace: WorkspaceSource::Explicit(workspace),
+            connector: Connector(output.global.connector.connector.id().raw() as _),
+        })
+    }
+
     fn clear(&self) {
         unsafe {
             if let Some(srv) = self.srv.take() {
@@ -292,6 +415,13 @@ impl TestConfig {
         })
     }
 
+    pub fn set_mirror(&self, connector: ConnectorId, source: Option<ConnectorId>) -> TestResult {
+        self.send(ClientMessage::ConnectorSetMirror {
+            connector: Connector(connector.raw() as _),
+            source: source.map(|s| Connector(s.raw() as _)),
+        })
+    }
+
     pub fn set_output_transform(&self, output: &OutputNode, transform: Transform) -> TestResult {
         self.send(ClientMessage::ConnectorSetTransform {
             connector: Connector(output.global.connector.connector.id().raw() as _),
@@ -25,12 +25,17 @@ pub mod test_input_method_keyboard_grab;
 pub mod test_input_method_manager;
 pub mod test_input_popup_surface;
 pub mod test_jay_compositor;
+pub mod test_jay_output;
 pub mod test_keyboard;
+pub mod test_output;
 pub mod test_pointer;
 pub mod test_region;
 pub mod test_registry;
+pub mod test_screencast;
 pub mod test_screenshot;
 pub mod test_seat;
+pub mod test_session_lock;
+pub mod test_session_lock_manager;
 pub mod test_shm;
 pub mod test_shm_buffer;
 pub mod test_shm_pool;
@@ -3,7 +3,10 @@ use {
         client::ClientId,
         it::{
             test_error::{TestError, TestResult},
-            test_ifs::test_screenshot::TestJayScreenshot,
+            test_ifs::{
+                test_jay_output::TestJayOutput, test_output::TestOutput,
+                test_screencast::TestJayScreencast, test_screenshot::TestJayScreenshot,
+            },
             test_object::TestObject,
             test_transport::TestTransport,
             testrun::ParseFull,
@@ -77,6 +80,30 @@ impl TestJayCompositor {
         }
     }
 
+    pub fn get_output(&self, output: &TestOutput) -> TestResult<Rc<TestJayOutput>> {
+        let jo = Rc::new(TestJayOutput { id: self.tran.id() });
+        self.tran.add_obj(jo.clone())?;
+        self.tran.send(GetOutput {
+            self_id: self.id,
+            id: jo.id,
+            output: output.id,
+        })?;
+        Ok(jo)
+    }
+
+    pub fn create_screencast(&self) -> TestResult<Rc<TestJayScreencast>> {
+        let sc = Rc::new(TestJayScreencast {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+        });
+        self.tran.add_obj(sc.clone())?;
+        self.tran.send(CreateScreencast {
+            self_id: self.id,
+            id: sc.id,
+        })?;
+        Ok(sc)
+    }
+
     fn handle_client_id(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
         let ev = jay_compositor::ClientId::parse_full(parser)?;
         self.client_id.set(Some(ClientId::from_raw(ev.client_id)));
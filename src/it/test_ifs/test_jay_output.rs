@@ -0,0 +1,36 @@
+use crate::{
+    it::{test_error::TestError, test_object::TestObject, testrun::ParseFull},
+    utils::buffd::MsgParser,
+    wire::{jay_output::*, JayOutputId},
+};
+
+pub struct TestJayOutput {
+    pub id: JayOutputId,
+}
+
+impl TestJayOutput {
+    fn handle_linear_id(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = LinearId::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_unused(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Unused::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_destroyed(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Destroyed::parse_full(parser)?;
+        Ok(())
+    }
+}
+
+test_object! {
+    TestJayOutput, JayOutput;
+
+    LINEAR_ID => handle_linear_id,
+    UNUSED => handle_unused,
+    DESTROYED => handle_destroyed,
+}
+
+impl TestObject for TestJayOutput {}
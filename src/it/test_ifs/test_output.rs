@@ -0,0 +1,64 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_object::TestObject, test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{wl_output::*, WlOutputId},
+    },
+    std::rc::Rc,
+};
+
+pub struct TestOutput {
+    pub id: WlOutputId,
+}
+
+impl TestOutput {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self { id: tran.id() }
+    }
+
+    fn handle_geometry(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Geometry::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_mode(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Mode::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Done::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_scale(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Scale::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_name(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Name::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_description(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Description::parse_full(parser)?;
+        Ok(())
+    }
+}
+
+test_object! {
+    TestOutput, WlOutput;
+
+    GEOMETRY => handle_geometry,
+    MODE => handle_mode,
+    DONE => handle_done,
+    SCALE => handle_scale,
+    NAME => handle_name,
+    DESCRIPTION => handle_description,
+}
+
+impl TestObject for TestOutput {}
@@ -0,0 +1,142 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestResult},
+            test_ifs::test_jay_output::TestJayOutput,
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{jay_screencast::*, JayScreencastId},
+    },
+    std::rc::Rc,
+};
+
+pub struct TestJayScreencast {
+    pub id: JayScreencastId,
+    pub tran: Rc<TestTransport>,
+}
+
+impl TestJayScreencast {
+    pub fn set_output(&self, output: &TestJayOutput) -> TestResult {
+        self.tran.send(SetOutput {
+            self_id: self.id,
+            output: output.id,
+        })?;
+        Ok(())
+    }
+
+    pub fn set_running(&self, running: bool) -> TestResult {
+        self.tran.send(SetRunning {
+            self_id: self.id,
+            running: running as _,
+        })?;
+        Ok(())
+    }
+
+    pub fn configure(&self) -> TestResult {
+        self.tran.send(Configure { self_id: self.id })?;
+        Ok(())
+    }
+
+    fn handle_plane(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Plane::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_buffer(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Buffer::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_buffers_done(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = BuffersDone::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_ready(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Ready::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_destroyed(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Destroyed::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_missed_frame(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = MissedFrame::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_config_output(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = ConfigOutput::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_config_allow_all_workspaces(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), TestError> {
+        let _ev = ConfigAllowAllWorkspaces::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_config_allow_workspace(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = ConfigAllowWorkspace::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_config_use_linear_buffers(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = ConfigUseLinearBuffers::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_config_running(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = ConfigRunning::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_config_done(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = ConfigDone::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_config_size(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = ConfigSize::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_clock_id(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = ClockId::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_presentation_time(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = PresentationTime::parse_full(parser)?;
+        Ok(())
+    }
+}
+
+test_object! {
+    TestJayScreencast, JayScreencast;
+
+    PLANE => handle_plane,
+    BUFFER => handle_buffer,
+    BUFFERS_DONE => handle_buffers_done,
+    READY => handle_ready,
+    DESTROYED => handle_destroyed,
+    MISSED_FRAME => handle_missed_frame,
+    CONFIG_OUTPUT => handle_config_output,
+    CONFIG_ALLOW_ALL_WORKSPACES => handle_config_allow_all_workspaces,
+    CONFIG_ALLOW_WORKSPACE => handle_config_allow_workspace,
+    CONFIG_USE_LINEAR_BUFFERS => handle_config_use_linear_buffers,
+    CONFIG_RUNNING => handle_config_running,
+    CONFIG_DONE => handle_config_done,
+    CONFIG_SIZE => handle_config_size,
+    CLOCK_ID => handle_clock_id,
+    PRESENTATION_TIME => handle_presentation_time,
+}
+
+impl TestObject for TestJayScreencast {}
@@ -0,0 +1,37 @@
+use {
+    crate::{
+        it::{test_error::TestError, test_object::TestObject, testrun::ParseFull},
+        utils::buffd::MsgParser,
+        wire::{ext_session_lock_v1::*, ExtSessionLockV1Id},
+    },
+    std::cell::Cell,
+};
+
+pub struct TestSessionLock {
+    pub id: ExtSessionLockV1Id,
+    pub locked: Cell<bool>,
+    pub finished: Cell<bool>,
+}
+
+impl TestSessionLock {
+    fn handle_locked(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Locked::parse_full(parser)?;
+        self.locked.set(true);
+        Ok(())
+    }
+
+    fn handle_finished(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Finished::parse_full(parser)?;
+        self.finished.set(true);
+        Ok(())
+    }
+}
+
+test_object! {
+    TestSessionLock, ExtSessionLockV1;
+
+    LOCKED => handle_locked,
+    FINISHED => handle_finished,
+}
+
+impl TestObject for TestSessionLock {}
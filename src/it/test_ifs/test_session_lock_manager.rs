@@ -0,0 +1,44 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult, test_ifs::test_session_lock::TestSessionLock,
+            test_object::TestObject, test_transport::TestTransport,
+        },
+        wire::{ext_session_lock_manager_v1::*, ExtSessionLockManagerV1Id},
+    },
+    std::rc::Rc,
+};
+
+pub struct TestSessionLockManager {
+    pub id: ExtSessionLockManagerV1Id,
+    pub tran: Rc<TestTransport>,
+}
+
+impl TestSessionLockManager {
+    pub fn new(tran: &Rc<TestTransport>) -> Self {
+        Self {
+            id: tran.id(),
+            tran: tran.clone(),
+        }
+    }
+
+    pub fn lock(&self) -> TestResult<Rc<TestSessionLock>> {
+        let obj = Rc::new(TestSessionLock {
+            id: self.tran.id(),
+            locked: Default::default(),
+            finished: Default::default(),
+        });
+        self.tran.add_obj(obj.clone())?;
+        self.tran.send(Lock {
+            self_id: self.id,
+            id: obj.id,
+        })?;
+        Ok(obj)
+    }
+}
+
+test_object! {
+    TestSessionLockManager, ExtSessionLockManagerV1;
+}
+
+impl TestObject for TestSessionLockManager {}
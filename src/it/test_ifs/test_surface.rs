@@ -3,7 +3,7 @@ use {
         ifs::wl_surface::WlSurface,
         it::{
             test_error::{TestError, TestResult},
-            test_ifs::test_region::TestRegion,
+            test_ifs::{test_callback::TestCallback, test_region::TestRegion},
             test_object::TestObject,
             test_transport::TestTransport,
             test_utils::test_expected_event::TEEH,
@@ -59,6 +59,21 @@ impl TestSurface {
         Ok(())
     }
 
+    pub fn frame(&self) -> Result<Rc<TestCallback>, TestError> {
+        let cb = Rc::new(TestCallback {
+            id: self.tran.id(),
+            _tran: self.tran.clone(),
+            handler: Cell::new(None),
+            done: Cell::new(false),
+        });
+        self.tran.send(Frame {
+            self_id: self.id,
+            callback: cb.id,
+        })?;
+        self.tran.add_obj(cb.clone())?;
+        Ok(cb)
+    }
+
     pub fn commit(&self) -> Result<(), TestError> {
         self.tran.send(Commit { self_id: self.id })?;
         Ok(())
@@ -74,6 +74,7 @@ impl TestTransport {
             input_method_manager: Default::default(),
             text_input_manager: Default::default(),
             wl_fixes: Default::default(),
+            session_lock_manager: Default::default(),
             seats: Default::default(),
         });
         self.send(wl_display::GetRegistry {
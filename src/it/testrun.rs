@@ -1,8 +1,9 @@
 use {
     crate::{
+        backend::{BackendEvent, ConnectorEvent, ConnectorKernelId, Mode, MonitorInfo},
         client::{ClientId, RequestParser},
         fixed::Fixed,
-        ifs::wl_seat::WlSeatGlobal,
+        ifs::{wl_output::OutputId, wl_seat::WlSeatGlobal},
         it::{
             test_backend::{TestBackend, TestBackendKb, TestBackendMouse, TestConnector},
             test_client::TestClient,
@@ -15,6 +16,7 @@ use {
         state::State,
         tree::OutputNode,
         utils::{bitfield::Bitfield, buffd::MsgParser, oserror::OsErrorExt, stack::Stack},
+        video::drm::ConnectorType,
     },
     std::{
         cell::{Cell, RefCell},
@@ -133,6 +135,50 @@ impl TestRun {
         })
     }
 
+    pub async fn add_connector(&self, model: &str, width: i32, height: i32) -> Rc<TestConnector> {
+        let id = self.state.connector_ids.next();
+        let connector = Rc::new(TestConnector {
+            id,
+            kernel_id: ConnectorKernelId {
+                ty: ConnectorType::VGA,
+                idx: id.raw(),
+            },
+            events: Default::default(),
+            feedback: Default::default(),
+            gamma_lut: Default::default(),
+            mirror_source: Default::default(),
+        });
+        let monitor_info = MonitorInfo {
+            modes: vec![],
+            output_id: Rc::new(OutputId {
+                connector: None,
+                manufacturer: "jay".to_string(),
+                model: model.to_string(),
+                serial_number: "".to_string(),
+            }),
+            initial_mode: Mode {
+                width,
+                height,
+                refresh_rate_millihz: 60000,
+            },
+            width_mm: 0,
+            height_mm: 0,
+            non_desktop: false,
+            vrr_capable: false,
+            hdr_capable: false,
+            edid: None,
+            properties: Default::default(),
+        };
+        self.state
+            .backend_events
+            .push(BackendEvent::NewConnector(connector.clone()));
+        connector
+            .events
+            .send_event(ConnectorEvent::Connected(monitor_info));
+        self.state.eng.yield_now().await;
+        connector
+    }
+
     pub async fn sync(&self) {
         self.state.eng.yield_now().await;
     }
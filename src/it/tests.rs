@@ -76,6 +76,21 @@ mod t0041_input_method;
 mod t0042_toplevel_select;
 mod t0043_destroy_registry;
 mod t0044_stacked_focus;
+mod t0045_client_disconnect;
+mod t0046_empty_workspace_behavior;
+mod t0047_float_scale_drag;
+mod t0048_autotiling;
+mod t0049_night_light;
+mod t0050_custom_layout;
+mod t0051_output_mirror;
+mod t0052_workspace_swipe;
+mod t0053_output_removal;
+mod t0054_master_stack;
+mod t0055_workspace_rename;
+mod t0056_workspace_back_and_forth;
+mod t0057_move_workspace_to_output;
+mod t0058_marks;
+mod t0059_gaps;
 
 pub trait TestCase: Sync {
     fn name(&self) -> &'static str;
@@ -139,5 +154,20 @@ pub fn tests() -> Vec<&'static dyn TestCase> {
         t0042_toplevel_select,
         t0043_destroy_registry,
         t0044_stacked_focus,
+        t0045_client_disconnect,
+        t0046_empty_workspace_behavior,
+        t0047_float_scale_drag,
+        t0048_autotiling,
+        t0049_night_light,
+        t0050_custom_layout,
+        t0051_output_mirror,
+        t0052_workspace_swipe,
+        t0053_output_removal,
+        t0054_master_stack,
+        t0055_workspace_rename,
+        t0056_workspace_back_and_forth,
+        t0057_move_workspace_to_output,
+        t0058_marks,
+        t0059_gaps,
     }
 }
@@ -30,6 +30,8 @@ async fn test(run: Rc<TestRun>) -> TestResult {
         },
         events: Default::default(),
         feedback: Default::default(),
+        gamma_lut: Default::default(),
+        mirror_source: Default::default(),
     });
     let new_monitor_info = MonitorInfo {
         modes: vec![],
@@ -48,6 +50,9 @@ async fn test(run: Rc<TestRun>) -> TestResult {
         height_mm: 0,
         non_desktop: false,
         vrr_capable: false,
+        hdr_capable: false,
+        edid: None,
+        properties: Default::default(),
     };
     run.backend
         .state
@@ -0,0 +1,121 @@
+use {
+    crate::{
+        ifs::wl_seat::BTN_LEFT,
+        it::{test_error::TestResult, test_ifs::test_output::TestOutput, testrun::TestRun},
+        tree::Node,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let observer = run.create_client().await?;
+    let win = observer.create_window().await?;
+    win.map2().await?;
+    let seat = observer.get_default_seat().await?;
+
+    // Kill a client while it holds an implicit pointer grab.
+    {
+        let client = run.create_client().await?;
+        let win1 = client.create_window().await?;
+        win1.map2().await?;
+
+        let (x, y) = win1.tl.server.node_absolute_position().center();
+        ds.move_to(x, y);
+        let click = ds.mouse.click(BTN_LEFT);
+        observer.sync().await;
+
+        let enter = seat.pointer.enter.expect()?;
+        client.tran.kill();
+        run.state.clients.kill(client.server.id);
+        observer.sync().await;
+
+        let (x, y) = win.tl.server.node_absolute_position().center();
+        ds.move_to(x, y);
+        observer.sync().await;
+        tassert_eq!(enter.last()?.surface, win.surface.id);
+        drop(click);
+    }
+
+    // Kill a client in the middle of a drag-and-drop operation.
+    {
+        let client = run.create_client().await?;
+        let win1 = client.create_window().await?;
+        win1.map2().await?;
+        let seat1 = client.get_default_seat().await?;
+        let button = seat1.pointer.button.expect()?;
+
+        let (x, y) = win1.tl.server.node_absolute_position().center();
+        ds.move_to(x, y);
+        let click = ds.mouse.click(BTN_LEFT);
+        client.sync().await;
+
+        let dev = client.data_device_manager.get_data_device(&seat1.seat)?;
+        let src = client.data_device_manager.create_data_source()?;
+        src.set_actions(1)?;
+        dev.start_drag(&src, &win1.surface, None, button.next()?.serial)?;
+        client.sync().await;
+
+        let enter = seat.pointer.enter.expect()?;
+        client.tran.kill();
+        run.state.clients.kill(client.server.id);
+        observer.sync().await;
+
+        let (x, y) = win.tl.server.node_absolute_position().center();
+        ds.move_to(x, y);
+        observer.sync().await;
+        tassert_eq!(enter.last()?.surface, win.surface.id);
+        drop(click);
+        observer.sync().await;
+    }
+
+    // Kill a client while it captures an output.
+    {
+        let client = run.create_client().await?;
+        let output = Rc::new(TestOutput::new(&client.tran));
+        client
+            .registry
+            .bind(&output, ds.output.global.name.raw(), 4)?;
+        let jo = client.jc.get_output(&output)?;
+        let sc = client.jc.create_screencast()?;
+        sc.set_output(&jo)?;
+        sc.set_running(true)?;
+        sc.configure()?;
+        client.sync().await;
+        tassert!(ds.output.screencasts.is_not_empty());
+
+        client.tran.kill();
+        run.state.clients.kill(client.server.id);
+        observer.sync().await;
+        tassert!(ds.output.screencasts.is_empty());
+    }
+
+    // The seat must behave normally afterwards.
+    let button = seat.pointer.button.expect()?;
+    let click = ds.mouse.click(BTN_LEFT);
+    observer.sync().await;
+    tassert!(button.next().is_ok());
+    drop(click);
+
+    // Kill a client while it holds the session lock.
+    {
+        let client = run.create_client().await?;
+        let manager = client.registry.get_session_lock_manager().await?;
+        let lock = manager.lock()?;
+        client.sync().await;
+        tassert!(lock.locked.get());
+        tassert!(run.state.lock.locked.get());
+
+        client.tran.kill();
+        run.state.clients.kill(client.server.id);
+        observer.sync().await;
+        tassert!(run.state.lock.locked.get());
+        tassert!(run.state.lock.fallback.is_active());
+        run.state.do_unlock();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::EmptyWorkspaceBehavior,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let client = run.create_client().await?;
+
+    run.cfg.show_workspace(ds.seat.id(), "1")?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+
+    run.cfg.show_workspace(ds.seat.id(), "2")?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    // The empty workspace is kept by default.
+    win2.tl.core.destroy()?;
+    client.sync().await;
+    tassert_eq!(run.state.workspaces.len(), 2);
+    tassert_eq!(
+        ds.output.workspace.get().unwrap().name.borrow().as_str(),
+        "2"
+    );
+
+    run.cfg
+        .set_empty_workspace_behavior(EmptyWorkspaceBehavior::ShowPrevious)?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    win2.tl.core.destroy()?;
+    client.sync().await;
+    tassert_eq!(run.state.workspaces.len(), 1);
+    tassert_eq!(
+        ds.output.workspace.get().unwrap().name.borrow().as_str(),
+        "1"
+    );
+
+    run.cfg
+        .set_empty_workspace_behavior(EmptyWorkspaceBehavior::Destroy)?;
+    run.cfg.show_workspace(ds.seat.id(), "3")?;
+    let win3 = client.create_window().await?;
+    win3.map2().await?;
+    win3.tl.core.destroy()?;
+    client.sync().await;
+    tassert_eq!(run.state.workspaces.len(), 1);
+    tassert!(ds.output.workspace.is_none());
+    tassert!(run.state.workspaces.get("1").is_some());
+
+    Ok(())
+}
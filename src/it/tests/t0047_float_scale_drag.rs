@@ -0,0 +1,53 @@
+use {
+    crate::{
+        ifs::wl_seat::BTN_LEFT,
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let new_connector = run.add_connector("jay second connector", 800, 600).await;
+    let Some(new_output) = run.state.root.outputs.get(&new_connector.id) else {
+        bail!("new output was not created");
+    };
+    run.cfg.set_scale(&new_output, 2.0)?;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    run.cfg.set_floating(ds.seat.id(), true)?;
+    client.sync().await;
+    let width = win1.tl.core.width.get();
+    let height = win1.tl.core.height.get();
+
+    let (x, y) = win1.tl.server.node_absolute_position().position();
+    ds.move_to(x + 10, y - 3);
+    let click = ds.mouse.click(BTN_LEFT);
+    let (x, y) = new_output.global.pos.get().position();
+    ds.move_to(x + 20, y + 20);
+    client.sync().await;
+
+    let float = win1.tl.float_parent()?;
+    tassert_eq!(float.workspace.get().output.get().id, new_output.id);
+    tassert_eq!(win1.surface.server.get_output().id, new_output.id);
+    tassert_eq!(
+        win1.tl.core.width.get(),
+        (width as f64 / 2.0).round() as i32
+    );
+    tassert_eq!(
+        win1.tl.core.height.get(),
+        (height as f64 / 2.0).round() as i32
+    );
+
+    drop(click);
+    client.sync().await;
+    tassert_eq!(float.workspace.get().output.get().id, new_output.id);
+
+    Ok(())
+}
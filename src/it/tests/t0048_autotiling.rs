@@ -0,0 +1,37 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ContainerSplit,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    run.cfg.set_autotiling(true)?;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    let root = win1.tl.container_parent()?;
+    tassert_eq!(root.split.get(), ContainerSplit::Horizontal);
+    tassert_eq!(win2.tl.container_parent()?.id, root.id);
+
+    let win3 = client.create_window().await?;
+    win3.map2().await?;
+
+    let inner = win3.tl.container_parent()?;
+    tassert!(inner.id != root.id);
+    tassert_eq!(inner.split.get(), ContainerSplit::Vertical);
+    tassert_eq!(win2.tl.container_parent()?.id, inner.id);
+    tassert_eq!(win1.tl.container_parent()?.id, root.id);
+    tassert_eq!(root.num_children(), 2);
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::video::NightLightSchedule,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    tassert!(ds.connector.gamma_lut.get().is_none());
+
+    run.cfg.set_night_light_temperature(3000)?;
+    run.cfg
+        .set_night_light_schedule(NightLightSchedule::Always)?;
+    run.sync().await;
+
+    let Some(lut) = ds.connector.gamma_lut.get() else {
+        bail!("The night light did not set a gamma ramp");
+    };
+    tassert_eq!(lut.red.len(), 256);
+    tassert_eq!(lut.red[255], u16::MAX);
+    tassert!(lut.green[255] < lut.red[255]);
+    tassert!(lut.blue[255] < lut.green[255]);
+
+    run.cfg
+        .set_night_light_schedule(NightLightSchedule::Disabled)?;
+    run.sync().await;
+    tassert!(ds.connector.gamma_lut.get().is_none());
+
+    Ok(())
+}
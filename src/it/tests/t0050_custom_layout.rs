@@ -0,0 +1,45 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    jay_config::layout::{Layout, LayoutGeometry, LayoutRequest},
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    // Stacks all windows vertically.
+    run.cfg.layout.set(Some(Rc::new(|req: &LayoutRequest| {
+        let n = req.windows.len() as i32;
+        let height = req.height / n;
+        (0..n)
+            .map(|i| LayoutGeometry::new(0, i * height, req.width, height))
+            .collect()
+    })));
+    run.cfg.set_workspace_layout("", Some(Layout(1)))?;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    let ws = ds.output.workspace_rect.get();
+    let rect1 = win1.tl.server.node_absolute_position();
+    let rect2 = win2.tl.server.node_absolute_position();
+    tassert_eq!(rect1.x1(), ws.x1());
+    tassert_eq!(rect1.width(), ws.width());
+    tassert_eq!(rect2.width(), ws.width());
+    tassert!(rect2.y1() > rect1.y2());
+
+    run.cfg.set_workspace_layout("", None)?;
+    client.sync().await;
+    let rect2 = win2.tl.server.node_absolute_position();
+    tassert!(rect2.width() < ws.width());
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let source = ds.connector.id;
+
+    let mirror = run.add_connector("jay mirror connector", 800, 600).await;
+    tassert!(run.state.root.outputs.contains(&mirror.id));
+
+    run.cfg.set_mirror(mirror.id, Some(source))?;
+    run.state.eng.yield_now().await;
+    tassert!(!run.state.root.outputs.contains(&mirror.id));
+    let Some(output) = run.state.outputs.get(&mirror.id) else {
+        bail!("mirror is not connected");
+    };
+    tassert!(output.node.is_none());
+    tassert_eq!(mirror.mirror_source.get(), Some(source));
+
+    // Mirrors cannot be mirrored.
+    run.cfg.set_mirror(source, Some(mirror.id))?;
+    let Some(source_data) = run.state.connectors.get(&source) else {
+        bail!("source does not exist");
+    };
+    tassert_eq!(source_data.mirror_source.get(), None);
+
+    run.cfg.set_mirror(mirror.id, None)?;
+    run.state.eng.yield_now().await;
+    tassert!(run.state.root.outputs.contains(&mirror.id));
+    tassert_eq!(mirror.mirror_source.get(), None);
+
+    Ok(())
+}
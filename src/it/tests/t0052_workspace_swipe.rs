@@ -0,0 +1,74 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let client = run.create_client().await?;
+
+    run.cfg.show_workspace(ds.seat.id(), "1")?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+
+    run.cfg.show_workspace(ds.seat.id(), "2")?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    // Workspace swipes are disabled by default.
+    ds.mouse.swipe(3, 500.0, 0.0);
+    tassert_eq!(ds.output.workspace_swipe_offset.get(), 0);
+    ds.mouse.swipe_end(false);
+    tassert_eq!(
+        ds.output.workspace.get().unwrap().name.borrow().as_str(),
+        "2"
+    );
+
+    run.cfg.set_workspace_swipe_fingers(ds.seat.id(), 3)?;
+
+    // Swipes with a different number of fingers are ignored.
+    ds.mouse.swipe(4, 500.0, 0.0);
+    tassert_eq!(ds.output.workspace_swipe_offset.get(), 0);
+    ds.mouse.swipe_end(false);
+
+    // There is no workspace after the last one.
+    ds.mouse.swipe(3, -500.0, 0.0);
+    tassert_eq!(ds.output.workspace_swipe_offset.get(), 0);
+    ds.mouse.swipe_end(false);
+    tassert_eq!(
+        ds.output.workspace.get().unwrap().name.borrow().as_str(),
+        "2"
+    );
+
+    // Short swipes snap back.
+    ds.mouse.swipe(3, 100.0, 0.0);
+    tassert_eq!(ds.output.workspace_swipe_offset.get(), 100);
+    ds.mouse.swipe_end(false);
+    tassert_eq!(ds.output.workspace_swipe_offset.get(), 0);
+    tassert_eq!(
+        ds.output.workspace.get().unwrap().name.borrow().as_str(),
+        "2"
+    );
+
+    // Cancelled swipes snap back.
+    ds.mouse.swipe(3, 500.0, 0.0);
+    ds.mouse.swipe_end(true);
+    tassert_eq!(
+        ds.output.workspace.get().unwrap().name.borrow().as_str(),
+        "2"
+    );
+
+    // The offset is limited to the width of the output.
+    ds.mouse.swipe(3, 5000.0, 0.0);
+    tassert_eq!(ds.output.workspace_swipe_offset.get(), 800);
+    ds.mouse.swipe_end(false);
+    tassert_eq!(ds.output.workspace_swipe_offset.get(), 0);
+    tassert_eq!(
+        ds.output.workspace.get().unwrap().name.borrow().as_str(),
+        "1"
+    );
+
+    Ok(())
+}
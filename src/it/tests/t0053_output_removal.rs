@@ -0,0 +1,49 @@
+use {
+    crate::{
+        backend::ConnectorEvent,
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that an output can be removed while a frame is in flight.
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let new_connector = run.add_connector("jay second connector", 400, 400).await;
+
+    let client = run.create_client().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    let surface = &win.surface.server;
+    tassert_eq!(surface.get_output().id, ds.output.id);
+    let workspace = ds.output.workspace.get().unwrap();
+
+    let frame = win.surface.frame()?;
+    win.surface.commit()?;
+    client.sync().await;
+    ds.output.latched(false);
+
+    ds.connector.events.send_event(ConnectorEvent::Disconnected);
+    ds.connector.events.send_event(ConnectorEvent::Removed);
+    run.state.eng.yield_now().await;
+
+    tassert!(run.state.connectors.get(&ds.connector.id).is_none());
+    tassert!(run.state.outputs.get(&ds.connector.id).is_none());
+    tassert!(ds.output.workspaces.is_empty());
+    tassert_eq!(workspace.output.get().id, surface.get_output().id);
+    tassert_eq!(
+        surface.get_output().global.connector.connector.id(),
+        new_connector.id
+    );
+
+    client.sync().await;
+    tassert!(!frame.done.get());
+    run.state.vblank(new_connector.id);
+    client.sync().await;
+    tassert!(frame.done.get());
+
+    Ok(())
+}
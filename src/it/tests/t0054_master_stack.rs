@@ -0,0 +1,60 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    jay_config::layout::{Layout, LayoutRequest, MasterStackParams},
+    std::{cell::Cell, rc::Rc},
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let params = Rc::new(Cell::new(MasterStackParams {
+        master_ratio: 0.5,
+        master_count: 1,
+    }));
+    {
+        let params = params.clone();
+        run.cfg.layout.set(Some(Rc::new(move |req: &LayoutRequest| {
+            params.get().geometries(req, 0)
+        })));
+    }
+    run.cfg.set_workspace_layout("", Some(Layout(1)))?;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    let win3 = client.create_window().await?;
+    win3.map2().await?;
+
+    let ws = ds.output.workspace_rect.get();
+    let rect1 = win1.tl.server.node_absolute_position();
+    let rect2 = win2.tl.server.node_absolute_position();
+    let rect3 = win3.tl.server.node_absolute_position();
+    tassert_eq!(rect1.x1(), ws.x1());
+    tassert_eq!(rect1.y2(), ws.y2());
+    tassert_eq!(rect2.x1(), rect3.x1());
+    tassert!(rect2.x1() >= rect1.x2());
+    tassert!(rect3.y1() > rect2.y2());
+
+    params.set(MasterStackParams {
+        master_ratio: 0.5,
+        master_count: 2,
+    });
+    run.cfg.relayout(Layout(1))?;
+    client.sync().await;
+    let rect1 = win1.tl.server.node_absolute_position();
+    let rect2 = win2.tl.server.node_absolute_position();
+    let rect3 = win3.tl.server.node_absolute_position();
+    tassert_eq!(rect1.x1(), rect2.x1());
+    tassert!(rect2.y1() > rect1.y2());
+    tassert!(rect3.x1() >= rect1.x2());
+    tassert_eq!(rect3.y2(), ws.y2());
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let client = run.create_client().await?;
+
+    run.cfg.show_workspace(ds.seat.id(), "1")?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+
+    run.cfg.rename_workspace("1", "web")?;
+    let ws = ds.output.workspace.get().unwrap();
+    tassert_eq!(ws.name.borrow().as_str(), "web");
+    tassert!(run.state.workspaces.contains("web"));
+    tassert!(!run.state.workspaces.contains("1"));
+
+    run.cfg.show_workspace(ds.seat.id(), "2")?;
+    run.cfg.show_workspace(ds.seat.id(), "web")?;
+    tassert_eq!(ds.output.workspace.get().unwrap().id, ws.id);
+
+    Ok(())
+}
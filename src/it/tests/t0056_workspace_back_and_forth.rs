@@ -0,0 +1,43 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let client = run.create_client().await?;
+
+    let name = || {
+        ds.output
+            .workspace
+            .get()
+            .map(|ws| ws.name.borrow().clone())
+            .unwrap_or_default()
+    };
+
+    run.cfg.show_workspace(ds.seat.id(), "1")?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+
+    run.cfg.show_workspace(ds.seat.id(), "2")?;
+    tassert_eq!(name(), "2");
+
+    run.cfg.show_previous_workspace(ds.seat.id())?;
+    tassert_eq!(name(), "1");
+
+    run.cfg.show_previous_workspace(ds.seat.id())?;
+    tassert_eq!(name(), "2");
+
+    run.cfg.show_workspace(ds.seat.id(), "2")?;
+    tassert_eq!(name(), "2");
+
+    run.cfg.set_workspace_back_and_forth(true)?;
+    run.cfg.show_workspace(ds.seat.id(), "2")?;
+    tassert_eq!(name(), "1");
+    run.cfg.show_workspace(ds.seat.id(), "1")?;
+    tassert_eq!(name(), "2");
+
+    Ok(())
+}
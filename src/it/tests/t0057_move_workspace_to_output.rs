@@ -0,0 +1,43 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let new_connector = run.add_connector("jay second connector", 800, 600).await;
+    let Some(new_output) = run.state.root.outputs.get(&new_connector.id) else {
+        bail!("new output was not created");
+    };
+
+    run.cfg.show_workspace(ds.seat.id(), "1")?;
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    run.cfg.set_floating(ds.seat.id(), true)?;
+    client.sync().await;
+    let float = win1.tl.float_parent()?;
+    let old_area = ds.output.workspace_rect.get();
+    let old_pos = float.position.get();
+
+    run.cfg.move_workspace_to_output("1", &new_output)?;
+    client.sync().await;
+
+    let ws = float.workspace.get();
+    tassert_eq!(ws.name.borrow().as_str(), "1");
+    tassert_eq!(ws.output.get().id, new_output.id);
+    tassert_eq!(win1.surface.server.get_output().id, new_output.id);
+    let new_area = new_output.workspace_rect.get();
+    let new_pos = float.position.get();
+    tassert_eq!(new_pos.size(), old_pos.size());
+    tassert_eq!(
+        (new_pos.x1() - new_area.x1(), new_pos.y1() - new_area.y1()),
+        (old_pos.x1() - old_area.x1(), old_pos.y1() - old_area.y1())
+    );
+    tassert!(new_area.contains_rect(&new_pos));
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use {
+    crate::{
+        it::{test_error::TestResult, test_utils::test_window::TestWindow, testrun::TestRun},
+        tree::{Node, ToplevelNodeBase},
+    },
+    jay_config::Direction,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let client = run.create_client().await?;
+
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    let marks = |win: &TestWindow| win.tl.server.tl_data().marks.borrow().clone();
+
+    run.cfg.focus(ds.seat.id(), Direction::Left)?;
+    run.cfg.mark(ds.seat.id(), "a")?;
+    tassert_eq!(marks(&win1), ["a"]);
+    let Some(marked) = run.state.marked_toplevel("a") else {
+        bail!("mark was not set");
+    };
+    tassert_eq!(marked.node_id(), win1.tl.server.node_id);
+
+    run.cfg.set_show_marks(true)?;
+    let Some(container) = ds.output.workspace.get().unwrap().container.get() else {
+        bail!("workspace has no container");
+    };
+    tassert!(container.tl_data().title.borrow().contains("[a] "));
+
+    run.cfg.focus(ds.seat.id(), Direction::Right)?;
+    let rect1 = win1.tl.server.node_absolute_position();
+    let rect2 = win2.tl.server.node_absolute_position();
+    run.cfg.swap_with_mark(ds.seat.id(), "a")?;
+    client.sync().await;
+    tassert_eq!(win1.tl.server.node_absolute_position(), rect2);
+    tassert_eq!(win2.tl.server.node_absolute_position(), rect1);
+
+    run.cfg.focus_mark(ds.seat.id(), "a")?;
+    run.cfg.mark(ds.seat.id(), "b")?;
+    tassert_eq!(marks(&win1), ["a", "b"]);
+    tassert_eq!(marks(&win2), Vec::<String>::new());
+
+    run.cfg.unmark(ds.seat.id())?;
+    tassert_eq!(marks(&win1), Vec::<String>::new());
+    tassert!(run.state.marked_toplevel("a").is_none());
+    tassert!(!container.tl_data().title.borrow().contains("[a] "));
+
+    Ok(())
+}
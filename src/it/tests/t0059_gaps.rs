@@ -0,0 +1,54 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    jay_config::theme::sized::{INNER_GAP, OUTER_GAP},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that inner and outer gaps are applied to tiled windows
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let client = run.create_client().await?;
+
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    let bw = run.state.theme.sizes.border_width.get();
+    let ws = ds.output.workspace.get().unwrap();
+    let pos = ws.position.get();
+    let name = ws.name.borrow().clone();
+
+    run.cfg.set_size(INNER_GAP, 10)?;
+    run.cfg.set_size(OUTER_GAP, 20)?;
+    client.sync().await;
+
+    let rect1 = win1.tl.server.node_absolute_position();
+    let rect2 = win2.tl.server.node_absolute_position();
+    tassert_eq!(rect1.x1(), pos.x1() + 20);
+    tassert_eq!(rect1.y2(), pos.y2() - 20);
+    tassert_eq!(rect2.x2(), pos.x2() - 20);
+    tassert_eq!(rect2.x1() - rect1.x2(), bw + 10);
+
+    run.cfg.set_workspace_inner_gap(&name, Some(0))?;
+    client.sync().await;
+
+    let rect1 = win1.tl.server.node_absolute_position();
+    let rect2 = win2.tl.server.node_absolute_position();
+    tassert_eq!(rect1.x1(), pos.x1() + 20);
+    tassert_eq!(rect2.x1() - rect1.x2(), bw);
+
+    run.cfg.set_workspace_inner_gap(&name, None)?;
+    client.sync().await;
+
+    let rect1 = win1.tl.server.node_absolute_position();
+    let rect2 = win2.tl.server.node_absolute_position();
+    tassert_eq!(rect2.x1() - rect1.x2(), bw + 10);
+
+    Ok(())
+}
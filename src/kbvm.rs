@@ -1,6 +1,6 @@
 use {
     crate::{
-        backend::KeyState,
+        backend::{KeyState, KeyboardLeds, LED_CAPS_LOCK, LED_NUM_LOCK, LED_SCROLL_LOCK},
         ifs::wl_seat::WlSeatGlobal,
         keyboard::{DynKeyboardState, KeyboardState, KeyboardStateId, KeymapFd},
         utils::{oserror::OsError, syncqueue::SyncQueue, vecset::VecSet},
@@ -11,9 +11,11 @@ use {
         xkb::{
             self,
             diagnostic::{Diagnostic, WriteToLog},
+            keymap::{Indicator, IndicatorMatcher},
+            rmlvo::Group,
             Keymap,
         },
-        Keycode,
+        Components, Keycode,
     },
     std::{
         cell::{Cell, Ref, RefCell},
@@ -56,6 +58,7 @@ pub struct KbvmMap {
     pub lookup_table: LookupTable,
     pub map: KeymapFd,
     pub xwayland_map: KeymapFd,
+    pub leds: Vec<(KeyboardLeds, IndicatorMatcher)>,
 }
 
 pub struct KbvmState {
@@ -89,13 +92,60 @@ impl KbvmContext {
             .ctx
             .keymap_from_bytes(WriteToLog, None, keymap)
             .map_err(KbvmError::CouldNotParseKeymap)?;
+        self.create_map(&map)
+    }
+
+    /// Creates a keymap from RMLVO names.
+    ///
+    /// Layouts, variants, and options are comma-separated lists. Names that are not
+    /// given are taken from the environment or use the libxkbcommon defaults.
+    pub fn keymap_from_names(
+        &self,
+        rules: Option<&str>,
+        model: Option<&str>,
+        layout: Option<&str>,
+        variant: Option<&str>,
+        options: Option<&str>,
+    ) -> Result<Rc<KbvmMap>, KbvmError> {
+        let groups: Option<Vec<_>> = layout.map(|layout| {
+            Group::from_layouts_and_variants(layout, variant.unwrap_or_default()).collect()
+        });
+        let options: Option<Vec<_>> = options.map(|options| {
+            options
+                .split(',')
+                .map(|o| o.trim())
+                .filter(|o| !o.is_empty())
+                .collect()
+        });
+        let map = self.ctx.keymap_from_names(
+            WriteToLog,
+            rules,
+            model,
+            groups.as_deref(),
+            options.as_deref(),
+        );
+        self.create_map(&map)
+    }
+
+    fn create_map(&self, map: &Keymap) -> Result<Rc<KbvmMap>, KbvmError> {
         let builder = map.to_builder();
+        let mut leds = vec![];
+        for indicator in map.indicators() {
+            let led = match indicator.name() {
+                Indicator::NUM_LOCK => LED_NUM_LOCK,
+                Indicator::CAPS_LOCK => LED_CAPS_LOCK,
+                Indicator::SCROLL_LOCK => LED_SCROLL_LOCK,
+                _ => continue,
+            };
+            leds.push((led, indicator.matcher()));
+        }
         Ok(Rc::new(KbvmMap {
             id: self.ids.next(),
             state_machine: builder.build_state_machine(),
-            map: create_keymap_memfd(&map, false).map_err(KbvmError::KeymapMemfd)?,
-            xwayland_map: create_keymap_memfd(&map, true).map_err(KbvmError::KeymapMemfd)?,
+            map: create_keymap_memfd(map, false).map_err(KbvmError::KeymapMemfd)?,
+            xwayland_map: create_keymap_memfd(map, true).map_err(KbvmError::KeymapMemfd)?,
             lookup_table: builder.build_lookup_table(),
+            leds,
         }))
     }
 }
@@ -121,6 +171,16 @@ fn create_keymap_memfd(map: &Keymap, xwayland: bool) -> Result<KeymapFd, OsError
 }
 
 impl KbvmMap {
+    pub fn leds(&self, components: &Components) -> KeyboardLeds {
+        let mut leds = KeyboardLeds::none();
+        for (led, matcher) in &self.leds {
+            if matcher.matches(components) {
+                leds |= *led;
+            }
+        }
+        leds
+    }
+
     pub fn state(self: &Rc<Self>, id: KeyboardStateId) -> KbvmState {
         KbvmState {
             map: self.clone(),
@@ -164,6 +224,34 @@ impl PhysicalKeyboardState {
         }
     }
 
+    /// Creates a state for a new keymap in which the keys that are currently pressed are
+    /// still pressed.
+    ///
+    /// No events are sent to clients. They receive the pressed keys when the new state is
+    /// entered.
+    pub fn transfer(&self, state: &Rc<RefCell<KbvmState>>) -> Self {
+        let slf = Self::new(state);
+        {
+            let old = self.inner.borrow();
+            let inner = &mut *slf.inner.borrow_mut();
+            let state = &mut *state.borrow_mut();
+            for &key in old.pressed_keys.iter() {
+                if !inner.pressed_keys.insert(key) {
+                    continue;
+                }
+                state.map.state_machine.handle_key(
+                    &mut state.state,
+                    &mut inner.event_stash,
+                    Keycode::from_evdev(key),
+                    Direction::Down,
+                );
+            }
+            slf.events.append(&mut inner.event_stash);
+            state.apply_events(&slf.events);
+        }
+        slf
+    }
+
     fn flush(&self, time_usec: u64, seat: &Rc<WlSeatGlobal>) {
         if self.flushing.replace(true) {
             return;
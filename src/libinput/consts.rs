@@ -187,3 +187,32 @@ cenum! {
     LIBINPUT_CONFIG_DRAG_LOCK_DISABLED = 0,
     LIBINPUT_CONFIG_DRAG_LOCK_ENABLED = 1,
 }
+
+cenum! {
+    ConfigDwtState, LIBINPUT_CONFIG_DWT_STATE;
+
+    LIBINPUT_CONFIG_DWT_DISABLED = 0,
+    LIBINPUT_CONFIG_DWT_ENABLED = 1,
+}
+
+cenum! {
+    ConfigDwtpState, LIBINPUT_CONFIG_DWTP_STATE;
+
+    LIBINPUT_CONFIG_DWTP_DISABLED = 0,
+    LIBINPUT_CONFIG_DWTP_ENABLED = 1,
+}
+
+cenum! {
+    ConfigMiddleEmulationState, LIBINPUT_CONFIG_MIDDLE_EMULATION_STATE;
+
+    LIBINPUT_CONFIG_MIDDLE_EMULATION_DISABLED = 0,
+    LIBINPUT_CONFIG_MIDDLE_EMULATION_ENABLED = 1,
+}
+
+cenum! {
+    ConfigSendEventsMode, LIBINPUT_CONFIG_SEND_EVENTS_MODE;
+
+    LIBINPUT_CONFIG_SEND_EVENTS_ENABLED = 0,
+    LIBINPUT_CONFIG_SEND_EVENTS_DISABLED = 1 << 0,
+    LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE = 1 << 1,
+}
@@ -1,9 +1,14 @@
 use {
     crate::libinput::{
         consts::{
-            AccelProfile, ConfigDragLockState, ConfigDragState, ConfigTapState, DeviceCapability,
+            AccelProfile, ConfigDragLockState, ConfigDragState, ConfigDwtState, ConfigDwtpState,
+            ConfigMiddleEmulationState, ConfigTapState, DeviceCapability,
             LIBINPUT_CONFIG_DRAG_DISABLED, LIBINPUT_CONFIG_DRAG_ENABLED,
             LIBINPUT_CONFIG_DRAG_LOCK_DISABLED, LIBINPUT_CONFIG_DRAG_LOCK_ENABLED,
+            LIBINPUT_CONFIG_DWTP_DISABLED, LIBINPUT_CONFIG_DWTP_ENABLED,
+            LIBINPUT_CONFIG_DWT_DISABLED, LIBINPUT_CONFIG_DWT_ENABLED,
+            LIBINPUT_CONFIG_MIDDLE_EMULATION_DISABLED, LIBINPUT_CONFIG_MIDDLE_EMULATION_ENABLED,
+            LIBINPUT_CONFIG_SEND_EVENTS_DISABLED, LIBINPUT_CONFIG_SEND_EVENTS_ENABLED,
             LIBINPUT_CONFIG_TAP_DISABLED, LIBINPUT_CONFIG_TAP_ENABLED,
         },
         sys::{
@@ -12,12 +17,21 @@ use {
             libinput_device_config_accel_set_profile, libinput_device_config_accel_set_speed,
             libinput_device_config_calibration_get_matrix,
             libinput_device_config_calibration_has_matrix,
-            libinput_device_config_calibration_set_matrix, libinput_device_config_left_handed_get,
+            libinput_device_config_calibration_set_matrix, libinput_device_config_dwt_get_enabled,
+            libinput_device_config_dwt_is_available, libinput_device_config_dwt_set_enabled,
+            libinput_device_config_dwtp_get_enabled, libinput_device_config_dwtp_is_available,
+            libinput_device_config_dwtp_set_enabled, libinput_device_config_left_handed_get,
             libinput_device_config_left_handed_is_available,
             libinput_device_config_left_handed_set,
+            libinput_device_config_middle_emulation_get_enabled,
+            libinput_device_config_middle_emulation_is_available,
+            libinput_device_config_middle_emulation_set_enabled,
             libinput_device_config_scroll_get_natural_scroll_enabled,
             libinput_device_config_scroll_has_natural_scroll,
             libinput_device_config_scroll_set_natural_scroll_enabled,
+            libinput_device_config_send_events_get_mode,
+            libinput_device_config_send_events_get_modes,
+            libinput_device_config_send_events_set_mode,
             libinput_device_config_tap_get_drag_enabled,
             libinput_device_config_tap_get_drag_lock_enabled,
             libinput_device_config_tap_get_enabled, libinput_device_config_tap_get_finger_count,
@@ -27,8 +41,9 @@ use {
             libinput_device_get_id_product, libinput_device_get_id_vendor,
             libinput_device_get_name, libinput_device_get_user_data, libinput_device_group,
             libinput_device_group_get_user_data, libinput_device_group_set_user_data,
-            libinput_device_has_capability, libinput_device_set_user_data,
-            libinput_device_tablet_pad_get_mode_group, libinput_device_tablet_pad_get_num_buttons,
+            libinput_device_has_capability, libinput_device_led_update,
+            libinput_device_set_user_data, libinput_device_tablet_pad_get_mode_group,
+            libinput_device_tablet_pad_get_num_buttons,
             libinput_device_tablet_pad_get_num_mode_groups,
             libinput_device_tablet_pad_get_num_rings, libinput_device_tablet_pad_get_num_strips,
             libinput_device_unref, libinput_path_remove_device, libinput_tablet_pad_mode_group,
@@ -198,6 +213,102 @@ impl<'a> LibInputDevice<'a> {
         }
     }
 
+    pub fn dwt_available(&self) -> bool {
+        unsafe { libinput_device_config_dwt_is_available(self.dev) != 0 }
+    }
+
+    pub fn set_dwt_enabled(&self, enabled: bool) {
+        let enabled = match enabled {
+            true => LIBINPUT_CONFIG_DWT_ENABLED,
+            false => LIBINPUT_CONFIG_DWT_DISABLED,
+        };
+        unsafe {
+            libinput_device_config_dwt_set_enabled(self.dev, enabled.raw() as _);
+        }
+    }
+
+    pub fn dwt_enabled(&self) -> bool {
+        let enabled = unsafe { ConfigDwtState(libinput_device_config_dwt_get_enabled(self.dev)) };
+        match enabled {
+            LIBINPUT_CONFIG_DWT_ENABLED => true,
+            _ => false,
+        }
+    }
+
+    pub fn send_events_disable_available(&self) -> bool {
+        let modes = unsafe { libinput_device_config_send_events_get_modes(self.dev) };
+        modes & LIBINPUT_CONFIG_SEND_EVENTS_DISABLED.raw() as u32 != 0
+    }
+
+    pub fn set_send_events_enabled(&self, enabled: bool) {
+        let mode = match enabled {
+            true => LIBINPUT_CONFIG_SEND_EVENTS_ENABLED,
+            false => LIBINPUT_CONFIG_SEND_EVENTS_DISABLED,
+        };
+        unsafe {
+            libinput_device_config_send_events_set_mode(self.dev, mode.raw() as u32);
+        }
+    }
+
+    pub fn send_events_enabled(&self) -> bool {
+        let mode = unsafe { libinput_device_config_send_events_get_mode(self.dev) };
+        mode & LIBINPUT_CONFIG_SEND_EVENTS_DISABLED.raw() as u32 == 0
+    }
+
+    pub fn led_update(&self, leds: u32) {
+        unsafe {
+            libinput_device_led_update(self.dev, leds as _);
+        }
+    }
+
+    pub fn dwtp_available(&self) -> bool {
+        unsafe { libinput_device_config_dwtp_is_available(self.dev) != 0 }
+    }
+
+    pub fn set_dwtp_enabled(&self, enabled: bool) {
+        let enabled = match enabled {
+            true => LIBINPUT_CONFIG_DWTP_ENABLED,
+            false => LIBINPUT_CONFIG_DWTP_DISABLED,
+        };
+        unsafe {
+            libinput_device_config_dwtp_set_enabled(self.dev, enabled.raw() as _);
+        }
+    }
+
+    pub fn dwtp_enabled(&self) -> bool {
+        let enabled = unsafe { ConfigDwtpState(libinput_device_config_dwtp_get_enabled(self.dev)) };
+        match enabled {
+            LIBINPUT_CONFIG_DWTP_ENABLED => true,
+            _ => false,
+        }
+    }
+
+    pub fn middle_emulation_available(&self) -> bool {
+        unsafe { libinput_device_config_middle_emulation_is_available(self.dev) != 0 }
+    }
+
+    pub fn set_middle_emulation_enabled(&self, enabled: bool) {
+        let enabled = match enabled {
+            true => LIBINPUT_CONFIG_MIDDLE_EMULATION_ENABLED,
+            false => LIBINPUT_CONFIG_MIDDLE_EMULATION_DISABLED,
+        };
+        unsafe {
+            libinput_device_config_middle_emulation_set_enabled(self.dev, enabled.raw() as _);
+        }
+    }
+
+    pub fn middle_emulation_enabled(&self) -> bool {
+        let enabled = unsafe {
+            ConfigMiddleEmulationState(libinput_device_config_middle_emulation_get_enabled(
+                self.dev,
+            ))
+        };
+        match enabled {
+            LIBINPUT_CONFIG_MIDDLE_EMULATION_ENABLED => true,
+            _ => false,
+        }
+    }
+
     pub fn set_natural_scrolling_enabled(&self, enabled: bool) {
         unsafe {
             libinput_device_config_scroll_set_natural_scroll_enabled(self.dev, enabled as _);
@@ -103,6 +103,39 @@ unsafe extern "C" {
     pub fn libinput_device_config_tap_get_drag_lock_enabled(
         device: *mut libinput_device,
     ) -> libinput_config_drag_lock_state;
+    pub fn libinput_device_config_dwt_is_available(device: *mut libinput_device) -> c::c_int;
+    pub fn libinput_device_config_dwt_set_enabled(
+        device: *mut libinput_device,
+        enable: libinput_config_dwt_state,
+    ) -> libinput_config_status;
+    pub fn libinput_device_config_dwt_get_enabled(
+        device: *mut libinput_device,
+    ) -> libinput_config_dwt_state;
+    pub fn libinput_device_config_dwtp_is_available(device: *mut libinput_device) -> c::c_int;
+    pub fn libinput_device_config_dwtp_set_enabled(
+        device: *mut libinput_device,
+        enable: libinput_config_dwtp_state,
+    ) -> libinput_config_status;
+    pub fn libinput_device_config_dwtp_get_enabled(
+        device: *mut libinput_device,
+    ) -> libinput_config_dwtp_state;
+    pub fn libinput_device_config_middle_emulation_is_available(
+        device: *mut libinput_device,
+    ) -> c::c_int;
+    pub fn libinput_device_config_middle_emulation_set_enabled(
+        device: *mut libinput_device,
+        enable: libinput_config_middle_emulation_state,
+    ) -> libinput_config_status;
+    pub fn libinput_device_config_middle_emulation_get_enabled(
+        device: *mut libinput_device,
+    ) -> libinput_config_middle_emulation_state;
+    pub fn libinput_device_config_send_events_get_modes(device: *mut libinput_device) -> u32;
+    pub fn libinput_device_config_send_events_set_mode(
+        device: *mut libinput_device,
+        mode: u32,
+    ) -> libinput_config_status;
+    pub fn libinput_device_config_send_events_get_mode(device: *mut libinput_device) -> u32;
+    pub fn libinput_device_led_update(device: *mut libinput_device, leds: libinput_led);
     pub fn libinput_device_config_scroll_set_natural_scroll_enabled(
         device: *mut libinput_device,
         enable: c::c_int,
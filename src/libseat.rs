@@ -0,0 +1,237 @@
+#![allow(non_camel_case_types)]
+
+use {
+    crate::utils::{errorfmt::ErrorFmt, oserror::OsError},
+    libloading::Library,
+    once_cell::sync::Lazy,
+    std::{
+        cell::{Cell, RefCell},
+        ffi::CStr,
+        mem,
+    },
+    thiserror::Error,
+    uapi::{c, Errno, OwnedFd},
+};
+
+#[repr(transparent)]
+struct libseat(u8);
+
+#[repr(C)]
+struct libseat_seat_listener {
+    enable_seat: unsafe extern "C" fn(seat: *mut libseat, userdata: *mut c::c_void),
+    disable_seat: unsafe extern "C" fn(seat: *mut libseat, userdata: *mut c::c_void),
+}
+
+type OpenSeat = unsafe extern "C" fn(
+    listener: *const libseat_seat_listener,
+    userdata: *mut c::c_void,
+) -> *mut libseat;
+type DisableSeat = unsafe extern "C" fn(seat: *mut libseat) -> c::c_int;
+type CloseSeat = unsafe extern "C" fn(seat: *mut libseat) -> c::c_int;
+type OpenDevice =
+    unsafe extern "C" fn(seat: *mut libseat, path: *const c::c_char, fd: *mut c::c_int) -> c::c_int;
+type CloseDevice = unsafe extern "C" fn(seat: *mut libseat, device_id: c::c_int) -> c::c_int;
+type SwitchSession = unsafe extern "C" fn(seat: *mut libseat, session: c::c_int) -> c::c_int;
+type GetFd = unsafe extern "C" fn(seat: *mut libseat) -> c::c_int;
+type Dispatch = unsafe extern "C" fn(seat: *mut libseat, timeout: c::c_int) -> c::c_int;
+
+struct Lib {
+    _lib: Library,
+    open_seat: OpenSeat,
+    disable_seat: DisableSeat,
+    close_seat: CloseSeat,
+    open_device: OpenDevice,
+    close_device: CloseDevice,
+    switch_session: SwitchSession,
+    get_fd: GetFd,
+    dispatch: Dispatch,
+}
+
+static LIB: Lazy<Option<Lib>> = Lazy::new(|| unsafe {
+    let lib = match Library::new("libseat.so.1") {
+        Ok(l) => l,
+        Err(e) => {
+            log::error!("Could not load libseat: {}", ErrorFmt(e));
+            return None;
+        }
+    };
+    macro_rules! get {
+        ($name:expr) => {
+            match lib.get($name) {
+                Ok(s) => *s,
+                Err(e) => {
+                    log::error!(
+                        "Could not load {} from libseat: {}",
+                        String::from_utf8_lossy($name),
+                        ErrorFmt(e),
+                    );
+                    return None;
+                }
+            }
+        };
+    }
+    Some(Lib {
+        open_seat: get!(b"libseat_open_seat"),
+        disable_seat: get!(b"libseat_disable_seat"),
+        close_seat: get!(b"libseat_close_seat"),
+        open_device: get!(b"libseat_open_device"),
+        close_device: get!(b"libseat_close_device"),
+        switch_session: get!(b"libseat_switch_session"),
+        get_fd: get!(b"libseat_get_fd"),
+        dispatch: get!(b"libseat_dispatch"),
+        _lib: lib,
+    })
+});
+
+#[derive(Debug, Error)]
+pub enum LibSeatError {
+    #[error("libseat is not available")]
+    NotAvailable,
+    #[error("Could not open the seat")]
+    OpenSeat(#[source] OsError),
+    #[error("The seat was not enabled")]
+    NotEnabled,
+    #[error("Could not dispatch libseat events")]
+    Dispatch(#[source] OsError),
+    #[error("Could not open the device")]
+    OpenDevice(#[source] OsError),
+    #[error("Could not switch the session")]
+    SwitchSession(#[source] OsError),
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LibSeatEvent {
+    Enable,
+    Disable,
+}
+
+struct Listener {
+    listener: libseat_seat_listener,
+    active: Cell<bool>,
+    events: RefCell<Vec<LibSeatEvent>>,
+}
+
+/// A seat opened via libseat.
+///
+/// libseat talks to seatd, logind, or the kernel directly, depending on what is
+/// available.
+pub struct LibSeat {
+    lib: &'static Lib,
+    seat: *mut libseat,
+    listener: Box<Listener>,
+}
+
+impl LibSeat {
+    /// Opens the seat and waits until it has been enabled.
+    pub fn open() -> Result<Self, LibSeatError> {
+        let Some(lib) = &*LIB else {
+            return Err(LibSeatError::NotAvailable);
+        };
+        let listener = Box::new(Listener {
+            listener: libseat_seat_listener {
+                enable_seat,
+                disable_seat,
+            },
+            active: Cell::new(false),
+            events: Default::default(),
+        });
+        let seat = unsafe {
+            (lib.open_seat)(
+                &listener.listener,
+                &*listener as *const Listener as *mut c::c_void,
+            )
+        };
+        if seat.is_null() {
+            return Err(LibSeatError::OpenSeat(Errno::default().into()));
+        }
+        let slf = Self {
+            lib,
+            seat,
+            listener,
+        };
+        for _ in 0..50 {
+            if slf.listener.active.get() {
+                break;
+            }
+            slf.dispatch(100)?;
+        }
+        slf.listener.events.borrow_mut().clear();
+        if !slf.listener.active.get() {
+            return Err(LibSeatError::NotEnabled);
+        }
+        Ok(slf)
+    }
+
+    /// Returns the file descriptor that becomes readable when events are available.
+    pub fn fd(&self) -> Result<OwnedFd, OsError> {
+        let fd = unsafe { (self.lib.get_fd)(self.seat) };
+        if fd < 0 {
+            return Err(Errno::default().into());
+        }
+        uapi::fcntl_dupfd_cloexec(fd, 0).map_err(|e| e.into())
+    }
+
+    /// Dispatches pending events and returns the events that were received.
+    pub fn dispatch(&self, timeout: c::c_int) -> Result<Vec<LibSeatEvent>, LibSeatError> {
+        let res = unsafe { (self.lib.dispatch)(self.seat, timeout) };
+        if res < 0 {
+            return Err(LibSeatError::Dispatch(Errno::default().into()));
+        }
+        Ok(mem::take(&mut *self.listener.events.borrow_mut()))
+    }
+
+    /// Acknowledges that the seat has been disabled.
+    pub fn disable(&self) {
+        let res = unsafe { (self.lib.disable_seat)(self.seat) };
+        if res < 0 {
+            let err = OsError::from(Errno::default());
+            log::error!("Could not disable the seat: {}", ErrorFmt(err));
+        }
+    }
+
+    /// Opens a device and returns its device id and file descriptor.
+    pub fn open_device(&self, path: &CStr) -> Result<(c::c_int, OwnedFd), LibSeatError> {
+        let mut fd = -1;
+        let id = unsafe { (self.lib.open_device)(self.seat, path.as_ptr(), &mut fd) };
+        if id < 0 {
+            return Err(LibSeatError::OpenDevice(Errno::default().into()));
+        }
+        Ok((id, OwnedFd::new(fd)))
+    }
+
+    pub fn close_device(&self, id: c::c_int) {
+        let res = unsafe { (self.lib.close_device)(self.seat, id) };
+        if res < 0 {
+            let err = OsError::from(Errno::default());
+            log::warn!("Could not close a device: {}", ErrorFmt(err));
+        }
+    }
+
+    pub fn switch_session(&self, session: c::c_int) -> Result<(), LibSeatError> {
+        let res = unsafe { (self.lib.switch_session)(self.seat, session) };
+        if res < 0 {
+            return Err(LibSeatError::SwitchSession(Errno::default().into()));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for LibSeat {
+    fn drop(&mut self) {
+        unsafe {
+            (self.lib.close_seat)(self.seat);
+        }
+    }
+}
+
+unsafe extern "C" fn enable_seat(_seat: *mut libseat, userdata: *mut c::c_void) {
+    let listener = unsafe { &*(userdata as *const Listener) };
+    listener.active.set(true);
+    listener.events.borrow_mut().push(LibSeatEvent::Enable);
+}
+
+unsafe extern "C" fn disable_seat(_seat: *mut libseat, userdata: *mut c::c_void) {
+    let listener = unsafe { &*(userdata as *const Listener) };
+    listener.active.set(false);
+    listener.events.borrow_mut().push(LibSeatEvent::Disable);
+}
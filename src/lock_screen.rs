@@ -0,0 +1,248 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        gfx_api::GfxTexture,
+        rect::Rect,
+        scale::Scale,
+        state::State,
+        text::TextTexture,
+        utils::{errorfmt::ErrorFmt, on_drop_event::OnDropEvent, smallmap::SmallMapMut},
+    },
+    jay_config::keyboard::syms::{KeySym, SYM_BackSpace, SYM_Escape, SYM_KP_Enter, SYM_Return},
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+const MAX_PASSWORD_LEN: usize = 1024;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Status {
+    Prompt,
+    Authenticating,
+    Failed,
+}
+
+/// A compositor-internal lock screen that is shown if the session-lock client disappears
+/// while the session is locked.
+///
+/// The password is verified via PAM in the forker. If the prompt is disabled, the outputs
+/// are filled with red instead.
+pub struct FallbackLockScreen {
+    active: Cell<bool>,
+    prompt: Cell<bool>,
+    status: Cell<Status>,
+    password: RefCell<String>,
+    textures: RefCell<SmallMapMut<Scale, TextTexture, 2>>,
+    render: Cell<Option<SpawnedFuture<()>>>,
+    auth: Cell<Option<SpawnedFuture<()>>>,
+}
+
+impl Default for FallbackLockScreen {
+    fn default() -> Self {
+        Self {
+            active: Default::default(),
+            prompt: Cell::new(true),
+            status: Cell::new(Status::Prompt),
+            password: Default::default(),
+            textures: Default::default(),
+            render: Default::default(),
+            auth: Default::default(),
+        }
+    }
+}
+
+impl FallbackLockScreen {
+    pub fn is_active(&self) -> bool {
+        self.active.get()
+    }
+
+    pub fn prompt_enabled(&self) -> bool {
+        self.prompt.get()
+    }
+
+    pub fn set_prompt_enabled(self: &Rc<Self>, state: &Rc<State>, enabled: bool) {
+        if self.prompt.replace(enabled) == enabled {
+            return;
+        }
+        if self.active.get() {
+            self.auth.take();
+            self.status.set(Status::Prompt);
+            self.clear_password();
+            self.textures.borrow_mut().clear();
+            self.schedule_render(state);
+            state.damage(state.root.extents.get());
+        }
+    }
+
+    pub fn activate(self: &Rc<Self>, state: &Rc<State>) {
+        if self.active.replace(true) {
+            return;
+        }
+        self.status.set(Status::Prompt);
+        self.clear_password();
+        self.schedule_render(state);
+        state.damage(state.root.extents.get());
+    }
+
+    pub fn deactivate(&self, state: &State) {
+        if !self.active.replace(false) {
+            return;
+        }
+        self.auth.take();
+        self.render.take();
+        self.clear_password();
+        self.textures.borrow_mut().clear();
+        state.damage(state.root.extents.get());
+    }
+
+    pub fn clear(&self) {
+        self.active.set(false);
+        self.auth.take();
+        self.render.take();
+        self.clear_password();
+        self.textures.borrow_mut().clear();
+    }
+
+    fn clear_password(&self) {
+        let password = &mut *self.password.borrow_mut();
+        unsafe {
+            password.as_bytes_mut().fill(0);
+        }
+        password.clear();
+    }
+
+    pub fn handle_key(self: &Rc<Self>, state: &Rc<State>, sym: KeySym, char: Option<char>) {
+        if !self.active.get() || !self.prompt.get() || self.status.get() == Status::Authenticating {
+            return;
+        }
+        self.status.set(Status::Prompt);
+        if sym == SYM_Return || sym == SYM_KP_Enter {
+            self.submit(state);
+        } else if sym == SYM_BackSpace {
+            self.password.borrow_mut().pop();
+        } else if sym == SYM_Escape {
+            self.clear_password();
+        } else if let Some(c) = char {
+            if !c.is_control() {
+                let password = &mut *self.password.borrow_mut();
+                if password.len() < MAX_PASSWORD_LEN {
+                    password.push(c);
+                }
+            }
+        }
+        self.schedule_render(state);
+    }
+
+    fn submit(self: &Rc<Self>, state: &Rc<State>) {
+        let Some(forker) = state.forker.get() else {
+            log::error!("There is no forker. Cannot authenticate.");
+            self.status.set(Status::Failed);
+            return;
+        };
+        let password = self.password.borrow().clone();
+        self.clear_password();
+        self.status.set(Status::Authenticating);
+        let slf = self.clone();
+        let state2 = state.clone();
+        let future = state.eng.spawn("fallback lock screen auth", async move {
+            let success = forker.authenticate(password).await;
+            if success {
+                log::info!("Unlocking the session via the fallback lock screen");
+                state2.do_unlock();
+            } else {
+                slf.status.set(Status::Failed);
+                slf.schedule_render(&state2);
+            }
+        });
+        self.auth.set(Some(future));
+    }
+
+    fn text(&self) -> String {
+        let mut text = match self.status.get() {
+            Status::Prompt => "The session is locked. Enter your password: ",
+            Status::Authenticating => "Authenticating...",
+            Status::Failed => "Authentication failed. Enter your password: ",
+        }
+        .to_string();
+        if self.status.get() != Status::Authenticating {
+            let len = self.password.borrow().chars().count();
+            text.extend((0..len).map(|_| '*'));
+        }
+        text
+    }
+
+    fn schedule_render(self: &Rc<Self>, state: &Rc<State>) {
+        if !self.prompt.get() {
+            return;
+        }
+        let Some(ctx) = state.render_ctx.get() else {
+            return;
+        };
+        let on_completed = Rc::new(OnDropEvent::default());
+        {
+            let text = self.text();
+            let theme = &state.theme;
+            let font = theme.font.get();
+            let color = theme.colors.bar_text.get();
+            let th = theme.sizes.title_height.get();
+            let scales = state.scales.lock();
+            let textures = &mut *self.textures.borrow_mut();
+            for (scale, _) in scales.iter() {
+                let tex = textures
+                    .get_or_insert_with(*scale, || TextTexture::new(&state.cpu_worker, &ctx));
+                let mut height = th;
+                let mut scalef = None;
+                if *scale != 1 {
+                    let scale = scale.to_f64();
+                    height = (height as f64 * scale).round() as _;
+                    scalef = Some(scale);
+                }
+                tex.schedule_render_fitting(
+                    on_completed.clone(),
+                    Some(height),
+                    &font,
+                    &text,
+                    color,
+                    false,
+                    scalef,
+                );
+            }
+        }
+        let completed = on_completed.event();
+        let slf = self.clone();
+        let state2 = state.clone();
+        let future = state.eng.spawn("fallback lock screen", async move {
+            completed.triggered().await;
+            for (_, tex) in &*slf.textures.borrow() {
+                if let Err(e) = tex.flip() {
+                    log::warn!("Could not render the fallback lock screen: {}", ErrorFmt(e));
+                }
+            }
+            state2.damage(state2.root.extents.get());
+        });
+        self.render.set(Some(future));
+    }
+
+    /// Returns the position of the prompt relative to an output of the given size and the
+    /// texture containing the text.
+    pub fn layout(
+        &self,
+        output_width: i32,
+        output_height: i32,
+        scale: Scale,
+    ) -> Option<(Rect, Rc<dyn GfxTexture>)> {
+        if !self.active.get() || !self.prompt.get() {
+            return None;
+        }
+        let texture = self.textures.borrow().get(&scale)?.texture()?;
+        let (tex_width, tex_height) = texture.size();
+        let width = (tex_width as f64 / scale.to_f64()).ceil() as i32;
+        let height = (tex_height as f64 / scale.to_f64()).ceil() as i32;
+        let x = (output_width - width) / 2;
+        let y = (output_height - height) / 2;
+        let rect = Rect::new_sized(x, y, width, height)?;
+        Some((rect, texture))
+    }
+}
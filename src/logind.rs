@@ -6,7 +6,7 @@ use {
             org,
             org::freedesktop::login1::{
                 seat::SwitchToReply,
-                session::{PauseDevice, ResumeDevice, TakeDeviceReply},
+                session::{PauseDevice, ResumeDevice, SetBrightnessReply, TakeDeviceReply},
             },
         },
     },
@@ -146,6 +146,22 @@ impl Session {
         );
     }
 
+    pub fn set_brightness<F>(&self, subsystem: &str, name: &str, brightness: u32, f: F)
+    where
+        F: FnOnce(Result<&SetBrightnessReply, DbusError>) + 'static,
+    {
+        self.socket.call(
+            LOGIND_NAME,
+            &self.session_path,
+            org::freedesktop::login1::session::SetBrightness {
+                subsystem: subsystem.into(),
+                name: name.into(),
+                brightness,
+            },
+            f,
+        );
+    }
+
     pub fn switch_to<F>(&self, vtnr: u32, f: F)
     where
         F: FnOnce(Result<&SwitchToReply, DbusError>) + 'static,
@@ -51,9 +51,11 @@ mod async_engine;
 mod backend;
 mod backends;
 mod bugs;
+mod capture_overlay;
 mod cli;
 mod client;
 mod clientmem;
+mod cmm;
 mod compositor;
 mod config;
 mod cpu_worker;
@@ -61,6 +63,7 @@ mod cursor;
 mod cursor_user;
 mod damage;
 mod dbus;
+mod dmabuf_bridge;
 mod drm_feedback;
 mod edid;
 mod ei;
@@ -70,17 +73,24 @@ mod format;
 mod gfx_api;
 mod gfx_apis;
 mod globals;
+mod idle_audio;
 mod ifs;
+mod interactive_resize;
 mod io_uring;
 #[cfg(feature = "it")]
 mod it;
 mod kbvm;
 mod keyboard;
 mod libinput;
+mod libseat;
+mod lock_screen;
 mod logger;
 mod logind;
+mod night_light;
+mod notifications;
 mod object;
 mod output_schedule;
+mod pam;
 mod pango;
 mod pipewire;
 mod portal;
@@ -91,6 +101,7 @@ mod screenshoter;
 mod security_context_acceptor;
 mod sighand;
 mod state;
+mod stats;
 mod tasks;
 mod text;
 mod theme;
@@ -104,6 +115,7 @@ mod utils;
 mod version;
 mod video;
 mod wheel;
+mod window_state;
 mod wire;
 mod wire_dbus;
 mod wire_ei;
@@ -0,0 +1,240 @@
+use {
+    crate::{
+        backend::GammaLut,
+        state::State,
+        utils::{asyncevent::AsyncEvent, errorfmt::ErrorFmt},
+    },
+    chrono::{Datelike, Local, Timelike},
+    futures_util::{select, FutureExt},
+    jay_config::video::NightLightSchedule,
+    std::{cell::Cell, f64::consts::PI, rc::Rc, time::Duration},
+};
+
+const NEUTRAL_TEMPERATURE: u32 = 6500;
+const MIN_TEMPERATURE: u32 = 1000;
+const DEFAULT_TEMPERATURE: u32 = 4000;
+const DEFAULT_TRANSITION: Duration = Duration::from_secs(30 * 60);
+const SECONDS_PER_DAY: f64 = 24.0 * 60.0 * 60.0;
+const TRANSITION_STEP_MS: u64 = 1000;
+const MAX_SLEEP_MS: u64 = 60 * 1000;
+
+/// A color temperature that is applied to all outputs by multiplying their gamma ramps.
+pub struct NightLight {
+    schedule: Cell<NightLightSchedule>,
+    temperature: Cell<u32>,
+    transition: Cell<Duration>,
+    applied_temperature: Cell<u32>,
+    whitepoint: Cell<Option<[f64; 3]>>,
+    changed: AsyncEvent,
+}
+
+impl Default for NightLight {
+    fn default() -> Self {
+        Self {
+            schedule: Default::default(),
+            temperature: Cell::new(DEFAULT_TEMPERATURE),
+            transition: Cell::new(DEFAULT_TRANSITION),
+            applied_temperature: Cell::new(NEUTRAL_TEMPERATURE),
+            whitepoint: Default::default(),
+            changed: Default::default(),
+        }
+    }
+}
+
+pub async fn night_light(state: Rc<State>) {
+    let nl = &state.night_light;
+    loop {
+        let Some(ms) = nl.update(&state) else {
+            nl.changed.triggered().await;
+            continue;
+        };
+        select! {
+            res = state.wheel.timeout(ms).fuse() => {
+                if let Err(e) = res {
+                    log::error!("Could not wait for the night light timer: {}", ErrorFmt(e));
+                    return;
+                }
+            },
+            _ = nl.changed.triggered().fuse() => { },
+        }
+    }
+}
+
+impl NightLight {
+    pub fn set_schedule(&self, schedule: NightLightSchedule) {
+        self.schedule.set(schedule);
+        self.changed.trigger();
+    }
+
+    pub fn set_temperature(&self, kelvin: u32) {
+        self.temperature
+            .set(kelvin.clamp(MIN_TEMPERATURE, NEUTRAL_TEMPERATURE));
+        self.changed.trigger();
+    }
+
+    pub fn set_transition(&self, duration: Duration) {
+        self.transition.set(duration);
+        self.changed.trigger();
+    }
+
+    /// Combines the current color temperature with a gamma ramp of the given size.
+    pub fn apply(&self, size: u32, lut: Option<Rc<GammaLut>>) -> Option<Rc<GammaLut>> {
+        let Some([r, g, b]) = self.whitepoint.get() else {
+            return lut;
+        };
+        let size = size as usize;
+        if size < 2 {
+            return lut;
+        }
+        let lut = lut.filter(|l| l.red.len() == size);
+        let scale = |ramp: Option<&Vec<u16>>, factor: f64| {
+            (0..size)
+                .map(|i| {
+                    let v = match ramp {
+                        Some(ramp) => ramp[i] as f64,
+                        None => (i * u16::MAX as usize / (size - 1)) as f64,
+                    };
+                    (v * factor).round() as u16
+                })
+                .collect()
+        };
+        Some(Rc::new(GammaLut {
+            red: scale(lut.as_ref().map(|l| &l.red), r),
+            green: scale(lut.as_ref().map(|l| &l.green), g),
+            blue: scale(lut.as_ref().map(|l| &l.blue), b),
+        }))
+    }
+
+    /// Updates the applied temperature and returns the number of milliseconds until the
+    /// next update.
+    fn update(&self, state: &State) -> Option<u64> {
+        let (fraction, sleep_ms) = self.night_fraction();
+        let mired = |kelvin: u32| 1_000_000.0 / kelvin as f64;
+        let neutral = mired(NEUTRAL_TEMPERATURE);
+        let target = mired(self.temperature.get());
+        let temperature = (1_000_000.0 / (neutral + (target - neutral) * fraction)).round() as u32;
+        if self.applied_temperature.replace(temperature) != temperature {
+            let whitepoint = match temperature >= NEUTRAL_TEMPERATURE {
+                true => None,
+                false => Some(whitepoint(temperature)),
+            };
+            self.whitepoint.set(whitepoint);
+            for output in state.root.outputs.lock().values() {
+                output.update_gamma_lut();
+            }
+        }
+        sleep_ms
+    }
+
+    /// Returns how far the night light is enabled and the number of milliseconds until
+    /// this value changes.
+    fn night_fraction(&self) -> (f64, Option<u64>) {
+        let (start, end) = match self.schedule.get() {
+            NightLightSchedule::Disabled => return (0.0, None),
+            NightLightSchedule::Always => return (1.0, None),
+            NightLightSchedule::Time { start, end } => (start as f64 * 60.0, end as f64 * 60.0),
+            NightLightSchedule::Location {
+                latitude,
+                longitude,
+            } => match sun_times(latitude, longitude) {
+                SunTimes::Night => return (1.0, Some(MAX_SLEEP_MS)),
+                SunTimes::Day => return (0.0, Some(MAX_SLEEP_MS)),
+                SunTimes::Times { sunrise, sunset } => (sunset, sunrise),
+            },
+        };
+        let now = Local::now().num_seconds_from_midnight() as f64;
+        let since = |t: f64| (now - t).rem_euclid(SECONDS_PER_DAY);
+        let transition = self.transition.get().as_secs_f64();
+        let ramp = |elapsed: f64| match transition > 0.0 {
+            true => (elapsed / transition).min(1.0),
+            false => 1.0,
+        };
+        let night_length = (end - start).rem_euclid(SECONDS_PER_DAY);
+        let (fraction, transitioning, until_next) = if since(start) < night_length {
+            let elapsed = since(start);
+            (ramp(elapsed), elapsed < transition, night_length - elapsed)
+        } else {
+            let elapsed = since(end);
+            let peak = ramp(night_length);
+            (
+                peak * (1.0 - ramp(elapsed)),
+                elapsed < transition,
+                SECONDS_PER_DAY - since(start),
+            )
+        };
+        let sleep_ms = match transitioning {
+            true => TRANSITION_STEP_MS,
+            false => ((until_next * 1000.0).ceil() as u64).clamp(1, MAX_SLEEP_MS),
+        };
+        (fraction, Some(sleep_ms))
+    }
+}
+
+/// Returns the relative intensities of the red, green, and blue channels of a black body
+/// with the given temperature, normalized to the neutral temperature.
+fn whitepoint(kelvin: u32) -> [f64; 3] {
+    let rgb = |kelvin: u32| {
+        let t = kelvin as f64 / 100.0;
+        let r = match t <= 66.0 {
+            true => 255.0,
+            false => 329.698727446 * (t - 60.0).powf(-0.1332047592),
+        };
+        let g = match t <= 66.0 {
+            true => 99.4708025861 * t.ln() - 161.1195681661,
+            false => 288.1221695283 * (t - 60.0).powf(-0.0755148492),
+        };
+        let b = if t >= 66.0 {
+            255.0
+        } else if t <= 19.0 {
+            0.0
+        } else {
+            138.5177312231 * (t - 10.0).ln() - 305.0447927307
+        };
+        [r, g, b].map(|c| c.clamp(0.0, 255.0))
+    };
+    let neutral = rgb(NEUTRAL_TEMPERATURE);
+    let color = rgb(kelvin);
+    [0, 1, 2].map(|i| (color[i] / neutral[i]).min(1.0))
+}
+
+enum SunTimes {
+    Night,
+    Day,
+    Times { sunrise: f64, sunset: f64 },
+}
+
+/// Calculates today's sunrise and sunset in seconds after local midnight.
+///
+/// This uses the approximations of the NOAA Global Monitoring Division.
+fn sun_times(latitude: f64, longitude: f64) -> SunTimes {
+    let now = Local::now();
+    let gamma = 2.0 * PI / 365.0 * (now.ordinal0() as f64 + 0.5);
+    let eqtime = 229.18
+        * (0.000075 + 0.001868 * gamma.cos()
+            - 0.032077 * gamma.sin()
+            - 0.014615 * (2.0 * gamma).cos()
+            - 0.040849 * (2.0 * gamma).sin());
+    let decl = 0.006918 - 0.399912 * gamma.cos() + 0.070257 * gamma.sin()
+        - 0.006758 * (2.0 * gamma).cos()
+        + 0.000907 * (2.0 * gamma).sin()
+        - 0.002697 * (3.0 * gamma).cos()
+        + 0.00148 * (3.0 * gamma).sin();
+    let lat = latitude.to_radians();
+    let cos_ha = 90.833f64.to_radians().cos() / (lat.cos() * decl.cos()) - lat.tan() * decl.tan();
+    if cos_ha > 1.0 {
+        return SunTimes::Night;
+    }
+    if cos_ha < -1.0 {
+        return SunTimes::Day;
+    }
+    let ha = cos_ha.acos().to_degrees();
+    let offset = now.offset().local_minus_utc() as f64;
+    let time = |ha: f64| {
+        let utc_minutes = 720.0 - 4.0 * (longitude + ha) - eqtime;
+        (utc_minutes * 60.0 + offset).rem_euclid(SECONDS_PER_DAY)
+    };
+    SunTimes::Times {
+        sunrise: time(ha),
+        sunset: time(-ha),
+    }
+}
@@ -0,0 +1,488 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        dbus::{
+            prelude::Variant, DbusObject, DbusSocket, PendingReply, BUS_DEST, BUS_PATH,
+            DBUS_NAME_FLAG_DO_NOT_QUEUE, DBUS_REQUEST_NAME_REPLY_PRIMARY_OWNER,
+        },
+        rect::Rect,
+        scale::Scale,
+        state::State,
+        text::TextTexture,
+        tree::OutputNode,
+        utils::{
+            asyncevent::AsyncEvent, clonecell::CloneCell, errorfmt::ErrorFmt, numcell::NumCell,
+            on_drop_event::OnDropEvent, smallmap::SmallMapMut,
+        },
+        version::VERSION,
+        wire_dbus::org::{
+            self,
+            freedesktop::notifications::{
+                ActionInvoked, CloseNotification, CloseNotificationReply, GetCapabilities,
+                GetCapabilitiesReply, GetServerInformation, GetServerInformationReply,
+                NotificationClosed, Notify, NotifyReply,
+            },
+        },
+    },
+    std::{
+        borrow::Cow,
+        cell::{Cell, RefCell},
+        rc::Rc,
+        time::Duration,
+    },
+};
+
+const SERVICE_NAME: &str = "org.freedesktop.Notifications";
+const SERVICE_PATH: &str = "/org/freedesktop/Notifications";
+
+const REASON_EXPIRED: u32 = 1;
+const REASON_DISMISSED: u32 = 2;
+const REASON_CLOSED: u32 = 3;
+
+const URGENCY_CRITICAL: u8 = 2;
+
+const WIDTH: i32 = 400;
+pub const NOTIFICATION_PADDING: i32 = 5;
+const GAP: i32 = 10;
+const MAX_VISIBLE: usize = 5;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub enum NotificationPosition {
+    TopLeft,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+pub struct Notifications {
+    pub enabled: Cell<bool>,
+    pub do_not_disturb: Cell<bool>,
+    pub position: Cell<NotificationPosition>,
+    pub default_timeout: Cell<Duration>,
+    next_id: NumCell<u32>,
+    notifications: RefCell<Vec<Rc<Notification>>>,
+    output: CloneCell<Option<Rc<OutputNode>>>,
+    socket: CloneCell<Option<Rc<DbusSocket>>>,
+    object: RefCell<Option<DbusObject>>,
+    service: Cell<Option<SpawnedFuture<()>>>,
+}
+
+pub struct Notification {
+    pub id: u32,
+    pub summary: String,
+    pub body: String,
+    actions: Vec<String>,
+    critical: bool,
+    render_scheduled: Cell<bool>,
+    pub textures: RefCell<SmallMapMut<Scale, NotificationTextures, 2>>,
+    expiration: Cell<Option<SpawnedFuture<()>>>,
+}
+
+pub struct NotificationTextures {
+    pub summary: TextTexture,
+    pub body: TextTexture,
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Self {
+            enabled: Default::default(),
+            do_not_disturb: Default::default(),
+            position: Default::default(),
+            default_timeout: Cell::new(Duration::from_secs(5)),
+            next_id: NumCell::new(1),
+            notifications: Default::default(),
+            output: Default::default(),
+            socket: Default::default(),
+            object: Default::default(),
+            service: Default::default(),
+        }
+    }
+}
+
+pub async fn notification_textures(state: Rc<State>) {
+    loop {
+        let notification = state.pending_notification_textures.pop().await;
+        if notification.render_scheduled.replace(false) {
+            notification.render_phase1(&state).triggered().await;
+            notification.render_phase2(&state);
+        }
+    }
+}
+
+impl Notifications {
+    pub fn clear(&self) {
+        self.service.take();
+        self.object.take();
+        self.socket.take();
+        self.output.take();
+        self.notifications.take();
+    }
+
+    pub fn set_enabled(&self, state: &Rc<State>, enabled: bool) {
+        if self.enabled.replace(enabled) == enabled {
+            return;
+        }
+        if enabled {
+            let future = state
+                .eng
+                .spawn("notification daemon", run_daemon(state.clone()));
+            self.service.set(Some(future));
+        } else {
+            self.service.take();
+            self.object.take();
+            if let Some(socket) = self.socket.take() {
+                socket.call_noreply(
+                    BUS_DEST,
+                    BUS_PATH,
+                    org::freedesktop::dbus::ReleaseName {
+                        name: SERVICE_NAME.into(),
+                    },
+                );
+            }
+            self.notifications.take();
+            self.damage(state);
+        }
+    }
+
+    pub fn set_do_not_disturb(&self, state: &State, dnd: bool) {
+        if self.do_not_disturb.replace(dnd) == dnd || !dnd {
+            return;
+        }
+        let hidden: Vec<_> = self
+            .notifications
+            .borrow()
+            .iter()
+            .filter(|n| !n.critical)
+            .map(|n| n.id)
+            .collect();
+        for id in hidden {
+            self.close(state, id, REASON_DISMISSED);
+        }
+    }
+
+    pub fn set_position(&self, state: &State, position: NotificationPosition) {
+        self.position.set(position);
+        self.damage(state);
+    }
+
+    pub fn dismiss(&self, state: &State) {
+        let id = self.notifications.borrow().last().map(|n| n.id);
+        if let Some(id) = id {
+            self.close(state, id, REASON_DISMISSED);
+        }
+    }
+
+    pub fn dismiss_all(&self, state: &State) {
+        let ids: Vec<_> = self.notifications.borrow().iter().map(|n| n.id).collect();
+        for id in ids {
+            self.close(state, id, REASON_DISMISSED);
+        }
+    }
+
+    pub fn invoke_default_action(&self, state: &State) {
+        let Some(n) = self.notifications.borrow().last().cloned() else {
+            return;
+        };
+        let key = n
+            .actions
+            .iter()
+            .step_by(2)
+            .find(|k| *k == "default")
+            .or(n.actions.first());
+        if let Some(key) = key {
+            if let Some(object) = &*self.object.borrow() {
+                object.emit_signal(&ActionInvoked {
+                    id: n.id,
+                    action_key: key.as_str().into(),
+                });
+            }
+        }
+        self.close(state, n.id, REASON_DISMISSED);
+    }
+
+    pub fn is_shown_on(&self, output: &OutputNode) -> bool {
+        match self.output.get() {
+            Some(o) => o.id == output.id && !self.notifications.borrow().is_empty(),
+            None => false,
+        }
+    }
+
+    /// Returns the visible notifications together with their positions relative to the
+    /// output.
+    pub fn layout(&self, state: &State, output: &OutputNode) -> Vec<(Rc<Notification>, Rect)> {
+        let theme = &state.theme;
+        let th = theme.sizes.title_height.get();
+        let bw = theme.sizes.border_width.get();
+        let area = output.non_exclusive_rect_rel.get();
+        let (top, bottom) = (area.y1() + th + 1 + GAP, area.y2() - GAP);
+        let position = self.position.get();
+        let x = match position {
+            NotificationPosition::TopLeft | NotificationPosition::BottomLeft => area.x1() + GAP,
+            _ => area.x2() - GAP - WIDTH,
+        };
+        let mut y = match position {
+            NotificationPosition::TopLeft | NotificationPosition::TopRight => top,
+            _ => bottom,
+        };
+        let mut res = vec![];
+        let notifications = self.notifications.borrow();
+        for n in notifications.iter().rev().take(MAX_VISIBLE) {
+            let lines = if n.body.is_empty() { 1 } else { 2 };
+            let height = 2 * bw + 2 * NOTIFICATION_PADDING + lines * th;
+            let rect = match position {
+                NotificationPosition::TopLeft | NotificationPosition::TopRight => {
+                    let rect = Rect::new_sized(x, y, WIDTH, height);
+                    y += height + GAP;
+                    rect
+                }
+                _ => {
+                    y -= height;
+                    let rect = Rect::new_sized(x, y, WIDTH, height);
+                    y -= GAP;
+                    rect
+                }
+            };
+            if let Some(rect) = rect {
+                if rect.y1() >= top && rect.y2() <= bottom {
+                    res.push((n.clone(), rect));
+                }
+            }
+        }
+        res
+    }
+
+    pub fn render_textures(&self, state: &State) {
+        for n in self.notifications.borrow().iter() {
+            n.textures.borrow_mut().clear();
+            n.schedule_render(state);
+        }
+    }
+
+    fn notify(&self, state: &Rc<State>, req: Notify, reply: PendingReply<NotifyReply>) {
+        let mut critical = false;
+        for hint in req.hints.iter() {
+            if hint.key == "urgency" {
+                if let Variant::U8(urgency) = hint.value {
+                    critical = urgency == URGENCY_CRITICAL;
+                }
+            }
+        }
+        let replaces = self
+            .notifications
+            .borrow()
+            .iter()
+            .any(|n| n.id == req.replaces_id);
+        let id = match replaces {
+            true => req.replaces_id,
+            false => self.next_id.fetch_add(1),
+        };
+        reply.ok(&NotifyReply { id });
+        if self.do_not_disturb.get() && !critical {
+            return;
+        }
+        let notification = Rc::new(Notification {
+            id,
+            summary: req.summary.to_string(),
+            body: req.body.to_string(),
+            actions: req.actions.iter().map(|a| a.to_string()).collect(),
+            critical,
+            render_scheduled: Cell::new(false),
+            textures: Default::default(),
+            expiration: Default::default(),
+        });
+        let timeout = match req.expire_timeout {
+            0 => None,
+            ms if ms > 0 => Some(Duration::from_millis(ms as u64)),
+            _ if critical => None,
+            _ => Some(self.default_timeout.get()),
+        };
+        if let Some(timeout) = timeout {
+            let future = state.eng.spawn(
+                "notification timeout",
+                expire(state.clone(), id, timeout.as_millis() as u64),
+            );
+            notification.expiration.set(Some(future));
+        }
+        {
+            let notifications = &mut *self.notifications.borrow_mut();
+            match notifications.iter_mut().find(|n| n.id == id) {
+                Some(n) => *n = notification.clone(),
+                None => notifications.push(notification.clone()),
+            }
+        }
+        if let Some(seat) = state.seat_queue.last() {
+            let output = seat.get_output();
+            if let Some(old) = self.output.set(Some(output)) {
+                state.damage(old.global.pos.get());
+            }
+        }
+        notification.schedule_render(state);
+        self.damage(state);
+    }
+
+    fn close(&self, state: &State, id: u32, reason: u32) {
+        let removed = {
+            let notifications = &mut *self.notifications.borrow_mut();
+            let len = notifications.len();
+            notifications.retain(|n| n.id != id);
+            len != notifications.len()
+        };
+        if !removed {
+            return;
+        }
+        if let Some(object) = &*self.object.borrow() {
+            object.emit_signal(&NotificationClosed { id, reason });
+        }
+        self.damage(state);
+    }
+
+    fn damage(&self, state: &State) {
+        if let Some(output) = self.output.get() {
+            state.damage(output.global.pos.get());
+        }
+    }
+}
+
+impl Notification {
+    fn schedule_render(self: &Rc<Self>, state: &State) {
+        if !self.render_scheduled.replace(true) {
+            state.pending_notification_textures.push(self.clone());
+        }
+    }
+
+    fn render_phase1(&self, state: &State) -> Rc<AsyncEvent> {
+        let on_completed = Rc::new(OnDropEvent::default());
+        let Some(ctx) = state.render_ctx.get() else {
+            return on_completed.event();
+        };
+        let theme = &state.theme;
+        let font = theme.font.get();
+        let color = theme.colors.notification_text.get();
+        let bw = theme.sizes.border_width.get();
+        let th = theme.sizes.title_height.get();
+        let scales = state.scales.lock();
+        let textures = &mut *self.textures.borrow_mut();
+        for (scale, _) in scales.iter() {
+            let tex = textures.get_or_insert_with(*scale, || NotificationTextures {
+                summary: TextTexture::new(&state.cpu_worker, &ctx),
+                body: TextTexture::new(&state.cpu_worker, &ctx),
+            });
+            let mut width = WIDTH - 2 * bw - 2 * NOTIFICATION_PADDING;
+            let mut height = th;
+            let mut scalef = None;
+            if *scale != 1 {
+                let scale = scale.to_f64();
+                width = (width as f64 * scale).round() as _;
+                height = (height as f64 * scale).round() as _;
+                scalef = Some(scale);
+            }
+            if width <= 0 || height <= 0 {
+                continue;
+            }
+            for (tex, text) in [(&tex.summary, &self.summary), (&tex.body, &self.body)] {
+                tex.schedule_render(
+                    on_completed.clone(),
+                    1,
+                    None,
+                    width,
+                    height,
+                    1,
+                    &font,
+                    text,
+                    color,
+                    true,
+                    false,
+                    scalef,
+                );
+            }
+        }
+        on_completed.event()
+    }
+
+    fn render_phase2(&self, state: &State) {
+        for (_, tex) in &*self.textures.borrow() {
+            for tex in [&tex.summary, &tex.body] {
+                if let Err(e) = tex.flip() {
+                    log::error!("Could not render notification {}: {}", self.id, ErrorFmt(e));
+                }
+            }
+        }
+        state.notifications.damage(state);
+    }
+}
+
+async fn expire(state: Rc<State>, id: u32, ms: u64) {
+    if let Err(e) = state.wheel.timeout(ms).await {
+        log::error!(
+            "Could not wait for the notification timeout: {}",
+            ErrorFmt(e)
+        );
+        return;
+    }
+    state.notifications.close(&state, id, REASON_EXPIRED);
+}
+
+async fn run_daemon(state: Rc<State>) {
+    let socket = match state.dbus.session().await {
+        Ok(s) => s,
+        Err(e) => {
+            log::error!("Could not connect to the session bus: {}", ErrorFmt(e));
+            return;
+        }
+    };
+    let rv = socket
+        .call_async(
+            BUS_DEST,
+            BUS_PATH,
+            org::freedesktop::dbus::RequestName {
+                name: SERVICE_NAME.into(),
+                flags: DBUS_NAME_FLAG_DO_NOT_QUEUE,
+            },
+        )
+        .await;
+    match rv {
+        Ok(r) if r.get().rv == DBUS_REQUEST_NAME_REPLY_PRIMARY_OWNER => {}
+        Ok(_) => {
+            log::warn!("Another notification daemon is already running");
+            return;
+        }
+        Err(e) => {
+            log::error!("Could not acquire {}: {}", SERVICE_NAME, ErrorFmt(e));
+            return;
+        }
+    }
+    let object = match socket.add_object(SERVICE_PATH) {
+        Ok(o) => o,
+        Err(e) => {
+            log::error!("Could not create the notification object: {}", ErrorFmt(e));
+            return;
+        }
+    };
+    object.add_method::<GetCapabilities, _>(|_, pr| {
+        pr.ok(&GetCapabilitiesReply {
+            capabilities: Cow::Borrowed(&[Cow::Borrowed("actions"), Cow::Borrowed("body")]),
+        });
+    });
+    object.add_method::<GetServerInformation, _>(|_, pr| {
+        pr.ok(&GetServerInformationReply {
+            name: "jay".into(),
+            vendor: "jay".into(),
+            version: VERSION.into(),
+            spec_version: "1.2".into(),
+        });
+    });
+    let s = state.clone();
+    object.add_method::<Notify, _>(move |req, pr| {
+        s.notifications.notify(&s, req, pr);
+    });
+    let s = state.clone();
+    object.add_method::<CloseNotification, _>(move |req, pr| {
+        s.notifications.close(&s, req.id, REASON_CLOSED);
+        pr.ok(&CloseNotificationReply);
+    });
+    log::info!("Acquired {}", SERVICE_NAME);
+    state.notifications.socket.set(Some(socket));
+    *state.notifications.object.borrow_mut() = Some(object);
+}
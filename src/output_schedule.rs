@@ -191,7 +191,7 @@ impl OutputSchedule {
             self.hardware_cursor_change.set(Change::AwaitingLatch);
         }
         if self.software_cursor_change.get() == Change::Scheduled {
-            self.connector.damage();
+            self.connector.damage_software_cursor();
             self.software_cursor_change.set(Change::AwaitingLatch);
         }
     }
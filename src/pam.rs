@@ -0,0 +1,148 @@
+#![allow(non_camel_case_types)]
+
+use {
+    std::{ffi::CStr, ptr},
+    thiserror::Error,
+    uapi::{c, IntoUstr},
+};
+
+const PAM_SUCCESS: c::c_int = 0;
+const PAM_BUF_ERR: c::c_int = 5;
+const PAM_CONV_ERR: c::c_int = 19;
+
+const PAM_PROMPT_ECHO_OFF: c::c_int = 1;
+const PAM_PROMPT_ECHO_ON: c::c_int = 2;
+
+#[repr(transparent)]
+struct pam_handle_t(u8);
+
+#[repr(C)]
+struct pam_message {
+    msg_style: c::c_int,
+    msg: *const c::c_char,
+}
+
+#[repr(C)]
+struct pam_response {
+    resp: *mut c::c_char,
+    resp_retcode: c::c_int,
+}
+
+#[repr(C)]
+struct pam_conv {
+    conv: unsafe extern "C" fn(
+        num_msg: c::c_int,
+        msg: *mut *const pam_message,
+        resp: *mut *mut pam_response,
+        appdata_ptr: *mut c::c_void,
+    ) -> c::c_int,
+    appdata_ptr: *mut c::c_void,
+}
+
+#[link(name = "pam")]
+unsafe extern "C" {
+    fn pam_start(
+        service_name: *const c::c_char,
+        user: *const c::c_char,
+        pam_conversation: *const pam_conv,
+        pamh: *mut *mut pam_handle_t,
+    ) -> c::c_int;
+    fn pam_end(pamh: *mut pam_handle_t, pam_status: c::c_int) -> c::c_int;
+    fn pam_authenticate(pamh: *mut pam_handle_t, flags: c::c_int) -> c::c_int;
+    fn pam_acct_mgmt(pamh: *mut pam_handle_t, flags: c::c_int) -> c::c_int;
+    fn pam_strerror(pamh: *mut pam_handle_t, errnum: c::c_int) -> *const c::c_char;
+}
+
+#[derive(Debug, Error)]
+pub enum PamError {
+    #[error("Could not determine the name of the current user")]
+    UnknownUser,
+    #[error("Could not start a PAM transaction: {0}")]
+    Start(c::c_int),
+    #[error("Authentication failed: {0}")]
+    Authenticate(String),
+    #[error("Account validation failed: {0}")]
+    AcctMgmt(String),
+}
+
+/// Authenticates the current user with the given password.
+///
+/// This function blocks until PAM has made a decision.
+pub fn authenticate(service: &str, password: &str) -> Result<(), PamError> {
+    let user = unsafe {
+        let pw = c::getpwuid(c::getuid());
+        if pw.is_null() || (*pw).pw_name.is_null() {
+            return Err(PamError::UnknownUser);
+        }
+        CStr::from_ptr((*pw).pw_name).to_owned()
+    };
+    let password = password.into_ustr();
+    let conv = pam_conv {
+        conv: conversation,
+        appdata_ptr: password.as_ptr() as *mut c::c_void,
+    };
+    let service = service.into_ustr();
+    let mut pamh = ptr::null_mut();
+    let res = unsafe { pam_start(service.as_ptr(), user.as_ptr(), &conv, &mut pamh) };
+    if res != PAM_SUCCESS {
+        return Err(PamError::Start(res));
+    }
+    let strerror = |res| unsafe {
+        CStr::from_ptr(pam_strerror(pamh, res))
+            .to_string_lossy()
+            .into_owned()
+    };
+    let mut res = unsafe { pam_authenticate(pamh, 0) };
+    let mut err = None;
+    if res != PAM_SUCCESS {
+        err = Some(PamError::Authenticate(strerror(res)));
+    } else {
+        res = unsafe { pam_acct_mgmt(pamh, 0) };
+        if res != PAM_SUCCESS {
+            err = Some(PamError::AcctMgmt(strerror(res)));
+        }
+    }
+    unsafe {
+        pam_end(pamh, res);
+    }
+    match err {
+        Some(e) => Err(e),
+        _ => Ok(()),
+    }
+}
+
+unsafe extern "C" fn conversation(
+    num_msg: c::c_int,
+    msg: *mut *const pam_message,
+    resp: *mut *mut pam_response,
+    appdata_ptr: *mut c::c_void,
+) -> c::c_int {
+    if num_msg <= 0 {
+        return PAM_CONV_ERR;
+    }
+    unsafe {
+        let responses = c::calloc(num_msg as usize, size_of::<pam_response>()) as *mut pam_response;
+        if responses.is_null() {
+            return PAM_BUF_ERR;
+        }
+        for i in 0..num_msg as usize {
+            let msg = &**msg.add(i);
+            match msg.msg_style {
+                PAM_PROMPT_ECHO_OFF | PAM_PROMPT_ECHO_ON => {
+                    let pw = c::strdup(appdata_ptr as *const c::c_char);
+                    if pw.is_null() {
+                        for j in 0..i {
+                            c::free((*responses.add(j)).resp as _);
+                        }
+                        c::free(responses as _);
+                        return PAM_BUF_ERR;
+                    }
+                    (*responses.add(i)).resp = pw;
+                }
+                _ => {}
+            }
+        }
+        *resp = responses;
+    }
+    PAM_SUCCESS
+}
@@ -11,6 +11,7 @@ use {
                     PW_CLIENT_NODE_VERSION,
                 },
                 pw_core::{PwCore, PwCoreMethods, PW_CORE_VERSION},
+                pw_node::{PwNode, PW_NODE_INTERFACE, PW_NODE_VERSION},
                 pw_registry::{PwRegistry, PW_REGISTRY_VERSION},
             },
             pw_mem::PwMemPool,
@@ -112,6 +113,19 @@ impl PwCon {
         node
     }
 
+    pub fn bind_node(self: &Rc<Self>, registry: &PwRegistry, id: u32) -> Rc<PwNode> {
+        let node = Rc::new(PwNode {
+            data: self.proxy_data(),
+            _con: self.clone(),
+            owner: Default::default(),
+        });
+        if !self.dead.get() {
+            self.objects.set(node.data.id, node.clone());
+        }
+        registry.bind(id, PW_NODE_INTERFACE, PW_NODE_VERSION, node.data.id);
+        node
+    }
+
     pub fn destroy_obj(&self, obj: &impl PwObject) {
         obj.break_loops();
         self.send2(0, "core", PwCoreMethods::Destroy, |f| {
@@ -218,11 +232,11 @@ impl PwCon {
         });
     }
 
-    #[expect(dead_code)]
     pub fn get_registry(self: &Rc<Self>) -> Rc<PwRegistry> {
         let registry = Rc::new(PwRegistry {
             data: self.proxy_data(),
-            _con: self.clone(),
+            con: self.clone(),
+            owner: Default::default(),
         });
         if !self.dead.get() {
             self.objects.set(registry.data.id, registry.clone());
@@ -261,13 +275,13 @@ impl PwCon {
         });
     }
 
-    pub fn send_properties(&self) {
+    pub fn send_properties(&self, application_name: &str) {
         self.send2(1, "client", PwClientMethods::UpdateProperties, |f| {
             f.write_struct(|f| {
                 f.write_struct(|f| {
                     f.write_int(1);
                     f.write_string("application.name");
-                    f.write_string("jay-portal");
+                    f.write_string(application_name);
                 });
             });
         });
@@ -299,7 +313,11 @@ impl Drop for PwConHolder {
 }
 
 impl PwConHolder {
-    pub async fn new(eng: &Rc<AsyncEngine>, ring: &Rc<IoUring>) -> Result<Rc<Self>, PwConError> {
+    pub async fn new(
+        eng: &Rc<AsyncEngine>,
+        ring: &Rc<IoUring>,
+        application_name: &str,
+    ) -> Result<Rc<Self>, PwConError> {
         let fd = match uapi::socket(c::AF_UNIX, c::SOCK_STREAM | c::SOCK_CLOEXEC, 0) {
             Ok(fd) => Rc::new(fd),
             Err(e) => return Err(PwConError::CreateSocket(e.into())),
@@ -345,7 +363,7 @@ impl PwConHolder {
         data.objects.set(0, core.clone());
         data.objects.set(1, client.clone());
         data.send_hello();
-        data.send_properties();
+        data.send_properties(application_name);
         let con = Rc::new(PwConHolder {
             outgoing: Cell::new(Some(
                 eng.spawn("pw outgoing", data.clone().handle_outgoing()),
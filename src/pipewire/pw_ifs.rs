@@ -1,4 +1,5 @@
 pub mod pw_client;
 pub mod pw_client_node;
 pub mod pw_core;
+pub mod pw_node;
 pub mod pw_registry;
@@ -134,7 +134,7 @@ pub struct PwClientNodeBufferConfig {
 }
 
 pub struct PwClientNodeBuffer {
-    pub _meta_header: Option<Rc<PwMemTyped<spa_meta_header>>>,
+    pub meta_header: Option<Rc<PwMemTyped<spa_meta_header>>>,
     pub _meta_busy: Option<Rc<PwMemTyped<spa_meta_busy>>>,
     pub meta_video_crop: Option<Rc<PwMemTyped<spa_meta_region>>>,
     pub chunks: Vec<Rc<PwMemTyped<spa_chunk>>>,
@@ -676,7 +676,7 @@ impl PwClientNode {
             }
 
             res.push(Rc::new(PwClientNodeBuffer {
-                _meta_header: meta_header,
+                meta_header,
                 _meta_busy: meta_busy,
                 meta_video_crop,
                 chunks,
@@ -0,0 +1,79 @@
+use {
+    crate::{
+        pipewire::{
+            pw_con::PwCon,
+            pw_object::{PwObject, PwObjectData},
+            pw_parser::{PwParser, PwParserError},
+        },
+        utils::clonecell::CloneCell,
+    },
+    ahash::AHashMap,
+    bstr::BString,
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub const PW_NODE_INTERFACE: &str = "PipeWire:Interface:Node";
+pub const PW_NODE_VERSION: i32 = 3;
+
+pub const PW_NODE_STATE_RUNNING: u32 = 3;
+
+pw_opcodes! {
+    PwNodeEvents;
+
+    Info = 0,
+    Param = 1,
+}
+
+pub trait PwNodeOwner {
+    fn info(&self, state: u32, props: &AHashMap<BString, BString>);
+}
+
+pub struct PwNode {
+    pub data: PwObjectData,
+    pub _con: Rc<PwCon>,
+    pub owner: CloneCell<Option<Rc<dyn PwNodeOwner>>>,
+}
+
+impl PwNode {
+    fn handle_info(&self, mut p: PwParser<'_>) -> Result<(), PwNodeError> {
+        let s1 = p.read_struct()?;
+        let mut p2 = s1.fields;
+        let _id = p2.read_int()?;
+        let _max_input_ports = p2.read_int()?;
+        let _max_output_ports = p2.read_int()?;
+        let _change_mask = p2.read_long()?;
+        let _n_input_ports = p2.read_int()?;
+        let _n_output_ports = p2.read_int()?;
+        let state = p2.read_id()?;
+        let _error = p2.read_pod()?;
+        let props = p2.read_dict_struct()?;
+        if let Some(owner) = self.owner.get() {
+            owner.info(state, &props);
+        }
+        Ok(())
+    }
+
+    fn handle_param(&self, _p: PwParser<'_>) -> Result<(), PwNodeError> {
+        Ok(())
+    }
+}
+
+pw_object_base! {
+    PwNode, "node", PwNodeEvents;
+
+    Info => handle_info,
+    Param => handle_param,
+}
+
+impl PwObject for PwNode {
+    fn break_loops(&self) {
+        self.owner.take();
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum PwNodeError {
+    #[error(transparent)]
+    PwParserError(#[from] PwParserError),
+}
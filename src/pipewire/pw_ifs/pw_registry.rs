@@ -1,15 +1,27 @@
 use {
-    crate::pipewire::{
-        pw_con::PwCon,
-        pw_object::{PwObject, PwObjectData},
-        pw_parser::{PwParser, PwParserError},
+    crate::{
+        pipewire::{
+            pw_con::PwCon,
+            pw_object::{PwObject, PwObjectData},
+            pw_parser::{PwParser, PwParserError},
+        },
+        utils::clonecell::CloneCell,
     },
+    ahash::AHashMap,
+    bstr::{BStr, BString},
     std::rc::Rc,
     thiserror::Error,
 };
 
 pub const PW_REGISTRY_VERSION: i32 = 3;
 
+pw_opcodes! {
+    PwRegistryMethods;
+
+    Bind = 1,
+    Destroy = 2,
+}
+
 pw_opcodes! {
     PwRegistryEvents;
 
@@ -17,17 +29,50 @@ pw_opcodes! {
     GlobalRemove = 1,
 }
 
+pub trait PwRegistryOwner {
+    fn global(self: Rc<Self>, id: u32, ty: &BStr, props: &AHashMap<BString, BString>);
+    fn global_remove(&self, id: u32);
+}
+
 pub struct PwRegistry {
     pub data: PwObjectData,
-    pub _con: Rc<PwCon>,
+    pub con: Rc<PwCon>,
+    pub owner: CloneCell<Option<Rc<dyn PwRegistryOwner>>>,
 }
 
 impl PwRegistry {
-    fn handle_global(&self, _p: PwParser<'_>) -> Result<(), PwRegistryError> {
+    pub fn bind(&self, id: u32, interface: &str, version: i32, new_id: u32) {
+        self.con.send(self, PwRegistryMethods::Bind, |f| {
+            f.write_struct(|f| {
+                f.write_uint(id);
+                f.write_string(interface);
+                f.write_int(version);
+                f.write_uint(new_id);
+            });
+        });
+    }
+
+    fn handle_global(&self, mut p: PwParser<'_>) -> Result<(), PwRegistryError> {
+        let s1 = p.read_struct()?;
+        let mut p2 = s1.fields;
+        let id = p2.read_uint()?;
+        let _permissions = p2.read_int()?;
+        let ty = p2.read_string()?;
+        let _version = p2.read_int()?;
+        let props = p2.read_dict_struct()?;
+        if let Some(owner) = self.owner.get() {
+            owner.global(id, ty, &props);
+        }
         Ok(())
     }
 
-    fn handle_global_remove(&self, _p: PwParser<'_>) -> Result<(), PwRegistryError> {
+    fn handle_global_remove(&self, mut p: PwParser<'_>) -> Result<(), PwRegistryError> {
+        let s1 = p.read_struct()?;
+        let mut p2 = s1.fields;
+        let id = p2.read_uint()?;
+        if let Some(owner) = self.owner.get() {
+            owner.global_remove(id);
+        }
         Ok(())
     }
 }
@@ -39,7 +84,11 @@ pw_object_base! {
     GlobalRemove => handle_global_remove,
 }
 
-impl PwObject for PwRegistry {}
+impl PwObject for PwRegistry {
+    fn break_loops(&self) {
+        self.owner.take();
+    }
+}
 
 #[derive(Debug, Error)]
 pub enum PwRegistryError {
@@ -188,7 +188,7 @@ async fn run_async(
             fatal!("Could not create a timer wheel: {}", ErrorFmt(e));
         }
     };
-    let pw_con = match PwConHolder::new(&eng, &ring).await {
+    let pw_con = match PwConHolder::new(&eng, &ring, "jay-portal").await {
         Ok(p) => Some(p),
         Err(e) => {
             log::error!("Could not connect to pipewire: {}", ErrorFmt(e));
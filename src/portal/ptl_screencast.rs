@@ -11,12 +11,12 @@ use {
             pw_ifs::pw_client_node::{
                 PwClientNode, PwClientNodeBufferConfig, PwClientNodeOwner, PwClientNodePort,
                 PwClientNodePortSupportedFormat, PwClientNodePortSupportedFormats,
-                SUPPORTED_META_VIDEO_CROP,
+                SUPPORTED_META_HEADER, SUPPORTED_META_VIDEO_CROP,
             },
             pw_pod::{
-                spa_point, spa_rectangle, spa_region, PwPodRectangle, SPA_DATA_DmaBuf,
-                SPA_MEDIA_SUBTYPE_raw, SPA_MEDIA_TYPE_video, SpaChunkFlags, SPA_STATUS_HAVE_DATA,
-                SPA_VIDEO_FORMAT_UNKNOWN,
+                spa_meta_header, spa_point, spa_rectangle, spa_region, PwPodRectangle,
+                SPA_DATA_DmaBuf, SPA_MEDIA_SUBTYPE_raw, SPA_MEDIA_TYPE_video, SpaChunkFlags,
+                SpaMetaHeaderFlags, SPA_STATUS_HAVE_DATA, SPA_VIDEO_FORMAT_UNKNOWN,
             },
         },
         portal::{
@@ -26,10 +26,12 @@ use {
             ptl_session::{PortalSession, PortalSessionReply},
             PortalState, PORTAL_SUCCESS,
         },
+        time::Time,
         utils::{
             clonecell::{CloneCell, UnsafeCellCloneSafe},
             copyhashmap::CopyHashMap,
             errorfmt::ErrorFmt,
+            numcell::NumCell,
             opaque::Opaque,
         },
         video::{dmabuf::DmaBuf, Modifier, LINEAR_MODIFIER},
@@ -140,6 +142,7 @@ pub struct StartedScreencast {
     pub modifier: Cell<Modifier>,
     pub width: Cell<i32>,
     pub height: Cell<i32>,
+    pub seq: NumCell<u64>,
 }
 
 bitflags! {
@@ -203,7 +206,8 @@ impl PwClientNodeOwner for StartingScreencast {
         let num_buffers = (jsc_version >= CLIENT_BUFFERS_SINCE).then_some(3);
         let port = self.node.create_port(true, supported_formats, num_buffers);
         port.can_alloc_buffers.set(true);
-        port.supported_metas.set(SUPPORTED_META_VIDEO_CROP);
+        port.supported_metas
+            .set(SUPPORTED_META_HEADER | SUPPORTED_META_VIDEO_CROP);
         let jsc = self.dpy.jc.create_screencast();
         match &self.target {
             ScreencastTarget::Output(o) => {
@@ -244,6 +248,7 @@ impl PwClientNodeOwner for StartingScreencast {
             modifier: Cell::new(LINEAR_MODIFIER),
             width: Cell::new(1),
             height: Cell::new(1),
+            seq: Default::default(),
         });
         self.session
             .sc_phase
@@ -649,7 +654,7 @@ impl UsrJayScreencastOwner for StartedScreencast {
         self.buffers_valid.set(false);
     }
 
-    fn ready(&self, ev: &Ready) {
+    fn ready(&self, ev: &Ready, present_nsec: Option<u64>) {
         let idx = ev.idx as usize;
         let buffers = &*self.buffers.borrow();
         let pbuffers = self.port.buffers.borrow();
@@ -680,6 +685,15 @@ impl UsrJayScreencastOwner for StartedScreencast {
             chunk.stride = plane.stride;
             chunk.size = plane.stride * buffer.height as u32;
         }
+        if let Some(header) = &pbuffer.meta_header {
+            *unsafe { header.write() } = spa_meta_header {
+                flags: SpaMetaHeaderFlags::none(),
+                offset: 0,
+                pts: present_nsec.unwrap_or_else(|| Time::now_unchecked().nsec()) as i64,
+                dts_offset: 0,
+                seq: self.seq.fetch_add(1),
+            };
+        }
         if let Some(crop) = &pbuffer.meta_video_crop {
             unsafe { crop.write() }.region = spa_region {
                 position: spa_point { x: 0, y: 0 },
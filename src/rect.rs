@@ -143,10 +143,10 @@ impl Rect {
         dx * dx + dy * dy
     }
 
-    #[expect(dead_code)]
+    #[cfg_attr(not(feature = "it"), expect(dead_code))]
     pub fn contains_rect(&self, rect: &Self) -> bool {
         self.raw.x1 <= rect.raw.x1
-            && self.raw.y1 <= rect.raw.x1
+            && self.raw.y1 <= rect.raw.y1
             && rect.raw.x2 <= self.raw.x2
             && rect.raw.y2 <= self.raw.y2
     }
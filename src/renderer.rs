@@ -1,12 +1,17 @@
 use {
     crate::{
         gfx_api::{AcquireSync, GfxApiOpt, ReleaseSync, SampleRect},
-        ifs::wl_surface::{
-            x_surface::xwindow::Xwindow,
-            xdg_surface::{xdg_toplevel::XdgToplevel, XdgSurface},
-            zwlr_layer_surface_v1::ZwlrLayerSurfaceV1,
-            SurfaceBuffer, WlSurface,
+        ifs::{
+            wl_seat::seat_hint::SEAT_HINT_PADDING,
+            wl_surface::{
+                x_surface::xwindow::Xwindow,
+                xdg_surface::{xdg_toplevel::XdgToplevel, XdgSurface},
+                zwlr_layer_surface_v1::ZwlrLayerSurfaceV1,
+                SurfaceBuffer, WlSurface,
+            },
+            xdg_toplevel_icon_v1::ToplevelIcon,
         },
+        notifications::NOTIFICATION_PADDING,
         rect::Rect,
         renderer::renderer_base::RendererBase,
         scale::Scale,
@@ -17,11 +22,30 @@ use {
             ToplevelNodeBase, WorkspaceNode,
         },
     },
-    std::{ops::Deref, rc::Rc, slice},
+    std::{iter, ops::Deref, rc::Rc, slice},
 };
 
 pub mod renderer_base;
 
+const INPUT_REGION_COLOR: Color = Color {
+    r: 0.0,
+    g: 0.25,
+    b: 0.0,
+    a: 0.25,
+};
+const FALLBACK_LOCK_SCREEN_COLOR: Color = Color {
+    r: 1.0,
+    g: 0.0,
+    b: 0.0,
+    a: 1.0,
+};
+const OPAQUE_REGION_COLOR: Color = Color {
+    r: 0.0,
+    g: 0.0,
+    b: 0.25,
+    a: 0.25,
+};
+
 pub struct Renderer<'a> {
     pub base: RendererBase<'a>,
     pub state: &'a State,
@@ -58,6 +82,8 @@ impl Renderer<'_> {
                 if surface.surface.buffer.is_some() {
                     self.render_surface(&surface.surface, x, y, None);
                 }
+            } else if self.state.lock.fallback.is_active() {
+                self.render_fallback_lock_screen(output, x, y);
             }
             return;
         }
@@ -72,6 +98,7 @@ impl Renderer<'_> {
             };
         }
         let mut fullscreen = None;
+        let mut swiped_stacked = vec![];
         if let Some(ws) = output.workspace.get() {
             fullscreen = ws.fullscreen.get();
         }
@@ -128,8 +155,31 @@ impl Renderer<'_> {
                         None,
                         AcquireSync::None,
                         ReleaseSync::None,
+                        None,
                     );
                 }
+                if let Some(title) = &rd.focused_title {
+                    if let Some(texture) = title.tex.texture() {
+                        if !rd.focused_title_rect.is_empty() {
+                            let bounds = self.base.scale_rect(rd.focused_title_rect.move_(x, y));
+                            let (x, y) = self.base.scale_point(x + title.tex_x, y);
+                            self.base.render_texture(
+                                &texture,
+                                None,
+                                x,
+                                y,
+                                None,
+                                None,
+                                scale,
+                                Some(&bounds),
+                                None,
+                                AcquireSync::None,
+                                ReleaseSync::None,
+                                None,
+                            );
+                        }
+                    }
+                }
                 if let Some(status) = &rd.status {
                     if let Some(texture) = status.tex.texture() {
                         let (x, y) = self.base.scale_point(x + status.tex_x, y);
@@ -145,6 +195,7 @@ impl Renderer<'_> {
                             None,
                             AcquireSync::None,
                             ReleaseSync::None,
+                            None,
                         );
                     }
                 }
@@ -158,13 +209,28 @@ impl Renderer<'_> {
                 }
             }
             if let Some(ws) = output.workspace.get() {
-                self.render_workspace(&ws, x, y + th + 1);
+                let offset = output.workspace_swipe_offset.get();
+                self.render_workspace(&ws, x + offset, y + th + 1);
+                if offset != 0 {
+                    self.render_workspace_stacked(&ws, &opos, offset);
+                    swiped_stacked.extend(ws.stacked.iter().map(|s| s.node_id()));
+                    if let Some(neighbor) = output.workspace_swipe_neighbor(offset) {
+                        let dx = offset - offset.signum() * opos.width();
+                        if let Some(fs) = neighbor.fullscreen.get() {
+                            let (x, y) = non_exclusive_rect.translate(x, y);
+                            fs.tl_as_node().node_render(self, x + dx, y, None);
+                        } else {
+                            self.render_workspace(&neighbor, x + dx, y + th + 1);
+                            self.render_workspace_stacked(&neighbor, &opos, dx);
+                        }
+                    }
+                }
             }
         }
         macro_rules! render_stacked {
             ($stack:expr) => {
                 for stacked in $stack.iter() {
-                    if stacked.node_visible() {
+                    if stacked.node_visible() && !swiped_stacked.contains(&stacked.node_id()) {
                         self.base.ops.push(GfxApiOpt::Sync);
                         let pos = stacked.node_absolute_position();
                         if pos.intersects(&opos) {
@@ -186,14 +252,185 @@ impl Renderer<'_> {
                 self.base.fill_boxes(&[bounds], &color);
             }
         }
+        if self.state.notifications.is_shown_on(output) {
+            self.render_notifications(output, x, y);
+        }
+        self.render_seat_hints(output, x, y);
+    }
+
+    fn render_seat_hints(&mut self, output: &OutputNode, x: i32, y: i32) {
+        let theme = &self.state.theme;
+        let bw = theme.sizes.border_width.get();
+        let border = theme.colors.notification_border.get();
+        let background = theme.colors.notification_background.get();
+        let scale = self.base.scale;
+        let seats = self.state.globals.seats.lock();
+        let hints = seats
+            .values()
+            .flat_map(|seat| seat.hints())
+            .chain(iter::once(&self.state.ei_authorization.hint));
+        for hint in hints {
+            let Some((rect, texture)) = hint.layout(self.state, output, scale) else {
+                continue;
+            };
+            self.base.fill_boxes2(slice::from_ref(&rect), &border, x, y);
+            let Some(inner) = Rect::new_sized(
+                rect.x1() + bw,
+                rect.y1() + bw,
+                rect.width() - 2 * bw,
+                rect.height() - 2 * bw,
+            ) else {
+                continue;
+            };
+            self.base
+                .fill_boxes2(slice::from_ref(&inner), &background, x, y);
+            let bounds = self.base.scale_rect(inner.move_(x, y));
+            let (tx, ty) = self.base.scale_point(
+                x + inner.x1() + SEAT_HINT_PADDING,
+                y + inner.y1() + SEAT_HINT_PADDING,
+            );
+            self.base.render_texture(
+                &texture,
+                None,
+                tx,
+                ty,
+                None,
+                None,
+                scale,
+                Some(&bounds),
+                None,
+                AcquireSync::None,
+                ReleaseSync::None,
+                None,
+            );
+        }
+    }
+
+    fn render_fallback_lock_screen(&mut self, output: &OutputNode, x: i32, y: i32) {
+        let pos = output.global.pos.get();
+        let background = match self.state.lock.fallback.prompt_enabled() {
+            true => self.state.theme.colors.background.get(),
+            false => FALLBACK_LOCK_SCREEN_COLOR,
+        };
+        let rect = Rect::new_sized_unchecked(0, 0, pos.width(), pos.height());
+        self.base
+            .fill_boxes2(slice::from_ref(&rect), &background, x, y);
+        let scale = self.base.scale;
+        let Some((rect, texture)) =
+            self.state
+                .lock
+                .fallback
+                .layout(pos.width(), pos.height(), scale)
+        else {
+            return;
+        };
+        let (tx, ty) = self.base.scale_point(x + rect.x1(), y + rect.y1());
+        self.base.render_texture(
+            &texture,
+            None,
+            tx,
+            ty,
+            None,
+            None,
+            scale,
+            None,
+            None,
+            AcquireSync::None,
+            ReleaseSync::None,
+            None,
+        );
+    }
+
+    fn render_notifications(&mut self, output: &OutputNode, x: i32, y: i32) {
+        let theme = &self.state.theme;
+        let bw = theme.sizes.border_width.get();
+        let th = theme.sizes.title_height.get();
+        let border = theme.colors.notification_border.get();
+        let background = theme.colors.notification_background.get();
+        let scale = self.base.scale;
+        for (notification, rect) in self.state.notifications.layout(self.state, output) {
+            self.base.fill_boxes2(slice::from_ref(&rect), &border, x, y);
+            let Some(inner) = Rect::new_sized(
+                rect.x1() + bw,
+                rect.y1() + bw,
+                rect.width() - 2 * bw,
+                rect.height() - 2 * bw,
+            ) else {
+                continue;
+            };
+            self.base
+                .fill_boxes2(slice::from_ref(&inner), &background, x, y);
+            let textures = notification.textures.borrow();
+            let Some(textures) = textures.get(&scale) else {
+                continue;
+            };
+            let bounds = self.base.scale_rect(inner.move_(x, y));
+            let lines = [(&textures.summary, 0), (&textures.body, th)];
+            for (tex, dy) in lines {
+                let Some(texture) = tex.texture() else {
+                    continue;
+                };
+                let (tx, ty) = self.base.scale_point(
+                    x + inner.x1() + NOTIFICATION_PADDING,
+                    y + inner.y1() + NOTIFICATION_PADDING + dy,
+                );
+                self.base.render_texture(
+                    &texture,
+                    None,
+                    tx,
+                    ty,
+                    None,
+                    None,
+                    scale,
+                    Some(&bounds),
+                    None,
+                    AcquireSync::None,
+                    ReleaseSync::None,
+                    None,
+                );
+            }
+        }
     }
 
     pub fn render_workspace(&mut self, workspace: &WorkspaceNode, x: i32, y: i32) {
         if let Some(node) = workspace.container.get() {
-            self.render_container(&node, x, y)
+            let pos = workspace.position.get();
+            let cpos = node.tl_data().pos.get();
+            self.render_container(&node, x + cpos.x1() - pos.x1(), y + cpos.y1() - pos.y1())
+        }
+    }
+
+    fn render_workspace_stacked(&mut self, workspace: &WorkspaceNode, opos: &Rect, dx: i32) {
+        for stacked in workspace.stacked.iter() {
+            self.base.ops.push(GfxApiOpt::Sync);
+            let pos = stacked.node_absolute_position();
+            let (x, y) = opos.translate(pos.x1(), pos.y1());
+            stacked.node_render(self, x + dx, y, None);
         }
     }
 
+    /// Renders a workspace, including its floating windows, regardless of whether it is
+    /// currently visible.
+    pub fn render_workspace_thumbnail(&mut self, workspace: &WorkspaceNode) {
+        let pos = workspace.position.get();
+        if let Some(fs) = workspace.fullscreen.get() {
+            let opos = workspace.output.get().global.pos.get();
+            let (x, y) = pos.translate(opos.x1(), opos.y1());
+            fs.tl_as_node().node_render(self, x, y, None);
+        } else {
+            self.render_workspace(workspace, 0, 0);
+            for stacked in workspace.stacked.iter() {
+                self.base.ops.push(GfxApiOpt::Sync);
+                let spos = stacked.node_absolute_position();
+                let (x, y) = pos.translate(spos.x1(), spos.y1());
+                stacked.node_render(self, x, y, None);
+            }
+        }
+        let rects = self.state.redacted_workspace_rects(workspace);
+        self.render_redactions(&rects, &pos, 0, 0);
+        self.render_capture_overlay(&pos, 0, 0);
+    }
+
     pub fn render_placeholder(
         &mut self,
         placeholder: &PlaceholderNode,
@@ -223,6 +460,7 @@ impl Renderer<'_> {
                     None,
                     AcquireSync::None,
                     ReleaseSync::None,
+                    None,
                 );
             }
         }
@@ -252,8 +490,14 @@ impl Renderer<'_> {
                 self.base.fill_boxes2(std::slice::from_ref(lar), &c, x, y);
             }
             if let Some(titles) = rd.titles.get(&self.base.scale) {
+                let th = self.state.theme.sizes.title_height.get();
                 for title in titles {
-                    let (x, y) = self.base.scale_point(x + title.x, y + title.y);
+                    let mut tx = title.x;
+                    if let Some(icon) = &title.icon {
+                        self.render_title_icon(icon, x + tx, y + title.y, th);
+                        tx += th;
+                    }
+                    let (x, y) = self.base.scale_point(x + tx, y + title.y);
                     self.base.render_texture(
                         &title.tex,
                         None,
@@ -266,6 +510,7 @@ impl Renderer<'_> {
                         None,
                         AcquireSync::None,
                         ReleaseSync::None,
+                        None,
                     );
                 }
             }
@@ -347,6 +592,70 @@ impl Renderer<'_> {
         self.base.fill_boxes(slice::from_ref(rect), &color);
     }
 
+    /// Blacks out the windows that must not be captured.
+    ///
+    /// `area` is the absolute position of the captured area. `x_off` and `y_off` are in
+    /// pixels.
+    pub fn render_capture_redactions(&mut self, area: &Rect, x_off: i32, y_off: i32) {
+        let rects = self.state.redacted_capture_rects();
+        self.render_redactions(&rects, area, x_off, y_off);
+    }
+
+    fn render_redactions(&mut self, rects: &[Rect], area: &Rect, x_off: i32, y_off: i32) {
+        if rects.is_empty() {
+            return;
+        }
+        let boxes: Vec<_> = rects
+            .iter()
+            .map(|r| {
+                let r = r.move_(-area.x1(), -area.y1());
+                self.base.scale_rect(r).move_(x_off, y_off)
+            })
+            .collect();
+        self.base.ops.push(GfxApiOpt::Sync);
+        self.base.fill_scaled_boxes(&boxes, &Color::SOLID_BLACK);
+    }
+
+    /// Renders the capture overlay into a captured area.
+    ///
+    /// The arguments are the same as for `render_capture_redactions`.
+    pub fn render_capture_overlay(&mut self, area: &Rect, x_off: i32, y_off: i32) {
+        let scale = self.base.scale;
+        let Some(layout) =
+            self.state
+                .capture_overlay
+                .layout(self.state, area.width(), area.height(), scale)
+        else {
+            return;
+        };
+        self.base.ops.push(GfxApiOpt::Sync);
+        let mut textures = vec![];
+        if let Some((bg, rect, texture)) = layout.text {
+            let color = self.state.theme.colors.bar_background.get();
+            let bg = self.base.scale_rect(bg).move_(x_off, y_off);
+            self.base.fill_scaled_boxes(slice::from_ref(&bg), &color);
+            textures.push((rect, texture));
+        }
+        textures.extend(layout.image);
+        for (rect, texture) in textures {
+            let (x, y) = self.base.scale_point(rect.x1(), rect.y1());
+            self.base.render_texture(
+                &texture,
+                None,
+                x + x_off,
+                y + y_off,
+                None,
+                None,
+                scale,
+                None,
+                None,
+                AcquireSync::None,
+                ReleaseSync::None,
+                None,
+            );
+        }
+    }
+
     pub fn render_surface(&mut self, surface: &WlSurface, x: i32, y: i32, bounds: Option<&Rect>) {
         let (x, y) = self.base.scale_point(x, y);
         self.render_surface_scaled(surface, x, y, None, bounds, false);
@@ -421,6 +730,7 @@ impl Renderer<'_> {
         bounds: Option<&Rect>,
     ) {
         if let Some(tex) = buffer.buffer.get_texture(surface) {
+            let (acquire_sync, release_sync) = buffer.buffer.texture_sync(buffer.release_sync);
             self.base.render_texture(
                 &tex,
                 alpha,
@@ -431,8 +741,9 @@ impl Renderer<'_> {
                 self.base.scale,
                 bounds,
                 Some(buffer.clone()),
-                AcquireSync::Unnecessary,
-                buffer.release_sync,
+                acquire_sync,
+                release_sync,
+                surface.color_transform(),
             );
         } else if let Some(color) = &buffer.buffer.color {
             if let Some(rect) = Rect::new_sized(x, y, tsize.0, tsize.1) {
@@ -452,6 +763,86 @@ impl Renderer<'_> {
         } else {
             log::info!("live buffer has neither a texture nor is a single-pixel buffer");
         }
+        if self.state.show_surface_regions.get() && !surface.is_cursor() {
+            self.render_surface_regions(surface, x, y, tsize, bounds);
+        }
+    }
+
+    fn render_surface_regions(
+        &mut self,
+        surface: &WlSurface,
+        x: i32,
+        y: i32,
+        tsize: (i32, i32),
+        bounds: Option<&Rect>,
+    ) {
+        let (width, height) = surface.buffer_abs_pos.get().size();
+        if width == 0 || height == 0 {
+            return;
+        }
+        let surface_rect = Rect::new_sized(0, 0, width, height).unwrap();
+        let to_pixels = |rect: Rect| {
+            let (mut x1, mut y1, mut x2, mut y2) = (rect.x1(), rect.y1(), rect.x2(), rect.y2());
+            client_wire_scale_to_logical!(surface.client, x1, y1, x2, y2);
+            let rect = Rect::new(x1, y1, x2, y2)?.intersect(surface_rect);
+            let rect = Rect::new(
+                x + rect.x1() * tsize.0 / width,
+                y + rect.y1() * tsize.1 / height,
+                x + rect.x2() * tsize.0 / width,
+                y + rect.y2() * tsize.1 / height,
+            )?;
+            let rect = match bounds {
+                None => rect,
+                Some(bounds) => rect.intersect(*bounds),
+            };
+            (!rect.is_empty()).then_some(rect)
+        };
+        let input: Vec<_> = match surface.input_region() {
+            Some(region) => region
+                .rects()
+                .iter()
+                .filter_map(|r| to_pixels(*r))
+                .collect(),
+            None => to_pixels(surface_rect).into_iter().collect(),
+        };
+        let opaque: Vec<_> = match surface.opaque_region() {
+            Some(region) => region
+                .rects()
+                .iter()
+                .filter_map(|r| to_pixels(*r))
+                .collect(),
+            None => vec![],
+        };
+        self.base.ops.push(GfxApiOpt::Sync);
+        self.base.fill_scaled_boxes(&input, &INPUT_REGION_COLOR);
+        self.base.fill_scaled_boxes(&opaque, &OPAQUE_REGION_COLOR);
+    }
+
+    fn render_title_icon(&mut self, icon: &ToplevelIcon, x: i32, y: i32, th: i32) {
+        let Some(rect) = Rect::new_sized(x + 1, y + 1, th - 2, th - 2) else {
+            return;
+        };
+        let rect = self.base.scale_rect(rect);
+        if rect.is_empty() {
+            return;
+        }
+        let Some(tex) = icon.texture(self.state, rect.width()) else {
+            return;
+        };
+        self.base.render_texture(
+            &tex,
+            None,
+            rect.x1(),
+            rect.y1(),
+            None,
+            Some(rect.size()),
+            self.base.scale,
+            None,
+            None,
+            AcquireSync::None,
+            ReleaseSync::None,
+            None,
+        );
     }
 
     pub fn render_floating(&mut self, floating: &FloatNode, x: i32, y: i32) {
@@ -484,9 +875,14 @@ impl Renderer<'_> {
         let title_underline =
             [Rect::new_sized(x + bw, y + bw + th, pos.width() - 2 * bw, 1).unwrap()];
         self.base.fill_boxes(&title_underline, &uc);
+        let mut tx = x + bw;
+        if let Some(icon) = child.tl_data().icon.get() {
+            self.render_title_icon(&icon, tx, y + bw, th);
+            tx += th;
+        }
         if let Some(title) = floating.title_textures.borrow().get(&self.base.scale) {
             if let Some(texture) = title.texture() {
-                let (x, y) = self.base.scale_point(x + bw, y + bw);
+                let (x, y) = self.base.scale_point(tx, y + bw);
                 self.base.render_texture(
                     &texture,
                     None,
@@ -499,6 +895,7 @@ impl Renderer<'_> {
                     None,
                     AcquireSync::None,
                     ReleaseSync::None,
+                    None,
                 );
             }
         }
@@ -1,8 +1,8 @@
 use {
     crate::{
         gfx_api::{
-            AcquireSync, BufferResv, CopyTexture, FillRect, FramebufferRect, GfxApiOpt, GfxTexture,
-            ReleaseSync, SampleRect,
+            AcquireSync, BufferResv, ColorTransform, CopyTexture, FillRect, FramebufferRect,
+            GfxApiOpt, GfxTexture, ReleaseSync, SampleRect,
         },
         rect::Rect,
         scale::Scale,
@@ -146,6 +146,7 @@ impl RendererBase<'_> {
         buffer_resv: Option<Rc<dyn BufferResv>>,
         acquire_sync: AcquireSync,
         release_sync: ReleaseSync,
+        color_transform: Option<ColorTransform>,
     ) {
         let mut texcoord = tpoints.unwrap_or_else(SampleRect::identity);
 
@@ -188,6 +189,7 @@ impl RendererBase<'_> {
             buffer_resv,
             acquire_sync,
             release_sync,
+            color_transform,
         }));
     }
 }
@@ -1,25 +1,54 @@
 use {
     crate::{
         allocator::{AllocatorError, BufferObject, BufferUsage, BO_USE_RENDERING},
+        cpu_worker::{AsyncCpuWork, CpuJob, CpuWork, PendingJob},
         format::XRGB8888,
-        gfx_api::{needs_render_usage, AcquireSync, GfxError, ReleaseSync},
+        gfx_api::{needs_render_usage, AcquireSync, GfxError, GfxFramebuffer, ReleaseSync},
         scale::Scale,
         state::State,
+        tree::{ToplevelNode, WorkspaceNode},
+        utils::{copyhashmap::CopyHashMap, errorfmt::ErrorFmt, windows::WindowsExt},
         video::drm::DrmError,
     },
+    chrono::Local,
     indexmap::IndexMap,
     jay_config::video::Transform,
-    std::{ops::Deref, rc::Rc},
+    png::{BitDepth, ColorType, Encoder, SrgbRenderingIntent},
+    std::{
+        cell::RefCell,
+        fmt::Write,
+        io,
+        ops::Deref,
+        path::{Path, PathBuf},
+        rc::{Rc, Weak},
+    },
     thiserror::Error,
     uapi::OwnedFd,
 };
 
+/// Thumbnails of a workspace are re-rendered at most once per interval.
+const THUMBNAIL_INTERVAL_NSEC: u64 = 1_000_000_000;
+
+const DEFAULT_WINDOW_SCREENSHOT_FILENAME: &str = "%Y-%m-%d-%H%M%S_jay.png";
+
 #[derive(Debug, Error)]
 pub enum ScreenshooterError {
     #[error("There is no render context")]
     NoRenderContext,
     #[error("Display is empty")]
     EmptyDisplay,
+    #[error("Workspace has been destroyed")]
+    WorkspaceDestroyed,
+    #[error("Workspace is empty")]
+    EmptyWorkspace,
+    #[error("Workspace must not be captured")]
+    CaptureNotAllowed,
+    #[error("Thumbnail size must be positive")]
+    InvalidSize,
+    #[error("Window is empty")]
+    EmptyWindow,
+    #[error("The screenshot filename template is invalid")]
+    InvalidFilename,
     #[error(transparent)]
     AllocatorError(#[from] AllocatorError),
     #[error(transparent)]
@@ -32,23 +61,265 @@ pub enum ScreenshooterError {
     Modifiers,
 }
 
+#[derive(Clone)]
 pub struct Screenshot {
     pub drm: Option<Rc<OwnedFd>>,
     pub bo: Rc<dyn BufferObject>,
 }
 
+/// The most recent thumbnail of a workspace.
+pub struct WorkspaceThumbnail {
+    time_nsec: u64,
+    max_size: (i32, i32),
+    screenshot: Screenshot,
+}
+
 pub fn take_screenshot(
     state: &State,
     include_cursor: bool,
 ) -> Result<Screenshot, ScreenshooterError> {
-    let ctx = match state.render_ctx.get() {
-        Some(ctx) => ctx,
-        _ => return Err(ScreenshooterError::NoRenderContext),
-    };
     let extents = state.root.extents.get();
     if extents.is_empty() {
         return Err(ScreenshooterError::EmptyDisplay);
     }
+    let (screenshot, fb) = create_buffer(state, extents.width(), extents.height())?;
+    fb.render_node(
+        AcquireSync::Unnecessary,
+        ReleaseSync::Implicit,
+        state.root.deref(),
+        state,
+        Some(state.root.extents.get()),
+        Scale::from_int(1),
+        include_cursor,
+        true,
+        false,
+        false,
+        true,
+        Transform::None,
+    )?;
+    Ok(screenshot)
+}
+
+/// Renders a scaled-down image of a workspace that fits into the given size.
+///
+/// The workspace does not have to be visible. Requests within a short interval reuse the
+/// previous thumbnail.
+pub fn take_workspace_thumbnail(
+    state: &State,
+    ws: &WorkspaceNode,
+    max_width: i32,
+    max_height: i32,
+) -> Result<Screenshot, ScreenshooterError> {
+    if !ws.may_capture.get() {
+        return Err(ScreenshooterError::CaptureNotAllowed);
+    }
+    if max_width <= 0 || max_height <= 0 {
+        return Err(ScreenshooterError::InvalidSize);
+    }
+    let now = state.now_nsec();
+    if let Some(thumbnail) = &*ws.thumbnail.borrow() {
+        if thumbnail.max_size == (max_width, max_height)
+            && now.saturating_sub(thumbnail.time_nsec) < THUMBNAIL_INTERVAL_NSEC
+        {
+            return Ok(thumbnail.screenshot.clone());
+        }
+    }
+    let pos = ws.position.get();
+    if pos.is_empty() {
+        return Err(ScreenshooterError::EmptyWorkspace);
+    }
+    let factor = (max_width as f64 / pos.width() as f64)
+        .min(max_height as f64 / pos.height() as f64)
+        .clamp(0.01, 1.0);
+    let scale = Scale::from_f64(factor);
+    let scalef = scale.to_f64();
+    let width = ((pos.width() as f64 * scalef).round() as i32).clamp(1, max_width);
+    let height = ((pos.height() as f64 * scalef).round() as i32).clamp(1, max_height);
+    let (screenshot, fb) = create_buffer(state, width, height)?;
+    fb.render_workspace_thumbnail(
+        AcquireSync::Unnecessary,
+        ReleaseSync::Implicit,
+        ws,
+        state,
+        scale,
+    )?;
+    *ws.thumbnail.borrow_mut() = Some(WorkspaceThumbnail {
+        time_nsec: now,
+        max_size: (max_width, max_height),
+        screenshot: screenshot.clone(),
+    });
+    Ok(screenshot)
+}
+
+linear_ids!(WindowScreenshotIds, WindowScreenshotId, u64);
+
+/// Settings and in-flight jobs of compositor-side window screenshots.
+#[derive(Default)]
+pub struct WindowScreenshots {
+    pub directory: RefCell<Option<String>>,
+    pub filename: RefCell<Option<String>>,
+    ids: WindowScreenshotIds,
+    pending: CopyHashMap<WindowScreenshotId, PendingJob>,
+}
+
+impl WindowScreenshots {
+    pub fn clear(&self) {
+        for (_, pending) in self.pending.clear() {
+            pending.detach();
+        }
+    }
+
+    fn path(&self) -> Result<PathBuf, ScreenshooterError> {
+        let filename = self.filename.borrow();
+        let template = filename
+            .as_deref()
+            .unwrap_or(DEFAULT_WINDOW_SCREENSHOT_FILENAME);
+        let mut name = String::new();
+        if write!(name, "{}", Local::now().format(template)).is_err() {
+            return Err(ScreenshooterError::InvalidFilename);
+        }
+        match &*self.directory.borrow() {
+            Some(dir) => Ok(Path::new(dir).join(name)),
+            None => Ok(PathBuf::from(name)),
+        }
+    }
+}
+
+/// Renders a window and writes it to a PNG file.
+///
+/// The file is encoded and written on the CPU worker. Once it has been written, the
+/// config is notified.
+pub fn save_window_screenshot(
+    state: &Rc<State>,
+    tl: &dyn ToplevelNode,
+) -> Result<(), ScreenshooterError> {
+    let node = tl.tl_as_node();
+    let pos = node.node_absolute_position();
+    if pos.is_empty() {
+        return Err(ScreenshooterError::EmptyWindow);
+    }
+    let path = state.window_screenshots.path()?;
+    let (screenshot, fb) = create_buffer(state, pos.width(), pos.height())?;
+    fb.render_node(
+        AcquireSync::Unnecessary,
+        ReleaseSync::Implicit,
+        node,
+        state,
+        None,
+        Scale::from_int(1),
+        false,
+        false,
+        true,
+        false,
+        true,
+        Transform::None,
+    )?;
+    let map = screenshot.bo.map_read()?;
+    let id = state.window_screenshots.ids.next();
+    let job = Box::new(WindowScreenshotJob {
+        id,
+        state: Rc::downgrade(state),
+        work: WindowScreenshotWork {
+            data: unsafe { map.data() }.to_vec(),
+            width: pos.width(),
+            height: pos.height(),
+            stride: map.stride(),
+            path,
+            result: None,
+        },
+    });
+    let pending = state.cpu_worker.submit(job);
+    state.window_screenshots.pending.set(id, pending);
+    Ok(())
+}
+
+struct WindowScreenshotWork {
+    data: Vec<u8>,
+    width: i32,
+    height: i32,
+    stride: i32,
+    path: PathBuf,
+    result: Option<Result<(), io::Error>>,
+}
+
+struct WindowScreenshotJob {
+    id: WindowScreenshotId,
+    state: Weak<State>,
+    work: WindowScreenshotWork,
+}
+
+impl CpuWork for WindowScreenshotWork {
+    fn run(&mut self) -> Option<Box<dyn AsyncCpuWork>> {
+        zone!("WindowScreenshotWork");
+        let png = xrgb8888_encode_png(&self.data, self.width, self.height, self.stride);
+        let mut res = Ok(());
+        if let Some(dir) = self.path.parent() {
+            if !dir.as_os_str().is_empty() {
+                res = std::fs::create_dir_all(dir);
+            }
+        }
+        self.result = Some(res.and_then(|_| std::fs::write(&self.path, png)));
+        None
+    }
+}
+
+impl CpuJob for WindowScreenshotJob {
+    fn work(&mut self) -> &mut dyn CpuWork {
+        &mut self.work
+    }
+
+    fn completed(self: Box<Self>) {
+        let Some(state) = self.state.upgrade() else {
+            return;
+        };
+        state.window_screenshots.pending.remove(&self.id);
+        let work = self.work;
+        let path = work.path.display();
+        match work.result {
+            Some(Ok(())) => {
+                log::info!("Saved window screenshot to {}", path);
+                if let Some(config) = state.config.get() {
+                    config.window_screenshot_saved(&path.to_string());
+                }
+            }
+            Some(Err(e)) => {
+                log::error!("Could not write `{}`: {}", path, ErrorFmt(e));
+            }
+            None => {}
+        }
+    }
+}
+
+/// Encodes an XRGB8888 image as an RGBA PNG.
+pub fn xrgb8888_encode_png(data: &[u8], width: i32, height: i32, stride: i32) -> Vec<u8> {
+    let mut out = vec![];
+    {
+        let mut image_data = Vec::with_capacity((width * height * 4) as usize);
+        let lines = data[..(height as usize * stride as usize)].chunks_exact(stride as usize);
+        for line in lines {
+            for pixel in line[..(width as usize * 4)].array_chunks_ext::<4>() {
+                image_data.extend_from_slice(&[pixel[2], pixel[1], pixel[0], 255])
+            }
+        }
+        let mut encoder = Encoder::new(&mut out, width as _, height as _);
+        encoder.set_color(ColorType::Rgba);
+        encoder.set_depth(BitDepth::Eight);
+        encoder.set_source_srgb(SrgbRenderingIntent::Perceptual);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&image_data).unwrap();
+    }
+    out
+}
+
+fn create_buffer(
+    state: &State,
+    width: i32,
+    height: i32,
+) -> Result<(Screenshot, Rc<dyn GfxFramebuffer>), ScreenshooterError> {
+    let ctx = match state.render_ctx.get() {
+        Some(ctx) => ctx,
+        _ => return Err(ScreenshooterError::NoRenderContext),
+    };
     let formats = ctx.formats();
     let modifiers: IndexMap<_, _> = match formats.get(&XRGB8888.drm) {
         None => return Err(ScreenshooterError::XRGB8888),
@@ -69,29 +340,16 @@ pub fn take_screenshot(
     let allocator = ctx.allocator();
     let bo = allocator.create_bo(
         &state.dma_buf_ids,
-        extents.width(),
-        extents.height(),
+        width,
+        height,
         XRGB8888,
         &modifiers,
         usage,
     )?;
     let fb = ctx.clone().dmabuf_fb(bo.dmabuf())?;
-    fb.render_node(
-        AcquireSync::Unnecessary,
-        ReleaseSync::Implicit,
-        state.root.deref(),
-        state,
-        Some(state.root.extents.get()),
-        Scale::from_int(1),
-        include_cursor,
-        true,
-        false,
-        false,
-        Transform::None,
-    )?;
     let drm = match allocator.drm() {
         Some(drm) => Some(drm.dup_render()?.fd().clone()),
         _ => None,
     };
-    Ok(Screenshot { drm, bo })
+    Ok((Screenshot { drm, bo }, fb))
 }
@@ -8,9 +8,11 @@ use {
             InputDeviceId, InputDeviceIds, MonitorInfo,
         },
         backends::dummy::DummyBackend,
+        capture_overlay::CaptureOverlay,
         cli::RunArgs,
-        client::{Client, ClientId, Clients, SerialRange, NUM_CACHED_SERIAL_RANGES},
+        client::{Client, ClientId, ClientLimits, Clients, SerialRange, NUM_CACHED_SERIAL_RANGES},
         clientmem::ClientMemOffset,
+        cmm::ColorManager,
         compositor::LIBEI_SOCKET,
         config::ConfigProxy,
         cpu_worker::CpuWorker,
@@ -21,17 +23,19 @@ use {
         drm_feedback::{DrmFeedback, DrmFeedbackIds},
         ei::{
             ei_acceptor::EiAcceptor,
+            ei_authorization::EiAuthorization,
             ei_client::{EiClient, EiClients},
         },
         fixed::Fixed,
         forker::ForkerProxy,
         format::Format,
         gfx_api::{
-            AcquireSync, BufferResv, GfxContext, GfxError, GfxFramebuffer, GfxTexture,
-            PendingShmTransfer, ReleaseSync, SampleRect, SyncFile, STAGING_DOWNLOAD,
+            AcquireSync, BufferResv, ColorTransform, GfxContext, GfxError, GfxFramebuffer,
+            GfxTexture, PendingShmTransfer, ReleaseSync, SampleRect, SyncFile, STAGING_DOWNLOAD,
         },
         gfx_apis::create_gfx_context,
         globals::{Globals, GlobalsError, RemovableWaylandGlobal, WaylandGlobal},
+        idle_audio::AudioIdleInhibitor,
         ifs::{
             ext_foreign_toplevel_list_v1::ExtForeignToplevelListV1,
             ext_idle_notification_v1::ExtIdleNotificationV1,
@@ -40,6 +44,8 @@ use {
                 data_control::DataControlDeviceIds, x_data_device::XIpcDeviceIds, DataOfferIds,
                 DataSourceIds,
             },
+            jay_binding_mode_watcher::JayBindingModeWatcher,
+            jay_launch_watcher::JayLaunchWatcher,
             jay_render_ctx::JayRenderCtx,
             jay_screencast::JayScreencast,
             jay_seat_events::JaySeatEvents,
@@ -57,36 +63,48 @@ use {
                 zwp_input_popup_surface_v2::ZwpInputPopupSurfaceV2,
                 NoneSurfaceExt,
             },
+            wlr_output_manager::WlrOutputManagerState,
             workspace_manager::WorkspaceManagerState,
             wp_drm_lease_connector_v1::WpDrmLeaseConnectorV1,
             wp_drm_lease_device_v1::WpDrmLeaseDeviceV1Global,
             wp_linux_drm_syncobj_manager_v1::WpLinuxDrmSyncobjManagerV1Global,
+            xdg_activation_token_v1::{ActivationEnd, PendingActivation},
+            zwlr_foreign_toplevel_manager_v1::ZwlrForeignToplevelManagerV1,
             zwlr_screencopy_frame_v1::ZwlrScreencopyFrameV1,
             zwp_linux_dmabuf_feedback_v1::ZwpLinuxDmabufFeedbackV1,
             zwp_linux_dmabuf_v1::ZwpLinuxDmabufV1Global,
+            zwp_linux_explicit_synchronization_v1::ZwpLinuxExplicitSynchronizationV1Global,
         },
+        interactive_resize::InteractiveResize,
         io_uring::IoUring,
         kbvm::{KbvmContext, KbvmMap},
         keyboard::KeyboardStateIds,
         leaks::Tracker,
+        lock_screen::FallbackLockScreen,
         logger::Logger,
+        night_light::NightLight,
+        notifications::{Notification, Notifications},
         rect::{Rect, Region},
         renderer::Renderer,
         scale::Scale,
+        screenshoter::WindowScreenshots,
         security_context_acceptor::SecurityContextAcceptors,
+        tasks::{self, ButtonDebounce, DisableWhileTyping, KeyRemap},
         theme::{Color, Theme},
         time::Time,
         tree::{
-            ContainerNode, ContainerSplit, Direction, DisplayNode, FloatNode, LatchListener, Node,
-            NodeIds, NodeVisitorBase, OutputNode, PlaceholderNode, TearingMode, ToplevelNode,
-            ToplevelNodeBase, VrrMode, WorkspaceNode,
+            ContainerNode, ContainerSplit, ContainingNode, Direction, DisplayNode,
+            EmptyWorkspaceBehavior, FloatNode, LatchListener, Node, NodeIds, NodeVisitorBase,
+            OutputNode, PlaceholderNode, TearingMode, ToplevelNode, ToplevelNodeBase, VrrMode,
+            WorkspaceNode,
         },
         utils::{
             activation_token::ActivationToken, asyncevent::AsyncEvent, bindings::Bindings,
             clonecell::CloneCell, copyhashmap::CopyHashMap, errorfmt::ErrorFmt,
             event_listener::EventSource, fdcloser::FdCloser, hash_map_ext::HashMapExt,
-            linkedlist::LinkedList, numcell::NumCell, queue::AsyncQueue, refcounted::RefCounted,
-            run_toplevel::RunToplevel, toplevel_identifier::ToplevelIdentifier,
+            linkedlist::LinkedList, numcell::NumCell, pid_info::PidInfo, queue::AsyncQueue,
+            refcounted::RefCounted, run_toplevel::RunToplevel,
+            toplevel_identifier::ToplevelIdentifier,
         },
         video::{
             dmabuf::DmaBufIds,
@@ -97,9 +115,11 @@ use {
             },
         },
         wheel::Wheel,
+        window_state::WindowStates,
         wire::{
-            ExtForeignToplevelListV1Id, ExtIdleNotificationV1Id, JayRenderCtxId, JaySeatEventsId,
-            JayWorkspaceWatcherId, ZwpLinuxDmabufFeedbackV1Id,
+            ExtForeignToplevelListV1Id, ExtIdleNotificationV1Id, JayBindingModeWatcherId,
+            JayLaunchWatcherId, JayRenderCtxId, JaySeatEventsId, JayWorkspaceWatcherId,
+            ZwlrForeignToplevelManagerV1Id, ZwpLinuxDmabufFeedbackV1Id,
         },
         xwayland::{self, XWaylandEvent},
     },
@@ -154,6 +174,7 @@ pub struct State {
     pub tree_changed_sent: Cell<bool>,
     pub config: CloneCell<Option<Rc<ConfigProxy>>>,
     pub theme: Theme,
+    pub color_manager: ColorManager,
     pub pending_container_layout: AsyncQueue<Rc<ContainerNode>>,
     pub pending_container_render_positions: AsyncQueue<Rc<ContainerNode>>,
     pub pending_container_render_title: AsyncQueue<Rc<ContainerNode>>,
@@ -164,6 +185,7 @@ pub struct State {
     pub pending_toplevel_screencasts: AsyncQueue<Rc<JayScreencast>>,
     pub pending_screencast_reallocs_or_reconfigures: AsyncQueue<Rc<JayScreencast>>,
     pub pending_placeholder_render_textures: AsyncQueue<Rc<PlaceholderNode>>,
+    pub pending_notification_textures: AsyncQueue<Rc<Notification>>,
     pub dbus: Dbus,
     pub fdcloser: Arc<FdCloser>,
     pub logger: Option<Arc<Logger>>,
@@ -171,6 +193,11 @@ pub struct State {
     pub outputs: CopyHashMap<ConnectorId, Rc<OutputData>>,
     pub drm_devs: CopyHashMap<DrmDeviceId, Rc<DrmDevData>>,
     pub status: CloneCell<Rc<String>>,
+    pub default_bar_clock_format: CloneCell<Option<Rc<String>>>,
+    pub bar_clock_future: CloneCell<Option<SpawnedFuture<()>>>,
+    pub default_bar_show_focused_title: Cell<bool>,
+    pub notifications: Notifications,
+    pub window_states: WindowStates,
     pub idle: IdleState,
     pub run_args: RunArgs,
     pub xwayland: XWaylandState,
@@ -190,11 +217,17 @@ pub struct State {
     pub testers: RefCell<AHashMap<(ClientId, JaySeatEventsId), Rc<JaySeatEvents>>>,
     pub render_ctx_watchers: CopyHashMap<(ClientId, JayRenderCtxId), Rc<JayRenderCtx>>,
     pub workspace_watchers: CopyHashMap<(ClientId, JayWorkspaceWatcherId), Rc<JayWorkspaceWatcher>>,
+    pub binding_mode_watchers:
+        CopyHashMap<(ClientId, JayBindingModeWatcherId), Rc<JayBindingModeWatcher>>,
     pub default_workspace_capture: Cell<bool>,
     pub default_gfx_api: Cell<GfxApi>,
-    pub activation_tokens: CopyHashMap<ActivationToken, ()>,
+    pub activation_tokens: CopyHashMap<ActivationToken, Rc<PendingActivation>>,
+    pub activation_token_lifetime: Cell<Duration>,
+    pub launch_watchers: CopyHashMap<(ClientId, JayLaunchWatcherId), Rc<JayLaunchWatcher>>,
     pub toplevel_lists:
         CopyHashMap<(ClientId, ExtForeignToplevelListV1Id), Rc<ExtForeignToplevelListV1>>,
+    pub toplevel_managers:
+        CopyHashMap<(ClientId, ZwlrForeignToplevelManagerV1Id), Rc<ZwlrForeignToplevelManagerV1>>,
     pub dma_buf_ids: DmaBufIds,
     pub drm_feedback_ids: DrmFeedbackIds,
     pub direct_scanout_enabled: Cell<bool>,
@@ -205,6 +238,8 @@ pub struct State {
     pub subsurface_ids: SubsurfaceIds,
     pub wait_for_sync_obj: Rc<WaitForSyncObj>,
     pub explicit_sync_enabled: Cell<bool>,
+    pub force_implicit_sync: Cell<bool>,
+    pub key_remap: RefCell<AHashMap<u32, u32>>,
     pub keyboard_state_ids: KeyboardStateIds,
     pub physical_keyboard_ids: PhysicalKeyboardIds,
     pub security_context_acceptors: SecurityContextAcceptors,
@@ -217,22 +252,38 @@ pub struct State {
     pub tablet_tool_ids: TabletToolIds,
     pub tablet_pad_ids: TabletPadIds,
     pub damage_visualizer: DamageVisualizer,
+    pub texture_uploads: NumCell<u64>,
+    pub show_surface_regions: Cell<bool>,
+    pub interactive_resize: InteractiveResize,
     pub default_vrr_mode: Cell<&'static VrrMode>,
     pub default_vrr_cursor_hz: Cell<Option<f64>>,
     pub default_tearing_mode: Cell<&'static TearingMode>,
     pub ei_acceptor: CloneCell<Option<Rc<EiAcceptor>>>,
     pub ei_acceptor_future: CloneCell<Option<SpawnedFuture<()>>>,
     pub enable_ei_acceptor: Cell<bool>,
+    pub jay_protocol_allowlist: RefCell<Option<AHashSet<String>>>,
     pub ei_clients: EiClients,
+    pub ei_authorization: Rc<EiAuthorization>,
     pub slow_ei_clients: AsyncQueue<Rc<EiClient>>,
     pub cpu_worker: Rc<CpuWorker>,
     pub ui_drag_enabled: Cell<bool>,
     pub ui_drag_threshold_squared: Cell<i32>,
+    pub empty_workspace_behavior: Cell<EmptyWorkspaceBehavior>,
+    pub workspace_back_and_forth: Cell<bool>,
+    pub autotiling: Cell<bool>,
     pub toplevels: CopyHashMap<ToplevelIdentifier, Weak<dyn ToplevelNode>>,
+    pub marks: CopyHashMap<String, Weak<dyn ToplevelNode>>,
+    pub show_marks: Cell<bool>,
+    pub capture_redactions: RefCell<AHashSet<String>>,
+    pub capture_overlay: CaptureOverlay,
+    pub night_light: NightLight,
     pub const_40hz_latch: EventSource<dyn LatchListener>,
     pub tray_item_ids: TrayItemIds,
     pub data_control_device_ids: DataControlDeviceIds,
     pub workspace_managers: WorkspaceManagerState,
+    pub wlr_output_managers: WlrOutputManagerState,
+    pub window_screenshots: WindowScreenshots,
+    pub client_limits: Cell<ClientLimits>,
 }
 
 // impl Drop for State {
@@ -250,6 +301,9 @@ impl Debug for State {
 pub struct ScreenlockState {
     pub locked: Cell<bool>,
     pub lock: CloneCell<Option<Rc<ExtSessionLockV1>>>,
+    pub fallback: Rc<FallbackLockScreen>,
+    pub grace_period: Cell<Duration>,
+    pub locked_usec: Cell<u64>,
 }
 
 pub struct XWaylandState {
@@ -273,6 +327,8 @@ pub struct IdleState {
     pub inhibited_idle_notifications:
         CopyHashMap<(ClientId, ExtIdleNotificationV1Id), Rc<ExtIdleNotificationV1>>,
     pub in_grace_period: Cell<bool>,
+    pub audio: Rc<AudioIdleInhibitor>,
+    pub audio_inhibited: Cell<bool>,
 }
 
 impl IdleState {
@@ -298,11 +354,26 @@ impl IdleState {
         self.inhibitors.remove(&inhibitor.inhibit_id);
         self.inhibitors_changed.set(true);
         self.change.trigger();
-        if self.inhibitors.is_empty() {
+        if !self.is_inhibited() {
+            self.resume_inhibited_notifications();
+        }
+    }
+
+    pub fn set_audio_inhibited(&self, inhibited: bool) {
+        if self.audio_inhibited.replace(inhibited) == inhibited {
+            return;
+        }
+        self.inhibitors_changed.set(true);
+        self.change.trigger();
+        if !self.is_inhibited() {
             self.resume_inhibited_notifications();
         }
     }
 
+    pub fn is_inhibited(&self) -> bool {
+        self.inhibitors.is_not_empty() || self.audio_inhibited.get()
+    }
+
     fn resume_inhibited_notifications(&self) {
         for notification in self.inhibited_idle_notifications.lock().drain_values() {
             notification.resume.trigger();
@@ -331,6 +402,9 @@ pub struct DeviceHandlerData {
     pub keyboard_id: PhysicalKeyboardId,
     pub seat: CloneCell<Option<Rc<WlSeatGlobal>>>,
     pub px_per_scroll_wheel: Cell<f64>,
+    pub button_debounce: ButtonDebounce,
+    pub disable_while_typing: DisableWhileTyping,
+    pub key_remap: KeyRemap,
     pub device: Rc<dyn InputDevice>,
     pub syspath: Option<String>,
     pub devnode: Option<String>,
@@ -350,6 +424,8 @@ pub struct ConnectorData {
     pub async_event: Rc<AsyncEvent>,
     pub damaged: Cell<bool>,
     pub needs_vblank_emulation: Cell<bool>,
+    pub monitor_info: CloneCell<Option<Rc<MonitorInfo>>>,
+    pub mirror_source: Cell<Option<ConnectorId>>,
 }
 
 pub struct OutputData {
@@ -377,6 +453,12 @@ impl ConnectorData {
             self.connector.damage();
         }
     }
+
+    pub fn damage_software_cursor(&self) {
+        if !self.damaged.get() {
+            self.connector.damage_software_cursor();
+        }
+    }
 }
 
 impl DrmDevData {
@@ -458,6 +540,8 @@ impl State {
 
     fn output_scales_changed(&self) {
         UpdateTextTexturesVisitor.visit_display(&self.root);
+        self.notifications.render_textures(self);
+        self.capture_overlay.render_textures();
         self.reload_cursors();
         self.update_xwayland_wire_scale();
     }
@@ -565,6 +649,8 @@ impl State {
         if ctx.is_some() {
             self.reload_cursors();
             UpdateTextTexturesVisitor.visit_display(&self.root);
+            self.notifications.render_textures(self);
+            self.capture_overlay.render_textures();
         }
 
         for cursor_user_groups in self.cursor_user_groups.lock().values() {
@@ -580,6 +666,9 @@ impl State {
                         self.add_global(&Rc::new(WpLinuxDrmSyncobjManagerV1Global::new(
                             self.globals.name(),
                         )));
+                        self.add_global(&Rc::new(ZwpLinuxExplicitSynchronizationV1Global::new(
+                            self.globals.name(),
+                        )));
                     }
                 }
                 if let Some(config) = self.config.get() {
@@ -673,6 +762,23 @@ impl State {
                 .get()
                 .and_then(|n| n.node_into_container());
             if let Some(lap) = lap {
+                if self.autotiling.get() && lap.mono_child.is_none() {
+                    let pos = la.node_absolute_position();
+                    let split = match pos.width() > pos.height() {
+                        true => ContainerSplit::Horizontal,
+                        false => ContainerSplit::Vertical,
+                    };
+                    if lap.split.get() != split {
+                        if lap.num_children() == 1 {
+                            lap.set_split(split);
+                        } else {
+                            let cn = ContainerNode::new(self, ws, la.clone(), split);
+                            lap.cnode_replace_child(la.tl_as_node(), cn.clone());
+                            cn.append_child(node);
+                            return;
+                        }
+                    }
+                }
                 lap.add_child_after(la.tl_as_node(), node);
             } else {
                 c.append_child(node);
@@ -728,6 +834,64 @@ impl State {
         }
     }
 
+    pub fn marked_toplevel(&self, mark: &str) -> Option<Rc<dyn ToplevelNode>> {
+        self.marks.get(mark)?.upgrade()
+    }
+
+    /// Adds a mark to the toplevel.
+    ///
+    /// Marks are unique. If another toplevel has this mark, the mark is removed from it.
+    pub fn mark_toplevel(&self, tl: &Rc<dyn ToplevelNode>, mark: &str) {
+        let data = tl.tl_data();
+        let prev = self.marks.set(mark.to_string(), data.slf.clone());
+        if let Some(prev) = prev.and_then(|p| p.upgrade()) {
+            let prev = prev.tl_data();
+            if prev.identifier.get() == data.identifier.get() {
+                return;
+            }
+            prev.marks.borrow_mut().retain(|m| m != mark);
+            prev.marks_changed();
+        }
+        data.marks.borrow_mut().push(mark.to_string());
+        data.marks_changed();
+    }
+
+    pub fn set_show_marks(&self, show: bool) {
+        if self.show_marks.replace(show) == show {
+            return;
+        }
+        let marked: Vec<_> = self
+            .marks
+            .lock()
+            .values()
+            .filter_map(|tl| tl.upgrade())
+            .collect();
+        for tl in marked {
+            tl.tl_title_changed();
+        }
+    }
+
+    /// Shows the workspace that was shown before the current workspace on the output of
+    /// the seat.
+    ///
+    /// Returns `false` if there is no such workspace.
+    pub fn show_previous_workspace(&self, seat: &Rc<WlSeatGlobal>) -> bool {
+        let output = seat.get_output();
+        if output.is_dummy {
+            return false;
+        }
+        let Some(name) = output.previous_workspace_name.borrow().clone() else {
+            return false;
+        };
+        if let Some(ws) = output.workspace.get() {
+            if *ws.name.borrow() == name {
+                return false;
+            }
+        }
+        self.show_workspace(seat, &name);
+        true
+    }
+
     pub fn show_workspace(&self, seat: &Rc<WlSeatGlobal>, name: &str) {
         let (output, ws) = match self.workspaces.get(name) {
             Some(ws) => {
@@ -781,6 +945,37 @@ impl State {
         }
     }
 
+    pub fn set_bar_clock_format(self: &Rc<Self>, format: Option<&str>) {
+        let format = format.map(|f| Rc::new(f.to_owned()));
+        self.default_bar_clock_format.set(format);
+        self.update_bar_clock();
+    }
+
+    pub fn update_bar_clock(self: &Rc<Self>) {
+        self.bar_clock_future.take();
+        let enabled = self
+            .root
+            .outputs
+            .lock()
+            .values()
+            .any(|o| o.bar_clock_format().is_some());
+        if enabled {
+            let future = self.eng.spawn("bar clock", tasks::bar_clock(self.clone()));
+            self.bar_clock_future.set(Some(future));
+            return;
+        }
+        for output in self.root.outputs.lock().values() {
+            output.set_bar_clock(String::new());
+        }
+    }
+
+    pub fn set_bar_show_focused_title(&self, show: bool) {
+        self.default_bar_show_focused_title.set(show);
+        for output in self.root.outputs.lock().values() {
+            output.schedule_update_render_data();
+        }
+    }
+
     pub fn input_occurred(&self) {
         if !self.idle.input.replace(true) {
             self.idle.change.trigger();
@@ -830,9 +1025,18 @@ impl State {
         }
         self.damage_visualizer.add(rect);
         for output in self.root.outputs.lock().values() {
-            if output.global.pos.get().intersects(&rect) {
-                if cursor && output.schedule.defer_cursor_updates() {
-                    output.schedule.software_cursor_changed();
+            let pos = output.global.pos.get();
+            if pos.intersects(&rect) {
+                if output.screencopy_damage.is_not_empty() {
+                    output.damage_screencopies(Some(rect.move_(-pos.x1(), -pos.y1())));
+                }
+                if cursor {
+                    output.add_software_cursor_damage(rect.move_(-pos.x1(), -pos.y1()));
+                    if output.schedule.defer_cursor_updates() {
+                        output.schedule.software_cursor_changed();
+                    } else {
+                        output.global.connector.damage_software_cursor();
+                    }
                 } else {
                     output.global.connector.damage();
                 }
@@ -840,9 +1044,35 @@ impl State {
         }
     }
 
+    /// Unlocks the session without the cooperation of the session-lock client.
+    pub fn force_unlock(&self) {
+        if self.lock.locked.get() {
+            if let Some(lock) = self.lock.lock.get() {
+                lock.finish();
+            }
+            self.do_unlock();
+        }
+    }
+
+    /// Unlocks the session if the lock grace period has not yet elapsed.
+    pub fn unlock_if_in_grace_period(&self) {
+        if !self.lock.locked.get() {
+            return;
+        }
+        let grace_period = self.lock.grace_period.get().as_micros() as u64;
+        if self.now_usec().saturating_sub(self.lock.locked_usec.get()) < grace_period {
+            log::info!("Unlocking the session during the lock grace period");
+            self.force_unlock();
+        }
+    }
+
     pub fn do_unlock(&self) {
         self.lock.locked.set(false);
         self.lock.lock.take();
+        self.lock.fallback.deactivate(self);
+        if let Some(config) = self.config.get() {
+            config.unlocked();
+        }
         for output in self.root.outputs.lock().values() {
             if let Some(surface) = output.set_lock_surface(None) {
                 surface.destroy_node();
@@ -854,6 +1084,7 @@ impl State {
 
     pub fn clear(&self) {
         self.lock.lock.take();
+        self.lock.fallback.clear();
         self.xwayland.handler.borrow_mut().take();
         self.clients.clear();
         if let Some(config) = self.config.set(None) {
@@ -868,6 +1099,7 @@ impl State {
         self.xwayland.handler.borrow_mut().take();
         self.xwayland.queue.clear();
         self.idle.inhibitors.clear();
+        self.idle.audio.clear();
         self.idle.change.clear();
         for drm_dev in self.drm_devs.lock().drain_values() {
             drm_dev.handler.take();
@@ -892,9 +1124,15 @@ impl State {
         self.pending_toplevel_screencasts.clear();
         self.pending_screencast_reallocs_or_reconfigures.clear();
         self.pending_placeholder_render_textures.clear();
+        self.pending_notification_textures.clear();
+        self.window_screenshots.clear();
         self.render_ctx_watchers.clear();
         self.workspace_watchers.clear();
+        self.binding_mode_watchers.clear();
+        self.launch_watchers.clear();
+        self.activation_tokens.clear();
         self.toplevel_lists.clear();
+        self.toplevel_managers.clear();
         self.security_context_acceptors.clear();
         self.slow_clients.clear();
         for h in self.input_device_handlers.borrow_mut().drain_values() {
@@ -918,10 +1156,17 @@ impl State {
         self.eng.clear();
         self.ei_acceptor.take();
         self.ei_acceptor_future.take();
+        self.bar_clock_future.take();
+        self.notifications.clear();
+        self.window_states.clear();
+        self.capture_overlay.clear();
+        self.ei_authorization.clear(self);
         self.ei_clients.clear();
         self.slow_ei_clients.clear();
         self.toplevels.clear();
+        self.marks.clear();
         self.workspace_managers.clear();
+        self.wlr_output_managers.clear();
     }
 
     pub fn damage_hardware_cursors(&self, render: bool) {
@@ -1018,6 +1263,7 @@ impl State {
         size: Option<(i32, i32)>,
         transform: Transform,
         scale: Scale,
+        color_transform: Option<ColorTransform>,
     ) -> Result<Option<SyncFile>, GfxError> {
         let mut ops = vec![];
         let mut renderer = Renderer {
@@ -1043,7 +1289,10 @@ impl State {
             resv.cloned(),
             acquire_sync.clone(),
             release_sync,
+            color_transform,
         );
+        renderer.render_capture_redactions(&position, x_off, y_off);
+        renderer.render_capture_overlay(&position, x_off, y_off);
         if render_hardware_cursors {
             if let Some(cursor_user_group) = self.cursor_user_group_hardware_cursor.get() {
                 if let Some(cursor_user) = cursor_user_group.active() {
@@ -1078,6 +1327,8 @@ impl State {
         format: &'static Format,
         transform: Transform,
         scale: Scale,
+        render_hardware_cursors: bool,
+        color_transform: Option<ColorTransform>,
     ) -> Result<Option<PendingShmTransfer>, ShmScreencopyError> {
         let Some(ctx) = self.render_ctx.get() else {
             return Err(ShmScreencopyError::NoRenderContext);
@@ -1102,12 +1353,13 @@ impl State {
             ReleaseSync::None,
             transform,
             position,
-            true,
+            render_hardware_cursors,
             x_off - capture.rect.x1(),
             y_off - capture.rect.y1(),
             size,
             transform,
             scale,
+            color_transform,
         )
         .map_err(ShmScreencopyError::CopyToTemporary)?;
         let staging = ctx.create_staging_buffer(fb.staging_size(), STAGING_DOWNLOAD);
@@ -1154,11 +1406,28 @@ impl State {
         !self.idle.backend_idle.get()
     }
 
-    pub fn find_closest_output(&self, mut x: i32, mut y: i32) -> (Rc<OutputNode>, i32, i32) {
+    pub fn find_closest_output(&self, x: i32, y: i32) -> (Rc<OutputNode>, i32, i32) {
+        self.find_closest_output_where(x, y, |_| true)
+    }
+
+    /// Like `find_closest_output` but only considers the outputs accepted by `filter`.
+    ///
+    /// If no connected output is accepted, all outputs are considered.
+    pub fn find_closest_output_where(
+        &self,
+        mut x: i32,
+        mut y: i32,
+        filter: impl Fn(&OutputNode) -> bool,
+    ) -> (Rc<OutputNode>, i32, i32) {
         let mut optimal_dist = i32::MAX;
         let mut optimal_output = None;
+        let mut filtered = false;
         let outputs = self.root.outputs.lock();
         for output in outputs.values() {
+            if !filter(output) {
+                filtered = true;
+                continue;
+            }
             let pos = output.global.pos.get();
             let dist = pos.dist_squared(x, y);
             if dist == 0 {
@@ -1188,6 +1457,10 @@ impl State {
             }
             return (output, x, y);
         }
+        drop(outputs);
+        if filtered {
+            return self.find_closest_output(x, y);
+        }
         (self.dummy_output.get().unwrap(), 0, 0)
     }
 
@@ -1212,6 +1485,119 @@ impl State {
         for seat in self.globals.seats.lock().values() {
             seat.output_extents_changed();
         }
+        self.wlr_output_managers.schedule_update();
+    }
+
+    pub fn set_outputs_powered(&self, powered: bool) {
+        for output in self.root.outputs.lock().values() {
+            output.set_powered(powered);
+        }
+    }
+
+    pub fn toggle_outputs_powered(&self) {
+        let any_powered = self.root.outputs.lock().values().any(|o| o.powered.get());
+        self.set_outputs_powered(!any_powered);
+    }
+
+    pub fn set_mirror_source(
+        &self,
+        connector: &ConnectorData,
+        source: Option<&ConnectorData>,
+    ) -> Result<(), MirrorError> {
+        let id = connector.connector.id();
+        if let Some(source) = source {
+            if source.connector.id() == id {
+                return Err(MirrorError::MirrorSelf);
+            }
+            if source.mirror_source.get().is_some() {
+                return Err(MirrorError::SourceIsMirror(source.name.clone()));
+            }
+            let is_mirrored = self
+                .connectors
+                .lock()
+                .values()
+                .any(|c| c.mirror_source.get() == Some(id));
+            if is_mirrored {
+                return Err(MirrorError::IsMirrored(connector.name.clone()));
+            }
+        }
+        connector
+            .mirror_source
+            .set(source.map(|s| s.connector.id()));
+        connector.async_event.trigger();
+        Ok(())
+    }
+
+    /// Returns the backlight brightness of a connector between 0 and 1.
+    pub fn brightness(&self, connector: &ConnectorData) -> Option<f64> {
+        let (brightness, max) = connector.connector.backlight()?;
+        Some(brightness as f64 / max as f64)
+    }
+
+    /// Sets the backlight brightness of a connector to a value between 0 and 1.
+    pub fn set_brightness(&self, connector: &ConnectorData, brightness: f64) {
+        let Some((_, max)) = connector.connector.backlight() else {
+            return;
+        };
+        let brightness = (brightness.clamp(0.0, 1.0) * max as f64).round() as u32;
+        self.set_backlight(connector, brightness, max);
+    }
+
+    /// Changes the backlight brightness of a connector by a fraction of the maximum.
+    ///
+    /// Decreasing the brightness never turns a backlight off that is currently on.
+    pub fn change_brightness(&self, connector: &ConnectorData, delta: f64) {
+        let Some((brightness, max)) = connector.connector.backlight() else {
+            return;
+        };
+        let mut step = (delta * max as f64).round() as i64;
+        if step == 0 && delta != 0.0 {
+            step = delta.signum() as i64;
+        }
+        let brightness = brightness as i64;
+        let new = (brightness + step).clamp(brightness.min(1), max as i64);
+        self.set_backlight(connector, new as u32, max);
+    }
+
+    fn set_backlight(&self, connector: &ConnectorData, brightness: u32, max: u32) {
+        connector.connector.set_backlight(brightness);
+        if let Some(config) = self.config.get() {
+            config.brightness_changed(connector.connector.id(), brightness as f64 / max as f64);
+        }
+    }
+
+    /// Returns the connector whose contents are currently displayed by the connector.
+    ///
+    /// Mirroring is only active while the source is connected to a desktop display.
+    pub fn active_mirror_source(&self, connector: &ConnectorData) -> Option<ConnectorId> {
+        let source = connector.mirror_source.get()?;
+        self.root.outputs.contains(&source).then_some(source)
+    }
+
+    pub fn mirror_source_changed(&self, source: ConnectorId) {
+        for connector in self.connectors.lock().values() {
+            if connector.mirror_source.get() == Some(source) {
+                connector.async_event.trigger();
+            }
+        }
+    }
+
+    pub fn set_show_surface_regions(&self, show: bool) {
+        if self.show_surface_regions.replace(show) != show {
+            self.damage(self.root.extents.get());
+        }
+    }
+
+    /// Turns off all outputs except the given one. If no output is given, all outputs
+    /// are turned on.
+    pub fn set_presentation_output(&self, output: Option<&OutputNode>) {
+        for o in self.root.outputs.lock().values() {
+            let powered = match output {
+                Some(output) => o.id == output.id,
+                None => true,
+            };
+            o.set_powered(powered);
+        }
     }
 
     pub fn update_ei_acceptor(self: &Rc<Self>) {
@@ -1228,6 +1614,74 @@ impl State {
         }
     }
 
+    pub fn set_capture_redactions(&self, app_ids: Vec<String>) {
+        *self.capture_redactions.borrow_mut() = app_ids.into_iter().collect();
+        self.damage(self.root.extents.get());
+    }
+
+    /// Returns the absolute positions of the visible windows that must not be captured.
+    pub fn redacted_capture_rects(&self) -> Vec<Rect> {
+        let app_ids = self.capture_redactions.borrow();
+        if app_ids.is_empty() {
+            return vec![];
+        }
+        let mut rects = vec![];
+        for tl in self.toplevels.lock().values() {
+            let Some(tl) = tl.upgrade() else {
+                continue;
+            };
+            if !tl.node_visible() || !app_ids.contains(&*tl.tl_data().app_id.borrow()) {
+                continue;
+            }
+            rects.push(tl.node_absolute_position());
+        }
+        rects
+    }
+
+    /// Like `redacted_capture_rects` but for the windows on a workspace that might not
+    /// be visible.
+    pub fn redacted_workspace_rects(&self, ws: &WorkspaceNode) -> Vec<Rect> {
+        let app_ids = self.capture_redactions.borrow();
+        if app_ids.is_empty() {
+            return vec![];
+        }
+        let mut rects = vec![];
+        for tl in self.toplevels.lock().values() {
+            let Some(tl) = tl.upgrade() else {
+                continue;
+            };
+            let data = tl.tl_data();
+            if data.workspace.get().map(|w| w.id) != Some(ws.id) {
+                continue;
+            }
+            if !app_ids.contains(&*data.app_id.borrow()) {
+                continue;
+            }
+            rects.push(tl.node_absolute_position());
+        }
+        rects
+    }
+
+    pub fn may_use_jay_protocols(&self, pid_info: &PidInfo) -> bool {
+        let allowlist = self.jay_protocol_allowlist.borrow();
+        let Some(allowlist) = &*allowlist else {
+            return true;
+        };
+        let Some(exe) = pid_info.exe() else {
+            return false;
+        };
+        // The binary might have been replaced by an update since it was started.
+        let strip = |s: &str| s.strip_suffix(" (deleted)").unwrap_or(s).to_owned();
+        let exe = strip(exe);
+        if allowlist.contains(&exe) {
+            return true;
+        }
+        match std::fs::read_link("/proc/self/exe") {
+            Ok(own) => strip(&own.to_string_lossy()) == exe,
+            Err(_) => false,
+        }
+    }
+
     fn update_ei_acceptor2(self: &Rc<Self>) {
         if self.ei_acceptor.is_some() == self.enable_ei_acceptor.get() {
             return;
@@ -1307,6 +1761,29 @@ impl State {
     pub fn tray_icon_size(&self) -> i32 {
         (self.theme.sizes.title_height.get() - 2).max(0)
     }
+
+    pub fn remove_activation_token(
+        &self,
+        token: &ActivationToken,
+        end: ActivationEnd,
+    ) -> Option<Rc<PendingActivation>> {
+        let pending = self.activation_tokens.remove(token)?;
+        pending.cancel_timeout();
+        for watcher in self.launch_watchers.lock().values() {
+            watcher.send_end(&pending, end);
+        }
+        Some(pending)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum MirrorError {
+    #[error("A connector cannot mirror itself")]
+    MirrorSelf,
+    #[error("Connector {0} is itself a mirror")]
+    SourceIsMirror(String),
+    #[error("Connector {0} is mirrored by other connectors")]
+    IsMirrored(String),
 }
 
 #[derive(Debug, Error)]
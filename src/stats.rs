@@ -0,0 +1,65 @@
+use {crate::utils::numcell::NumCell, std::cell::Cell};
+
+/// Frame statistics of an output.
+#[derive(Default)]
+pub struct OutputStats {
+    pub frames: NumCell<u64>,
+    pub missed_vblanks: NumCell<u64>,
+    /// The time from latching a frame until it is presented.
+    pub frame_time: DurationStats,
+    /// The time from starting a render pass until the GPU has completed it.
+    pub render_time: DurationStats,
+    latch_nsec: Cell<Option<u64>>,
+}
+
+#[derive(Default)]
+pub struct DurationStats {
+    count: NumCell<u64>,
+    total_nsec: NumCell<u64>,
+    max_nsec: Cell<u64>,
+}
+
+impl DurationStats {
+    pub fn add(&self, nsec: u64) {
+        self.count.fetch_add(1);
+        self.total_nsec.fetch_add(nsec);
+        self.max_nsec.set(self.max_nsec.get().max(nsec));
+    }
+
+    pub fn avg_nsec(&self) -> u64 {
+        self.total_nsec
+            .get()
+            .checked_div(self.count.get())
+            .unwrap_or(0)
+    }
+
+    pub fn max_nsec(&self) -> u64 {
+        self.max_nsec.get()
+    }
+
+    fn reset(&self) {
+        self.count.set(0);
+        self.total_nsec.set(0);
+        self.max_nsec.set(0);
+    }
+}
+
+impl OutputStats {
+    pub fn latched(&self, now_nsec: u64) {
+        self.latch_nsec.set(Some(now_nsec));
+    }
+
+    pub fn presented(&self, nsec: u64) {
+        self.frames.fetch_add(1);
+        if let Some(latch) = self.latch_nsec.take() {
+            self.frame_time.add(nsec.saturating_sub(latch));
+        }
+    }
+
+    pub fn reset(&self) {
+        self.frames.set(0);
+        self.missed_vblanks.set(0);
+        self.frame_time.reset();
+        self.render_time.reset();
+    }
+}
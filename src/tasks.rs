@@ -1,4 +1,5 @@
 mod backend;
+mod bar_clock;
 mod connector;
 mod const_clock;
 mod drmdev;
@@ -19,7 +20,12 @@ use {
     },
     std::{rc::Rc, time::Duration},
 };
-pub use {hardware_cursor::handle_hardware_cursor_tick, idle::idle};
+pub use {
+    bar_clock::bar_clock,
+    hardware_cursor::handle_hardware_cursor_tick,
+    idle::idle,
+    input_device::{ButtonDebounce, DisableWhileTyping, KeyRemap},
+};
 
 pub async fn handle_backend_events(state: Rc<State>) {
     let mut beh = BackendEventHandler { state };
@@ -0,0 +1,45 @@
+use {
+    crate::{state::State, utils::errorfmt::ErrorFmt},
+    chrono::Local,
+    std::{fmt::Write, rc::Rc},
+};
+
+pub async fn bar_clock(state: Rc<State>) {
+    loop {
+        let now = Local::now();
+        for output in state.root.outputs.lock().values() {
+            let mut clock = String::new();
+            if let Some(format) = output.bar_clock_format() {
+                if write!(clock, "{}", now.format(&format)).is_err() {
+                    log::error!("Invalid clock format `{}`", format);
+                    let persistent = &output.global.persistent;
+                    if persistent.bar_clock_format.get().is_some() {
+                        persistent.bar_clock_format.set(Some(None));
+                    } else {
+                        state.default_bar_clock_format.set(None);
+                    }
+                    clock.clear();
+                }
+            }
+            output.set_bar_clock(escape_markup(&clock));
+        }
+        let ms = 1000u64.saturating_sub(now.timestamp_subsec_millis() as u64);
+        if let Err(e) = state.wheel.timeout(ms.max(1)).await {
+            log::error!("Could not wait for the next clock tick: {}", ErrorFmt(e));
+            return;
+        }
+    }
+}
+
+fn escape_markup(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => res.push_str("&amp;"),
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            _ => res.push(c),
+        }
+    }
+    res
+}
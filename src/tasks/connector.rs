@@ -1,7 +1,6 @@
 use {
     crate::{
         backend::{Connector, ConnectorEvent, ConnectorId, MonitorInfo},
-        globals::GlobalName,
         ifs::{
             jay_tray_v1::JayTrayV1Global,
             wl_output::{PersistentOutputState, WlOutputGlobal},
@@ -36,6 +35,8 @@ pub fn handle(state: &Rc<State>, connector: &Rc<dyn Connector>) {
         async_event: Rc::new(AsyncEvent::default()),
         damaged: Cell::new(false),
         needs_vblank_emulation: Cell::new(false),
+        monitor_info: Default::default(),
+        mirror_source: Default::default(),
     });
     if let Some(dev) = drm_dev {
         dev.connectors.set(id, data.clone());
@@ -85,22 +86,36 @@ impl ConnectorHandler {
         }
         self.data.handler.set(None);
         self.state.connectors.remove(&self.id);
+        self.state.wlr_output_managers.schedule_update();
     }
 
-    async fn handle_connected(&self, info: MonitorInfo) {
+    async fn handle_connected(&self, mut info: MonitorInfo) {
         log::info!("Connector {} connected", self.data.connector.kernel_id());
         self.data.connected.set(true);
-        let name = self.state.globals.name();
         if info.non_desktop {
             self.handle_non_desktop_connected(info).await;
         } else {
-            self.handle_desktop_connected(info, name).await;
+            let mut first = true;
+            loop {
+                let disconnected = match self.state.active_mirror_source(&self.data) {
+                    Some(source) => self.handle_mirror_connected(&mut info, source, first).await,
+                    None => self.handle_desktop_connected(&mut info, first).await,
+                };
+                if disconnected {
+                    break;
+                }
+                first = false;
+            }
         }
         self.data.connected.set(false);
         log::info!("Connector {} disconnected", self.data.connector.kernel_id());
     }
 
-    async fn handle_desktop_connected(&self, info: MonitorInfo, name: GlobalName) {
+    /// Returns whether the connector was disconnected.
+    async fn handle_desktop_connected(&self, info: &mut MonitorInfo, first: bool) -> bool {
+        let name = self.state.globals.name();
+        self.data.monitor_info.set(Some(Rc::new(info.clone())));
+        self.data.connector.set_powered(true);
         let output_id = info.output_id.clone();
         let desired_state = match self.state.persistent_output_states.get(&output_id) {
             Some(ds) => ds,
@@ -121,6 +136,8 @@ impl ConnectorHandler {
                     vrr_mode: Cell::new(self.state.default_vrr_mode.get()),
                     vrr_cursor_hz: Cell::new(self.state.default_vrr_cursor_hz.get()),
                     tearing_mode: Cell::new(self.state.default_tearing_mode.get()),
+                    bar_clock_format: Default::default(),
+                    bar_show_focused_title: Default::default(),
                 });
                 self.state
                     .persistent_output_states
@@ -172,6 +189,8 @@ impl ConnectorHandler {
                 captured_inactive_workspaces: Default::default(),
                 titles: Default::default(),
                 status: None,
+                focused_title: None,
+                focused_title_rect: Default::default(),
             }),
             state: self.state.clone(),
             is_dummy: false,
@@ -186,33 +205,51 @@ impl ConnectorHandler {
             update_render_data_scheduled: Cell::new(false),
             hardware_cursor_needs_render: Cell::new(false),
             screencopies: Default::default(),
+            screencopy_damage: Default::default(),
             title_visible: Default::default(),
+            focused_toplevel: Default::default(),
+            bar_clock: Default::default(),
             schedule,
             latch_event: Default::default(),
             vblank_event: Default::default(),
             presentation_event: Default::default(),
             render_margin_ns: Default::default(),
             flip_margin_ns: Default::default(),
+            expected_present_nsec: Default::default(),
+            previous_workspace: Default::default(),
+            previous_workspace_name: Default::default(),
+            software_cursor_damage: Default::default(),
+            stats: Default::default(),
             ext_copy_sessions: Default::default(),
             before_latch_event: Default::default(),
             tray_start_rel: Default::default(),
             tray_items: Default::default(),
             ext_workspace_groups: Default::default(),
+            powered: Cell::new(true),
+            power_controls: Default::default(),
+            gamma_control: Default::default(),
+            client_gamma_lut: Default::default(),
+            workspace_swipe_offset: Default::default(),
         });
         on.update_visible();
         on.update_rects();
+        on.update_gamma_lut();
         self.state
             .add_output_scale(on.global.persistent.scale.get());
         let output_data = Rc::new(OutputData {
             connector: self.data.clone(),
-            monitor_info: info,
+            monitor_info: info.clone(),
             node: Some(on.clone()),
             lease_connectors: Default::default(),
         });
         self.state.outputs.set(self.id, output_data);
         on.schedule_update_render_data();
         self.state.root.outputs.set(self.id, on.clone());
+        if on.bar_clock_format().is_some() {
+            self.state.update_bar_clock();
+        }
         self.state.output_extents_changed();
+        self.state.mirror_source_changed(self.id);
         global.opt.node.set(Some(on.clone()));
         global.opt.global.set(Some(global.clone()));
         let mut ws_to_move = VecDeque::new();
@@ -253,29 +290,54 @@ impl ConnectorHandler {
             };
             move_ws_to_output(&ws, &on, config);
         }
-        if let Some(config) = self.state.config.get() {
-            config.connector_connected(self.id);
+        if first {
+            if let Some(config) = self.state.config.get() {
+                config.connector_connected(self.id);
+            }
         }
         self.state.add_global(&global);
         self.state.add_global(&tray);
         self.state.tree_changed();
         on.update_presentation_type();
         self.state.workspace_managers.announce_output(&on);
+        let mut disconnected = false;
         'outer: loop {
             while let Some(event) = self.data.connector.event() {
                 match event {
-                    ConnectorEvent::Disconnected => break 'outer,
+                    ConnectorEvent::Disconnected => {
+                        disconnected = true;
+                        break 'outer;
+                    }
                     ConnectorEvent::HardwareCursor(hc) => {
                         on.schedule.set_hardware_cursor(&hc);
                         on.hardware_cursor.set(hc);
                         self.state.refresh_hardware_cursors();
                     }
                     ConnectorEvent::ModeChanged(mode) => {
+                        let changed = on.global.mode.get() != mode;
                         on.update_mode(mode);
+                        if changed {
+                            if let Some(config) = self.state.config.get() {
+                                config.connector_mode_changed(self.id, mode);
+                            }
+                        }
+                    }
+                    ConnectorEvent::ModeFallback(requested) => {
+                        on.global.requested_mode.set(requested);
+                        if let Some(requested) = requested {
+                            if let Some(config) = self.state.config.get() {
+                                config.mode_fallback(self.id, requested, on.global.mode.get());
+                            }
+                        }
                     }
                     ConnectorEvent::VrrChanged(enabled) => {
                         on.schedule.set_vrr_enabled(enabled);
                     }
+                    ConnectorEvent::HdrChanged(enabled) => {
+                        if on.global.hdr_enabled.replace(enabled) != enabled {
+                            self.state.damage(on.global.pos.get());
+                        }
+                    }
                     ConnectorEvent::FormatsChanged(formats, format) => {
                         on.global.formats.set(formats);
                         on.global.format.set(format);
@@ -283,15 +345,28 @@ impl ConnectorHandler {
                     ev => unreachable!("received unexpected event {:?}", ev),
                 }
             }
+            if self.state.active_mirror_source(&self.data).is_some() {
+                break;
+            }
             self.data.async_event.triggered().await;
         }
-        if let Some(config) = self.state.config.get() {
-            config.connector_disconnected(self.id);
+        if disconnected {
+            if let Some(config) = self.state.config.get() {
+                config.connector_disconnected(self.id);
+            }
         }
+        info.initial_mode = on.global.mode.get();
         global.clear();
         for jo in on.jay_outputs.lock().drain_values() {
             jo.send_destroyed();
         }
+        for control in on.power_controls.lock().drain_values() {
+            control.send_failed();
+        }
+        if let Some(control) = on.gamma_control.take() {
+            control.deactivate();
+        }
+        self.data.connector.set_gamma_lut(None);
         let screencasts: Vec<_> = on.screencasts.lock().values().cloned().collect();
         for sc in screencasts {
             sc.do_destroy();
@@ -306,6 +381,7 @@ impl ConnectorHandler {
         self.state.root.outputs.remove(&self.id);
         self.state.output_extents_changed();
         self.state.outputs.remove(&self.id);
+        self.state.mirror_source_changed(self.id);
         on.lock_surface.take();
         {
             let mut surfaces = vec![];
@@ -348,6 +424,61 @@ impl ConnectorHandler {
         let _ = self.state.remove_global(&tray);
         self.state.tree_changed();
         self.state.damage(self.state.root.extents.get());
+        disconnected
+    }
+
+    /// Returns whether the connector was disconnected.
+    async fn handle_mirror_connected(
+        &self,
+        info: &mut MonitorInfo,
+        source: ConnectorId,
+        first: bool,
+    ) -> bool {
+        self.data.monitor_info.set(Some(Rc::new(info.clone())));
+        self.data.connector.set_powered(true);
+        let output_data = Rc::new(OutputData {
+            connector: self.data.clone(),
+            monitor_info: info.clone(),
+            node: None,
+            lease_connectors: Default::default(),
+        });
+        self.state.outputs.set(self.id, output_data);
+        self.data.connector.set_mirror_source(Some(source));
+        if first {
+            if let Some(config) = self.state.config.get() {
+                config.connector_connected(self.id);
+            }
+        }
+        let mut disconnected = false;
+        'outer: loop {
+            while let Some(event) = self.data.connector.event() {
+                match event {
+                    ConnectorEvent::Disconnected => {
+                        disconnected = true;
+                        break 'outer;
+                    }
+                    ConnectorEvent::ModeChanged(mode) => info.initial_mode = mode,
+                    ConnectorEvent::HardwareCursor(_)
+                    | ConnectorEvent::ModeFallback(_)
+                    | ConnectorEvent::VrrChanged(_)
+                    | ConnectorEvent::HdrChanged(_)
+                    | ConnectorEvent::FormatsChanged(..) => {}
+                    ev => unreachable!("received unexpected event {:?}", ev),
+                }
+            }
+            if self.state.active_mirror_source(&self.data) != Some(source) {
+                break;
+            }
+            self.data.async_event.triggered().await;
+        }
+        if disconnected {
+            if let Some(config) = self.state.config.get() {
+                config.connector_disconnected(self.id);
+            }
+        }
+        self.data.connector.set_mirror_source(None);
+        self.state.outputs.remove(&self.id);
+        disconnected
     }
 
     async fn handle_non_desktop_connected(&self, monitor_info: MonitorInfo) {
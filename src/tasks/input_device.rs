@@ -1,14 +1,22 @@
+mod button_debounce;
+mod disable_while_typing;
+mod key_remap;
+
 use {
     crate::{
         backend::{InputDevice, InputDeviceCapability},
         ifs::wl_seat::PX_PER_SCROLL,
         state::{DeviceHandlerData, InputDeviceData, State},
         tasks::udev_utils::{udev_props, UdevProps},
-        utils::asyncevent::AsyncEvent,
+        utils::{asyncevent::AsyncEvent, clonecell::CloneCell, errorfmt::ErrorFmt},
     },
+    futures_util::{select, FutureExt},
     jay_config::_private::DEFAULT_SEAT_NAME,
     std::{cell::Cell, rc::Rc},
 };
+pub use {
+    button_debounce::ButtonDebounce, disable_while_typing::DisableWhileTyping, key_remap::KeyRemap,
+};
 
 pub fn handle(state: &Rc<State>, dev: Rc<dyn InputDevice>) {
     let props = match dev.dev_t() {
@@ -19,10 +27,13 @@ pub fn handle(state: &Rc<State>, dev: Rc<dyn InputDevice>) {
         keyboard_id: state.physical_keyboard_ids.next(),
         seat: Default::default(),
         px_per_scroll_wheel: Cell::new(PX_PER_SCROLL),
+        button_debounce: Default::default(),
+        disable_while_typing: Default::default(),
+        key_remap: Default::default(),
         device: dev.clone(),
         syspath: props.syspath,
         devnode: props.devnode,
-        keymap: Default::default(),
+        keymap: CloneCell::new(dev.keymap()),
         output: Default::default(),
         tablet_init: dev.tablet_info(),
         tablet_pad_init: dev.tablet_pad_info(),
@@ -73,12 +84,24 @@ impl DeviceHandler {
             if self.dev.removed() {
                 break;
             }
+            let debounce = &self.data.button_debounce;
+            let dwt = &self.data.disable_while_typing;
+            let remap = &self.data.key_remap;
             if let Some(seat) = self.data.seat.get() {
                 let mut any_events = false;
+                let now = self.state.now_usec();
                 while let Some(event) = self.dev.event() {
-                    seat.event(&self.data, event);
+                    let event = remap.handle(&self.state.key_remap.borrow(), event);
+                    if dwt.suppresses(&seat, now, &event) {
+                        continue;
+                    }
+                    debounce.handle(event, |event| seat.event(&self.data, event));
                     any_events = true;
                 }
+                debounce.flush(self.state.now_usec(), |event| {
+                    seat.event(&self.data, event);
+                    any_events = true;
+                });
                 if any_events {
                     seat.mark_last_active();
                     self.state.input_occurred();
@@ -87,8 +110,29 @@ impl DeviceHandler {
                 while self.dev.event().is_some() {
                     // nothing
                 }
+                debounce.clear();
+                dwt.clear();
+                remap.clear();
+            }
+            match debounce.next_deadline_usec() {
+                Some(deadline) => {
+                    let ms = deadline
+                        .saturating_sub(self.state.now_usec())
+                        .div_ceil(1000);
+                    let res = select! {
+                        _ = self.ae.triggered().fuse() => Ok(()),
+                        res = self.state.wheel.timeout(ms).fuse() => res,
+                    };
+                    if let Err(e) = res {
+                        log::error!(
+                            "Could not wait for button debounce timeout: {}",
+                            ErrorFmt(e)
+                        );
+                        self.ae.triggered().await;
+                    }
+                }
+                None => self.ae.triggered().await,
             }
-            self.ae.triggered().await;
         }
         if let Some(config) = self.state.config.get() {
             config.del_input_device(self.dev.id());
@@ -0,0 +1,100 @@
+use {
+    crate::{
+        backend::{InputEvent, KeyState},
+        utils::numcell::NumCell,
+    },
+    std::cell::{Cell, RefCell},
+};
+
+/// Suppresses release+press pairs of mouse buttons that occur within a short time.
+///
+/// Releases are held back until the timeout expires. If the same button is pressed
+/// again before that, both events are dropped.
+#[derive(Default)]
+pub struct ButtonDebounce {
+    pub timeout_usec: Cell<u64>,
+    pub suppressed: NumCell<u64>,
+    pending: RefCell<Vec<PendingRelease>>,
+}
+
+#[derive(Copy, Clone)]
+struct PendingRelease {
+    time_usec: u64,
+    button: u32,
+}
+
+impl PendingRelease {
+    fn event(self) -> InputEvent {
+        InputEvent::Button {
+            time_usec: self.time_usec,
+            button: self.button,
+            state: KeyState::Released,
+        }
+    }
+}
+
+impl ButtonDebounce {
+    pub fn handle(&self, event: InputEvent, mut emit: impl FnMut(InputEvent)) {
+        let InputEvent::Button {
+            time_usec,
+            button,
+            state,
+        } = event
+        else {
+            emit(event);
+            return;
+        };
+        let timeout = self.timeout_usec.get();
+        match state {
+            KeyState::Released if timeout > 0 => {
+                self.flush(time_usec, &mut emit);
+                self.pending
+                    .borrow_mut()
+                    .push(PendingRelease { time_usec, button });
+            }
+            KeyState::Released => emit(event),
+            KeyState::Pressed => {
+                let pending = {
+                    let pending = &mut *self.pending.borrow_mut();
+                    let idx = pending.iter().position(|p| p.button == button);
+                    idx.map(|idx| pending.remove(idx))
+                };
+                if let Some(pending) = pending {
+                    if time_usec.saturating_sub(pending.time_usec) < timeout {
+                        self.suppressed.fetch_add(1);
+                        return;
+                    }
+                    emit(pending.event());
+                }
+                emit(event);
+            }
+        }
+    }
+
+    /// Emits all releases whose timeout has expired at `now_usec`.
+    pub fn flush(&self, now_usec: u64, mut emit: impl FnMut(InputEvent)) {
+        let timeout = self.timeout_usec.get();
+        let expired: Vec<_> = {
+            let pending = &mut *self.pending.borrow_mut();
+            let n = pending
+                .iter()
+                .take_while(|p| p.time_usec.saturating_add(timeout) <= now_usec)
+                .count();
+            pending.drain(..n).collect()
+        };
+        for pending in expired {
+            emit(pending.event());
+        }
+    }
+
+    /// Returns the time at which the next pending release expires.
+    pub fn next_deadline_usec(&self) -> Option<u64> {
+        let pending = self.pending.borrow();
+        let first = pending.first()?;
+        Some(first.time_usec.saturating_add(self.timeout_usec.get()))
+    }
+
+    pub fn clear(&self) {
+        self.pending.borrow_mut().clear();
+    }
+}
@@ -0,0 +1,58 @@
+use {
+    crate::{
+        backend::{InputEvent, KeyState},
+        ifs::wl_seat::WlSeatGlobal,
+    },
+    std::cell::{Cell, RefCell},
+};
+
+/// Ignores pointer motion, button presses, and scrolling of a device for a short time
+/// after a key was typed on any keyboard of the seat.
+///
+/// Unlike the libinput setting, this also works for touchpads and keyboards that
+/// libinput does not pair, e.g. external devices.
+#[derive(Default)]
+pub struct DisableWhileTyping {
+    pub window_usec: Cell<u64>,
+    suppressed_buttons: RefCell<Vec<u32>>,
+}
+
+impl DisableWhileTyping {
+    /// Returns whether the event should be dropped.
+    pub fn suppresses(&self, seat: &WlSeatGlobal, now_usec: u64, event: &InputEvent) -> bool {
+        if let InputEvent::Button {
+            button,
+            state: KeyState::Released,
+            ..
+        } = *event
+        {
+            let buttons = &mut *self.suppressed_buttons.borrow_mut();
+            if let Some(idx) = buttons.iter().position(|&b| b == button) {
+                buttons.swap_remove(idx);
+                return true;
+            }
+            return false;
+        }
+        let window = self.window_usec.get();
+        if window == 0 || now_usec.saturating_sub(seat.last_typing()) >= window {
+            return false;
+        }
+        match *event {
+            InputEvent::Button { button, .. } => {
+                self.suppressed_buttons.borrow_mut().push(button);
+                true
+            }
+            InputEvent::Motion { .. }
+            | InputEvent::AxisPx { .. }
+            | InputEvent::AxisSource { .. }
+            | InputEvent::AxisStop { .. }
+            | InputEvent::Axis120 { .. }
+            | InputEvent::AxisFrame { .. } => true,
+            _ => false,
+        }
+    }
+
+    pub fn clear(&self) {
+        self.suppressed_buttons.borrow_mut().clear();
+    }
+}
@@ -0,0 +1,54 @@
+use {
+    crate::backend::{InputEvent, KeyState},
+    ahash::AHashMap,
+    std::cell::RefCell,
+};
+
+/// Replaces the evdev codes of key and button events before they reach the seat.
+///
+/// The device table takes precedence over the global table. Releases use the code that
+/// the press was mapped to, even if the tables have changed in between.
+#[derive(Default)]
+pub struct KeyRemap {
+    pub map: RefCell<AHashMap<u32, u32>>,
+    pressed: RefCell<AHashMap<u32, u32>>,
+}
+
+impl KeyRemap {
+    pub fn handle(&self, global: &AHashMap<u32, u32>, mut event: InputEvent) -> InputEvent {
+        let (code, state) = match &mut event {
+            InputEvent::Key { key, state, .. } => (key, *state),
+            InputEvent::Button { button, state, .. } => (button, *state),
+            _ => return event,
+        };
+        match state {
+            KeyState::Pressed => {
+                let target = self
+                    .map
+                    .borrow()
+                    .get(code)
+                    .or_else(|| global.get(code))
+                    .copied();
+                match target {
+                    Some(target) if target != *code => {
+                        self.pressed.borrow_mut().insert(*code, target);
+                        *code = target;
+                    }
+                    _ => {
+                        self.pressed.borrow_mut().remove(code);
+                    }
+                }
+            }
+            KeyState::Released => {
+                if let Some(target) = self.pressed.borrow_mut().remove(code) {
+                    *code = target;
+                }
+            }
+        }
+        event
+    }
+
+    pub fn clear(&self) {
+        self.pressed.borrow_mut().clear();
+    }
+}
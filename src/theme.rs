@@ -205,6 +205,9 @@ colors! {
     bar_text = (0xff, 0xff, 0xff),
     attention_requested_background = (0x23, 0x09, 0x2c),
     highlight = (0x9d, 0x28, 0xc6, 0x7f),
+    notification_background = (0x22, 0x22, 0x22),
+    notification_border = (0x28, 0x55, 0x77),
+    notification_text = (0xff, 0xff, 0xff),
 }
 
 macro_rules! sizes {
@@ -282,6 +285,8 @@ macro_rules! sizes {
 sizes! {
     title_height = (1, 1000, 17),
     border_width = (1, 1000, 4),
+    inner_gap = (0, 1000, 0),
+    outer_gap = (0, 1000, 0),
 }
 
 pub const DEFAULT_FONT: &str = "monospace 8";
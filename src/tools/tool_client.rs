@@ -74,7 +74,7 @@ pub enum ToolClientError {
 pub struct ToolClient {
     pub _logger: Arc<Logger>,
     pub ring: Rc<IoUring>,
-    pub _wheel: Rc<Wheel>,
+    pub wheel: Rc<Wheel>,
     pub eng: Rc<AsyncEngine>,
     obj_ids: RefCell<Bitfield>,
     handlers: RefCell<
@@ -176,7 +176,7 @@ impl ToolClient {
         let slf = Rc::new(Self {
             _logger: logger,
             ring,
-            _wheel: wheel,
+            wheel,
             eng,
             obj_ids: RefCell::new(obj_ids),
             handlers: Default::default(),
@@ -332,7 +332,7 @@ impl ToolClient {
             self_id: s.registry,
             name: s.jay_compositor.0,
             interface: JayCompositor.name(),
-            version: s.jay_compositor.1.min(13),
+            version: s.jay_compositor.1.min(35),
             id: id.into(),
         });
         self.jay_compositor.set(Some(id));
@@ -84,7 +84,6 @@ impl NodeIds {
 pub struct NodeId(pub u32);
 
 impl NodeId {
-    #[expect(dead_code)]
     pub fn raw(&self) -> u32 {
         self.0
     }
@@ -5,12 +5,16 @@ use {
         cursor_user::CursorUser,
         fixed::Fixed,
         gfx_api::GfxTexture,
-        ifs::wl_seat::{
-            collect_kb_foci, collect_kb_foci2,
-            tablet::{TabletTool, TabletToolChanges, TabletToolId},
-            wl_pointer::PendingScroll,
-            NodeSeatState, SeatId, WlSeatGlobal, BTN_LEFT, BTN_RIGHT,
+        ifs::{
+            wl_seat::{
+                collect_kb_foci, collect_kb_foci2, scroll_pointer_input,
+                tablet::{TabletTool, TabletToolChanges, TabletToolId},
+                wl_pointer::PendingScroll,
+                NodeSeatState, SeatId, WlSeatGlobal, BTN_LEFT, BTN_RIGHT,
+            },
+            xdg_toplevel_icon_v1::ToplevelIcon,
         },
+        interactive_resize::InteractiveResizeGuard,
         rect::Rect,
         renderer::Renderer,
         scale::Scale,
@@ -37,7 +41,11 @@ use {
         },
     },
     ahash::AHashMap,
-    jay_config::Axis,
+    jay_config::{
+        input::{PointerInput, PointerTarget},
+        layout::{LayoutRequest, LayoutWindow},
+        Axis,
+    },
     smallvec::SmallVec,
     std::{
         cell::{Cell, RefCell},
@@ -95,6 +103,7 @@ pub struct ContainerTitle {
     pub x: i32,
     pub y: i32,
     pub tex: Rc<dyn GfxTexture>,
+    pub icon: Option<Rc<ToplevelIcon>>,
 }
 
 #[derive(Default)]
@@ -121,6 +130,8 @@ pub struct ContainerNode {
     pub content_width: Cell<i32>,
     pub content_height: Cell<i32>,
     pub sum_factors: Cell<f64>,
+    custom_layout: Cell<bool>,
+    layout_windows: RefCell<Vec<LayoutWindow>>,
     layout_scheduled: Cell<bool>,
     compute_render_positions_scheduled: Cell<bool>,
     render_titles_scheduled: Cell<bool>,
@@ -226,6 +237,8 @@ impl ContainerNode {
             content_width: Cell::new(0),
             content_height: Cell::new(0),
             sum_factors: Cell::new(1.0),
+            custom_layout: Cell::new(false),
+            layout_windows: Default::default(),
             layout_scheduled: Cell::new(false),
             compute_render_positions_scheduled: Cell::new(false),
             render_titles_scheduled: Cell::new(false),
@@ -382,7 +395,7 @@ impl ContainerNode {
         );
     }
 
-    fn schedule_layout(self: &Rc<Self>) {
+    pub fn schedule_layout(self: &Rc<Self>) {
         if !self.layout_scheduled.replace(true) {
             self.state.pending_container_layout.push(self.clone());
             if self.toplevel_data.visible.get() {
@@ -396,9 +409,10 @@ impl ContainerNode {
             return;
         }
         self.layout_scheduled.set(false);
+        self.custom_layout.set(false);
         if let Some(child) = self.mono_child.get() {
             self.perform_mono_layout(&child);
-        } else {
+        } else if !self.perform_custom_layout() {
             self.perform_split_layout();
         }
         self.state.tree_changed();
@@ -436,9 +450,15 @@ impl ContainerNode {
         }
     }
 
+    /// Returns the space between two tiled children: the border plus the inner gap of
+    /// the workspace.
+    fn spacing(&self) -> i32 {
+        self.state.theme.sizes.border_width.get() + self.workspace.get().inner_gap()
+    }
+
     fn perform_split_layout(self: &Rc<Self>) {
         let sum_factors = self.sum_factors.get();
-        let border_width = self.state.theme.sizes.border_width.get();
+        let border_width = self.spacing();
         let title_height = self.state.theme.sizes.title_height.get();
         let split = self.split.get();
         let (content_size, other_content_size) = match split {
@@ -523,8 +543,78 @@ impl ContainerNode {
         }
     }
 
+    /// Lays out the children with the layout of the config if this is the root container
+    /// of a workspace that uses such a layout.
+    fn perform_custom_layout(self: &Rc<Self>) -> bool {
+        let ws = self.workspace.get();
+        if ws.container.get().map(|c| c.id) != Some(self.id) {
+            return false;
+        }
+        let Some(config) = self.state.config.get() else {
+            return false;
+        };
+        let Some(layout) = config.workspace_layout(&ws.name.borrow()) else {
+            return false;
+        };
+        let windows: Vec<_> = self
+            .children
+            .iter()
+            .map(|c| LayoutWindow(c.node.node_id().raw() as u64))
+            .collect();
+        let request = {
+            let prev = self.layout_windows.borrow();
+            LayoutRequest {
+                width: self.width.get(),
+                height: self.height.get(),
+                windows: windows.clone(),
+                added: windows
+                    .iter()
+                    .filter(|w| !prev.contains(w))
+                    .copied()
+                    .collect(),
+                removed: prev
+                    .iter()
+                    .filter(|w| !windows.contains(w))
+                    .copied()
+                    .collect(),
+            }
+        };
+        let Some(geometries) = config.layout(layout, request) else {
+            return false;
+        };
+        if geometries.len() != windows.len() {
+            log::warn!(
+                "Layout returned {} geometries for {} windows",
+                geometries.len(),
+                windows.len(),
+            );
+            return false;
+        }
+        *self.layout_windows.borrow_mut() = windows;
+        let width = self.width.get();
+        let height = self.height.get();
+        let title_height = self.state.theme.sizes.title_height.get();
+        for (child, g) in self.children.iter().zip(geometries) {
+            let x1 = g.x.clamp(0, width);
+            let y1 = g.y.clamp(0, height);
+            let x2 = g.x.saturating_add(g.width).clamp(x1, width);
+            let y2 = g.y.saturating_add(g.height).clamp(y1, height);
+            let body_y1 = y1.saturating_add(title_height + 1).min(y2);
+            child
+                .title_rect
+                .set(Rect::new_unchecked(x1, y1, x2, (y1 + title_height).min(y2)));
+            let body = Rect::new_unchecked(x1, body_y1, x2, y2);
+            child.body.set(body);
+            let body = body.move_(self.abs_x1.get(), self.abs_y1.get());
+            child.node.clone().tl_change_extents(&body);
+            child.position_content();
+        }
+        self.custom_layout.set(true);
+        true
+    }
+
     fn update_content_size(&self) {
-        let border_width = self.state.theme.sizes.border_width.get();
+        let border_width = self.spacing();
         let title_height = self.state.theme.sizes.title_height.get();
         let nc = self.num_children.get();
         match self.split.get() {
@@ -635,7 +725,7 @@ impl ContainerNode {
             }
             return;
         }
-        let new_cursor = if self.mono_child.is_some() {
+        let new_cursor = if self.mono_child.is_some() || self.custom_layout.get() {
             KnownCursor::Default
         } else if self.split.get() == ContainerSplit::Horizontal {
             if y < title_height + 1 {
@@ -714,12 +804,13 @@ impl ContainerNode {
             };
             let title = child.title.borrow_mut();
             let tt = &mut *child.title_tex.borrow_mut();
+            let icon_width = child.node.tl_data().title_icon_width(th);
             for (scale, _) in scales.iter() {
                 let tex = tt
                     .get_or_insert_with(*scale, || TextTexture::new(&self.state.cpu_worker, &ctx));
                 let mut th = th;
                 let mut scalef = None;
-                let mut width = rect.width();
+                let mut width = (rect.width() - icon_width).max(0);
                 if *scale != 1 {
                     let scale = scale.to_f64();
                     th = (th as f64 * scale).round() as _;
@@ -769,6 +860,7 @@ impl ContainerNode {
                         x: rect.x1(),
                         y: rect.y1(),
                         tex,
+                        icon: child.node.tl_data().icon.get(),
                     })
                 }
             }
@@ -804,6 +896,7 @@ impl ContainerNode {
         rd.last_active_rect.take();
         let last_active = self.focus_history.last().map(|v| v.node.node_id());
         let mono = self.mono_child.is_some();
+        let custom = self.custom_layout.get();
         let split = self.split.get();
         let have_active = self.children.iter().any(|c| c.active.get());
         let abs_x = self.abs_x1.get();
@@ -813,7 +906,7 @@ impl ContainerNode {
             if self.toplevel_data.visible.get() {
                 self.state.damage(rect.move_(abs_x, abs_y));
             }
-            if i > 0 {
+            if i > 0 && !custom {
                 let rect = if mono {
                     Rect::new_sized(rect.x1() - bw, 0, bw, th)
                 } else if split == ContainerSplit::Horizontal {
@@ -844,6 +937,7 @@ impl ContainerNode {
                         x: rect.x1(),
                         y: rect.y1(),
                         tex,
+                        icon: child.node.tl_data().icon.get(),
                     })
                 }
             }
@@ -930,6 +1024,10 @@ impl ContainerNode {
         self.update_title();
     }
 
+    pub fn num_children(&self) -> usize {
+        self.num_children.get()
+    }
+
     pub fn set_split(self: &Rc<Self>, split: ContainerSplit) {
         if self.split.replace(split) != split {
             self.update_content_size();
@@ -1134,7 +1232,7 @@ impl ContainerNode {
                 self.mod_attention_requests(true);
             }
         }
-        self.update_child_title(child, &data.title.borrow());
+        self.update_child_title(child, &data.display_title());
         self.update_child_active(child, data.active(), 1);
         {
             let pos = data.pos.get();
@@ -1160,6 +1258,73 @@ impl ContainerNode {
         }
     }
 
+    fn title_child_at(&self, x: i32, y: i32) -> Option<NodeRef<ContainerChild>> {
+        self.children
+            .iter()
+            .find(|child| child.title_rect.get().contains(x, y))
+    }
+
+    /// Returns the child whose border contains the point.
+    ///
+    /// The caller must ensure that the point is not in a title.
+    fn border_child_at(&self, x: i32, y: i32) -> Option<NodeRef<ContainerChild>> {
+        if let Some(mono) = self.mono_child.get() {
+            return Some(mono);
+        }
+        let horizontal = self.split.get() == ContainerSplit::Horizontal;
+        let mut last = None;
+        for child in self.children.iter() {
+            let body = child.body.get();
+            let end = match horizontal {
+                true => body.x2(),
+                false => body.y2(),
+            };
+            let pos = match horizontal {
+                true => x,
+                false => y,
+            };
+            if pos < end {
+                return Some(child);
+            }
+            last = Some(child);
+        }
+        last
+    }
+
+    fn pointer_target_at(
+        &self,
+        x: i32,
+        y: i32,
+    ) -> Option<(PointerTarget, NodeRef<ContainerChild>)> {
+        if let Some(child) = self.title_child_at(x, y) {
+            return Some((PointerTarget::Title, child));
+        }
+        self.border_child_at(x, y)
+            .map(|child| (PointerTarget::Border, child))
+    }
+
+    fn invoke_pointer_binding(
+        self: &Rc<Self>,
+        seat: &Rc<WlSeatGlobal>,
+        target: PointerTarget,
+        child: &NodeRef<ContainerChild>,
+        input: PointerInput,
+        count: i32,
+    ) -> bool {
+        if !seat.has_pointer_binding(target, input) {
+            return false;
+        }
+        self.activate_child(child);
+        child
+            .node
+            .clone()
+            .node_do_focus(seat, Direction::Unspecified);
+        for _ in 0..count {
+            seat.invoke_pointer_binding(target, input);
+        }
+        true
+    }
+
     fn toggle_mono(self: &Rc<Self>) {
         if self.mono_child.is_some() {
             self.set_mono(None);
@@ -1181,8 +1346,25 @@ impl ContainerNode {
             Some(s) => s,
             _ => return,
         };
+        if pressed && seat_data.op.is_none() {
+            if let Some((target, child)) = self.pointer_target_at(seat_data.x, seat_data.y) {
+                drop(seat_datas);
+                let input = PointerInput::Button(button);
+                if self.invoke_pointer_binding(seat, target, &child, input, 1) {
+                    return;
+                }
+                seat_datas = self.cursors.borrow_mut();
+            }
+        }
+        let seat_data = match seat_datas.get_mut(&id) {
+            Some(s) => s,
+            _ => return,
+        };
         if button == BTN_RIGHT && pressed {
-            if self.mono_child.is_some() || self.split.get() == ContainerSplit::Horizontal {
+            let custom = self.custom_layout.get();
+            if self.mono_child.is_some()
+                || (!custom && self.split.get() == ContainerSplit::Horizontal)
+            {
                 if seat_data.y < self.state.theme.sizes.title_height.get() {
                     self.toggle_mono();
                 }
@@ -1203,7 +1385,7 @@ impl ContainerNode {
                 return;
             }
             let (kind, child) = 'res: {
-                let mono = self.mono_child.is_some();
+                let mono = self.mono_child.is_some() || self.custom_layout.get();
                 for child in self.children.iter() {
                     let rect = child.title_rect.get();
                     if rect.contains(seat_data.x, seat_data.y) {
@@ -1250,11 +1432,16 @@ impl ContainerNode {
                 seat.set_tl_floating(child.node.clone(), true);
                 return;
             }
+            let resize = match kind {
+                SeatOpKind::Move => None,
+                SeatOpKind::Resize { .. } => Some(self.state.interactive_resize.begin(&self.state)),
+            };
             seat_data.op = Some(SeatOp {
                 child,
                 kind,
                 x: seat_data.x,
                 y: seat_data.y,
+                _resize: resize,
             })
         } else if !pressed {
             seat_data.op = None;
@@ -1464,6 +1651,7 @@ struct SeatOp {
     kind: SeatOpKind,
     x: i32,
     y: i32,
+    _resize: Option<InteractiveResizeGuard>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -1636,17 +1824,26 @@ impl Node for ContainerNode {
             Some(s) => s,
             _ => return,
         };
-        if seat_data.y > self.state.theme.sizes.title_height.get() {
+        let y = seat_data.y;
+        let target = self.pointer_target_at(seat_data.x, y);
+        drop(seat_datas);
+        let discrete = match self.scroller.handle(event) {
+            Some(d) => d,
+            _ => return,
+        };
+        if let Some((target, child)) = &target {
+            let input = scroll_pointer_input(discrete);
+            if self.invoke_pointer_binding(seat, *target, child, input, discrete.abs()) {
+                return;
+            }
+        }
+        if y > self.state.theme.sizes.title_height.get() {
             return;
         }
         let cur_mc = match self.mono_child.get() {
             Some(mc) => mc,
             _ => return,
         };
-        let discrete = match self.scroller.handle(event) {
-            Some(d) => d,
-            _ => return,
-        };
         let mut new_mc = cur_mc.clone();
         for _ in 0..discrete.abs() {
             let new = if discrete < 0 {
@@ -1896,10 +2093,30 @@ impl ContainingNode for ContainerNode {
         self.schedule_compute_render_positions();
     }
 
+    fn cnode_child_icon_changed(self: Rc<Self>, child: &dyn Node) {
+        if self.child_nodes.borrow().contains_key(&child.node_id()) {
+            self.schedule_render_titles();
+        }
+    }
+
     fn cnode_workspace(self: Rc<Self>) -> Rc<WorkspaceNode> {
         self.workspace.get()
     }
 
+    fn cnode_make_visible(self: Rc<Self>, child: &dyn Node) {
+        let child = self
+            .child_nodes
+            .borrow()
+            .get(&child.node_id())
+            .map(|c| c.to_ref());
+        if let Some(child) = child {
+            self.activate_child(&child);
+        }
+        if let Some(parent) = self.toplevel_data.parent.get() {
+            parent.cnode_make_visible(&*self);
+        }
+    }
+
     fn cnode_set_child_position(self: Rc<Self>, child: &dyn Node, x: i32, y: i32) {
         let Some(parent) = self.toplevel_data.parent.get() else {
             return;
@@ -1928,7 +2145,7 @@ impl ContainingNode for ContainerNode {
     ) {
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
-        let bw = theme.sizes.border_width.get();
+        let spacing = self.spacing();
         let mut left_outside = false;
         let mut right_outside = false;
         let mut top_outside = false;
@@ -1968,8 +2185,8 @@ impl ContainingNode for ContainerNode {
                 ci = 1;
             }
             let (new_delta, between) = match split {
-                ContainerSplit::Horizontal => (self.abs_x1.get(), bw),
-                ContainerSplit::Vertical => (self.abs_y1.get(), bw + th + 1),
+                ContainerSplit::Horizontal => (self.abs_x1.get(), spacing),
+                ContainerSplit::Vertical => (self.abs_y1.get(), spacing + th + 1),
             };
             let new_i1 = new_i1.map(|v| v - new_delta);
             let new_i2 = new_i2.map(|v| v - new_delta);
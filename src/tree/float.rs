@@ -5,9 +5,12 @@ use {
         cursor_user::CursorUser,
         fixed::Fixed,
         ifs::wl_seat::{
+            scroll_pointer_input,
             tablet::{TabletTool, TabletToolChanges, TabletToolId},
+            wl_pointer::PendingScroll,
             NodeSeatState, SeatId, WlSeatGlobal, BTN_LEFT,
         },
+        interactive_resize::InteractiveResizeGuard,
         rect::Rect,
         renderer::Renderer,
         scale::Scale,
@@ -20,10 +23,11 @@ use {
         utils::{
             asyncevent::AsyncEvent, clonecell::CloneCell, double_click_state::DoubleClickState,
             errorfmt::ErrorFmt, linkedlist::LinkedNode, on_drop_event::OnDropEvent,
-            smallmap::SmallMapMut,
+            scroller::Scroller, smallmap::SmallMapMut,
         },
     },
     ahash::AHashMap,
+    jay_config::input::{PointerInput, PointerTarget},
     std::{
         cell::{Cell, RefCell},
         fmt::{Debug, Formatter},
@@ -51,6 +55,7 @@ pub struct FloatNode {
     pub title_textures: RefCell<SmallMapMut<Scale, TextTexture, 2>>,
     cursors: RefCell<AHashMap<CursorType, CursorState>>,
     pub attention_requested: Cell<bool>,
+    scroller: Scroller,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
@@ -69,6 +74,7 @@ struct CursorState {
     dist_hor: i32,
     dist_ver: i32,
     double_click_state: DoubleClickState,
+    resize: Option<InteractiveResizeGuard>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -128,6 +134,7 @@ impl FloatNode {
             title_textures: Default::default(),
             cursors: Default::default(),
             attention_requested: Cell::new(false),
+            scroller: Default::default(),
         });
         floater.pull_child_properties();
         *floater.display_link.borrow_mut() = Some(state.root.stacked.add_last(floater.clone()));
@@ -144,6 +151,35 @@ impl FloatNode {
         floater
     }
 
+    /// Moves the float from a workspace area to another workspace area.
+    ///
+    /// The position relative to the top-left corner of the area is preserved if possible.
+    /// Otherwise the float is moved as little as possible to place it inside the new area.
+    pub fn relocate(self: &Rc<Self>, from: &Rect, to: &Rect) {
+        let pos = self.position.get();
+        let clamp = |v: i32, lo: i32, hi: i32| v.min(hi).max(lo);
+        let x = clamp(
+            pos.x1() - from.x1() + to.x1(),
+            to.x1(),
+            to.x2() - pos.width(),
+        );
+        let y = clamp(
+            pos.y1() - from.y1() + to.y1(),
+            to.y1(),
+            to.y2() - pos.height(),
+        );
+        if pos.position() == (x, y) {
+            return;
+        }
+        let new_pos = pos.at_point(x, y);
+        self.position.set(new_pos);
+        if self.visible.get() {
+            self.state.damage(pos);
+            self.state.damage(new_pos);
+        }
+        self.schedule_layout();
+    }
+
     pub fn on_spaces_changed(self: &Rc<Self>) {
         self.schedule_layout();
     }
@@ -205,7 +241,17 @@ impl FloatNode {
             _ => return on_completed.event(),
         };
         let scales = self.state.scales.lock();
-        let tr = Rect::new_sized(pos.x1() + bw, pos.y1() + bw, pos.width() - 2 * bw, th).unwrap();
+        let icon_width = match self.child.get() {
+            Some(c) => c.tl_data().title_icon_width(th),
+            None => 0,
+        };
+        let tr = Rect::new_sized(
+            pos.x1() + bw + icon_width,
+            pos.y1() + bw,
+            (pos.width() - 2 * bw - icon_width).max(0),
+            th,
+        )
+        .unwrap();
         let tt = &mut *self.title_textures.borrow_mut();
         for (scale, _) in scales.iter() {
             let tex =
@@ -283,6 +329,7 @@ impl FloatNode {
             dist_hor: 0,
             dist_ver: 0,
             double_click_state: Default::default(),
+            resize: None,
         });
         seat_state.x = x;
         seat_state.y = y;
@@ -342,13 +389,28 @@ impl FloatNode {
                     y2 = y2.max(y1 + 2 * bw + th + 1);
                 }
             }
-            let new_pos = Rect::new(x1, y1, x2, y2).unwrap();
+            let mut new_pos = Rect::new(x1, y1, x2, y2).unwrap();
+            let mut new_output = None;
+            if seat_state.op_type == OpType::Move {
+                let output = cursor.output();
+                let old_output = self.workspace.get().output.get();
+                if output.id != old_output.id {
+                    let old_scale = old_output.global.persistent.scale.get();
+                    let new_scale = output.global.persistent.scale.get();
+                    new_pos = self.convert_scale(seat_state, new_pos, old_scale, new_scale);
+                    new_output = Some(output);
+                }
+            }
             self.position.set(new_pos);
             if self.visible.get() {
                 self.state.damage(pos);
                 self.state.damage(new_pos);
             }
             self.schedule_layout();
+            if let Some(output) = new_output {
+                drop(seats);
+                self.set_workspace(&output.ensure_workspace());
+            }
             return;
         }
         let resize_left = x < bw;
@@ -398,6 +460,44 @@ impl FloatNode {
         }
     }
 
+    /// Converts the size of the window so that it has the same physical size at the new
+    /// scale. The grabbed point of the window stays under the cursor.
+    fn convert_scale(
+        &self,
+        seat_state: &mut CursorState,
+        pos: Rect,
+        old_scale: Scale,
+        new_scale: Scale,
+    ) -> Rect {
+        if old_scale == new_scale {
+            return pos;
+        }
+        let theme = &self.state.theme;
+        let bw = theme.sizes.border_width.get();
+        let th = theme.sizes.title_height.get();
+        let factor = old_scale.to_f64() / new_scale.to_f64();
+        let convert = |v: i32| (v as f64 * factor).round() as i32;
+        let width = 2 * bw + convert(pos.width() - 2 * bw).max(1);
+        let height = 2 * bw + th + 1 + convert(pos.height() - 2 * bw - th - 1).max(1);
+        let cursor_x = pos.x1() + seat_state.dist_hor;
+        let cursor_y = pos.y1() + seat_state.dist_ver;
+        seat_state.dist_hor =
+            (seat_state.dist_hor as i64 * width as i64 / pos.width() as i64) as i32;
+        if seat_state.dist_ver > bw + th {
+            seat_state.dist_ver =
+                (seat_state.dist_ver as i64 * height as i64 / pos.height() as i64) as i32;
+        }
+        seat_state.x = seat_state.dist_hor;
+        seat_state.y = seat_state.dist_ver;
+        Rect::new_sized(
+            cursor_x - seat_state.dist_hor,
+            cursor_y - seat_state.dist_ver,
+            width,
+            height,
+        )
+        .unwrap()
+    }
+
     fn set_workspace(self: &Rc<Self>, ws: &Rc<WorkspaceNode>) {
         if let Some(c) = self.child.get() {
             c.tl_set_workspace(ws);
@@ -436,7 +536,7 @@ impl FloatNode {
                 .get()
                 .cnode_child_attention_request_changed(&**self, true);
         }
-        self.update_child_title(&data.title.borrow());
+        self.update_child_title(&data.display_title());
         self.update_child_active(data.active());
     }
 
@@ -458,6 +558,44 @@ impl FloatNode {
         }
     }
 
+    fn pointer_target(&self, id: CursorType) -> Option<PointerTarget> {
+        let cursors = self.cursors.borrow();
+        let cursor_data = cursors.get(&id)?;
+        if cursor_data.op_active {
+            return None;
+        }
+        if cursor_data.op_type != OpType::Move {
+            return Some(PointerTarget::Border);
+        }
+        let bw = self.state.theme.sizes.border_width.get();
+        let th = self.state.theme.sizes.title_height.get();
+        let pos = self.position.get();
+        let (x, y) = (cursor_data.x, cursor_data.y);
+        if x >= bw && x < pos.width() - bw && y >= bw && y < bw + th {
+            return Some(PointerTarget::Title);
+        }
+        Some(PointerTarget::Border)
+    }
+
+    fn invoke_pointer_binding(
+        &self,
+        seat: &Rc<WlSeatGlobal>,
+        target: PointerTarget,
+        input: PointerInput,
+        count: i32,
+    ) -> bool {
+        if !seat.has_pointer_binding(target, input) {
+            return false;
+        }
+        if let Some(tl) = self.child.get() {
+            tl.node_do_focus(seat, Direction::Unspecified);
+        }
+        for _ in 0..count {
+            seat.invoke_pointer_binding(target, input);
+        }
+        true
+    }
+
     fn button(
         self: Rc<Self>,
         id: CursorType,
@@ -494,6 +632,9 @@ impl FloatNode {
                 }
             }
             cursor_data.op_active = true;
+            if cursor_data.op_type != OpType::Move {
+                cursor_data.resize = Some(self.state.interactive_resize.begin(&self.state));
+            }
             let pos = self.position.get();
             match cursor_data.op_type {
                 OpType::Move => {
@@ -524,6 +665,7 @@ impl FloatNode {
             }
         } else if !pressed {
             cursor_data.op_active = false;
+            cursor_data.resize = None;
             let ws = cursor.output().ensure_workspace();
             self.set_workspace(&ws);
         }
@@ -634,11 +776,19 @@ impl Node for FloatNode {
         state: KeyState,
         _serial: u64,
     ) {
+        let id = CursorType::Seat(seat.id());
+        if state == KeyState::Pressed {
+            if let Some(target) = self.pointer_target(id) {
+                if self.invoke_pointer_binding(seat, target, PointerInput::Button(button), 1) {
+                    return;
+                }
+            }
+        }
         if button != BTN_LEFT {
             return;
         }
         self.button(
-            CursorType::Seat(seat.id()),
+            id,
             seat.pointer_cursor(),
             seat,
             time_usec,
@@ -646,6 +796,16 @@ impl Node for FloatNode {
         );
     }
 
+    fn node_on_axis_event(self: Rc<Self>, seat: &Rc<WlSeatGlobal>, event: &PendingScroll) {
+        let Some(target) = self.pointer_target(CursorType::Seat(seat.id())) else {
+            return;
+        };
+        let Some(steps) = self.scroller.handle(event) else {
+            return;
+        };
+        self.invoke_pointer_binding(seat, target, scroll_pointer_input(steps), steps.abs());
+    }
+
     fn node_on_pointer_enter(self: Rc<Self>, seat: &Rc<WlSeatGlobal>, x: Fixed, y: Fixed) {
         self.pointer_move(
             CursorType::Seat(seat.id()),
@@ -770,10 +930,18 @@ impl ContainingNode for FloatNode {
         }
     }
 
+    fn cnode_child_icon_changed(self: Rc<Self>, _child: &dyn Node) {
+        self.schedule_render_titles();
+    }
+
     fn cnode_workspace(self: Rc<Self>) -> Rc<WorkspaceNode> {
         self.workspace.get()
     }
 
+    fn cnode_make_visible(self: Rc<Self>, _child: &dyn Node) {
+        self.workspace.get().cnode_make_visible(&*self);
+    }
+
     fn cnode_set_child_position(self: Rc<Self>, _child: &dyn Node, x: i32, y: i32) {
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
@@ -1,14 +1,14 @@
 use {
     crate::{
-        backend::{HardwareCursor, KeyState, Mode},
+        backend::{GammaLut, HardwareCursor, KeyState, Mode},
         client::ClientId,
         cursor::KnownCursor,
         fixed::Fixed,
-        gfx_api::{AcquireSync, BufferResv, GfxTexture, ReleaseSync},
+        gfx_api::{AcquireSync, BufferResv, ColorTransform, GfxTexture, ReleaseSync},
         ifs::{
             ext_image_copy::ext_image_copy_capture_session_v1::ExtImageCopyCaptureSessionV1,
             jay_output::JayOutput,
-            jay_screencast::JayScreencast,
+            jay_screencast::{JayScreencast, ScreencastWriteback},
             wl_buffer::WlBufferStorage,
             wl_output::WlOutputGlobal,
             wl_seat::{
@@ -28,32 +28,40 @@ use {
                 ext_workspace_manager_v1::WorkspaceManagerId,
             },
             wp_content_type_v1::ContentType,
+            zwlr_gamma_control_v1::ZwlrGammaControlV1,
             zwlr_layer_shell_v1::{BACKGROUND, BOTTOM, OVERLAY, TOP},
-            zwlr_screencopy_frame_v1::ZwlrScreencopyFrameV1,
+            zwlr_output_power_v1::ZwlrOutputPowerV1,
+            zwlr_screencopy_frame_v1::{ScreencopyDamage, ZwlrScreencopyFrameV1},
         },
         output_schedule::OutputSchedule,
         rect::Rect,
         renderer::Renderer,
         scale::Scale,
         state::State,
+        stats::OutputStats,
         text::TextTexture,
         tree::{
-            walker::NodeVisitor, Direction, FindTreeResult, FindTreeUsecase, FoundNode, Node,
-            NodeId, StackedNode, TddType, TileDragDestination, WorkspaceDragDestination,
-            WorkspaceNode, WorkspaceNodeId,
+            walker::NodeVisitor, Direction, EmptyWorkspaceBehavior, FindTreeResult,
+            FindTreeUsecase, FoundNode, Node, NodeId, StackedNode, TddType, TileDragDestination,
+            WorkspaceDragDestination, WorkspaceNode, WorkspaceNodeId,
         },
         utils::{
             asyncevent::AsyncEvent, clonecell::CloneCell, copyhashmap::CopyHashMap,
             errorfmt::ErrorFmt, event_listener::EventSource, hash_map_ext::HashMapExt,
             linkedlist::LinkedList, on_drop_event::OnDropEvent, scroller::Scroller,
-            transform_ext::TransformExt,
+            toplevel_identifier::ToplevelIdentifier, transform_ext::TransformExt,
+            workspace_identifier::workspace_identifier,
         },
         wire::{
-            ExtImageCopyCaptureSessionV1Id, JayOutputId, JayScreencastId, ZwlrScreencopyFrameV1Id,
+            ExtImageCopyCaptureSessionV1Id, JayOutputId, JayScreencastId, ZwlrOutputPowerV1Id,
+            ZwlrScreencopyFrameV1Id, ZwlrScreencopyManagerV1Id,
         },
     },
-    ahash::AHashMap,
-    jay_config::video::{TearingMode as ConfigTearingMode, Transform, VrrMode as ConfigVrrMode},
+    ahash::{AHashMap, AHashSet},
+    jay_config::{
+        input::{PointerInput, PointerTarget},
+        video::{TearingMode as ConfigTearingMode, Transform, VrrMode as ConfigVrrMode},
+    },
     smallvec::SmallVec,
     std::{
         cell::{Cell, RefCell},
@@ -89,19 +97,32 @@ pub struct OutputNode {
     pub update_render_data_scheduled: Cell<bool>,
     pub screencasts: CopyHashMap<(ClientId, JayScreencastId), Rc<JayScreencast>>,
     pub screencopies: CopyHashMap<(ClientId, ZwlrScreencopyFrameV1Id), Rc<ZwlrScreencopyFrameV1>>,
+    pub screencopy_damage: CopyHashMap<(ClientId, ZwlrScreencopyManagerV1Id), Rc<ScreencopyDamage>>,
     pub title_visible: Cell<bool>,
+    pub focused_toplevel: Cell<Option<ToplevelIdentifier>>,
+    pub bar_clock: RefCell<String>,
     pub schedule: Rc<OutputSchedule>,
     pub latch_event: EventSource<dyn LatchListener>,
     pub vblank_event: EventSource<dyn VblankListener>,
     pub presentation_event: EventSource<dyn PresentationListener>,
     pub render_margin_ns: Cell<u64>,
     pub flip_margin_ns: Cell<Option<u64>>,
+    pub expected_present_nsec: Cell<u64>,
+    pub previous_workspace: Cell<Option<WorkspaceNodeId>>,
+    pub previous_workspace_name: RefCell<Option<String>>,
+    pub software_cursor_damage: Cell<Option<Rect>>,
+    pub stats: OutputStats,
     pub ext_copy_sessions:
         CopyHashMap<(ClientId, ExtImageCopyCaptureSessionV1Id), Rc<ExtImageCopyCaptureSessionV1>>,
     pub before_latch_event: EventSource<dyn BeforeLatchListener>,
     pub tray_start_rel: Cell<i32>,
     pub tray_items: LinkedList<Rc<dyn DynTrayItem>>,
     pub ext_workspace_groups: CopyHashMap<WorkspaceManagerId, Rc<ExtWorkspaceGroupHandleV1>>,
+    pub powered: Cell<bool>,
+    pub power_controls: CopyHashMap<(ClientId, ZwlrOutputPowerV1Id), Rc<ZwlrOutputPowerV1>>,
+    pub gamma_control: CloneCell<Option<Rc<ZwlrGammaControlV1>>>,
+    pub client_gamma_lut: CloneCell<Option<Rc<GammaLut>>>,
+    pub workspace_swipe_offset: Cell<i32>,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -165,6 +186,7 @@ pub async fn output_render_data(state: Rc<State>) {
 
 impl OutputNode {
     pub async fn before_latch(&self, present: u64) {
+        self.expected_present_nsec.set(present);
         let mut res = BeforeLatchResult::None;
         for listener in self.before_latch_event.iter() {
             res |= listener.before_latch(present);
@@ -174,7 +196,19 @@ impl OutputNode {
         }
     }
 
+    /// Returns the time at which the contents of the latest frame will be shown.
+    ///
+    /// Captures of the frame use this time as their timestamp so that recorders can
+    /// synchronize them with audio.
+    pub fn present_nsec(&self) -> u64 {
+        match self.expected_present_nsec.get() {
+            0 => self.state.now_nsec(),
+            nsec => nsec,
+        }
+    }
+
     pub fn latched(&self, tearing: bool) {
+        self.stats.latched(self.state.now_nsec());
         self.schedule.latched();
         for listener in self.latch_event.iter() {
             listener.after_latch(self, tearing);
@@ -206,6 +240,8 @@ impl OutputNode {
         flags: u32,
         vrr: bool,
     ) {
+        self.stats
+            .presented(tv_sec * 1_000_000_000 + tv_nsec as u64);
         for listener in self.presentation_event.iter() {
             listener.presented(self, tv_sec, tv_nsec, refresh, seq, flags, vrr);
         }
@@ -244,6 +280,23 @@ impl OutputNode {
         self.screencast_changed();
     }
 
+    pub fn add_software_cursor_damage(&self, rect: Rect) {
+        let rect = match self.software_cursor_damage.get() {
+            Some(r) => r.union(rect),
+            None => rect,
+        };
+        self.software_cursor_damage.set(Some(rect));
+    }
+
+    pub fn damage_screencopies(&self, rect: Option<Rect>) {
+        for damage in self.screencopy_damage.lock().values() {
+            match rect {
+                Some(rect) => damage.damage(rect),
+                None => damage.damage_full(),
+            }
+        }
+    }
+
     pub fn screencast_changed(&self) {
         for ws in self.workspaces.iter() {
             ws.update_has_captures();
@@ -304,6 +357,39 @@ impl OutputNode {
         }
     }
 
+    /// Returns the transform that converts the contents of the output to sRGB.
+    pub fn capture_color_transform(&self) -> Option<ColorTransform> {
+        self.global
+            .hdr_enabled
+            .get()
+            .then(|| self.state.color_manager.hdr_capture_transform())
+    }
+
+    /// Claims a screencast buffer that can be filled by a writeback connector.
+    ///
+    /// `formats` are the formats supported by the writeback connector.
+    pub fn claim_writeback_buffer(&self, formats: &AHashSet<u32>) -> Option<ScreencastWriteback> {
+        if let Some(workspace) = self.workspace.get() {
+            if !workspace.may_capture.get() {
+                return None;
+            }
+        }
+        if self.global.persistent.transform.get() != Transform::None {
+            return None;
+        }
+        // Writeback connectors bypass the renderer and cannot black out windows or
+        // render the capture overlay.
+        if !self.state.redacted_capture_rects().is_empty() || self.state.capture_overlay.is_set() {
+            return None;
+        }
+        for sc in self.screencasts.lock().values() {
+            if let Some(wb) = sc.claim_writeback_buffer(self, formats) {
+                return Some(wb);
+            }
+        }
+        None
+    }
+
     pub fn perform_wlr_screencopies(
         &self,
         tex: &Rc<dyn GfxTexture>,
@@ -318,8 +404,21 @@ impl OutputNode {
         if self.screencopies.is_empty() {
             return;
         }
-        let now = self.state.now();
+        let present = self.present_nsec();
+        let mut undamaged = vec![];
         for capture in self.screencopies.lock().drain_values() {
+            let mut damage = None;
+            if capture.with_damage.get() {
+                damage = self
+                    .screencopy_damage
+                    .get(&(capture.client.id, capture.manager));
+                if let Some(damage) = &damage {
+                    if !damage.is_damaged() {
+                        undamaged.push(capture);
+                        continue;
+                    }
+                }
+            }
             let wl_buffer = match capture.buffer.take() {
                 Some(b) => b,
                 _ => {
@@ -349,6 +448,8 @@ impl OutputNode {
                             wl_buffer.format,
                             self.global.persistent.transform.get(),
                             self.global.persistent.scale.get(),
+                            render_hardware_cursors && capture.overlay_cursor,
+                            self.capture_color_transform(),
                         );
                         match res {
                             Ok(p) => {
@@ -381,12 +482,13 @@ impl OutputNode {
                             ReleaseSync::Implicit,
                             self.global.persistent.transform.get(),
                             self.global.pos.get(),
-                            render_hardware_cursors,
+                            render_hardware_cursors && capture.overlay_cursor,
                             x_off - capture.rect.x1(),
                             y_off - capture.rect.y1(),
                             size,
                             self.global.persistent.transform.get(),
                             self.global.persistent.scale.get(),
+                            self.capture_color_transform(),
                         );
                         if let Err(e) = res {
                             log::warn!("Could not perform screencopy: {}", ErrorFmt(e));
@@ -396,13 +498,21 @@ impl OutputNode {
                     }
                 }
             }
-            if capture.with_damage.get() {
-                capture.send_damage();
+            if let Some(damage) = damage {
+                capture.send_damage(
+                    &damage,
+                    self.global.persistent.scale.get(),
+                    self.global.persistent.transform.get(),
+                );
             }
             if ready {
-                capture.send_ready(now.0.tv_sec as _, now.0.tv_nsec as _);
+                capture.send_ready(present / 1_000_000_000, (present % 1_000_000_000) as u32);
             }
         }
+        for capture in undamaged {
+            self.screencopies
+                .set((capture.client.id, capture.id), capture);
+        }
         self.screencast_changed();
     }
 
@@ -418,8 +528,36 @@ impl OutputNode {
         self.jay_outputs.clear();
         self.screencasts.clear();
         self.screencopies.clear();
+        self.screencopy_damage.clear();
         self.ext_copy_sessions.clear();
         self.ext_workspace_groups.clear();
+        self.power_controls.clear();
+        self.gamma_control.take();
+        self.client_gamma_lut.take();
+    }
+
+    pub fn set_powered(&self, powered: bool) {
+        if self.powered.replace(powered) == powered {
+            return;
+        }
+        self.global.connector.connector.set_powered(powered);
+        for control in self.power_controls.lock().values() {
+            control.send_mode(powered);
+        }
+    }
+
+    pub fn set_client_gamma_lut(&self, lut: Option<Rc<GammaLut>>) {
+        self.client_gamma_lut.set(lut);
+        self.update_gamma_lut();
+    }
+
+    pub fn update_gamma_lut(&self) {
+        let connector = &self.global.connector.connector;
+        let lut = self
+            .state
+            .night_light
+            .apply(connector.gamma_lut_size(), self.client_gamma_lut.get());
+        connector.set_gamma_lut(lut);
     }
 
     pub fn on_spaces_changed(self: &Rc<Self>) {
@@ -491,7 +629,7 @@ impl OutputNode {
                 on_completed.clone(),
                 Some(texture_height),
                 &font,
-                &ws.name,
+                &ws.name.borrow(),
                 tc,
                 false,
                 scale,
@@ -502,7 +640,14 @@ impl OutputNode {
             tex_x: 0,
             tex: TextTexture::new(&self.state.cpu_worker, &ctx),
         });
-        let status = self.status.get();
+        let mut status = self.status.get();
+        let clock = self.bar_clock.borrow();
+        if !clock.is_empty() {
+            status = match status.is_empty() {
+                true => Rc::new(clock.clone()),
+                false => Rc::new(format!("{status}  {clock}")),
+            };
+        }
         let tc = self.state.theme.colors.bar_text.get();
         tex.tex.schedule_render_fitting(
             on_completed.clone(),
@@ -513,6 +658,23 @@ impl OutputNode {
             true,
             scale,
         );
+        if let Some(title) = self.focused_title() {
+            let tex = rd.focused_title.get_or_insert_with(|| OutputStatus {
+                tex_x: 0,
+                tex: TextTexture::new(&self.state.cpu_worker, &ctx),
+            });
+            tex.tex.schedule_render_fitting(
+                on_completed.clone(),
+                Some(texture_height),
+                &font,
+                &title,
+                tc,
+                false,
+                scale,
+            );
+        } else {
+            rd.focused_title = None;
+        }
         on_completed.event()
     }
 
@@ -595,6 +757,21 @@ impl OutputNode {
                 status.tex_x = pos;
             }
         }
+        rd.focused_title_rect = Rect::default();
+        let mut x2 = self.tray_start_rel.get();
+        if let Some(status) = &rd.status {
+            if status.tex.texture().is_some() {
+                x2 = status.tex_x;
+            }
+        }
+        if let Some(title) = &mut rd.focused_title {
+            if let Err(e) = title.tex.flip() {
+                log::error!("Could not render focused title: {}", ErrorFmt(e));
+            }
+            let x1 = pos + th / 2;
+            title.tex_x = x1;
+            rd.focused_title_rect = Rect::new(x1, 0, x2 - th / 2, th).unwrap_or_default();
+        }
         if self.title_visible.get() {
             let title_rect = Rect::new_sized(
                 non_exclusive_rect.x1(),
@@ -636,19 +813,13 @@ impl OutputNode {
                 return false;
             }
             collect_kb_foci2(old.clone(), &mut seats);
+            *self.previous_workspace_name.borrow_mut() = Some(old.name.borrow().clone());
             if old.is_empty() {
-                for jw in old.jay_workspaces.lock().values() {
-                    jw.send_destroyed();
-                    jw.workspace.set(None);
-                }
-                for wh in old.ext_workspaces.lock().values() {
-                    wh.handle_destroyed();
-                }
-                old.clear();
-                self.state.workspaces.remove(&old.name);
+                self.destroy_workspace(&old);
             } else {
                 old.set_visible(false);
                 old.flush_jay_workspaces();
+                self.previous_workspace.set(Some(old.id));
             }
         }
         self.update_visible();
@@ -665,9 +836,85 @@ impl OutputNode {
         true
     }
 
+    /// Returns the workspace that is revealed when the current workspace is moved by
+    /// `offset` during a workspace swipe.
+    pub fn workspace_swipe_neighbor(&self, offset: i32) -> Option<Rc<WorkspaceNode>> {
+        let ws = self.workspace.get()?;
+        let link = ws.output_link.borrow().as_ref()?.to_ref();
+        let neighbor = match offset > 0 {
+            true => link.prev(),
+            false => link.next(),
+        };
+        neighbor.map(|n| n.deref().clone())
+    }
+
+    pub fn set_workspace_swipe_offset(&self, offset: i32) {
+        if self.workspace_swipe_offset.replace(offset) != offset && self.node_visible() {
+            self.state.damage(self.global.pos.get());
+        }
+    }
+
+    fn destroy_workspace(&self, ws: &WorkspaceNode) {
+        for jw in ws.jay_workspaces.lock().values() {
+            jw.send_destroyed();
+            jw.workspace.set(None);
+        }
+        for wh in ws.ext_workspaces.lock().values() {
+            wh.handle_destroyed();
+        }
+        ws.clear();
+        self.state.workspaces.remove(&*ws.name.borrow());
+    }
+
+    fn previous_workspace(&self) -> Option<Rc<WorkspaceNode>> {
+        let id = self.previous_workspace.get()?;
+        self.workspaces
+            .iter()
+            .find(|ws| ws.id == id)
+            .map(|ws| (*ws).clone())
+    }
+
+    /// Applies the configured [`EmptyWorkspaceBehavior`] after the last window of a
+    /// workspace on this output has been closed.
+    pub fn last_window_closed(self: &Rc<Self>, ws: &Rc<WorkspaceNode>) {
+        if self.is_dummy || !ws.is_empty() {
+            return;
+        }
+        let visible = self.workspace.get().map(|w| w.id) == Some(ws.id);
+        match self.state.empty_workspace_behavior.get() {
+            EmptyWorkspaceBehavior::Keep => return,
+            EmptyWorkspaceBehavior::Destroy => {
+                if visible {
+                    self.workspace.take();
+                    ws.seat_state.destroy_node(&**ws);
+                }
+                self.destroy_workspace(ws);
+                if visible {
+                    self.update_visible();
+                }
+            }
+            EmptyWorkspaceBehavior::ShowPrevious => {
+                if !visible {
+                    return;
+                }
+                let Some(prev) = self.previous_workspace() else {
+                    return;
+                };
+                self.show_workspace(&prev);
+                prev.flush_jay_workspaces();
+            }
+        }
+        self.schedule_update_render_data();
+        self.state.tree_changed();
+        if self.node_visible() {
+            self.state.damage(self.global.pos.get());
+        }
+    }
+
     pub fn create_workspace(self: &Rc<Self>, name: &str) -> Rc<WorkspaceNode> {
         let ws = Rc::new(WorkspaceNode {
             id: self.state.node_ids.next(),
+            identifier: workspace_identifier(),
             state: self.state.clone(),
             is_dummy: false,
             output: CloneCell::new(self.clone()),
@@ -675,7 +922,7 @@ impl OutputNode {
             container: Default::default(),
             stacked: Default::default(),
             seat_state: Default::default(),
-            name: name.to_string(),
+            name: RefCell::new(name.to_string()),
             output_link: Default::default(),
             visible: Cell::new(false),
             fullscreen: Default::default(),
@@ -689,6 +936,7 @@ impl OutputNode {
             render_highlight: Default::default(),
             ext_workspaces: Default::default(),
             opt: Default::default(),
+            thumbnail: Default::default(),
         });
         ws.opt.set(Some(ws.clone()));
         ws.update_has_captures();
@@ -893,6 +1141,40 @@ impl OutputNode {
         FindTreeResult::Other
     }
 
+    fn focused_title(&self) -> Option<String> {
+        if !self.bar_show_focused_title() {
+            return None;
+        }
+        let tl = self.state.toplevels.get(&self.focused_toplevel.get()?)?;
+        let tl = tl.upgrade()?;
+        let title = tl.tl_data().title.borrow();
+        match title.is_empty() {
+            true => None,
+            false => Some(title.clone()),
+        }
+    }
+
+    pub fn bar_clock_format(&self) -> Option<Rc<String>> {
+        match self.global.persistent.bar_clock_format.get() {
+            Some(format) => format,
+            _ => self.state.default_bar_clock_format.get(),
+        }
+    }
+
+    pub fn bar_show_focused_title(&self) -> bool {
+        match self.global.persistent.bar_show_focused_title.get() {
+            Some(show) => show,
+            _ => self.state.default_bar_show_focused_title.get(),
+        }
+    }
+
+    pub fn set_bar_clock(self: &Rc<Self>, clock: String) {
+        if *self.bar_clock.borrow() != clock {
+            *self.bar_clock.borrow_mut() = clock;
+            self.schedule_update_render_data();
+        }
+    }
+
     pub fn set_status(self: &Rc<Self>, status: &Rc<String>) {
         self.status.set(status.clone());
         self.schedule_update_render_data();
@@ -959,6 +1241,14 @@ impl OutputNode {
         set_layer_visible!(self.layers[3], visible);
     }
 
+    fn pointer_in_bar(&self, id: PointerType) -> bool {
+        let Some((x, y)) = self.pointer_positions.get(&id) else {
+            return false;
+        };
+        let rect = self.non_exclusive_rect_rel.get();
+        rect.contains(x, y) && rect.translate(x, y).1 <= self.state.theme.sizes.title_height.get()
+    }
+
     fn button(self: Rc<Self>, id: PointerType) {
         let (x, y) = match self.pointer_positions.get(&id) {
             Some(p) => p,
@@ -1250,6 +1540,8 @@ pub struct OutputRenderData {
     pub captured_inactive_workspaces: Vec<Rect>,
     pub titles: Vec<OutputTitle>,
     pub status: Option<OutputStatus>,
+    pub focused_title: Option<OutputStatus>,
+    pub focused_title_rect: Rect,
 }
 
 impl Debug for OutputNode {
@@ -1429,6 +1721,12 @@ impl Node for OutputNode {
         state: KeyState,
         _serial: u64,
     ) {
+        if state == KeyState::Pressed
+            && !self.pointer_in_bar(PointerType::Seat(seat.id()))
+            && seat.invoke_pointer_binding(PointerTarget::Desktop, PointerInput::Button(button))
+        {
+            return;
+        }
         if button != BTN_LEFT {
             return;
         }
@@ -1447,6 +1745,11 @@ impl Node for OutputNode {
         if steps == 0 {
             return;
         }
+        if !self.pointer_in_bar(PointerType::Seat(seat.id()))
+            && seat.invoke_scroll_binding(PointerTarget::Desktop, steps)
+        {
+            return;
+        }
         let ws = match self.workspace.get() {
             Some(ws) => ws,
             _ => return,
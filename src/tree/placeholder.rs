@@ -49,14 +49,16 @@ pub async fn placeholder_render_textures(state: Rc<State>) {
 
 impl PlaceholderNode {
     pub fn new_for(state: &Rc<State>, node: Rc<dyn ToplevelNode>, slf: &Weak<Self>) -> Self {
+        let toplevel = ToplevelData::new(
+            state,
+            node.tl_data().title.borrow().clone(),
+            node.node_client(),
+            slf,
+        );
+        toplevel.icon.set(node.tl_data().icon.get());
         Self {
             id: state.node_ids.next(),
-            toplevel: ToplevelData::new(
-                state,
-                node.tl_data().title.borrow().clone(),
-                node.node_client(),
-                slf,
-            ),
+            toplevel,
             destroyed: Default::default(),
             update_textures_scheduled: Cell::new(false),
             state: state.clone(),
@@ -7,8 +7,12 @@ use {
             ext_image_copy::ext_image_copy_capture_session_v1::ExtImageCopyCaptureSessionV1,
             jay_screencast::JayScreencast,
             jay_toplevel::JayToplevel,
+            wl_output::WlOutputGlobal,
             wl_seat::{collect_kb_foci, collect_kb_foci2, NodeSeatState, SeatId},
             wl_surface::WlSurface,
+            xdg_toplevel_icon_v1::ToplevelIcon,
+            zwlr_foreign_toplevel_handle_v1::ZwlrForeignToplevelHandleV1,
+            zwlr_foreign_toplevel_manager_v1::ZwlrForeignToplevelManagerV1,
         },
         rect::Rect,
         state::State,
@@ -28,14 +32,16 @@ use {
         },
         wire::{
             ExtForeignToplevelHandleV1Id, ExtImageCopyCaptureSessionV1Id, JayScreencastId,
-            JayToplevelId,
+            JayToplevelId, ZwlrForeignToplevelHandleV1Id,
         },
     },
     std::{
         cell::{Cell, RefCell},
+        fmt::Write,
         ops::Deref,
         rc::{Rc, Weak},
     },
+    uapi::c,
 };
 
 tree_id!(ToplevelNodeId);
@@ -89,10 +95,11 @@ impl<T: ToplevelNodeBase> ToplevelNode for T {
 
     fn tl_title_changed(&self) {
         let data = self.tl_data();
-        let title = data.title.borrow_mut();
+        data.bar_title_changed();
         if let Some(parent) = data.parent.get() {
-            parent.node_child_title_changed(self, &title);
+            parent.node_child_title_changed(self, &data.display_title());
         }
+        let title = data.title.borrow_mut();
         if let Some(data) = data.fullscrceen_data.borrow_mut().deref() {
             data.placeholder
                 .tl_data()
@@ -138,12 +145,17 @@ impl<T: ToplevelNodeBase> ToplevelNode for T {
         for sc in data.ext_copy_sessions.lock().values() {
             sc.update_latch_listener();
         }
+        data.wlr_output_changed();
+        if !self.tl_as_node().node_is_container() && data.active() {
+            data.bar_output_changed();
+        }
     }
 
     fn tl_change_extents(self: Rc<Self>, rect: &Rect) {
         let data = self.tl_data();
         let prev = data.desired_extents.replace(*rect);
         if prev.size() != rect.size() {
+            data.state.interactive_resize.toplevel_resized(&*self);
             for sc in data.jay_screencasts.lock().values() {
                 sc.schedule_realloc_or_reconfigure();
             }
@@ -211,6 +223,10 @@ pub trait ToplevelNodeBase: Node {
     fn tl_scanout_surface(&self) -> Option<Rc<WlSurface>> {
         None
     }
+
+    fn tl_pid(&self) -> Option<c::pid_t> {
+        self.tl_data().client.as_ref().map(|c| c.pid_info.pid)
+    }
     fn tl_restack_popups(&self) {
         // nothing
     }
@@ -275,15 +291,19 @@ pub struct ToplevelData {
     pub wants_attention: Cell<bool>,
     pub requested_attention: Cell<bool>,
     pub app_id: RefCell<String>,
+    pub icon: CloneCell<Option<Rc<ToplevelIcon>>>,
     pub identifier: Cell<ToplevelIdentifier>,
     pub handles:
         CopyHashMap<(ClientId, ExtForeignToplevelHandleV1Id), Rc<ExtForeignToplevelHandleV1>>,
+    pub wlr_handles:
+        CopyHashMap<(ClientId, ZwlrForeignToplevelHandleV1Id), Rc<ZwlrForeignToplevelHandleV1>>,
     pub render_highlight: NumCell<u32>,
     pub jay_toplevels: CopyHashMap<(ClientId, JayToplevelId), Rc<JayToplevel>>,
     pub jay_screencasts: CopyHashMap<(ClientId, JayScreencastId), Rc<JayScreencast>>,
     pub ext_copy_sessions:
         CopyHashMap<(ClientId, ExtImageCopyCaptureSessionV1Id), Rc<ExtImageCopyCaptureSessionV1>>,
     pub slf: Weak<dyn ToplevelNode>,
+    pub marks: RefCell<Vec<String>>,
 }
 
 impl ToplevelData {
@@ -316,16 +336,56 @@ impl ToplevelData {
             wants_attention: Cell::new(false),
             requested_attention: Cell::new(false),
             app_id: Default::default(),
+            icon: Default::default(),
             identifier: Cell::new(id),
             handles: Default::default(),
+            wlr_handles: Default::default(),
             render_highlight: Default::default(),
             jay_toplevels: Default::default(),
             jay_screencasts: Default::default(),
             ext_copy_sessions: Default::default(),
             slf: slf.clone(),
+            marks: Default::default(),
         }
     }
 
+    /// Returns the title as it should be shown in title bars.
+    ///
+    /// If marks are shown, the marks of the toplevel are prepended to the title.
+    pub fn display_title(&self) -> String {
+        let title = self.title.borrow();
+        let marks = self.marks.borrow();
+        if marks.is_empty() || !self.state.show_marks.get() {
+            return title.clone();
+        }
+        let mut res = String::new();
+        for mark in marks.iter() {
+            let _ = write!(res, "[{}] ", mark);
+        }
+        res.push_str(&title);
+        res
+    }
+
+    pub fn marks_changed(&self) {
+        if !self.state.show_marks.get() {
+            return;
+        }
+        if let Some(tl) = self.slf.upgrade() {
+            tl.tl_title_changed();
+        }
+    }
+
+    pub fn remove_marks(&self) {
+        let marks = self.marks.take();
+        if marks.is_empty() {
+            return;
+        }
+        for mark in &marks {
+            self.state.marks.remove(mark);
+        }
+        self.marks_changed();
+    }
+
     pub fn active(&self) -> bool {
         self.active_surfaces.active() || self.self_active.get()
     }
@@ -336,9 +396,67 @@ impl ToplevelData {
         let active_new = self.active();
         if active_old != active_new {
             tl.tl_set_active(active_new);
+            if !tl.tl_as_node().node_is_container() {
+                self.update_bar_focus(active_new);
+            }
             if let Some(parent) = self.parent.get() {
                 parent.node_child_active_changed(tl.tl_as_node(), active_new, 1);
             }
+            self.wlr_state_changed();
+        }
+    }
+
+    fn update_bar_focus(&self, active: bool) {
+        let Some(ws) = self.workspace.get() else {
+            return;
+        };
+        let output = ws.output.get();
+        let id = self.identifier.get();
+        if active {
+            output.focused_toplevel.set(Some(id));
+        } else if output.focused_toplevel.get() == Some(id) {
+            output.focused_toplevel.set(None);
+        } else {
+            return;
+        }
+        if output.bar_show_focused_title() {
+            output.schedule_update_render_data();
+        }
+    }
+
+    fn bar_output_changed(&self) {
+        let id = self.identifier.get();
+        let new = self.workspace.get().map(|ws| ws.output.get());
+        for output in self.state.root.outputs.lock().values() {
+            if output.focused_toplevel.get() != Some(id) {
+                continue;
+            }
+            if new.as_ref().map(|n| n.id) == Some(output.id) {
+                continue;
+            }
+            output.focused_toplevel.set(None);
+            if output.bar_show_focused_title() {
+                output.schedule_update_render_data();
+            }
+        }
+        if let Some(output) = new {
+            output.focused_toplevel.set(Some(id));
+            if output.bar_show_focused_title() {
+                output.schedule_update_render_data();
+            }
+        }
+    }
+
+    fn bar_title_changed(&self) {
+        let Some(ws) = self.workspace.get() else {
+            return;
+        };
+        let output = ws.output.get();
+        if !output.bar_show_focused_title() {
+            return;
+        }
+        if output.focused_toplevel.get() == Some(self.identifier.get()) {
+            output.schedule_update_render_data();
         }
     }
 
@@ -360,6 +478,9 @@ impl ToplevelData {
     }
 
     pub fn destroy_node(&self, node: &dyn Node) {
+        for mark in self.marks.take() {
+            self.state.marks.remove(&mark);
+        }
         for jay_tl in self.jay_toplevels.lock().drain_values() {
             jay_tl.destroy();
         }
@@ -381,7 +502,19 @@ impl ToplevelData {
                 handle.send_closed();
             }
         }
+        for handle in self.wlr_handles.lock().drain_values() {
+            handle.send_closed();
+        }
+        if !node.node_is_placeholder() {
+            self.state.window_states.record(self);
+        }
+        let ws = self.workspace.get();
         self.detach_node(node);
+        if !node.node_is_placeholder() && !node.node_is_container() {
+            if let Some(ws) = ws {
+                ws.output.get().last_window_closed(&ws);
+            }
+        }
     }
 
     pub fn detach_node(&self, node: &dyn Node) {
@@ -403,6 +536,9 @@ impl ToplevelData {
         for list in self.state.toplevel_lists.lock().values() {
             self.send_once(&toplevel, list, &id, &title, &app_id);
         }
+        for manager in self.state.toplevel_managers.lock().values() {
+            self.send_wlr_once(&toplevel, manager, &title, &app_id);
+        }
     }
 
     pub fn send(&self, toplevel: Rc<dyn ToplevelNode>, list: &ExtForeignToplevelListV1) {
@@ -436,12 +572,69 @@ impl ToplevelData {
             .set((handle.client.id, handle.id), handle.clone());
     }
 
+    pub fn send_wlr(&self, toplevel: Rc<dyn ToplevelNode>, manager: &ZwlrForeignToplevelManagerV1) {
+        let title = self.title.borrow();
+        let app_id = self.app_id.borrow();
+        self.send_wlr_once(&toplevel, manager, &title, &app_id);
+    }
+
+    fn send_wlr_once(
+        &self,
+        toplevel: &Rc<dyn ToplevelNode>,
+        manager: &ZwlrForeignToplevelManagerV1,
+        title: &str,
+        app_id: &str,
+    ) {
+        let opt = ToplevelOpt {
+            toplevel: Rc::downgrade(toplevel),
+            identifier: self.identifier.get(),
+        };
+        let handle = match manager.publish_toplevel(opt) {
+            None => return,
+            Some(handle) => handle,
+        };
+        handle.send_title(title);
+        handle.send_app_id(app_id);
+        handle.set_output(self.wlr_output().as_ref());
+        handle.send_state(self);
+        handle.send_done();
+        self.wlr_handles
+            .set((handle.client.id, handle.id), handle.clone());
+    }
+
+    fn wlr_output(&self) -> Option<Rc<WlOutputGlobal>> {
+        let output = self.workspace.get()?.output.get();
+        if output.is_dummy {
+            return None;
+        }
+        Some(output.global.clone())
+    }
+
+    fn wlr_state_changed(&self) {
+        for handle in self.wlr_handles.lock().values() {
+            handle.send_state(self);
+            handle.send_done();
+        }
+    }
+
+    fn wlr_output_changed(&self) {
+        let output = self.wlr_output();
+        for handle in self.wlr_handles.lock().values() {
+            handle.set_output(output.as_ref());
+            handle.send_done();
+        }
+    }
+
     pub fn set_title(&self, title: &str) {
         *self.title.borrow_mut() = title.to_string();
         for handle in self.handles.lock().values() {
             handle.send_title(title);
             handle.send_done();
         }
+        for handle in self.wlr_handles.lock().values() {
+            handle.send_title(title);
+            handle.send_done();
+        }
     }
 
     pub fn set_app_id(&self, app_id: &str) {
@@ -450,6 +643,30 @@ impl ToplevelData {
             handle.send_app_id(app_id);
             handle.send_done();
         }
+        for handle in self.wlr_handles.lock().values() {
+            handle.send_app_id(app_id);
+            handle.send_done();
+        }
+    }
+
+    pub fn set_icon(&self, icon: Option<Rc<ToplevelIcon>>) {
+        self.icon.set(icon.clone());
+        if let Some(data) = self.fullscrceen_data.borrow().deref() {
+            data.placeholder.tl_data().set_icon(icon);
+        }
+        if let Some(parent) = self.parent.get() {
+            if let Some(slf) = self.slf.upgrade() {
+                parent.cnode_child_icon_changed(slf.tl_as_node());
+            }
+        }
+    }
+
+    /// Returns the width of the area reserved for the icon in front of the title.
+    pub fn title_icon_width(&self, title_height: i32) -> i32 {
+        match self.icon.get() {
+            Some(_) => title_height,
+            None => 0,
+        }
     }
 
     pub fn set_fullscreen(
@@ -509,6 +726,7 @@ impl ToplevelData {
         });
         drop(data);
         self.is_fullscreen.set(true);
+        self.wlr_state_changed();
         node.tl_set_parent(ws.clone());
         ws.set_fullscreen_node(&node);
         node.clone()
@@ -533,6 +751,7 @@ impl ToplevelData {
             }
         };
         self.is_fullscreen.set(false);
+        self.wlr_state_changed();
         match fd.workspace.fullscreen.get() {
             None => {
                 log::error!("Node is supposed to be fullscreened on a workspace but workspace has not fullscreen node.");
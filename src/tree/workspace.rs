@@ -17,12 +17,13 @@ use {
         },
         rect::Rect,
         renderer::Renderer,
+        screenshoter::WorkspaceThumbnail,
         state::State,
         text::TextTexture,
         tree::{
-            container::ContainerNode, walker::NodeVisitor, ContainingNode, Direction,
-            FindTreeResult, FindTreeUsecase, FoundNode, Node, NodeId, NodeVisitorBase, OutputNode,
-            PlaceholderNode, StackedNode, ToplevelNode,
+            container::ContainerNode, float::FloatNode, walker::NodeVisitor, ContainingNode,
+            Direction, FindTreeResult, FindTreeUsecase, FoundNode, Node, NodeId, NodeVisitorBase,
+            OutputNode, PlaceholderNode, StackedNode, ToplevelNode, ToplevelNodeBase,
         },
         utils::{
             clonecell::CloneCell,
@@ -31,12 +32,15 @@ use {
             numcell::NumCell,
             opt::Opt,
             threshold_counter::ThresholdCounter,
+            workspace_identifier::WorkspaceIdentifier,
         },
         wire::JayWorkspaceId,
     },
+    jay_config::EmptyWorkspaceBehavior as ConfigEmptyWorkspaceBehavior,
     std::{
         cell::{Cell, RefCell},
         fmt::Debug,
+        mem,
         ops::Deref,
         rc::Rc,
     },
@@ -46,6 +50,7 @@ tree_id!(WorkspaceNodeId);
 
 pub struct WorkspaceNode {
     pub id: WorkspaceNodeId,
+    pub identifier: WorkspaceIdentifier,
     pub state: Rc<State>,
     pub is_dummy: bool,
     pub output: CloneCell<Rc<OutputNode>>,
@@ -53,7 +58,7 @@ pub struct WorkspaceNode {
     pub container: CloneCell<Option<Rc<ContainerNode>>>,
     pub stacked: LinkedList<Rc<dyn StackedNode>>,
     pub seat_state: NodeSeatState,
-    pub name: String,
+    pub name: RefCell<String>,
     pub output_link: RefCell<Option<LinkedNode<Rc<WorkspaceNode>>>>,
     pub visible: Cell<bool>,
     pub fullscreen: CloneCell<Option<Rc<dyn ToplevelNode>>>,
@@ -67,6 +72,7 @@ pub struct WorkspaceNode {
     pub render_highlight: NumCell<u32>,
     pub ext_workspaces: CopyHashMap<WorkspaceManagerId, Rc<ExtWorkspaceHandleV1>>,
     pub opt: Rc<Opt<WorkspaceNode>>,
+    pub thumbnail: RefCell<Option<WorkspaceThumbnail>>,
 }
 
 impl WorkspaceNode {
@@ -77,6 +83,7 @@ impl WorkspaceNode {
         self.jay_workspaces.clear();
         self.ext_workspaces.clear();
         self.opt.set(None);
+        self.thumbnail.take();
     }
 
     pub fn update_has_captures(&self) {
@@ -92,7 +99,7 @@ impl WorkspaceNode {
                     break 'update;
                 }
             }
-            if output.screencopies.is_not_empty() {
+            if output.screencopies.is_not_empty() || output.screencopy_damage.is_not_empty() {
                 has_capture = true;
             }
         }
@@ -149,8 +156,7 @@ impl WorkspaceNode {
             self.discard_child_properties(&*prev);
         }
         self.pull_child_properties(&**container);
-        let pos = self.position.get();
-        container.clone().tl_change_extents(&pos);
+        container.clone().tl_change_extents(&self.container_rect());
         container.tl_set_parent(self.clone());
         container.tl_set_visible(self.container_visible());
         self.container.set(Some(container.clone()));
@@ -168,7 +174,90 @@ impl WorkspaceNode {
     pub fn change_extents(&self, rect: &Rect) {
         self.position.set(*rect);
         if let Some(c) = self.container.get() {
-            c.tl_change_extents(rect);
+            c.tl_change_extents(&self.container_rect());
+        }
+    }
+
+    /// Returns the gap between tiled windows on this workspace.
+    pub fn inner_gap(&self) -> i32 {
+        if let Some(config) = self.state.config.get() {
+            if let Some(gap) = config.workspace_inner_gap(&self.name.borrow()) {
+                return gap;
+            }
+        }
+        self.state.theme.sizes.inner_gap.get()
+    }
+
+    /// Returns the gap between the tiled windows and the edges of the workspace.
+    pub fn outer_gap(&self) -> i32 {
+        if let Some(config) = self.state.config.get() {
+            if let Some(gap) = config.workspace_outer_gap(&self.name.borrow()) {
+                return gap;
+            }
+        }
+        self.state.theme.sizes.outer_gap.get()
+    }
+
+    /// Returns the area of the workspace that is used by the tiled windows.
+    pub fn container_rect(&self) -> Rect {
+        let pos = self.position.get();
+        let gap = self.outer_gap().min(pos.width() / 2).min(pos.height() / 2);
+        Rect::new_unchecked(
+            pos.x1() + gap,
+            pos.y1() + gap,
+            pos.x2() - gap,
+            pos.y2() - gap,
+        )
+    }
+
+    /// Re-applies the gaps of this workspace after they have changed.
+    pub fn on_gaps_changed(&self) {
+        struct V;
+        impl NodeVisitorBase for V {
+            fn visit_container(&mut self, node: &Rc<ContainerNode>) {
+                node.on_spaces_changed();
+                node.node_visit_children(self);
+            }
+        }
+        self.node_visit_children(&mut V);
+        for stacked in self.stacked.iter() {
+            stacked.deref().clone().node_visit(&mut V);
+        }
+        if let Some(c) = self.container.get() {
+            c.tl_change_extents(&self.container_rect());
+        }
+        if self.visible.get() {
+            self.state.damage(self.position.get());
+        }
+    }
+
+    /// Changes the name of the workspace.
+    ///
+    /// The caller must ensure that no other workspace has this name.
+    pub fn rename(self: &Rc<Self>, name: &str) {
+        let old = mem::replace(&mut *self.name.borrow_mut(), name.to_string());
+        if !self.is_dummy {
+            self.state.workspaces.remove(&old);
+            self.state.workspaces.set(name.to_string(), self.clone());
+        }
+        for jw in self.jay_workspaces.lock().values() {
+            jw.send_name(self);
+            jw.send_done();
+        }
+        for wh in self.ext_workspaces.lock().values() {
+            wh.handle_name_changed(name);
+        }
+        let output = self.output.get();
+        {
+            let mut prev = output.previous_workspace_name.borrow_mut();
+            if prev.as_deref() == Some(old.as_str()) {
+                *prev = Some(name.to_string());
+            }
+        }
+        output.schedule_update_render_data();
+        self.state.tree_changed();
+        if output.node_visible() {
+            self.state.damage(output.global.pos.get());
         }
     }
 
@@ -313,6 +402,13 @@ impl Node for WorkspaceNode {
         usecase: FindTreeUsecase,
     ) -> FindTreeResult {
         if let Some(n) = self.container.get() {
+            let pos = self.position.get();
+            let cpos = n.tl_data().pos.get();
+            let x = x + pos.x1() - cpos.x1();
+            let y = y + pos.y1() - cpos.y1();
+            if x < 0 || y < 0 || x >= cpos.width() || y >= cpos.height() {
+                return FindTreeResult::Other;
+            }
             tree.push(FoundNode {
                 node: n.clone(),
                 x,
@@ -402,6 +498,16 @@ impl ContainingNode for WorkspaceNode {
     fn cnode_workspace(self: Rc<Self>) -> Rc<WorkspaceNode> {
         self
     }
+
+    fn cnode_make_visible(self: Rc<Self>, _child: &dyn Node) {
+        let output = self.output.get();
+        if output.is_dummy || !output.show_workspace(&self) {
+            return;
+        }
+        self.flush_jay_workspaces();
+        output.schedule_update_render_data();
+        self.state.tree_changed();
+    }
 }
 
 pub struct WsMoveConfig {
@@ -418,6 +524,28 @@ pub fn move_ws_to_output(
 ) {
     let source = ws.output.get();
     ws.set_output(&target);
+    if !source.is_dummy && !target.is_dummy {
+        let from = source.workspace_rect.get();
+        let to = target.workspace_rect.get();
+        if from != to {
+            struct Relocate(Rect, Rect);
+            impl NodeVisitorBase for Relocate {
+                fn visit_float(&mut self, node: &Rc<FloatNode>) {
+                    node.relocate(&self.0, &self.1);
+                }
+            }
+            let mut visitor = Relocate(from, to);
+            for stacked in ws.stacked.iter() {
+                stacked.deref().clone().node_visit(&mut visitor);
+            }
+        }
+    }
+    if !target.is_dummy {
+        ws.change_extents(&target.workspace_rect.get());
+        if let Some(fs) = ws.fullscreen.get() {
+            fs.tl_change_extents(&target.global.pos.get());
+        }
+    }
     'link: {
         if let Some(before) = config.before {
             if let Some(link) = &*before.output_link.borrow() {
@@ -463,6 +591,24 @@ pub fn move_ws_to_output(
     }
 }
 
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum EmptyWorkspaceBehavior {
+    #[default]
+    Keep,
+    Destroy,
+    ShowPrevious,
+}
+
+impl EmptyWorkspaceBehavior {
+    pub fn from_config(behavior: ConfigEmptyWorkspaceBehavior) -> Self {
+        match behavior {
+            ConfigEmptyWorkspaceBehavior::Keep => Self::Keep,
+            ConfigEmptyWorkspaceBehavior::Destroy => Self::Destroy,
+            ConfigEmptyWorkspaceBehavior::ShowPrevious => Self::ShowPrevious,
+        }
+    }
+}
+
 pub struct WorkspaceDragDestination {
     pub highlight: Rect,
     pub output: Rc<OutputNode>,
@@ -393,6 +393,10 @@ impl UdevDevice {
     pub fn pci_id(&self) -> Option<&CStr> {
         self.get_property(c"PCI_ID")
     }
+
+    pub fn is_joystick(&self) -> bool {
+        self.get_property(c"ID_INPUT_JOYSTICK") == Some(c"1")
+    }
 }
 
 impl Drop for UdevDevice {
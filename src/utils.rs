@@ -63,4 +63,5 @@ pub mod vecdeque_ext;
 pub mod vecset;
 pub mod vecstorage;
 pub mod windows;
+pub mod workspace_identifier;
 pub mod xrd;
@@ -3,7 +3,7 @@ use {
         linkedlist::NodeRef,
         ptr_ext::{MutPtrExt, PtrExt},
     },
-    jay_config::keyboard::mods::Modifiers,
+    jay_config::{keyboard::mods::Modifiers, layout::Layout},
     std::{
         cell::UnsafeCell,
         fmt::{Debug, Formatter},
@@ -97,3 +97,4 @@ unsafe impl UnsafeCellCloneSafe for usize {}
 unsafe impl<A: UnsafeCellCloneSafe, B: UnsafeCellCloneSafe> UnsafeCellCloneSafe for (A, B) {}
 
 unsafe impl UnsafeCellCloneSafe for Modifiers {}
+unsafe impl UnsafeCellCloneSafe for Layout {}
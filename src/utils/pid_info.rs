@@ -1,6 +1,7 @@
 use {
     crate::utils::{errorfmt::ErrorFmt, oserror::OsError},
     bstr::ByteSlice,
+    std::cell::OnceCell,
     uapi::{c, OwnedFd},
 };
 
@@ -8,6 +9,26 @@ pub struct PidInfo {
     pub uid: c::uid_t,
     pub pid: c::pid_t,
     pub comm: String,
+    exe: OnceCell<Option<String>>,
+}
+
+impl PidInfo {
+    /// Returns the path of the executable of the process.
+    ///
+    /// The path is only read from `/proc` when it is first needed.
+    pub fn exe(&self) -> Option<&str> {
+        self.exe.get_or_init(|| read_exe(self.pid)).as_deref()
+    }
+}
+
+fn read_exe(pid: c::pid_t) -> Option<String> {
+    match std::fs::read_link(format!("/proc/{}/exe", pid)) {
+        Ok(exe) => Some(exe.to_string_lossy().into_owned()),
+        Err(e) => {
+            log::debug!("Could not read `exe` of pid {}: {}", pid, ErrorFmt(e));
+            None
+        }
+    }
 }
 
 pub fn get_pid_info(uid: c::uid_t, pid: c::pid_t) -> PidInfo {
@@ -18,7 +39,12 @@ pub fn get_pid_info(uid: c::uid_t, pid: c::pid_t) -> PidInfo {
             "Unknown".to_string()
         }
     };
-    PidInfo { uid, pid, comm }
+    PidInfo {
+        uid,
+        pid,
+        comm,
+        exe: Default::default(),
+    }
 }
 
 pub fn get_socket_creds(socket: &OwnedFd) -> Option<(c::uid_t, c::pid_t)> {
@@ -12,14 +12,17 @@ use {
             mode_getconnector, mode_getencoder, mode_getplane, mode_getplaneresources,
             mode_getprobblob, mode_getproperty, mode_obj_getproperties, mode_rmfb,
             prime_fd_to_handle, set_client_cap, DRM_DISPLAY_MODE_LEN, DRM_MODE_ATOMIC_TEST_ONLY,
-            DRM_MODE_FB_MODIFIERS, DRM_MODE_OBJECT_BLOB, DRM_MODE_OBJECT_CONNECTOR,
-            DRM_MODE_OBJECT_CRTC, DRM_MODE_OBJECT_ENCODER, DRM_MODE_OBJECT_FB,
-            DRM_MODE_OBJECT_MODE, DRM_MODE_OBJECT_PLANE, DRM_MODE_OBJECT_PROPERTY,
+            DRM_MODE_FB_MODIFIERS, DRM_MODE_FLAG_NHSYNC, DRM_MODE_FLAG_NVSYNC,
+            DRM_MODE_FLAG_PHSYNC, DRM_MODE_FLAG_PVSYNC, DRM_MODE_OBJECT_BLOB,
+            DRM_MODE_OBJECT_CONNECTOR, DRM_MODE_OBJECT_CRTC, DRM_MODE_OBJECT_ENCODER,
+            DRM_MODE_OBJECT_FB, DRM_MODE_OBJECT_MODE, DRM_MODE_OBJECT_PLANE,
+            DRM_MODE_OBJECT_PROPERTY, DRM_MODE_TYPE_USERDEF,
         },
     },
     ahash::AHashMap,
     bstr::{BString, ByteSlice},
     indexmap::IndexSet,
+    jay_config::video::CustomMode,
     std::{
         cell::{Cell, RefCell},
         ffi::CString,
@@ -48,8 +51,10 @@ use crate::{
     },
 };
 pub use sys::{
-    drm_mode_modeinfo, DRM_CLIENT_CAP_ATOMIC, DRM_MODE_ATOMIC_ALLOW_MODESET,
+    drm_color_lut, drm_mode_modeinfo, hdr_metadata_infoframe, hdr_output_metadata, sync_ioc_merge,
+    DRM_CLIENT_CAP_ATOMIC, DRM_CLIENT_CAP_WRITEBACK_CONNECTORS, DRM_MODE_ATOMIC_ALLOW_MODESET,
     DRM_MODE_ATOMIC_NONBLOCK, DRM_MODE_PAGE_FLIP_ASYNC, DRM_MODE_PAGE_FLIP_EVENT,
+    HDMI_EOTF_SMPTE_ST2084, HDMI_STATIC_METADATA_TYPE1,
 };
 
 #[derive(Debug, Error)]
@@ -381,7 +386,7 @@ impl DrmMaster {
         res
     }
 
-    pub fn create_blob<T>(self: &Rc<Self>, t: &T) -> Result<PropBlob, DrmError> {
+    pub fn create_blob<T: ?Sized>(self: &Rc<Self>, t: &T) -> Result<PropBlob, DrmError> {
         match mode_create_blob(self.raw(), t) {
             Ok(b) => Ok(PropBlob {
                 master: self.clone(),
@@ -684,7 +689,7 @@ pub enum DrmPropertyType {
     Blob,
     Enum {
         values: Vec<DrmPropertyEnumValue>,
-        _bitmask: bool,
+        bitmask: bool,
     },
 }
 
@@ -804,6 +809,36 @@ pub struct DrmVersion {
 }
 
 impl DrmModeInfo {
+    pub fn custom(mode: &CustomMode) -> Self {
+        let hsync = match mode.hsync_positive {
+            true => DRM_MODE_FLAG_PHSYNC,
+            false => DRM_MODE_FLAG_NHSYNC,
+        };
+        let vsync = match mode.vsync_positive {
+            true => DRM_MODE_FLAG_PVSYNC,
+            false => DRM_MODE_FLAG_NVSYNC,
+        };
+        let mut res = Self {
+            clock: mode.clock_khz,
+            hdisplay: mode.hdisplay,
+            hsync_start: mode.hsync_start,
+            hsync_end: mode.hsync_end,
+            htotal: mode.htotal,
+            hskew: 0,
+            vdisplay: mode.vdisplay,
+            vsync_start: mode.vsync_start,
+            vsync_end: mode.vsync_end,
+            vtotal: mode.vtotal,
+            vscan: 0,
+            vrefresh: 0,
+            flags: hsync | vsync,
+            ty: DRM_MODE_TYPE_USERDEF,
+            name: format!("{}x{}", mode.hdisplay, mode.vdisplay).into(),
+        };
+        res.vrefresh = (res.refresh_rate_millihz() + 500) / 1000;
+        res
+    }
+
     pub fn create_blob(&self, master: &Rc<DrmMaster>) -> Result<PropBlob, DrmError> {
         let raw = self.to_raw();
         master.create_blob(&raw)
@@ -881,7 +916,6 @@ pub struct ObjectChange<'a> {
 }
 
 impl Change {
-    #[expect(dead_code)]
     pub fn test(&self, flags: u32) -> Result<(), DrmError> {
         mode_atomic(
             self.master.raw(),
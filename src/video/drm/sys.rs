@@ -305,7 +305,7 @@ pub fn mode_getproperty(
             }
             DrmPropertyType::Enum {
                 values,
-                _bitmask: ty == DRM_MODE_PROP_BITMASK,
+                bitmask: ty == DRM_MODE_PROP_BITMASK,
             }
         }
         DRM_MODE_PROP_BLOB => DrmPropertyType::Blob,
@@ -439,6 +439,7 @@ struct drm_set_client_cap {
 const DRM_IOCTL_SET_CLIENT_CAP: u64 = drm_iow::<drm_set_client_cap>(0x0d);
 
 pub const DRM_CLIENT_CAP_ATOMIC: u64 = 3;
+pub const DRM_CLIENT_CAP_WRITEBACK_CONNECTORS: u64 = 5;
 
 pub fn set_client_cap(fd: c::c_int, capability: u64, value: u64) -> Result<(), OsError> {
     let mut cap = drm_set_client_cap { capability, value };
@@ -715,6 +716,45 @@ pub struct drm_mode_modeinfo {
 
 unsafe impl Pod for drm_mode_modeinfo {}
 
+pub const DRM_MODE_FLAG_PHSYNC: u32 = 1 << 0;
+pub const DRM_MODE_FLAG_NHSYNC: u32 = 1 << 1;
+pub const DRM_MODE_FLAG_PVSYNC: u32 = 1 << 2;
+pub const DRM_MODE_FLAG_NVSYNC: u32 = 1 << 3;
+
+pub const DRM_MODE_TYPE_USERDEF: u32 = 1 << 5;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct drm_color_lut {
+    pub red: u16,
+    pub green: u16,
+    pub blue: u16,
+    pub reserved: u16,
+}
+
+pub const HDMI_EOTF_SMPTE_ST2084: u8 = 2;
+pub const HDMI_STATIC_METADATA_TYPE1: u8 = 0;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct hdr_metadata_infoframe {
+    pub eotf: u8,
+    pub metadata_type: u8,
+    pub display_primaries: [[u16; 2]; 3],
+    pub white_point: [u16; 2],
+    pub max_display_mastering_luminance: u16,
+    pub min_display_mastering_luminance: u16,
+    pub max_cll: u16,
+    pub max_fall: u16,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct hdr_output_metadata {
+    pub metadata_type: u32,
+    pub hdmi_metadata_type1: hdr_metadata_infoframe,
+}
+
 impl Into<DrmModeInfo> for drm_mode_modeinfo {
     fn into(self) -> DrmModeInfo {
         DrmModeInfo {
@@ -917,9 +957,9 @@ struct drm_mode_create_blob {
 
 const DRM_IOCTL_MODE_CREATEPROPBLOB: u64 = drm_iowr::<drm_mode_create_blob>(0xbd);
 
-pub fn mode_create_blob<T>(fd: c::c_int, t: &T) -> Result<DrmBlob, OsError> {
+pub fn mode_create_blob<T: ?Sized>(fd: c::c_int, t: &T) -> Result<DrmBlob, OsError> {
     let mut res = drm_mode_create_blob {
-        data: t as *const T as _,
+        data: t as *const T as *const u8 as _,
         length: size_of_val(t) as _,
         blob_id: 0,
     };
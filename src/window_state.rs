@@ -0,0 +1,333 @@
+use {
+    crate::{
+        cpu_worker::{AsyncCpuWork, CpuJob, CpuWork, PendingJob},
+        state::State,
+        tree::{ToplevelData, ToplevelNode, WorkspaceNode},
+        utils::{asyncevent::AsyncEvent, errorfmt::ErrorFmt},
+    },
+    ahash::AHashSet,
+    serde::{Deserialize, Serialize},
+    std::{
+        cell::{Cell, RefCell},
+        collections::BTreeMap,
+        fs,
+        path::{Path, PathBuf},
+        rc::{Rc, Weak},
+    },
+    thiserror::Error,
+};
+
+/// The state of a window that is restored when an application with the same app-id
+/// maps its next window.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct WindowState {
+    pub floating: bool,
+    pub fullscreen: bool,
+    #[serde(default)]
+    pub float_width: i32,
+    #[serde(default)]
+    pub float_height: i32,
+    #[serde(default)]
+    pub workspace: Option<String>,
+}
+
+#[derive(Default)]
+pub struct WindowStates {
+    enabled: Cell<bool>,
+    exclusions: RefCell<AHashSet<String>>,
+    entries: RefCell<BTreeMap<String, WindowState>>,
+    save_scheduled: Cell<bool>,
+    changed: AsyncEvent,
+    pending: Cell<Option<PendingJob>>,
+    job_completed: AsyncEvent,
+}
+
+#[derive(Debug, Error)]
+enum WindowStateError {
+    #[error("Could not determine the state directory")]
+    NoStateDir,
+    #[error("Could not read {0}")]
+    Read(String, #[source] std::io::Error),
+    #[error("Could not parse {0}")]
+    Parse(String, #[source] serde_json::Error),
+    #[error("Could not serialize the window states")]
+    Serialize(#[source] serde_json::Error),
+    #[error("Could not create {0}")]
+    CreateDir(String, #[source] std::io::Error),
+    #[error("Could not write {0}")]
+    Write(String, #[source] std::io::Error),
+}
+
+fn state_file() -> Result<PathBuf, WindowStateError> {
+    let mut path = dirs::state_dir().ok_or(WindowStateError::NoStateDir)?;
+    path.push("jay");
+    path.push("window-state.json");
+    Ok(path)
+}
+
+impl WindowStates {
+    pub fn clear(&self) {
+        self.changed.clear();
+        self.job_completed.clear();
+        if let Some(pending) = self.pending.take() {
+            pending.detach();
+        }
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+    }
+
+    pub fn set_exclusions(&self, app_ids: Vec<String>) {
+        *self.exclusions.borrow_mut() = app_ids.into_iter().collect();
+    }
+
+    fn applies_to(&self, app_id: &str) -> bool {
+        self.enabled.get() && !app_id.is_empty() && !self.exclusions.borrow().contains(app_id)
+    }
+
+    pub fn get(&self, app_id: &str) -> Option<WindowState> {
+        if !self.applies_to(app_id) {
+            return None;
+        }
+        self.entries.borrow().get(app_id).cloned()
+    }
+
+    /// Merges the states loaded from disk into the states recorded since startup.
+    fn merge_loaded(&self, entries: BTreeMap<String, WindowState>) {
+        let mut current = self.entries.borrow_mut();
+        for (app_id, state) in entries {
+            current.entry(app_id).or_insert(state);
+        }
+    }
+
+    /// Records the state of a toplevel that is about to be unmapped.
+    pub fn record(&self, data: &ToplevelData) {
+        if data.parent.is_none() {
+            return;
+        }
+        let app_id = data.app_id.borrow();
+        if !self.applies_to(&app_id) {
+            return;
+        }
+        let state = WindowState {
+            floating: data.is_floating.get(),
+            fullscreen: data.is_fullscreen.get(),
+            float_width: data.float_width.get(),
+            float_height: data.float_height.get(),
+            workspace: data.workspace.get().map(|ws| ws.name.borrow().clone()),
+        };
+        let changed = {
+            let entries = &mut *self.entries.borrow_mut();
+            match entries.get(&*app_id) {
+                Some(old) if *old == state => false,
+                _ => {
+                    entries.insert(app_id.clone(), state);
+                    true
+                }
+            }
+        };
+        if changed && !self.save_scheduled.replace(true) {
+            self.changed.trigger();
+        }
+    }
+}
+
+fn load(path: &Path) -> Result<BTreeMap<String, WindowState>, WindowStateError> {
+    let contents = match fs::read(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Default::default()),
+        Err(e) => return Err(WindowStateError::Read(path.display().to_string(), e)),
+    };
+    serde_json::from_slice(&contents)
+        .map_err(|e| WindowStateError::Parse(path.display().to_string(), e))
+}
+
+fn save(path: &Path, contents: &[u8]) -> Result<(), WindowStateError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| WindowStateError::CreateDir(dir.display().to_string(), e))?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, contents)
+        .and_then(|_| fs::rename(&tmp, path))
+        .map_err(|e| WindowStateError::Write(path.display().to_string(), e))
+}
+
+fn create_save_job(state: &Rc<State>) -> Result<Box<SaveJob>, WindowStateError> {
+    let entries = state.window_states.entries.borrow();
+    let contents = serde_json::to_vec(&*entries).map_err(WindowStateError::Serialize)?;
+    Ok(Box::new(SaveJob {
+        state: Rc::downgrade(state),
+        work: SaveWork {
+            path: state_file()?,
+            contents,
+            result: None,
+        },
+    }))
+}
+
+pub async fn persist_window_states(state: Rc<State>) {
+    let ws = &state.window_states;
+    match state_file() {
+        Ok(path) => {
+            let job = Box::new(LoadJob {
+                state: Rc::downgrade(&state),
+                work: LoadWork { path, result: None },
+            });
+            ws.pending.set(Some(state.cpu_worker.submit(job)));
+            ws.job_completed.triggered().await;
+        }
+        Err(e) => {
+            log::warn!("Could not load the window states: {}", ErrorFmt(e));
+            ws.merge_loaded(Default::default());
+        }
+    }
+    loop {
+        ws.changed.triggered().await;
+        ws.save_scheduled.set(false);
+        let job = match create_save_job(&state) {
+            Ok(job) => job,
+            Err(e) => {
+                log::warn!("Could not save the window states: {}", ErrorFmt(e));
+                continue;
+            }
+        };
+        ws.pending.set(Some(state.cpu_worker.submit(job)));
+        ws.job_completed.triggered().await;
+    }
+}
+
+struct LoadWork {
+    path: PathBuf,
+    result: Option<Result<BTreeMap<String, WindowState>, WindowStateError>>,
+}
+
+struct LoadJob {
+    state: Weak<State>,
+    work: LoadWork,
+}
+
+impl CpuWork for LoadWork {
+    fn run(&mut self) -> Option<Box<dyn AsyncCpuWork>> {
+        zone!("LoadWindowStates");
+        self.result = Some(load(&self.path));
+        None
+    }
+}
+
+impl CpuJob for LoadJob {
+    fn work(&mut self) -> &mut dyn CpuWork {
+        &mut self.work
+    }
+
+    fn completed(self: Box<Self>) {
+        let Some(state) = self.state.upgrade() else {
+            return;
+        };
+        let ws = &state.window_states;
+        ws.pending.take();
+        let entries = match self.work.result {
+            Some(Ok(entries)) => entries,
+            Some(Err(e)) => {
+                log::warn!("Could not load the window states: {}", ErrorFmt(e));
+                Default::default()
+            }
+            None => Default::default(),
+        };
+        ws.merge_loaded(entries);
+        ws.job_completed.trigger();
+    }
+}
+
+struct SaveWork {
+    path: PathBuf,
+    contents: Vec<u8>,
+    result: Option<Result<(), WindowStateError>>,
+}
+
+struct SaveJob {
+    state: Weak<State>,
+    work: SaveWork,
+}
+
+impl CpuWork for SaveWork {
+    fn run(&mut self) -> Option<Box<dyn AsyncCpuWork>> {
+        zone!("SaveWindowStates");
+        self.result = Some(save(&self.path, &self.contents));
+        None
+    }
+}
+
+impl CpuJob for SaveJob {
+    fn work(&mut self) -> &mut dyn CpuWork {
+        &mut self.work
+    }
+
+    fn completed(self: Box<Self>) {
+        let Some(state) = self.state.upgrade() else {
+            return;
+        };
+        let ws = &state.window_states;
+        ws.pending.take();
+        if let Some(Err(e)) = self.work.result {
+            log::warn!("Could not save the window states: {}", ErrorFmt(e));
+        }
+        ws.job_completed.trigger();
+    }
+}
+
+impl State {
+    /// Maps a new toplevel without a parent, restoring the state that was last
+    /// recorded for its app-id.
+    pub fn map_toplevel(self: &Rc<Self>, node: Rc<dyn ToplevelNode>) {
+        let app_id = node.tl_data().app_id.borrow().clone();
+        let assigned_ws = self.assigned_workspace(&app_id);
+        let Some(ws_state) = self.window_states.get(&app_id) else {
+            match assigned_ws {
+                Some(ws) => self.map_tiled_on(node, &ws),
+                None => self.map_tiled(node),
+            }
+            return;
+        };
+        let ws = assigned_ws.or_else(|| {
+            ws_state
+                .workspace
+                .as_ref()
+                .and_then(|name| self.workspaces.get(name))
+        });
+        if ws_state.floating {
+            let data = node.tl_data();
+            if ws_state.float_width > 0 && ws_state.float_height > 0 {
+                data.float_width.set(ws_state.float_width);
+                data.float_height.set(ws_state.float_height);
+            }
+            let ws = ws.unwrap_or_else(|| self.float_map_ws());
+            let (width, height) = data.float_size(&ws);
+            self.map_floating(node.clone(), width, height, &ws, None);
+        } else if let Some(ws) = ws {
+            self.map_tiled_on(node.clone(), &ws);
+        } else {
+            self.map_tiled(node.clone());
+        }
+        if ws_state.fullscreen {
+            node.tl_set_fullscreen(true);
+        }
+    }
+
+    /// Returns the workspace that the config assigned the app-id to, creating it if
+    /// necessary.
+    fn assigned_workspace(self: &Rc<Self>, app_id: &str) -> Option<Rc<WorkspaceNode>> {
+        let name = self.config.get()?.app_workspace(app_id)?;
+        if let Some(ws) = self.workspaces.get(name.as_str()) {
+            return Some(ws);
+        }
+        let output = self
+            .seat_queue
+            .last()
+            .map(|s| s.get_output())
+            .filter(|o| !o.is_dummy)
+            .or_else(|| self.root.outputs.lock().values().next().cloned())?;
+        Some(output.create_workspace(&name))
+    }
+}
@@ -71,6 +71,8 @@ impl UsrJayCompositor {
             pending_buffers: Default::default(),
             pending_planes: Default::default(),
             pending_config: Default::default(),
+            clock_id: Default::default(),
+            pending_presentation_time: Default::default(),
         });
         self.con.request(CreateScreencast {
             self_id: self.id,
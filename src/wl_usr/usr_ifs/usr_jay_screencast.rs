@@ -14,8 +14,14 @@ use {
             UsrCon,
         },
     },
-    std::{cell::RefCell, mem, ops::DerefMut, rc::Rc},
+    std::{
+        cell::{Cell, RefCell},
+        mem,
+        ops::DerefMut,
+        rc::Rc,
+    },
     thiserror::Error,
+    uapi::c,
 };
 
 pub struct UsrJayScreencast {
@@ -28,6 +34,9 @@ pub struct UsrJayScreencast {
     pub pending_planes: RefCell<PlaneVec<DmaBufPlane>>,
 
     pub pending_config: RefCell<UsrJayScreencastServerConfig>,
+
+    pub clock_id: Cell<Option<u32>>,
+    pub pending_presentation_time: Cell<Option<u64>>,
 }
 
 #[derive(Default)]
@@ -46,8 +55,10 @@ pub trait UsrJayScreencastOwner {
         let _ = buffers;
     }
 
-    fn ready(&self, ev: &Ready) {
+    /// `present_nsec` is the CLOCK_MONOTONIC time at which the frame was presented.
+    fn ready(&self, ev: &Ready, present_nsec: Option<u64>) {
         let _ = ev;
+        let _ = present_nsec;
     }
 
     fn destroyed(&self) {}
@@ -171,8 +182,12 @@ impl JayScreencastEventHandler for UsrJayScreencast {
     }
 
     fn ready(&self, ev: Ready, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let present_nsec = self
+            .pending_presentation_time
+            .take()
+            .filter(|_| self.clock_id.get() == Some(c::CLOCK_MONOTONIC as u32));
         if let Some(owner) = self.owner.get() {
-            owner.ready(&ev);
+            owner.ready(&ev, present_nsec);
         }
         Ok(())
     }
@@ -247,6 +262,18 @@ impl JayScreencastEventHandler for UsrJayScreencast {
         self.pending_config.borrow_mut().height = ev.height;
         Ok(())
     }
+
+    fn clock_id(&self, ev: ClockId, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.clock_id.set(Some(ev.clock_id));
+        Ok(())
+    }
+
+    fn presentation_time(&self, ev: PresentationTime, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let tv_sec = ((ev.tv_sec_hi as u64) << 32) | ev.tv_sec_lo as u64;
+        self.pending_presentation_time
+            .set(Some(tv_sec * 1_000_000_000 + ev.tv_nsec as u64));
+        Ok(())
+    }
 }
 
 usr_object_base! {
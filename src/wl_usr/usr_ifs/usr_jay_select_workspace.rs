@@ -43,6 +43,7 @@ impl JaySelectWorkspaceEventHandler for UsrJaySelectWorkspace {
             linear_id: Default::default(),
             output: Default::default(),
             name: Default::default(),
+            identifier: Default::default(),
         });
         self.con.add_object(tl.clone());
         tl.owner.set(Some(slf.clone()));
@@ -20,6 +20,7 @@ pub struct UsrJayWorkspace {
     pub linear_id: Cell<u32>,
     pub output: Cell<u32>,
     pub name: RefCell<Option<String>>,
+    pub identifier: RefCell<Option<String>>,
 }
 
 pub trait UsrJayWorkspaceOwner {
@@ -81,6 +82,11 @@ impl JayWorkspaceEventHandler for UsrJayWorkspace {
         }
         Ok(())
     }
+
+    fn id_(&self, ev: Id<'_>, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        *self.identifier.borrow_mut() = Some(ev.id.to_string());
+        Ok(())
+    }
 }
 
 usr_object_base! {
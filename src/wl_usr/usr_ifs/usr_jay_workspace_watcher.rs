@@ -34,6 +34,7 @@ impl JayWorkspaceWatcherEventHandler for UsrJayWorkspaceWatcher {
             linear_id: Default::default(),
             output: Default::default(),
             name: Default::default(),
+            identifier: Default::default(),
         });
         self.con.add_object(jw.clone());
         if let Some(owner) = self.owner.get() {
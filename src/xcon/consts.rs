@@ -138,6 +138,14 @@ pub const PRESENT_EVENT_MASK_COMPLETE_NOTIFY: u32 = 2;
 pub const PRESENT_EVENT_MASK_IDLE_NOTIFY: u32 = 4;
 pub const PRESENT_EVENT_MASK_REDIRECT_NOTIFY: u32 = 8;
 
+pub const PRESENT_COMPLETE_KIND_PIXMAP: u8 = 0;
+pub const PRESENT_COMPLETE_KIND_NOTIFY_MSC: u8 = 1;
+
+pub const PRESENT_COMPLETE_MODE_COPY: u8 = 0;
+pub const PRESENT_COMPLETE_MODE_FLIP: u8 = 1;
+pub const PRESENT_COMPLETE_MODE_SKIP: u8 = 2;
+pub const PRESENT_COMPLETE_MODE_SUBOPTIMAL_COPY: u8 = 3;
+
 pub const INPUT_DEVICE_TYPE_MASTER_POINTER: u16 = 1;
 pub const INPUT_DEVICE_TYPE_MASTER_KEYBOARD: u16 = 2;
 pub const INPUT_DEVICE_TYPE_SLAVE_POINTER: u16 = 3;
@@ -150,6 +158,13 @@ pub const XKB_PER_CLIENT_FLAG_AUTO_RESET_CONTROLS: u32 = 4;
 pub const XKB_PER_CLIENT_FLAG_LOOKUP_STATE_WHEN_GRABBED: u32 = 8;
 pub const XKB_PER_CLIENT_FLAG_SEND_EVENT_USES_XKB_STATE: u32 = 16;
 
+pub const XKB_NAME_DETAIL_KEYCODES: u32 = 1;
+pub const XKB_NAME_DETAIL_GEOMETRY: u32 = 2;
+pub const XKB_NAME_DETAIL_SYMBOLS: u32 = 4;
+pub const XKB_NAME_DETAIL_PHYS_SYMBOLS: u32 = 8;
+pub const XKB_NAME_DETAIL_TYPES: u32 = 16;
+pub const XKB_NAME_DETAIL_COMPAT: u32 = 32;
+
 pub const INPUT_HIERARCHY_MASK_MASTER_ADDED: u32 = 1;
 pub const INPUT_HIERARCHY_MASK_MASTER_REMOVED: u32 = 2;
 pub const INPUT_HIERARCHY_MASK_SLAVE_ADDED: u32 = 4;
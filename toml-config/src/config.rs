@@ -17,14 +17,18 @@ use {
     },
     ahash::AHashMap,
     jay_config::{
-        input::{acceleration::AccelProfile, SwitchEvent},
+        input::{
+            acceleration::AccelProfile, PointerGesture, PointerInput, PointerTarget, SwitchEvent,
+            TouchGesture,
+        },
         keyboard::{mods::Modifiers, Keymap, ModifiedKeySym},
         logging::LogLevel,
+        notifications::NotificationPosition,
         status::MessageFormat,
         theme::Color,
-        video::{Format, GfxApi, TearingMode, Transform, VrrMode},
+        video::{CustomMode, Format, GfxApi, NightLightSchedule, TearingMode, Transform, VrrMode},
         xwayland::XScalingMode,
-        Axis, Direction, Workspace,
+        Axis, Direction, EmptyWorkspaceBehavior, Workspace,
     },
     std::{
         error::Error,
@@ -53,6 +57,20 @@ pub enum SimpleCommand {
     ToggleSplit,
     Forward(bool),
     EnableWindowManagement(bool),
+    DismissNotification(bool),
+    InvokeNotificationAction,
+    ToggleDoNotDisturb,
+    SetOutputsPowered(bool),
+    ToggleOutputsPowered,
+    SetShowSurfaceRegions(bool),
+    ToggleShowSurfaceRegions,
+    ToggleScrollLockLed,
+    ScreenshotWindow,
+    BrightnessUp,
+    BrightnessDown,
+    ForceQuit,
+    ShowPreviousWorkspace,
+    Unmark,
 }
 
 #[derive(Debug, Clone)]
@@ -69,6 +87,7 @@ pub enum Action {
     ConfigureIdle {
         idle: Option<Duration>,
         grace_period: Option<Duration>,
+        audio_inhibit: Option<AudioIdleInhibit>,
     },
     ConfigureInput {
         input: Box<Input>,
@@ -125,6 +144,40 @@ pub enum Action {
     SetRepeatRate {
         rate: RepeatRate,
     },
+    PresentOutput {
+        output: OutputMatch,
+    },
+    SetInputDeviceEnabled {
+        input: Box<InputMatch>,
+        enabled: Option<bool>,
+    },
+    SetBindingMode {
+        mode: String,
+    },
+    ForceImplicitSync {
+        enabled: bool,
+    },
+    Mark {
+        mark: String,
+    },
+    FocusMark {
+        mark: String,
+    },
+    SwapWithMark {
+        mark: String,
+    },
+    MoveToMark {
+        mark: String,
+    },
+    SetGaps {
+        workspace: Option<Workspace>,
+        inner: Option<i32>,
+        outer: Option<i32>,
+    },
+    AdjustGaps {
+        inner: Option<i32>,
+        outer: Option<i32>,
+    },
 }
 
 #[derive(Debug, Clone, Default)]
@@ -144,8 +197,13 @@ pub struct Theme {
     pub unfocused_title_bg_color: Option<Color>,
     pub unfocused_title_text_color: Option<Color>,
     pub highlight_color: Option<Color>,
+    pub notification_bg_color: Option<Color>,
+    pub notification_border_color: Option<Color>,
+    pub notification_text_color: Option<Color>,
     pub border_width: Option<i32>,
     pub title_height: Option<i32>,
+    pub inner_gap: Option<i32>,
+    pub outer_gap: Option<i32>,
     pub font: Option<String>,
 }
 
@@ -156,6 +214,77 @@ pub struct Status {
     pub separator: Option<String>,
 }
 
+#[derive(Debug, Clone)]
+pub struct ConfigGesture {
+    pub gesture: PointerGesture,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigPointerBinding {
+    pub target: PointerTarget,
+    pub input: PointerInput,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigTouchGesture {
+    pub gesture: TouchGesture,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Bar {
+    pub clock_format: Option<String>,
+    pub show_focused_title: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WindowScreenshots {
+    pub directory: Option<String>,
+    pub filename: Option<String>,
+    pub on_saved: Option<Action>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ClientLimits {
+    pub max_fds: Option<u64>,
+    pub max_memory_mb: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Notifications {
+    pub enabled: Option<bool>,
+    pub do_not_disturb: Option<bool>,
+    pub position: Option<NotificationPosition>,
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AudioIdleInhibit {
+    pub enabled: bool,
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CaptureOverlay {
+    pub text: Option<String>,
+    pub image: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NightLight {
+    pub schedule: NightLightSchedule,
+    pub temperature: Option<u32>,
+    pub transition: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WindowState {
+    pub remember: Option<bool>,
+    pub exclude: Option<Vec<String>>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct UiDrag {
     pub enabled: Option<bool>,
@@ -214,9 +343,14 @@ pub struct Output {
     pub scale: Option<f64>,
     pub transform: Option<Transform>,
     pub mode: Option<Mode>,
+    pub custom_mode: Option<CustomMode>,
     pub vrr: Option<Vrr>,
     pub tearing: Option<Tearing>,
     pub format: Option<Format>,
+    pub hdr: Option<bool>,
+    pub render_margin_ms: Option<f64>,
+    pub mirror: Option<String>,
+    pub bar: Option<Bar>,
 }
 
 #[derive(Debug, Clone)]
@@ -240,6 +374,8 @@ pub enum InputMatch {
         is_tablet_pad: Option<bool>,
         is_gesture: Option<bool>,
         is_switch: Option<bool>,
+        vendor_id: Option<u32>,
+        product_id: Option<u32>,
     },
 }
 
@@ -260,6 +396,16 @@ pub struct Input {
     pub switch_actions: AHashMap<SwitchEvent, Action>,
     pub output: Option<Option<OutputMatch>>,
     pub calibration_matrix: Option<[[f32; 3]; 2]>,
+    pub button_debounce: Option<Duration>,
+    pub key_remap: Option<Vec<(u32, u32)>>,
+    pub gamepad_resets_idle: Option<bool>,
+    pub disable_while_typing: Option<bool>,
+    pub disable_while_trackpointing: Option<bool>,
+    pub disable_while_typing_window: Option<Duration>,
+    pub middle_button_emulation: Option<bool>,
+    pub seat: Option<String>,
+    pub enabled: Option<bool>,
+    pub on_attach: Option<Action>,
 }
 
 #[derive(Debug, Clone)]
@@ -317,6 +463,7 @@ pub struct Tearing {
 #[derive(Debug, Clone, Default)]
 pub struct Libei {
     pub enable_socket: Option<bool>,
+    pub prompt: Option<bool>,
 }
 
 #[derive(Debug, Clone)]
@@ -327,13 +474,34 @@ pub struct Shortcut {
     pub latch: Option<Action>,
 }
 
+#[derive(Debug, Clone)]
+pub struct ShortcutSequence {
+    pub keys: Vec<ModifiedKeySym>,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone)]
+pub struct BindingMode {
+    pub name: String,
+    pub shortcuts: Vec<Shortcut>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub keymap: Option<ConfigKeymap>,
     pub repeat_rate: Option<RepeatRate>,
     pub shortcuts: Vec<Shortcut>,
+    pub shortcut_sequences: Vec<ShortcutSequence>,
+    pub shortcut_sequence_timeout: Option<Duration>,
+    pub binding_modes: Vec<BindingMode>,
+    pub activation_token_lifetime: Option<Duration>,
     pub on_graphics_initialized: Option<Action>,
     pub on_idle: Option<Action>,
+    pub on_lock: Option<Action>,
+    pub on_unlock: Option<Action>,
+    pub lock_grace_period: Option<Duration>,
+    pub on_locker_crash: Option<Action>,
+    pub fallback_lock_screen_prompt: Option<bool>,
     pub status: Option<Status>,
     pub connectors: Vec<ConfigConnector>,
     pub outputs: Vec<Output>,
@@ -350,14 +518,43 @@ pub struct Config {
     pub inputs: Vec<Input>,
     pub idle: Option<Duration>,
     pub grace_period: Option<Duration>,
+    pub audio_idle_inhibit: Option<AudioIdleInhibit>,
     pub explicit_sync_enabled: Option<bool>,
+    pub force_implicit_sync: bool,
+    pub key_remap: Vec<(u32, u32)>,
     pub focus_follows_mouse: bool,
+    pub pointer_motion_coalescing: Option<bool>,
     pub window_management_key: Option<ModifiedKeySym>,
+    pub break_out_key: Option<ModifiedKeySym>,
+    pub throttle_interactive_resize: Option<bool>,
     pub vrr: Option<Vrr>,
     pub tearing: Option<Tearing>,
     pub libei: Libei,
     pub ui_drag: UiDrag,
     pub xwayland: Option<Xwayland>,
+    pub touch_gestures: Vec<ConfigTouchGesture>,
+    pub gestures: Vec<ConfigGesture>,
+    pub gesture_passthrough: Vec<String>,
+    pub pointer_bindings: Vec<ConfigPointerBinding>,
+    pub bar: Bar,
+    pub notifications: Notifications,
+    pub jay_protocol_allowlist: Option<Vec<String>>,
+    pub window_state: WindowState,
+    pub capture_redactions: Option<Vec<String>>,
+    pub capture_overlay: CaptureOverlay,
+    pub on_connector_connected: Option<Action>,
+    pub on_connector_disconnected: Option<Action>,
+    pub on_connector_mode_changed: Option<Action>,
+    pub on_drm_device_added: Option<Action>,
+    pub on_drm_device_removed: Option<Action>,
+    pub empty_workspace_behavior: Option<EmptyWorkspaceBehavior>,
+    pub autotiling: Option<bool>,
+    pub workspace_back_and_forth: Option<bool>,
+    pub show_marks: Option<bool>,
+    pub night_light: Option<NightLight>,
+    pub workspace_swipe_fingers: Option<u32>,
+    pub window_screenshots: WindowScreenshots,
+    pub client_limits: ClientLimits,
 }
 
 #[derive(Debug, Error)]
@@ -7,33 +7,47 @@ use {
 };
 
 pub mod action;
+mod bar;
+mod binding_mode;
+mod capture_overlay;
+mod client_limits;
 mod color;
 pub mod config;
 mod connector;
 mod connector_match;
+mod custom_mode;
 mod drm_device;
 mod drm_device_match;
+mod empty_workspace_behavior;
 mod env;
 pub mod exec;
 mod format;
+mod gesture;
 mod gfx_api;
 mod idle;
 mod input;
 mod input_match;
+mod key_remap;
 pub mod keymap;
 mod libei;
 mod log_level;
 mod mode;
 pub mod modified_keysym;
+mod night_light;
+mod notifications;
 mod output;
 mod output_match;
+mod pointer_binding;
 mod repeat_rate;
 pub mod shortcuts;
 mod status;
 mod tearing;
 mod theme;
+mod touch_gesture;
 mod ui_drag;
 mod vrr;
+mod window_screenshots;
+mod window_state;
 mod xwayland;
 
 #[derive(Debug, Error)]
@@ -2,7 +2,7 @@ use {
     crate::{
         config::{
             context::Context,
-            extractor::{arr, bol, n32, opt, str, val, Extractor, ExtractorError},
+            extractor::{arr, bol, n32, opt, recover, s32, str, val, Extractor, ExtractorError},
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
             parsers::{
                 connector::{ConnectorParser, ConnectorParserError},
@@ -13,6 +13,7 @@ use {
                 gfx_api::{GfxApiParser, GfxApiParserError},
                 idle::{IdleParser, IdleParserError},
                 input::{InputParser, InputParserError},
+                input_match::{InputMatchParser, InputMatchParserError},
                 keymap::{KeymapParser, KeymapParserError},
                 log_level::{LogLevelParser, LogLevelParserError},
                 output::{OutputParser, OutputParserError},
@@ -80,6 +81,12 @@ pub enum ActionParserError {
     MoveToOutput(#[source] OutputMatchParserError),
     #[error("Could not parse a set-repeat-rate action")]
     RepeatRate(#[source] RepeatRateParserError),
+    #[error("Could not parse a present-output action")]
+    PresentOutput(#[source] OutputMatchParserError),
+    #[error("Could not parse a toggle-input-device action")]
+    ToggleInputDevice(#[source] InputMatchParserError),
+    #[error("Could not parse a set-input-device-enabled action")]
+    SetInputDeviceEnabled(#[source] InputMatchParserError),
 }
 
 pub struct ActionParser<'a>(pub &'a Context<'a>);
@@ -113,6 +120,23 @@ impl ActionParser<'_> {
             "consume" => Forward(false),
             "enable-window-management" => EnableWindowManagement(true),
             "disable-window-management" => EnableWindowManagement(false),
+            "dismiss-notification" => DismissNotification(false),
+            "dismiss-all-notifications" => DismissNotification(true),
+            "invoke-notification-action" => InvokeNotificationAction,
+            "toggle-do-not-disturb" => ToggleDoNotDisturb,
+            "power-on-outputs" => SetOutputsPowered(true),
+            "power-off-outputs" => SetOutputsPowered(false),
+            "toggle-outputs-powered" => ToggleOutputsPowered,
+            "show-surface-regions" => SetShowSurfaceRegions(true),
+            "hide-surface-regions" => SetShowSurfaceRegions(false),
+            "toggle-surface-regions" => ToggleShowSurfaceRegions,
+            "toggle-scroll-lock-led" => ToggleScrollLockLed,
+            "screenshot-window" => ScreenshotWindow,
+            "brightness-up" => BrightnessUp,
+            "brightness-down" => BrightnessDown,
+            "force-quit" => ForceQuit,
+            "show-previous-workspace" => ShowPreviousWorkspace,
+            "unmark" => Unmark,
             _ => {
                 return Err(ActionParserError::UnknownSimpleAction(string.to_string()).spanned(span))
             }
@@ -180,6 +204,7 @@ impl ActionParser<'_> {
         Ok(Action::ConfigureIdle {
             idle: idle.timeout,
             grace_period: idle.grace_period,
+            audio_inhibit: idle.audio_inhibit,
         })
     }
 
@@ -308,6 +333,14 @@ impl ActionParser<'_> {
         })
     }
 
+    fn parse_present_output(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let output = ext
+            .extract(val("output"))?
+            .parse_map(&mut OutputMatchParser(self.0))
+            .map_spanned_err(ActionParserError::PresentOutput)?;
+        Ok(Action::PresentOutput { output })
+    }
+
     fn parse_set_repeat_rate(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
         let rate = ext
             .extract(val("rate"))?
@@ -315,6 +348,80 @@ impl ActionParser<'_> {
             .map_spanned_err(ActionParserError::RepeatRate)?;
         Ok(Action::SetRepeatRate { rate })
     }
+
+    fn parse_toggle_input_device(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let input = ext
+            .extract(val("input"))?
+            .parse_map(&mut InputMatchParser(self.0))
+            .map_spanned_err(ActionParserError::ToggleInputDevice)?;
+        Ok(Action::SetInputDeviceEnabled {
+            input: Box::new(input),
+            enabled: None,
+        })
+    }
+
+    fn parse_set_input_device_enabled(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let (input, enabled) = ext.extract((val("input"), bol("enabled")))?;
+        let input = input
+            .parse_map(&mut InputMatchParser(self.0))
+            .map_spanned_err(ActionParserError::SetInputDeviceEnabled)?;
+        Ok(Action::SetInputDeviceEnabled {
+            input: Box::new(input),
+            enabled: Some(enabled.value),
+        })
+    }
+
+    fn parse_set_binding_mode(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let mode = ext.extract(str("mode"))?.value.to_string();
+        Ok(Action::SetBindingMode { mode })
+    }
+
+    fn parse_force_implicit_sync(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let enabled = ext.extract(bol("enabled"))?.value;
+        Ok(Action::ForceImplicitSync { enabled })
+    }
+
+    fn parse_mark(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let mark = ext.extract(str("mark"))?.value.to_string();
+        Ok(Action::Mark { mark })
+    }
+
+    fn parse_focus_mark(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let mark = ext.extract(str("mark"))?.value.to_string();
+        Ok(Action::FocusMark { mark })
+    }
+
+    fn parse_swap_with_mark(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let mark = ext.extract(str("mark"))?.value.to_string();
+        Ok(Action::SwapWithMark { mark })
+    }
+
+    fn parse_move_to_mark(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let mark = ext.extract(str("mark"))?.value.to_string();
+        Ok(Action::MoveToMark { mark })
+    }
+
+    fn parse_set_gaps(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let (workspace, inner, outer) = ext.extract((
+            opt(str("workspace")),
+            recover(opt(s32("inner"))),
+            recover(opt(s32("outer"))),
+        ))?;
+        Ok(Action::SetGaps {
+            workspace: workspace.despan().map(get_workspace),
+            inner: inner.despan(),
+            outer: outer.despan(),
+        })
+    }
+
+    fn parse_adjust_gaps(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let (inner, outer) =
+            ext.extract((recover(opt(s32("inner"))), recover(opt(s32("outer")))))?;
+        Ok(Action::AdjustGaps {
+            inner: inner.despan(),
+            outer: outer.despan(),
+        })
+    }
 }
 
 impl Parser for ActionParser<'_> {
@@ -366,6 +473,17 @@ impl Parser for ActionParser<'_> {
             "configure-idle" => self.parse_configure_idle(&mut ext),
             "move-to-output" => self.parse_move_to_output(&mut ext),
             "set-repeat-rate" => self.parse_set_repeat_rate(&mut ext),
+            "present-output" => self.parse_present_output(&mut ext),
+            "toggle-input-device" => self.parse_toggle_input_device(&mut ext),
+            "set-input-device-enabled" => self.parse_set_input_device_enabled(&mut ext),
+            "set-binding-mode" => self.parse_set_binding_mode(&mut ext),
+            "force-implicit-sync" => self.parse_force_implicit_sync(&mut ext),
+            "mark" => self.parse_mark(&mut ext),
+            "focus-mark" => self.parse_focus_mark(&mut ext),
+            "swap-with-mark" => self.parse_swap_with_mark(&mut ext),
+            "move-to-mark" => self.parse_move_to_mark(&mut ext),
+            "set-gaps" => self.parse_set_gaps(&mut ext),
+            "adjust-gaps" => self.parse_adjust_gaps(&mut ext),
             v => {
                 ext.ignore_unused();
                 return Err(ActionParserError::UnknownType(v.to_string()).spanned(ty.span));
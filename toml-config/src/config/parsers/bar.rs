@@ -0,0 +1,48 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{bol, opt, recover, str, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            Bar,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum BarParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct BarParser<'a>(pub &'a Context<'a>);
+
+impl Parser for BarParser<'_> {
+    type Value = Bar;
+    type Error = BarParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (clock_format, show_focused_title) = ext.extract((
+            recover(opt(str("clock-format"))),
+            recover(opt(bol("show-focused-title"))),
+        ))?;
+        Ok(Bar {
+            clock_format: clock_format.despan().map(|v| v.to_string()),
+            show_focused_title: show_focused_title.despan(),
+        })
+    }
+}
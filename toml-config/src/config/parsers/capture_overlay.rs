@@ -0,0 +1,45 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{opt, recover, str, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            CaptureOverlay,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum CaptureOverlayParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct CaptureOverlayParser<'a>(pub &'a Context<'a>);
+
+impl Parser for CaptureOverlayParser<'_> {
+    type Value = CaptureOverlay;
+    type Error = CaptureOverlayParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (text, image) = ext.extract((recover(opt(str("text"))), recover(opt(str("image")))))?;
+        Ok(CaptureOverlay {
+            text: text.despan_into(),
+            image: image.despan_into(),
+        })
+    }
+}
@@ -0,0 +1,48 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{n64, opt, recover, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            ClientLimits,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum ClientLimitsParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct ClientLimitsParser<'a>(pub &'a Context<'a>);
+
+impl Parser for ClientLimitsParser<'_> {
+    type Value = ClientLimits;
+    type Error = ClientLimitsParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (max_fds, max_memory_mb) = ext.extract((
+            recover(opt(n64("max-fds"))),
+            recover(opt(n64("max-memory-mb"))),
+        ))?;
+        Ok(ClientLimits {
+            max_fds: max_fds.despan(),
+            max_memory_mb: max_memory_mb.despan(),
+        })
+    }
+}
@@ -2,21 +2,31 @@ use {
     crate::{
         config::{
             context::Context,
-            extractor::{arr, bol, opt, recover, str, val, Extractor, ExtractorError},
+            extractor::{arr, bol, n32, n64, opt, recover, str, val, Extractor, ExtractorError},
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
             parsers::{
                 action::ActionParser,
+                bar::BarParser,
+                binding_mode::BindingModesParser,
+                capture_overlay::CaptureOverlayParser,
+                client_limits::ClientLimitsParser,
                 connector::ConnectorsParser,
                 drm_device::DrmDevicesParser,
                 drm_device_match::DrmDeviceMatchParser,
+                empty_workspace_behavior::EmptyWorkspaceBehaviorParser,
                 env::EnvParser,
+                gesture::GesturesParser,
                 gfx_api::GfxApiParser,
-                idle::IdleParser,
+                idle::{GracePeriodParser, IdleParser},
                 input::InputsParser,
+                key_remap::KeyRemapParser,
                 keymap::KeymapParser,
                 libei::LibeiParser,
                 log_level::LogLevelParser,
+                night_light::NightLightParser,
+                notifications::NotificationsParser,
                 output::OutputsParser,
+                pointer_binding::PointerBindingsParser,
                 repeat_rate::RepeatRateParser,
                 shortcuts::{
                     parse_modified_keysym_str, ComplexShortcutsParser, ShortcutsParser,
@@ -25,12 +35,17 @@ use {
                 status::StatusParser,
                 tearing::TearingParser,
                 theme::ThemeParser,
+                touch_gesture::TouchGesturesParser,
                 ui_drag::UiDragParser,
                 vrr::VrrParser,
+                window_screenshots::WindowScreenshotsParser,
+                window_state::WindowStateParser,
                 xwayland::XwaylandParser,
+                StringParser,
             },
             spanned::SpannedErrorExt,
-            Action, Config, Libei, Theme, UiDrag,
+            Action, Bar, CaptureOverlay, ClientLimits, Config, Libei, Notifications, Theme, UiDrag,
+            WindowScreenshots, WindowState,
         },
         toml::{
             toml_span::{DespanExt, Span, Spanned},
@@ -38,7 +53,7 @@ use {
         },
     },
     indexmap::IndexMap,
-    std::collections::HashSet,
+    std::{collections::HashSet, time::Duration},
     thiserror::Error,
 };
 
@@ -117,6 +132,50 @@ impl Parser for ConfigParser<'_> {
                 ui_drag_val,
                 xwayland_val,
             ),
+            (
+                touch_gestures_val,
+                gestures_val,
+                gesture_passthrough_val,
+                pointer_bindings_val,
+                bar_val,
+                notifications_val,
+                jay_protocol_allowlist_val,
+                window_state_val,
+                shortcut_sequence_timeout_val,
+                break_out_key_val,
+            ),
+            (
+                throttle_interactive_resize,
+                on_lock_val,
+                on_unlock_val,
+                lock_grace_period_val,
+                on_locker_crash_val,
+                fallback_lock_screen_prompt,
+                capture_redactions_val,
+                capture_overlay_val,
+                client_limits_val,
+                pointer_motion_coalescing,
+            ),
+            (
+                on_connector_connected_val,
+                on_connector_disconnected_val,
+                on_connector_mode_changed_val,
+                on_drm_device_added_val,
+                on_drm_device_removed_val,
+                empty_workspace_behavior_val,
+                autotiling,
+                night_light_val,
+                workspace_swipe_fingers,
+                window_screenshots_val,
+            ),
+            (
+                binding_modes_val,
+                force_implicit_sync,
+                activation_token_lifetime_val,
+                key_remap_val,
+                workspace_back_and_forth,
+                show_marks,
+            ),
         ) = ext.extract((
             (
                 opt(val("keymap")),
@@ -154,6 +213,50 @@ impl Parser for ConfigParser<'_> {
                 opt(val("ui-drag")),
                 opt(val("xwayland")),
             ),
+            (
+                opt(val("touch-gestures")),
+                opt(val("gestures")),
+                recover(opt(arr("gesture-passthrough"))),
+                opt(val("pointer-bindings")),
+                opt(val("bar")),
+                opt(val("notifications")),
+                recover(opt(arr("jay-protocol-allowlist"))),
+                opt(val("window-state")),
+                recover(opt(n64("shortcut-sequence-timeout-ms"))),
+                recover(opt(str("break-out-key"))),
+            ),
+            (
+                recover(opt(bol("throttle-interactive-resize"))),
+                opt(val("on-lock")),
+                opt(val("on-unlock")),
+                opt(val("lock-grace-period")),
+                opt(val("on-locker-crash")),
+                recover(opt(bol("fallback-lock-screen-prompt"))),
+                recover(opt(arr("capture-redactions"))),
+                opt(val("capture-overlay")),
+                opt(val("client-limits")),
+                recover(opt(bol("pointer-motion-coalescing"))),
+            ),
+            (
+                opt(val("on-connector-connected")),
+                opt(val("on-connector-disconnected")),
+                opt(val("on-connector-mode-changed")),
+                opt(val("on-drm-device-added")),
+                opt(val("on-drm-device-removed")),
+                opt(val("empty-workspace-behavior")),
+                recover(opt(bol("autotiling"))),
+                opt(val("night-light")),
+                recover(opt(n32("workspace-swipe-fingers"))),
+                opt(val("window-screenshots")),
+            ),
+            (
+                opt(val("binding-modes")),
+                recover(opt(bol("force-implicit-sync"))),
+                recover(opt(n64("activation-token-lifetime-ms"))),
+                opt(val("key-remap")),
+                recover(opt(bol("workspace-back-and-forth"))),
+                recover(opt(bol("show-marks"))),
+            ),
         ))?;
         let mut keymap = None;
         if let Some(value) = keymap_val {
@@ -169,12 +272,14 @@ impl Parser for ConfigParser<'_> {
         }
         let mut used_keys = HashSet::new();
         let mut shortcuts = vec![];
+        let mut shortcut_sequences = vec![];
         if let Some(value) = shortcuts_val {
             value
                 .parse(&mut ShortcutsParser {
                     cx: self.0,
                     used_keys: &mut used_keys,
                     shortcuts: &mut shortcuts,
+                    sequences: &mut shortcut_sequences,
                 })
                 .map_spanned_err(ConfigParserError::ParseShortcuts)?;
         }
@@ -190,10 +295,29 @@ impl Parser for ConfigParser<'_> {
         if shortcuts.is_empty() {
             log::warn!("Config defines no shortcuts");
         }
+        let mut binding_modes = vec![];
+        if let Some(value) = binding_modes_val {
+            match value.parse(&mut BindingModesParser(self.0)) {
+                Ok(v) => binding_modes = v,
+                Err(e) => log::warn!("Could not parse the binding modes: {}", self.0.error(e)),
+            }
+        }
         let on_graphics_initialized =
             self.parse_action("on-graphics-initialized", on_graphics_init_val);
         let on_idle = self.parse_action("on-idle", on_idle_val);
+        let on_lock = self.parse_action("on-lock", on_lock_val);
+        let on_unlock = self.parse_action("on-unlock", on_unlock_val);
+        let on_locker_crash = self.parse_action("on-locker-crash", on_locker_crash_val);
         let on_startup = self.parse_action("on-startup", on_startup_val);
+        let on_connector_connected =
+            self.parse_action("on-connector-connected", on_connector_connected_val);
+        let on_connector_disconnected =
+            self.parse_action("on-connector-disconnected", on_connector_disconnected_val);
+        let on_connector_mode_changed =
+            self.parse_action("on-connector-mode-changed", on_connector_mode_changed_val);
+        let on_drm_device_added = self.parse_action("on-drm-device-added", on_drm_device_added_val);
+        let on_drm_device_removed =
+            self.parse_action("on-drm-device-removed", on_drm_device_removed_val);
         let mut status = None;
         if let Some(value) = status_val {
             match value.parse(&mut StatusParser(self.0)) {
@@ -248,6 +372,18 @@ impl Parser for ConfigParser<'_> {
                 }
             }
         }
+        let mut empty_workspace_behavior = None;
+        if let Some(value) = empty_workspace_behavior_val {
+            match value.parse(&mut EmptyWorkspaceBehaviorParser) {
+                Ok(v) => empty_workspace_behavior = Some(v),
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse the empty workspace behavior: {}",
+                        self.0.error(e)
+                    );
+                }
+            }
+        }
         let mut theme = Theme::default();
         if let Some(value) = theme_val {
             match value.parse(&mut ThemeParser(self.0)) {
@@ -295,17 +431,28 @@ impl Parser for ConfigParser<'_> {
         }
         let mut idle = None;
         let mut grace_period = None;
+        let mut audio_idle_inhibit = None;
         if let Some(value) = idle_val {
             match value.parse(&mut IdleParser(self.0)) {
                 Ok(v) => {
                     idle = v.timeout;
                     grace_period = v.grace_period;
+                    audio_idle_inhibit = v.audio_inhibit;
                 }
                 Err(e) => {
                     log::warn!("Could not parse the idle timeout: {}", self.0.error(e));
                 }
             }
         }
+        let mut lock_grace_period = None;
+        if let Some(value) = lock_grace_period_val {
+            match value.parse(&mut GracePeriodParser(self.0)) {
+                Ok(v) => lock_grace_period = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse the lock grace period: {}", self.0.error(e));
+                }
+            }
+        }
         let mut repeat_rate = None;
         if let Some(value) = repeat_rate_val {
             match value.parse(&mut RepeatRateParser(self.0)) {
@@ -321,6 +468,12 @@ impl Parser for ConfigParser<'_> {
                 window_management_key = Some(key);
             }
         }
+        let mut break_out_key = None;
+        if let Some(value) = break_out_key_val {
+            if let Some(key) = parse_modified_keysym_str(self.0, value.span, value.value) {
+                break_out_key = Some(key);
+            }
+        }
         let mut vrr = None;
         if let Some(value) = vrr_val {
             match value.parse(&mut VrrParser(self.0)) {
@@ -366,12 +519,167 @@ impl Parser for ConfigParser<'_> {
                 }
             }
         }
+        let mut touch_gestures = vec![];
+        if let Some(value) = touch_gestures_val {
+            match value.parse(&mut TouchGesturesParser(self.0)) {
+                Ok(v) => touch_gestures = v,
+                Err(e) => {
+                    log::warn!("Could not parse the touch gestures: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut gestures = vec![];
+        if let Some(value) = gestures_val {
+            match value.parse(&mut GesturesParser(self.0)) {
+                Ok(v) => gestures = v,
+                Err(e) => {
+                    log::warn!("Could not parse the gestures: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut gesture_passthrough = vec![];
+        if let Some(value) = gesture_passthrough_val {
+            for value in value.value {
+                match value.parse(&mut StringParser) {
+                    Ok(v) => gesture_passthrough.push(v),
+                    Err(e) => {
+                        log::warn!("Could not parse an app-id: {}", self.0.error(e));
+                    }
+                }
+            }
+        }
+        let mut pointer_bindings = vec![];
+        if let Some(value) = pointer_bindings_val {
+            match value.parse(&mut PointerBindingsParser(self.0)) {
+                Ok(v) => pointer_bindings = v,
+                Err(e) => {
+                    log::warn!("Could not parse the pointer bindings: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut bar = Bar::default();
+        if let Some(value) = bar_val {
+            match value.parse(&mut BarParser(self.0)) {
+                Ok(v) => bar = v,
+                Err(e) => {
+                    log::warn!("Could not parse bar setting: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut notifications = Notifications::default();
+        if let Some(value) = notifications_val {
+            match value.parse(&mut NotificationsParser(self.0)) {
+                Ok(v) => notifications = v,
+                Err(e) => {
+                    log::warn!("Could not parse notifications setting: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut jay_protocol_allowlist = None;
+        if let Some(value) = jay_protocol_allowlist_val {
+            let mut executables = vec![];
+            for value in value.value {
+                match value.parse(&mut StringParser) {
+                    Ok(v) => executables.push(v),
+                    Err(e) => {
+                        log::warn!("Could not parse an executable: {}", self.0.error(e));
+                    }
+                }
+            }
+            jay_protocol_allowlist = Some(executables);
+        }
+        let mut capture_redactions = None;
+        if let Some(value) = capture_redactions_val {
+            let mut app_ids = vec![];
+            for value in value.value {
+                match value.parse(&mut StringParser) {
+                    Ok(v) => app_ids.push(v),
+                    Err(e) => {
+                        log::warn!("Could not parse an app-id: {}", self.0.error(e));
+                    }
+                }
+            }
+            capture_redactions = Some(app_ids);
+        }
+        let mut night_light = None;
+        if let Some(value) = night_light_val {
+            match value.parse(&mut NightLightParser(self.0)) {
+                Ok(v) => night_light = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse night-light setting: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut capture_overlay = CaptureOverlay::default();
+        if let Some(value) = capture_overlay_val {
+            match value.parse(&mut CaptureOverlayParser(self.0)) {
+                Ok(v) => capture_overlay = v,
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse capture-overlay setting: {}",
+                        self.0.error(e)
+                    );
+                }
+            }
+        }
+        let mut window_screenshots = WindowScreenshots::default();
+        if let Some(value) = window_screenshots_val {
+            match value.parse(&mut WindowScreenshotsParser(self.0)) {
+                Ok(v) => window_screenshots = v,
+                Err(e) => {
+                    log::warn!(
+                        "Could not parse window-screenshots setting: {}",
+                        self.0.error(e)
+                    );
+                }
+            }
+        }
+        let mut client_limits = ClientLimits::default();
+        if let Some(value) = client_limits_val {
+            match value.parse(&mut ClientLimitsParser(self.0)) {
+                Ok(v) => client_limits = v,
+                Err(e) => {
+                    log::warn!("Could not parse client-limits setting: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut key_remap = vec![];
+        if let Some(value) = key_remap_val {
+            match value.parse(&mut KeyRemapParser(self.0)) {
+                Ok(v) => key_remap = v,
+                Err(e) => {
+                    log::warn!("Could not parse key-remap setting: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut window_state = WindowState::default();
+        if let Some(value) = window_state_val {
+            match value.parse(&mut WindowStateParser(self.0)) {
+                Ok(v) => window_state = v,
+                Err(e) => {
+                    log::warn!("Could not parse window-state setting: {}", self.0.error(e));
+                }
+            }
+        }
         Ok(Config {
             keymap,
             repeat_rate,
             shortcuts,
+            shortcut_sequences,
+            shortcut_sequence_timeout: shortcut_sequence_timeout_val
+                .despan()
+                .map(Duration::from_millis),
+            binding_modes,
+            activation_token_lifetime: activation_token_lifetime_val
+                .despan()
+                .map(Duration::from_millis),
             on_graphics_initialized,
             on_idle,
+            on_lock,
+            on_unlock,
+            lock_grace_period,
+            on_locker_crash,
+            fallback_lock_screen_prompt: fallback_lock_screen_prompt.despan(),
             status,
             outputs,
             connectors,
@@ -385,17 +693,46 @@ impl Parser for ConfigParser<'_> {
             drm_devices,
             direct_scanout_enabled: direct_scanout.despan(),
             explicit_sync_enabled: explicit_sync.despan(),
+            force_implicit_sync: force_implicit_sync.despan().unwrap_or(false),
+            key_remap,
             render_device,
             inputs,
             idle,
             grace_period,
+            audio_idle_inhibit,
             focus_follows_mouse: focus_follows_mouse.despan().unwrap_or(true),
+            pointer_motion_coalescing: pointer_motion_coalescing.despan(),
             window_management_key,
+            break_out_key,
+            throttle_interactive_resize: throttle_interactive_resize.despan(),
             vrr,
             tearing,
             libei,
             ui_drag,
             xwayland,
+            touch_gestures,
+            gestures,
+            gesture_passthrough,
+            pointer_bindings,
+            bar,
+            notifications,
+            jay_protocol_allowlist,
+            window_state,
+            capture_redactions,
+            capture_overlay,
+            on_connector_connected,
+            on_connector_disconnected,
+            on_connector_mode_changed,
+            on_drm_device_added,
+            on_drm_device_removed,
+            empty_workspace_behavior,
+            autotiling: autotiling.despan(),
+            workspace_back_and_forth: workspace_back_and_forth.despan(),
+            show_marks: show_marks.despan(),
+            night_light,
+            workspace_swipe_fingers: workspace_swipe_fingers.despan(),
+            window_screenshots,
+            client_limits,
         })
     }
 }
@@ -0,0 +1,90 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{fltorint, opt, s32, str, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+        },
+        toml::{
+            toml_span::{Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::video::CustomMode,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum CustomModeParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("Could not parse the modeline")]
+    Modeline,
+    #[error("Unknown formula {0}")]
+    UnknownFormula(String),
+    #[error("Either `modeline` or `width` and `height` must be specified")]
+    MissingSize,
+    #[error("Size {0} is out of range")]
+    InvalidSize(i32),
+    #[error("The timings are invalid")]
+    InvalidTimings,
+}
+
+pub struct CustomModeParser<'a>(pub &'a Context<'a>);
+
+impl Parser for CustomModeParser<'_> {
+    type Value = CustomMode;
+    type Error = CustomModeParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (modeline, width, height, refresh_rate, formula) = ext.extract((
+            opt(str("modeline")),
+            opt(s32("width")),
+            opt(s32("height")),
+            opt(fltorint("refresh-rate")),
+            opt(str("formula")),
+        ))?;
+        let mode = if let Some(modeline) = modeline {
+            match CustomMode::from_modeline(modeline.value) {
+                Some(m) => m,
+                _ => return Err(CustomModeParserError::Modeline.spanned(modeline.span)),
+            }
+        } else {
+            let (Some(width), Some(height)) = (width, height) else {
+                return Err(CustomModeParserError::MissingSize.spanned(span));
+            };
+            let size = |s: Spanned<i32>| match u16::try_from(s.value) {
+                Ok(v) if v > 0 => Ok(v),
+                _ => Err(CustomModeParserError::InvalidSize(s.value).spanned(s.span)),
+            };
+            let width = size(width)?;
+            let height = size(height)?;
+            let refresh_millihz = match refresh_rate {
+                Some(rr) => (rr.value * 1000.0).round() as u32,
+                None => 60_000,
+            };
+            match formula.as_ref().map(|f| f.value).unwrap_or("cvt") {
+                "cvt" => CustomMode::cvt(width, height, refresh_millihz, false),
+                "cvt-reduced-blanking" => CustomMode::cvt(width, height, refresh_millihz, true),
+                "gtf" => CustomMode::gtf(width, height, refresh_millihz),
+                f => {
+                    let span = formula.map(|f| f.span).unwrap_or(span);
+                    return Err(CustomModeParserError::UnknownFormula(f.to_string()).spanned(span));
+                }
+            }
+        };
+        if !mode.is_valid() {
+            return Err(CustomModeParserError::InvalidTimings.spanned(span));
+        }
+        Ok(mode)
+    }
+}
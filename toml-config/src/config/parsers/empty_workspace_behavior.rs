@@ -0,0 +1,39 @@
+use {
+    crate::{
+        config::parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+        toml::toml_span::{Span, SpannedExt},
+    },
+    jay_config::EmptyWorkspaceBehavior,
+    thiserror::Error,
+};
+
+pub struct EmptyWorkspaceBehaviorParser;
+
+#[derive(Debug, Error)]
+pub enum EmptyWorkspaceBehaviorParserError {
+    #[error(transparent)]
+    DataType(#[from] UnexpectedDataType),
+    #[error("Unknown empty workspace behavior {0}")]
+    Unknown(String),
+}
+
+impl Parser for EmptyWorkspaceBehaviorParser {
+    type Value = EmptyWorkspaceBehavior;
+    type Error = EmptyWorkspaceBehaviorParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        use EmptyWorkspaceBehavior::*;
+        let behavior = match string {
+            "keep" => Keep,
+            "destroy" => Destroy,
+            "show-previous" => ShowPrevious,
+            _ => {
+                return Err(
+                    EmptyWorkspaceBehaviorParserError::Unknown(string.to_string()).spanned(span),
+                );
+            }
+        };
+        Ok(behavior)
+    }
+}
@@ -0,0 +1,119 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{n32, opt, str, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::action::{ActionParser, ActionParserError},
+            spanned::SpannedErrorExt,
+            ConfigGesture,
+        },
+        toml::{
+            toml_span::{Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::{input::PointerGesture, Direction},
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum GestureParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("Unknown gesture {0}")]
+    UnknownGesture(String),
+    #[error("Unknown direction {0}")]
+    UnknownDirection(String),
+    #[error("Could not parse the action")]
+    Action(#[source] ActionParserError),
+}
+
+pub struct GestureParser<'a>(pub &'a Context<'a>);
+
+impl Parser for GestureParser<'_> {
+    type Value = ConfigGesture;
+    type Error = GestureParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (gesture, fingers, direction, action_val) = ext.extract((
+            str("gesture"),
+            n32("fingers"),
+            opt(str("direction")),
+            val("action"),
+        ))?;
+        let fingers = fingers.value;
+        if gesture.value != "swipe" && direction.is_some() {
+            log::warn!(
+                "`direction` has no effect for this gesture: {}",
+                self.0.error3(span)
+            );
+        }
+        let gesture = match gesture.value {
+            "swipe" => {
+                let Some(direction) = direction else {
+                    return Err(GestureParserError::Extract(ExtractorError::MissingField(
+                        "direction",
+                    ))
+                    .spanned(span));
+                };
+                let direction = match direction.value {
+                    "left" => Direction::Left,
+                    "right" => Direction::Right,
+                    "up" => Direction::Up,
+                    "down" => Direction::Down,
+                    _ => {
+                        return Err(GestureParserError::UnknownDirection(
+                            direction.value.to_string(),
+                        )
+                        .spanned(direction.span))
+                    }
+                };
+                PointerGesture::Swipe { fingers, direction }
+            }
+            "pinch-in" => PointerGesture::PinchIn { fingers },
+            "pinch-out" => PointerGesture::PinchOut { fingers },
+            "hold" => PointerGesture::Hold { fingers },
+            _ => {
+                return Err(
+                    GestureParserError::UnknownGesture(gesture.value.to_string())
+                        .spanned(gesture.span),
+                )
+            }
+        };
+        let action = action_val
+            .parse(&mut ActionParser(self.0))
+            .map_spanned_err(GestureParserError::Action)?;
+        Ok(ConfigGesture { gesture, action })
+    }
+}
+
+pub struct GesturesParser<'a>(pub &'a Context<'a>);
+
+impl Parser for GesturesParser<'_> {
+    type Value = Vec<ConfigGesture>;
+    type Error = GestureParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Array];
+
+    fn parse_array(&mut self, _span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        let mut res = vec![];
+        for el in array {
+            match el.parse(&mut GestureParser(self.0)) {
+                Ok(o) => res.push(o),
+                Err(e) => {
+                    log::warn!("Could not parse gesture: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(res)
+    }
+}
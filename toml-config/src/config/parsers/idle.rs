@@ -2,8 +2,10 @@ use {
     crate::{
         config::{
             context::Context,
-            extractor::{n64, opt, val, Extractor, ExtractorError},
+            extractor::{arr, bol, n64, opt, recover, val, Extractor, ExtractorError},
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::StringParser,
+            AudioIdleInhibit,
         },
         toml::{
             toml_span::{DespanExt, Span, Spanned},
@@ -28,6 +30,7 @@ pub struct IdleParser<'a>(pub &'a Context<'a>);
 pub struct Idle {
     pub timeout: Option<Duration>,
     pub grace_period: Option<Duration>,
+    pub audio_inhibit: Option<AudioIdleInhibit>,
 }
 
 impl Parser for IdleParser<'_> {
@@ -41,11 +44,14 @@ impl Parser for IdleParser<'_> {
         table: &IndexMap<Spanned<String>, Spanned<Value>>,
     ) -> ParseResult<Self> {
         let mut ext = Extractor::new(self.0, span, table);
-        let (minutes, seconds, grace_period_val) = ext.extract((
-            opt(n64("minutes")),
-            opt(n64("seconds")),
-            opt(val("grace-period")),
-        ))?;
+        let (minutes, seconds, grace_period_val, inhibit_on_audio, audio_roles_val) =
+            ext.extract((
+                opt(n64("minutes")),
+                opt(n64("seconds")),
+                opt(val("grace-period")),
+                recover(opt(bol("inhibit-on-audio"))),
+                recover(opt(arr("audio-roles"))),
+            ))?;
         let mut timeout = None;
         if minutes.is_some() || seconds.is_some() {
             timeout = Some(parse_duration(&minutes, &seconds));
@@ -54,14 +60,33 @@ impl Parser for IdleParser<'_> {
         if let Some(gp) = grace_period_val {
             grace_period = Some(gp.parse(&mut GracePeriodParser(self.0))?);
         }
+        let mut audio_inhibit = None;
+        if let Some(enabled) = inhibit_on_audio {
+            let mut roles = vec![];
+            if let Some(value) = audio_roles_val {
+                for value in value.value {
+                    match value.parse(&mut StringParser) {
+                        Ok(v) => roles.push(v),
+                        Err(e) => {
+                            log::warn!("Could not parse an audio role: {}", self.0.error(e));
+                        }
+                    }
+                }
+            }
+            audio_inhibit = Some(AudioIdleInhibit {
+                enabled: enabled.value,
+                roles,
+            });
+        }
         Ok(Idle {
             timeout,
             grace_period,
+            audio_inhibit,
         })
     }
 }
 
-struct GracePeriodParser<'a>(pub &'a Context<'a>);
+pub struct GracePeriodParser<'a>(pub &'a Context<'a>);
 
 impl Parser for GracePeriodParser<'_> {
     type Value = Duration;
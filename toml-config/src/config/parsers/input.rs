@@ -2,11 +2,12 @@ use {
     crate::{
         config::{
             context::Context,
-            extractor::{bol, fltorint, opt, recover, str, val, Extractor, ExtractorError},
+            extractor::{bol, fltorint, n64, opt, recover, str, val, Extractor, ExtractorError},
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
             parsers::{
                 action::ActionParser,
                 input_match::{InputMatchParser, InputMatchParserError},
+                key_remap::KeyRemapParser,
                 keymap::KeymapParser,
                 output_match::OutputMatchParser,
             },
@@ -23,6 +24,7 @@ use {
         acceleration::{ACCEL_PROFILE_ADAPTIVE, ACCEL_PROFILE_FLAT},
         SwitchEvent,
     },
+    std::time::Duration,
     thiserror::Error,
 };
 
@@ -87,6 +89,18 @@ impl Parser for InputParser<'_> {
                 output_val,
                 remove_mapping,
                 calibration_matrix,
+                button_debounce_ms,
+            ),
+            (
+                gamepad_resets_idle,
+                disable_while_typing,
+                disable_while_trackpointing,
+                disable_while_typing_window_ms,
+                middle_button_emulation,
+                seat,
+                enabled,
+                on_attach_val,
+                key_remap_val,
             ),
         ) = ext.extract((
             (
@@ -111,6 +125,18 @@ impl Parser for InputParser<'_> {
                 opt(val("output")),
                 recover(opt(bol("remove-mapping"))),
                 recover(opt(val("calibration-matrix"))),
+                recover(opt(n64("button-debounce-ms"))),
+            ),
+            (
+                recover(opt(bol("gamepad-resets-idle"))),
+                recover(opt(bol("disable-while-typing"))),
+                recover(opt(bol("disable-while-trackpointing"))),
+                recover(opt(n64("disable-while-typing-window-ms"))),
+                recover(opt(bol("middle-button-emulation"))),
+                recover(opt(str("seat"))),
+                recover(opt(bol("enabled"))),
+                opt(val("on-attach")),
+                opt(val("key-remap")),
             ),
         ))?;
         let accel_profile = match accel_profile {
@@ -189,6 +215,22 @@ impl Parser for InputParser<'_> {
             "on-converted-to-tablet",
             SwitchEvent::ConvertedToTablet,
         );
+        let mut on_attach = None;
+        if let Some(val) = on_attach_val {
+            if !self.is_inputs_array {
+                log::warn!(
+                    "on-attach has no effect in this position: {}",
+                    self.cx.error3(val.span)
+                );
+            } else {
+                match val.parse(&mut ActionParser(self.cx)) {
+                    Ok(a) => on_attach = Some(a),
+                    Err(e) => {
+                        log::warn!("Could not parse on-attach action: {}", self.cx.error(e));
+                    }
+                }
+            }
+        }
         let mut output = None;
         if let Some(val) = output_val {
             match val.parse(&mut OutputMatchParser(self.cx)) {
@@ -232,6 +274,16 @@ impl Parser for InputParser<'_> {
                 }
             },
         };
+        let key_remap = match key_remap_val {
+            None => None,
+            Some(value) => match value.parse(&mut KeyRemapParser(self.cx)) {
+                Ok(v) => Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse key-remap setting: {}", self.cx.error(e));
+                    None
+                }
+            },
+        };
         Ok(Input {
             tag: tag.despan_into(),
             match_: match_val.parse_map(&mut InputMatchParser(self.cx))?,
@@ -248,6 +300,18 @@ impl Parser for InputParser<'_> {
             switch_actions,
             output,
             calibration_matrix,
+            button_debounce: button_debounce_ms.despan().map(Duration::from_millis),
+            key_remap,
+            gamepad_resets_idle: gamepad_resets_idle.despan(),
+            disable_while_typing: disable_while_typing.despan(),
+            disable_while_trackpointing: disable_while_trackpointing.despan(),
+            disable_while_typing_window: disable_while_typing_window_ms
+                .despan()
+                .map(Duration::from_millis),
+            middle_button_emulation: middle_button_emulation.despan(),
+            seat: seat.despan_into(),
+            enabled: enabled.despan(),
+            on_attach,
         })
     }
 }
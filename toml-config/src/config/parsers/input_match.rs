@@ -2,7 +2,7 @@ use {
     crate::{
         config::{
             context::Context,
-            extractor::{bol, opt, str, Extractor, ExtractorError},
+            extractor::{bol, n32, opt, recover, str, Extractor, ExtractorError},
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
             InputMatch,
         },
@@ -62,7 +62,7 @@ impl Parser for InputMatchParser<'_> {
                 is_tablet_pad,
                 is_gesture,
             ),
-            (is_switch,),
+            (is_switch, vendor_id, product_id),
         ) = ext.extract((
             (
                 opt(str("tag")),
@@ -76,7 +76,11 @@ impl Parser for InputMatchParser<'_> {
                 opt(bol("is-tablet-pad")),
                 opt(bol("is-gesture")),
             ),
-            (opt(bol("is-switch")),),
+            (
+                opt(bol("is-switch")),
+                recover(opt(n32("vendor-id"))),
+                recover(opt(n32("product-id"))),
+            ),
         ))?;
         if let Some(tag) = tag {
             self.0.used.borrow_mut().inputs.push(tag.into());
@@ -93,6 +97,8 @@ impl Parser for InputMatchParser<'_> {
             is_tablet_pad: is_tablet_pad.despan(),
             is_gesture: is_gesture.despan(),
             is_switch: is_switch.despan(),
+            vendor_id: vendor_id.despan(),
+            product_id: product_id.despan(),
         })
     }
 }
@@ -0,0 +1,62 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{n32, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+        },
+        toml::{
+            toml_span::{Span, Spanned},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum KeyRemapParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct KeyRemapParser<'a>(pub &'a Context<'a>);
+
+impl Parser for KeyRemapParser<'_> {
+    type Value = Vec<(u32, u32)>;
+    type Error = KeyRemapParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Array];
+
+    fn parse_array(&mut self, _span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        let mut res = vec![];
+        for el in array {
+            match el.parse(&mut KeyRemapEntryParser(self.0)) {
+                Ok(e) => res.push(e),
+                Err(e) => {
+                    log::warn!("Could not parse key remapping: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(res)
+    }
+}
+
+struct KeyRemapEntryParser<'a>(&'a Context<'a>);
+
+impl Parser for KeyRemapEntryParser<'_> {
+    type Value = (u32, u32);
+    type Error = KeyRemapParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (from, to) = ext.extract((n32("from"), n32("to")))?;
+        Ok((from.value, to.value))
+    }
+}
@@ -7,14 +7,14 @@ use {
             ConfigKeymap,
         },
         toml::{
-            toml_span::{Span, Spanned, SpannedExt},
+            toml_span::{DespanExt, Span, Spanned, SpannedExt},
             toml_value::Value,
         },
     },
     indexmap::IndexMap,
     jay_config::{
         config_dir,
-        keyboard::{parse_keymap, Keymap},
+        keyboard::{keymap_from_names, parse_keymap, Keymap, KeymapNames},
     },
     std::{io, path::PathBuf},
     thiserror::Error,
@@ -28,7 +28,7 @@ pub enum KeymapParserError {
     Extractor(#[from] ExtractorError),
     #[error("The keymap is invalid")]
     Invalid,
-    #[error("Keymap table must contain at least one of `name`, `map`")]
+    #[error("Keymap table must contain at least one of `name`, `map`, `path`, `layout`")]
     MissingField,
     #[error("Keymap must have both `name` and `map` fields in this context")]
     DefinitionRequired,
@@ -56,8 +56,25 @@ impl Parser for KeymapParser<'_> {
         table: &IndexMap<Spanned<String>, Spanned<Value>>,
     ) -> ParseResult<Self> {
         let mut ext = Extractor::new(self.cx, span, table);
-        let (mut name_val, mut map_val, mut path) =
-            ext.extract((opt(str("name")), opt(str("map")), opt(str("path"))))?;
+        let (mut name_val, mut map_val, mut path, rules, model, layout, variant, options) = ext
+            .extract((
+                opt(str("name")),
+                opt(str("map")),
+                opt(str("path")),
+                opt(str("rules")),
+                opt(str("model")),
+                opt(str("layout")),
+                opt(str("variant")),
+                opt(str("options")),
+            ))?;
+        let names = KeymapNames {
+            rules: rules.despan(),
+            model: model.despan(),
+            layout: layout.despan(),
+            variant: variant.despan(),
+            options: options.despan(),
+        };
+        let has_names = names != KeymapNames::default();
         if map_val.is_some() && path.is_some() {
             log::warn!(
                 "Both `name` and `path` are specified. Ignoring `path`: {}",
@@ -78,10 +95,23 @@ impl Parser for KeymapParser<'_> {
             };
             map_val = Some(file_content.as_str().spanned(path.span));
         }
-        if self.definition && (name_val.is_none() || map_val.is_none()) {
+        let map = match map_val {
+            Some(map_val) => {
+                if has_names {
+                    log::warn!(
+                        "Both an XKB keymap and RMLVO names are specified. Ignoring the names: {}",
+                        self.cx.error3(span)
+                    );
+                }
+                Some(parse(map_val.span, map_val.value)?)
+            }
+            None if has_names => Some(from_names(span, &names)?),
+            None => None,
+        };
+        if self.definition && (name_val.is_none() || map.is_none()) {
             return Err(KeymapParserError::DefinitionRequired.spanned(span));
         }
-        if !self.definition && map_val.is_some() {
+        if !self.definition && map.is_some() {
             if let Some(val) = name_val {
                 log::warn!(
                     "Cannot use both `name` and `map` in this position. Ignoring `name`: {}",
@@ -101,13 +131,13 @@ impl Parser for KeymapParser<'_> {
                 self.cx.used.borrow_mut().keymaps.push(name.into());
             }
         }
-        let res = match (name_val, map_val) {
-            (Some(name_val), Some(map_val)) => ConfigKeymap::Defined {
+        let res = match (name_val, map) {
+            (Some(name_val), Some(map)) => ConfigKeymap::Defined {
                 name: name_val.value.to_string(),
-                map: parse(map_val.span, map_val.value)?,
+                map,
             },
             (Some(name_val), None) => ConfigKeymap::Named(name_val.value.to_string()),
-            (None, Some(map_val)) => ConfigKeymap::Literal(parse(map_val.span, map_val.value)?),
+            (None, Some(map)) => ConfigKeymap::Literal(map),
             (None, None) => return Err(KeymapParserError::MissingField.spanned(span)),
         };
         Ok(res)
@@ -121,3 +151,11 @@ fn parse(span: Span, string: &str) -> Result<Keymap, Spanned<KeymapParserError>>
         false => Err(KeymapParserError::Invalid.spanned(span)),
     }
 }
+
+fn from_names(span: Span, names: &KeymapNames<'_>) -> Result<Keymap, Spanned<KeymapParserError>> {
+    let map = keymap_from_names(names);
+    match map.is_valid() {
+        true => Ok(map),
+        false => Err(KeymapParserError::Invalid.spanned(span)),
+    }
+}
@@ -36,9 +36,13 @@ impl Parser for LibeiParser<'_> {
         table: &IndexMap<Spanned<String>, Spanned<Value>>,
     ) -> ParseResult<Self> {
         let mut ext = Extractor::new(self.0, span, table);
-        let enable_socket = ext.extract(recover(opt(bol("enable-socket"))))?;
+        let (enable_socket, prompt) = ext.extract((
+            recover(opt(bol("enable-socket"))),
+            recover(opt(bol("prompt"))),
+        ))?;
         Ok(Libei {
             enable_socket: enable_socket.despan(),
+            prompt: prompt.despan(),
         })
     }
 }
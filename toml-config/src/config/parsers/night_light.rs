@@ -0,0 +1,83 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{fltorint, n32, n64, opt, recover, str, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            NightLight,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::video::NightLightSchedule,
+    std::time::Duration,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum NightLightParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("Times must have the format HH:MM")]
+    InvalidTime,
+    #[error("Either both `start` and `end` or both `latitude` and `longitude` must be set")]
+    InvalidSchedule,
+}
+
+pub struct NightLightParser<'a>(pub &'a Context<'a>);
+
+impl Parser for NightLightParser<'_> {
+    type Value = NightLight;
+    type Error = NightLightParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (temperature, transition_minutes, start, end, latitude, longitude) = ext.extract((
+            recover(opt(n32("temperature"))),
+            recover(opt(n64("transition-minutes"))),
+            opt(str("start")),
+            opt(str("end")),
+            opt(fltorint("latitude")),
+            opt(fltorint("longitude")),
+        ))?;
+        let schedule = match (start, end, latitude, longitude) {
+            (None, None, None, None) => NightLightSchedule::Always,
+            (Some(start), Some(end), None, None) => NightLightSchedule::Time {
+                start: parse_time(start)?,
+                end: parse_time(end)?,
+            },
+            (None, None, Some(latitude), Some(longitude)) => NightLightSchedule::Location {
+                latitude: latitude.value,
+                longitude: longitude.value,
+            },
+            _ => return Err(NightLightParserError::InvalidSchedule.spanned(span)),
+        };
+        Ok(NightLight {
+            schedule,
+            temperature: temperature.despan(),
+            transition: transition_minutes
+                .despan()
+                .map(|m| Duration::from_secs(m * 60)),
+        })
+    }
+}
+
+fn parse_time(time: Spanned<&str>) -> Result<u32, Spanned<NightLightParserError>> {
+    let parse = || {
+        let (hours, minutes) = time.value.split_once(':')?;
+        let hours: u32 = hours.parse().ok()?;
+        let minutes: u32 = minutes.parse().ok()?;
+        (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+    };
+    parse().ok_or(NightLightParserError::InvalidTime.spanned(time.span))
+}
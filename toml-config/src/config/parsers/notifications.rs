@@ -0,0 +1,93 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{bol, n64, opt, recover, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            Notifications,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::notifications::NotificationPosition,
+    std::time::Duration,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum NotificationsParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct NotificationsParser<'a>(pub &'a Context<'a>);
+
+impl Parser for NotificationsParser<'_> {
+    type Value = Notifications;
+    type Error = NotificationsParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (enabled, do_not_disturb, position, timeout_ms) = ext.extract((
+            recover(opt(bol("enabled"))),
+            recover(opt(bol("do-not-disturb"))),
+            opt(val("position")),
+            recover(opt(n64("timeout-ms"))),
+        ))?;
+        let position = position.and_then(|p| match p.parse(&mut NotificationPositionParser) {
+            Ok(p) => Some(p),
+            Err(e) => {
+                log::error!("Could not parse notification position: {}", self.0.error(e));
+                None
+            }
+        });
+        Ok(Notifications {
+            enabled: enabled.despan(),
+            do_not_disturb: do_not_disturb.despan(),
+            position,
+            timeout: timeout_ms.despan().map(Duration::from_millis),
+        })
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum NotificationPositionParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error("Unknown position {0}")]
+    UnknownPosition(String),
+}
+
+struct NotificationPositionParser;
+
+impl Parser for NotificationPositionParser {
+    type Value = NotificationPosition;
+    type Error = NotificationPositionParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        let position = match string {
+            "top-left" => NotificationPosition::TopLeft,
+            "top-right" => NotificationPosition::TopRight,
+            "bottom-left" => NotificationPosition::BottomLeft,
+            "bottom-right" => NotificationPosition::BottomRight,
+            _ => {
+                return Err(
+                    NotificationPositionParserError::UnknownPosition(string.to_string())
+                        .spanned(span),
+                )
+            }
+        };
+        Ok(position)
+    }
+}
@@ -2,9 +2,11 @@ use {
     crate::{
         config::{
             context::Context,
-            extractor::{fltorint, opt, recover, s32, str, val, Extractor, ExtractorError},
+            extractor::{bol, fltorint, opt, recover, s32, str, val, Extractor, ExtractorError},
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
             parsers::{
+                bar::BarParser,
+                custom_mode::CustomModeParser,
                 format::FormatParser,
                 mode::ModeParser,
                 output_match::{OutputMatchParser, OutputMatchParserError},
@@ -49,8 +51,11 @@ impl Parser for OutputParser<'_> {
         table: &IndexMap<Spanned<String>, Spanned<Value>>,
     ) -> ParseResult<Self> {
         let mut ext = Extractor::new(self.cx, span, table);
-        let (name, match_val, x, y, scale, transform, mode, vrr_val, tearing_val, format_val) = ext
-            .extract((
+        let (
+            (name, match_val, x, y, scale, transform, mode, vrr_val, tearing_val, format_val),
+            (hdr, render_margin_ms, mirror, custom_mode_val, bar_val),
+        ) = ext.extract((
+            (
                 opt(str("name")),
                 val("match"),
                 recover(opt(s32("x"))),
@@ -61,7 +66,15 @@ impl Parser for OutputParser<'_> {
                 opt(val("vrr")),
                 opt(val("tearing")),
                 opt(val("format")),
-            ))?;
+            ),
+            (
+                recover(opt(bol("hdr"))),
+                recover(opt(fltorint("render-margin-ms"))),
+                recover(opt(str("mirror"))),
+                opt(val("custom-mode")),
+                opt(val("bar")),
+            ),
+        ))?;
         let transform = match transform {
             None => None,
             Some(t) => match t.value {
@@ -89,6 +102,16 @@ impl Parser for OutputParser<'_> {
             },
             None => None,
         };
+        let custom_mode = match custom_mode_val {
+            Some(mode) => match mode.parse(&mut CustomModeParser(self.cx)) {
+                Ok(m) => Some(m),
+                Err(e) => {
+                    log::warn!("Could not parse custom mode: {}", self.cx.error(e));
+                    None
+                }
+            },
+            None => None,
+        };
         if let Some(name) = name {
             if self.name_ok {
                 self.cx
@@ -133,6 +156,15 @@ impl Parser for OutputParser<'_> {
                 }
             }
         }
+        let mut bar = None;
+        if let Some(value) = bar_val {
+            match value.parse(&mut BarParser(self.cx)) {
+                Ok(v) => bar = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse bar setting: {}", self.cx.error(e));
+                }
+            }
+        }
         Ok(Output {
             name: name.despan().map(|v| v.to_string()),
             match_: match_val.parse_map(&mut OutputMatchParser(self.cx))?,
@@ -141,9 +173,14 @@ impl Parser for OutputParser<'_> {
             scale: scale.despan(),
             transform,
             mode,
+            custom_mode,
             vrr,
             tearing,
             format,
+            hdr: hdr.despan(),
+            render_margin_ms: render_margin_ms.despan(),
+            mirror: mirror.despan().map(|v| v.to_string()),
+            bar,
         })
     }
 }
@@ -0,0 +1,108 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{str, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::action::{ActionParser, ActionParserError},
+            spanned::SpannedErrorExt,
+            ConfigPointerBinding,
+        },
+        toml::{
+            toml_span::{Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::input::{
+        PointerInput, PointerTarget, BTN_EXTRA, BTN_LEFT, BTN_MIDDLE, BTN_RIGHT, BTN_SIDE,
+    },
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum PointerBindingParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("Unknown target {0}")]
+    UnknownTarget(String),
+    #[error("Unknown input {0}")]
+    UnknownInput(String),
+    #[error("Could not parse the action")]
+    Action(#[source] ActionParserError),
+}
+
+pub struct PointerBindingParser<'a>(pub &'a Context<'a>);
+
+impl Parser for PointerBindingParser<'_> {
+    type Value = ConfigPointerBinding;
+    type Error = PointerBindingParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (target, input, action_val) =
+            ext.extract((str("target"), str("input"), val("action")))?;
+        let target = match target.value {
+            "desktop" => PointerTarget::Desktop,
+            "title" => PointerTarget::Title,
+            "border" => PointerTarget::Border,
+            _ => {
+                return Err(
+                    PointerBindingParserError::UnknownTarget(target.value.to_string())
+                        .spanned(target.span),
+                )
+            }
+        };
+        let input = match input.value {
+            "button-left" => PointerInput::Button(BTN_LEFT),
+            "button-right" => PointerInput::Button(BTN_RIGHT),
+            "button-middle" => PointerInput::Button(BTN_MIDDLE),
+            "button-side" => PointerInput::Button(BTN_SIDE),
+            "button-extra" => PointerInput::Button(BTN_EXTRA),
+            "scroll-up" => PointerInput::ScrollUp,
+            "scroll-down" => PointerInput::ScrollDown,
+            _ => {
+                return Err(
+                    PointerBindingParserError::UnknownInput(input.value.to_string())
+                        .spanned(input.span),
+                )
+            }
+        };
+        let action = action_val
+            .parse(&mut ActionParser(self.0))
+            .map_spanned_err(PointerBindingParserError::Action)?;
+        Ok(ConfigPointerBinding {
+            target,
+            input,
+            action,
+        })
+    }
+}
+
+pub struct PointerBindingsParser<'a>(pub &'a Context<'a>);
+
+impl Parser for PointerBindingsParser<'_> {
+    type Value = Vec<ConfigPointerBinding>;
+    type Error = PointerBindingParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Array];
+
+    fn parse_array(&mut self, _span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        let mut res = vec![];
+        for el in array {
+            match el.parse(&mut PointerBindingParser(self.0)) {
+                Ok(o) => res.push(o),
+                Err(e) => {
+                    log::warn!("Could not parse pointer binding: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(res)
+    }
+}
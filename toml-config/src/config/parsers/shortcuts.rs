@@ -11,7 +11,7 @@ use {
                 },
             },
             spanned::SpannedErrorExt,
-            Action, Shortcut, SimpleCommand,
+            Action, Shortcut, ShortcutSequence, SimpleCommand,
         },
         toml::{
             toml_span::{Span, Spanned, SpannedExt},
@@ -42,6 +42,7 @@ pub struct ShortcutsParser<'a, 'b> {
     pub cx: &'a Context<'a>,
     pub used_keys: &'b mut HashSet<Spanned<ModifiedKeySym>>,
     pub shortcuts: &'b mut Vec<Shortcut>,
+    pub sequences: &'b mut Vec<ShortcutSequence>,
 }
 
 impl Parser for ShortcutsParser<'_, '_> {
@@ -55,6 +56,16 @@ impl Parser for ShortcutsParser<'_, '_> {
         table: &IndexMap<Spanned<String>, Spanned<Value>>,
     ) -> ParseResult<Self> {
         for (key, value) in table.iter() {
+            if key.value.contains(char::is_whitespace) {
+                let Some(keys) = parse_sequence(self.cx, key) else {
+                    continue;
+                };
+                let Some(action) = parse_action(self.cx, &key.value, value) else {
+                    continue;
+                };
+                self.sequences.push(ShortcutSequence { keys, action });
+                continue;
+            }
             let Some(keysym) = parse_modified_keysym(self.cx, key) else {
                 continue;
             };
@@ -174,6 +185,13 @@ fn parse_action(cx: &Context<'_>, key: &str, value: &Spanned<Value>) -> Option<A
     }
 }
 
+fn parse_sequence(cx: &Context<'_>, key: &Spanned<String>) -> Option<Vec<ModifiedKeySym>> {
+    key.value
+        .split_whitespace()
+        .map(|part| parse_modified_keysym_str(cx, key.span, part))
+        .collect()
+}
+
 fn parse_modified_keysym(cx: &Context<'_>, key: &Spanned<String>) -> Option<ModifiedKeySym> {
     parse_modified_keysym_str(cx, key.span, &key.value)
 }
@@ -60,6 +60,13 @@ impl Parser for ThemeParser<'_> {
                 title_height,
                 font,
             ),
+            (
+                notification_bg_color,
+                notification_border_color,
+                notification_text_color,
+                inner_gap,
+                outer_gap,
+            ),
         ) = ext.extract((
             (
                 opt(val("attention-requested-bg-color")),
@@ -83,6 +90,13 @@ impl Parser for ThemeParser<'_> {
                 recover(opt(s32("title-height"))),
                 recover(opt(str("font"))),
             ),
+            (
+                opt(val("notification-bg-color")),
+                opt(val("notification-border-color")),
+                opt(val("notification-text-color")),
+                recover(opt(s32("inner-gap"))),
+                recover(opt(s32("outer-gap"))),
+            ),
         ))?;
         macro_rules! color {
             ($e:expr) => {
@@ -114,8 +128,13 @@ impl Parser for ThemeParser<'_> {
             unfocused_title_bg_color: color!(unfocused_title_bg_color),
             unfocused_title_text_color: color!(unfocused_title_text_color),
             highlight_color: color!(highlight_color),
+            notification_bg_color: color!(notification_bg_color),
+            notification_border_color: color!(notification_border_color),
+            notification_text_color: color!(notification_text_color),
             border_width: border_width.despan(),
             title_height: title_height.despan(),
+            inner_gap: inner_gap.despan(),
+            outer_gap: outer_gap.despan(),
             font: font.map(|f| f.value.to_string()),
         })
     }
@@ -0,0 +1,142 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{n32, opt, str, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::action::{ActionParser, ActionParserError},
+            spanned::SpannedErrorExt,
+            ConfigTouchGesture,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::{input::TouchGesture, Direction},
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum TouchGestureParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("Unknown gesture {0}")]
+    UnknownGesture(String),
+    #[error("Unknown direction {0}")]
+    UnknownDirection(String),
+    #[error("Swipes require at least 3 fingers")]
+    TooFewFingers,
+    #[error("Could not parse the action")]
+    Action(#[source] ActionParserError),
+}
+
+pub struct TouchGestureParser<'a>(pub &'a Context<'a>);
+
+impl Parser for TouchGestureParser<'_> {
+    type Value = ConfigTouchGesture;
+    type Error = TouchGestureParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (gesture, fingers, direction, edge, action_val) = ext.extract((
+            str("gesture"),
+            opt(n32("fingers")),
+            opt(str("direction")),
+            opt(str("edge")),
+            val("action"),
+        ))?;
+        let parse_direction = |v: Spanned<&str>, up: &str, down: &str| {
+            let d = match v.value {
+                "left" => Direction::Left,
+                "right" => Direction::Right,
+                _ if v.value == up => Direction::Up,
+                _ if v.value == down => Direction::Down,
+                _ => {
+                    return Err(
+                        TouchGestureParserError::UnknownDirection(v.value.to_string())
+                            .spanned(v.span),
+                    )
+                }
+            };
+            Ok(d)
+        };
+        let gesture = match gesture.value {
+            "swipe" => {
+                if edge.is_some() {
+                    log::warn!("`edge` has no effect for swipes: {}", self.0.error3(span));
+                }
+                let fingers = fingers.despan().unwrap_or(3);
+                if fingers < 3 {
+                    return Err(TouchGestureParserError::TooFewFingers.spanned(span));
+                }
+                let Some(direction) = direction else {
+                    return Err(
+                        TouchGestureParserError::Extract(ExtractorError::MissingField("direction"))
+                            .spanned(span),
+                    );
+                };
+                TouchGesture::Swipe {
+                    fingers,
+                    direction: parse_direction(direction, "up", "down")?,
+                }
+            }
+            "edge-swipe" => {
+                if fingers.is_some() || direction.is_some() {
+                    log::warn!(
+                        "`fingers` and `direction` have no effect for edge swipes: {}",
+                        self.0.error3(span)
+                    );
+                }
+                let Some(edge) = edge else {
+                    return Err(
+                        TouchGestureParserError::Extract(ExtractorError::MissingField("edge"))
+                            .spanned(span),
+                    );
+                };
+                TouchGesture::EdgeSwipe {
+                    edge: parse_direction(edge, "top", "bottom")?,
+                }
+            }
+            _ => {
+                return Err(
+                    TouchGestureParserError::UnknownGesture(gesture.value.to_string())
+                        .spanned(gesture.span),
+                )
+            }
+        };
+        let action = action_val
+            .parse(&mut ActionParser(self.0))
+            .map_spanned_err(TouchGestureParserError::Action)?;
+        Ok(ConfigTouchGesture { gesture, action })
+    }
+}
+
+pub struct TouchGesturesParser<'a>(pub &'a Context<'a>);
+
+impl Parser for TouchGesturesParser<'_> {
+    type Value = Vec<ConfigTouchGesture>;
+    type Error = TouchGestureParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Array];
+
+    fn parse_array(&mut self, _span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        let mut res = vec![];
+        for el in array {
+            match el.parse(&mut TouchGestureParser(self.0)) {
+                Ok(o) => res.push(o),
+                Err(e) => {
+                    log::warn!("Could not parse touch gesture: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(res)
+    }
+}
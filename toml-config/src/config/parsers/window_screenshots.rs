@@ -0,0 +1,58 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{opt, recover, str, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::action::ActionParser,
+            WindowScreenshots,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum WindowScreenshotsParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct WindowScreenshotsParser<'a>(pub &'a Context<'a>);
+
+impl Parser for WindowScreenshotsParser<'_> {
+    type Value = WindowScreenshots;
+    type Error = WindowScreenshotsParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (directory, filename, on_saved) = ext.extract((
+            recover(opt(str("directory"))),
+            recover(opt(str("filename"))),
+            opt(val("on-saved")),
+        ))?;
+        let on_saved = on_saved.and_then(|a| match a.parse(&mut ActionParser(self.0)) {
+            Ok(a) => Some(a),
+            Err(e) => {
+                log::warn!("Could not parse the on-saved action: {}", self.0.error(e));
+                None
+            }
+        });
+        Ok(WindowScreenshots {
+            directory: directory.despan().map(|d| d.to_string()),
+            filename: filename.despan().map(|f| f.to_string()),
+            on_saved,
+        })
+    }
+}
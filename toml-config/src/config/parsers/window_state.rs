@@ -0,0 +1,60 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{arr, bol, opt, recover, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::StringParser,
+            WindowState,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum WindowStateParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+}
+
+pub struct WindowStateParser<'a>(pub &'a Context<'a>);
+
+impl Parser for WindowStateParser<'_> {
+    type Value = WindowState;
+    type Error = WindowStateParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (remember, exclude_val) =
+            ext.extract((recover(opt(bol("remember"))), recover(opt(arr("exclude")))))?;
+        let mut exclude = None;
+        if let Some(value) = exclude_val {
+            let mut app_ids = vec![];
+            for value in value.value {
+                match value.parse(&mut StringParser) {
+                    Ok(v) => app_ids.push(v),
+                    Err(e) => {
+                        log::warn!("Could not parse an app-id: {}", self.0.error(e));
+                    }
+                }
+            }
+            exclude = Some(app_ids);
+        }
+        Ok(WindowState {
+            remember: remember.despan(),
+            exclude,
+        })
+    }
+}
@@ -5,9 +5,9 @@ mod toml;
 
 use {
     crate::config::{
-        parse_config, Action, Config, ConfigConnector, ConfigDrmDevice, ConfigKeymap,
-        ConnectorMatch, DrmDeviceMatch, Exec, Input, InputMatch, Output, OutputMatch, Shortcut,
-        SimpleCommand, Status, Theme,
+        parse_config, Action, BindingMode, Config, ConfigConnector, ConfigDrmDevice, ConfigGesture,
+        ConfigKeymap, ConfigPointerBinding, ConnectorMatch, DrmDeviceMatch, Exec, Input,
+        InputMatch, Output, OutputMatch, Shortcut, ShortcutSequence, SimpleCommand, Status, Theme,
     },
     ahash::{AHashMap, AHashSet},
     error_reporter::Report,
@@ -16,26 +16,46 @@ use {
         exec::{set_env, unset_env, Command},
         get_workspace,
         input::{
-            capability::CAP_SWITCH, get_seat, input_devices, on_input_device_removed,
-            on_new_input_device, set_libei_socket_enabled, FocusFollowsMouseMode, InputDevice,
-            Seat, SwitchEvent,
+            capability::CAP_SWITCH, get_seat, input_devices, led::LED_SCROLL_LOCK,
+            on_input_device_removed, on_new_input_device, set_key_remap,
+            set_libei_authorization_prompt_enabled, set_libei_socket_enabled,
+            FocusFollowsMouseMode, InputDevice, PointerGesture, PointerInput, PointerTarget, Seat,
+            SwitchEvent, DEFAULT_BINDING_MODE,
         },
         is_reload,
-        keyboard::{Keymap, ModifiedKeySym},
+        keyboard::{mods::LOGO, syms::SYM_Escape, Keymap, ModifiedKeySym},
         logging::set_log_level,
-        on_devices_enumerated, on_idle, quit, reload, set_default_workspace_capture,
-        set_explicit_sync_enabled, set_idle, set_idle_grace_period, set_ui_drag_enabled,
-        set_ui_drag_threshold,
-        status::{set_i3bar_separator, set_status, set_status_command, unset_status_command},
+        notifications, on_devices_enumerated, on_idle, on_lock, on_locker_crashed, on_unlock,
+        on_window_screenshot_saved, quit, reload, set_activation_token_lifetime, set_autotiling,
+        set_capture_overlay, set_capture_redactions, set_client_limits,
+        set_default_workspace_capture, set_empty_workspace_behavior, set_explicit_sync_enabled,
+        set_fallback_lock_screen_prompt, set_force_implicit_sync, set_idle, set_idle_grace_period,
+        set_idle_inhibit_on_audio, set_jay_protocol_allowlist, set_lock_grace_period,
+        set_remember_window_state, set_show_marks, set_show_surface_regions,
+        set_throttle_interactive_resize, set_ui_drag_enabled, set_ui_drag_threshold,
+        set_window_screenshot_directory, set_window_screenshot_filename,
+        set_window_state_exclusions, set_workspace_back_and_forth,
+        status::{
+            set_bar_clock_format, set_bar_show_focused_title, set_i3bar_separator, set_status,
+            set_status_command, unset_status_command,
+        },
         switch_to_vt,
-        theme::{reset_colors, reset_font, reset_sizes, set_font},
+        theme::{
+            reset_colors, reset_font, reset_sizes, set_font,
+            sized::{INNER_GAP, OUTER_GAP},
+        },
+        toggle_show_surface_regions,
         video::{
-            connectors, drm_devices, on_connector_connected, on_connector_disconnected,
+            connectors, drm_devices, get_connector, on_connector_connected,
+            on_connector_disconnected, on_connector_mode_changed, on_drm_device_removed,
             on_graphics_initialized, on_new_connector, on_new_drm_device,
-            set_direct_scanout_enabled, set_gfx_api, set_tearing_mode, set_vrr_cursor_hz,
-            set_vrr_mode, Connector, DrmDevice,
+            set_direct_scanout_enabled, set_gfx_api, set_night_light_schedule,
+            set_night_light_temperature, set_night_light_transition, set_outputs_powered,
+            set_tearing_mode, set_vrr_cursor_hz, set_vrr_mode, toggle_outputs_powered, Connector,
+            DrmDevice, NightLightSchedule,
         },
         xwayland::set_x_scaling_mode,
+        EmptyWorkspaceBehavior,
     },
     std::{cell::RefCell, io::ErrorKind, path::PathBuf, rc::Rc, time::Duration},
 };
@@ -96,6 +116,32 @@ impl Action {
                 SimpleCommand::EnableWindowManagement(bool) => {
                     B::new(move || s.set_window_management_enabled(bool))
                 }
+                SimpleCommand::DismissNotification(all) => match all {
+                    true => B::new(notifications::dismiss_all),
+                    false => B::new(notifications::dismiss),
+                },
+                SimpleCommand::InvokeNotificationAction => {
+                    B::new(notifications::invoke_default_action)
+                }
+                SimpleCommand::ToggleDoNotDisturb => B::new(notifications::toggle_do_not_disturb),
+                SimpleCommand::SetOutputsPowered(powered) => {
+                    B::new(move || set_outputs_powered(powered))
+                }
+                SimpleCommand::ToggleOutputsPowered => B::new(toggle_outputs_powered),
+                SimpleCommand::SetShowSurfaceRegions(show) => {
+                    B::new(move || set_show_surface_regions(show))
+                }
+                SimpleCommand::ToggleShowSurfaceRegions => B::new(toggle_show_surface_regions),
+                SimpleCommand::ToggleScrollLockLed => B::new(move || {
+                    let on = s.leds().contains(LED_SCROLL_LOCK);
+                    s.set_led_override(LED_SCROLL_LOCK, Some(!on));
+                }),
+                SimpleCommand::ScreenshotWindow => B::new(move || s.screenshot_window()),
+                SimpleCommand::BrightnessUp => B::new(move || s.change_brightness(0.05)),
+                SimpleCommand::BrightnessDown => B::new(move || s.change_brightness(-0.05)),
+                SimpleCommand::ForceQuit => B::new(move || s.force_quit()),
+                SimpleCommand::ShowPreviousWorkspace => B::new(move || s.show_previous_workspace()),
+                SimpleCommand::Unmark => B::new(move || s.unmark()),
             },
             Action::Multi { actions } => {
                 let actions: Vec<_> = actions.into_iter().map(|a| a.into_fn(state)).collect();
@@ -105,7 +151,16 @@ impl Action {
                     }
                 })
             }
-            Action::Exec { exec } => B::new(move || create_command(&exec).spawn()),
+            Action::Exec { exec } => {
+                let persistent = state.persistent.clone();
+                B::new(move || {
+                    let mut command = create_command(&exec);
+                    for (k, v) in &*persistent.event_env.borrow() {
+                        command.env(k, v);
+                    }
+                    command.spawn()
+                })
+            }
             Action::SwitchToVt { num } => B::new(move || switch_to_vt(num)),
             Action::ShowWorkspace { name } => {
                 let workspace = get_workspace(&name);
@@ -189,13 +244,20 @@ impl Action {
                     }
                 })
             }
-            Action::ConfigureIdle { idle, grace_period } => B::new(move || {
+            Action::ConfigureIdle {
+                idle,
+                grace_period,
+                audio_inhibit,
+            } => B::new(move || {
                 if let Some(idle) = idle {
                     set_idle(Some(idle))
                 }
                 if let Some(period) = grace_period {
                     set_idle_grace_period(period)
                 }
+                if let Some(audio) = &audio_inhibit {
+                    set_idle_inhibit_on_audio(audio.enabled, audio.roles.clone())
+                }
             }),
             Action::MoveToOutput { output, workspace } => {
                 let state = state.clone();
@@ -217,6 +279,71 @@ impl Action {
             Action::SetRepeatRate { rate } => {
                 B::new(move || s.set_repeat_rate(rate.rate, rate.delay))
             }
+            Action::PresentOutput { output } => {
+                let state = state.clone();
+                B::new(move || {
+                    for connector in connectors() {
+                        if connector.connected() && output.matches(connector, &state) {
+                            connector.present();
+                            return;
+                        }
+                    }
+                })
+            }
+            Action::SetBindingMode { mode } => B::new(move || s.set_binding_mode(&mode)),
+            Action::Mark { mark } => B::new(move || s.mark(&mark)),
+            Action::FocusMark { mark } => B::new(move || s.focus_mark(&mark)),
+            Action::SwapWithMark { mark } => B::new(move || s.swap_with_mark(&mark)),
+            Action::MoveToMark { mark } => B::new(move || s.move_to_mark(&mark)),
+            Action::SetGaps {
+                workspace,
+                inner,
+                outer,
+            } => B::new(move || match workspace {
+                Some(ws) => {
+                    if inner.is_some() {
+                        ws.set_inner_gap(inner);
+                    }
+                    if outer.is_some() {
+                        ws.set_outer_gap(outer);
+                    }
+                }
+                None => {
+                    if let Some(inner) = inner {
+                        INNER_GAP.set(inner);
+                    }
+                    if let Some(outer) = outer {
+                        OUTER_GAP.set(outer);
+                    }
+                }
+            }),
+            Action::AdjustGaps { inner, outer } => B::new(move || {
+                if let Some(inner) = inner {
+                    INNER_GAP.set((INNER_GAP.get() + inner).max(0));
+                }
+                if let Some(outer) = outer {
+                    OUTER_GAP.set((OUTER_GAP.get() + outer).max(0));
+                }
+            }),
+            Action::ForceImplicitSync { enabled } => {
+                B::new(move || set_force_implicit_sync(enabled))
+            }
+            Action::SetInputDeviceEnabled { input, enabled } => {
+                let state = state.clone();
+                B::new(move || {
+                    for d in input_devices() {
+                        if input.matches(d, &state) {
+                            let enabled = enabled.unwrap_or_else(|| !d.enabled());
+                            d.set_enabled(enabled);
+                            state
+                                .persistent
+                                .input_devices_enabled
+                                .borrow_mut()
+                                .insert(d.name(), enabled);
+                        }
+                    }
+                })
+            }
         }
     }
 }
@@ -355,6 +482,8 @@ impl InputMatch {
                 is_tablet_pad,
                 is_gesture,
                 is_switch,
+                vendor_id,
+                product_id,
             } => {
                 if let Some(name) = name {
                     if d.name() != *name {
@@ -399,6 +528,16 @@ impl InputMatch {
                 check_cap!(is_tablet_pad, CAP_TABLET_PAD);
                 check_cap!(is_gesture, CAP_GESTURE);
                 check_cap!(is_switch, CAP_SWITCH);
+                if let Some(vendor_id) = *vendor_id {
+                    if d.vendor_id() != vendor_id {
+                        return false;
+                    }
+                }
+                if let Some(product_id) = *product_id {
+                    if d.product_id() != product_id {
+                        return false;
+                    }
+                }
                 true
             }
         }
@@ -453,6 +592,33 @@ impl Input {
         if let Some(v) = self.calibration_matrix {
             c.set_calibration_matrix(v);
         }
+        if let Some(v) = self.button_debounce {
+            c.set_button_debounce(v);
+        }
+        if let Some(v) = &self.key_remap {
+            c.set_key_remap(v);
+        }
+        if let Some(v) = self.gamepad_resets_idle {
+            c.set_gamepad_resets_idle(v);
+        }
+        if let Some(v) = self.disable_while_typing {
+            c.set_disable_while_typing_enabled(v);
+        }
+        if let Some(v) = self.disable_while_trackpointing {
+            c.set_disable_while_trackpointing_enabled(v);
+        }
+        if let Some(v) = self.disable_while_typing_window {
+            c.set_disable_while_typing_window(v);
+        }
+        if let Some(v) = self.middle_button_emulation {
+            c.set_middle_button_emulation_enabled(v);
+        }
+        if let Some(v) = &self.seat {
+            c.set_seat(get_seat(v));
+        }
+        if let Some(v) = self.enabled {
+            c.set_enabled(v);
+        }
     }
 }
 
@@ -572,6 +738,9 @@ impl Output {
                 Some(m) => c.set_mode(m.width(), m.height(), Some(m.refresh_rate())),
             }
         }
+        if let Some(mode) = self.custom_mode {
+            c.set_custom_mode(mode);
+        }
         if let Some(vrr) = &self.vrr {
             if let Some(mode) = vrr.mode {
                 c.set_vrr_mode(mode);
@@ -588,6 +757,23 @@ impl Output {
         if let Some(format) = self.format {
             c.set_format(format);
         }
+        if let Some(hdr) = self.hdr {
+            c.set_hdr_enabled(hdr);
+        }
+        if let Some(rm) = self.render_margin_ms {
+            c.set_render_margin(Duration::from_nanos((rm * 1_000_000.0) as _));
+        }
+        if let Some(mirror) = &self.mirror {
+            c.set_mirror(Some(get_connector(mirror.as_str())));
+        }
+        if let Some(bar) = &self.bar {
+            if let Some(format) = &bar.clock_format {
+                c.set_bar_clock_format(Some(format.as_str()).filter(|f| !f.is_empty()));
+            }
+            if let Some(show) = bar.show_focused_title {
+                c.set_bar_show_focused_title(show);
+            }
+        }
     }
 }
 
@@ -614,11 +800,63 @@ impl Drop for State {
 type SwitchActions = Vec<(InputMatch, AHashMap<SwitchEvent, Box<dyn Fn()>>)>;
 
 impl State {
+    /// Runs an event action. `exec` actions receive the given environment variables.
+    fn run_with_env(&self, env: Vec<(&'static str, String)>, action: &dyn Fn()) {
+        *self.persistent.event_env.borrow_mut() = env;
+        action();
+        self.persistent.event_env.borrow_mut().clear();
+    }
+
     fn unbind_all(&self) {
+        self.persistent.seat.set_binding_mode(DEFAULT_BINDING_MODE);
         let mut binds = self.persistent.binds.borrow_mut();
         for bind in binds.drain() {
             self.persistent.seat.unbind(bind);
         }
+        let mut mode_binds = self.persistent.mode_binds.borrow_mut();
+        for (mode, bind) in mode_binds.drain() {
+            self.persistent.seat.unbind_in_mode(&mode, bind);
+        }
+        let mut gesture_binds = self.persistent.gesture_binds.borrow_mut();
+        for gesture in gesture_binds.drain() {
+            self.persistent.seat.unbind_gesture(gesture);
+        }
+        let mut passthrough = self.persistent.gesture_passthrough.borrow_mut();
+        for app_id in passthrough.drain(..) {
+            self.persistent.seat.set_gesture_passthrough(&app_id, false);
+        }
+        let mut pointer_binds = self.persistent.pointer_binds.borrow_mut();
+        for (target, input) in pointer_binds.drain() {
+            self.persistent.seat.unbind_pointer(target, input);
+        }
+        let mut sequence_binds = self.persistent.sequence_binds.borrow_mut();
+        for keys in sequence_binds.drain() {
+            self.persistent.seat.unbind_sequence(keys);
+        }
+    }
+
+    fn apply_gestures(self: &Rc<Self>, gestures: Vec<ConfigGesture>, passthrough: Vec<String>) {
+        let mut gesture_binds = self.persistent.gesture_binds.borrow_mut();
+        for gesture in gestures {
+            let f = gesture.action.into_fn(self);
+            self.persistent.seat.bind_gesture(gesture.gesture, f);
+            gesture_binds.insert(gesture.gesture);
+        }
+        for app_id in &passthrough {
+            self.persistent.seat.set_gesture_passthrough(app_id, true);
+        }
+        *self.persistent.gesture_passthrough.borrow_mut() = passthrough;
+    }
+
+    fn apply_pointer_bindings(self: &Rc<Self>, bindings: Vec<ConfigPointerBinding>) {
+        let mut pointer_binds = self.persistent.pointer_binds.borrow_mut();
+        for binding in bindings {
+            let f = binding.action.into_fn(self);
+            self.persistent
+                .seat
+                .bind_pointer(binding.target, binding.input, f);
+            pointer_binds.insert((binding.target, binding.input));
+        }
     }
 
     fn apply_shortcuts(self: &Rc<Self>, shortcuts: impl IntoIterator<Item = Shortcut>) {
@@ -651,6 +889,46 @@ impl State {
         }
     }
 
+    fn apply_binding_modes(self: &Rc<Self>, modes: Vec<BindingMode>) {
+        let mut mode_binds = self.persistent.mode_binds.borrow_mut();
+        for mode in modes {
+            for shortcut in mode.shortcuts {
+                let mut f = shortcut.action.into_fn(self);
+                if let Some(l) = shortcut.latch {
+                    let l = l.into_rc_fn(self);
+                    let s = self.persistent.seat;
+                    f = Box::new(move || {
+                        f();
+                        let l = l.clone();
+                        s.latch(move || l());
+                    });
+                }
+                self.persistent.seat.bind_masked_in_mode(
+                    &mode.name,
+                    shortcut.mask,
+                    shortcut.keysym,
+                    f,
+                );
+                mode_binds.insert((mode.name.clone(), shortcut.keysym));
+            }
+        }
+    }
+
+    fn apply_shortcut_sequences(self: &Rc<Self>, sequences: Vec<ShortcutSequence>) {
+        let mut sequence_binds = self.persistent.sequence_binds.borrow_mut();
+        for sequence in sequences {
+            if let Action::SimpleCommand {
+                cmd: SimpleCommand::None,
+            } = sequence.action
+            {
+                continue;
+            }
+            let f = sequence.action.into_fn(self);
+            self.persistent.seat.bind_sequence(sequence.keys.clone(), f);
+            sequence_binds.insert(sequence.keys);
+        }
+    }
+
     fn get_keymap(&self, map: &ConfigKeymap) -> Option<Keymap> {
         let map = match map {
             ConfigKeymap::Named(n) => match self.keymaps.get(n) {
@@ -722,6 +1000,9 @@ impl State {
         color!(UNFOCUSED_TITLE_BACKGROUND_COLOR, unfocused_title_bg_color);
         color!(UNFOCUSED_TITLE_TEXT_COLOR, unfocused_title_text_color);
         color!(HIGHLIGHT_COLOR, highlight_color);
+        color!(NOTIFICATION_BACKGROUND_COLOR, notification_bg_color);
+        color!(NOTIFICATION_BORDER_COLOR, notification_border_color);
+        color!(NOTIFICATION_TEXT_COLOR, notification_text_color);
         macro_rules! size {
             ($sized:ident, $field:ident) => {
                 if let Some(size) = theme.$field {
@@ -731,6 +1012,8 @@ impl State {
         }
         size!(BORDER_WIDTH, border_width);
         size!(TITLE_HEIGHT, title_height);
+        size!(INNER_GAP, inner_gap);
+        size!(OUTER_GAP, outer_gap);
         if let Some(font) = &theme.font {
             set_font(font);
         }
@@ -820,6 +1103,14 @@ struct PersistentState {
     default: Config,
     seat: Seat,
     binds: RefCell<AHashSet<ModifiedKeySym>>,
+    mode_binds: RefCell<AHashSet<(String, ModifiedKeySym)>>,
+    gesture_binds: RefCell<AHashSet<PointerGesture>>,
+    gesture_passthrough: RefCell<Vec<String>>,
+    pointer_binds: RefCell<AHashSet<(PointerTarget, PointerInput)>>,
+    sequence_binds: RefCell<AHashSet<Vec<ModifiedKeySym>>>,
+    event_env: RefCell<Vec<(&'static str, String)>>,
+    /// The enabled state set by actions at runtime, keyed by device name.
+    input_devices_enabled: RefCell<AHashMap<String, bool>>,
 }
 
 fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
@@ -911,6 +1202,12 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         }
     }
     let switch_actions = Rc::new(switch_actions);
+    let mut attach_actions = vec![];
+    for input in &mut config.inputs {
+        if let Some(action) = input.on_attach.take() {
+            attach_actions.push((input.match_.clone(), action.into_fn(&state)));
+        }
+    }
     match config.on_graphics_initialized {
         None => on_graphics_initialized(|| ()),
         Some(a) => on_graphics_initialized(a.into_fn(&state)),
@@ -919,8 +1216,55 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         None => on_idle(|| ()),
         Some(a) => on_idle(a.into_fn(&state)),
     }
+    match config.on_lock {
+        None => on_lock(|| ()),
+        Some(a) => on_lock(a.into_fn(&state)),
+    }
+    match config.on_unlock {
+        None => on_unlock(|| ()),
+        Some(a) => on_unlock(a.into_fn(&state)),
+    }
+    match config.on_locker_crash {
+        None => on_locker_crashed(|| ()),
+        Some(a) => on_locker_crashed(a.into_fn(&state)),
+    }
+    set_window_screenshot_directory(config.window_screenshots.directory.as_deref());
+    set_window_screenshot_filename(config.window_screenshots.filename.as_deref());
+    match config.window_screenshots.on_saved {
+        None => on_window_screenshot_saved(|_| ()),
+        Some(a) => {
+            let a = a.into_fn(&state);
+            on_window_screenshot_saved(move |_| a())
+        }
+    }
+    set_client_limits(
+        config.client_limits.max_fds,
+        config
+            .client_limits
+            .max_memory_mb
+            .map(|mb| mb.saturating_mul(1024 * 1024)),
+    );
+    set_lock_grace_period(config.lock_grace_period.unwrap_or_default());
+    set_activation_token_lifetime(
+        config
+            .activation_token_lifetime
+            .unwrap_or(Duration::from_secs(30)),
+    );
+    set_fallback_lock_screen_prompt(config.fallback_lock_screen_prompt.unwrap_or(true));
     state.unbind_all();
     state.apply_shortcuts(config.shortcuts);
+    state.apply_binding_modes(config.binding_modes);
+    state.apply_shortcut_sequences(config.shortcut_sequences);
+    persistent.seat.set_shortcut_sequence_timeout(
+        config
+            .shortcut_sequence_timeout
+            .unwrap_or(Duration::from_secs(2)),
+    );
+    state.apply_gestures(config.gestures, config.gesture_passthrough);
+    persistent
+        .seat
+        .set_workspace_swipe_fingers(config.workspace_swipe_fingers.unwrap_or(0));
+    state.apply_pointer_bindings(config.pointer_bindings);
     if let Some(keymap) = config.keymap {
         state.set_keymap(&keymap);
     }
@@ -938,6 +1282,7 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
     });
     on_connector_connected({
         let state = state.clone();
+        let action = config.on_connector_connected.map(|a| a.into_fn(&state));
         move |c| {
             state.add_io_output(c);
             state.map_output_to_input(c);
@@ -953,12 +1298,28 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
                     }
                 }
             }
+            if let Some(action) = &action {
+                state.run_with_env(connector_env(c), action);
+            }
         }
     });
     on_connector_disconnected({
         let state = state.clone();
+        let action = config.on_connector_disconnected.map(|a| a.into_fn(&state));
         move |c| {
             state.io_outputs.borrow_mut().remove(&c);
+            if let Some(action) = &action {
+                state.run_with_env(connector_env(c), action);
+            }
+        }
+    });
+    on_connector_mode_changed({
+        let state = state.clone();
+        let action = config.on_connector_mode_changed.map(|a| a.into_fn(&state));
+        move |c, _| {
+            if let Some(action) = &action {
+                state.run_with_env(connector_env(c), action);
+            }
         }
     });
     set_default_workspace_capture(config.workspace_capture);
@@ -978,6 +1339,9 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         if let Some(period) = config.grace_period {
             set_idle_grace_period(period);
         }
+        if let Some(audio) = config.audio_idle_inhibit {
+            set_idle_inhibit_on_audio(audio.enabled, audio.roles);
+        }
     }
     on_devices_enumerated({
         let state = state.clone();
@@ -1005,14 +1369,29 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
     if let Some(ese) = config.explicit_sync_enabled {
         set_explicit_sync_enabled(ese);
     }
+    set_force_implicit_sync(config.force_implicit_sync);
+    set_key_remap(&config.key_remap);
     on_new_drm_device({
         let state = state.clone();
+        let action = config.on_drm_device_added.map(|a| a.into_fn(&state));
         move |d| {
             for dev in &config.drm_devices {
                 if dev.match_.matches(d, &state) {
                     dev.apply(d);
                 }
             }
+            if let Some(action) = &action {
+                state.run_with_env(drm_device_env(d), action);
+            }
+        }
+    });
+    on_drm_device_removed({
+        let state = state.clone();
+        let action = config.on_drm_device_removed.map(|a| a.into_fn(&state));
+        move |d| {
+            if let Some(action) = &action {
+                state.run_with_env(drm_device_env(d), action);
+            }
         }
     });
     on_new_input_device({
@@ -1025,7 +1404,21 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
                     input.apply(c, &state);
                 }
             }
+            let enabled = state
+                .persistent
+                .input_devices_enabled
+                .borrow()
+                .get(&c.name())
+                .copied();
+            if let Some(enabled) = enabled {
+                c.set_enabled(enabled);
+            }
             state.handle_switch_device(c, &switch_actions);
+            for (match_, action) in &attach_actions {
+                if match_.matches(c, &state) {
+                    action();
+                }
+            }
         }
     });
     on_input_device_removed({
@@ -1048,11 +1441,40 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
             true => FocusFollowsMouseMode::True,
             false => FocusFollowsMouseMode::False,
         });
+    persistent
+        .seat
+        .set_pointer_motion_coalescing(config.pointer_motion_coalescing.unwrap_or(false));
     if let Some(window_management_key) = config.window_management_key {
         persistent
             .seat
             .set_window_management_key(window_management_key);
     }
+    persistent
+        .seat
+        .set_break_out_key(config.break_out_key.unwrap_or(LOGO | SYM_Escape));
+    set_throttle_interactive_resize(config.throttle_interactive_resize.unwrap_or(false));
+    set_empty_workspace_behavior(
+        config
+            .empty_workspace_behavior
+            .unwrap_or(EmptyWorkspaceBehavior::Keep),
+    );
+    set_autotiling(config.autotiling.unwrap_or(false));
+    set_workspace_back_and_forth(config.workspace_back_and_forth.unwrap_or(false));
+    set_show_marks(config.show_marks.unwrap_or(false));
+    {
+        let night_light = config.night_light.as_ref();
+        set_night_light_temperature(night_light.and_then(|n| n.temperature).unwrap_or(4000));
+        set_night_light_transition(
+            night_light
+                .and_then(|n| n.transition)
+                .unwrap_or(Duration::from_secs(30 * 60)),
+        );
+        set_night_light_schedule(
+            night_light
+                .map(|n| n.schedule)
+                .unwrap_or(NightLightSchedule::Disabled),
+        );
+    }
     if let Some(vrr) = config.vrr {
         if let Some(mode) = vrr.mode {
             set_vrr_mode(mode);
@@ -1067,6 +1489,27 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         }
     }
     set_libei_socket_enabled(config.libei.enable_socket.unwrap_or(false));
+    set_libei_authorization_prompt_enabled(config.libei.prompt.unwrap_or(true));
+    set_jay_protocol_allowlist(config.jay_protocol_allowlist);
+    set_capture_redactions(config.capture_redactions.unwrap_or_default());
+    set_capture_overlay(
+        config.capture_overlay.text.as_deref(),
+        config.capture_overlay.image.as_deref(),
+    );
+    set_window_state_exclusions(config.window_state.exclude.unwrap_or_default());
+    set_remember_window_state(config.window_state.remember.unwrap_or(false));
+    set_bar_clock_format(config.bar.clock_format.as_deref().filter(|f| !f.is_empty()));
+    set_bar_show_focused_title(config.bar.show_focused_title.unwrap_or(false));
+    if let Some(position) = config.notifications.position {
+        notifications::set_position(position);
+    }
+    if let Some(timeout) = config.notifications.timeout {
+        notifications::set_default_timeout(timeout);
+    }
+    if let Some(dnd) = config.notifications.do_not_disturb {
+        notifications::set_do_not_disturb(dnd);
+    }
+    notifications::set_enabled(config.notifications.enabled.unwrap_or(false));
     if let Some(enabled) = config.ui_drag.enabled {
         set_ui_drag_enabled(enabled);
     }
@@ -1078,6 +1521,45 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
             set_x_scaling_mode(mode);
         }
     }
+    let mut touch_gestures = AHashMap::new();
+    for gesture in config.touch_gestures {
+        touch_gestures.insert(gesture.gesture, gesture.action.into_fn(&state));
+    }
+    persistent
+        .seat
+        .set_touch_gestures_enabled(!touch_gestures.is_empty());
+    persistent.seat.on_touch_gesture(move |gesture| {
+        if let Some(action) = touch_gestures.get(&gesture) {
+            action();
+        }
+    });
+}
+
+/// Returns the environment variables passed to `exec` actions run for connector events.
+fn connector_env(c: Connector) -> Vec<(&'static str, String)> {
+    let mode = c.mode();
+    vec![
+        ("JAY_CONNECTOR", c.name()),
+        ("JAY_OUTPUT_MANUFACTURER", c.manufacturer()),
+        ("JAY_OUTPUT_MODEL", c.model()),
+        ("JAY_OUTPUT_SERIAL_NUMBER", c.serial_number()),
+        ("JAY_OUTPUT_WIDTH", mode.width().to_string()),
+        ("JAY_OUTPUT_HEIGHT", mode.height().to_string()),
+        (
+            "JAY_OUTPUT_REFRESH_RATE_MHZ",
+            mode.refresh_rate().to_string(),
+        ),
+    ]
+}
+
+/// Returns the environment variables passed to `exec` actions run for DRM device events.
+fn drm_device_env(d: DrmDevice) -> Vec<(&'static str, String)> {
+    vec![
+        ("JAY_DRM_DEVICE", d.devnode()),
+        ("JAY_DRM_DEVICE_SYSPATH", d.syspath()),
+        ("JAY_DRM_DEVICE_VENDOR", d.vendor()),
+        ("JAY_DRM_DEVICE_MODEL", d.model()),
+    ]
 }
 
 fn create_command(exec: &Exec) -> Command {
@@ -1105,6 +1587,13 @@ pub fn configure() {
         default: default.unwrap(),
         seat: default_seat(),
         binds: Default::default(),
+        mode_binds: Default::default(),
+        gesture_binds: Default::default(),
+        gesture_passthrough: Default::default(),
+        pointer_binds: Default::default(),
+        sequence_binds: Default::default(),
+        event_env: Default::default(),
+        input_devices_enabled: Default::default(),
     });
     load_config(true, &persistent);
 }